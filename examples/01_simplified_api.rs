@@ -103,6 +103,12 @@ fn main() -> Result<()> {
         .filter_gpus(|gpu| gpu.memory_gb() >= 4.0) // Only GPUs with 4GB+ VRAM
         .query()?;
     println!("   Gaming query (4GB+ VRAM): {} GPUs found", gaming_focused.gpus.len());
+
+    // Telemetry payload limited to the fields it needs
+    let telemetry = HardwareQueryBuilder::new()
+        .select(&["cpu.model_name", "gpus.*.memory_mb"])
+        .query()?;
+    println!("   Telemetry payload: {}", telemetry.to_json()?);
     println!();
 
     // 6. Quick Compatibility Checks
@@ -0,0 +1,248 @@
+//! CPU sets for thread pinning
+//!
+//! [`AffinityCriteria`] picks logical CPUs out of a [`CPUTopology`] by NUMA
+//! node, socket and core type, optionally keeping one CPU per physical core
+//! so pinned threads don't share a core with their SMT sibling. The resulting
+//! [`CpuSet`] converts to the formats the pinning APIs take: a Linux CPU list
+//! or `cpu_set_t`, a hexadecimal mask, or Windows processor group affinities.
+//!
+//! ```rust
+//! use hardware_query::{AffinityCriteria, CoreType, HardwareInfo};
+//!
+//! # fn main() -> hardware_query::Result<()> {
+//! let hw_info = HardwareInfo::query()?;
+//! // Physical cores on NUMA node 0, excluding E-cores
+//! let criteria = AffinityCriteria::new()
+//!     .numa_node(0)
+//!     .core_type(CoreType::Performance)
+//!     .one_per_core();
+//! let cpus = hw_info.cpu().topology().cpu_set(&criteria);
+//! println!("taskset -c {}", cpus.to_cpu_list());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{CPUTopology, CoreType};
+use serde::{Deserialize, Serialize};
+
+/// Logical CPUs in a Windows processor group
+const GROUP_SIZE: u32 = 64;
+
+/// Which logical CPUs to pin to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AffinityCriteria {
+    /// Only CPUs on this NUMA node
+    pub numa_node: Option<u32>,
+    /// Only CPUs in this socket
+    pub socket: Option<u32>,
+    /// Only cores of this type
+    pub core_type: Option<CoreType>,
+    /// Keep the lowest-numbered selected CPU of each physical core
+    pub one_per_core: bool,
+}
+
+impl AffinityCriteria {
+    /// Every logical CPU
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only CPUs on NUMA node `node`
+    pub fn numa_node(mut self, node: u32) -> Self {
+        self.numa_node = Some(node);
+        self
+    }
+
+    /// Only CPUs in socket `socket`
+    pub fn socket(mut self, socket: u32) -> Self {
+        self.socket = Some(socket);
+        self
+    }
+
+    /// Only cores of `core_type`; every core of a homogeneous CPU is a performance core
+    pub fn core_type(mut self, core_type: CoreType) -> Self {
+        self.core_type = Some(core_type);
+        self
+    }
+
+    /// One logical CPU per physical core, leaving SMT siblings idle
+    pub fn one_per_core(mut self) -> Self {
+        self.one_per_core = true;
+        self
+    }
+
+    /// Logical CPUs of `topology` that match
+    pub fn select(&self, topology: &CPUTopology) -> CpuSet {
+        let core_type_of = |cpu: u32| {
+            topology
+                .clusters()
+                .iter()
+                .find(|cluster| cluster.logical_cpus.contains(&cpu))
+                .map_or(CoreType::Performance, |cluster| cluster.core_type)
+        };
+        let selected: Vec<u32> = topology
+            .processors()
+            .iter()
+            .filter(|processor| self.numa_node.is_none_or(|node| processor.numa_node == Some(node)))
+            .filter(|processor| self.socket.is_none_or(|socket| processor.socket_id == socket))
+            .filter(|processor| self.core_type.is_none_or(|core_type| core_type_of(processor.id) == core_type))
+            .map(|processor| processor.id)
+            .collect();
+
+        if !self.one_per_core {
+            return CpuSet::from_cpus(selected);
+        }
+        let first_of_core = topology.processors().iter().filter_map(|processor| {
+            processor
+                .smt_siblings
+                .iter()
+                .chain([&processor.id])
+                .filter(|sibling| selected.contains(sibling))
+                .min()
+                .copied()
+        });
+        CpuSet::from_cpus(first_of_core)
+    }
+}
+
+/// Affinity within one Windows processor group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupAffinity {
+    /// Processor group
+    pub group: u16,
+    /// Bit n selects logical processor n of the group
+    pub mask: u64,
+}
+
+/// A set of logical CPU ids, in the operating system's numbering
+///
+/// On Windows, CPU `n` is processor `n % 64` of group `n / 64`, matching
+/// [`CPUTopology`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CpuSet {
+    cpus: Vec<u32>,
+}
+
+impl CpuSet {
+    /// Set of the given CPU ids
+    pub fn from_cpus(cpus: impl IntoIterator<Item = u32>) -> Self {
+        let mut cpus: Vec<u32> = cpus.into_iter().collect();
+        cpus.sort_unstable();
+        cpus.dedup();
+        Self { cpus }
+    }
+
+    /// Parse a Linux CPU list such as `0-3,8,10-11`
+    pub fn from_cpu_list(list: &str) -> Self {
+        Self::from_cpus(crate::topology::parse_cpu_list(list))
+    }
+
+    /// CPU ids in ascending order
+    pub fn cpus(&self) -> &[u32] {
+        &self.cpus
+    }
+
+    /// Number of CPUs
+    pub fn len(&self) -> usize {
+        self.cpus.len()
+    }
+
+    /// Check if no CPU is selected
+    pub fn is_empty(&self) -> bool {
+        self.cpus.is_empty()
+    }
+
+    /// Check if `cpu` is in the set
+    pub fn contains(&self, cpu: u32) -> bool {
+        self.cpus.binary_search(&cpu).is_ok()
+    }
+
+    /// Linux CPU list with ranges, as taken by `taskset -c` and cpusets (`0-3,8`)
+    pub fn to_cpu_list(&self) -> String {
+        let mut ranges: Vec<(u32, u32)> = Vec::new();
+        for &cpu in &self.cpus {
+            match ranges.last_mut() {
+                Some((_, end)) if *end + 1 == cpu => *end = cpu,
+                _ => ranges.push((cpu, cpu)),
+            }
+        }
+        ranges
+            .iter()
+            .map(|&(start, end)| if start == end { start.to_string() } else { format!("{start}-{end}") })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Bitmask words, bit `n % 64` of word `n / 64` selecting CPU `n`
+    pub fn mask_words(&self) -> Vec<u64> {
+        let mut words = vec![0u64; self.cpus.last().map_or(0, |&last| (last / 64 + 1) as usize)];
+        for &cpu in &self.cpus {
+            words[(cpu / 64) as usize] |= 1 << (cpu % 64);
+        }
+        words
+    }
+
+    /// Hexadecimal mask as taken by `taskset` and `start /affinity` (`0xf0`)
+    pub fn to_hex_mask(&self) -> String {
+        let words = self.mask_words();
+        let mut hex = String::from("0x");
+        match words.iter().rposition(|&word| word != 0) {
+            Some(top) => {
+                hex.push_str(&format!("{:x}", words[top]));
+                for word in words[..top].iter().rev() {
+                    hex.push_str(&format!("{word:016x}"));
+                }
+            }
+            None => hex.push('0'),
+        }
+        hex
+    }
+
+    /// Affinity per Windows processor group, groups without selected CPUs left out
+    pub fn group_affinities(&self) -> Vec<GroupAffinity> {
+        let mut groups: Vec<GroupAffinity> = Vec::new();
+        for &cpu in &self.cpus {
+            let group = (cpu / GROUP_SIZE) as u16;
+            let bit = 1u64 << (cpu % GROUP_SIZE);
+            match groups.last_mut() {
+                Some(affinity) if affinity.group == group => affinity.mask |= bit,
+                _ => groups.push(GroupAffinity { group, mask: bit }),
+            }
+        }
+        groups
+    }
+
+    /// Set for `sched_setaffinity` / `pthread_setaffinity_np`
+    ///
+    /// CPUs beyond `CPU_SETSIZE` (1024) are left out.
+    #[cfg(target_os = "linux")]
+    pub fn to_cpu_set_t(&self) -> libc::cpu_set_t {
+        // SAFETY: cpu_set_t is a plain bit array, valid when zeroed
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        for &cpu in self.cpus.iter().filter(|&&cpu| cpu < libc::CPU_SETSIZE as u32) {
+            // SAFETY: cpu is below CPU_SETSIZE, so the bit is inside the set
+            unsafe { libc::CPU_SET(cpu as usize, &mut set) };
+        }
+        set
+    }
+
+    /// `GROUP_AFFINITY` structures for `SetThreadGroupAffinity`, one per group
+    #[cfg(target_os = "windows")]
+    pub fn to_windows_group_affinity(&self) -> Vec<windows::Win32::System::SystemInformation::GROUP_AFFINITY> {
+        self.group_affinities()
+            .into_iter()
+            .map(|affinity| windows::Win32::System::SystemInformation::GROUP_AFFINITY {
+                Mask: affinity.mask as usize,
+                Group: affinity.group,
+                Reserved: [0; 3],
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for CpuSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_cpu_list())
+    }
+}
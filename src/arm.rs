@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 #[cfg(target_arch = "aarch64")]
-use std::process::Command;
+use crate::probe::Command;
 
 /// ARM-based system type
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1,5 +1,6 @@
 use crate::{HardwareQueryError, Result};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Battery status
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -13,13 +14,14 @@ pub enum BatteryStatus {
 
 impl std::fmt::Display for BatteryStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            BatteryStatus::Charging => write!(f, "Charging"),
-            BatteryStatus::Discharging => write!(f, "Discharging"),
-            BatteryStatus::Full => write!(f, "Full"),
-            BatteryStatus::NotCharging => write!(f, "Not Charging"),
-            BatteryStatus::Unknown => write!(f, "Unknown"),
-        }
+        let text = match self {
+            BatteryStatus::Charging => "Charging",
+            BatteryStatus::Discharging => "Discharging",
+            BatteryStatus::Full => "Full",
+            BatteryStatus::NotCharging => "Not Charging",
+            BatteryStatus::Unknown => "Unknown",
+        };
+        f.write_str(&crate::l10n::localize("BatteryStatus", text))
     }
 }
 
@@ -36,8 +38,17 @@ pub struct BatteryInfo {
     pub health_percent: Option<f32>,
     /// Design capacity in Wh
     pub design_capacity_wh: Option<f32>,
-    /// Current capacity in Wh
+    /// Full-charge capacity in Wh (what the worn battery holds when full)
     pub current_capacity_wh: Option<f32>,
+    /// Energy currently stored in Wh
+    #[serde(default)]
+    pub remaining_capacity_wh: Option<f32>,
+    /// Charge (positive) or discharge (negative) rate in watts
+    #[serde(default)]
+    pub charge_rate_watts: Option<f32>,
+    /// Estimated minutes until fully charged (while charging)
+    #[serde(default)]
+    pub time_to_full_minutes: Option<u32>,
     /// Cycle count
     pub cycle_count: Option<u32>,
     /// Battery temperature in Celsius
@@ -57,11 +68,295 @@ pub struct BatteryInfo {
 impl BatteryInfo {
     /// Query battery information
     pub fn query() -> Result<Self> {
-        // This would be platform-specific implementation
-        // For now, return an error if no battery detected
+        #[cfg(target_os = "linux")]
+        {
+            Self::query_linux()
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Self::query_windows()
+        }
+        #[cfg(target_os = "macos")]
+        {
+            Self::query_macos()
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            Err(HardwareQueryError::device_not_found("No battery detected"))
+        }
+    }
+
+    fn empty(percentage: f32, status: BatteryStatus) -> Self {
+        Self {
+            percentage,
+            status,
+            time_remaining_minutes: None,
+            health_percent: None,
+            design_capacity_wh: None,
+            current_capacity_wh: None,
+            remaining_capacity_wh: None,
+            charge_rate_watts: None,
+            time_to_full_minutes: None,
+            cycle_count: None,
+            temperature: None,
+            voltage: None,
+            current: None,
+            manufacturer: None,
+            model: None,
+            serial_number: None,
+        }
+    }
+
+    /// Fill in health and time estimates that can be derived from capacities and rates
+    fn derive_estimates(mut self) -> Self {
+        if self.health_percent.is_none() {
+            if let (Some(design), Some(full)) = (self.design_capacity_wh, self.current_capacity_wh) {
+                if design > 0.0 {
+                    self.health_percent = Some((full / design * 100.0).min(100.0));
+                }
+            }
+        }
+
+        if let (Some(rate), Some(remaining)) = (self.charge_rate_watts, self.remaining_capacity_wh) {
+            if rate < -0.1 && self.time_remaining_minutes.is_none() {
+                self.time_remaining_minutes = Some((remaining / -rate * 60.0) as u32);
+            } else if rate > 0.1 && self.time_to_full_minutes.is_none() {
+                if let Some(full) = self.current_capacity_wh {
+                    self.time_to_full_minutes = Some(((full - remaining).max(0.0) / rate * 60.0) as u32);
+                }
+            }
+        }
+
+        self
+    }
+
+    #[cfg(target_os = "linux")]
+    fn query_linux() -> Result<Self> {
+        use std::fs;
+
+        let entries = fs::read_dir("/sys/class/power_supply")
+            .map_err(|_| HardwareQueryError::device_not_found("No battery detected"))?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let read = |name: &str| {
+                fs::read_to_string(path.join(name))
+                    .ok()
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+            let number = |name: &str| read(name).and_then(|value| value.parse::<f64>().ok());
+
+            if read("type").as_deref() != Some("Battery") || read("present").as_deref() == Some("0") {
+                continue;
+            }
+            // Skip peripheral batteries (mice, keyboards) that also show up here
+            if read("scope").as_deref() == Some("Device") {
+                continue;
+            }
+
+            let status = match read("status").as_deref() {
+                Some("Charging") => BatteryStatus::Charging,
+                Some("Discharging") => BatteryStatus::Discharging,
+                Some("Full") => BatteryStatus::Full,
+                Some("Not charging") => BatteryStatus::NotCharging,
+                _ => BatteryStatus::Unknown,
+            };
+            let mut battery = Self::empty(number("capacity").unwrap_or(0.0) as f32, status);
+
+            // sysfs units are micro-units: µV, µA, µW, µWh, µAh
+            let voltage = number("voltage_now").map(|uv| uv / 1e6);
+            let design_voltage = number("voltage_min_design").map(|uv| uv / 1e6).or(voltage);
+            battery.voltage = voltage.map(|v| v as f32);
+            battery.current = number("current_now").map(|ua| (ua / 1e6) as f32);
+
+            let energy = |energy_name: &str, charge_name: &str| {
+                number(energy_name).map(|uwh| uwh / 1e6).or_else(|| {
+                    Some(number(charge_name)? / 1e6 * design_voltage?)
+                })
+            };
+            battery.design_capacity_wh = energy("energy_full_design", "charge_full_design").map(|v| v as f32);
+            battery.current_capacity_wh = energy("energy_full", "charge_full").map(|v| v as f32);
+            battery.remaining_capacity_wh = energy("energy_now", "charge_now").map(|v| v as f32);
+
+            let power = number("power_now")
+                .map(|uw| uw / 1e6)
+                .or_else(|| Some(number("current_now")? / 1e6 * voltage?));
+            battery.charge_rate_watts = power.map(|watts| match battery.status {
+                BatteryStatus::Discharging => -(watts.abs() as f32),
+                _ => watts.abs() as f32,
+            });
+
+            battery.cycle_count = number("cycle_count").map(|c| c as u32).filter(|c| *c > 0);
+            battery.temperature = number("temp").map(|t| (t / 10.0) as f32);
+            battery.time_remaining_minutes = number("time_to_empty_now").map(|s| (s / 60.0) as u32);
+            battery.time_to_full_minutes = number("time_to_full_now").map(|s| (s / 60.0) as u32);
+            battery.manufacturer = read("manufacturer");
+            battery.model = read("model_name");
+            battery.serial_number = read("serial_number");
+
+            return Ok(battery.derive_estimates());
+        }
+
         Err(HardwareQueryError::device_not_found("No battery detected"))
     }
 
+    #[cfg(target_os = "windows")]
+    fn query_windows() -> Result<Self> {
+        use std::collections::HashMap;
+        use wmi::{Variant, WMIConnection};
+
+        let com = crate::probe::com_library()?;
+        let cimv2 = WMIConnection::new(com)?;
+        let batteries: Vec<HashMap<String, Variant>> = cimv2
+            .raw_query("SELECT EstimatedChargeRemaining, EstimatedRunTime, BatteryStatus FROM Win32_Battery")?;
+        let Some(summary) = batteries.first() else {
+            return Err(HardwareQueryError::device_not_found("No battery detected"));
+        };
+
+        let as_f64 = |value: Option<&Variant>| match value {
+            Some(Variant::UI1(v)) => Some(*v as f64),
+            Some(Variant::UI2(v)) => Some(*v as f64),
+            Some(Variant::UI4(v)) => Some(*v as f64),
+            Some(Variant::UI8(v)) => Some(*v as f64),
+            Some(Variant::I4(v)) => Some(*v as f64),
+            _ => None,
+        };
+        let as_string = |value: Option<&Variant>| match value {
+            Some(Variant::String(s)) if !s.trim().is_empty() => Some(s.trim().to_string()),
+            _ => None,
+        };
+
+        // Win32_Battery.BatteryStatus: 1 = discharging, 2 = on AC, 3 = fully charged,
+        // 6-9 = charging variants
+        let status = match as_f64(summary.get("BatteryStatus")).map(|v| v as u32) {
+            Some(1) => BatteryStatus::Discharging,
+            Some(2) => BatteryStatus::NotCharging,
+            Some(3) => BatteryStatus::Full,
+            Some(6..=9) => BatteryStatus::Charging,
+            _ => BatteryStatus::Unknown,
+        };
+        let mut battery = Self::empty(
+            as_f64(summary.get("EstimatedChargeRemaining")).unwrap_or(0.0) as f32,
+            status,
+        );
+        // 71582788 is reported while on AC power
+        battery.time_remaining_minutes = as_f64(summary.get("EstimatedRunTime"))
+            .filter(|minutes| *minutes < 71_582_788.0)
+            .map(|minutes| minutes as u32);
+
+        // The battery miniport classes in root\WMI carry capacities (mWh) and rates (mW)
+        if let Ok(wmi_con) = WMIConnection::with_namespace_path(r"root\WMI", com) {
+            let first = |query: &str| -> Option<HashMap<String, Variant>> {
+                wmi_con
+                    .raw_query::<HashMap<String, Variant>>(query)
+                    .ok()?
+                    .into_iter()
+                    .next()
+            };
+
+            if let Some(data) = first("SELECT * FROM BatteryStaticData") {
+                battery.design_capacity_wh = as_f64(data.get("DesignedCapacity")).map(|mwh| (mwh / 1000.0) as f32);
+                battery.manufacturer = as_string(data.get("ManufactureName"));
+                battery.model = as_string(data.get("DeviceName"));
+                battery.serial_number = as_string(data.get("SerialNumber"));
+            }
+            if let Some(data) = first("SELECT FullChargedCapacity FROM BatteryFullChargedCapacity") {
+                battery.current_capacity_wh = as_f64(data.get("FullChargedCapacity")).map(|mwh| (mwh / 1000.0) as f32);
+            }
+            if let Some(data) = first("SELECT CycleCount FROM BatteryCycleCount") {
+                battery.cycle_count = as_f64(data.get("CycleCount")).map(|c| c as u32).filter(|c| *c > 0);
+            }
+            if let Some(data) = first("SELECT * FROM BatteryStatus") {
+                battery.remaining_capacity_wh = as_f64(data.get("RemainingCapacity")).map(|mwh| (mwh / 1000.0) as f32);
+                battery.voltage = as_f64(data.get("Voltage")).map(|mv| (mv / 1000.0) as f32);
+                let charge = as_f64(data.get("ChargeRate")).unwrap_or(0.0);
+                let discharge = as_f64(data.get("DischargeRate")).unwrap_or(0.0);
+                battery.charge_rate_watts = if discharge > 0.0 {
+                    Some((-discharge / 1000.0) as f32)
+                } else if charge > 0.0 {
+                    Some((charge / 1000.0) as f32)
+                } else {
+                    None
+                };
+            }
+        }
+
+        Ok(battery.derive_estimates())
+    }
+
+    #[cfg(target_os = "macos")]
+    fn query_macos() -> Result<Self> {
+        use std::collections::HashMap;
+        use crate::probe::Command;
+
+        // AppleSmartBattery is the IOPMPowerSource driver for the internal battery
+        let output = Command::new("ioreg").args(["-rn", "AppleSmartBattery"]).output()?;
+        let text = String::from_utf8_lossy(&output.stdout);
+
+        // Lines look like:  "CycleCount" = 123
+        let properties: HashMap<&str, &str> = text
+            .lines()
+            .filter_map(|line| {
+                let (key, value) = line.trim().split_once(" = ")?;
+                Some((key.trim_matches('"'), value.trim()))
+            })
+            .collect();
+        if properties.is_empty() {
+            return Err(HardwareQueryError::device_not_found("No battery detected"));
+        }
+
+        let number = |key: &str| properties.get(key).and_then(|v| v.parse::<i64>().ok());
+        let flag = |key: &str| properties.get(key).map(|v| *v == "Yes");
+        let text_value = |key: &str| properties.get(key).map(|v| v.trim_matches('"').to_string());
+
+        let status = match (flag("IsCharging"), flag("FullyCharged"), flag("ExternalConnected")) {
+            (_, Some(true), _) => BatteryStatus::Full,
+            (Some(true), _, _) => BatteryStatus::Charging,
+            (_, _, Some(true)) => BatteryStatus::NotCharging,
+            (_, _, Some(false)) => BatteryStatus::Discharging,
+            _ => BatteryStatus::Unknown,
+        };
+
+        // Capacities are in mAh; Apple Silicon reports MaxCapacity/CurrentCapacity
+        // as percentages and the raw mAh values under AppleRaw*
+        let max_mah = number("AppleRawMaxCapacity").or_else(|| number("MaxCapacity"));
+        let current_mah = number("AppleRawCurrentCapacity").or_else(|| number("CurrentCapacity"));
+        let voltage = number("Voltage").map(|mv| mv as f32 / 1000.0);
+        let to_wh = |mah: i64| voltage.map(|v| mah as f32 / 1000.0 * v);
+
+        let percentage = match (current_mah, max_mah) {
+            (Some(current), Some(max)) if max > 0 => current as f32 / max as f32 * 100.0,
+            _ => 0.0,
+        };
+        let mut battery = Self::empty(percentage.min(100.0), status);
+
+        battery.voltage = voltage;
+        // Amperage is a signed mA value (negative while discharging)
+        battery.current = number("Amperage").map(|ma| ma as f32 / 1000.0);
+        battery.charge_rate_watts = match (battery.current, voltage) {
+            (Some(amps), Some(volts)) if amps != 0.0 => Some(amps * volts),
+            _ => None,
+        };
+        battery.design_capacity_wh = number("DesignCapacity").and_then(to_wh);
+        battery.current_capacity_wh = max_mah.and_then(to_wh);
+        battery.remaining_capacity_wh = current_mah.and_then(to_wh);
+        battery.cycle_count = number("CycleCount").map(|c| c as u32);
+        battery.temperature = number("Temperature").map(|t| t as f32 / 100.0);
+        // 65535 means "still calculating"
+        battery.time_remaining_minutes = number("AvgTimeToEmpty")
+            .filter(|m| *m > 0 && *m < 65535)
+            .map(|m| m as u32);
+        battery.time_to_full_minutes = number("AvgTimeToFull")
+            .filter(|m| *m > 0 && *m < 65535)
+            .map(|m| m as u32);
+        battery.manufacturer = text_value("Manufacturer");
+        battery.model = text_value("DeviceName");
+        battery.serial_number = text_value("Serial").or_else(|| text_value("BatterySerialNumber"));
+
+        Ok(battery.derive_estimates())
+    }
+
     /// Get battery percentage
     pub fn percentage(&self) -> f32 {
         self.percentage
@@ -88,6 +383,32 @@ impl BatteryInfo {
             .map(|minutes| minutes as f32 / 60.0)
     }
 
+    /// Get estimated time until fully charged in hours
+    pub fn time_to_full_hours(&self) -> Option<f32> {
+        self.time_to_full_minutes
+            .map(|minutes| minutes as f32 / 60.0)
+    }
+
+    /// Get design capacity in Wh
+    pub fn design_capacity_wh(&self) -> Option<f32> {
+        self.design_capacity_wh
+    }
+
+    /// Get full-charge capacity in Wh
+    pub fn full_charge_capacity_wh(&self) -> Option<f32> {
+        self.current_capacity_wh
+    }
+
+    /// Get charge cycle count
+    pub fn cycle_count(&self) -> Option<u32> {
+        self.cycle_count
+    }
+
+    /// Get charge (positive) or discharge (negative) rate in watts
+    pub fn charge_rate_watts(&self) -> Option<f32> {
+        self.charge_rate_watts
+    }
+
     /// Get battery health percentage
     pub fn health_percent(&self) -> Option<f32> {
         self.health_percent
@@ -124,3 +445,50 @@ impl BatteryInfo {
         self.percentage
     }
 }
+
+/// Battery drain between two readings taken while discharging
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatteryDrain {
+    /// Charge lost per hour in percentage points
+    pub percent_per_hour: f64,
+    /// Discharge power in watts, from the OS rate or the stored energy delta
+    pub watts: Option<f64>,
+    /// Time between the two readings
+    pub window: Duration,
+}
+
+impl BatteryDrain {
+    /// Drain from `earlier` to `later`, taken `elapsed` apart
+    ///
+    /// Returns `None` unless the battery was discharging at both readings.
+    /// Batteries report charge in whole percent, so short windows give coarse
+    /// rates; the monitor measures over at least a minute.
+    pub fn between(earlier: &BatteryInfo, later: &BatteryInfo, elapsed: Duration) -> Option<Self> {
+        let hours = elapsed.as_secs_f64() / 3600.0;
+        if hours <= 0.0 || !earlier.is_discharging() || !later.is_discharging() {
+            return None;
+        }
+
+        let percent_per_hour = (earlier.percentage - later.percentage) as f64 / hours;
+        // The OS rate is instantaneous; the energy delta covers the whole window
+        let watts = later
+            .charge_rate_watts
+            .filter(|rate| *rate < 0.0)
+            .map(|rate| -rate as f64)
+            .or_else(|| match (earlier.remaining_capacity_wh, later.remaining_capacity_wh) {
+                (Some(before), Some(after)) if before > after => Some((before - after) as f64 / hours),
+                _ => None,
+            });
+
+        Some(Self {
+            percent_per_hour: percent_per_hour.max(0.0),
+            watts,
+            window: elapsed,
+        })
+    }
+
+    /// Hours until empty from `percentage` at this drain rate
+    pub fn hours_remaining(&self, percentage: f32) -> Option<f64> {
+        (self.percent_per_hour > 0.0).then(|| percentage as f64 / self.percent_per_hour)
+    }
+}
@@ -0,0 +1,282 @@
+//! Opt-in micro-benchmarks for calibrating hardware scores
+//!
+//! The scores in [`SystemOverview`](crate::SystemOverview) and
+//! [`HardwarePresets`](crate::HardwarePresets) are derived from specifications
+//! alone. The probes in this module measure a few things directly (CPU
+//! floating point throughput, memory bandwidth and storage reads) so those
+//! scores can be checked against what the machine actually delivers.
+//!
+//! Benchmarks only run when explicitly requested and stay within the time
+//! budget they are given.
+
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Reference numbers a score of 100 is measured against
+///
+/// Roughly a current high-end desktop: 16 cores with AVX2, dual-channel DDR5
+/// and a PCIe 4.0 NVMe drive.
+const REFERENCE_CPU_GFLOPS: f64 = 1000.0;
+const REFERENCE_MEMORY_GBPS: f64 = 60.0;
+const REFERENCE_SEQ_READ_MB_S: f64 = 5000.0;
+const REFERENCE_RANDOM_READ_IOPS: f64 = 500_000.0;
+
+/// Measured results from a benchmark run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkResults {
+    /// Single-thread floating point throughput in GFLOPS (f32 multiply-add)
+    pub cpu_gflops_single: f64,
+    /// All-thread floating point throughput in GFLOPS
+    pub cpu_gflops_multi: f64,
+    /// Sustained memory read bandwidth in GB/s
+    pub memory_bandwidth_gbps: f64,
+    /// Sequential read throughput of the temp directory's filesystem in MB/s
+    pub storage_seq_read_mb_s: Option<f64>,
+    /// Random 4 KiB read operations per second
+    pub storage_random_read_iops: Option<f64>,
+    /// Wall-clock time spent benchmarking
+    pub elapsed: Duration,
+}
+
+impl BenchmarkResults {
+    /// Run the CPU, memory and storage probes within roughly `budget`
+    ///
+    /// 40% of the budget goes to the CPU, 30% to memory and 30% to storage.
+    /// Storage probes read a file they have just written in the temp
+    /// directory, so results can include operating system caching and should
+    /// be read as an upper bound.
+    pub fn run(budget: Duration) -> Result<Self> {
+        let start = Instant::now();
+        let cpu_budget = budget.mul_f32(0.4);
+
+        let cpu_gflops_single = cpu_gflops(cpu_budget / 2);
+        let cpu_gflops_multi = cpu_gflops_all_threads(cpu_budget / 2);
+
+        let memory_bandwidth_gbps = memory_bandwidth_gbps(budget.mul_f32(0.3));
+
+        let (storage_seq_read_mb_s, storage_random_read_iops) =
+            match storage_read(budget.mul_f32(0.3)) {
+                Ok((seq, iops)) => (Some(seq), Some(iops)),
+                Err(_) => (None, None),
+            };
+
+        Ok(Self {
+            cpu_gflops_single,
+            cpu_gflops_multi,
+            memory_bandwidth_gbps,
+            storage_seq_read_mb_s,
+            storage_random_read_iops,
+            elapsed: start.elapsed(),
+        })
+    }
+
+    /// CPU score (0-100) relative to the reference machine
+    pub fn cpu_score(&self) -> u8 {
+        ratio_score(self.cpu_gflops_multi, REFERENCE_CPU_GFLOPS)
+    }
+
+    /// Memory score (0-100) relative to the reference machine
+    pub fn memory_score(&self) -> u8 {
+        ratio_score(self.memory_bandwidth_gbps, REFERENCE_MEMORY_GBPS)
+    }
+
+    /// Storage score (0-100) relative to the reference machine, if storage was measured
+    pub fn storage_score(&self) -> Option<u8> {
+        let seq = self.storage_seq_read_mb_s?;
+        let iops = self.storage_random_read_iops?;
+        let seq_score = ratio_score(seq, REFERENCE_SEQ_READ_MB_S) as u32;
+        let iops_score = ratio_score(iops, REFERENCE_RANDOM_READ_IOPS) as u32;
+        Some(((seq_score + iops_score) / 2) as u8)
+    }
+
+    /// Overall measured score (0-100), weighted like the heuristic performance score
+    pub fn overall_score(&self) -> u8 {
+        let cpu = self.cpu_score() as f64;
+        let memory = self.memory_score() as f64;
+        match self.storage_score() {
+            Some(storage) => (cpu * 0.5 + memory * 0.3 + storage as f64 * 0.2) as u8,
+            None => (cpu * 0.6 + memory * 0.4) as u8,
+        }
+    }
+}
+
+/// Theoretical CPU and memory figures next to measured ones
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstimateValidation {
+    /// Estimated peak single-precision GFLOPS ([`CPUInfo::estimated_gflops`](crate::CPUInfo::estimated_gflops))
+    pub estimated_gflops: Option<f64>,
+    /// Measured all-thread GFLOPS
+    pub measured_gflops: f64,
+    /// Estimated peak memory bandwidth in GB/s
+    pub estimated_memory_bandwidth_gbps: Option<f64>,
+    /// Measured memory read bandwidth in GB/s
+    pub measured_memory_bandwidth_gbps: f64,
+}
+
+impl EstimateValidation {
+    /// Measured GFLOPS as a fraction of the estimate
+    ///
+    /// The probe is portable Rust, so it rarely reaches the AVX/FMA peak;
+    /// values well below 1.0 are expected.
+    pub fn gflops_efficiency(&self) -> Option<f64> {
+        self.estimated_gflops
+            .filter(|&estimate| estimate > 0.0)
+            .map(|estimate| self.measured_gflops / estimate)
+    }
+
+    /// Measured memory bandwidth as a fraction of the estimate
+    pub fn memory_bandwidth_efficiency(&self) -> Option<f64> {
+        self.estimated_memory_bandwidth_gbps
+            .filter(|&estimate| estimate > 0.0)
+            .map(|estimate| self.measured_memory_bandwidth_gbps / estimate)
+    }
+}
+
+/// Run the CPU and memory probes (half the budget each) against estimates
+pub(crate) fn validate_estimates(
+    estimated_gflops: Option<f64>,
+    estimated_memory_bandwidth_gbps: Option<f64>,
+    budget: Duration,
+) -> EstimateValidation {
+    EstimateValidation {
+        estimated_gflops,
+        measured_gflops: cpu_gflops_all_threads(budget / 2),
+        estimated_memory_bandwidth_gbps,
+        measured_memory_bandwidth_gbps: memory_bandwidth_gbps(budget / 2),
+    }
+}
+
+/// Blend a heuristic score with a measured one (equal weight)
+pub(crate) fn calibrate(heuristic: u8, measured: u8) -> u8 {
+    ((heuristic as u16 + measured as u16) / 2) as u8
+}
+
+fn ratio_score(measured: f64, reference: f64) -> u8 {
+    (measured / reference * 100.0).clamp(0.0, 100.0) as u8
+}
+
+/// Measure f32 multiply-add throughput on every available thread at once
+fn cpu_gflops_all_threads(budget: Duration) -> f64 {
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| scope.spawn(|| cpu_gflops(budget)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or(0.0))
+            .sum::<f64>()
+    })
+}
+
+/// Measure f32 multiply-add throughput on the current thread
+fn cpu_gflops(budget: Duration) -> f64 {
+    // Independent accumulators keep the loop free of dependency chains so the
+    // compiler can vectorize it
+    const LANES: usize = 64;
+    const ITERATIONS_PER_CHECK: usize = 10_000;
+
+    let mut acc = [1.0f32; LANES];
+    let a = black_box(0.999_999f32);
+    let b = black_box(0.000_001f32);
+    let mut iterations = 0u64;
+    let start = Instant::now();
+
+    while start.elapsed() < budget {
+        for _ in 0..ITERATIONS_PER_CHECK {
+            for value in acc.iter_mut() {
+                *value = *value * a + b;
+            }
+        }
+        black_box(&mut acc);
+        iterations += ITERATIONS_PER_CHECK as u64;
+    }
+
+    let seconds = start.elapsed().as_secs_f64();
+    let flops = iterations as f64 * LANES as f64 * 2.0;
+    if seconds > 0.0 { flops / seconds / 1e9 } else { 0.0 }
+}
+
+/// Measure read bandwidth over a buffer much larger than the last-level cache
+fn memory_bandwidth_gbps(budget: Duration) -> f64 {
+    const BUFFER_BYTES: usize = 256 * 1024 * 1024;
+
+    let buffer = vec![1u64; BUFFER_BYTES / 8];
+    let mut bytes_read = 0u64;
+    let start = Instant::now();
+
+    while start.elapsed() < budget {
+        let sum = buffer.iter().fold(0u64, |acc, value| acc.wrapping_add(*value));
+        black_box(sum);
+        bytes_read += BUFFER_BYTES as u64;
+    }
+
+    let seconds = start.elapsed().as_secs_f64();
+    if seconds > 0.0 { bytes_read as f64 / seconds / 1e9 } else { 0.0 }
+}
+
+/// Measure sequential and random 4 KiB reads of a scratch file in the temp directory
+fn storage_read(budget: Duration) -> Result<(f64, f64)> {
+    use std::fs::{self, File};
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    const FILE_BYTES: usize = 64 * 1024 * 1024;
+    const CHUNK_BYTES: usize = 1024 * 1024;
+    const BLOCK_BYTES: usize = 4096;
+
+    let path = std::env::temp_dir().join(format!("hardware-query-bench-{}", std::process::id()));
+    let result = (|| -> Result<(f64, f64)> {
+        let chunk = vec![0xA5u8; CHUNK_BYTES];
+        let mut file = File::create(&path)?;
+        for _ in 0..FILE_BYTES / CHUNK_BYTES {
+            file.write_all(&chunk)?;
+        }
+        file.sync_all()?;
+        drop(file);
+
+        let half = budget / 2;
+        let mut file = File::open(&path)?;
+        let mut buf = vec![0u8; CHUNK_BYTES];
+
+        // Sequential pass(es)
+        let mut bytes_read = 0u64;
+        let start = Instant::now();
+        while start.elapsed() < half {
+            file.seek(SeekFrom::Start(0))?;
+            loop {
+                let n = file.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                bytes_read += n as u64;
+            }
+        }
+        let seq_mb_s = bytes_read as f64 / start.elapsed().as_secs_f64() / 1e6;
+
+        // Random 4 KiB reads with a cheap xorshift for offsets
+        let blocks = (FILE_BYTES / BLOCK_BYTES) as u64;
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let mut operations = 0u64;
+        let start = Instant::now();
+        while start.elapsed() < half {
+            for _ in 0..256 {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                file.seek(SeekFrom::Start((state % blocks) * BLOCK_BYTES as u64))?;
+                file.read_exact(&mut buf[..BLOCK_BYTES])?;
+                operations += 1;
+            }
+        }
+        let iops = operations as f64 / start.elapsed().as_secs_f64();
+
+        Ok((seq_mb_s, iops))
+    })();
+
+    let _ = fs::remove_file(&path);
+    result
+}
@@ -0,0 +1,544 @@
+//! `hardware-query` command-line interface
+//!
+//! Exposes the library's main query tiers to shell scripts:
+//!
+//! ```text
+//! hardware-query overview
+//! hardware-query ai --json
+//! hardware-query gpu --json
+//! hardware-query full --json --redact
+//! hardware-query monitor --interval 2s
+//! hardware-query capabilities
+//! hardware-query report --html --detail full --redact > hardware.html
+//! ```
+//!
+//! Built with `cargo install hardware-query --features cli`.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use futures::StreamExt;
+use hardware_query::{
+    Capabilities, GPUInfo, HardwareInfo, HardwareMonitor, HardwarePresets, MonitoringConfig, MonitoringEvent,
+    ReportDetail, Result, SystemOverview, VideoCodec,
+};
+use serde::Serialize;
+use std::process::ExitCode;
+use std::time::Duration;
+
+#[derive(Parser)]
+#[command(name = "hardware-query", version, about = "Query hardware information")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Quick system overview with health status
+    Overview(OutputArgs),
+    /// AI/ML hardware assessment
+    Ai(OutputArgs),
+    /// Local LLM inference assessment
+    Llm(OutputArgs),
+    /// Stable Diffusion / image generation assessment
+    Image(OutputArgs),
+    /// Video editing / content creation assessment
+    Video(OutputArgs),
+    /// Gaming hardware assessment
+    Gaming(OutputArgs),
+    /// Developer workstation assessment
+    Developer(OutputArgs),
+    /// Server hardware assessment
+    Server(OutputArgs),
+    /// Detected GPUs
+    Gpu(OutputArgs),
+    /// Complete hardware information
+    Full(FullArgs),
+    /// Stream monitoring events until interrupted
+    Monitor(MonitorArgs),
+    /// Which subsystems need more privileges to query fully
+    Capabilities(OutputArgs),
+    /// Markdown or HTML report for support tickets
+    Report(ReportArgs),
+}
+
+#[derive(Args)]
+struct OutputArgs {
+    /// Print JSON instead of a human-readable summary
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct FullArgs {
+    /// Print JSON instead of a human-readable summary
+    #[arg(long)]
+    json: bool,
+
+    /// Mask serial numbers, UUIDs, MAC and IP addresses (for bug reports)
+    #[arg(long)]
+    redact: bool,
+}
+
+#[derive(Args)]
+struct ReportArgs {
+    /// Write a standalone HTML page instead of Markdown
+    #[arg(long)]
+    html: bool,
+
+    /// How much detail to include
+    #[arg(long, value_enum, default_value = "standard")]
+    detail: Detail,
+
+    /// Mask serial numbers, UUIDs, MAC and IP addresses
+    #[arg(long)]
+    redact: bool,
+
+    /// Append an assessment section (repeatable)
+    #[arg(long = "assessment", value_enum)]
+    assessments: Vec<Assessment>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Detail {
+    Summary,
+    Standard,
+    Full,
+}
+
+impl From<Detail> for ReportDetail {
+    fn from(detail: Detail) -> Self {
+        match detail {
+            Detail::Summary => ReportDetail::Summary,
+            Detail::Standard => ReportDetail::Standard,
+            Detail::Full => ReportDetail::Full,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Assessment {
+    Ai,
+    Llm,
+    Image,
+    Video,
+    Gaming,
+    Developer,
+    Server,
+}
+
+#[derive(Args)]
+struct MonitorArgs {
+    /// Polling interval, e.g. `2s`, `500ms` or `1m`
+    #[arg(long, default_value = "5s", value_parser = parse_interval)]
+    interval: Duration,
+
+    /// Print one JSON object per event
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("hardware-query: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Overview(args) => {
+            let overview = SystemOverview::quick()?;
+            output(&overview, args.json, || print_overview(&overview))
+        }
+        Command::Ai(args) => {
+            let assessment = HardwarePresets::ai_assessment()?;
+            output(&assessment, args.json, || {
+                println!("AI Score: {}/100", assessment.ai_score);
+                println!("Training: {:?}", assessment.performance.training_capability);
+                println!("Inference: {:?}", assessment.performance.inference_capability);
+                println!("Frameworks:");
+                for framework in &assessment.frameworks {
+                    println!("  {}: {:?}", framework.name, framework.compatibility);
+                }
+                print_list("Optimizations", &assessment.optimizations);
+            })
+        }
+        Command::Llm(args) => {
+            let assessment = HardwarePresets::llm_assessment()?;
+            output(&assessment, args.json, || {
+                let rate = |rate: Option<f64>| rate.map_or("-".to_string(), |rate| format!("{rate:.0} tok/s"));
+                println!("Reference model: {}", assessment.reference_model);
+                println!("Prefill: {} ({})", rate(assessment.prefill.tokens_per_second), assessment.prefill.class);
+                println!("Decode: {} ({})", rate(assessment.decode.tokens_per_second), assessment.decode.class);
+                println!("Largest models ({}-token context):", assessment.context_length);
+                for size in &assessment.max_model_sizes {
+                    println!(
+                        "  {}: {:.1}B in GPU memory, {:.1}B overall",
+                        size.quantization, size.max_parameters_in_gpu_billions, size.max_parameters_billions
+                    );
+                }
+                println!("Engines:");
+                for runtime in &assessment.runtimes {
+                    let status = if runtime.viable { "viable" } else { "not viable" };
+                    println!("  {}: {status} ({})", runtime.name, runtime.notes);
+                }
+                print_list("Recommendations", &assessment.recommendations);
+            })
+        }
+        Command::Image(args) => {
+            let assessment = HardwarePresets::image_generation_assessment()?;
+            output(&assessment, args.json, || {
+                println!(
+                    "GPU: {} ({:.1} GB usable)",
+                    assessment.gpu.as_deref().unwrap_or("none"),
+                    assessment.usable_vram_gb
+                );
+                println!("fp16: {}, bf16: {}", assessment.fp16_supported, assessment.bf16_supported);
+                for fit in &assessment.models {
+                    let status = if fit.fits {
+                        format!("batch {}", fit.recommended_batch_size)
+                    } else {
+                        fit.notes.clone().unwrap_or_default()
+                    };
+                    println!(
+                        "  {} {}x{}: {:.1} GB, {status}",
+                        fit.model, fit.resolution, fit.resolution, fit.vram_required_gb
+                    );
+                }
+                for acceleration in &assessment.accelerations {
+                    let status = if acceleration.supported { "supported" } else { "unsupported" };
+                    println!("  {}: {status} ({})", acceleration.name, acceleration.notes);
+                }
+                print_list("Recommendations", &assessment.recommendations);
+            })
+        }
+        Command::Video(args) => {
+            let assessment = HardwarePresets::content_creation_assessment()?;
+            output(&assessment, args.json, || {
+                for engine in &assessment.video_engines {
+                    let codecs = |codecs: &[VideoCodec]| {
+                        codecs.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+                    };
+                    println!(
+                        "{} {}: encode {} / decode {}",
+                        engine.gpu,
+                        engine.generation.as_deref().unwrap_or(&engine.name),
+                        codecs(&engine.encode),
+                        codecs(&engine.decode)
+                    );
+                }
+                println!(
+                    "Storage: {} ({:.0} MB/s)",
+                    assessment.media_drive.as_deref().unwrap_or("unknown"),
+                    assessment.storage_throughput_mb_s
+                );
+                for timeline in &assessment.timelines {
+                    let status = if timeline.native_editing { "native" } else { "proxies" };
+                    println!("  {}: {status}", timeline.resolution);
+                }
+                print_list("Recommendations", &assessment.recommendations);
+            })
+        }
+        Command::Gaming(args) => {
+            let assessment = HardwarePresets::gaming_assessment()?;
+            output(&assessment, args.json, || {
+                let settings = &assessment.recommended_settings;
+                println!("Gaming Score: {}/100", assessment.gaming_score);
+                println!(
+                    "Recommended: {} {:?} @ {} FPS",
+                    settings.resolution, settings.quality_preset, settings.target_fps
+                );
+                print_list("Bottlenecks", &assessment.bottlenecks);
+                print_list("Upgrades", &assessment.upgrade_recommendations);
+            })
+        }
+        Command::Developer(args) => {
+            let assessment = HardwarePresets::developer_assessment()?;
+            output(&assessment, args.json, || {
+                println!("Development Score: {}/100", assessment.dev_score);
+                println!(
+                    "Max recommended VMs: {}",
+                    assessment.virtualization_support.max_recommended_vms
+                );
+                print_list("Recommendations", &assessment.tool_recommendations);
+            })
+        }
+        Command::Server(args) => {
+            let assessment = HardwarePresets::server_assessment()?;
+            output(&assessment, args.json, || {
+                println!("Server Score: {}/100", assessment.server_score);
+                for workload in &assessment.workload_suitability {
+                    println!("  {}: {}/100", workload.workload_type, workload.suitability_score);
+                }
+                print_list("Maintenance", &assessment.reliability.maintenance_requirements);
+            })
+        }
+        Command::Gpu(args) => {
+            let gpus = GPUInfo::query_all()?;
+            output(&gpus, args.json, || {
+                for (index, gpu) in gpus.iter().enumerate() {
+                    println!(
+                        "GPU {index}: {} {} ({:.1} GB, {})",
+                        gpu.vendor(),
+                        gpu.model_name(),
+                        gpu.memory_gb(),
+                        gpu.gpu_type()
+                    );
+                }
+            })
+        }
+        Command::Full(args) => {
+            let mut hw_info = HardwareInfo::query()?;
+            if args.redact {
+                hw_info = hw_info.redacted();
+            }
+            if args.json {
+                println!("{}", hw_info.to_json_pretty()?);
+            } else {
+                println!("{hw_info:#?}");
+            }
+            Ok(())
+        }
+        Command::Monitor(args) => monitor(args),
+        Command::Capabilities(args) => {
+            let capabilities = Capabilities::check();
+            output(&capabilities, args.json, || {
+                println!("Elevated: {}", capabilities.is_elevated());
+                for access in capabilities.subsystems() {
+                    match &access.degraded_data {
+                        Some(degraded) => println!("{}: {} ({degraded})", access.subsystem, access.level),
+                        None => println!("{}: {}", access.subsystem, access.level),
+                    }
+                }
+                let missing: Vec<String> = capabilities
+                    .missing_requirements()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect();
+                print_list("Missing", &missing);
+            })
+        }
+        Command::Report(args) => {
+            let mut hw_info = HardwareInfo::query()?;
+            if args.redact {
+                hw_info = hw_info.redacted();
+            }
+            let mut report = hw_info.report(args.detail.into());
+            for assessment in args.assessments {
+                report = match assessment {
+                    Assessment::Ai => report.with_section(&HardwarePresets::ai_assessment()?),
+                    Assessment::Llm => report.with_section(&HardwarePresets::llm_assessment()?),
+                    Assessment::Image => report.with_section(&HardwarePresets::image_generation_assessment()?),
+                    Assessment::Video => report.with_section(&HardwarePresets::content_creation_assessment()?),
+                    Assessment::Gaming => report.with_section(&HardwarePresets::gaming_assessment()?),
+                    Assessment::Developer => report.with_section(&HardwarePresets::developer_assessment()?),
+                    Assessment::Server => report.with_section(&HardwarePresets::server_assessment()?),
+                };
+            }
+            print!("{}", if args.html { report.to_html() } else { report.to_markdown() });
+            Ok(())
+        }
+    }
+}
+
+/// Print `value` as JSON, or run `human` to print a summary
+fn output<T: Serialize>(value: &T, json: bool, human: impl FnOnce()) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(value)?);
+    } else {
+        human();
+    }
+    Ok(())
+}
+
+fn print_overview(overview: &SystemOverview) {
+    println!("CPU: {} ({} cores, {} threads)", overview.cpu.name, overview.cpu.cores, overview.cpu.threads);
+    println!("Memory: {:.1} GB", overview.memory_gb);
+    match &overview.gpu {
+        Some(gpu) => println!("GPU: {} ({:.1} GB VRAM)", gpu.name, gpu.vram_gb),
+        None => println!("GPU: None"),
+    }
+    println!(
+        "Storage: {:.0} GB {} ({})",
+        overview.storage.total_gb, overview.storage.drive_type, overview.storage.health
+    );
+    println!("Environment: {}", overview.environment);
+    if overview.cpu_limit.is_some() || overview.memory_limit_gb.is_some() {
+        println!(
+            "Container limits: {} threads, {:.1} GB",
+            overview.effective_cores(),
+            overview.effective_memory_gb()
+        );
+    }
+    println!("Health: {}", overview.health.status);
+    println!("Performance Score: {}/100", overview.performance_score);
+    print_list("Warnings", &overview.health.warnings);
+}
+
+fn print_list(title: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    println!("{title}:");
+    for item in items {
+        println!("  - {item}");
+    }
+}
+
+fn monitor(args: MonitorArgs) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+
+    runtime.block_on(async move {
+        let monitor = HardwareMonitor::with_config(MonitoringConfig {
+            update_interval: args.interval,
+            ..MonitoringConfig::default()
+        });
+        let mut events = monitor.events();
+        monitor.start_monitoring().await?;
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => break,
+                event = events.next() => {
+                    let Some(event) = event else {
+                        break;
+                    };
+                    if args.json {
+                        println!("{}", serde_json::to_string(&event)?);
+                    } else {
+                        print_event(&event);
+                    }
+                }
+            }
+        }
+
+        monitor.stop_monitoring().await;
+        Ok(())
+    })
+}
+
+fn print_event(event: &MonitoringEvent) {
+    match event {
+        MonitoringEvent::MetricsUpdate {
+            hardware_info,
+            thermal_info,
+            gpu_usage,
+            ..
+        } => {
+            let mut line = Vec::new();
+            if let Some(info) = hardware_info {
+                let usage = info.cpu().core_usage();
+                if !usage.is_empty() {
+                    let average = usage.iter().sum::<f32>() / usage.len() as f32;
+                    line.push(format!("cpu {average:.0}%"));
+                }
+                line.push(format!("mem {:.0}%", info.memory().usage_percent()));
+            }
+            for (index, gpu) in gpu_usage.iter().enumerate() {
+                if let Some(usage) = gpu.utilization_percent {
+                    line.push(format!("gpu{index} {usage:.0}%"));
+                }
+                if let (Some(used), Some(total)) = (gpu.memory_used_mb, gpu.memory_total_mb) {
+                    line.push(format!("vram{index} {used}/{total} MB"));
+                }
+            }
+            if let Some(max) = thermal_info.as_ref().and_then(|t| t.max_temperature()) {
+                line.push(format!("max temp {max:.0}°C"));
+            }
+            println!("{}", line.join("  "));
+        }
+        MonitoringEvent::ThermalAlert {
+            sensor_name,
+            temperature,
+            threshold,
+            ..
+        } => println!("THERMAL {sensor_name}: {temperature:.1}°C (threshold {threshold:.1}°C)"),
+        MonitoringEvent::PowerAlert {
+            current_power,
+            threshold,
+            ..
+        } => println!("POWER {current_power:.1} W (threshold {threshold:.1} W)"),
+        MonitoringEvent::RuleTriggered { rule_id, value, .. } => println!("RULE {rule_id}: {value:.1}"),
+        MonitoringEvent::RuleCleared { rule_id, value, .. } => println!("CLEARED {rule_id}: {value:.1}"),
+        MonitoringEvent::MemoryErrors {
+            new_uncorrectable,
+            uncorrectable_errors,
+            ..
+        } => println!("ECC {new_uncorrectable} new uncorrectable error(s) ({uncorrectable_errors} total)"),
+        MonitoringEvent::DiskSaturated {
+            device,
+            utilization_percent,
+            queue_depth,
+            duration,
+            ..
+        } => match utilization_percent {
+            Some(utilization) => println!(
+                "DISK {device}: {utilization:.0}% busy, queue depth {queue_depth:.1} for {}s",
+                duration.as_secs()
+            ),
+            None => println!("DISK {device}: queue depth {queue_depth:.1} for {}s", duration.as_secs()),
+        },
+        MonitoringEvent::LinkStateChanged {
+            interface,
+            is_up,
+            speed_mbps,
+            ..
+        } => match (is_up, speed_mbps) {
+            (true, Some(speed)) => println!("LINK {interface} up ({speed} Mbit/s)"),
+            (true, None) => println!("LINK {interface} up"),
+            (false, _) => println!("LINK {interface} down"),
+        },
+        MonitoringEvent::PowerStateChanged {
+            power_source,
+            power_state,
+            power_plan,
+            ..
+        } => match power_plan {
+            Some(plan) => println!("POWER on {power_source}, plan {plan} ({power_state})"),
+            None => println!("POWER on {power_source}"),
+        },
+        MonitoringEvent::ThrottlingDetected { device, causes, .. } => {
+            let causes: Vec<String> = causes.iter().map(ToString::to_string).collect();
+            println!("THROTTLE {device}: {}", causes.join(", "))
+        }
+        MonitoringEvent::VramExhausted {
+            device,
+            memory_used_mb,
+            memory_total_mb,
+            used_percent,
+            ..
+        } => println!("VRAM {device}: {used_percent:.0}% ({memory_used_mb}/{memory_total_mb} MB)"),
+        MonitoringEvent::ChargerStateChanged { .. }
+        | MonitoringEvent::LowBattery { .. }
+        | MonitoringEvent::AbnormalBatteryDrain { .. } => println!("BATTERY {}", event.summary()),
+        MonitoringEvent::HardwareChanged { description, .. } => println!("CHANGE {description}"),
+        MonitoringEvent::MonitoringError { error, .. } => eprintln!("ERROR {error}"),
+    }
+}
+
+/// Parse `2s`, `500ms`, `1m` or a bare number of seconds
+fn parse_interval(value: &str) -> std::result::Result<Duration, String> {
+    let value = value.trim();
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit() && c != '.') {
+        Some(index) => value.split_at(index),
+        None => (value, "s"),
+    };
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid interval `{value}`"))?;
+    let seconds = match unit {
+        "ms" => number / 1000.0,
+        "s" => number,
+        "m" => number * 60.0,
+        _ => return Err(format!("unknown interval unit `{unit}` (use ms, s or m)")),
+    };
+    if seconds <= 0.0 {
+        return Err("interval must be positive".to_string());
+    }
+    Ok(Duration::from_secs_f64(seconds))
+}
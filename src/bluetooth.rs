@@ -0,0 +1,509 @@
+//! Bluetooth adapters and paired devices
+//!
+//! Linux reads BlueZ's object tree over D-Bus (through `busctl`) and the
+//! controller's HCI version from `btmgmt`; adapters are still listed from
+//! `/sys/class/bluetooth` when `bluetoothd` is not running. Windows reads the
+//! Bluetooth PnP class through WMI. macOS reads IOBluetooth's view through
+//! `system_profiler SPBluetoothDataType`.
+
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+/// A local Bluetooth controller
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BluetoothAdapter {
+    /// Platform identifier ("hci0" on Linux, the PnP device ID on Windows)
+    pub id: String,
+    /// Friendly name
+    pub name: String,
+    /// Controller address ("AA:BB:CC:DD:EE:FF")
+    pub address: Option<String>,
+    /// Bluetooth Core specification version (e.g. "5.3")
+    pub version: Option<String>,
+    /// Controller manufacturer
+    pub manufacturer: Option<String>,
+    /// Radio is powered on
+    pub powered: Option<bool>,
+    /// Bluetooth Low Energy support
+    pub low_energy: Option<bool>,
+}
+
+impl BluetoothAdapter {
+    /// Get platform identifier
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get friendly name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get Bluetooth Core specification version
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+}
+
+/// A remote device known to a local adapter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BluetoothDevice {
+    /// Device name
+    pub name: String,
+    /// Device address ("AA:BB:CC:DD:EE:FF")
+    pub address: Option<String>,
+    /// Device kind as reported by the platform (e.g. "audio-headset", "Keyboard")
+    pub device_type: Option<String>,
+    /// Device is paired (bonded) with this machine
+    pub paired: bool,
+    /// Device is currently connected (`None` if the platform does not say)
+    pub connected: Option<bool>,
+    /// Device uses Bluetooth Low Energy
+    pub low_energy: Option<bool>,
+    /// Battery level reported over Bluetooth, in percent
+    pub battery_percent: Option<u8>,
+    /// Identifier of the adapter the device belongs to
+    pub adapter: Option<String>,
+}
+
+impl BluetoothDevice {
+    /// Get device name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Check if the device is currently connected
+    pub fn is_connected(&self) -> bool {
+        self.connected == Some(true)
+    }
+}
+
+/// Bluetooth adapters and, optionally, their paired and connected devices
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BluetoothInfo {
+    /// Local controllers
+    pub adapters: Vec<BluetoothAdapter>,
+    /// Paired or connected devices (empty unless queried with devices)
+    pub devices: Vec<BluetoothDevice>,
+}
+
+impl BluetoothInfo {
+    /// Query local adapters
+    pub fn query() -> Result<Self> {
+        Self::query_platform(false)
+    }
+
+    /// Query local adapters and their paired or connected devices
+    pub fn query_with_devices() -> Result<Self> {
+        Self::query_platform(true)
+    }
+
+    fn query_platform(include_devices: bool) -> Result<Self> {
+        #[cfg(target_os = "linux")]
+        {
+            linux::query(include_devices)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            windows::query(include_devices)
+        }
+        #[cfg(target_os = "macos")]
+        {
+            macos::query(include_devices)
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            let _ = include_devices;
+            Err(crate::HardwareQueryError::platform_not_supported(
+                "Bluetooth detection is not supported on this platform",
+            ))
+        }
+    }
+
+    /// Get local adapters
+    pub fn adapters(&self) -> &[BluetoothAdapter] {
+        &self.adapters
+    }
+
+    /// Get paired or connected devices
+    pub fn devices(&self) -> &[BluetoothDevice] {
+        &self.devices
+    }
+
+    /// Devices that are currently connected
+    pub fn connected_devices(&self) -> impl Iterator<Item = &BluetoothDevice> {
+        self.devices.iter().filter(|device| device.is_connected())
+    }
+
+    /// Check if the machine has a Bluetooth adapter
+    pub fn is_available(&self) -> bool {
+        !self.adapters.is_empty()
+    }
+
+    /// Check if any adapter supports Bluetooth Low Energy
+    pub fn supports_low_energy(&self) -> bool {
+        self.adapters.iter().any(|adapter| adapter.low_energy == Some(true))
+    }
+}
+
+/// Bluetooth Core version of an HCI/LMP version number
+#[cfg_attr(target_os = "windows", allow(dead_code))]
+fn core_version(hci_version: u8) -> Option<&'static str> {
+    const VERSIONS: [&str; 15] = [
+        "1.0b", "1.1", "1.2", "2.0", "2.1", "3.0", "4.0", "4.1", "4.2", "5.0", "5.1", "5.2", "5.3", "5.4", "6.0",
+    ];
+    VERSIONS.get(hci_version as usize).copied()
+}
+
+/// Format 12 hex digits as a colon-separated address
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn format_address(hex: &str) -> Option<String> {
+    if hex.len() != 12 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let octets: Vec<&str> = (0..12).step_by(2).map(|i| &hex[i..i + 2]).collect();
+    Some(octets.join(":").to_uppercase())
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{BluetoothAdapter, BluetoothDevice, BluetoothInfo, core_version};
+    use crate::probe::Command;
+    use crate::Result;
+    use serde_json::Value;
+    use std::collections::HashMap;
+
+    /// Controller details from `btmgmt info`
+    #[derive(Default)]
+    struct Controller {
+        address: Option<String>,
+        version: Option<u8>,
+        manufacturer: Option<u16>,
+        low_energy: Option<bool>,
+    }
+
+    pub(super) fn query(include_devices: bool) -> Result<BluetoothInfo> {
+        let controllers = controllers();
+        let mut info = BluetoothInfo::default();
+
+        // BlueZ D-Bus API: one object per adapter (/org/bluez/hci0) and per device
+        let objects = Command::new("busctl")
+            .args([
+                "--system",
+                "--json=short",
+                "call",
+                "org.bluez",
+                "/",
+                "org.freedesktop.DBus.ObjectManager",
+                "GetManagedObjects",
+            ])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| serde_json::from_slice::<Value>(&output.stdout).ok());
+        let objects = objects
+            .as_ref()
+            .and_then(|reply| reply.pointer("/data/0"))
+            .and_then(Value::as_object);
+
+        if let Some(objects) = objects {
+            for (path, interfaces) in objects {
+                let id = path.rsplit('/').next().unwrap_or(path).to_string();
+                if let Some(adapter) = interfaces.get("org.bluez.Adapter1") {
+                    let controller = controllers.get(&id);
+                    // Roles lists the LE roles (central, peripheral) the adapter can take
+                    let le_roles = property(adapter, "Roles")
+                        .and_then(Value::as_array)
+                        .map(|roles| !roles.is_empty());
+                    info.adapters.push(BluetoothAdapter {
+                        name: text(adapter, "Alias").or_else(|| text(adapter, "Name")).unwrap_or_else(|| id.clone()),
+                        address: text(adapter, "Address").or_else(|| controller.and_then(|c| c.address.clone())),
+                        version: controller.and_then(|c| c.version).and_then(core_version).map(str::to_string),
+                        manufacturer: controller.and_then(|c| c.manufacturer).map(manufacturer_name),
+                        powered: property(adapter, "Powered").and_then(Value::as_bool),
+                        low_energy: controller.and_then(|c| c.low_energy).or(le_roles),
+                        id,
+                    });
+                } else if let Some(device) = interfaces.get("org.bluez.Device1").filter(|_| include_devices) {
+                    let paired = property(device, "Paired").and_then(Value::as_bool).unwrap_or(false);
+                    let connected = property(device, "Connected").and_then(Value::as_bool);
+                    if !paired && connected != Some(true) {
+                        // Devices merely seen during discovery
+                        continue;
+                    }
+                    info.devices.push(BluetoothDevice {
+                        name: text(device, "Alias").or_else(|| text(device, "Name")).unwrap_or_default(),
+                        address: text(device, "Address"),
+                        device_type: text(device, "Icon"),
+                        paired,
+                        connected,
+                        // Only BR/EDR devices carry a class of device
+                        low_energy: Some(property(device, "Class").is_none()),
+                        battery_percent: interfaces
+                            .get("org.bluez.Battery1")
+                            .and_then(|battery| property(battery, "Percentage"))
+                            .and_then(Value::as_u64)
+                            .and_then(|percent| u8::try_from(percent).ok()),
+                        adapter: text(device, "Adapter").and_then(|path| path.rsplit('/').next().map(str::to_string)),
+                    });
+                }
+            }
+        }
+
+        // Without bluetoothd the kernel still lists its controllers
+        if info.adapters.is_empty() {
+            let mut ids: Vec<String> = std::fs::read_dir("/sys/class/bluetooth")
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                        .filter(|name| name.starts_with("hci") && !name.contains(':'))
+                        .collect()
+                })
+                .unwrap_or_default();
+            ids.sort();
+            for id in ids {
+                let controller = controllers.get(&id);
+                info.adapters.push(BluetoothAdapter {
+                    name: id.clone(),
+                    address: controller.and_then(|c| c.address.clone()),
+                    version: controller.and_then(|c| c.version).and_then(core_version).map(str::to_string),
+                    manufacturer: controller.and_then(|c| c.manufacturer).map(manufacturer_name),
+                    powered: None,
+                    low_energy: controller.and_then(|c| c.low_energy),
+                    id,
+                });
+            }
+        }
+
+        Ok(info)
+    }
+
+    /// `data` of a `{"type": ..., "data": ...}` property in busctl's JSON
+    fn property<'a>(interface: &'a Value, key: &str) -> Option<&'a Value> {
+        interface.get(key)?.get("data")
+    }
+
+    fn text(interface: &Value, key: &str) -> Option<String> {
+        property(interface, key)
+            .and_then(Value::as_str)
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    }
+
+    /// Parse `btmgmt info`
+    ///
+    /// ```text
+    /// hci0:   Primary controller
+    ///         addr 5C:F3:70:8B:3B:AB version 8 manufacturer 15 class 0x0c010c
+    ///         supported settings: powered connectable ... br/edr le advertising
+    /// ```
+    fn controllers() -> HashMap<String, Controller> {
+        let mut controllers = HashMap::new();
+        let Ok(output) = Command::new("btmgmt").arg("info").output() else {
+            return controllers;
+        };
+        let text = String::from_utf8_lossy(&output.stdout);
+
+        let mut current: Option<String> = None;
+        for line in text.lines() {
+            if let Some((id, _)) = line.split_once(':').filter(|(id, _)| id.starts_with("hci")) {
+                current = Some(id.to_string());
+                continue;
+            }
+            let Some(controller) = current.as_ref().map(|id| controllers.entry(id.clone()).or_insert_with(Controller::default)) else {
+                continue;
+            };
+            let line = line.trim();
+            if let Some(settings) = line.strip_prefix("supported settings:") {
+                controller.low_energy = Some(settings.split_whitespace().any(|s| s == "le"));
+            } else if line.starts_with("addr ") {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let after = |key: &str| fields.iter().position(|f| *f == key).and_then(|i| fields.get(i + 1));
+                controller.address = after("addr").map(|a| a.to_string());
+                controller.version = after("version").and_then(|v| v.parse().ok());
+                controller.manufacturer = after("manufacturer").and_then(|m| m.parse().ok());
+            }
+        }
+
+        controllers
+    }
+
+    /// Company name of a Bluetooth SIG company identifier
+    fn manufacturer_name(id: u16) -> String {
+        match id {
+            2 => "Intel".to_string(),
+            10 => "Qualcomm (CSR)".to_string(),
+            13 => "Texas Instruments".to_string(),
+            15 => "Broadcom".to_string(),
+            29 => "Qualcomm".to_string(),
+            70 => "MediaTek".to_string(),
+            76 => "Apple".to_string(),
+            93 => "Realtek".to_string(),
+            305 => "Cypress".to_string(),
+            _ => format!("Company {id}"),
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use super::{BluetoothAdapter, BluetoothDevice, BluetoothInfo, format_address};
+    use crate::Result;
+    use std::collections::HashMap;
+    use wmi::{Variant, WMIConnection};
+
+    pub(super) fn query(include_devices: bool) -> Result<BluetoothInfo> {
+        let wmi_con = WMIConnection::new(crate::probe::com_library()?)?;
+        let entities: Vec<HashMap<String, Variant>> = wmi_con.raw_query(
+            "SELECT DeviceID, Name, Manufacturer, ConfigManagerErrorCode FROM Win32_PnPEntity \
+             WHERE PNPClass = 'Bluetooth'",
+        )?;
+
+        let string = |entity: &HashMap<String, Variant>, key: &str| match entity.get(key) {
+            Some(Variant::String(value)) if !value.is_empty() => Some(value.clone()),
+            _ => None,
+        };
+
+        let ids: Vec<String> = entities.iter().filter_map(|e| string(e, "DeviceID")).collect();
+        // The LE enumerator is only installed for radios that support LE
+        let low_energy = ids.iter().any(|id| id.to_uppercase().starts_with("BTH\\MS_BTHLE"));
+
+        let mut info = BluetoothInfo::default();
+        for entity in &entities {
+            let Some(device_id) = string(entity, "DeviceID") else {
+                continue;
+            };
+            let upper = device_id.to_uppercase();
+            let name = string(entity, "Name").unwrap_or_default();
+
+            if upper.starts_with("USB\\") || upper.starts_with("PCI\\") || upper.starts_with("ACPI\\") {
+                // Radios sit on a hardware bus; the enumerators and services below them do not
+                let disabled = matches!(entity.get("ConfigManagerErrorCode"), Some(Variant::UI4(22)));
+                info.adapters.push(BluetoothAdapter {
+                    id: device_id,
+                    name,
+                    address: None,
+                    version: None,
+                    manufacturer: string(entity, "Manufacturer").filter(|m| !m.starts_with('(')),
+                    powered: disabled.then_some(false),
+                    low_energy: Some(low_energy),
+                });
+            } else if include_devices
+                && (upper.starts_with("BTHENUM\\DEV_") || upper.starts_with("BTHLE\\DEV_"))
+            {
+                // "BTHENUM\DEV_001A7DDA7113\7&..." is the device node; per-service nodes are skipped.
+                // Windows only creates device nodes for paired devices.
+                let address = upper
+                    .split('\\')
+                    .nth(1)
+                    .and_then(|part| part.strip_prefix("DEV_"))
+                    .and_then(format_address);
+                info.devices.push(BluetoothDevice {
+                    name,
+                    address,
+                    device_type: None,
+                    paired: true,
+                    connected: None,
+                    low_energy: Some(upper.starts_with("BTHLE\\")),
+                    battery_percent: None,
+                    adapter: None,
+                });
+            }
+        }
+
+        Ok(info)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::{BluetoothAdapter, BluetoothDevice, BluetoothInfo};
+    use crate::probe::Command;
+    use crate::Result;
+    use serde_json::Value;
+
+    pub(super) fn query(include_devices: bool) -> Result<BluetoothInfo> {
+        let output = Command::new("system_profiler")
+            .args(["SPBluetoothDataType", "-json"])
+            .output()?;
+        let json: Value = serde_json::from_slice(&output.stdout)?;
+
+        let text = |value: &Value, key: &str| {
+            value
+                .get(key)
+                .and_then(Value::as_str)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        let mut info = BluetoothInfo::default();
+        let Some(entries) = json.get("SPBluetoothDataType").and_then(Value::as_array) else {
+            return Ok(info);
+        };
+
+        for entry in entries {
+            let Some(controller) = entry.get("controller_properties") else {
+                continue;
+            };
+            let address = text(controller, "controller_address");
+            // e.g. "0x392039 < HFP AVRCP A2DP HID Braille AACP GATT SerialPort >"
+            let low_energy = text(controller, "controller_supportedServices")
+                .map(|services| services.split_whitespace().any(|s| s == "GATT"));
+            info.adapters.push(BluetoothAdapter {
+                id: address.clone().unwrap_or_else(|| "default".to_string()),
+                name: text(controller, "controller_chipset").unwrap_or_else(|| "Bluetooth Controller".to_string()),
+                address,
+                version: controller
+                    .as_object()
+                    .and_then(|properties| {
+                        properties
+                            .iter()
+                            .find(|(key, _)| key.to_lowercase().contains("hci") && key.to_lowercase().contains("version"))
+                    })
+                    .and_then(|(_, value)| value.as_str())
+                    .and_then(parse_version),
+                manufacturer: text(controller, "controller_vendorID")
+                    .and_then(|vendor| Some(vendor.split_once('(')?.1.trim_end_matches(')').to_string())),
+                powered: text(controller, "controller_state").map(|state| state == "attrib_on"),
+                low_energy,
+            });
+
+            if !include_devices {
+                continue;
+            }
+            for (list, connected) in [("device_connected", true), ("device_not_connected", false)] {
+                let Some(devices) = entry.get(list).and_then(Value::as_array) else {
+                    continue;
+                };
+                // Each element is a one-key object: {"<device name>": {properties}}
+                for (name, properties) in devices.iter().filter_map(Value::as_object).flatten() {
+                    let battery = ["device_batteryLevelMain", "device_batteryLevel"]
+                        .iter()
+                        .find_map(|key| text(properties, key))
+                        .and_then(|level| level.trim_end_matches('%').trim().parse().ok());
+                    info.devices.push(BluetoothDevice {
+                        name: name.clone(),
+                        address: text(properties, "device_address"),
+                        device_type: text(properties, "device_minorType"),
+                        paired: true,
+                        connected: Some(connected),
+                        low_energy: None,
+                        battery_percent: battery,
+                        adapter: info.adapters.last().map(|adapter| adapter.id.clone()),
+                    });
+                }
+            }
+        }
+
+        Ok(info)
+    }
+
+    /// Extract "5.0" from values like "0x9 (5.0)" or "5.0 (0x9)"
+    fn parse_version(value: &str) -> Option<String> {
+        value
+            .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+            .find(|part| part.contains('.') && part.split('.').all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())))
+            .map(str::to_string)
+    }
+}
@@ -0,0 +1,357 @@
+//! Browser backend for `wasm32-unknown-unknown`
+//!
+//! Enabled by the `wasm` feature. A web page sees very little of the machine
+//! it runs on; [`BrowserProvider`] reports what the navigator exposes:
+//! `hardwareConcurrency` as the logical core count, `deviceMemory` (rounded
+//! and capped by the browser) as total memory, and the WebGPU adapter's
+//! vendor, architecture and description. Every other component is listed in
+//! [`HardwareInfo::errors`] with
+//! [`ComponentErrorKind::Unsupported`](crate::ComponentErrorKind::Unsupported).
+//!
+//! ```ignore
+//! let info = hardware_query::HardwareInfo::query_browser().await?;
+//! for component in info.unsupported_components() {
+//!     log(&format!("{component} is not visible to web pages"));
+//! }
+//! ```
+
+use crate::{
+    ARMHardwareInfo, BatteryInfo, CPUInfo, CPUTopology, ComputeCapabilities, FPGAInfo, GPUInfo, GPUType,
+    GPUVendor, HardwareInfo, HardwareProvider, HardwareQueryError, MemoryInfo, NPUInfo,
+    NetworkInfo, PCIDevice, PowerProfile, Result, StorageLayout, SystemInfo, TPUInfo, ThermalInfo,
+    USBDevice, VirtualizationInfo,
+};
+use js_sys::{Function, Promise, Reflect};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// `GPUAdapterInfo` of the browser's WebGPU adapter
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebGpuAdapterInfo {
+    /// Vendor name (`nvidia`, `amd`, `intel`, `apple`, ...), empty if the browser hides it
+    pub vendor: String,
+    /// GPU family (`ampere`, `rdna-3`, `gen-12lp`, ...)
+    pub architecture: String,
+    /// Vendor-specific device identifier
+    pub device: String,
+    /// Human-readable description, usually empty unless the user allowed it
+    pub description: String,
+    /// Whether the adapter is a software fallback
+    pub is_fallback: bool,
+}
+
+/// What a web page can learn about the machine
+///
+/// Create one with [`detect`](Self::detect) and pass it to
+/// [`HardwareInfo::query_with_provider`], or call
+/// [`HardwareInfo::query_browser`] directly.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BrowserProvider {
+    /// `navigator.hardwareConcurrency`
+    pub logical_cores: Option<u32>,
+    /// `navigator.deviceMemory` in GB (Chromium only, rounded and capped at 8)
+    pub device_memory_gb: Option<f64>,
+    /// `navigator.userAgent`
+    pub user_agent: Option<String>,
+    /// Whether the browser implements WebGPU (`navigator.gpu`)
+    pub webgpu_available: bool,
+    /// The WebGPU adapter, if one was granted
+    pub webgpu_adapter: Option<WebGpuAdapterInfo>,
+    /// Detection time in seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+impl BrowserProvider {
+    /// Read the navigator and request a WebGPU adapter
+    ///
+    /// Works on the main thread and in workers.
+    pub async fn detect() -> Self {
+        let navigator = property(&js_sys::global(), "navigator");
+        let number = |name: &str| navigator.as_ref().and_then(|n| property(n, name)).and_then(|v| v.as_f64());
+        let gpu = navigator.as_ref().and_then(|n| property(n, "gpu"));
+
+        let webgpu_adapter = match &gpu {
+            Some(gpu) => webgpu_adapter(gpu).await,
+            None => None,
+        };
+        Self {
+            logical_cores: number("hardwareConcurrency").filter(|n| *n >= 1.0).map(|n| n as u32),
+            device_memory_gb: number("deviceMemory").filter(|gb| *gb > 0.0),
+            user_agent: navigator
+                .as_ref()
+                .and_then(|n| property(n, "userAgent"))
+                .and_then(|v| v.as_string()),
+            webgpu_available: gpu.is_some(),
+            webgpu_adapter,
+            timestamp: (js_sys::Date::now() / 1000.0) as u64,
+        }
+    }
+}
+
+fn unsupported<T>(what: &str) -> Result<T> {
+    Err(HardwareQueryError::platform_not_supported(format!(
+        "{what} is not exposed to web pages"
+    )))
+}
+
+impl HardwareProvider for BrowserProvider {
+    fn timestamp(&self) -> Result<u64> {
+        Ok(self.timestamp)
+    }
+
+    fn cpu(&self) -> Result<CPUInfo> {
+        let Some(logical_cores) = self.logical_cores else {
+            return unsupported("CPU core count");
+        };
+        let mut cpu = CPUInfo::unknown();
+        cpu.logical_cores = logical_cores;
+        cpu.physical_cores = logical_cores;
+        cpu.architecture = "Unknown".to_string();
+        cpu.topology = CPUTopology::flat(logical_cores);
+        Ok(cpu)
+    }
+
+    fn gpus(&self) -> Result<Vec<GPUInfo>> {
+        if !self.webgpu_available {
+            return unsupported("WebGPU");
+        }
+        Ok(self.webgpu_adapter.iter().map(webgpu_gpu).collect())
+    }
+
+    fn npus(&self) -> Result<Vec<NPUInfo>> {
+        unsupported("NPU information")
+    }
+
+    fn tpus(&self) -> Result<Vec<TPUInfo>> {
+        unsupported("TPU information")
+    }
+
+    fn arm_hardware(&self) -> Result<Option<ARMHardwareInfo>> {
+        unsupported("SoC information")
+    }
+
+    fn fpgas(&self) -> Result<Vec<FPGAInfo>> {
+        unsupported("FPGA information")
+    }
+
+    fn memory(&self) -> Result<MemoryInfo> {
+        let Some(gb) = self.device_memory_gb else {
+            return unsupported("Memory size");
+        };
+        let mut memory = MemoryInfo::unknown();
+        memory.total_mb = (gb * 1024.0) as u64;
+        Ok(memory)
+    }
+
+    fn storage(&self) -> Result<StorageLayout> {
+        unsupported("Storage information")
+    }
+
+    fn network_interfaces(&self) -> Result<Vec<NetworkInfo>> {
+        unsupported("Network interface information")
+    }
+
+    fn battery(&self) -> Result<Option<BatteryInfo>> {
+        unsupported("Battery information")
+    }
+
+    fn thermal(&self) -> Result<ThermalInfo> {
+        unsupported("Thermal information")
+    }
+
+    fn pci_devices(&self) -> Result<Vec<PCIDevice>> {
+        unsupported("PCI device information")
+    }
+
+    fn usb_devices(&self) -> Result<Vec<USBDevice>> {
+        unsupported("USB device information")
+    }
+
+    fn power_profile(&self) -> Result<Option<PowerProfile>> {
+        unsupported("Power profile information")
+    }
+
+    fn virtualization(&self) -> Result<VirtualizationInfo> {
+        unsupported("Virtualization information")
+    }
+
+    fn system(&self) -> Result<SystemInfo> {
+        unsupported("System information")
+    }
+}
+
+impl HardwareInfo {
+    /// Query what the browser exposes (`wasm` feature, wasm32 only)
+    ///
+    /// Components a web page cannot see are listed in
+    /// [`errors`](Self::errors) as unsupported.
+    pub async fn query_browser() -> Result<Self> {
+        Self::query_with_provider(BrowserProvider::detect().await)
+    }
+}
+
+fn webgpu_gpu(adapter: &WebGpuAdapterInfo) -> GPUInfo {
+    let vendor = match adapter.vendor.to_ascii_lowercase().as_str() {
+        "nvidia" => GPUVendor::NVIDIA,
+        "amd" | "ati" => GPUVendor::AMD,
+        "intel" => GPUVendor::Intel,
+        "apple" => GPUVendor::Apple,
+        "arm" => GPUVendor::ARM,
+        "qualcomm" => GPUVendor::Qualcomm,
+        "" => GPUVendor::Unknown("Unknown".to_string()),
+        other => GPUVendor::Unknown(other.to_string()),
+    };
+    let model_name = if !adapter.description.is_empty() {
+        adapter.description.clone()
+    } else {
+        let name = format!("{} {}", adapter.vendor, adapter.architecture);
+        match name.trim() {
+            "" => "WebGPU adapter".to_string(),
+            name => name.to_string(),
+        }
+    };
+
+    GPUInfo {
+        vendor,
+        model_name,
+        gpu_type: if adapter.is_fallback { GPUType::Virtual } else { GPUType::Unknown },
+        // Browsers don't reveal VRAM
+        memory_mb: 0,
+        memory_type: None,
+        memory_bandwidth: None,
+        base_clock: None,
+        boost_clock: None,
+        memory_clock: None,
+        shader_units: None,
+        rt_cores: None,
+        tensor_cores: None,
+        compute_capabilities: ComputeCapabilities {
+            cuda: None,
+            rocm: false,
+            directml: false,
+            opencl: false,
+            vulkan: false,
+            metal: false,
+            compute_units: None,
+            max_workgroup_size: None,
+            metal_family: None,
+            vulkan_device: None,
+            opencl_device: None,
+            metal_device: None,
+            level_zero: false,
+            oneapi: false,
+        },
+        usage_percent: None,
+        temperature: None,
+        power_consumption: None,
+        power_limit: None,
+        driver_version: None,
+        vbios_version: None,
+        pci_device_id: None,
+        pci_subsystem_id: None,
+        pci_bus_id: None,
+        unified_memory: false,
+        directx: None,
+        video: None,
+        resizable_bar: None,
+    }
+}
+
+/// Request an adapter from `navigator.gpu` and read its `GPUAdapterInfo`
+async fn webgpu_adapter(gpu: &JsValue) -> Option<WebGpuAdapterInfo> {
+    let adapter = call_async(gpu, "requestAdapter").await?;
+    // `adapter.info` replaced `requestAdapterInfo()`, which older Chromium still needs
+    let info = match property(&adapter, "info") {
+        Some(info) => info,
+        None => call_async(&adapter, "requestAdapterInfo").await?,
+    };
+    let text = |name: &str| property(&info, name).and_then(|v| v.as_string()).unwrap_or_default();
+    let is_fallback = property(&info, "isFallbackAdapter")
+        .or_else(|| property(&adapter, "isFallbackAdapter"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    Some(WebGpuAdapterInfo {
+        vendor: text("vendor"),
+        architecture: text("architecture"),
+        device: text("device"),
+        description: text("description"),
+        is_fallback,
+    })
+}
+
+/// Call a promise-returning method and await its (non-null) result
+async fn call_async(target: &JsValue, method: &str) -> Option<JsValue> {
+    let method = property(target, method)?.dyn_into::<Function>().ok()?;
+    let promise = method.call0(target).ok()?.dyn_into::<Promise>().ok()?;
+    PromiseFuture::new(&promise)
+        .await
+        .ok()
+        .filter(|value| !value.is_null() && !value.is_undefined())
+}
+
+fn property(target: &JsValue, name: &str) -> Option<JsValue> {
+    Reflect::get(target, &JsValue::from_str(name))
+        .ok()
+        .filter(|value| !value.is_null() && !value.is_undefined())
+}
+
+type Callback = Closure<dyn FnMut(JsValue)>;
+
+#[derive(Default)]
+struct PromiseState {
+    result: Option<std::result::Result<JsValue, JsValue>>,
+    waker: Option<Waker>,
+}
+
+/// Resolves when a JS promise settles
+///
+/// The same job as `wasm_bindgen_futures::JsFuture`, which would be the only
+/// reason to depend on that crate.
+struct PromiseFuture {
+    state: Rc<RefCell<PromiseState>>,
+    _callbacks: (Callback, Callback),
+}
+
+impl PromiseFuture {
+    fn new(promise: &Promise) -> Self {
+        let state = Rc::new(RefCell::new(PromiseState::default()));
+        let settle = |fulfilled: bool| -> Callback {
+            let state = Rc::clone(&state);
+            Closure::new(move |value: JsValue| {
+                let mut state = state.borrow_mut();
+                state.result = Some(if fulfilled { Ok(value) } else { Err(value) });
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            })
+        };
+        let callbacks = (settle(true), settle(false));
+        let _ = promise.then2(&callbacks.0, &callbacks.1);
+        Self {
+            state,
+            _callbacks: callbacks,
+        }
+    }
+}
+
+impl Future for PromiseFuture {
+    type Output = std::result::Result<JsValue, JsValue>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -4,33 +4,164 @@
 //! allowing developers to request only the information they need without the
 //! overhead of collecting all available hardware data.
 
+use crate::probe::{self, ProbePolicy};
 use crate::{
-    HardwareInfo, CPUInfo, GPUInfo, MemoryInfo, StorageInfo, NetworkInfo, 
+    HardwareInfo, CPUInfo, GPUInfo, MemoryInfo, StorageInfo, NetworkInfo,
     BatteryInfo, ThermalInfo, PCIDevice, USBDevice, VirtualizationInfo,
-    Result,
+    NPUInfo, TPUInfo, FPGAInfo, SystemInfo, PhysicalDisk, Volume, Result,
+    ComponentError, HardwareComponent,
 };
+use crate::storage::StorageLayout;
 
 #[cfg(feature = "monitoring")]
 use crate::PowerProfile;
 
 use serde::{Serialize, Deserialize};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+/// Individual subsystem that a [`HardwareQueryBuilder`] can include or skip
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Component {
+    Cpu,
+    Gpu,
+    Npu,
+    Tpu,
+    Fpga,
+    Memory,
+    Storage,
+    Network,
+    Battery,
+    Thermal,
+    Pci,
+    Usb,
+    Virtualization,
+    /// Power profile (requires the `monitoring` feature)
+    Power,
+    System,
+}
+
+impl Component {
+    /// All components, in query order
+    pub const ALL: [Component; 15] = [
+        Component::Cpu,
+        Component::Gpu,
+        Component::Npu,
+        Component::Tpu,
+        Component::Fpga,
+        Component::Memory,
+        Component::Storage,
+        Component::Network,
+        Component::Battery,
+        Component::Thermal,
+        Component::Pci,
+        Component::Usb,
+        Component::Virtualization,
+        Component::Power,
+        Component::System,
+    ];
+}
+
+impl std::fmt::Display for Component {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Component::Cpu => write!(f, "CPU"),
+            Component::Gpu => write!(f, "GPU"),
+            Component::Npu => write!(f, "NPU"),
+            Component::Tpu => write!(f, "TPU"),
+            Component::Fpga => write!(f, "FPGA"),
+            Component::Memory => write!(f, "Memory"),
+            Component::Storage => write!(f, "Storage"),
+            Component::Network => write!(f, "Network"),
+            Component::Battery => write!(f, "Battery"),
+            Component::Thermal => write!(f, "Thermal"),
+            Component::Pci => write!(f, "PCI"),
+            Component::Usb => write!(f, "USB"),
+            Component::Virtualization => write!(f, "Virtualization"),
+            Component::Power => write!(f, "Power"),
+            Component::System => write!(f, "System"),
+        }
+    }
+}
+
+impl From<Component> for HardwareComponent {
+    /// Component group that [`HardwareInfo`] reports the subsystem under
+    fn from(component: Component) -> Self {
+        match component {
+            Component::Cpu => HardwareComponent::CPU,
+            Component::Gpu => HardwareComponent::GPU,
+            Component::Npu | Component::Tpu | Component::Fpga => HardwareComponent::Accelerators,
+            Component::Memory => HardwareComponent::Memory,
+            Component::Storage => HardwareComponent::Storage,
+            Component::Network => HardwareComponent::Network,
+            Component::Battery => HardwareComponent::Battery,
+            Component::Thermal => HardwareComponent::Thermal,
+            Component::Pci | Component::Usb => HardwareComponent::Devices,
+            Component::Virtualization => HardwareComponent::Virtualization,
+            Component::Power => HardwareComponent::Power,
+            Component::System => HardwareComponent::System,
+        }
+    }
+}
+
+/// Top-level fields of [`CustomHardwareInfo`] and the component that fills them
+const FIELDS: &[(&str, Option<Component>)] = &[
+    ("cpu", Some(Component::Cpu)),
+    ("gpus", Some(Component::Gpu)),
+    ("npus", Some(Component::Npu)),
+    ("tpus", Some(Component::Tpu)),
+    ("fpgas", Some(Component::Fpga)),
+    ("memory", Some(Component::Memory)),
+    ("storage_devices", Some(Component::Storage)),
+    ("physical_disks", Some(Component::Storage)),
+    ("volumes", Some(Component::Storage)),
+    ("network_interfaces", Some(Component::Network)),
+    ("battery", Some(Component::Battery)),
+    ("thermal", Some(Component::Thermal)),
+    ("pci_devices", Some(Component::Pci)),
+    ("usb_devices", Some(Component::Usb)),
+    ("virtualization", Some(Component::Virtualization)),
+    ("system", Some(Component::System)),
+    ("power_profile", Some(Component::Power)),
+    ("timestamp", None),
+    ("query_time_ms", None),
+    ("requested_components", None),
+    ("errors", None),
+];
+
+/// Component names accepted in place of the field they fill
+const FIELD_ALIASES: &[(&str, &str)] = &[
+    ("gpu", "gpus"),
+    ("npu", "npus"),
+    ("tpu", "tpus"),
+    ("fpga", "fpgas"),
+    ("storage", "storage_devices"),
+    ("network", "network_interfaces"),
+    ("pci", "pci_devices"),
+    ("usb", "usb_devices"),
+    ("power", "power_profile"),
+];
+
+type Filter<T> = Box<dyn Fn(&T) -> bool + Send>;
 
 /// Hardware query builder for selective information gathering
+///
+/// Only the selected components are probed, so a CPU + memory query never
+/// spawns `lsusb`/`dmidecode` or initializes NVML. Probes can additionally be
+/// restricted with [`without_wmi`](Self::without_wmi) and
+/// [`without_external_commands`](Self::without_external_commands); affected
+/// components then report what the remaining mechanisms can detect.
+///
+/// To keep serialized results small, [`select`](Self::select) limits the
+/// output of [`CustomHardwareInfo::to_json`] to the listed fields and the
+/// `filter_*` methods drop devices that don't match a predicate.
 pub struct HardwareQueryBuilder {
-    include_cpu: bool,
-    include_gpu: bool,
-    include_memory: bool,
-    include_storage: bool,
-    include_network: bool,
-    include_battery: bool,
-    include_thermal: bool,
-    include_pci: bool,
-    include_usb: bool,
-    include_virtualization: bool,
-    include_capabilities: bool,
-    
-    #[cfg(feature = "monitoring")]
-    include_power: bool,
+    components: HashSet<Component>,
+    policy: ProbePolicy,
+    fields: Vec<String>,
+    gpu_filters: Vec<Filter<GPUInfo>>,
+    storage_filters: Vec<Filter<StorageInfo>>,
+    network_filters: Vec<Filter<NetworkInfo>>,
 }
 
 /// Customizable hardware information result
@@ -38,14 +169,26 @@ pub struct HardwareQueryBuilder {
 pub struct CustomHardwareInfo {
     pub cpu: Option<CPUInfo>,
     pub gpus: Vec<GPUInfo>,
+    #[serde(default)]
+    pub npus: Vec<NPUInfo>,
+    #[serde(default)]
+    pub tpus: Vec<TPUInfo>,
+    #[serde(default)]
+    pub fpgas: Vec<FPGAInfo>,
     pub memory: Option<MemoryInfo>,
     pub storage_devices: Vec<StorageInfo>,
+    #[serde(default)]
+    pub physical_disks: Vec<PhysicalDisk>,
+    #[serde(default)]
+    pub volumes: Vec<Volume>,
     pub network_interfaces: Vec<NetworkInfo>,
     pub battery: Option<BatteryInfo>,
     pub thermal: Option<ThermalInfo>,
     pub pci_devices: Vec<PCIDevice>,
     pub usb_devices: Vec<USBDevice>,
     pub virtualization: Option<VirtualizationInfo>,
+    #[serde(default)]
+    pub system: Option<SystemInfo>,
     
     #[cfg(feature = "monitoring")]
     pub power_profile: Option<PowerProfile>,
@@ -56,282 +199,376 @@ pub struct CustomHardwareInfo {
     pub query_time_ms: u64,
     /// Which components were requested
     pub requested_components: Vec<String>,
+    /// Requested components that failed to query, reported like [`HardwareInfo::errors`]
+    #[serde(default)]
+    pub errors: Vec<ComponentError>,
+    /// Field paths passed to [`HardwareQueryBuilder::select`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub selected_fields: Vec<String>,
 }
 
 impl HardwareQueryBuilder {
     /// Create a new hardware query builder
     pub fn new() -> Self {
         Self {
-            include_cpu: false,
-            include_gpu: false,
-            include_memory: false,
-            include_storage: false,
-            include_network: false,
-            include_battery: false,
-            include_thermal: false,
-            include_pci: false,
-            include_usb: false,
-            include_virtualization: false,
-            include_capabilities: false,
-            
-            #[cfg(feature = "monitoring")]
-            include_power: false,
+            components: HashSet::new(),
+            policy: ProbePolicy::default(),
+            fields: Vec::new(),
+            gpu_filters: Vec::new(),
+            storage_filters: Vec::new(),
+            network_filters: Vec::new(),
         }
     }
 
-    /// Include CPU information in the query
-    pub fn with_cpu(mut self) -> Self {
-        self.include_cpu = true;
+    /// Include a component in the query
+    pub fn with(mut self, component: Component) -> Self {
+        self.components.insert(component);
         self
     }
 
-    /// Include GPU information in the query
-    pub fn with_gpu(mut self) -> Self {
-        self.include_gpu = true;
+    /// Leave a component out of the query, even if a preset selected it
+    pub fn skip(mut self, component: Component) -> Self {
+        self.components.remove(&component);
         self
     }
 
+    /// Check if a component is selected
+    pub fn includes(&self, component: Component) -> bool {
+        self.components.contains(&component)
+    }
+
+    /// Include CPU information in the query
+    pub fn with_cpu(self) -> Self {
+        self.with(Component::Cpu)
+    }
+
+    /// Include GPU information in the query
+    pub fn with_gpu(self) -> Self {
+        self.with(Component::Gpu)
+    }
+
+    /// Include NPU information in the query
+    pub fn with_npu(self) -> Self {
+        self.with(Component::Npu)
+    }
+
+    /// Include TPU information in the query
+    pub fn with_tpu(self) -> Self {
+        self.with(Component::Tpu)
+    }
+
+    /// Include FPGA information in the query
+    pub fn with_fpga(self) -> Self {
+        self.with(Component::Fpga)
+    }
+
     /// Include memory information in the query
-    pub fn with_memory(mut self) -> Self {
-        self.include_memory = true;
-        self
+    pub fn with_memory(self) -> Self {
+        self.with(Component::Memory)
     }
 
     /// Include storage information in the query
-    pub fn with_storage(mut self) -> Self {
-        self.include_storage = true;
-        self
+    pub fn with_storage(self) -> Self {
+        self.with(Component::Storage)
     }
 
     /// Include network information in the query
-    pub fn with_network(mut self) -> Self {
-        self.include_network = true;
-        self
+    pub fn with_network(self) -> Self {
+        self.with(Component::Network)
     }
 
     /// Include battery information in the query
-    pub fn with_battery(mut self) -> Self {
-        self.include_battery = true;
-        self
+    pub fn with_battery(self) -> Self {
+        self.with(Component::Battery)
     }
 
     /// Include thermal information in the query
-    pub fn with_thermal(mut self) -> Self {
-        self.include_thermal = true;
-        self
+    pub fn with_thermal(self) -> Self {
+        self.with(Component::Thermal)
     }
 
     /// Include PCI device information in the query
-    pub fn with_pci(mut self) -> Self {
-        self.include_pci = true;
-        self
+    pub fn with_pci(self) -> Self {
+        self.with(Component::Pci)
     }
 
     /// Include USB device information in the query
-    pub fn with_usb(mut self) -> Self {
-        self.include_usb = true;
-        self
+    pub fn with_usb(self) -> Self {
+        self.with(Component::Usb)
     }
 
     /// Include virtualization information in the query
-    pub fn with_virtualization(mut self) -> Self {
-        self.include_virtualization = true;
-        self
+    pub fn with_virtualization(self) -> Self {
+        self.with(Component::Virtualization)
+    }
+
+    /// Include system vendor, motherboard and firmware information in the query
+    pub fn with_system(self) -> Self {
+        self.with(Component::System)
     }
 
     /// Include power management information (requires monitoring feature)
     #[cfg(feature = "monitoring")]
-    pub fn with_power(mut self) -> Self {
-        self.include_power = true;
+    pub fn with_power(self) -> Self {
+        self.with(Component::Power)
+    }
+
+    /// Don't open WMI connections (Windows)
+    pub fn without_wmi(mut self) -> Self {
+        self.policy.wmi = false;
+        self
+    }
+
+    /// Don't spawn external tools such as `lspci`, `lsusb`, `dmidecode` or `system_profiler`
+    pub fn without_external_commands(mut self) -> Self {
+        self.policy.external_commands = false;
         self
     }
 
     /// Include all available hardware information
     pub fn with_all(mut self) -> Self {
-        self.include_cpu = true;
-        self.include_gpu = true;
-        self.include_memory = true;
-        self.include_storage = true;
-        self.include_network = true;
-        self.include_battery = true;
-        self.include_thermal = true;
-        self.include_pci = true;
-        self.include_usb = true;
-        self.include_virtualization = true;
-        
-        #[cfg(feature = "monitoring")]
-        {
-            self.include_power = true;
-        }
-        
+        self.components.extend(Component::ALL);
         self
     }
 
     /// Include basic system information (CPU, memory, storage)
-    pub fn with_basic(mut self) -> Self {
-        self.include_cpu = true;
-        self.include_memory = true;
-        self.include_storage = true;
-        self
+    pub fn with_basic(self) -> Self {
+        self.with_cpu().with_memory().with_storage()
     }
 
     /// Include AI/ML relevant information (CPU, GPU, memory)
-    pub fn with_ai_focused(mut self) -> Self {
-        self.include_cpu = true;
-        self.include_gpu = true;
-        self.include_memory = true;
-        self.include_thermal = true;
-        self.include_virtualization = true;
-        
-        #[cfg(feature = "monitoring")]
-        {
-            self.include_power = true;
-        }
-        
-        self
+    pub fn with_ai_focused(self) -> Self {
+        self.with_cpu()
+            .with_gpu()
+            .with_memory()
+            .with_thermal()
+            .with_virtualization()
+            .with(Component::Power)
     }
 
     /// Include gaming-relevant information (CPU, GPU, memory, thermal)
-    pub fn with_gaming_focused(mut self) -> Self {
-        self.include_cpu = true;
-        self.include_gpu = true;
-        self.include_memory = true;
-        self.include_thermal = true;
-        self.include_storage = true;
-        self
+    pub fn with_gaming_focused(self) -> Self {
+        self.with_cpu()
+            .with_gpu()
+            .with_memory()
+            .with_thermal()
+            .with_storage()
     }
 
     /// Include server/enterprise relevant information
-    pub fn with_server_focused(mut self) -> Self {
-        self.include_cpu = true;
-        self.include_memory = true;
-        self.include_storage = true;
-        self.include_network = true;
-        self.include_thermal = true;
-        self.include_pci = true;
-        self.include_virtualization = true;
-        
-        #[cfg(feature = "monitoring")]
-        {
-            self.include_power = true;
-        }
-        
-        self
+    pub fn with_server_focused(self) -> Self {
+        self.with_cpu()
+            .with_memory()
+            .with_storage()
+            .with_network()
+            .with_thermal()
+            .with_pci()
+            .with_virtualization()
+            .with(Component::Power)
     }
 
-    /// Filter GPUs by a custom predicate  
-    pub fn filter_gpus<F>(self, _filter: F) -> Self 
+    /// Keep only the GPUs matching a predicate
+    ///
+    /// Repeated filters must all match.
+    pub fn filter_gpus<F>(mut self, filter: F) -> Self
     where
-        F: Fn(&GPUInfo) -> bool + 'static,
+        F: Fn(&GPUInfo) -> bool + Send + 'static,
     {
-        // TODO: Implement filtering in a future version
+        self.gpu_filters.push(Box::new(filter));
         self
     }
 
-    /// Filter storage devices by a custom predicate
-    pub fn filter_storage<F>(self, _filter: F) -> Self 
+    /// Keep only the storage devices matching a predicate
+    pub fn filter_storage<F>(mut self, filter: F) -> Self
     where
-        F: Fn(&StorageInfo) -> bool + 'static,
+        F: Fn(&StorageInfo) -> bool + Send + 'static,
     {
-        // TODO: Implement filtering in a future version
+        self.storage_filters.push(Box::new(filter));
         self
     }
 
-    /// Filter network interfaces by a custom predicate
-    pub fn filter_network<F>(self, _filter: F) -> Self 
+    /// Keep only the network interfaces matching a predicate
+    pub fn filter_network<F>(mut self, filter: F) -> Self
     where
-        F: Fn(&NetworkInfo) -> bool + 'static,
+        F: Fn(&NetworkInfo) -> bool + Send + 'static,
     {
-        // TODO: Implement filtering in a future version
+        self.network_filters.push(Box::new(filter));
+        self
+    }
+
+    /// Limit serialized output to the given field paths
+    ///
+    /// Paths use the JSON field names of [`CustomHardwareInfo`], separated by
+    /// dots; `*` matches every element of a list (or every value of a map)
+    /// and a number selects one element: `cpu.model_name`,
+    /// `gpus.*.memory_mb`, `storage_devices.0.capacity_gb`. Component names
+    /// such as `gpu` or `storage` are accepted for the first segment. The components
+    /// the paths refer to are queried even if they weren't added with
+    /// `with_*`. The selection applies to [`CustomHardwareInfo::to_json`] and
+    /// [`CustomHardwareInfo::to_value`]; the struct itself keeps every field.
+    ///
+    /// [`query`](Self::query) rejects unknown fields, and `power_profile`
+    /// (`power`) when the `monitoring` feature is disabled.
+    pub fn select(mut self, paths: &[&str]) -> Self {
+        self.fields.extend(paths.iter().map(|path| {
+            let (field, rest) = path.split_once('.').unwrap_or((path, ""));
+            match FIELD_ALIASES.iter().find(|(alias, _)| *alias == field) {
+                Some((_, field)) if rest.is_empty() => field.to_string(),
+                Some((_, field)) => format!("{field}.{rest}"),
+                None => path.to_string(),
+            }
+        }));
         self
     }
 
     /// Execute the query and return the requested hardware information
-    pub fn query(self) -> Result<CustomHardwareInfo> {
+    ///
+    /// Each selected component is probed on its own; a component that fails is
+    /// left empty and its error recorded in
+    /// [`CustomHardwareInfo::errors`] rather than failing the whole query.
+    pub fn query(mut self) -> Result<CustomHardwareInfo> {
         let start_time = std::time::Instant::now();
         let timestamp = std::time::SystemTime::now();
-        
-        // Track which components were requested
+        for path in &self.fields {
+            let field = path.split('.').next().unwrap_or_default();
+            match FIELDS.iter().find(|(name, _)| *name == field) {
+                Some((_, Some(Component::Power))) if !cfg!(feature = "monitoring") => {
+                    return Err(crate::HardwareQueryError::invalid_configuration(format!(
+                        "Field `{field}` in selection `{path}` requires the `monitoring` feature"
+                    )));
+                }
+                Some((_, Some(component))) => {
+                    self.components.insert(*component);
+                }
+                Some((_, None)) => {}
+                None => {
+                    return Err(crate::HardwareQueryError::invalid_configuration(format!(
+                        "Unknown field `{field}` in selection `{path}`"
+                    )));
+                }
+            }
+        }
+        let _restriction = probe::restrict(self.policy);
+
         let mut requested_components = Vec::new();
-        
-        // Get full hardware info first (we'll optimize this later)
-        let full_hw = HardwareInfo::query()?;
-        
-        // Extract requested components
-        let cpu = if self.include_cpu {
-            requested_components.push("CPU".to_string());
-            Some(full_hw.cpu().clone())
+        let mut errors = Vec::new();
+        let mut selected = |component: Component| {
+            let included = self.components.contains(&component);
+            if included {
+                requested_components.push(component.to_string());
+            }
+            included
+        };
+        let mut collect = |component: Component, error: crate::HardwareQueryError| {
+            errors.push(ComponentError::from_error(component.into(), &error));
+        };
+
+        let cpu = if selected(Component::Cpu) {
+            CPUInfo::query().map_err(|e| collect(Component::Cpu, e)).ok()
         } else {
             None
         };
-
-        let gpus = if self.include_gpu {
-            requested_components.push("GPU".to_string());
-            full_hw.gpus().to_vec()
+        let mut gpus = if selected(Component::Gpu) {
+            GPUInfo::query_all().unwrap_or_else(|e| {
+                collect(Component::Gpu, e);
+                Vec::new()
+            })
         } else {
             Vec::new()
         };
-
-        let memory = if self.include_memory {
-            requested_components.push("Memory".to_string());
-            Some(full_hw.memory().clone())
+        gpus.retain(|gpu| self.gpu_filters.iter().all(|filter| filter(gpu)));
+        let npus = if selected(Component::Npu) {
+            NPUInfo::query_all().unwrap_or_else(|e| {
+                collect(Component::Npu, e);
+                Vec::new()
+            })
         } else {
-            None
+            Vec::new()
         };
-
-        let storage_devices = if self.include_storage {
-            requested_components.push("Storage".to_string());
-            full_hw.storage_devices().to_vec()
+        let tpus = if selected(Component::Tpu) {
+            TPUInfo::query_all().unwrap_or_else(|e| {
+                collect(Component::Tpu, e);
+                Vec::new()
+            })
         } else {
             Vec::new()
         };
-
-        let network_interfaces = if self.include_network {
-            requested_components.push("Network".to_string());
-            full_hw.network_interfaces().to_vec()
+        let fpgas = if selected(Component::Fpga) {
+            FPGAInfo::detect_fpgas().unwrap_or_else(|e| {
+                collect(Component::Fpga, e);
+                Vec::new()
+            })
         } else {
             Vec::new()
         };
-
-        let battery = if self.include_battery {
-            requested_components.push("Battery".to_string());
-            full_hw.battery().cloned()
+        let memory = if selected(Component::Memory) {
+            MemoryInfo::query().map_err(|e| collect(Component::Memory, e)).ok()
         } else {
             None
         };
-
-        let thermal = if self.include_thermal {
-            requested_components.push("Thermal".to_string());
-            Some(full_hw.thermal().clone())
+        let mut storage = if selected(Component::Storage) {
+            StorageLayout::query().unwrap_or_else(|e| {
+                collect(Component::Storage, e);
+                StorageLayout::default()
+            })
+        } else {
+            StorageLayout::default()
+        };
+        storage
+            .devices
+            .retain(|device| self.storage_filters.iter().all(|filter| filter(device)));
+        let mut network_interfaces = if selected(Component::Network) {
+            NetworkInfo::query_all().unwrap_or_else(|e| {
+                collect(Component::Network, e);
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+        network_interfaces.retain(|interface| self.network_filters.iter().all(|filter| filter(interface)));
+        // A missing battery is not an error
+        let battery = if selected(Component::Battery) {
+            BatteryInfo::query().ok()
         } else {
             None
         };
-
-        let pci_devices = if self.include_pci {
-            requested_components.push("PCI".to_string());
-            full_hw.pci_devices().to_vec()
+        let thermal = if selected(Component::Thermal) {
+            ThermalInfo::query().map_err(|e| collect(Component::Thermal, e)).ok()
+        } else {
+            None
+        };
+        let pci_devices = if selected(Component::Pci) {
+            PCIDevice::query_all().unwrap_or_else(|e| {
+                collect(Component::Pci, e);
+                Vec::new()
+            })
         } else {
             Vec::new()
         };
-
-        let usb_devices = if self.include_usb {
-            requested_components.push("USB".to_string());
-            full_hw.usb_devices().to_vec()
+        let usb_devices = if selected(Component::Usb) {
+            USBDevice::query_all().unwrap_or_else(|e| {
+                collect(Component::Usb, e);
+                Vec::new()
+            })
         } else {
             Vec::new()
         };
-
-        let virtualization = if self.include_virtualization {
-            requested_components.push("Virtualization".to_string());
-            Some(full_hw.virtualization().clone())
+        let virtualization = if selected(Component::Virtualization) {
+            VirtualizationInfo::detect()
+                .map_err(|e| collect(Component::Virtualization, e))
+                .ok()
+        } else {
+            None
+        };
+        let system = if selected(Component::System) {
+            SystemInfo::query().map_err(|e| collect(Component::System, e)).ok()
         } else {
             None
         };
 
         #[cfg(feature = "monitoring")]
-        let power_profile = if self.include_power {
-            requested_components.push("Power".to_string());
-            full_hw.power_profile().cloned()
+        let power_profile = if selected(Component::Power) {
+            PowerProfile::query().map_err(|e| collect(Component::Power, e)).ok()
         } else {
             None
         };
@@ -341,14 +578,20 @@ impl HardwareQueryBuilder {
         Ok(CustomHardwareInfo {
             cpu,
             gpus,
+            npus,
+            tpus,
+            fpgas,
             memory,
-            storage_devices,
+            storage_devices: storage.devices,
+            physical_disks: storage.disks,
+            volumes: storage.volumes,
             network_interfaces,
             battery,
             thermal,
             pci_devices,
             usb_devices,
             virtualization,
+            system,
             
             #[cfg(feature = "monitoring")]
             power_profile,
@@ -356,9 +599,21 @@ impl HardwareQueryBuilder {
             timestamp,
             query_time_ms,
             requested_components,
+            errors,
+            selected_fields: self.fields,
         })
     }
 
+    /// Execute the query on tokio's blocking thread pool
+    ///
+    /// Async counterpart of [`HardwareQueryBuilder::query`].
+    #[cfg(feature = "async")]
+    pub async fn query_async(self) -> Result<CustomHardwareInfo> {
+        tokio::task::spawn_blocking(move || self.query())
+            .await
+            .map_err(|e| crate::HardwareQueryError::unknown(format!("Hardware query task failed: {e}")))?
+    }
+
     /// Execute a quick query that only gathers essential information
     pub fn quick_query(self) -> Result<CustomHardwareInfo> {
         // For quick queries, we can optimize by avoiding expensive operations
@@ -384,6 +639,27 @@ impl CustomHardwareInfo {
         todo!("Implement conversion to full HardwareInfo")
     }
 
+    /// Serialize to a JSON value, limited to the selected fields if any were selected
+    pub fn to_value(&self) -> Result<Value> {
+        let value = serde_json::to_value(self)?;
+        if self.selected_fields.is_empty() {
+            return Ok(value);
+        }
+        let mut selected = Value::Object(Map::new());
+        for path in &self.selected_fields {
+            let segments: Vec<&str> = path.split('.').collect();
+            if let Some(projection) = project(&value, &segments) {
+                merge(&mut selected, projection);
+            }
+        }
+        Ok(selected)
+    }
+
+    /// Export as JSON, limited to the selected fields if any were selected
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(&self.to_value()?)?)
+    }
+
     /// Get a summary of what was queried
     pub fn query_summary(&self) -> String {
         format!(
@@ -428,3 +704,60 @@ impl HardwareQueryBuilder {
             .query()
     }
 }
+
+/// The parts of `value` reached by `path`, nested as in the original
+fn project(value: &Value, path: &[&str]) -> Option<Value> {
+    let Some((segment, rest)) = path.split_first() else {
+        return Some(value.clone());
+    };
+    match (value, *segment) {
+        // Elements without the field become null so indices still line up
+        (Value::Array(items), "*") => Some(Value::Array(
+            items.iter().map(|item| project(item, rest).unwrap_or(Value::Null)).collect(),
+        )),
+        (Value::Object(fields), "*") => Some(Value::Object(
+            fields
+                .iter()
+                .filter_map(|(key, item)| Some((key.clone(), project(item, rest)?)))
+                .collect(),
+        )),
+        (Value::Array(items), index) => {
+            let index: usize = index.parse().ok()?;
+            let projection = project(items.get(index)?, rest)?;
+            let mut selected = vec![Value::Null; index];
+            selected.push(projection);
+            Some(Value::Array(selected))
+        }
+        (Value::Object(fields), key) => {
+            let projection = project(fields.get(key)?, rest)?;
+            Some(Value::Object(Map::from_iter([(key.to_string(), projection)])))
+        }
+        _ => None,
+    }
+}
+
+/// Merge one projection into the accumulated selection
+fn merge(target: &mut Value, source: Value) {
+    match (target, source) {
+        (Value::Object(target), Value::Object(source)) => {
+            for (key, value) in source {
+                match target.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Array(target), Value::Array(source)) => {
+            for (index, value) in source.into_iter().enumerate() {
+                match target.get_mut(index) {
+                    Some(existing) => merge(existing, value),
+                    None => target.push(value),
+                }
+            }
+        }
+        (_, Value::Null) => {}
+        (target, source) => *target = source,
+    }
+}
@@ -0,0 +1,371 @@
+//! Hardware query caching
+//!
+//! Full hardware detection is expensive: WMI queries, sysfs walks and external
+//! commands can take hundreds of milliseconds. Most of that data (CPU model,
+//! cache sizes, GPU inventory, PCI devices) never changes while a process runs,
+//! so this module caches each component with its own time-to-live while keeping
+//! dynamic readings such as memory usage and temperatures fresh.
+
+use crate::{
+    ARMHardwareInfo, BatteryInfo, CPUInfo, FPGAInfo, GPUInfo, HardwareInfo, HardwareQueryError,
+    MemoryInfo, NPUInfo, NetworkInfo, PCIDevice, PowerProfile, Result, SystemInfo, TPUInfo,
+    ThermalInfo, USBDevice, VirtualizationInfo,
+};
+use crate::storage::StorageLayout;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Hardware component groups that can be queried and cached independently
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HardwareComponent {
+    /// CPU model, topology, caches and features
+    CPU,
+    /// GPU inventory
+    GPU,
+    /// NPUs, TPUs, FPGAs and ARM SoC details
+    Accelerators,
+    /// System memory
+    Memory,
+    /// Storage devices
+    Storage,
+    /// Network interfaces
+    Network,
+    /// Battery
+    Battery,
+    /// Thermal sensors and fans
+    Thermal,
+    /// PCI and USB devices
+    Devices,
+    /// Power profile
+    Power,
+    /// Virtualization and container environment
+    Virtualization,
+    /// System vendor, motherboard and firmware
+    System,
+}
+
+impl HardwareComponent {
+    /// All component groups
+    pub const ALL: [HardwareComponent; 12] = [
+        HardwareComponent::CPU,
+        HardwareComponent::GPU,
+        HardwareComponent::Accelerators,
+        HardwareComponent::Memory,
+        HardwareComponent::Storage,
+        HardwareComponent::Network,
+        HardwareComponent::Battery,
+        HardwareComponent::Thermal,
+        HardwareComponent::Devices,
+        HardwareComponent::Power,
+        HardwareComponent::Virtualization,
+        HardwareComponent::System,
+    ];
+
+    /// Whether this component mostly reports values that change at runtime
+    pub fn is_dynamic(&self) -> bool {
+        matches!(
+            self,
+            HardwareComponent::Memory
+                | HardwareComponent::Network
+                | HardwareComponent::Battery
+                | HardwareComponent::Thermal
+                | HardwareComponent::Power
+        )
+    }
+}
+
+impl std::fmt::Display for HardwareComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HardwareComponent::CPU => write!(f, "CPU"),
+            HardwareComponent::GPU => write!(f, "GPU"),
+            HardwareComponent::Accelerators => write!(f, "Accelerators"),
+            HardwareComponent::Memory => write!(f, "Memory"),
+            HardwareComponent::Storage => write!(f, "Storage"),
+            HardwareComponent::Network => write!(f, "Network"),
+            HardwareComponent::Battery => write!(f, "Battery"),
+            HardwareComponent::Thermal => write!(f, "Thermal"),
+            HardwareComponent::Devices => write!(f, "Devices"),
+            HardwareComponent::Power => write!(f, "Power"),
+            HardwareComponent::Virtualization => write!(f, "Virtualization"),
+            HardwareComponent::System => write!(f, "System"),
+        }
+    }
+}
+
+/// Time-to-live configuration for [`QueryCache`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// TTL for components whose values rarely change (CPU model, GPU inventory, etc.)
+    pub static_ttl: Duration,
+    /// TTL for components with live readings (memory usage, temperatures, etc.)
+    pub dynamic_ttl: Duration,
+    /// Per-component TTL overrides
+    pub overrides: HashMap<HardwareComponent, Duration>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            static_ttl: Duration::from_secs(300),
+            dynamic_ttl: Duration::from_secs(1),
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Create a cache configuration with default TTLs
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the TTL used for static components
+    pub fn with_static_ttl(mut self, ttl: Duration) -> Self {
+        self.static_ttl = ttl;
+        self
+    }
+
+    /// Set the TTL used for dynamic components
+    pub fn with_dynamic_ttl(mut self, ttl: Duration) -> Self {
+        self.dynamic_ttl = ttl;
+        self
+    }
+
+    /// Override the TTL for a single component
+    pub fn with_component_ttl(mut self, component: HardwareComponent, ttl: Duration) -> Self {
+        self.overrides.insert(component, ttl);
+        self
+    }
+
+    /// Get the effective TTL for a component
+    pub fn ttl_for(&self, component: HardwareComponent) -> Duration {
+        self.overrides.get(&component).copied().unwrap_or(if component.is_dynamic() {
+            self.dynamic_ttl
+        } else {
+            self.static_ttl
+        })
+    }
+}
+
+/// A cached value and the instant it was collected
+#[derive(Debug, Clone)]
+struct Cached<T> {
+    value: T,
+    fetched_at: Instant,
+}
+
+#[derive(Debug, Clone)]
+struct Accelerators {
+    npus: Vec<NPUInfo>,
+    tpus: Vec<TPUInfo>,
+    fpgas: Vec<FPGAInfo>,
+    arm_hardware: Option<ARMHardwareInfo>,
+}
+
+#[derive(Debug, Clone)]
+struct Devices {
+    pci_devices: Vec<PCIDevice>,
+    usb_devices: Vec<USBDevice>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    cpu: Option<Cached<CPUInfo>>,
+    cpu_usage: Option<Cached<Vec<f32>>>,
+    gpus: Option<Cached<Vec<GPUInfo>>>,
+    accelerators: Option<Cached<Accelerators>>,
+    memory: Option<Cached<MemoryInfo>>,
+    storage: Option<Cached<StorageLayout>>,
+    network: Option<Cached<Vec<NetworkInfo>>>,
+    battery: Option<Cached<Option<BatteryInfo>>>,
+    thermal: Option<Cached<ThermalInfo>>,
+    devices: Option<Cached<Devices>>,
+    power: Option<Cached<Option<PowerProfile>>>,
+    virtualization: Option<Cached<VirtualizationInfo>>,
+    system: Option<Cached<SystemInfo>>,
+}
+
+/// Thread-safe cache of hardware query results with per-component TTLs
+///
+/// ```rust
+/// use hardware_query::{CacheConfig, HardwareComponent, QueryCache};
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let cache = QueryCache::new(
+///     CacheConfig::new()
+///         .with_static_ttl(Duration::from_secs(600))
+///         .with_component_ttl(HardwareComponent::Thermal, Duration::from_millis(500)),
+/// );
+///
+/// let first = cache.query()?;   // full detection
+/// let second = cache.query()?;  // static components served from cache
+/// assert_eq!(first.cpu().model_name(), second.cpu().model_name());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct QueryCache {
+    config: CacheConfig,
+    state: Mutex<CacheState>,
+}
+
+impl QueryCache {
+    /// Create a new, empty cache
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Get the cache configuration
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    /// Get hardware information, refreshing only components whose TTL has expired
+    pub fn query(&self) -> Result<HardwareInfo> {
+        self.query_with(&self.config)
+    }
+
+    /// Drop the cached value for a single component
+    pub fn invalidate(&self, component: HardwareComponent) {
+        let mut state = self.lock_state();
+        match component {
+            HardwareComponent::CPU => {
+                state.cpu = None;
+                state.cpu_usage = None;
+            }
+            HardwareComponent::GPU => state.gpus = None,
+            HardwareComponent::Accelerators => state.accelerators = None,
+            HardwareComponent::Memory => state.memory = None,
+            HardwareComponent::Storage => state.storage = None,
+            HardwareComponent::Network => state.network = None,
+            HardwareComponent::Battery => state.battery = None,
+            HardwareComponent::Thermal => state.thermal = None,
+            HardwareComponent::Devices => state.devices = None,
+            HardwareComponent::Power => state.power = None,
+            HardwareComponent::Virtualization => state.virtualization = None,
+            HardwareComponent::System => state.system = None,
+        }
+    }
+
+    /// Drop all cached values
+    pub fn clear(&self) {
+        *self.lock_state() = CacheState::default();
+    }
+
+    pub(crate) fn query_with(&self, config: &CacheConfig) -> Result<HardwareInfo> {
+        let mut state = self.lock_state();
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| HardwareQueryError::unknown(format!("Failed to get timestamp: {e}")))?
+            .as_secs();
+
+        // CPU identity is static, but per-core usage is refreshed on the dynamic TTL
+        let mut cpu = refresh(&mut state.cpu, config.ttl_for(HardwareComponent::CPU), CPUInfo::query)?;
+        cpu.core_usage = refresh(&mut state.cpu_usage, config.dynamic_ttl, || {
+            Ok(CPUInfo::sample_core_usage())
+        })?;
+
+        let accelerators = refresh(
+            &mut state.accelerators,
+            config.ttl_for(HardwareComponent::Accelerators),
+            || {
+                Ok(Accelerators {
+                    npus: NPUInfo::query_all()?,
+                    tpus: TPUInfo::query_all()?,
+                    fpgas: FPGAInfo::detect_fpgas().unwrap_or_default(),
+                    arm_hardware: ARMHardwareInfo::detect().ok().flatten(),
+                })
+            },
+        )?;
+
+        let devices = refresh(&mut state.devices, config.ttl_for(HardwareComponent::Devices), || {
+            Ok(Devices {
+                pci_devices: PCIDevice::query_all()?,
+                usb_devices: USBDevice::query_all()?,
+            })
+        })?;
+
+        let storage = refresh(
+            &mut state.storage,
+            config.ttl_for(HardwareComponent::Storage),
+            StorageLayout::query,
+        )?;
+
+        Ok(HardwareInfo {
+            schema_version: crate::hardware_info::SCHEMA_VERSION,
+            timestamp,
+            cpu,
+            gpus: refresh(&mut state.gpus, config.ttl_for(HardwareComponent::GPU), GPUInfo::query_all)?,
+            npus: accelerators.npus,
+            tpus: accelerators.tpus,
+            arm_hardware: accelerators.arm_hardware,
+            fpgas: accelerators.fpgas,
+            memory: refresh(&mut state.memory, config.ttl_for(HardwareComponent::Memory), MemoryInfo::query)?,
+            storage_devices: storage.devices,
+            physical_disks: storage.disks,
+            volumes: storage.volumes,
+            network_interfaces: refresh(
+                &mut state.network,
+                config.ttl_for(HardwareComponent::Network),
+                NetworkInfo::query_all,
+            )?,
+            battery: refresh(&mut state.battery, config.ttl_for(HardwareComponent::Battery), || {
+                Ok(BatteryInfo::query().ok())
+            })?,
+            thermal: refresh(&mut state.thermal, config.ttl_for(HardwareComponent::Thermal), ThermalInfo::query)?,
+            pci_devices: devices.pci_devices,
+            usb_devices: devices.usb_devices,
+            power_profile: refresh(&mut state.power, config.ttl_for(HardwareComponent::Power), || {
+                Ok(PowerProfile::query().ok())
+            })?,
+            virtualization: refresh(
+                &mut state.virtualization,
+                config.ttl_for(HardwareComponent::Virtualization),
+                VirtualizationInfo::detect,
+            )?,
+            system: refresh(&mut state.system, config.ttl_for(HardwareComponent::System), SystemInfo::query)?,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        })
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        // A panic while refreshing leaves the state consistent (entries are replaced
+        // atomically), so a poisoned lock is safe to reuse
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Process-wide cache used by [`HardwareInfo::query_cached`]
+    pub(crate) fn global() -> &'static QueryCache {
+        static GLOBAL: OnceLock<QueryCache> = OnceLock::new();
+        GLOBAL.get_or_init(QueryCache::default)
+    }
+}
+
+/// Return the cached value if it is younger than `ttl`, otherwise fetch and store a new one
+fn refresh<T: Clone>(
+    slot: &mut Option<Cached<T>>,
+    ttl: Duration,
+    fetch: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    if let Some(cached) = slot {
+        if cached.fetched_at.elapsed() < ttl {
+            return Ok(cached.value.clone());
+        }
+    }
+
+    let value = fetch()?;
+    *slot = Some(Cached {
+        value: value.clone(),
+        fetched_at: Instant::now(),
+    });
+    Ok(value)
+}
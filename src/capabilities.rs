@@ -0,0 +1,457 @@
+//! Privilege pre-flight checks
+//!
+//! Several probes read data that the operating system only hands to
+//! privileged processes: memory module details from SMBIOS (`dmidecode`),
+//! SMART health, CPU model-specific registers, RAPL energy counters and fan
+//! control. Without the privilege the query still succeeds, with less data.
+//! [`Capabilities::check`] reports ahead of time which subsystems will be
+//! degraded and what is missing, so an application can ask the user to
+//! re-run elevated before detection starts.
+//!
+//! ```rust
+//! use hardware_query::Capabilities;
+//!
+//! let capabilities = Capabilities::check();
+//! for access in capabilities.subsystems() {
+//!     if let Some(degraded) = &access.degraded_data {
+//!         println!("{}: {degraded}", access.subsystem);
+//!     }
+//! }
+//! if !capabilities.missing_requirements().is_empty() {
+//!     println!("Re-run with: {:?}", capabilities.missing_requirements());
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// A part of detection that needs more than an unprivileged process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Subsystem {
+    /// System, baseboard and chassis serial numbers and the system UUID
+    SystemIdentity,
+    /// Per-module memory details (size, speed, part and serial numbers)
+    MemoryModules,
+    /// SMART and NVMe health logs
+    SmartHealth,
+    /// CPU model-specific registers (throttle reasons)
+    CpuMsr,
+    /// Measured energy counters (RAPL, Energy Meter, powermetrics)
+    EnergyCounters,
+    /// Setting fan speeds
+    FanControl,
+}
+
+impl Subsystem {
+    /// All subsystems checked by [`Capabilities::check`]
+    pub const ALL: [Subsystem; 6] = [
+        Subsystem::SystemIdentity,
+        Subsystem::MemoryModules,
+        Subsystem::SmartHealth,
+        Subsystem::CpuMsr,
+        Subsystem::EnergyCounters,
+        Subsystem::FanControl,
+    ];
+}
+
+impl std::fmt::Display for Subsystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Subsystem::SystemIdentity => "System identity",
+            Subsystem::MemoryModules => "Memory modules",
+            Subsystem::SmartHealth => "SMART health",
+            Subsystem::CpuMsr => "CPU MSRs",
+            Subsystem::EnergyCounters => "Energy counters",
+            Subsystem::FanControl => "Fan control",
+        };
+        f.write_str(&crate::l10n::localize("Subsystem", text))
+    }
+}
+
+/// How much of a subsystem the current process can query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AccessLevel {
+    /// Everything the hardware exposes will be returned
+    Full,
+    /// Some data will be missing until the requirements are met
+    Degraded,
+    /// The hardware or interface is not present on this machine
+    Unavailable,
+}
+
+impl std::fmt::Display for AccessLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            AccessLevel::Full => "Full",
+            AccessLevel::Degraded => "Degraded",
+            AccessLevel::Unavailable => "Unavailable",
+        };
+        f.write_str(&crate::l10n::localize("AccessLevel", text))
+    }
+}
+
+/// Something the process lacks to query a subsystem fully
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Requirement {
+    /// Run as root (or with the equivalent Linux capability)
+    Root,
+    /// Run elevated as Administrator
+    Administrator,
+    /// Load a kernel module (`msr`)
+    KernelModule(String),
+    /// Install an external tool (`dmidecode`, `smartctl`)
+    Tool(String),
+}
+
+impl std::fmt::Display for Requirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Requirement::Root => write!(f, "root"),
+            Requirement::Administrator => write!(f, "Administrator"),
+            Requirement::KernelModule(module) => write!(f, "kernel module `{module}`"),
+            Requirement::Tool(tool) => write!(f, "`{tool}`"),
+        }
+    }
+}
+
+/// Access to one subsystem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubsystemAccess {
+    /// Subsystem checked
+    pub subsystem: Subsystem,
+    /// How much of it can be queried
+    pub level: AccessLevel,
+    /// What would raise the level to [`AccessLevel::Full`]
+    pub missing: Vec<Requirement>,
+    /// What will be missing or estimated, when not [`AccessLevel::Full`]
+    pub degraded_data: Option<String>,
+}
+
+impl SubsystemAccess {
+    fn full(subsystem: Subsystem) -> Self {
+        Self {
+            subsystem,
+            level: AccessLevel::Full,
+            missing: Vec::new(),
+            degraded_data: None,
+        }
+    }
+
+    fn degraded(subsystem: Subsystem, missing: Vec<Requirement>, degraded_data: &str) -> Self {
+        Self {
+            subsystem,
+            level: AccessLevel::Degraded,
+            missing,
+            degraded_data: Some(degraded_data.to_string()),
+        }
+    }
+
+    fn unavailable(subsystem: Subsystem, missing: Vec<Requirement>, degraded_data: &str) -> Self {
+        Self {
+            subsystem,
+            level: AccessLevel::Unavailable,
+            missing,
+            degraded_data: Some(degraded_data.to_string()),
+        }
+    }
+
+    /// Check if the subsystem can be queried completely
+    pub fn is_full(&self) -> bool {
+        self.level == AccessLevel::Full
+    }
+}
+
+/// What the current process can query, per subsystem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// Whether the process runs as root or elevated Administrator
+    pub elevated: bool,
+    /// Access to each subsystem
+    pub subsystems: Vec<SubsystemAccess>,
+}
+
+impl Capabilities {
+    /// Check the current process's access to every [`Subsystem`]
+    ///
+    /// Only opens files and looks up tools; no hardware is queried.
+    pub fn check() -> Self {
+        let elevated = is_elevated();
+        Self {
+            elevated,
+            subsystems: Subsystem::ALL
+                .iter()
+                .map(|&subsystem| check_subsystem(subsystem, elevated))
+                .collect(),
+        }
+    }
+
+    /// Check if the process runs as root or elevated Administrator
+    pub fn is_elevated(&self) -> bool {
+        self.elevated
+    }
+
+    /// Get access to every subsystem
+    pub fn subsystems(&self) -> &[SubsystemAccess] {
+        &self.subsystems
+    }
+
+    /// Get access to one subsystem
+    pub fn get(&self, subsystem: Subsystem) -> Option<&SubsystemAccess> {
+        self.subsystems.iter().find(|access| access.subsystem == subsystem)
+    }
+
+    /// Subsystems that will return less data than the hardware exposes
+    pub fn degraded(&self) -> Vec<&SubsystemAccess> {
+        self.subsystems
+            .iter()
+            .filter(|access| access.level == AccessLevel::Degraded)
+            .collect()
+    }
+
+    /// Everything missing for full access, without duplicates
+    pub fn missing_requirements(&self) -> Vec<Requirement> {
+        let mut missing: Vec<Requirement> = Vec::new();
+        for requirement in self.subsystems.iter().flat_map(|access| &access.missing) {
+            if !missing.contains(requirement) {
+                missing.push(requirement.clone());
+            }
+        }
+        missing
+    }
+}
+
+/// Whether the current process runs as root
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn is_elevated() -> bool {
+    // SAFETY: geteuid has no preconditions and cannot fail
+    unsafe { libc::geteuid() == 0 }
+}
+
+/// Whether the current process token is elevated
+#[cfg(target_os = "windows")]
+fn is_elevated() -> bool {
+    use ::windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use ::windows::Win32::Security::{GetTokenInformation, TOKEN_ELEVATION, TOKEN_QUERY, TokenElevation};
+    use ::windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+    let mut token = HANDLE::default();
+    // SAFETY: the pseudo handle of the current process is always valid
+    if unsafe { OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) }.is_err() {
+        return false;
+    }
+    let mut elevation = TOKEN_ELEVATION::default();
+    let mut size = 0u32;
+    // SAFETY: the buffer is a TOKEN_ELEVATION of the size passed
+    let queried = unsafe {
+        GetTokenInformation(
+            token,
+            TokenElevation,
+            Some(&mut elevation as *mut TOKEN_ELEVATION as *mut std::ffi::c_void),
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut size,
+        )
+    };
+    // SAFETY: token was opened above and is closed once
+    let _ = unsafe { CloseHandle(token) };
+    queried.is_ok() && elevation.TokenIsElevated != 0
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn is_elevated() -> bool {
+    false
+}
+
+/// Whether an executable is on `PATH` and external commands are allowed
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn on_path(tool: &str) -> bool {
+    crate::probe::external_commands_allowed()
+        && std::env::var_os("PATH").is_some_and(|path| {
+            std::env::split_paths(&path).any(|dir| dir.join(tool).is_file())
+        })
+}
+
+/// Open a file for reading and classify the result
+#[cfg(target_os = "linux")]
+fn readable(path: &str) -> Option<std::io::ErrorKind> {
+    std::fs::File::open(path).err().map(|e| e.kind())
+}
+
+#[cfg(target_os = "linux")]
+fn check_subsystem(subsystem: Subsystem, elevated: bool) -> SubsystemAccess {
+    use std::io::ErrorKind;
+
+    let root = || if elevated { Vec::new() } else { vec![Requirement::Root] };
+
+    match subsystem {
+        Subsystem::SystemIdentity => match readable("/sys/class/dmi/id/product_serial") {
+            None => SubsystemAccess::full(subsystem),
+            Some(ErrorKind::PermissionDenied) => SubsystemAccess::degraded(
+                subsystem,
+                vec![Requirement::Root],
+                "Serial numbers and the system UUID are omitted; vendor and model are reported",
+            ),
+            Some(_) => SubsystemAccess::unavailable(subsystem, Vec::new(), "The firmware provides no DMI tables"),
+        },
+        Subsystem::MemoryModules => {
+            let mut missing = Vec::new();
+            if !on_path("dmidecode") {
+                missing.push(Requirement::Tool("dmidecode".to_string()));
+            }
+            match readable("/sys/firmware/dmi/tables/DMI") {
+                None => {}
+                Some(ErrorKind::PermissionDenied) => missing.push(Requirement::Root),
+                Some(_) => {
+                    return SubsystemAccess::unavailable(
+                        subsystem,
+                        Vec::new(),
+                        "The firmware provides no SMBIOS memory records",
+                    );
+                }
+            }
+            if missing.is_empty() {
+                SubsystemAccess::full(subsystem)
+            } else {
+                SubsystemAccess::degraded(
+                    subsystem,
+                    missing,
+                    "Memory modules are not listed; total size and usage are reported",
+                )
+            }
+        }
+        Subsystem::SmartHealth => {
+            let mut missing = root();
+            if !on_path("smartctl") {
+                missing.push(Requirement::Tool("smartctl".to_string()));
+            }
+            if missing.is_empty() {
+                SubsystemAccess::full(subsystem)
+            } else {
+                SubsystemAccess::degraded(
+                    subsystem,
+                    missing,
+                    "SMART health is missing for SATA/SAS drives, and for NVMe drives without root",
+                )
+            }
+        }
+        Subsystem::CpuMsr => {
+            if !std::path::Path::new("/dev/cpu/0/msr").exists() {
+                return SubsystemAccess::unavailable(
+                    subsystem,
+                    vec![Requirement::KernelModule("msr".to_string())],
+                    "Throttle reasons come from sysfs counters only",
+                );
+            }
+            match readable("/dev/cpu/0/msr") {
+                None => SubsystemAccess::full(subsystem),
+                Some(_) => SubsystemAccess::degraded(
+                    subsystem,
+                    vec![Requirement::Root],
+                    "Power and current limit throttling is not reported; thermal throttling still is",
+                ),
+            }
+        }
+        Subsystem::EnergyCounters => energy_access(elevated),
+        Subsystem::FanControl => {
+            let pwm_channels: Vec<std::path::PathBuf> = crate::ThermalInfo::hwmon_chips()
+                .into_iter()
+                .flat_map(|(_, dir)| {
+                    (1..=8)
+                        .map(move |n| dir.join(format!("pwm{n}")))
+                        .filter(|path| path.exists())
+                })
+                .collect();
+            if pwm_channels.is_empty() {
+                return SubsystemAccess::unavailable(subsystem, Vec::new(), "No controllable fans were found");
+            }
+            let writable = pwm_channels
+                .iter()
+                .any(|path| std::fs::OpenOptions::new().write(true).open(path).is_ok());
+            if writable {
+                SubsystemAccess::full(subsystem)
+            } else {
+                SubsystemAccess::degraded(
+                    subsystem,
+                    vec![Requirement::Root],
+                    "Fan speeds are reported but cannot be changed",
+                )
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn check_subsystem(subsystem: Subsystem, elevated: bool) -> SubsystemAccess {
+    let admin = || if elevated { Vec::new() } else { vec![Requirement::Administrator] };
+
+    match subsystem {
+        // WMI serves identity and memory module details to standard users
+        Subsystem::SystemIdentity | Subsystem::MemoryModules => SubsystemAccess::full(subsystem),
+        Subsystem::SmartHealth if elevated => SubsystemAccess::full(subsystem),
+        Subsystem::SmartHealth => SubsystemAccess::degraded(
+            subsystem,
+            admin(),
+            "SMART failure prediction and NVMe health logs are omitted",
+        ),
+        Subsystem::CpuMsr => SubsystemAccess::unavailable(
+            subsystem,
+            Vec::new(),
+            "MSRs need a kernel driver on Windows; throttle causes come from NVML only",
+        ),
+        Subsystem::EnergyCounters => energy_access(elevated),
+        Subsystem::FanControl if elevated => SubsystemAccess::full(subsystem),
+        Subsystem::FanControl => SubsystemAccess::degraded(
+            subsystem,
+            admin(),
+            "Fan speeds are reported but cannot be changed",
+        ),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn check_subsystem(subsystem: Subsystem, elevated: bool) -> SubsystemAccess {
+    match subsystem {
+        // system_profiler serves identity and memory details to standard users
+        Subsystem::SystemIdentity | Subsystem::MemoryModules => SubsystemAccess::full(subsystem),
+        Subsystem::SmartHealth if on_path("smartctl") => SubsystemAccess::full(subsystem),
+        Subsystem::SmartHealth => SubsystemAccess::degraded(
+            subsystem,
+            vec![Requirement::Tool("smartctl".to_string())],
+            "Only the pass/fail SMART status is reported",
+        ),
+        Subsystem::CpuMsr => SubsystemAccess::unavailable(
+            subsystem,
+            Vec::new(),
+            "macOS does not expose MSRs to user space",
+        ),
+        Subsystem::EnergyCounters if elevated => SubsystemAccess::full(subsystem),
+        Subsystem::EnergyCounters => SubsystemAccess::degraded(
+            subsystem,
+            vec![Requirement::Root],
+            "powermetrics energy readings are unavailable; GPU counters from NVML still work",
+        ),
+        Subsystem::FanControl => SubsystemAccess::unavailable(
+            subsystem,
+            Vec::new(),
+            "Fan control is not supported on macOS",
+        ),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn check_subsystem(subsystem: Subsystem, _elevated: bool) -> SubsystemAccess {
+    SubsystemAccess::unavailable(subsystem, Vec::new(), "Not supported on this platform")
+}
+
+/// Read the platform energy counters once and classify the outcome
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn energy_access(elevated: bool) -> SubsystemAccess {
+    let subsystem = Subsystem::EnergyCounters;
+    match crate::EnergyMeter::platform_counters() {
+        Ok(counters) if !counters.is_empty() => SubsystemAccess::full(subsystem),
+        Err(e) if e.is_permission_denied() && !elevated => SubsystemAccess::degraded(
+            subsystem,
+            vec![if cfg!(target_os = "windows") { Requirement::Administrator } else { Requirement::Root }],
+            "CPU and DRAM energy is not measured; GPU counters from NVML still work",
+        ),
+        _ => SubsystemAccess::unavailable(subsystem, Vec::new(), "The platform exposes no energy counters"),
+    }
+}
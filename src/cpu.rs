@@ -1,6 +1,8 @@
-use crate::{HardwareQueryError, Result};
+use crate::{CPUTopology, CoreCluster, CoreType, HardwareQueryError, Result};
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::fs;
+use std::sync::{Mutex, OnceLock};
 use sysinfo::System;
 
 /// CPU vendor information
@@ -56,6 +58,22 @@ pub enum CPUFeature {
     FSGSBASE,
     RDTSCP,
     F16C,
+    AVX512DQ,
+    AVX512CD,
+    AVX512BW,
+    AVX512VL,
+    AVX512IFMA,
+    AVX512VBMI,
+    AVX512VBMI2,
+    AVX512VNNI,
+    AVX512BITALG,
+    AVX512VPOPCNTDQ,
+    AVX512BF16,
+    AVX512FP16,
+    AVXVNNI,
+    AMX,
+    AMXBF16,
+    AMXINT8,
     Unknown(String),
 }
 
@@ -90,11 +108,290 @@ impl fmt::Display for CPUFeature {
             CPUFeature::FSGSBASE => write!(f, "FSGSBASE"),
             CPUFeature::RDTSCP => write!(f, "RDTSCP"),
             CPUFeature::F16C => write!(f, "F16C"),
+            CPUFeature::AVX512DQ => write!(f, "AVX512DQ"),
+            CPUFeature::AVX512CD => write!(f, "AVX512CD"),
+            CPUFeature::AVX512BW => write!(f, "AVX512BW"),
+            CPUFeature::AVX512VL => write!(f, "AVX512VL"),
+            CPUFeature::AVX512IFMA => write!(f, "AVX512IFMA"),
+            CPUFeature::AVX512VBMI => write!(f, "AVX512VBMI"),
+            CPUFeature::AVX512VBMI2 => write!(f, "AVX512VBMI2"),
+            CPUFeature::AVX512VNNI => write!(f, "AVX512VNNI"),
+            CPUFeature::AVX512BITALG => write!(f, "AVX512BITALG"),
+            CPUFeature::AVX512VPOPCNTDQ => write!(f, "AVX512VPOPCNTDQ"),
+            CPUFeature::AVX512BF16 => write!(f, "AVX512BF16"),
+            CPUFeature::AVX512FP16 => write!(f, "AVX512FP16"),
+            CPUFeature::AVXVNNI => write!(f, "AVXVNNI"),
+            CPUFeature::AMX => write!(f, "AMX"),
+            CPUFeature::AMXBF16 => write!(f, "AMXBF16"),
+            CPUFeature::AMXINT8 => write!(f, "AMXINT8"),
             CPUFeature::Unknown(name) => write!(f, "{name}"),
         }
     }
 }
 
+/// Current CPU frequencies and the frequency scaling policy
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CPUFrequencyInfo {
+    /// Current frequency of each logical CPU in MHz
+    pub core_frequencies_mhz: Vec<u32>,
+    /// Scaling driver (intel_pstate, amd-pstate-epp, acpi-cpufreq, cppc_cpufreq)
+    pub driver: Option<String>,
+    /// Active scaling governor (powersave, performance, schedutil), or the
+    /// active power plan on Windows
+    pub governor: Option<String>,
+    /// Governors offered by the driver
+    pub available_governors: Vec<String>,
+    /// Energy/performance preference hint passed to HWP or CPPC
+    pub energy_performance_preference: Option<String>,
+    /// Turbo/boost frequencies are allowed
+    pub turbo_enabled: Option<bool>,
+    /// Lowest frequency the governor may select in MHz
+    pub scaling_min_mhz: Option<u32>,
+    /// Highest frequency the governor may select in MHz
+    pub scaling_max_mhz: Option<u32>,
+    /// Lowest frequency supported by the hardware in MHz
+    pub hardware_min_mhz: Option<u32>,
+    /// Highest frequency supported by the hardware in MHz
+    pub hardware_max_mhz: Option<u32>,
+}
+
+impl CPUFrequencyInfo {
+    /// Read current frequencies and the scaling policy
+    ///
+    /// Linux reads cpufreq under `/sys/devices/system/cpu`, taking the governor
+    /// and driver from the first CPU and the widest limits across all CPUs
+    /// (hybrid parts have per-cluster limits). Windows derives each core's
+    /// effective frequency from the processor performance counters and reports
+    /// the active power plan as the governor. Elsewhere only the per-core
+    /// frequencies are available.
+    pub fn query() -> Self {
+        #[cfg(target_os = "linux")]
+        let mut info = Self::query_linux(std::path::Path::new("/sys/devices/system/cpu"));
+
+        #[cfg(target_os = "windows")]
+        let mut info = Self::query_windows();
+
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        let mut info = Self::default();
+
+        if info.core_frequencies_mhz.is_empty() {
+            let mut system = System::new();
+            system.refresh_cpu_frequency();
+            info.core_frequencies_mhz = system.cpus().iter().map(|cpu| cpu.frequency() as u32).collect();
+        }
+        info
+    }
+
+    /// Get current per-core frequencies in MHz
+    pub fn core_frequencies_mhz(&self) -> &[u32] {
+        &self.core_frequencies_mhz
+    }
+
+    /// Get average frequency across cores in MHz
+    pub fn average_frequency_mhz(&self) -> Option<f64> {
+        let frequencies: Vec<u32> = self
+            .core_frequencies_mhz
+            .iter()
+            .copied()
+            .filter(|mhz| *mhz > 0)
+            .collect();
+        if frequencies.is_empty() {
+            return None;
+        }
+        Some(frequencies.iter().map(|mhz| *mhz as f64).sum::<f64>() / frequencies.len() as f64)
+    }
+
+    /// Get scaling driver
+    pub fn driver(&self) -> Option<&str> {
+        self.driver.as_deref()
+    }
+
+    /// Get active scaling governor
+    pub fn governor(&self) -> Option<&str> {
+        self.governor.as_deref()
+    }
+
+    /// Get available scaling governors
+    pub fn available_governors(&self) -> &[String] {
+        &self.available_governors
+    }
+
+    /// Get energy/performance preference
+    pub fn energy_performance_preference(&self) -> Option<&str> {
+        self.energy_performance_preference.as_deref()
+    }
+
+    /// Check if turbo/boost is enabled
+    pub fn turbo_enabled(&self) -> Option<bool> {
+        self.turbo_enabled
+    }
+
+    /// Get minimum scaling frequency in MHz
+    pub fn scaling_min_mhz(&self) -> Option<u32> {
+        self.scaling_min_mhz
+    }
+
+    /// Get maximum scaling frequency in MHz
+    pub fn scaling_max_mhz(&self) -> Option<u32> {
+        self.scaling_max_mhz
+    }
+
+    /// Get minimum hardware frequency in MHz
+    pub fn hardware_min_mhz(&self) -> Option<u32> {
+        self.hardware_min_mhz
+    }
+
+    /// Get maximum hardware frequency in MHz
+    pub fn hardware_max_mhz(&self) -> Option<u32> {
+        self.hardware_max_mhz
+    }
+
+    #[cfg(target_os = "linux")]
+    fn query_linux(cpu_root: &std::path::Path) -> Self {
+        use std::fs;
+
+        let read = |path: std::path::PathBuf| {
+            fs::read_to_string(path)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        // cpufreq reports kHz
+        let mhz = |path: std::path::PathBuf| {
+            read(path)
+                .and_then(|khz| khz.parse::<u64>().ok())
+                .map(|khz| (khz / 1000) as u32)
+        };
+
+        let mut cpus: Vec<(u32, std::path::PathBuf)> = fs::read_dir(cpu_root)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| {
+                        let name = entry.file_name().to_string_lossy().into_owned();
+                        let index = name.strip_prefix("cpu")?.parse::<u32>().ok()?;
+                        Some((index, entry.path().join("cpufreq")))
+                    })
+                    .filter(|(_, cpufreq)| cpufreq.is_dir())
+                    .collect()
+            })
+            .unwrap_or_default();
+        cpus.sort_by_key(|(index, _)| *index);
+
+        let mut info = Self::default();
+        for (_, cpufreq) in &cpus {
+            // scaling_cur_freq is the APERF/MPERF average on intel_pstate and amd-pstate
+            let current = mhz(cpufreq.join("scaling_cur_freq")).or_else(|| mhz(cpufreq.join("cpuinfo_cur_freq")));
+            if let Some(current) = current {
+                info.core_frequencies_mhz.push(current);
+            }
+            let widen = |limit: &mut Option<u32>, value: Option<u32>, pick: fn(u32, u32) -> u32| {
+                if let Some(value) = value {
+                    *limit = Some(limit.map_or(value, |limit| pick(limit, value)));
+                }
+            };
+            widen(&mut info.scaling_min_mhz, mhz(cpufreq.join("scaling_min_freq")), u32::min);
+            widen(&mut info.scaling_max_mhz, mhz(cpufreq.join("scaling_max_freq")), u32::max);
+            widen(&mut info.hardware_min_mhz, mhz(cpufreq.join("cpuinfo_min_freq")), u32::min);
+            widen(&mut info.hardware_max_mhz, mhz(cpufreq.join("cpuinfo_max_freq")), u32::max);
+        }
+
+        if let Some((_, first)) = cpus.first() {
+            info.driver = read(first.join("scaling_driver"));
+            info.governor = read(first.join("scaling_governor"));
+            info.available_governors = read(first.join("scaling_available_governors"))
+                .map(|list| list.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_default();
+            info.energy_performance_preference = read(first.join("energy_performance_preference"));
+        }
+
+        // Without cpufreq (most VMs) the kernel still reports a per-CPU clock
+        if info.core_frequencies_mhz.is_empty() {
+            if let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") {
+                info.core_frequencies_mhz = cpuinfo
+                    .lines()
+                    .filter(|line| line.starts_with("cpu MHz"))
+                    .filter_map(|line| line.split(':').nth(1)?.trim().parse::<f32>().ok())
+                    .map(|mhz| mhz as u32)
+                    .collect();
+            }
+        }
+
+        // intel_pstate has its own switch; acpi-cpufreq and amd-pstate use the global
+        // boost file or, on newer kernels, a per-policy one
+        info.turbo_enabled = read(cpu_root.join("intel_pstate/no_turbo"))
+            .map(|no_turbo| no_turbo == "0")
+            .or_else(|| read(cpu_root.join("cpufreq/boost")).map(|boost| boost == "1"))
+            .or_else(|| read(cpu_root.join("cpufreq/policy0/boost")).map(|boost| boost == "1"));
+
+        info
+    }
+
+    #[cfg(target_os = "windows")]
+    fn query_windows() -> Self {
+        use std::collections::HashMap;
+        use wmi::{Variant, WMIConnection};
+
+        let mut info = Self::default();
+        let Ok(com) = crate::probe::com_library() else {
+            return info;
+        };
+        let number = |value: Option<&Variant>| match value {
+            Some(Variant::UI4(v)) => Some(*v as u64),
+            Some(Variant::UI8(v)) => Some(*v),
+            // 64-bit perf counters are returned as strings
+            Some(Variant::String(v)) => v.parse().ok(),
+            _ => None,
+        };
+
+        if let Ok(cimv2) = WMIConnection::new(com) {
+            // Instances are "<group>,<core>" plus "_Total" and "<group>,_Total"
+            let rows: Vec<HashMap<String, Variant>> = cimv2
+                .raw_query(
+                    "SELECT Name, ProcessorFrequency, PercentProcessorPerformance \
+                     FROM Win32_PerfFormattedData_Counters_ProcessorInformation",
+                )
+                .unwrap_or_default();
+            let mut cores: Vec<((u32, u32), u32)> = rows
+                .iter()
+                .filter_map(|row| {
+                    let Some(Variant::String(name)) = row.get("Name") else {
+                        return None;
+                    };
+                    let (group, core) = name.split_once(',')?;
+                    let key = (group.parse().ok()?, core.parse().ok()?);
+                    let base = number(row.get("ProcessorFrequency"))?;
+                    let performance = number(row.get("PercentProcessorPerformance")).unwrap_or(100);
+                    Some((key, (base * performance / 100) as u32))
+                })
+                .collect();
+            cores.sort_by_key(|(key, _)| *key);
+            info.core_frequencies_mhz = cores.into_iter().map(|(_, mhz)| mhz).collect();
+
+            let processors: Vec<HashMap<String, Variant>> = cimv2
+                .raw_query("SELECT MaxClockSpeed FROM Win32_Processor")
+                .unwrap_or_default();
+            info.hardware_max_mhz = processors
+                .iter()
+                .filter_map(|row| number(row.get("MaxClockSpeed")))
+                .max()
+                .map(|mhz| mhz as u32);
+        }
+
+        if let Ok(power) = WMIConnection::with_namespace_path(r"root\cimv2\power", com) {
+            let plans: Vec<HashMap<String, Variant>> = power
+                .raw_query("SELECT ElementName FROM Win32_PowerPlan WHERE IsActive = TRUE")
+                .unwrap_or_default();
+            info.governor = plans.iter().find_map(|plan| match plan.get("ElementName") {
+                Some(Variant::String(name)) => Some(name.clone()),
+                _ => None,
+            });
+        }
+
+        info
+    }
+}
+
 /// CPU information and specifications
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CPUInfo {
@@ -138,6 +435,9 @@ pub struct CPUInfo {
     pub microcode: Option<String>,
     /// CPU vulnerabilities (Spectre, Meltdown, etc.)
     pub vulnerabilities: Vec<String>,
+    /// Socket, NUMA, SMT and cache topology
+    #[serde(default)]
+    pub topology: CPUTopology,
 }
 
 impl CPUInfo {
@@ -178,9 +478,42 @@ impl CPUInfo {
             model: Self::detect_model()?,
             microcode: Self::detect_microcode(),
             vulnerabilities: Self::detect_vulnerabilities()?,
+            topology: CPUTopology::query()
+                .unwrap_or_else(|_| CPUTopology::flat(cpus.len() as u32)),
         })
     }
 
+    /// Placeholder used when CPU detection did not complete
+    ///
+    /// Only the logical core count and architecture are filled in, since both
+    /// are known without touching the operating system's CPU interfaces.
+    pub(crate) fn unknown() -> Self {
+        let logical_cores = num_cpus::get() as u32;
+        Self {
+            vendor: CPUVendor::Unknown("Unknown".to_string()),
+            model_name: "Unknown".to_string(),
+            brand: "Unknown".to_string(),
+            physical_cores: logical_cores,
+            logical_cores,
+            base_frequency: 0,
+            max_frequency: 0,
+            l1_cache_kb: 0,
+            l2_cache_kb: 0,
+            l3_cache_kb: 0,
+            features: Vec::new(),
+            architecture: Self::detect_architecture(),
+            core_usage: Vec::new(),
+            temperature: None,
+            power_consumption: None,
+            stepping: None,
+            family: None,
+            model: None,
+            microcode: None,
+            vulnerabilities: Vec::new(),
+            topology: CPUTopology::flat(logical_cores),
+        }
+    }
+
     /// Get CPU vendor
     pub fn vendor(&self) -> &CPUVendor {
         &self.vendor
@@ -237,10 +570,106 @@ impl CPUInfo {
     }
 
     /// Check if CPU supports a specific feature
+    ///
+    /// Matching ignores case and `_`, `-` and `.` separators, so `"avx512_vnni"`,
+    /// `"AVX512VNNI"` and `"sse4.1"`/`"sse41"` are all accepted.
     pub fn has_feature(&self, feature: &str) -> bool {
-        self.features
+        fn normalize(name: &str) -> String {
+            name.chars()
+                .filter(|c| !matches!(c, '_' | '-' | '.'))
+                .collect::<String>()
+                .to_lowercase()
+        }
+
+        let wanted = normalize(feature);
+        self.features.iter().any(|f| normalize(&f.to_string()) == wanted)
+    }
+
+    /// Single-precision floating point operations per core per clock
+    ///
+    /// SIMD lanes x 2 (multiply and add) x FMA pipes. AMD runs AVX-512 on
+    /// 256-bit units before Zen 5, so it counts as AVX2 there; SVE is
+    /// assumed 256 bits wide (Graviton 3) and Apple cores have four NEON
+    /// FMA pipes.
+    pub fn flops_per_cycle(&self) -> f64 {
+        let is_arm = self.architecture.starts_with("aarch64")
+            || self.architecture.starts_with("arm")
+            || matches!(self.vendor, CPUVendor::ARM | CPUVendor::Apple);
+        if is_arm {
+            return if self.vendor == CPUVendor::Apple || self.has_feature("sve") {
+                32.0
+            } else {
+                // Two 128-bit NEON FMA pipes (Cortex-A7x, Neoverse N1)
+                16.0
+            };
+        }
+
+        let lanes = if self.features.contains(&CPUFeature::AVX512) && self.vendor != CPUVendor::AMD {
+            16.0
+        } else if self.features.contains(&CPUFeature::AVX) || self.features.contains(&CPUFeature::AVX2) {
+            8.0
+        } else if self.features.contains(&CPUFeature::SSE) || self.features.contains(&CPUFeature::SSE2) {
+            4.0
+        } else {
+            1.0
+        };
+        // Two FMA pipes, or one multiply and one add pipe without FMA
+        let pipes = if self.features.contains(&CPUFeature::FMA) { 2.0 } else { 1.0 };
+        lanes * 2.0 * pipes
+    }
+
+    /// Estimate peak single-precision throughput of all cores in GFLOPS
+    ///
+    /// Physical cores x maximum clock x [`flops_per_cycle`](Self::flops_per_cycle).
+    /// Returns `None` when the clock speed is unknown.
+    pub fn estimated_gflops(&self) -> Option<f64> {
+        let mhz = self.max_frequency.max(self.base_frequency);
+        if mhz == 0 {
+            return None;
+        }
+        Some(self.physical_cores.max(1) as f64 * mhz as f64 / 1000.0 * self.flops_per_cycle())
+    }
+
+    /// Estimate peak memory bandwidth available to the CPU in GB/s
+    ///
+    /// Uses the memory speed and channel count, falling back to the chip's
+    /// memory interface for Apple silicon and to the installed modules
+    /// (at most two channels) otherwise.
+    pub fn estimated_memory_bandwidth(&self, memory: &crate::MemoryInfo) -> Option<f64> {
+        if let Some(bandwidth) = memory.bandwidth_gb_s() {
+            return Some(bandwidth as f64);
+        }
+        if self.vendor == CPUVendor::Apple {
+            let chip = if self.brand.is_empty() { &self.model_name } else { &self.brand };
+            if let Some(specs) = crate::GPUSpecsDatabase::global().lookup(chip) {
+                return Some(specs.memory_bandwidth_gbps());
+            }
+        }
+        let speed = memory
+            .modules
             .iter()
-            .any(|f| f.to_string().to_lowercase() == feature.to_lowercase())
+            .map(|module| module.configured_speed_mhz.unwrap_or(module.speed_mhz))
+            .max()
+            .filter(|&speed| speed > 0)?;
+        let channels = if memory.channels > 0 {
+            memory.channels
+        } else {
+            (memory.modules.len() as u32).clamp(1, 2)
+        };
+        // 8 bytes per transfer per 64-bit channel
+        Some(speed as f64 * channels as f64 * 8.0 / 1000.0)
+    }
+
+    /// Measure FLOPS and memory bandwidth and compare them with the estimates
+    ///
+    /// Runs the [`BenchmarkResults`](crate::BenchmarkResults) CPU and memory
+    /// probes for roughly `budget`.
+    pub fn validate_estimates(&self, memory: &crate::MemoryInfo, budget: std::time::Duration) -> crate::EstimateValidation {
+        crate::benchmark::validate_estimates(
+            self.estimated_gflops(),
+            self.estimated_memory_bandwidth(memory),
+            budget,
+        )
     }
 
     /// Get CPU architecture
@@ -253,6 +682,41 @@ impl CPUInfo {
         &self.core_usage
     }
 
+    /// Get socket, NUMA node, SMT sibling and cache sharing topology
+    pub fn topology(&self) -> &CPUTopology {
+        &self.topology
+    }
+
+    /// Get performance and efficiency core clusters, fastest first
+    ///
+    /// Homogeneous CPUs report a single performance cluster.
+    pub fn core_clusters(&self) -> &[CoreCluster] {
+        self.topology.clusters()
+    }
+
+    /// Get number of performance cores (all physical cores on homogeneous CPUs)
+    pub fn performance_cores(&self) -> u32 {
+        self.topology.cores_of_type(CoreType::Performance)
+    }
+
+    /// Get number of efficiency cores
+    pub fn efficiency_cores(&self) -> u32 {
+        self.topology.cores_of_type(CoreType::Efficiency)
+    }
+
+    /// Read current per-core frequencies and the frequency scaling policy
+    ///
+    /// Queried live, so repeated calls follow frequency changes. The hardware
+    /// maximum falls back to the detected maximum frequency when the platform
+    /// exposes no limits.
+    pub fn frequency_scaling(&self) -> CPUFrequencyInfo {
+        let mut info = CPUFrequencyInfo::query();
+        if info.hardware_max_mhz.is_none() && self.max_frequency > 0 {
+            info.hardware_max_mhz = Some(self.max_frequency);
+        }
+        info
+    }
+
     /// Get CPU temperature (if available)
     pub fn temperature(&self) -> Option<f32> {
         self.temperature
@@ -263,6 +727,29 @@ impl CPUInfo {
         self.power_consumption
     }
 
+    /// Sample current per-core usage without re-running full CPU detection
+    ///
+    /// Usage is measured since the previous call, using one process-wide
+    /// sampler. The first call takes two samples a short interval apart.
+    pub(crate) fn sample_core_usage() -> Vec<f32> {
+        static SAMPLER: OnceLock<Mutex<System>> = OnceLock::new();
+
+        let mut primed = true;
+        let sampler = SAMPLER.get_or_init(|| {
+            primed = false;
+            Mutex::new(System::new())
+        });
+        let mut system = sampler.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        // Browsers cannot block; the first wasm32 sample reads zero usage
+        #[cfg(not(target_arch = "wasm32"))]
+        if !primed {
+            system.refresh_cpu_usage();
+            std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        }
+        system.refresh_cpu_usage();
+        system.cpus().iter().map(|cpu| cpu.cpu_usage()).collect()
+    }
+
     fn parse_vendor(brand: &str) -> CPUVendor {
         let brand_lower = brand.to_lowercase();
         if brand_lower.contains("intel") {
@@ -471,7 +958,7 @@ impl CPUInfo {
     }
 
     fn detect_vulnerabilities() -> Result<Vec<String>> {
-        let vulnerabilities = Vec::new();
+        let mut vulnerabilities = Vec::new();
 
         #[cfg(target_os = "linux")]
         {
@@ -497,7 +984,7 @@ impl CPUInfo {
     // Windows-specific implementations
     #[cfg(target_os = "windows")]
     fn detect_physical_cores_windows() -> Result<u32> {
-        match wmi::WMIConnection::new(wmi::COMLibrary::new()?) {
+        match wmi::WMIConnection::new(crate::probe::com_library()?) {
             Ok(wmi_con) => {
                 let results: Vec<std::collections::HashMap<String, wmi::Variant>> = wmi_con
                     .raw_query("SELECT NumberOfCores FROM Win32_Processor")
@@ -522,7 +1009,7 @@ impl CPUInfo {
 
     #[cfg(target_os = "windows")]
     fn detect_max_frequency_windows() -> Result<u32> {
-        match wmi::WMIConnection::new(wmi::COMLibrary::new()?) {
+        match wmi::WMIConnection::new(crate::probe::com_library()?) {
             Ok(wmi_con) => {
                 let results: Vec<std::collections::HashMap<String, wmi::Variant>> = wmi_con
                     .raw_query("SELECT MaxClockSpeed FROM Win32_Processor")
@@ -545,7 +1032,7 @@ impl CPUInfo {
 
     #[cfg(target_os = "windows")]
     fn detect_l1_cache_windows() -> Result<u32> {
-        match wmi::WMIConnection::new(wmi::COMLibrary::new()?) {
+        match wmi::WMIConnection::new(crate::probe::com_library()?) {
             Ok(wmi_con) => {
                 let results: Vec<std::collections::HashMap<String, wmi::Variant>> = wmi_con
                     .raw_query("SELECT MaxCacheSize FROM Win32_CacheMemory WHERE Level = 3")
@@ -578,17 +1065,160 @@ impl CPUInfo {
 
     #[cfg(target_os = "windows")]
     fn detect_features_windows() -> Result<Vec<CPUFeature>> {
-        // Windows feature detection would use CPUID instructions
-        // This is a simplified implementation
-        Ok(vec![
-            CPUFeature::SSE,
-            CPUFeature::SSE2,
-            CPUFeature::SSE3,
-            CPUFeature::SSE41,
-            CPUFeature::SSE42,
-            CPUFeature::AVX,
-            CPUFeature::AVX2,
-        ])
+        #[cfg(target_arch = "x86_64")]
+        {
+            Ok(Self::detect_features_cpuid())
+        }
+        #[cfg(not(target_arch = "x86_64"))]
+        {
+            // CPUID is x86-only; Windows on ARM exposes no comparable feature list here
+            Ok(Vec::new())
+        }
+    }
+
+    /// Detect x86 features directly with CPUID
+    ///
+    /// AVX, AVX-512 and AMX are only reported when the OS has enabled the
+    /// corresponding register state in XCR0, matching what software can actually use.
+    #[cfg(all(target_arch = "x86_64", any(target_os = "windows", target_os = "macos")))]
+    #[allow(unused_unsafe)] // CPUID intrinsics are safe on newer toolchains
+    fn detect_features_cpuid() -> Vec<CPUFeature> {
+        use std::arch::x86_64::{__cpuid, __cpuid_count, _xgetbv};
+
+        #[target_feature(enable = "xsave")]
+        unsafe fn read_xcr0() -> u64 {
+            unsafe { _xgetbv(0) }
+        }
+
+        let bit = |reg: u32, n: u32| reg & (1 << n) != 0;
+        let mut features = Vec::new();
+
+        let max_leaf = unsafe { __cpuid(0) }.eax;
+        let leaf1 = unsafe { __cpuid(1) };
+
+        // SAFETY: XGETBV is only executed when CPUID reports OSXSAVE
+        let xcr0 = if bit(leaf1.ecx, 27) { unsafe { read_xcr0() } } else { 0 };
+        let os_avx = xcr0 & 0b110 == 0b110;
+        let os_avx512 = os_avx && xcr0 & 0b1110_0000 == 0b1110_0000;
+        let os_amx = xcr0 & (0b11 << 17) == (0b11 << 17);
+
+        let leaf1_checks = [
+            (leaf1.edx, 25, CPUFeature::SSE),
+            (leaf1.edx, 26, CPUFeature::SSE2),
+            (leaf1.ecx, 0, CPUFeature::SSE3),
+            (leaf1.ecx, 19, CPUFeature::SSE41),
+            (leaf1.ecx, 20, CPUFeature::SSE42),
+            (leaf1.ecx, 22, CPUFeature::MOVBE),
+            (leaf1.ecx, 23, CPUFeature::POPCNT),
+            (leaf1.ecx, 25, CPUFeature::AES),
+            (leaf1.ecx, 26, CPUFeature::XSAVE),
+            (leaf1.ecx, 30, CPUFeature::RDRAND),
+        ];
+        for (reg, n, feature) in leaf1_checks {
+            if bit(reg, n) {
+                features.push(feature);
+            }
+        }
+        if os_avx {
+            for (n, feature) in [(28, CPUFeature::AVX), (12, CPUFeature::FMA), (29, CPUFeature::F16C)] {
+                if bit(leaf1.ecx, n) {
+                    features.push(feature);
+                }
+            }
+        }
+
+        if max_leaf >= 7 {
+            let leaf7 = unsafe { __cpuid_count(7, 0) };
+
+            for (reg, n, feature) in [
+                (leaf7.ebx, 0, CPUFeature::FSGSBASE),
+                (leaf7.ebx, 3, CPUFeature::BMI1),
+                (leaf7.ebx, 8, CPUFeature::BMI2),
+                (leaf7.ebx, 18, CPUFeature::RDSEED),
+                (leaf7.ebx, 23, CPUFeature::CLFLUSHOPT),
+                (leaf7.ebx, 24, CPUFeature::CLWB),
+                (leaf7.ebx, 29, CPUFeature::SHA),
+                (leaf7.ecx, 0, CPUFeature::PREFETCHWT1),
+            ] {
+                if bit(reg, n) {
+                    features.push(feature);
+                }
+            }
+
+            if os_avx && bit(leaf7.ebx, 5) {
+                features.push(CPUFeature::AVX2);
+            }
+
+            if os_avx512 {
+                for (reg, n, feature) in [
+                    (leaf7.ebx, 16, CPUFeature::AVX512),
+                    (leaf7.ebx, 17, CPUFeature::AVX512DQ),
+                    (leaf7.ebx, 21, CPUFeature::AVX512IFMA),
+                    (leaf7.ebx, 28, CPUFeature::AVX512CD),
+                    (leaf7.ebx, 30, CPUFeature::AVX512BW),
+                    (leaf7.ebx, 31, CPUFeature::AVX512VL),
+                    (leaf7.ecx, 1, CPUFeature::AVX512VBMI),
+                    (leaf7.ecx, 6, CPUFeature::AVX512VBMI2),
+                    (leaf7.ecx, 11, CPUFeature::AVX512VNNI),
+                    (leaf7.ecx, 12, CPUFeature::AVX512BITALG),
+                    (leaf7.ecx, 14, CPUFeature::AVX512VPOPCNTDQ),
+                    (leaf7.edx, 23, CPUFeature::AVX512FP16),
+                ] {
+                    if bit(reg, n) {
+                        features.push(feature);
+                    }
+                }
+            }
+
+            if os_amx {
+                for (n, feature) in [
+                    (24, CPUFeature::AMX),
+                    (22, CPUFeature::AMXBF16),
+                    (25, CPUFeature::AMXINT8),
+                ] {
+                    if bit(leaf7.edx, n) {
+                        features.push(feature);
+                    }
+                }
+            }
+
+            // Sub-leaf 1 carries the newer VNNI/BF16 extensions
+            if leaf7.eax >= 1 {
+                let leaf7_1 = unsafe { __cpuid_count(7, 1) };
+                if os_avx && bit(leaf7_1.eax, 4) {
+                    features.push(CPUFeature::AVXVNNI);
+                }
+                if os_avx512 && bit(leaf7_1.eax, 5) {
+                    features.push(CPUFeature::AVX512BF16);
+                }
+            }
+        }
+
+        if max_leaf >= 0xD && bit(leaf1.ecx, 26) {
+            let leaf_d = unsafe { __cpuid_count(0xD, 1) };
+            for (n, feature) in [
+                (0, CPUFeature::XSAVEOPT),
+                (1, CPUFeature::XSAVEC),
+                (3, CPUFeature::XSAVES),
+            ] {
+                if bit(leaf_d.eax, n) {
+                    features.push(feature);
+                }
+            }
+        }
+
+        let max_ext_leaf = unsafe { __cpuid(0x8000_0000) }.eax;
+        if max_ext_leaf >= 0x8000_0001 {
+            let ext = unsafe { __cpuid(0x8000_0001) };
+            if bit(ext.ecx, 5) {
+                features.push(CPUFeature::LZCNT);
+            }
+            if bit(ext.edx, 27) {
+                features.push(CPUFeature::RDTSCP);
+            }
+        }
+
+        features
     }
 
     // Linux-specific implementations
@@ -702,7 +1332,34 @@ impl CPUInfo {
                                 "rdrand" => features.push(CPUFeature::RDRAND),
                                 "rdseed" => features.push(CPUFeature::RDSEED),
                                 "popcnt" => features.push(CPUFeature::POPCNT),
-                                "lzcnt" => features.push(CPUFeature::LZCNT),
+                                "lzcnt" | "abm" => features.push(CPUFeature::LZCNT),
+                                "movbe" => features.push(CPUFeature::MOVBE),
+                                "f16c" => features.push(CPUFeature::F16C),
+                                "xsave" => features.push(CPUFeature::XSAVE),
+                                "xsaveopt" => features.push(CPUFeature::XSAVEOPT),
+                                "xsavec" => features.push(CPUFeature::XSAVEC),
+                                "xsaves" => features.push(CPUFeature::XSAVES),
+                                "fsgsbase" => features.push(CPUFeature::FSGSBASE),
+                                "rdtscp" => features.push(CPUFeature::RDTSCP),
+                                "clflushopt" => features.push(CPUFeature::CLFLUSHOPT),
+                                "clwb" => features.push(CPUFeature::CLWB),
+                                "prefetchwt1" => features.push(CPUFeature::PREFETCHWT1),
+                                "avx512dq" => features.push(CPUFeature::AVX512DQ),
+                                "avx512cd" => features.push(CPUFeature::AVX512CD),
+                                "avx512bw" => features.push(CPUFeature::AVX512BW),
+                                "avx512vl" => features.push(CPUFeature::AVX512VL),
+                                "avx512ifma" => features.push(CPUFeature::AVX512IFMA),
+                                "avx512vbmi" => features.push(CPUFeature::AVX512VBMI),
+                                "avx512_vbmi2" => features.push(CPUFeature::AVX512VBMI2),
+                                "avx512_vnni" => features.push(CPUFeature::AVX512VNNI),
+                                "avx512_bitalg" => features.push(CPUFeature::AVX512BITALG),
+                                "avx512_vpopcntdq" => features.push(CPUFeature::AVX512VPOPCNTDQ),
+                                "avx512_bf16" => features.push(CPUFeature::AVX512BF16),
+                                "avx512_fp16" => features.push(CPUFeature::AVX512FP16),
+                                "avx_vnni" => features.push(CPUFeature::AVXVNNI),
+                                "amx_tile" => features.push(CPUFeature::AMX),
+                                "amx_bf16" => features.push(CPUFeature::AMXBF16),
+                                "amx_int8" => features.push(CPUFeature::AMXINT8),
                                 _ => {}
                             }
                         }
@@ -718,7 +1375,7 @@ impl CPUInfo {
     // macOS-specific implementations
     #[cfg(target_os = "macos")]
     fn detect_physical_cores_macos() -> Result<u32> {
-        use std::process::Command;
+        use crate::probe::Command;
 
         let output = Command::new("sysctl")
             .arg("-n")
@@ -740,7 +1397,7 @@ impl CPUInfo {
 
     #[cfg(target_os = "macos")]
     fn detect_max_frequency_macos() -> Result<u32> {
-        use std::process::Command;
+        use crate::probe::Command;
 
         let output = Command::new("sysctl")
             .arg("-n")
@@ -762,7 +1419,7 @@ impl CPUInfo {
 
     #[cfg(target_os = "macos")]
     fn detect_l1_cache_macos() -> Result<u32> {
-        use std::process::Command;
+        use crate::probe::Command;
 
         let output = Command::new("sysctl")
             .arg("-n")
@@ -784,7 +1441,7 @@ impl CPUInfo {
 
     #[cfg(target_os = "macos")]
     fn detect_l2_cache_macos() -> Result<u32> {
-        use std::process::Command;
+        use crate::probe::Command;
 
         let output = Command::new("sysctl")
             .arg("-n")
@@ -806,7 +1463,7 @@ impl CPUInfo {
 
     #[cfg(target_os = "macos")]
     fn detect_l3_cache_macos() -> Result<u32> {
-        use std::process::Command;
+        use crate::probe::Command;
 
         let output = Command::new("sysctl")
             .arg("-n")
@@ -828,7 +1485,20 @@ impl CPUInfo {
 
     #[cfg(target_os = "macos")]
     fn detect_features_macos() -> Result<Vec<CPUFeature>> {
-        use std::process::Command;
+        // Intel Macs (and Rosetta) can use CPUID directly
+        #[cfg(target_arch = "x86_64")]
+        {
+            Ok(Self::detect_features_cpuid())
+        }
+        #[cfg(not(target_arch = "x86_64"))]
+        {
+            Self::detect_features_macos_sysctl()
+        }
+    }
+
+    #[cfg(all(target_os = "macos", not(target_arch = "x86_64")))]
+    fn detect_features_macos_sysctl() -> Result<Vec<CPUFeature>> {
+        use crate::probe::Command;
 
         let mut features = Vec::new();
 
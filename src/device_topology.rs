@@ -0,0 +1,284 @@
+//! PCI device placement: NUMA nodes, IOMMU groups, PCIe links and interrupts
+//!
+//! Low-latency networking and storage need the NIC or NVMe drive, its
+//! interrupts and the threads serving it on the same NUMA node. This module
+//! maps every PCI device to the node and PCIe root port it hangs off, its
+//! IOMMU group, negotiated link and the CPUs its interrupts are routed to.
+//! The information comes from sysfs and `/proc/irq` on Linux; elsewhere only
+//! the device list itself is filled in.
+//!
+//! ```rust
+//! use hardware_query::HardwareInfo;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let hw_info = HardwareInfo::query()?;
+//! for device in hw_info.device_topology().network_devices() {
+//!     println!(
+//!         "{} ({}) on NUMA node {:?}, {} remote interrupts",
+//!         device.bus_location,
+//!         device.interfaces.join(", "),
+//!         device.numa_node,
+//!         device.remote_interrupts().len()
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{PCIDevice, PCIeLink};
+use serde::{Deserialize, Serialize};
+
+/// An interrupt line of a PCI device and where it is routed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceInterrupt {
+    /// IRQ number
+    pub irq: u32,
+    /// Logical CPUs the interrupt is delivered to (empty if unknown)
+    pub affinity: Vec<u32>,
+}
+
+/// Placement of a single PCI device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceNode {
+    /// PCI bus address (domain:bus:device.function)
+    pub bus_location: String,
+    /// PCI device ID (vendor:device)
+    pub device_id: String,
+    /// PCI device name
+    pub device_name: String,
+    /// PCI device class
+    pub device_class: String,
+    /// Driver name (if loaded)
+    pub driver: Option<String>,
+    /// NUMA node the device is attached to (if known)
+    pub numa_node: Option<u32>,
+    /// Logical CPUs local to the device
+    pub local_cpus: Vec<u32>,
+    /// IOMMU group (if the IOMMU is enabled)
+    pub iommu_group: Option<u32>,
+    /// PCIe link state (if known)
+    pub pcie: Option<PCIeLink>,
+    /// Root port the device sits below (if it is not itself on the root bus)
+    pub root_port: Option<String>,
+    /// Bridges between the root port and the device, root port first
+    pub upstream_bridges: Vec<String>,
+    /// Legacy and MSI/MSI-X interrupts
+    pub interrupts: Vec<DeviceInterrupt>,
+    /// Network interfaces and NVMe controllers bound to the device
+    pub interfaces: Vec<String>,
+    /// Whether this is a network controller
+    pub is_network: bool,
+    /// Whether this is a storage controller
+    pub is_storage: bool,
+}
+
+impl DeviceNode {
+    /// Get interrupts routed to at least one CPU outside the device's NUMA node
+    pub fn remote_interrupts(&self) -> Vec<&DeviceInterrupt> {
+        if self.local_cpus.is_empty() || self.numa_node.is_none() {
+            return Vec::new();
+        }
+        self.interrupts
+            .iter()
+            .filter(|interrupt| interrupt.affinity.iter().any(|cpu| !self.local_cpus.contains(cpu)))
+            .collect()
+    }
+}
+
+/// Where the system's PCI devices are attached
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeviceTopology {
+    /// One entry per PCI device, in the same order as the device list it was built from
+    pub devices: Vec<DeviceNode>,
+}
+
+impl DeviceTopology {
+    /// Query placement information for `devices`
+    ///
+    /// Fields that cannot be determined are left empty.
+    pub fn query(devices: &[PCIDevice]) -> Self {
+        #[allow(unused_mut)]
+        let mut nodes: Vec<DeviceNode> = devices
+            .iter()
+            .map(|device| DeviceNode {
+                bus_location: device.bus_location.clone(),
+                device_id: device.device_id.clone(),
+                device_name: device.device_name.clone(),
+                device_class: device.device_class.clone(),
+                driver: device.driver.clone(),
+                numa_node: None,
+                local_cpus: Vec::new(),
+                iommu_group: device.iommu_group,
+                pcie: None,
+                root_port: None,
+                upstream_bridges: Vec::new(),
+                interrupts: device.irq.map(|irq| DeviceInterrupt { irq, affinity: Vec::new() }).into_iter().collect(),
+                interfaces: Vec::new(),
+                is_network: device.is_network_device() || device.base_class() == Some(0x02),
+                is_storage: device.is_storage_device() || device.base_class() == Some(0x01),
+            })
+            .collect();
+
+        #[cfg(target_os = "linux")]
+        for node in &mut nodes {
+            linux::fill_node(node);
+        }
+
+        Self { devices: nodes }
+    }
+
+    /// Get per-device placement information
+    pub fn devices(&self) -> &[DeviceNode] {
+        &self.devices
+    }
+
+    /// Get a device by PCI bus address
+    pub fn device(&self, bus_location: &str) -> Option<&DeviceNode> {
+        self.devices
+            .iter()
+            .find(|device| crate::gpu::same_pci_address(&device.bus_location, bus_location))
+    }
+
+    /// Get the device a network interface or NVMe controller (`eth0`, `nvme0`) is bound to
+    pub fn device_for_interface(&self, interface: &str) -> Option<&DeviceNode> {
+        self.devices
+            .iter()
+            .find(|device| device.interfaces.iter().any(|name| name == interface))
+    }
+
+    /// Get network controllers
+    pub fn network_devices(&self) -> Vec<&DeviceNode> {
+        self.devices.iter().filter(|device| device.is_network).collect()
+    }
+
+    /// Get storage controllers
+    pub fn storage_devices(&self) -> Vec<&DeviceNode> {
+        self.devices.iter().filter(|device| device.is_storage).collect()
+    }
+
+    /// Get devices attached to a NUMA node
+    pub fn devices_on_node(&self, numa_node: u32) -> Vec<&DeviceNode> {
+        self.devices
+            .iter()
+            .filter(|device| device.numa_node == Some(numa_node))
+            .collect()
+    }
+
+    /// Get devices below a PCIe root port
+    pub fn devices_below(&self, root_port: &str) -> Vec<&DeviceNode> {
+        self.devices
+            .iter()
+            .filter(|device| device.root_port.as_deref() == Some(root_port))
+            .collect()
+    }
+
+    /// Get IOMMU groups with the devices in each, ordered by group
+    pub fn iommu_groups(&self) -> Vec<(u32, Vec<&DeviceNode>)> {
+        let mut groups: Vec<(u32, Vec<&DeviceNode>)> = Vec::new();
+        for device in &self.devices {
+            let Some(group) = device.iommu_group else {
+                continue;
+            };
+            match groups.iter_mut().find(|(id, _)| *id == group) {
+                Some((_, members)) => members.push(device),
+                None => groups.push((group, vec![device])),
+            }
+        }
+        groups.sort_by_key(|(id, _)| *id);
+        groups
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{DeviceInterrupt, DeviceNode};
+    use crate::topology::parse_cpu_list;
+    use crate::PCIeLink;
+    use std::fs;
+    use std::path::Path;
+
+    /// Whether a sysfs path component is a PCI address (`0000:00:1c.0`)
+    fn is_pci_address(name: &str) -> bool {
+        let bytes = name.as_bytes();
+        bytes.len() == 12 && bytes[4] == b':' && bytes[7] == b':' && bytes[10] == b'.'
+    }
+
+    fn names_in(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn irq_affinity(irq: u32) -> Vec<u32> {
+        let dir = Path::new("/proc/irq").join(irq.to_string());
+        ["effective_affinity_list", "smp_affinity_list"]
+            .iter()
+            .find_map(|name| fs::read_to_string(dir.join(name)).ok())
+            .map(|list| parse_cpu_list(&list))
+            .unwrap_or_default()
+    }
+
+    pub(super) fn fill_node(node: &mut DeviceNode) {
+        let device = Path::new("/sys/bus/pci/devices").join(&node.bus_location);
+        if !device.exists() {
+            return;
+        }
+        let read = |name: &str| {
+            fs::read_to_string(device.join(name))
+                .ok()
+                .map(|value| value.trim().to_string())
+        };
+
+        node.numa_node = read("numa_node")
+            .and_then(|value| value.parse::<i32>().ok())
+            .and_then(|node| u32::try_from(node).ok());
+        node.local_cpus = read("local_cpulist").map(|list| parse_cpu_list(&list)).unwrap_or_default();
+        node.pcie = PCIeLink::from_sysfs(&device);
+
+        // /sys/devices/pci0000:00/0000:00:1c.0/0000:03:00.0: every PCI address
+        // before the device itself is an upstream bridge
+        if let Ok(path) = fs::canonicalize(&device) {
+            let chain: Vec<String> = path
+                .components()
+                .filter_map(|component| component.as_os_str().to_str())
+                .filter(|name| is_pci_address(name))
+                .map(str::to_string)
+                .collect();
+            if let Some((_, bridges)) = chain.split_last() {
+                node.root_port = bridges.first().cloned();
+                node.upstream_bridges = bridges.to_vec();
+            }
+        }
+
+        let mut irqs: Vec<u32> = names_in(&device.join("msi_irqs"))
+            .iter()
+            .filter_map(|irq| irq.parse().ok())
+            .collect();
+        irqs.extend(node.interrupts.iter().map(|interrupt| interrupt.irq));
+        irqs.sort_unstable();
+        irqs.dedup();
+        node.interrupts = irqs
+            .into_iter()
+            .map(|irq| DeviceInterrupt { irq, affinity: irq_affinity(irq) })
+            .collect();
+
+        // virtio devices bind their net/block children one level down
+        let mut parents = vec![device.clone()];
+        parents.extend(
+            names_in(&device)
+                .into_iter()
+                .filter(|name| name.starts_with("virtio"))
+                .map(|name| device.join(name)),
+        );
+        node.interfaces = parents
+            .iter()
+            .flat_map(|parent| ["net", "nvme", "block"].map(|class| names_in(&parent.join(class))))
+            .flatten()
+            .collect();
+    }
+}
@@ -0,0 +1,433 @@
+//! Configuration drift between two hardware snapshots
+//!
+//! [`HardwareInfo::diff`](crate::HardwareInfo::diff) compares the parts of a
+//! snapshot that describe how a machine is built and configured: devices,
+//! capacities, firmware and driver versions. Live readings (usage,
+//! temperatures, free space, traffic counters) are ignored, so two snapshots
+//! of an unchanged machine produce an empty diff.
+
+use crate::{HardwareComponent, HardwareInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What happened to an item between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    /// Present only in the newer snapshot
+    Added,
+    /// Present only in the older snapshot
+    Removed,
+    /// Present in both with a different value
+    Modified,
+}
+
+/// One difference between two snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardwareChange {
+    /// Component group the item belongs to
+    pub component: HardwareComponent,
+    /// Added, removed or modified
+    pub kind: ChangeKind,
+    /// The device or setting (e.g. "NVIDIA GeForce RTX 4090", "BIOS")
+    pub item: String,
+    /// Changed property, for modifications
+    pub field: Option<String>,
+    /// Value in the older snapshot
+    pub before: Option<String>,
+    /// Value in the newer snapshot
+    pub after: Option<String>,
+}
+
+impl std::fmt::Display for HardwareChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = |v: &Option<String>| v.clone().unwrap_or_else(|| "none".to_string());
+        match self.kind {
+            ChangeKind::Added => write!(f, "+ {}: {}", self.component, self.item),
+            ChangeKind::Removed => write!(f, "- {}: {}", self.component, self.item),
+            ChangeKind::Modified => write!(
+                f,
+                "~ {}: {} {}: {} -> {}",
+                self.component,
+                self.item,
+                self.field.as_deref().unwrap_or("value"),
+                value(&self.before),
+                value(&self.after)
+            ),
+        }
+    }
+}
+
+/// Differences between two [`HardwareInfo`] snapshots
+///
+/// The `Display` implementation renders a human-readable change report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardwareDiff {
+    /// Timestamp of the older snapshot
+    pub before_timestamp: u64,
+    /// Timestamp of the newer snapshot
+    pub after_timestamp: u64,
+    /// Changes, grouped by component
+    pub changes: Vec<HardwareChange>,
+}
+
+impl HardwareDiff {
+    /// Compare `before` with `after`
+    pub fn between(before: &HardwareInfo, after: &HardwareInfo) -> Self {
+        let mut diff = Differ::default();
+        diff.cpu(before, after);
+        diff.gpus(before, after);
+        diff.accelerators(before, after);
+        diff.memory(before, after);
+        diff.storage(before, after);
+        diff.network(before, after);
+        diff.battery(before, after);
+        diff.devices(before, after);
+        diff.system(before, after);
+
+        Self {
+            before_timestamp: before.timestamp,
+            after_timestamp: after.timestamp,
+            changes: diff.changes,
+        }
+    }
+
+    /// Check if the snapshots describe the same configuration
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Get all changes
+    pub fn changes(&self) -> &[HardwareChange] {
+        &self.changes
+    }
+
+    /// Changes of one kind
+    pub fn of_kind(&self, kind: ChangeKind) -> impl Iterator<Item = &HardwareChange> {
+        self.changes.iter().filter(move |change| change.kind == kind)
+    }
+
+    /// Changes in one component group
+    pub fn for_component(&self, component: HardwareComponent) -> impl Iterator<Item = &HardwareChange> {
+        self.changes.iter().filter(move |change| change.component == component)
+    }
+}
+
+impl std::fmt::Display for HardwareDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "No hardware changes");
+        }
+        writeln!(f, "{} hardware change(s):", self.changes.len())?;
+        for change in &self.changes {
+            writeln!(f, "  {change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Properties compared for one item
+type Fields = Vec<(&'static str, Option<String>)>;
+
+#[derive(Default)]
+struct Differ {
+    changes: Vec<HardwareChange>,
+}
+
+impl Differ {
+    fn push(&mut self, component: HardwareComponent, kind: ChangeKind, item: &str) {
+        self.changes.push(HardwareChange {
+            component,
+            kind,
+            item: item.to_string(),
+            field: None,
+            before: None,
+            after: None,
+        });
+    }
+
+    /// Record every field whose value differs
+    fn fields(&mut self, component: HardwareComponent, item: &str, before: Fields, after: Fields) {
+        for ((field, old), (_, new)) in before.into_iter().zip(after) {
+            if old != new {
+                self.changes.push(HardwareChange {
+                    component,
+                    kind: ChangeKind::Modified,
+                    item: item.to_string(),
+                    field: Some(field.to_string()),
+                    before: old,
+                    after: new,
+                });
+            }
+        }
+    }
+
+    /// Match items by key and record additions, removals and field changes
+    ///
+    /// Items sharing a key (two identical GPUs without bus IDs) are paired
+    /// in order.
+    fn list<T>(
+        &mut self,
+        component: HardwareComponent,
+        before: &[T],
+        after: &[T],
+        key: impl Fn(&T) -> String,
+        label: impl Fn(&T) -> String,
+        fields: impl Fn(&T) -> Fields,
+    ) {
+        let keyed = |items: &[T]| -> Vec<(String, usize)> {
+            let mut seen: HashMap<String, usize> = HashMap::new();
+            items
+                .iter()
+                .map(|item| {
+                    let key = key(item);
+                    let occurrence = seen.entry(key.clone()).or_insert(0);
+                    *occurrence += 1;
+                    (key, *occurrence)
+                })
+                .collect()
+        };
+        let (old_keys, new_keys) = (keyed(before), keyed(after));
+
+        for (item, id) in before.iter().zip(&old_keys) {
+            match new_keys.iter().position(|k| k == id) {
+                Some(index) => {
+                    let label = label(&after[index]);
+                    self.fields(component, &label, fields(item), fields(&after[index]));
+                }
+                None => self.push(component, ChangeKind::Removed, &label(item)),
+            }
+        }
+        for (item, id) in after.iter().zip(&new_keys) {
+            if !old_keys.contains(id) {
+                self.push(component, ChangeKind::Added, &label(item));
+            }
+        }
+    }
+
+    fn cpu(&mut self, before: &HardwareInfo, after: &HardwareInfo) {
+        let fields = |info: &HardwareInfo| -> Fields {
+            let cpu = &info.cpu;
+            vec![
+                ("model", Some(cpu.brand.clone())),
+                ("physical_cores", Some(cpu.physical_cores.to_string())),
+                ("logical_cores", Some(cpu.logical_cores.to_string())),
+                ("microcode", cpu.microcode.clone()),
+            ]
+        };
+        self.fields(HardwareComponent::CPU, "CPU", fields(before), fields(after));
+    }
+
+    fn gpus(&mut self, before: &HardwareInfo, after: &HardwareInfo) {
+        self.list(
+            HardwareComponent::GPU,
+            &before.gpus,
+            &after.gpus,
+            |gpu| gpu.pci_bus_id.clone().unwrap_or_else(|| gpu.model_name.clone()),
+            |gpu| gpu.model_name.clone(),
+            |gpu| {
+                vec![
+                    ("model", Some(gpu.model_name.clone())),
+                    ("memory", Some(format!("{} MB", gpu.memory_mb))),
+                    ("driver_version", gpu.driver_version.clone()),
+                    ("vbios_version", gpu.vbios_version.clone()),
+                ]
+            },
+        );
+    }
+
+    fn accelerators(&mut self, before: &HardwareInfo, after: &HardwareInfo) {
+        let component = HardwareComponent::Accelerators;
+        self.list(
+            component,
+            &before.npus,
+            &after.npus,
+            |npu| npu.pci_device_id.clone().unwrap_or_else(|| npu.model_name.clone()),
+            |npu| npu.model_name.clone(),
+            |npu| {
+                vec![
+                    ("driver_version", npu.driver_version.clone()),
+                    ("firmware_version", npu.firmware_version.clone()),
+                ]
+            },
+        );
+        self.list(
+            component,
+            &before.tpus,
+            &after.tpus,
+            |tpu| tpu.device_id.clone().unwrap_or_else(|| tpu.model_name.clone()),
+            |tpu| tpu.model_name.clone(),
+            |tpu| {
+                vec![
+                    ("driver_version", tpu.driver_version.clone()),
+                    ("firmware_version", tpu.firmware_version.clone()),
+                ]
+            },
+        );
+        self.list(
+            component,
+            &before.fpgas,
+            &after.fpgas,
+            |fpga| fpga.device_id.clone().unwrap_or_else(|| fpga.model.clone()),
+            |fpga| fpga.model.clone(),
+            |fpga| {
+                vec![
+                    ("driver_version", fpga.driver_version.clone()),
+                    ("configuration", fpga.current_config.clone()),
+                ]
+            },
+        );
+    }
+
+    fn memory(&mut self, before: &HardwareInfo, after: &HardwareInfo) {
+        let component = HardwareComponent::Memory;
+        self.fields(
+            component,
+            "Memory",
+            vec![("total", Some(format!("{} MB", before.memory.total_mb)))],
+            vec![("total", Some(format!("{} MB", after.memory.total_mb)))],
+        );
+        self.list(
+            component,
+            &before.memory.modules,
+            &after.memory.modules,
+            |module| module.slot.clone().unwrap_or_default(),
+            |module| match &module.slot {
+                Some(slot) => format!("{} MB module in {slot}", module.size_mb),
+                None => format!("{} MB module", module.size_mb),
+            },
+            |module| {
+                vec![
+                    ("size", Some(format!("{} MB", module.size_mb))),
+                    ("speed", Some(format!("{} MHz", module.speed_mhz))),
+                    ("part_number", module.part_number.clone()),
+                    ("serial_number", module.serial_number.clone()),
+                ]
+            },
+        );
+    }
+
+    fn storage(&mut self, before: &HardwareInfo, after: &HardwareInfo) {
+        self.list(
+            HardwareComponent::Storage,
+            &before.physical_disks,
+            &after.physical_disks,
+            |disk| disk.serial_number.clone().unwrap_or_else(|| disk.device.clone()),
+            |disk| format!("{} ({})", disk.model, disk.device),
+            |disk| {
+                vec![
+                    ("capacity", Some(format!("{} bytes", disk.capacity_bytes))),
+                    ("firmware_version", disk.firmware_version.clone()),
+                ]
+            },
+        );
+    }
+
+    fn network(&mut self, before: &HardwareInfo, after: &HardwareInfo) {
+        self.list(
+            HardwareComponent::Network,
+            &before.network_interfaces,
+            &after.network_interfaces,
+            |interface| {
+                let mac = interface.mac_address.trim();
+                if mac.is_empty() || mac.chars().all(|c| c == '0' || c == ':') {
+                    interface.name.clone()
+                } else {
+                    mac.to_lowercase()
+                }
+            },
+            |interface| interface.name.clone(),
+            |interface| {
+                vec![
+                    ("name", Some(interface.name.clone())),
+                    ("speed", interface.speed_mbps.map(|speed| format!("{speed} Mbit/s"))),
+                    ("mtu", interface.mtu.map(|mtu| mtu.to_string())),
+                    ("driver", interface.driver.clone()),
+                    ("driver_version", interface.driver_version.clone()),
+                ]
+            },
+        );
+    }
+
+    fn battery(&mut self, before: &HardwareInfo, after: &HardwareInfo) {
+        let component = HardwareComponent::Battery;
+        let label = |battery: &crate::BatteryInfo| battery.model.clone().unwrap_or_else(|| "Battery".to_string());
+        match (&before.battery, &after.battery) {
+            (Some(old), None) => self.push(component, ChangeKind::Removed, &label(old)),
+            (None, Some(new)) => self.push(component, ChangeKind::Added, &label(new)),
+            (Some(old), Some(new)) => {
+                let fields = |battery: &crate::BatteryInfo| -> Fields {
+                    vec![
+                        ("model", battery.model.clone()),
+                        ("serial_number", battery.serial_number.clone()),
+                    ]
+                };
+                self.fields(component, &label(new), fields(old), fields(new));
+            }
+            (None, None) => {}
+        }
+    }
+
+    fn devices(&mut self, before: &HardwareInfo, after: &HardwareInfo) {
+        let component = HardwareComponent::Devices;
+        self.list(
+            component,
+            &before.pci_devices,
+            &after.pci_devices,
+            |device| device.bus_location.clone(),
+            |device| format!("{} {} [{}]", device.vendor_name, device.device_name, device.bus_location),
+            |device| {
+                vec![
+                    ("device_id", Some(device.device_id.clone())),
+                    ("revision", device.revision.clone()),
+                    ("driver", device.driver.clone()),
+                ]
+            },
+        );
+        self.list(
+            component,
+            &before.usb_devices,
+            &after.usb_devices,
+            |device| {
+                let location = device.serial_number.clone().or_else(|| device.port_path.clone());
+                format!("{}:{}@{}", device.vendor_id, device.product_id, location.unwrap_or_default())
+            },
+            |device| format!("{} {}", device.vendor_name, device.product_name).trim().to_string(),
+            |device| vec![("driver", device.driver.clone())],
+        );
+    }
+
+    fn system(&mut self, before: &HardwareInfo, after: &HardwareInfo) {
+        let component = HardwareComponent::System;
+        let (old, new) = (&before.system, &after.system);
+        self.fields(
+            component,
+            "System",
+            vec![
+                ("manufacturer", old.manufacturer.clone()),
+                ("product_name", old.product_name.clone()),
+                ("serial_number", old.serial_number.clone()),
+            ],
+            vec![
+                ("manufacturer", new.manufacturer.clone()),
+                ("product_name", new.product_name.clone()),
+                ("serial_number", new.serial_number.clone()),
+            ],
+        );
+        let board = |system: &crate::SystemInfo| -> Fields {
+            vec![
+                ("product", system.baseboard.product.clone()),
+                ("version", system.baseboard.version.clone()),
+            ]
+        };
+        self.fields(component, "Motherboard", board(old), board(new));
+        let firmware = |system: &crate::SystemInfo| -> Fields {
+            vec![
+                ("vendor", system.firmware.vendor.clone()),
+                ("version", system.firmware.version.clone()),
+                ("release_date", system.firmware.release_date.clone()),
+                ("secure_boot", system.firmware.secure_boot.map(|on| on.to_string())),
+            ]
+        };
+        self.fields(component, "BIOS", firmware(old), firmware(new));
+    }
+}
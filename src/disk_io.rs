@@ -0,0 +1,323 @@
+//! Per-disk I/O throughput, IOPS and queue depth
+//!
+//! The OS keeps cumulative counters per physical disk (`/proc/diskstats` on
+//! Linux, the PhysicalDisk performance counters on Windows, IOKit's
+//! `IOBlockStorageDriver` statistics on macOS). [`DiskIoCounters`] is one
+//! reading of them; [`DiskIoStats`] turns two readings into rates.
+
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Cumulative I/O counters of one physical disk
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskIoCounters {
+    /// Device identifier, matching [`PhysicalDisk::device`](crate::PhysicalDisk::device)
+    pub device: String,
+    /// Bytes read since boot
+    pub read_bytes: u64,
+    /// Bytes written since boot
+    pub write_bytes: u64,
+    /// Completed read operations
+    pub reads: u64,
+    /// Completed write operations
+    pub writes: u64,
+    /// Requests currently in flight
+    pub in_flight: Option<u64>,
+    /// Time the device had I/O in progress, in milliseconds
+    pub busy_time_ms: Option<u64>,
+    /// Sum of the time each request spent queued or in service, in milliseconds
+    pub weighted_time_ms: Option<u64>,
+    /// When the counters were read
+    pub read_at: Instant,
+}
+
+impl DiskIoCounters {
+    /// Read the counters of every physical disk
+    pub fn query_all() -> Result<Vec<Self>> {
+        #[cfg(target_os = "linux")]
+        {
+            linux::query_all()
+        }
+        #[cfg(target_os = "windows")]
+        {
+            windows::query_all()
+        }
+        #[cfg(target_os = "macos")]
+        {
+            macos::query_all()
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            Err(crate::HardwareQueryError::platform_not_supported(
+                "Disk I/O counters are not supported on this platform",
+            ))
+        }
+    }
+}
+
+/// I/O rates of one physical disk over a sampling interval
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiskIoStats {
+    /// Device identifier, matching [`PhysicalDisk::device`](crate::PhysicalDisk::device)
+    pub device: String,
+    /// Bytes read per second
+    pub read_bytes_per_sec: f64,
+    /// Bytes written per second
+    pub write_bytes_per_sec: f64,
+    /// Read operations per second
+    pub read_iops: f64,
+    /// Write operations per second
+    pub write_iops: f64,
+    /// Average number of requests queued or in service
+    pub queue_depth: f64,
+    /// Share of the interval the device was busy, in percent
+    pub utilization_percent: Option<f64>,
+    /// Length of the sampling interval
+    pub interval: Duration,
+}
+
+impl DiskIoStats {
+    /// Measure every physical disk over `interval`
+    ///
+    /// Blocks for `interval` between the two counter readings.
+    pub fn sample(interval: Duration) -> Result<Vec<Self>> {
+        let before = DiskIoCounters::query_all()?;
+        std::thread::sleep(interval);
+        let after = DiskIoCounters::query_all()?;
+        Ok(Self::between(&before, &after))
+    }
+
+    /// Rates between two counter readings, for disks present in both
+    pub fn between(before: &[DiskIoCounters], after: &[DiskIoCounters]) -> Vec<Self> {
+        after
+            .iter()
+            .filter_map(|current| {
+                let previous = before.iter().find(|c| c.device == current.device)?;
+                Self::from_counters(previous, current)
+            })
+            .collect()
+    }
+
+    fn from_counters(previous: &DiskIoCounters, current: &DiskIoCounters) -> Option<Self> {
+        let interval = current.read_at.checked_duration_since(previous.read_at)?;
+        let seconds = interval.as_secs_f64();
+        if seconds <= 0.0 {
+            return None;
+        }
+        // Counters reset when a device is re-attached; treat that as zero activity
+        let delta = |before: u64, after: u64| after.saturating_sub(before) as f64;
+        let elapsed_ms = seconds * 1000.0;
+
+        let weighted = match (previous.weighted_time_ms, current.weighted_time_ms) {
+            (Some(before), Some(after)) => Some(delta(before, after) / elapsed_ms),
+            _ => None,
+        };
+        let queue_depth = weighted
+            .or_else(|| current.in_flight.map(|n| n as f64))
+            .unwrap_or(0.0);
+
+        Some(Self {
+            device: current.device.clone(),
+            read_bytes_per_sec: delta(previous.read_bytes, current.read_bytes) / seconds,
+            write_bytes_per_sec: delta(previous.write_bytes, current.write_bytes) / seconds,
+            read_iops: delta(previous.reads, current.reads) / seconds,
+            write_iops: delta(previous.writes, current.writes) / seconds,
+            queue_depth,
+            utilization_percent: match (previous.busy_time_ms, current.busy_time_ms) {
+                (Some(before), Some(after)) => {
+                    Some((delta(before, after) / elapsed_ms * 100.0).min(100.0))
+                }
+                _ => None,
+            },
+            interval,
+        })
+    }
+
+    /// Get device identifier
+    pub fn device(&self) -> &str {
+        &self.device
+    }
+
+    /// Read plus write throughput in MB/s
+    pub fn throughput_mb_s(&self) -> f64 {
+        (self.read_bytes_per_sec + self.write_bytes_per_sec) / (1024.0 * 1024.0)
+    }
+
+    /// Read plus write operations per second
+    pub fn total_iops(&self) -> f64 {
+        self.read_iops + self.write_iops
+    }
+
+    /// Whether the disk was busy for at least `percent` of the interval
+    ///
+    /// Falls back to the queue depth (one or more requests outstanding on
+    /// average) where the platform does not report busy time.
+    pub fn is_saturated(&self, percent: f64) -> bool {
+        match self.utilization_percent {
+            Some(utilization) => utilization >= percent,
+            None => self.queue_depth >= 1.0,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::DiskIoCounters;
+    use crate::Result;
+    use std::time::Instant;
+
+    /// `/proc/diskstats` always counts 512-byte sectors
+    const SECTOR_BYTES: u64 = 512;
+
+    pub(super) fn query_all() -> Result<Vec<DiskIoCounters>> {
+        let text = std::fs::read_to_string("/proc/diskstats")?;
+        let read_at = Instant::now();
+        Ok(parse_diskstats(&text, read_at)
+            .into_iter()
+            .filter(|counters| {
+                crate::storage::is_physical_block_device(
+                    counters.device.trim_start_matches("/dev/"),
+                )
+            })
+            .collect())
+    }
+
+    /// Parse `/proc/diskstats` (every block device, partitions included)
+    fn parse_diskstats(text: &str, read_at: Instant) -> Vec<DiskIoCounters> {
+        text.lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() < 14 {
+                    return None;
+                }
+                let field = |index: usize| fields[index].parse::<u64>().ok();
+                Some(DiskIoCounters {
+                    device: format!("/dev/{}", fields[2]),
+                    reads: field(3)?,
+                    read_bytes: field(5)? * SECTOR_BYTES,
+                    writes: field(7)?,
+                    write_bytes: field(9)? * SECTOR_BYTES,
+                    in_flight: field(11),
+                    busy_time_ms: field(12),
+                    weighted_time_ms: field(13),
+                    read_at,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use super::DiskIoCounters;
+    use crate::Result;
+    use std::collections::HashMap;
+    use std::time::Instant;
+    use wmi::{Variant, WMIConnection};
+
+    pub(super) fn query_all() -> Result<Vec<DiskIoCounters>> {
+        let wmi_con = WMIConnection::new(crate::probe::com_library()?)?;
+        let rows: Vec<HashMap<String, Variant>> = wmi_con.raw_query(
+            "SELECT Name, DiskReadBytesPersec, DiskWriteBytesPersec, DiskReadsPersec, \
+             DiskWritesPersec, CurrentDiskQueueLength, PercentIdleTime, Timestamp_Sys100NS \
+             FROM Win32_PerfRawData_PerfDisk_PhysicalDisk",
+        )?;
+        let read_at = Instant::now();
+
+        // uint64 counters arrive as strings
+        let number = |row: &HashMap<String, Variant>, key: &str| match row.get(key) {
+            Some(Variant::UI4(v)) => Some(*v as u64),
+            Some(Variant::UI8(v)) => Some(*v),
+            Some(Variant::String(v)) => v.parse().ok(),
+            _ => None,
+        };
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                // Instance names look like "0 C:"; "_Total" is skipped
+                let Some(Variant::String(name)) = row.get("Name") else {
+                    return None;
+                };
+                let disk_number: u32 = name.split_whitespace().next()?.parse().ok()?;
+                // PercentIdleTime counts idle 100 ns ticks against the system timestamp
+                let busy_time_ms = match (number(row, "Timestamp_Sys100NS"), number(row, "PercentIdleTime")) {
+                    (Some(timestamp), Some(idle)) => Some(timestamp.saturating_sub(idle) / 10_000),
+                    _ => None,
+                };
+                Some(DiskIoCounters {
+                    device: format!("PhysicalDrive{disk_number}"),
+                    read_bytes: number(row, "DiskReadBytesPersec")?,
+                    write_bytes: number(row, "DiskWriteBytesPersec")?,
+                    reads: number(row, "DiskReadsPersec")?,
+                    writes: number(row, "DiskWritesPersec")?,
+                    in_flight: number(row, "CurrentDiskQueueLength"),
+                    busy_time_ms,
+                    weighted_time_ms: None,
+                    read_at,
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::DiskIoCounters;
+    use crate::probe::Command;
+    use crate::Result;
+    use std::collections::HashMap;
+    use std::time::Instant;
+
+    pub(super) fn query_all() -> Result<Vec<DiskIoCounters>> {
+        // Each IOBlockStorageDriver carries a Statistics dictionary; its IOMedia
+        // child names the BSD disk
+        let output = Command::new("ioreg")
+            .args(["-r", "-c", "IOBlockStorageDriver", "-w", "0", "-d", "2"])
+            .output()?;
+        let read_at = Instant::now();
+        let text = String::from_utf8_lossy(&output.stdout);
+
+        let mut counters = Vec::new();
+        let mut pending: Option<HashMap<String, u64>> = None;
+        for line in text.lines() {
+            if let Some((_, dict)) = line.split_once("\"Statistics\" = {") {
+                pending = Some(parse_statistics(dict.trim_end_matches('}')));
+            } else if let Some((_, name)) = line.split_once("\"BSD Name\" = ") {
+                let Some(stats) = pending.take() else {
+                    continue;
+                };
+                let value = |key: &str| stats.get(key).copied();
+                // Total Time counters are in nanoseconds
+                let weighted_time_ms = match (value("Total Time (Read)"), value("Total Time (Write)")) {
+                    (Some(read), Some(write)) => Some((read + write) / 1_000_000),
+                    _ => None,
+                };
+                counters.push(DiskIoCounters {
+                    device: name.trim().trim_matches('"').to_string(),
+                    read_bytes: value("Bytes (Read)").unwrap_or(0),
+                    write_bytes: value("Bytes (Write)").unwrap_or(0),
+                    reads: value("Operations (Read)").unwrap_or(0),
+                    writes: value("Operations (Write)").unwrap_or(0),
+                    in_flight: None,
+                    busy_time_ms: None,
+                    weighted_time_ms,
+                    read_at,
+                });
+            }
+        }
+
+        Ok(counters)
+    }
+
+    /// Parse `"Bytes (Read)"=123,"Operations (Read)"=4`
+    fn parse_statistics(dict: &str) -> HashMap<String, u64> {
+        dict.split(',')
+            .filter_map(|entry| {
+                let (key, value) = entry.split_once('=')?;
+                Some((key.trim().trim_matches('"').to_string(), value.trim().parse().ok()?))
+            })
+            .collect()
+    }
+}
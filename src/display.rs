@@ -0,0 +1,308 @@
+//! Attached monitors
+//!
+//! Monitors identify themselves through their EDID: a PNP manufacturer ID,
+//! product code, serial number, year of manufacture, physical size and
+//! preferred (native) mode. Linux reads the EDID each DRM connector exports
+//! in `/sys/class/drm`. Windows reads the decoded fields from the
+//! `WmiMonitorID` and `WmiMonitorBasicDisplayParams` WMI classes. macOS reads
+//! `system_profiler SPDisplaysDataType`.
+
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+/// Fixed pattern every EDID base block starts with
+const EDID_HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+
+/// Size of the EDID base block
+const EDID_BLOCK_LEN: usize = 128;
+
+/// PNP IDs of common monitor and panel makers
+const MANUFACTURERS: &[(&str, &str)] = &[
+    ("ACR", "Acer"),
+    ("AOC", "AOC"),
+    ("APP", "Apple"),
+    ("AUO", "AU Optronics"),
+    ("AUS", "ASUS"),
+    ("BNQ", "BenQ"),
+    ("BOE", "BOE"),
+    ("CMN", "Innolux"),
+    ("DEL", "Dell"),
+    ("GSM", "LG"),
+    ("HWP", "HP"),
+    ("LEN", "Lenovo"),
+    ("LGD", "LG Display"),
+    ("MSI", "MSI"),
+    ("NEC", "NEC"),
+    ("PHL", "Philips"),
+    ("SAM", "Samsung"),
+    ("SHP", "Sharp"),
+    ("SNY", "Sony"),
+    ("VSC", "ViewSonic"),
+];
+
+/// A monitor attached to the system
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MonitorInfo {
+    /// Connector or platform identifier (`card0-DP-1`, WMI instance name, display name)
+    pub connector: Option<String>,
+    /// Three-letter PNP manufacturer ID (`DEL`, `SAM`)
+    pub manufacturer_id: Option<String>,
+    /// Model name the monitor reports
+    pub model: Option<String>,
+    /// Manufacturer's product code
+    pub product_code: Option<u16>,
+    /// Serial number, from the serial string descriptor or the numeric serial
+    pub serial_number: Option<String>,
+    /// Year of manufacture
+    pub manufacture_year: Option<u16>,
+    /// Width of the visible area in centimetres
+    pub width_cm: Option<u32>,
+    /// Height of the visible area in centimetres
+    pub height_cm: Option<u32>,
+    /// Horizontal pixels of the preferred mode
+    pub native_width: Option<u32>,
+    /// Vertical pixels of the preferred mode
+    pub native_height: Option<u32>,
+}
+
+impl MonitorInfo {
+    /// Query attached monitors
+    pub fn query_all() -> Result<Vec<Self>> {
+        #[cfg(target_os = "linux")]
+        {
+            Ok(linux::monitors())
+        }
+        #[cfg(target_os = "windows")]
+        {
+            windows::monitors()
+        }
+        #[cfg(target_os = "macos")]
+        {
+            macos::monitors()
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            Err(crate::HardwareQueryError::platform_not_supported(
+                "Monitor detection is not supported on this platform",
+            ))
+        }
+    }
+
+    /// Decode an EDID base block (128 bytes; extension blocks are ignored)
+    pub fn from_edid(connector: Option<&str>, edid: &[u8]) -> Option<Self> {
+        if edid.len() < EDID_BLOCK_LEN || edid[..8] != EDID_HEADER {
+            return None;
+        }
+
+        // Either is 0 for projectors and when the aspect ratio is stored instead
+        let size = (edid[21] != 0 && edid[22] != 0).then_some((edid[21] as u32, edid[22] as u32));
+        let mut monitor = Self {
+            connector: connector.map(str::to_string),
+            manufacturer_id: pnp_id(u16::from_be_bytes([edid[8], edid[9]])),
+            product_code: Some(u16::from_le_bytes([edid[10], edid[11]])),
+            // 0 means the serial is only in a descriptor, if anywhere
+            serial_number: Some(u32::from_le_bytes([edid[12], edid[13], edid[14], edid[15]]))
+                .filter(|&serial| serial != 0)
+                .map(|serial| serial.to_string()),
+            // Week 0xFF marks byte 17 as a model year; both count from 1990
+            manufacture_year: Some(edid[17]).filter(|&year| year > 0).map(|year| 1990 + year as u16),
+            width_cm: size.map(|(width, _)| width),
+            height_cm: size.map(|(_, height)| height),
+            ..Default::default()
+        };
+
+        for descriptor in edid[54..126].chunks_exact(18) {
+            if descriptor[0] != 0 || descriptor[1] != 0 {
+                // The first detailed timing descriptor is the preferred mode
+                if monitor.native_width.is_none() {
+                    monitor.native_width = Some(descriptor[2] as u32 | ((descriptor[4] as u32 & 0xF0) << 4));
+                    monitor.native_height = Some(descriptor[5] as u32 | ((descriptor[7] as u32 & 0xF0) << 4));
+                }
+                continue;
+            }
+            // Display descriptor text ends at a line feed and is padded with spaces
+            let text = || {
+                let bytes = &descriptor[5..18];
+                let end = bytes.iter().position(|&byte| byte == 0x0A).unwrap_or(bytes.len());
+                Some(String::from_utf8_lossy(&bytes[..end]).trim().to_string()).filter(|text| !text.is_empty())
+            };
+            match descriptor[3] {
+                0xFC => monitor.model = text(),
+                0xFF => monitor.serial_number = text().or(monitor.serial_number.take()),
+                _ => {}
+            }
+        }
+
+        Some(monitor)
+    }
+
+    /// Manufacturer name for well-known PNP IDs, otherwise the ID itself
+    pub fn manufacturer(&self) -> Option<&str> {
+        let id = self.manufacturer_id.as_deref()?;
+        Some(MANUFACTURERS.iter().find(|(pnp, _)| *pnp == id).map_or(id, |(_, name)| name))
+    }
+
+    /// Diagonal of the visible area in inches
+    pub fn diagonal_inches(&self) -> Option<f64> {
+        let (width, height) = (self.width_cm? as f64, self.height_cm? as f64);
+        Some((width * width + height * height).sqrt() / 2.54)
+    }
+}
+
+/// Decode the compressed PNP ID: three 5-bit letters, 1 = `A`
+fn pnp_id(code: u16) -> Option<String> {
+    let letters: String = [10, 5, 0]
+        .iter()
+        .map(|shift| ((code >> shift) & 0x1F) as u8)
+        .filter(|letter| (1..=26).contains(letter))
+        .map(|letter| (b'A' + letter - 1) as char)
+        .collect();
+    (letters.len() == 3).then_some(letters)
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::MonitorInfo;
+
+    /// Connected DRM connectors that export an EDID
+    pub(super) fn monitors() -> Vec<MonitorInfo> {
+        let mut monitors: Vec<MonitorInfo> = std::fs::read_dir("/sys/class/drm")
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| {
+                std::fs::read_to_string(entry.path().join("status")).is_ok_and(|status| status.trim() == "connected")
+            })
+            .filter_map(|entry| {
+                let edid = std::fs::read(entry.path().join("edid")).ok()?;
+                MonitorInfo::from_edid(Some(&entry.file_name().to_string_lossy()), &edid)
+            })
+            .collect();
+        monitors.sort_by(|a, b| a.connector.cmp(&b.connector));
+        monitors
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use super::MonitorInfo;
+    use crate::Result;
+    use std::collections::HashMap;
+    use wmi::{Variant, WMIConnection};
+
+    /// Monitors from the `root\WMI` monitor classes, joined on `InstanceName`
+    pub(super) fn monitors() -> Result<Vec<MonitorInfo>> {
+        let wmi_con = WMIConnection::with_namespace_path(r"root\WMI", crate::probe::com_library()?)?;
+        let ids: Vec<HashMap<String, Variant>> = wmi_con.raw_query(
+            "SELECT InstanceName, ManufacturerName, ProductCodeID, SerialNumberID, UserFriendlyName, \
+             YearOfManufacture FROM WmiMonitorID WHERE Active = TRUE",
+        )?;
+        let params: Vec<HashMap<String, Variant>> = wmi_con
+            .raw_query("SELECT InstanceName, MaxHorizontalImageSize, MaxVerticalImageSize FROM WmiMonitorBasicDisplayParams")
+            .unwrap_or_default();
+
+        let string = |entity: &HashMap<String, Variant>, key: &str| match entity.get(key) {
+            Some(Variant::String(value)) if !value.is_empty() => Some(value.clone()),
+            _ => None,
+        };
+        // Identification strings are NUL-padded arrays of UTF-16 code units
+        let char_array = |entity: &HashMap<String, Variant>, key: &str| match entity.get(key) {
+            Some(Variant::Array(units)) => {
+                let units: Vec<u16> = units
+                    .iter()
+                    .filter_map(|unit| match unit {
+                        Variant::UI2(unit) => Some(*unit),
+                        Variant::UI1(unit) => Some(*unit as u16),
+                        _ => None,
+                    })
+                    .take_while(|&unit| unit != 0)
+                    .collect();
+                Some(String::from_utf16_lossy(&units).trim().to_string()).filter(|text| !text.is_empty())
+            }
+            _ => None,
+        };
+        let number = |entity: &HashMap<String, Variant>, key: &str| match entity.get(key) {
+            Some(Variant::UI1(value)) => Some(*value as u32),
+            Some(Variant::UI2(value)) => Some(*value as u32),
+            Some(Variant::UI4(value)) => Some(*value),
+            _ => None,
+        };
+
+        Ok(ids
+            .iter()
+            .map(|id| {
+                let instance = string(id, "InstanceName");
+                let size = params.iter().find(|param| string(param, "InstanceName") == instance);
+                MonitorInfo {
+                    manufacturer_id: char_array(id, "ManufacturerName"),
+                    model: char_array(id, "UserFriendlyName"),
+                    // ProductCodeID holds the code as hex digits
+                    product_code: char_array(id, "ProductCodeID").and_then(|code| u16::from_str_radix(&code, 16).ok()),
+                    serial_number: char_array(id, "SerialNumberID").filter(|serial| serial != "0"),
+                    manufacture_year: number(id, "YearOfManufacture").filter(|&year| year > 0).map(|year| year as u16),
+                    width_cm: size.and_then(|size| number(size, "MaxHorizontalImageSize")).filter(|&cm| cm > 0),
+                    height_cm: size.and_then(|size| number(size, "MaxVerticalImageSize")).filter(|&cm| cm > 0),
+                    native_width: None,
+                    native_height: None,
+                    connector: instance,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::{MonitorInfo, pnp_id};
+    use crate::probe::Command;
+    use crate::Result;
+    use serde_json::Value;
+
+    /// Displays listed under each graphics device by `system_profiler`
+    pub(super) fn monitors() -> Result<Vec<MonitorInfo>> {
+        let output = Command::new("system_profiler")
+            .args(["SPDisplaysDataType", "-json"])
+            .output()?;
+        let json: Value = serde_json::from_slice(&output.stdout)?;
+
+        let text = |value: &Value, key: &str| {
+            value
+                .get(key)
+                .and_then(Value::as_str)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let hex = |value: &Value, key: &str| text(value, key).and_then(|v| u32::from_str_radix(&v, 16).ok());
+
+        let displays = json
+            .get("SPDisplaysDataType")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|gpu| gpu.get("spdisplays_ndrvs").and_then(Value::as_array))
+            .flatten();
+
+        Ok(displays
+            .map(|display| {
+                // "_spdisplays_pixels": "2880 x 1800"
+                let pixels = text(display, "_spdisplays_pixels").and_then(|pixels| {
+                    let (width, height) = pixels.split_once(" x ")?;
+                    Some((width.trim().parse().ok()?, height.trim().parse().ok()?))
+                });
+                MonitorInfo {
+                    connector: text(display, "_name"),
+                    // The vendor ID is the EDID's compressed PNP ID
+                    manufacturer_id: hex(display, "_spdisplays_display-vendor-id").and_then(|code| pnp_id(code as u16)),
+                    model: text(display, "_name"),
+                    product_code: hex(display, "_spdisplays_display-product-id").map(|code| code as u16),
+                    serial_number: text(display, "_spdisplays_display-serial-number"),
+                    manufacture_year: text(display, "_spdisplays_display-year").and_then(|year| year.parse().ok()),
+                    width_cm: None,
+                    height_cm: None,
+                    native_width: pixels.map(|(width, _)| width),
+                    native_height: pixels.map(|(_, height)| height),
+                }
+            })
+            .collect())
+    }
+}
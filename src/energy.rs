@@ -0,0 +1,590 @@
+//! Measured energy consumption
+//!
+//! [`EnergyMeter`] reads the hardware's cumulative energy counters instead of
+//! estimating power from utilization:
+//!
+//! - Linux: RAPL package, core, uncore, DRAM and platform domains from
+//!   `/sys/class/powercap` (Intel, and AMD Zen on kernel 5.8+), plus the
+//!   energy counters of discrete GPUs in hwmon. RAPL counters are readable by
+//!   root only since kernel 5.10.
+//! - Windows: the `Energy Meter` performance counters that feed the Energy
+//!   Estimation Engine, on machines whose firmware exposes them.
+//! - macOS: `powermetrics`, integrated in the background (requires root).
+//! - With the `nvidia` feature, NVML's per-GPU energy counter on every platform.
+//!
+//! [`EnergySample`] is one reading of the counters; [`EnergyReading`] is the
+//! energy used between two readings.
+//!
+//! ```no_run
+//! use hardware_query::{EnergyDomain, EnergyMeter};
+//! use std::time::Duration;
+//!
+//! # fn main() -> hardware_query::Result<()> {
+//! let meter = EnergyMeter::new()?;
+//! let before = meter.sample()?;
+//! // ... run the workload ...
+//! let reading = meter.sample()?.energy_since(&before);
+//! println!("{:.1} J ({:.1} J in the CPU packages)", reading.total_joules(), reading.joules(EnergyDomain::Package));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{HardwareQueryError, Result};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Part of the machine an energy counter covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EnergyDomain {
+    /// CPU package: cores, caches, memory controller and integrated GPU
+    Package,
+    /// CPU cores only (RAPL PP0), part of [`Package`](Self::Package)
+    Cores,
+    /// Integrated GPU and other uncore logic (RAPL PP1), part of [`Package`](Self::Package)
+    Uncore,
+    /// Memory
+    Dram,
+    /// Discrete GPU, or the GPU cluster of an Apple Silicon SoC
+    Gpu,
+    /// Apple Neural Engine
+    NeuralEngine,
+    /// Whole platform as seen by the power delivery (RAPL psys)
+    Platform,
+}
+
+impl std::fmt::Display for EnergyDomain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnergyDomain::Package => write!(f, "Package"),
+            EnergyDomain::Cores => write!(f, "Cores"),
+            EnergyDomain::Uncore => write!(f, "Uncore"),
+            EnergyDomain::Dram => write!(f, "DRAM"),
+            EnergyDomain::Gpu => write!(f, "GPU"),
+            EnergyDomain::NeuralEngine => write!(f, "Neural Engine"),
+            EnergyDomain::Platform => write!(f, "Platform"),
+        }
+    }
+}
+
+/// One cumulative energy counter
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnergyCounter {
+    /// Covered part of the machine
+    pub domain: EnergyDomain,
+    /// Counter name, unique within a sample (`package-0`, `dram-0`, a GPU's PCI address)
+    pub name: String,
+    /// Energy counted so far, in joules
+    pub joules: f64,
+    /// Value at which the counter wraps around to zero, in joules
+    pub wrap_joules: Option<f64>,
+}
+
+/// Reading of every energy counter at one point in time
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnergySample {
+    /// Counter values
+    pub counters: Vec<EnergyCounter>,
+    /// When the counters were read
+    pub read_at: Instant,
+}
+
+impl EnergySample {
+    /// Energy used between `earlier` and this sample
+    pub fn energy_since(&self, earlier: &EnergySample) -> EnergyReading {
+        EnergyReading::between(earlier, self)
+    }
+}
+
+/// Energy used by one counter's domain over an interval
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomainEnergy {
+    /// Covered part of the machine
+    pub domain: EnergyDomain,
+    /// Counter name
+    pub name: String,
+    /// Energy used, in joules
+    pub joules: f64,
+    /// Average power over the interval, in watts
+    pub average_watts: f64,
+}
+
+/// Energy used between two [`EnergySample`]s
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnergyReading {
+    /// Energy per counter
+    pub domains: Vec<DomainEnergy>,
+    /// Time between the samples
+    pub interval: Duration,
+}
+
+impl EnergyReading {
+    /// Energy used between two samples, for counters present in both
+    pub fn between(before: &EnergySample, after: &EnergySample) -> Self {
+        let interval = after.read_at.saturating_duration_since(before.read_at);
+        let seconds = interval.as_secs_f64();
+        let domains = after
+            .counters
+            .iter()
+            .filter_map(|current| {
+                let previous = before
+                    .counters
+                    .iter()
+                    .find(|c| c.domain == current.domain && c.name == current.name)?;
+                let joules = if current.joules >= previous.joules {
+                    current.joules - previous.joules
+                } else {
+                    // Wrapped once; counters without a known range are treated as reset
+                    current
+                        .wrap_joules
+                        .map(|wrap| wrap - previous.joules + current.joules)
+                        .unwrap_or(0.0)
+                };
+                Some(DomainEnergy {
+                    domain: current.domain,
+                    name: current.name.clone(),
+                    joules,
+                    average_watts: if seconds > 0.0 { joules / seconds } else { 0.0 },
+                })
+            })
+            .collect();
+        Self { domains, interval }
+    }
+
+    /// Energy used by every counter of `domain`, in joules
+    pub fn joules(&self, domain: EnergyDomain) -> f64 {
+        self.domains
+            .iter()
+            .filter(|energy| energy.domain == domain)
+            .map(|energy| energy.joules)
+            .sum()
+    }
+
+    /// Average power of every counter of `domain`, in watts
+    pub fn watts(&self, domain: EnergyDomain) -> f64 {
+        self.domains
+            .iter()
+            .filter(|energy| energy.domain == domain)
+            .map(|energy| energy.average_watts)
+            .sum()
+    }
+
+    /// Energy used by the machine as far as it is measured, in joules
+    ///
+    /// The platform domain where the hardware has one; otherwise CPU packages,
+    /// DRAM, GPUs and the neural engine. Core and uncore counters are part of
+    /// their package and are not added again.
+    pub fn total_joules(&self) -> f64 {
+        if self.domains.iter().any(|energy| energy.domain == EnergyDomain::Platform) {
+            return self.joules(EnergyDomain::Platform);
+        }
+        [
+            EnergyDomain::Package,
+            EnergyDomain::Dram,
+            EnergyDomain::Gpu,
+            EnergyDomain::NeuralEngine,
+        ]
+        .into_iter()
+        .map(|domain| self.joules(domain))
+        .sum()
+    }
+
+    /// Average power of the machine over the interval, in watts
+    pub fn average_watts(&self) -> f64 {
+        let seconds = self.interval.as_secs_f64();
+        if seconds > 0.0 { self.total_joules() / seconds } else { 0.0 }
+    }
+
+    /// Energy used by the machine in kilowatt-hours, for carbon accounting
+    pub fn total_kwh(&self) -> f64 {
+        self.total_joules() / 3_600_000.0
+    }
+}
+
+/// Reads the platform's energy counters
+///
+/// Creating a meter checks that at least one counter is readable. On macOS it
+/// starts a `powermetrics` process that runs until the meter is dropped.
+pub struct EnergyMeter {
+    #[cfg(target_os = "macos")]
+    powermetrics: Option<macos::Powermetrics>,
+    #[cfg(feature = "nvidia")]
+    nvml: Option<nvml_wrapper::Nvml>,
+}
+
+impl std::fmt::Debug for EnergyMeter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EnergyMeter").finish_non_exhaustive()
+    }
+}
+
+impl EnergyMeter {
+    /// Open the energy counters of this machine
+    ///
+    /// Fails if none can be read, typically for lack of root privileges or on
+    /// hardware without energy counters.
+    pub fn new() -> Result<Self> {
+        #[cfg(target_os = "macos")]
+        let (powermetrics, platform_error) = match macos::Powermetrics::start() {
+            Ok(powermetrics) => (Some(powermetrics), None),
+            Err(e) => (None, Some(e)),
+        };
+        #[cfg(not(target_os = "macos"))]
+        let platform_error = Self::platform_counters().err();
+
+        let meter = Self {
+            #[cfg(target_os = "macos")]
+            powermetrics,
+            #[cfg(feature = "nvidia")]
+            nvml: nvml_wrapper::Nvml::init().ok(),
+        };
+
+        if meter.sample()?.counters.is_empty() {
+            return Err(platform_error.unwrap_or_else(|| {
+                HardwareQueryError::device_not_found("No energy counters available on this machine")
+            }));
+        }
+        Ok(meter)
+    }
+
+    /// Read every counter
+    pub fn sample(&self) -> Result<EnergySample> {
+        #[cfg(target_os = "macos")]
+        let mut counters = self
+            .powermetrics
+            .as_ref()
+            .map(macos::Powermetrics::counters)
+            .unwrap_or_default();
+        #[cfg(not(target_os = "macos"))]
+        let mut counters = Self::platform_counters().unwrap_or_default();
+
+        #[cfg(feature = "nvidia")]
+        if let Some(nvml) = &self.nvml {
+            for index in 0..nvml.device_count().unwrap_or(0) {
+                let Ok(device) = nvml.device_by_index(index) else {
+                    continue;
+                };
+                // Millijoules since the driver was loaded
+                if let (Ok(millijoules), Ok(pci)) = (device.total_energy_consumption(), device.pci_info()) {
+                    counters.push(EnergyCounter {
+                        domain: EnergyDomain::Gpu,
+                        name: pci.bus_id,
+                        joules: millijoules as f64 / 1000.0,
+                        wrap_joules: None,
+                    });
+                }
+            }
+        }
+
+        Ok(EnergySample {
+            counters,
+            read_at: Instant::now(),
+        })
+    }
+
+    /// Measure the energy used over `interval`
+    ///
+    /// Blocks for `interval` between the two samples.
+    pub fn measure(&self, interval: Duration) -> Result<EnergyReading> {
+        let before = self.sample()?;
+        std::thread::sleep(interval);
+        Ok(self.sample()?.energy_since(&before))
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub(crate) fn platform_counters() -> Result<Vec<EnergyCounter>> {
+        #[cfg(target_os = "linux")]
+        {
+            linux::counters()
+        }
+        #[cfg(target_os = "windows")]
+        {
+            windows::counters()
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        {
+            Err(HardwareQueryError::platform_not_supported(
+                "Energy counters are not supported on this platform",
+            ))
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{EnergyCounter, EnergyDomain};
+    use crate::{HardwareQueryError, Result};
+    use std::fs;
+    use std::path::Path;
+
+    pub(super) fn counters() -> Result<Vec<EnergyCounter>> {
+        let mut counters = Vec::new();
+        let mut denied = false;
+
+        // intel-rapl:<package> zones hold intel-rapl:<package>:<n> subzones; the
+        // intel-rapl-mmio duplicates of the package zones are skipped
+        if let Ok(entries) = fs::read_dir("/sys/class/powercap") {
+            let mut zones: Vec<String> = entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| name.starts_with("intel-rapl:"))
+                .collect();
+            zones.sort();
+
+            for zone in zones {
+                let dir = Path::new("/sys/class/powercap").join(&zone);
+                let Some(name) = read(&dir.join("name")) else {
+                    continue;
+                };
+                let package = zone.split(':').nth(1).unwrap_or("0");
+                let (domain, name) = match name.as_str() {
+                    "psys" => (EnergyDomain::Platform, "psys".to_string()),
+                    "core" => (EnergyDomain::Cores, format!("core-{package}")),
+                    "uncore" => (EnergyDomain::Uncore, format!("uncore-{package}")),
+                    "dram" => (EnergyDomain::Dram, format!("dram-{package}")),
+                    name if name.starts_with("package") => (EnergyDomain::Package, name.to_string()),
+                    _ => continue,
+                };
+                let microjoules = match fs::read_to_string(dir.join("energy_uj")) {
+                    Ok(value) => value.trim().parse::<f64>().ok(),
+                    Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                        denied = true;
+                        None
+                    }
+                    Err(_) => None,
+                };
+                let Some(microjoules) = microjoules else {
+                    continue;
+                };
+                counters.push(EnergyCounter {
+                    domain,
+                    name,
+                    joules: microjoules / 1e6,
+                    wrap_joules: read(&dir.join("max_energy_range_uj"))
+                        .and_then(|value| value.parse::<f64>().ok())
+                        .map(|microjoules| microjoules / 1e6),
+                });
+            }
+        }
+
+        // Discrete Intel GPUs report energy in their hwmon node (µJ)
+        if let Ok(entries) = fs::read_dir("/sys/class/drm") {
+            let mut cards: Vec<String> = entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| name.starts_with("card") && !name.contains('-'))
+                .collect();
+            cards.sort();
+
+            for card in cards {
+                let device = Path::new("/sys/class/drm").join(&card).join("device");
+                let Ok(hwmons) = fs::read_dir(device.join("hwmon")) else {
+                    continue;
+                };
+                let bus_id = fs::canonicalize(&device)
+                    .ok()
+                    .and_then(|path| path.file_name().map(|name| name.to_string_lossy().into_owned()))
+                    .unwrap_or(card);
+                for hwmon in hwmons.flatten().map(|entry| entry.path()) {
+                    if let Some(microjoules) =
+                        read(&hwmon.join("energy1_input")).and_then(|value| value.parse::<f64>().ok())
+                    {
+                        counters.push(EnergyCounter {
+                            domain: EnergyDomain::Gpu,
+                            name: bus_id.clone(),
+                            joules: microjoules / 1e6,
+                            wrap_joules: None,
+                        });
+                        break;
+                    }
+                }
+            }
+        }
+
+        if counters.is_empty() && denied {
+            return Err(HardwareQueryError::permission_denied(
+                "Reading RAPL energy counters requires root",
+            ));
+        }
+        Ok(counters)
+    }
+
+    fn read(path: &Path) -> Option<String> {
+        fs::read_to_string(path)
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use super::{EnergyCounter, EnergyDomain};
+    use crate::Result;
+    use std::collections::HashMap;
+    use wmi::{Variant, WMIConnection};
+
+    /// The Energy Meter counters count picowatt-hours
+    const JOULES_PER_PICOWATT_HOUR: f64 = 3.6e-9;
+
+    pub(super) fn counters() -> Result<Vec<EnergyCounter>> {
+        let wmi_con = WMIConnection::new(crate::probe::com_library()?)?;
+        let rows: Vec<HashMap<String, Variant>> =
+            wmi_con.raw_query("SELECT Name, Energy FROM Win32_PerfRawData_Counters_EnergyMeter")?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                // Instances look like RAPL_Package0_PKG, RAPL_Package0_PP0, RAPL_Package0_DRAM
+                let Some(Variant::String(name)) = row.get("Name") else {
+                    return None;
+                };
+                let upper = name.to_ascii_uppercase();
+                let domain = if upper.ends_with("_PKG") {
+                    EnergyDomain::Package
+                } else if upper.ends_with("_PP0") {
+                    EnergyDomain::Cores
+                } else if upper.ends_with("_PP1") {
+                    EnergyDomain::Uncore
+                } else if upper.ends_with("_DRAM") {
+                    EnergyDomain::Dram
+                } else if upper.contains("PSYS") || upper.contains("PLATFORM") {
+                    EnergyDomain::Platform
+                } else {
+                    return None;
+                };
+                // uint64 counters arrive as strings
+                let picowatt_hours = match row.get("Energy") {
+                    Some(Variant::UI8(v)) => *v as f64,
+                    Some(Variant::String(v)) => v.parse().ok()?,
+                    _ => return None,
+                };
+                Some(EnergyCounter {
+                    domain,
+                    name: name.clone(),
+                    joules: picowatt_hours * JOULES_PER_PICOWATT_HOUR,
+                    wrap_joules: None,
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::{EnergyCounter, EnergyDomain};
+    use crate::{HardwareQueryError, Result};
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Stdio};
+    use std::sync::mpsc;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// powermetrics sampling interval in milliseconds
+    const INTERVAL_MS: u64 = 500;
+
+    /// A running `powermetrics` whose power readings are summed into energy counters
+    pub(super) struct Powermetrics {
+        child: Child,
+        totals: Arc<Mutex<Vec<EnergyCounter>>>,
+    }
+
+    impl Powermetrics {
+        /// Start `powermetrics` and wait for its first sample
+        pub(super) fn start() -> Result<Self> {
+            let mut child = crate::probe::Command::new("powermetrics")
+                .args(["--samplers", "cpu_power,gpu_power", "-i"])
+                .arg(INTERVAL_MS.to_string())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()?;
+            let stdout = child.stdout.take().ok_or_else(|| {
+                HardwareQueryError::power_management_error("powermetrics produced no output")
+            })?;
+
+            let totals = Arc::new(Mutex::new(Vec::new()));
+            let (started, first_sample) = mpsc::channel();
+            let shared = Arc::clone(&totals);
+            std::thread::spawn(move || {
+                let mut elapsed_seconds = 0.0;
+                let mut started = Some(started);
+                for line in BufReader::new(stdout).lines().map_while(|line| line.ok()) {
+                    // *** Sampled system activity (Wed Oct 16 10:00:00 2026 +0000) (502.31ms elapsed) ***
+                    if line.starts_with("*** Sampled system activity") {
+                        elapsed_seconds = line
+                            .rsplit_once('(')
+                            .and_then(|(_, rest)| rest.split_once("ms elapsed"))
+                            .and_then(|(ms, _)| ms.trim().parse::<f64>().ok())
+                            .map_or(INTERVAL_MS as f64 / 1000.0, |ms| ms / 1000.0);
+                        if let Some(started) = started.take() {
+                            let _ = started.send(());
+                        }
+                        continue;
+                    }
+                    let Some((domain, name, watts)) = parse_power_line(&line) else {
+                        continue;
+                    };
+                    let Ok(mut totals) = shared.lock() else {
+                        return;
+                    };
+                    match totals.iter_mut().find(|counter| counter.name == name) {
+                        Some(counter) => counter.joules += watts * elapsed_seconds,
+                        None => totals.push(EnergyCounter {
+                            domain,
+                            name: name.to_string(),
+                            joules: watts * elapsed_seconds,
+                            wrap_joules: None,
+                        }),
+                    }
+                }
+            });
+
+            // powermetrics exits right away when not run as root
+            let mut powermetrics = Self { child, totals };
+            match first_sample.recv_timeout(Duration::from_millis(INTERVAL_MS * 4)) {
+                Ok(()) => Ok(powermetrics),
+                Err(_) => {
+                    let _ = powermetrics.child.kill();
+                    Err(HardwareQueryError::permission_denied(
+                        "powermetrics requires root to report energy",
+                    ))
+                }
+            }
+        }
+
+        pub(super) fn counters(&self) -> Vec<EnergyCounter> {
+            self.totals.lock().map(|totals| totals.clone()).unwrap_or_default()
+        }
+    }
+
+    impl Drop for Powermetrics {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+
+    /// Parse `CPU Power: 1234 mW` or `Intel energy model derived package power (CPUs+GT+SA): 3.45W`
+    ///
+    /// Sums such as `Combined Power (CPU + GPU + ANE)` are skipped.
+    fn parse_power_line(line: &str) -> Option<(EnergyDomain, &'static str, f64)> {
+        let (label, value) = line.rsplit_once(':')?;
+        let label = label.trim().to_ascii_lowercase();
+        let (domain, name) = match label.as_str() {
+            "cpu power" => (EnergyDomain::Package, "cpu"),
+            "gpu power" => (EnergyDomain::Gpu, "gpu"),
+            "ane power" => (EnergyDomain::NeuralEngine, "ane"),
+            "dram power" => (EnergyDomain::Dram, "dram"),
+            label if label.starts_with("intel energy model derived package power") => {
+                (EnergyDomain::Package, "package")
+            }
+            _ => return None,
+        };
+        let value = value.trim();
+        let watts = if let Some(milliwatts) = value.strip_suffix("mW") {
+            milliwatts.trim().parse::<f64>().ok()? / 1000.0
+        } else {
+            value.strip_suffix('W')?.trim().parse::<f64>().ok()?
+        };
+        Some((domain, name, watts))
+    }
+}
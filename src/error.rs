@@ -1,6 +1,169 @@
+use serde::{Deserialize, Serialize};
+
 /// Result type for hardware query operations
 pub type Result<T> = std::result::Result<T, HardwareQueryError>;
 
+/// Hardware component or backend an error came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErrorComponent {
+    /// CPU detection
+    Cpu,
+    /// GPU detection
+    Gpu,
+    /// NPU, TPU, FPGA or SoC detection
+    Accelerator,
+    /// System memory
+    Memory,
+    /// Disks and volumes
+    Storage,
+    /// Network interfaces
+    Network,
+    /// Battery
+    Battery,
+    /// Temperature sensors and fans
+    Thermal,
+    /// Power profile and energy counters
+    Power,
+    /// PCI and USB devices
+    Devices,
+    /// Virtualization and container detection
+    Virtualization,
+    /// System vendor, motherboard and firmware
+    System,
+    /// Real-time monitoring
+    Monitoring,
+    /// Windows Management Instrumentation
+    Wmi,
+    /// NVIDIA Management Library
+    Nvml,
+    /// AMD ROCm SMI
+    Rocm,
+    /// Vulkan loader
+    Vulkan,
+    /// OpenCL loader
+    OpenCl,
+    /// External command (`nvidia-smi`, `lspci`, `system_profiler`, ...)
+    Command,
+    /// JSON, YAML, MessagePack or SQLite snapshots
+    Serialization,
+}
+
+impl std::fmt::Display for ErrorComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorComponent::Cpu => write!(f, "CPU"),
+            ErrorComponent::Gpu => write!(f, "GPU"),
+            ErrorComponent::Accelerator => write!(f, "Accelerator"),
+            ErrorComponent::Memory => write!(f, "Memory"),
+            ErrorComponent::Storage => write!(f, "Storage"),
+            ErrorComponent::Network => write!(f, "Network"),
+            ErrorComponent::Battery => write!(f, "Battery"),
+            ErrorComponent::Thermal => write!(f, "Thermal"),
+            ErrorComponent::Power => write!(f, "Power"),
+            ErrorComponent::Devices => write!(f, "Devices"),
+            ErrorComponent::Virtualization => write!(f, "Virtualization"),
+            ErrorComponent::System => write!(f, "System"),
+            ErrorComponent::Monitoring => write!(f, "Monitoring"),
+            ErrorComponent::Wmi => write!(f, "WMI"),
+            ErrorComponent::Nvml => write!(f, "NVML"),
+            ErrorComponent::Rocm => write!(f, "ROCm"),
+            ErrorComponent::Vulkan => write!(f, "Vulkan"),
+            ErrorComponent::OpenCl => write!(f, "OpenCL"),
+            ErrorComponent::Command => write!(f, "Command"),
+            ErrorComponent::Serialization => write!(f, "Serialization"),
+        }
+    }
+}
+
+impl From<crate::HardwareComponent> for ErrorComponent {
+    fn from(component: crate::HardwareComponent) -> Self {
+        use crate::HardwareComponent;
+
+        match component {
+            HardwareComponent::CPU => ErrorComponent::Cpu,
+            HardwareComponent::GPU => ErrorComponent::Gpu,
+            HardwareComponent::Accelerators => ErrorComponent::Accelerator,
+            HardwareComponent::Memory => ErrorComponent::Memory,
+            HardwareComponent::Storage => ErrorComponent::Storage,
+            HardwareComponent::Network => ErrorComponent::Network,
+            HardwareComponent::Battery => ErrorComponent::Battery,
+            HardwareComponent::Thermal => ErrorComponent::Thermal,
+            HardwareComponent::Devices => ErrorComponent::Devices,
+            HardwareComponent::Power => ErrorComponent::Power,
+            HardwareComponent::Virtualization => ErrorComponent::Virtualization,
+            HardwareComponent::System => ErrorComponent::System,
+        }
+    }
+}
+
+/// Machine-readable reason for a failure
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErrorCode {
+    /// Reading the data requires more privileges (root, Administrator, group membership)
+    PermissionDenied,
+    /// The hardware, driver or interface is not present on this machine
+    NotPresent,
+    /// The platform has no way to provide this information
+    Unsupported,
+    /// The data exists but could not be read this time
+    Unavailable,
+    /// A temporary condition (busy device, interrupted call); retrying may succeed
+    Transient,
+    /// The operation did not finish in time; retrying may succeed
+    Timeout,
+    /// The hardware or a tool returned data that could not be parsed
+    InvalidData,
+    /// A caller-supplied argument or configuration is invalid
+    InvalidInput,
+    /// A GPU or device driver reported an error
+    DriverError,
+    /// An I/O operation failed
+    Io,
+    /// Data could not be serialized or deserialized
+    Serialization,
+    /// The cause is not known
+    #[default]
+    Unknown,
+}
+
+impl ErrorCode {
+    /// Map an I/O error kind to a code
+    pub fn from_io_kind(kind: std::io::ErrorKind) -> Self {
+        use std::io::ErrorKind;
+
+        match kind {
+            ErrorKind::PermissionDenied => ErrorCode::PermissionDenied,
+            ErrorKind::NotFound => ErrorCode::NotPresent,
+            ErrorKind::Unsupported => ErrorCode::Unsupported,
+            ErrorKind::TimedOut => ErrorCode::Timeout,
+            ErrorKind::Interrupted | ErrorKind::WouldBlock | ErrorKind::ResourceBusy => ErrorCode::Transient,
+            ErrorKind::InvalidData | ErrorKind::UnexpectedEof => ErrorCode::InvalidData,
+            ErrorKind::InvalidInput => ErrorCode::InvalidInput,
+            _ => ErrorCode::Io,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            ErrorCode::PermissionDenied => "permission denied",
+            ErrorCode::NotPresent => "not present",
+            ErrorCode::Unsupported => "unsupported",
+            ErrorCode::Unavailable => "unavailable",
+            ErrorCode::Transient => "transient",
+            ErrorCode::Timeout => "timeout",
+            ErrorCode::InvalidData => "invalid data",
+            ErrorCode::InvalidInput => "invalid input",
+            ErrorCode::DriverError => "driver error",
+            ErrorCode::Io => "I/O error",
+            ErrorCode::Serialization => "serialization error",
+            ErrorCode::Unknown => "unknown",
+        };
+        f.write_str(&crate::l10n::localize("ErrorCode", text))
+    }
+}
+
 /// Error types that can occur during hardware querying
 #[derive(Debug, thiserror::Error)]
 pub enum HardwareQueryError {
@@ -28,6 +191,25 @@ pub enum HardwareQueryError {
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
 
+    /// YAML serialization error
+    #[cfg(feature = "yaml")]
+    #[error("YAML serialization error: {0}")]
+    YamlError(#[from] serde_yaml::Error),
+
+    /// MessagePack serialization error
+    #[cfg(feature = "msgpack")]
+    #[error("MessagePack serialization error: {0}")]
+    MessagePackError(String),
+
+    /// SQLite snapshot store error
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    SqliteError(#[from] rusqlite::Error),
+
+    /// Serialized data uses a schema version this library cannot read
+    #[error("Unsupported schema version: {found} (supported up to {supported})")]
+    UnsupportedSchemaVersion { found: u32, supported: u32 },
+
     /// WMI error (Windows only)
     #[cfg(target_os = "windows")]
     #[error("WMI error: {0}")]
@@ -60,9 +242,119 @@ pub enum HardwareQueryError {
     /// Unknown error
     #[error("Unknown error: {0}")]
     Unknown(String),
+
+    /// Failure with its component, reason and underlying cause
+    #[error("{component} error ({code}): {message}")]
+    Component {
+        /// Component or backend that failed
+        component: ErrorComponent,
+        /// Why it failed
+        code: ErrorCode,
+        /// Description of the problem
+        message: String,
+        /// Underlying error, if any
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    },
 }
 
 impl HardwareQueryError {
+    /// Create a structured error
+    pub fn new(component: ErrorComponent, code: ErrorCode, message: impl Into<String>) -> Self {
+        Self::Component {
+            component,
+            code,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Create a structured error wrapping its cause
+    pub fn with_source(
+        component: ErrorComponent,
+        code: ErrorCode,
+        message: impl Into<String>,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    ) -> Self {
+        Self::Component {
+            component,
+            code,
+            message: message.into(),
+            source: Some(source.into()),
+        }
+    }
+
+    /// Create a structured error from an I/O error, deriving the code from its kind
+    pub fn from_io(component: ErrorComponent, message: impl Into<String>, error: std::io::Error) -> Self {
+        Self::with_source(component, ErrorCode::from_io_kind(error.kind()), message, error)
+    }
+
+    /// Get the machine-readable reason for this error
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::SystemInfoUnavailable(_)
+            | Self::PowerManagementError(_)
+            | Self::VirtualizationError(_)
+            | Self::ThermalError(_) => ErrorCode::Unavailable,
+            Self::DeviceNotFound(_) => ErrorCode::NotPresent,
+            Self::PlatformNotSupported(_) | Self::UnsupportedSchemaVersion { .. } => ErrorCode::Unsupported,
+            Self::PermissionDenied(_) => ErrorCode::PermissionDenied,
+            Self::IoError(e) => ErrorCode::from_io_kind(e.kind()),
+            Self::SerializationError(_) => ErrorCode::Serialization,
+            #[cfg(feature = "yaml")]
+            Self::YamlError(_) => ErrorCode::Serialization,
+            #[cfg(feature = "msgpack")]
+            Self::MessagePackError(_) => ErrorCode::Serialization,
+            #[cfg(feature = "sqlite")]
+            Self::SqliteError(_) => ErrorCode::Io,
+            #[cfg(target_os = "windows")]
+            Self::WMIError(_) => ErrorCode::Unavailable,
+            Self::GPUDriverError(_) => ErrorCode::DriverError,
+            Self::InvalidConfiguration(_) => ErrorCode::InvalidInput,
+            Self::MonitoringError(_) | Self::Unknown(_) => ErrorCode::Unknown,
+            Self::Component { code, .. } => *code,
+        }
+    }
+
+    /// Get the component or backend this error came from, when known
+    pub fn component(&self) -> Option<ErrorComponent> {
+        match self {
+            Self::SerializationError(_) | Self::UnsupportedSchemaVersion { .. } => {
+                Some(ErrorComponent::Serialization)
+            }
+            #[cfg(feature = "yaml")]
+            Self::YamlError(_) => Some(ErrorComponent::Serialization),
+            #[cfg(feature = "msgpack")]
+            Self::MessagePackError(_) => Some(ErrorComponent::Serialization),
+            #[cfg(feature = "sqlite")]
+            Self::SqliteError(_) => Some(ErrorComponent::Serialization),
+            #[cfg(target_os = "windows")]
+            Self::WMIError(_) => Some(ErrorComponent::Wmi),
+            Self::GPUDriverError(_) => Some(ErrorComponent::Gpu),
+            Self::MonitoringError(_) => Some(ErrorComponent::Monitoring),
+            Self::PowerManagementError(_) => Some(ErrorComponent::Power),
+            Self::VirtualizationError(_) => Some(ErrorComponent::Virtualization),
+            Self::ThermalError(_) => Some(ErrorComponent::Thermal),
+            Self::Component { component, .. } => Some(*component),
+            _ => None,
+        }
+    }
+
+    /// Whether the failure is due to missing privileges
+    pub fn is_permission_denied(&self) -> bool {
+        self.code() == ErrorCode::PermissionDenied
+    }
+
+    /// Whether the hardware or interface is absent or unsupported on this machine
+    pub fn is_not_present(&self) -> bool {
+        matches!(self.code(), ErrorCode::NotPresent | ErrorCode::Unsupported)
+    }
+
+    /// Whether retrying later may succeed
+    pub fn is_transient(&self) -> bool {
+        matches!(self.code(), ErrorCode::Transient | ErrorCode::Timeout)
+    }
+
     pub fn system_info_unavailable(msg: impl Into<String>) -> Self {
         Self::SystemInfoUnavailable(msg.into())
     }
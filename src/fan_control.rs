@@ -0,0 +1,424 @@
+//! Fan speed control
+//!
+//! Enabled by the `fan-control` feature. [`FanController`] takes over fans
+//! reported as [`FanInfo::controllable`](crate::FanInfo::controllable): hwmon
+//! `pwmN` channels on Linux, and on Windows the `Win32_Fan.SetSpeed` method of
+//! machines whose embedded controller firmware implements it. Both need root
+//! or Administrator rights.
+//!
+//! Duty cycles are clamped to a floor (20% unless changed with
+//! [`with_minimum_duty`](FanController::with_minimum_duty)) so a fan isn't
+//! stopped by accident, and every fan the controller touched is handed back
+//! to its original mode and speed on [`restore`](FanController::restore) or
+//! when the controller is dropped.
+//!
+//! ```no_run
+//! use hardware_query::{CurvePoint, FanController, FanCurve, ThermalInfo};
+//!
+//! # fn main() -> hardware_query::Result<()> {
+//! let mut controller = FanController::open()?;
+//! let curve = FanCurve {
+//!     curve_points: vec![
+//!         CurvePoint { temperature: 40.0, speed_percent: 30.0 },
+//!         CurvePoint { temperature: 80.0, speed_percent: 100.0 },
+//!     ],
+//!     hysteresis: 3.0,
+//!     min_speed_percent: 30.0,
+//!     max_speed_percent: 100.0,
+//! };
+//! let fan = controller.fans()[0].name.clone();
+//! if let Some(temperature) = ThermalInfo::query()?.cpu_temperature() {
+//!     controller.apply_curve(&fan, &curve, temperature)?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{FanCurve, HardwareQueryError, Result};
+use std::collections::HashMap;
+
+/// Lowest duty cycle the controller sets unless configured otherwise
+const DEFAULT_MINIMUM_DUTY: f32 = 20.0;
+
+/// A fan the controller can drive
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControllableFan {
+    /// Fan name, matching [`FanInfo::name`](crate::FanInfo::name)
+    pub name: String,
+    /// Current duty cycle in percent, where the interface reports it
+    pub duty_percent: Option<f32>,
+    /// Maximum speed in RPM, where known
+    pub max_speed_rpm: Option<u32>,
+}
+
+/// Sets fan duty cycles and restores the original settings when dropped
+#[derive(Debug)]
+pub struct FanController {
+    fans: Vec<ControllableFan>,
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    backends: Vec<platform::Fan>,
+    minimum_duty: f32,
+    /// Temperature and duty of the last speed change made by a curve, per fan
+    curve_state: HashMap<String, (f32, f32)>,
+}
+
+impl FanController {
+    /// Take control of this machine's controllable fans
+    ///
+    /// Nothing is written until a duty cycle is set. Fails if there are no
+    /// controllable fans, or if the process lacks the rights to drive them.
+    pub fn open() -> Result<Self> {
+        #[cfg(any(target_os = "linux", target_os = "windows"))]
+        {
+            let backends = platform::fans()?;
+            if backends.is_empty() {
+                return Err(HardwareQueryError::device_not_found("No controllable fans found"));
+            }
+            Ok(Self {
+                fans: backends.iter().map(platform::Fan::info).collect(),
+                backends,
+                minimum_duty: DEFAULT_MINIMUM_DUTY,
+                curve_state: HashMap::new(),
+            })
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        {
+            Err(HardwareQueryError::platform_not_supported(
+                "Fan control is not supported on this platform",
+            ))
+        }
+    }
+
+    /// Set the lowest duty cycle the controller will apply (0-100)
+    ///
+    /// Setting 0 allows fans that support it to stop entirely.
+    pub fn with_minimum_duty(mut self, percent: f32) -> Self {
+        self.minimum_duty = percent.clamp(0.0, 100.0);
+        self
+    }
+
+    /// Set a fan's maximum speed, so duty cycles can be converted to RPM
+    ///
+    /// Only needed on Windows, where `Win32_Fan` takes a speed in RPM but
+    /// doesn't report the fan's maximum.
+    pub fn with_max_speed(mut self, fan: &str, rpm: u32) -> Self {
+        if let Some(info) = self.fans.iter_mut().find(|info| info.name == fan) {
+            info.max_speed_rpm = Some(rpm);
+        }
+        self
+    }
+
+    /// Get the fans this controller can drive
+    pub fn fans(&self) -> &[ControllableFan] {
+        &self.fans
+    }
+
+    /// Get the lowest duty cycle the controller applies
+    pub fn minimum_duty(&self) -> f32 {
+        self.minimum_duty
+    }
+
+    /// Set a fan's duty cycle in percent
+    ///
+    /// The value is clamped to the minimum duty and 100%. Returns the duty
+    /// cycle that was applied.
+    pub fn set_duty(&mut self, fan: &str, percent: f32) -> Result<f32> {
+        if !percent.is_finite() {
+            return Err(HardwareQueryError::invalid_configuration(format!(
+                "Invalid duty cycle {percent} for fan {fan}"
+            )));
+        }
+        let index = self.index(fan)?;
+        let duty = percent.clamp(self.minimum_duty, 100.0);
+
+        #[cfg(any(target_os = "linux", target_os = "windows"))]
+        self.backends[index].set_duty(duty, self.fans[index].max_speed_rpm)?;
+
+        self.fans[index].duty_percent = Some(duty);
+        self.curve_state.remove(fan);
+        Ok(duty)
+    }
+
+    /// Drive a fan from `curve` at the given temperature
+    ///
+    /// The speed only drops once the temperature has fallen by the curve's
+    /// hysteresis below the reading that last changed it. Returns the duty
+    /// cycle now in effect.
+    pub fn apply_curve(&mut self, fan: &str, curve: &FanCurve, temperature: f32) -> Result<f32> {
+        if let Some(&(last_temperature, last_duty)) = self.curve_state.get(fan) {
+            let target = curve.speed_at(temperature).max(self.minimum_duty);
+            if target < last_duty && last_temperature - temperature < curve.hysteresis {
+                return Ok(last_duty);
+            }
+            if (target - last_duty).abs() < f32::EPSILON {
+                return Ok(last_duty);
+            }
+        }
+        let duty = self.set_duty(fan, curve.speed_at(temperature))?;
+        self.curve_state.insert(fan.to_string(), (temperature, duty));
+        Ok(duty)
+    }
+
+    /// Return every fan to its original mode and speed
+    ///
+    /// Called automatically when the controller is dropped. The controller
+    /// can still be used afterwards.
+    pub fn restore(&mut self) -> Result<()> {
+        self.curve_state.clear();
+        #[cfg(any(target_os = "linux", target_os = "windows"))]
+        {
+            let mut first_error = None;
+            for backend in &mut self.backends {
+                if let Err(e) = backend.restore() {
+                    first_error.get_or_insert(e);
+                }
+            }
+            if let Some(e) = first_error {
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    fn index(&self, fan: &str) -> Result<usize> {
+        self.fans
+            .iter()
+            .position(|info| info.name == fan)
+            .ok_or_else(|| HardwareQueryError::device_not_found(format!("No controllable fan named {fan}")))
+    }
+}
+
+impl Drop for FanController {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::ControllableFan;
+    use crate::{ErrorComponent, HardwareQueryError, Result, ThermalInfo};
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// An hwmon `pwmN` channel
+    ///
+    /// `pwmN_enable` is 1 for manual control; other values select full speed
+    /// or the chip's automatic modes and are restored as they were found.
+    #[derive(Debug)]
+    pub(super) struct Fan {
+        name: String,
+        pwm: PathBuf,
+        enable: PathBuf,
+        max_speed_rpm: Option<u32>,
+        /// `pwmN_enable` and `pwmN` before the first write
+        original: Option<(String, String)>,
+    }
+
+    pub(super) fn fans() -> Result<Vec<Fan>> {
+        let mut fans = Vec::new();
+        let mut denied = false;
+        for (chip, dir) in ThermalInfo::hwmon_chips() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            let mut channels: Vec<u32> = entries
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    name.strip_prefix("pwm")?.strip_suffix("_enable")?.parse().ok()
+                })
+                .collect();
+            channels.sort_unstable();
+
+            for channel in channels {
+                let pwm = dir.join(format!("pwm{channel}"));
+                let enable = dir.join(format!("pwm{channel}_enable"));
+                if !pwm.exists() {
+                    continue;
+                }
+                // Same name as ThermalInfo's fan, which assumes pwmN drives fanN
+                let label = ThermalInfo::read_sysfs(&dir.join(format!("fan{channel}_label")))
+                    .unwrap_or_else(|| format!("fan{channel}"));
+                if fs::OpenOptions::new().write(true).open(&enable).is_err() {
+                    denied = true;
+                    continue;
+                }
+                fans.push(Fan {
+                    name: format!("{chip} {label}"),
+                    pwm,
+                    enable,
+                    max_speed_rpm: ThermalInfo::read_sysfs(&dir.join(format!("fan{channel}_max")))
+                        .and_then(|value| value.parse().ok())
+                        .filter(|rpm| *rpm > 0),
+                    original: None,
+                });
+            }
+        }
+
+        if fans.is_empty() && denied {
+            return Err(HardwareQueryError::permission_denied(
+                "Controlling hwmon fans requires root",
+            ));
+        }
+        Ok(fans)
+    }
+
+    impl Fan {
+        pub(super) fn info(&self) -> ControllableFan {
+            ControllableFan {
+                name: self.name.clone(),
+                duty_percent: ThermalInfo::read_sysfs(&self.pwm)
+                    .and_then(|value| value.parse::<f32>().ok())
+                    .map(|pwm| pwm / 255.0 * 100.0),
+                max_speed_rpm: self.max_speed_rpm,
+            }
+        }
+
+        pub(super) fn set_duty(&mut self, percent: f32, _max_speed_rpm: Option<u32>) -> Result<()> {
+            if self.original.is_none() {
+                let read = |path: &PathBuf| {
+                    ThermalInfo::read_sysfs(path).ok_or_else(|| {
+                        HardwareQueryError::system_info_unavailable(format!("Cannot read {}", path.display()))
+                    })
+                };
+                self.original = Some((read(&self.enable)?, read(&self.pwm)?));
+            }
+            write(&self.enable, "1")?;
+            write(&self.pwm, &((percent / 100.0 * 255.0).round() as u8).to_string())
+        }
+
+        pub(super) fn restore(&mut self) -> Result<()> {
+            let Some((enable, pwm)) = self.original.take() else {
+                return Ok(());
+            };
+            // Written while still in manual mode, so chips that ignore pwmN
+            // in automatic mode keep the original value for later
+            write(&self.pwm, &pwm)?;
+            write(&self.enable, &enable)
+        }
+    }
+
+    fn write(path: &PathBuf, value: &str) -> Result<()> {
+        fs::write(path, value).map_err(|e| {
+            let message = match e.kind() {
+                std::io::ErrorKind::PermissionDenied => format!("Writing {} requires root", path.display()),
+                _ => format!("Failed to write {}", path.display()),
+            };
+            HardwareQueryError::from_io(ErrorComponent::Thermal, message, e)
+        })
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::ControllableFan;
+    use crate::{HardwareQueryError, Result};
+    use serde::{Deserialize, Serialize};
+    use wmi::WMIConnection;
+
+    #[derive(Deserialize)]
+    #[serde(rename = "Win32_Fan")]
+    #[serde(rename_all = "PascalCase")]
+    struct Win32Fan {
+        #[serde(rename = "__Path")]
+        path: String,
+        name: Option<String>,
+        desired_speed: Option<u64>,
+        variable_speed: Option<bool>,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct SetSpeedInput {
+        desired_speed: u64,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct SetSpeedOutput {
+        return_value: u32,
+    }
+
+    /// A `Win32_Fan` instance whose firmware accepts `SetSpeed`
+    #[derive(Debug)]
+    pub(super) struct Fan {
+        name: String,
+        path: String,
+        /// `DesiredSpeed` before the first write
+        original_rpm: Option<u64>,
+        current_rpm: Option<u64>,
+    }
+
+    fn connection() -> Result<WMIConnection> {
+        Ok(WMIConnection::new(crate::probe::com_library()?)?)
+    }
+
+    pub(super) fn fans() -> Result<Vec<Fan>> {
+        let fans: Vec<Win32Fan> = connection()?.query()?;
+        Ok(fans
+            .into_iter()
+            .filter(|fan| fan.variable_speed == Some(true))
+            .map(|fan| Fan {
+                name: fan.name.unwrap_or_else(|| "System Fan".to_string()),
+                path: fan.path,
+                original_rpm: None,
+                current_rpm: fan.desired_speed,
+            })
+            .collect())
+    }
+
+    impl Fan {
+        pub(super) fn info(&self) -> ControllableFan {
+            ControllableFan {
+                name: self.name.clone(),
+                duty_percent: None,
+                max_speed_rpm: None,
+            }
+        }
+
+        pub(super) fn set_duty(&mut self, percent: f32, max_speed_rpm: Option<u32>) -> Result<()> {
+            let Some(max_speed_rpm) = max_speed_rpm else {
+                return Err(HardwareQueryError::invalid_configuration(format!(
+                    "Maximum speed of {} is unknown; set it with FanController::with_max_speed",
+                    self.name
+                )));
+            };
+            let rpm = (percent / 100.0 * max_speed_rpm as f32).round() as u64;
+            if self.original_rpm.is_none() {
+                self.original_rpm = self.current_rpm;
+            }
+            self.set_speed(rpm)
+        }
+
+        pub(super) fn restore(&mut self) -> Result<()> {
+            match self.original_rpm.take() {
+                Some(rpm) => self.set_speed(rpm),
+                None => Ok(()),
+            }
+        }
+
+        fn set_speed(&mut self, rpm: u64) -> Result<()> {
+            let output: SetSpeedOutput = connection()?.exec_instance_method::<Win32Fan, _>(
+                &self.path,
+                "SetSpeed",
+                SetSpeedInput { desired_speed: rpm },
+            )?;
+            match output.return_value {
+                0 => {
+                    self.current_rpm = Some(rpm);
+                    Ok(())
+                }
+                // 1 = not supported by the firmware, 5 = access denied
+                5 => Err(HardwareQueryError::permission_denied(format!(
+                    "Setting the speed of {} requires Administrator rights",
+                    self.name
+                ))),
+                code => Err(HardwareQueryError::platform_not_supported(format!(
+                    "Firmware rejected SetSpeed for {} (error {code})",
+                    self.name
+                ))),
+            }
+        }
+    }
+}
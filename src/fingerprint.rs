@@ -0,0 +1,264 @@
+//! Stable machine fingerprints
+//!
+//! [`HardwareInfo::fingerprint`](crate::HardwareInfo::fingerprint) hashes
+//! identifiers that survive reboots, OS reinstalls and driver updates (CPU
+//! model, board and disk serials, MAC addresses) into a deterministic ID.
+//! Only SHA-256 digests leave the machine, and a per-application salt keeps
+//! IDs from different vendors unlinkable.
+
+use crate::{BusType, HardwareInfo, HardwareQueryError, NetworkType, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Identifier that can contribute to a [`Fingerprint`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FingerprintSource {
+    /// CPU brand string
+    CpuModel,
+    /// Baseboard serial number
+    BoardSerial,
+    /// System (chassis) serial number
+    SystemSerial,
+    /// SMBIOS system UUID
+    SystemUuid,
+    /// MAC addresses of built-in Ethernet and WiFi adapters
+    MacAddresses,
+    /// Serial numbers of internal disks
+    DiskSerials,
+}
+
+impl FingerprintSource {
+    /// Every source, in hashing order
+    pub const ALL: [FingerprintSource; 6] = [
+        FingerprintSource::CpuModel,
+        FingerprintSource::BoardSerial,
+        FingerprintSource::SystemSerial,
+        FingerprintSource::SystemUuid,
+        FingerprintSource::MacAddresses,
+        FingerprintSource::DiskSerials,
+    ];
+
+    /// Stable name mixed into the hash
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FingerprintSource::CpuModel => "cpu_model",
+            FingerprintSource::BoardSerial => "board_serial",
+            FingerprintSource::SystemSerial => "system_serial",
+            FingerprintSource::SystemUuid => "system_uuid",
+            FingerprintSource::MacAddresses => "mac_addresses",
+            FingerprintSource::DiskSerials => "disk_serials",
+        }
+    }
+
+    /// Normalized values of this source, sorted and without duplicates
+    fn values(&self, info: &HardwareInfo) -> Vec<String> {
+        let values: BTreeSet<String> = match self {
+            FingerprintSource::CpuModel => {
+                let brand = info.cpu.brand.split_whitespace().collect::<Vec<_>>().join(" ");
+                std::iter::once(brand).collect()
+            }
+            FingerprintSource::BoardSerial => normalized(info.system.baseboard.serial_number.as_deref()),
+            FingerprintSource::SystemSerial => normalized(info.system.serial_number.as_deref()),
+            FingerprintSource::SystemUuid => normalized(info.system.uuid.as_deref()),
+            FingerprintSource::MacAddresses => info
+                .network_interfaces
+                .iter()
+                .filter(|interface| !interface.is_virtual)
+                .filter(|interface| {
+                    matches!(
+                        interface.network_type,
+                        NetworkType::Ethernet | NetworkType::WiFi | NetworkType::Unknown
+                    )
+                })
+                .filter_map(|interface| hardware_mac(&interface.mac_address))
+                .collect(),
+            FingerprintSource::DiskSerials => info
+                .physical_disks
+                .iter()
+                .filter(|disk| !disk.removable && !matches!(disk.bus, BusType::USB | BusType::Virtual))
+                .flat_map(|disk| normalized(disk.serial_number.as_deref()))
+                .collect(),
+        };
+        values.into_iter().filter(|value| !value.is_empty()).collect()
+    }
+}
+
+impl std::fmt::Display for FingerprintSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Which identifiers go into a fingerprint, and how they are salted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FingerprintOptions {
+    /// Identifiers to hash
+    pub sources: BTreeSet<FingerprintSource>,
+    /// Application-specific salt mixed into every hash
+    pub salt: Option<String>,
+}
+
+impl Default for FingerprintOptions {
+    fn default() -> Self {
+        Self {
+            sources: FingerprintSource::ALL.into_iter().collect(),
+            salt: None,
+        }
+    }
+}
+
+impl FingerprintOptions {
+    /// Every source, no salt
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hash only the given sources
+    pub fn only(sources: impl IntoIterator<Item = FingerprintSource>) -> Self {
+        Self {
+            sources: sources.into_iter().collect(),
+            salt: None,
+        }
+    }
+
+    /// Include a source
+    pub fn with_source(mut self, source: FingerprintSource) -> Self {
+        self.sources.insert(source);
+        self
+    }
+
+    /// Exclude a source
+    pub fn without_source(mut self, source: FingerprintSource) -> Self {
+        self.sources.remove(&source);
+        self
+    }
+
+    /// Set the salt
+    pub fn with_salt(mut self, salt: impl Into<String>) -> Self {
+        self.salt = Some(salt.into());
+        self
+    }
+}
+
+/// Deterministic machine ID with per-source digests
+///
+/// Besides the combined [`id`](Self::id), each contributing source keeps its
+/// own digest so a license check can tolerate a single replaced part (see
+/// [`matching_components`](Self::matching_components)).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    /// SHA-256 over all component digests, hex encoded
+    pub id: String,
+    /// SHA-256 of each source that had a value, hex encoded
+    pub components: BTreeMap<FingerprintSource, String>,
+}
+
+impl Fingerprint {
+    /// Fingerprint `info` using `options`
+    ///
+    /// Sources without a usable value on this machine are skipped. Fails if
+    /// none of the selected sources has a value.
+    pub fn compute(info: &HardwareInfo, options: &FingerprintOptions) -> Result<Self> {
+        let salt = options.salt.as_deref().unwrap_or("");
+
+        let components: BTreeMap<FingerprintSource, String> = options
+            .sources
+            .iter()
+            .filter_map(|source| {
+                let values = source.values(info);
+                if values.is_empty() {
+                    return None;
+                }
+                let mut hasher = salted(salt);
+                hasher.update(source.as_str().as_bytes());
+                for value in values {
+                    hasher.update([0]);
+                    hasher.update(value.as_bytes());
+                }
+                Some((*source, hex(&hasher.finalize())))
+            })
+            .collect();
+
+        if components.is_empty() {
+            return Err(HardwareQueryError::system_info_unavailable(
+                "None of the selected fingerprint sources are available",
+            ));
+        }
+
+        let mut hasher = salted(salt);
+        for (source, digest) in &components {
+            hasher.update(format!("{source}={digest}\n").as_bytes());
+        }
+
+        Ok(Self {
+            id: hex(&hasher.finalize()),
+            components,
+        })
+    }
+
+    /// Get the machine ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Sources that contributed to the ID
+    pub fn sources(&self) -> impl Iterator<Item = FingerprintSource> + '_ {
+        self.components.keys().copied()
+    }
+
+    /// Number of sources with the same digest in both fingerprints
+    pub fn matching_components(&self, other: &Fingerprint) -> usize {
+        self.components
+            .iter()
+            .filter(|(source, digest)| other.components.get(source) == Some(digest))
+            .count()
+    }
+}
+
+impl std::fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
+/// Hasher primed with a length-prefixed salt
+pub(crate) fn salted(salt: &str) -> Sha256 {
+    let mut hasher = Sha256::new();
+    hasher.update((salt.len() as u64).to_le_bytes());
+    hasher.update(salt.as_bytes());
+    hasher
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn normalized(value: Option<&str>) -> BTreeSet<String> {
+    value
+        .map(|value| value.trim().to_uppercase())
+        .into_iter()
+        .collect()
+}
+
+/// Canonical form of a burned-in MAC address
+///
+/// All-zero addresses and locally administered ones (randomized WiFi MACs,
+/// virtual adapters) are rejected because they change between boots.
+fn hardware_mac(mac: &str) -> Option<String> {
+    let octets: Vec<u8> = mac
+        .split([':', '-'])
+        .map(|octet| u8::from_str_radix(octet, 16).ok())
+        .collect::<Option<_>>()?;
+    if octets.len() != 6 || octets.iter().all(|&octet| octet == 0) || octets[0] & 0x02 != 0 {
+        return None;
+    }
+    Some(
+        octets
+            .iter()
+            .map(|octet| format!("{octet:02X}"))
+            .collect::<Vec<_>>()
+            .join(":"),
+    )
+}
@@ -1,10 +1,8 @@
 #![allow(dead_code)] // Many helper functions are for future implementation
 
-use crate::Result;
+use crate::{PCIDevice, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
 
 /// FPGA vendor information
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -161,63 +159,28 @@ impl FPGAInfo {
     fn detect_pcie_fpgas() -> Result<Vec<FPGAInfo>> {
         let mut fpgas = Vec::new();
         
-        #[cfg(target_os = "linux")]
-        {
-            // Read from /sys/bus/pci/devices
-            if let Ok(entries) = fs::read_dir("/sys/bus/pci/devices") {
-                for entry in entries.flatten() {
-                    if let Some(fpga) = Self::check_pci_device_for_fpga(&entry.path())? {
-                        fpgas.push(fpga);
-                    }
-                }
-            }
-        }
-        
-        #[cfg(target_os = "windows")]
-        {
-            // Use WMI to detect PCIe devices
-            fpgas.extend(Self::detect_fpgas_windows_wmi()?);
-        }
-        
-        #[cfg(target_os = "macos")]
-        {
-            // Use system_profiler to detect PCIe devices
-            fpgas.extend(Self::detect_fpgas_macos()?);
-        }
-        
-        Ok(fpgas)
-    }
-    
-    #[cfg(target_os = "linux")]
-    fn check_pci_device_for_fpga(device_path: &Path) -> Result<Option<FPGAInfo>> {
-        let vendor_id = Self::read_hex_file(&device_path.join("vendor"))?;
-        let device_id = Self::read_hex_file(&device_path.join("device"))?;
-        
-        if let (Some(vendor), Some(device)) = (vendor_id, device_id) {
-            // Check known FPGA vendor/device IDs
-            if let Some(fpga_info) = Self::identify_fpga_by_ids(vendor, device) {
-                return Ok(Some(fpga_info));
-            }
+        // Enumeration problems just mean no PCIe FPGAs are reported
+        for device in PCIDevice::query_all().unwrap_or_default() {
+            let (Some(vendor), Some(device_id)) = (device.vendor_id(), device.product_id()) else {
+                continue;
+            };
+            let (vendor, device_id) = (vendor as u32, device_id as u32);
             
-            // Check device class for FPGA-like devices
-            if let Some(class) = Self::read_hex_file(&device_path.join("class"))? {
+            // Check known FPGA vendor/device IDs
+            if let Some(fpga_info) = Self::identify_fpga_by_ids(vendor, device_id) {
+                fpgas.push(fpga_info);
+            } else if matches!(device.class_code, Some(0x120000) | Some(0x058000)) {
                 // Class 0x120000 is often used for FPGA devices
-                if class == 0x120000 || class == 0x058000 {
-                    return Ok(Some(Self::create_generic_fpga_info(vendor, device)));
-                }
+                fpgas.push(Self::create_generic_fpga_info(vendor, device_id));
             }
         }
         
-        Ok(None)
+        Ok(fpgas)
     }
     
-    fn read_hex_file(path: &Path) -> Result<Option<u32>> {
-        if let Ok(content) = fs::read_to_string(path) {
-            if let Ok(value) = u32::from_str_radix(content.trim().trim_start_matches("0x"), 16) {
-                return Ok(Some(value));
-            }
-        }
-        Ok(None)
+    /// Model name from the PCI ID database, or `fallback` if the device is not listed
+    fn pci_model_name(vendor_id: u32, device_id: u32, fallback: String) -> String {
+        PCIDevice::lookup_device_name(vendor_id as u16, device_id as u16).unwrap_or(fallback)
     }
     
     fn identify_fpga_by_ids(vendor_id: u32, device_id: u32) -> Option<FPGAInfo> {
@@ -247,7 +210,7 @@ impl FPGAInfo {
             0x4350 => (FPGAFamily::IntelAgilex, "Agilex F-Series", (2700000, 270000000, 5760, 1100)),
             _ => {
                 let family = FPGAFamily::Unknown(format!("Intel Device 0x{device_id:04X}"));
-                let model = format!("Intel FPGA Device 0x{device_id:04X}");
+                let model = Self::pci_model_name(0x1172, device_id, format!("Intel FPGA Device 0x{device_id:04X}"));
                 return FPGAInfo {
                     vendor: FPGAVendor::Intel,
                     family,
@@ -316,7 +279,7 @@ impl FPGAInfo {
             0x5008 => (FPGAFamily::XilinxVersal, "Versal Prime VP1202", (899000, 57600000, 1968, 1300)),
             _ => {
                 let family = FPGAFamily::Unknown(format!("Xilinx Device 0x{device_id:04X}"));
-                let model = format!("Xilinx FPGA Device 0x{device_id:04X}");
+                let model = Self::pci_model_name(0x10EE, device_id, format!("Xilinx FPGA Device 0x{device_id:04X}"));
                 return FPGAInfo {
                     vendor: FPGAVendor::Xilinx,
                     family,
@@ -378,7 +341,7 @@ impl FPGAInfo {
         FPGAInfo {
             vendor: FPGAVendor::Microsemi,
             family: FPGAFamily::MicrosemiPolarFire,
-            model: format!("Microsemi Device 0x{device_id:04X}"),
+            model: Self::pci_model_name(0x11F8, device_id, format!("Microsemi Device 0x{device_id:04X}")),
             device_id: Some(format!("0x{device_id:04X}")),
             vendor_id: Some("0x11F8".to_string()),
             interface: FPGAInterface::PCIe,
@@ -401,7 +364,7 @@ impl FPGAInfo {
         FPGAInfo {
             vendor: FPGAVendor::Lattice,
             family: FPGAFamily::LatticeECP5,
-            model: format!("Lattice Device 0x{device_id:04X}"),
+            model: Self::pci_model_name(0x1204, device_id, format!("Lattice Device 0x{device_id:04X}")),
             device_id: Some(format!("0x{device_id:04X}")),
             vendor_id: Some("0x1204".to_string()),
             interface: FPGAInterface::PCIe,
@@ -423,9 +386,11 @@ impl FPGAInfo {
     
     fn create_generic_fpga_info(vendor_id: u32, device_id: u32) -> FPGAInfo {
         FPGAInfo {
-            vendor: FPGAVendor::Unknown(format!("0x{vendor_id:04X}")),
+            vendor: FPGAVendor::Unknown(
+                PCIDevice::lookup_vendor_name(vendor_id as u16).unwrap_or_else(|| format!("0x{vendor_id:04X}")),
+            ),
             family: FPGAFamily::Unknown("Unknown".to_string()),
-            model: format!("FPGA Device 0x{vendor_id:04X}:0x{device_id:04X}"),
+            model: Self::pci_model_name(vendor_id, device_id, format!("FPGA Device 0x{vendor_id:04X}:0x{device_id:04X}")),
             device_id: Some(format!("0x{device_id:04X}")),
             vendor_id: Some(format!("0x{vendor_id:04X}")),
             interface: FPGAInterface::PCIe,
@@ -452,18 +417,6 @@ impl FPGAInfo {
         Ok(Vec::new())
     }
     
-    #[cfg(target_os = "windows")]
-    fn detect_fpgas_windows_wmi() -> Result<Vec<FPGAInfo>> {
-        // Placeholder for Windows WMI detection
-        Ok(Vec::new())
-    }
-    
-    #[cfg(target_os = "macos")]
-    fn detect_fpgas_macos() -> Result<Vec<FPGAInfo>> {
-        // Placeholder for macOS detection
-        Ok(Vec::new())
-    }
-    
     /// Calculate theoretical AI performance metrics for the FPGA
     pub fn calculate_ai_performance(&self) -> HashMap<String, f64> {
         let mut metrics = HashMap::new();
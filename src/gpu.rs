@@ -1,3 +1,5 @@
+use crate::gpu_api::{DirectXCapabilities, MetalDeviceInfo, OpenCLDeviceInfo, VulkanDeviceInfo};
+use crate::units::MIB;
 use crate::Result;
 use serde::{Deserialize, Serialize};
 
@@ -6,10 +8,219 @@ use nvml_wrapper::Nvml;
 
 // ROCm detection will be done via system calls
 #[cfg(target_os = "linux")]
-use std::process::Command;
+use crate::probe::Command;
 
 #[cfg(target_os = "windows")]
-use wmi::{COMLibrary, WMIConnection};
+use wmi::WMIConnection;
+
+/// Compare PCI addresses that may differ in domain width
+/// (NVML reports `00000000:01:00.0`, sysfs `0000:01:00.0`)
+pub(crate) fn same_pci_address(a: &str, b: &str) -> bool {
+    fn normalize(addr: &str) -> Option<(u32, String)> {
+        let (domain, rest) = addr.split_once(':')?;
+        let domain = u32::from_str_radix(domain, 16).ok()?;
+        Some((domain, rest.to_lowercase()))
+    }
+
+    match (normalize(a), normalize(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Fill in process names from the OS process table
+fn fill_process_names(processes: &mut [GPUProcess]) {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+    if processes.is_empty() {
+        return;
+    }
+    let pids: Vec<Pid> = processes.iter().map(|p| Pid::from_u32(p.pid)).collect();
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&pids), true, ProcessRefreshKind::nothing());
+    for process in processes.iter_mut() {
+        if let Some(info) = system.process(Pid::from_u32(process.pid)) {
+            process.name = info.name().to_string_lossy().into_owned();
+        }
+    }
+}
+
+/// A DRM client described by `/proc/<pid>/fdinfo/<fd>`
+#[cfg(target_os = "linux")]
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct DrmClient {
+    pub pdev: String,
+    pub client_id: u64,
+    pub vram_bytes: Option<u64>,
+    pub graphics: bool,
+    pub compute: bool,
+}
+
+/// Parse the DRM client usage stats the kernel appends to a GPU fd's `fdinfo`
+///
+/// Device memory is reported per region (`vram`, i915's `local0`) as
+/// `drm-resident-*`, `drm-total-*`, or amdgpu's older `drm-memory-*`.
+/// Engine busy time (`drm-engine-*`) or cycles (`drm-cycles-*`, xe) show
+/// whether the client submitted graphics or compute work.
+#[cfg(target_os = "linux")]
+pub(crate) fn parse_drm_fdinfo(text: &str) -> Option<DrmClient> {
+    fn bytes(value: &str) -> Option<u64> {
+        let mut parts = value.split_whitespace();
+        let amount: u64 = parts.next()?.parse().ok()?;
+        Some(match parts.next() {
+            Some("KiB") => amount * 1024,
+            Some("MiB") => amount * 1024 * 1024,
+            Some("GiB") => amount * 1024 * 1024 * 1024,
+            _ => amount,
+        })
+    }
+
+    let mut client = DrmClient::default();
+    let (mut has_driver, mut client_id) = (false, None);
+    let (mut resident, mut total, mut legacy) = (None::<u64>, None::<u64>, None::<u64>);
+
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "drm-driver" => has_driver = true,
+            "drm-pdev" => client.pdev = value.to_string(),
+            "drm-client-id" => client_id = value.parse().ok(),
+            key => {
+                if let Some((kind, region)) = key.strip_prefix("drm-").and_then(|k| k.split_once('-')) {
+                    let device_memory = region.starts_with("vram") || region.starts_with("local");
+                    match kind {
+                        "resident" if device_memory => *resident.get_or_insert(0) += bytes(value)?,
+                        "total" if device_memory => *total.get_or_insert(0) += bytes(value)?,
+                        "memory" if device_memory => *legacy.get_or_insert(0) += bytes(value)?,
+                        "engine" | "cycles" => {
+                            let busy = value.split_whitespace().next().and_then(|v| v.parse::<u64>().ok()).unwrap_or(0) > 0;
+                            if busy && matches!(region, "gfx" | "render" | "rcs") {
+                                client.graphics = true;
+                            } else if busy && (region.starts_with("compute") || region == "ccs") {
+                                client.compute = true;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    if !has_driver || client.pdev.is_empty() {
+        return None;
+    }
+    client.client_id = client_id?;
+    client.vram_bytes = resident.or(legacy).or(total);
+    Some(client)
+}
+
+/// Split a GPU performance counter instance name into (pid, luid, engine type)
+#[cfg(target_os = "windows")]
+fn parse_gpu_counter_instance(name: &str) -> Option<(u32, String, Option<String>)> {
+    // pid_1234_luid_0x00000000_0x0000D1B5_phys_0_eng_0_engtype_3D
+    let rest = name.strip_prefix("pid_")?;
+    let (pid, rest) = rest.split_once('_')?;
+    let luid_start = rest.find("luid_")?;
+    let luid = rest.get(luid_start..luid_start + "luid_0x00000000_0x00000000".len())?;
+    let engine = rest.split_once("engtype_").map(|(_, engine)| engine.to_string());
+    Some((pid.parse().ok()?, luid.to_string(), engine))
+}
+
+/// Intel GPUs with marketing names that pci.ids doesn't carry (or carries as
+/// codenames), with their type and stock VRAM in MB (0 for integrated)
+const INTEL_GPU_MODELS: &[(u16, &str, GPUType, u64)] = &[
+    (0x4905, "Intel Iris Xe MAX", GPUType::Discrete, 4096),
+    (0x5690, "Intel Arc A770M", GPUType::Discrete, 16384),
+    (0x5691, "Intel Arc A730M", GPUType::Discrete, 12288),
+    (0x5692, "Intel Arc A550M", GPUType::Discrete, 8192),
+    (0x5693, "Intel Arc A370M", GPUType::Discrete, 4096),
+    (0x5694, "Intel Arc A350M", GPUType::Discrete, 4096),
+    (0x56a0, "Intel Arc A770", GPUType::Discrete, 16384),
+    (0x56a1, "Intel Arc A750", GPUType::Discrete, 8192),
+    (0x56a2, "Intel Arc A580", GPUType::Discrete, 8192),
+    (0x56a5, "Intel Arc A380", GPUType::Discrete, 6144),
+    (0x56a6, "Intel Arc A310", GPUType::Discrete, 4096),
+    (0x56b1, "Intel Arc Pro A40/A50", GPUType::Workstation, 6144),
+    (0x56b3, "Intel Arc Pro A60", GPUType::Workstation, 12288),
+    (0x56c0, "Intel Data Center GPU Flex 170", GPUType::Datacenter, 16384),
+    (0x56c1, "Intel Data Center GPU Flex 140", GPUType::Datacenter, 12288),
+    (0x0bd5, "Intel Data Center GPU Max 1550", GPUType::Datacenter, 131072),
+    (0x0bda, "Intel Data Center GPU Max 1100", GPUType::Datacenter, 49152),
+    (0xe20b, "Intel Arc B580", GPUType::Discrete, 12288),
+    (0xe20c, "Intel Arc B570", GPUType::Discrete, 10240),
+    (0x64a0, "Intel Arc 140V", GPUType::Integrated, 0),
+    (0x7d55, "Intel Arc Graphics", GPUType::Integrated, 0),
+    (0x7dd5, "Intel Arc Graphics", GPUType::Integrated, 0),
+];
+
+/// Check whether an Intel PCI device ID belongs to a discrete GPU family
+/// (DG1, Alchemist, Battlemage, Ponte Vecchio)
+fn is_intel_discrete(device_id: u16) -> bool {
+    matches!(device_id, 0x4905..=0x4909 | 0x5690..=0x56cf | 0xe202..=0xe21f | 0x0bd0..=0x0bdb)
+}
+
+/// Check for the Level Zero loader and Intel's GPU backend for it
+fn level_zero_installed() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        let dirs = shared_library_dirs();
+        ["libze_loader.so.1", "libze_intel_gpu.so.1"]
+            .iter()
+            .all(|lib| dirs.iter().any(|dir| dir.join(lib).exists()))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        // The Intel graphics driver installs the loader alongside its backend
+        let root = std::env::var_os("SystemRoot").unwrap_or_else(|| "C:\\Windows".into());
+        std::path::Path::new(&root).join("System32").join("ze_loader.dll").exists()
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    {
+        false
+    }
+}
+
+/// Check for an Intel oneAPI toolkit installation
+fn oneapi_installed() -> bool {
+    if std::env::var_os("ONEAPI_ROOT").is_some_and(|root| std::path::Path::new(&root).is_dir()) {
+        return true;
+    }
+    #[cfg(target_os = "linux")]
+    {
+        std::path::Path::new("/opt/intel/oneapi").is_dir()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        std::path::Path::new("C:\\Program Files (x86)\\Intel\\oneAPI").is_dir()
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    {
+        false
+    }
+}
+
+/// Directories searched for shared libraries: `LD_LIBRARY_PATH`, then the usual system paths
+#[cfg(target_os = "linux")]
+fn shared_library_dirs() -> Vec<std::path::PathBuf> {
+    let mut dirs: Vec<std::path::PathBuf> = std::env::var_os("LD_LIBRARY_PATH")
+        .map(|paths| std::env::split_paths(&paths).collect())
+        .unwrap_or_default();
+    dirs.extend(
+        [
+            "/usr/lib/x86_64-linux-gnu",
+            "/usr/lib/aarch64-linux-gnu",
+            "/usr/lib64",
+            "/usr/lib",
+            "/usr/local/lib",
+        ]
+        .map(std::path::PathBuf::from),
+    );
+    dirs
+}
 
 /// GPU vendor information
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -37,6 +248,24 @@ impl std::fmt::Display for GPUVendor {
     }
 }
 
+impl GPUVendor {
+    /// Classify a GPU by its device name as reported by Metal
+    pub fn from_device_name(name: &str) -> Self {
+        let lower = name.to_lowercase();
+        if lower.starts_with("apple") {
+            GPUVendor::Apple
+        } else if lower.contains("amd") || lower.contains("radeon") {
+            GPUVendor::AMD
+        } else if lower.contains("intel") {
+            GPUVendor::Intel
+        } else if lower.contains("nvidia") || lower.contains("geforce") {
+            GPUVendor::NVIDIA
+        } else {
+            GPUVendor::Unknown(name.to_string())
+        }
+    }
+}
+
 /// GPU type classification
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GPUType {
@@ -86,6 +315,105 @@ pub struct ComputeCapabilities {
     pub compute_units: Option<u32>,
     /// Maximum workgroup size
     pub max_workgroup_size: Option<u32>,
+    /// Highest supported Metal GPU family (e.g. "Apple9", "Mac2")
+    #[serde(default)]
+    pub metal_family: Option<String>,
+    /// Vulkan device details (requires the `vulkan` feature)
+    #[serde(default)]
+    pub vulkan_device: Option<VulkanDeviceInfo>,
+    /// OpenCL device details (requires the `opencl` feature)
+    #[serde(default)]
+    pub opencl_device: Option<OpenCLDeviceInfo>,
+    /// Metal device details (macOS)
+    #[serde(default)]
+    pub metal_device: Option<MetalDeviceInfo>,
+    /// Intel Level Zero runtime (loader and GPU driver) installed
+    #[serde(default)]
+    pub level_zero: bool,
+    /// Intel oneAPI toolkit installed
+    #[serde(default)]
+    pub oneapi: bool,
+}
+
+/// Kind of work a process submits to a GPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GPUContextType {
+    /// Compute (CUDA, ROCm, OpenCL, compute queues)
+    Compute,
+    /// Graphics / 3D rendering
+    Graphics,
+    /// Both compute and graphics contexts
+    ComputeAndGraphics,
+    /// Holds GPU memory but no engine activity was observed
+    Unknown,
+}
+
+impl GPUContextType {
+    fn from_flags(graphics: bool, compute: bool) -> Self {
+        match (graphics, compute) {
+            (true, true) => GPUContextType::ComputeAndGraphics,
+            (true, false) => GPUContextType::Graphics,
+            (false, true) => GPUContextType::Compute,
+            (false, false) => GPUContextType::Unknown,
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        let graphics = |t: Self| matches!(t, GPUContextType::Graphics | GPUContextType::ComputeAndGraphics);
+        let compute = |t: Self| matches!(t, GPUContextType::Compute | GPUContextType::ComputeAndGraphics);
+        Self::from_flags(graphics(self) || graphics(other), compute(self) || compute(other))
+    }
+}
+
+impl std::fmt::Display for GPUContextType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GPUContextType::Compute => write!(f, "Compute"),
+            GPUContextType::Graphics => write!(f, "Graphics"),
+            GPUContextType::ComputeAndGraphics => write!(f, "Compute+Graphics"),
+            GPUContextType::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// A process holding a context on a GPU
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GPUProcess {
+    /// Process ID
+    pub pid: u32,
+    /// Process name
+    pub name: String,
+    /// Device memory held by the process in MB (None if the driver doesn't report it)
+    pub used_memory_mb: Option<u64>,
+    /// Compute and/or graphics context
+    pub context_type: GPUContextType,
+}
+
+impl GPUProcess {
+    /// Get process ID
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Get process name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get device memory used in MB
+    pub fn used_memory_mb(&self) -> Option<u64> {
+        self.used_memory_mb
+    }
+
+    /// Get device memory used in bytes (MiB granularity)
+    pub fn used_memory_bytes(&self) -> Option<u64> {
+        self.used_memory_mb.map(|mb| mb * MIB)
+    }
+
+    /// Get context type
+    pub fn context_type(&self) -> GPUContextType {
+        self.context_type
+    }
 }
 
 /// GPU information and specifications
@@ -133,6 +461,20 @@ pub struct GPUInfo {
     pub pci_device_id: Option<String>,
     /// PCI subsystem ID
     pub pci_subsystem_id: Option<String>,
+    /// PCI bus address (domain:bus:device.function)
+    pub pci_bus_id: Option<String>,
+    /// GPU shares system memory with the CPU (Apple Silicon, most integrated GPUs)
+    #[serde(default)]
+    pub unified_memory: bool,
+    /// Direct3D 12 feature level and DirectX 12 Ultimate support (Windows, `directx` feature)
+    #[serde(default)]
+    pub directx: Option<DirectXCapabilities>,
+    /// Hardware encode/decode codec matrix reported by the platform video API
+    #[serde(default)]
+    pub video: Option<crate::VideoCapabilities>,
+    /// Resizable BAR and Above 4G Decoding state (discrete GPUs)
+    #[serde(default)]
+    pub resizable_bar: Option<crate::ResizableBar>,
 }
 
 impl GPUInfo {
@@ -149,8 +491,18 @@ impl GPUInfo {
         if let Ok(nvidia_gpus) = Self::query_nvidia_gpus() {
             // Merge NVIDIA-specific details with WMI results
             for nvidia_gpu in nvidia_gpus {
-                // Check if we already have this GPU from WMI
-                if let Some(existing) = gpus.iter_mut().find(|g| 
+                // Check if we already have this GPU from WMI or sysfs, preferring an
+                // exact PCI address match (NVML names and memory are authoritative)
+                if let Some(existing) = gpus.iter_mut().find(|g| {
+                    g.vendor == GPUVendor::NVIDIA && Self::same_pci_bus(&g.pci_bus_id, &nvidia_gpu.pci_bus_id)
+                }) {
+                    *existing = Self {
+                        driver_version: nvidia_gpu.driver_version.clone().or(existing.driver_version.take()),
+                        pci_device_id: existing.pci_device_id.take(),
+                        pci_subsystem_id: existing.pci_subsystem_id.take(),
+                        ..nvidia_gpu
+                    };
+                } else if let Some(existing) = gpus.iter_mut().find(|g| 
                     g.vendor == GPUVendor::NVIDIA && 
                     g.model_name.contains("RTX") == nvidia_gpu.model_name.contains("RTX")
                 ) {
@@ -162,6 +514,7 @@ impl GPUInfo {
                     existing.shader_units = nvidia_gpu.shader_units;
                     existing.rt_cores = nvidia_gpu.rt_cores;
                     existing.tensor_cores = nvidia_gpu.tensor_cores;
+                    existing.video = nvidia_gpu.video;
                 } else {
                     // Add as new GPU if not found in WMI results
                     gpus.push(nvidia_gpu);
@@ -172,7 +525,20 @@ impl GPUInfo {
         if let Ok(amd_gpus) = Self::query_amd_gpus() {
             // Similar merge logic for AMD GPUs
             for amd_gpu in amd_gpus {
-                if !gpus.iter().any(|g| g.vendor == GPUVendor::AMD && g.model_name == amd_gpu.model_name) {
+                if let Some(existing) = gpus.iter_mut().find(|g| {
+                    g.vendor == GPUVendor::AMD
+                        && (Self::same_pci_bus(&g.pci_bus_id, &amd_gpu.pci_bus_id)
+                            || g.model_name == amd_gpu.model_name)
+                }) {
+                    // Keep the generic entry, but take the live metrics from amdgpu/ROCm
+                    existing.compute_capabilities.rocm = amd_gpu.compute_capabilities.rocm;
+                    existing.usage_percent = amd_gpu.usage_percent.or(existing.usage_percent);
+                    existing.temperature = amd_gpu.temperature.or(existing.temperature);
+                    existing.power_consumption = amd_gpu.power_consumption.or(existing.power_consumption);
+                    existing.power_limit = amd_gpu.power_limit.or(existing.power_limit);
+                    existing.boost_clock = amd_gpu.boost_clock.or(existing.boost_clock);
+                    existing.memory_clock = amd_gpu.memory_clock.or(existing.memory_clock);
+                } else {
                     gpus.push(amd_gpu);
                 }
             }
@@ -181,12 +547,28 @@ impl GPUInfo {
         if let Ok(intel_gpus) = Self::query_intel_gpus() {
             // Similar merge logic for Intel GPUs
             for intel_gpu in intel_gpus {
-                if !gpus.iter().any(|g| g.vendor == GPUVendor::Intel && g.model_name == intel_gpu.model_name) {
+                if let Some(existing) = gpus.iter_mut().find(|g| {
+                    g.vendor == GPUVendor::Intel && Self::same_pci_bus(&g.pci_bus_id, &intel_gpu.pci_bus_id)
+                }) {
+                    // The i915/xe entry is the same sysfs device with the model decoded
+                    *existing = intel_gpu;
+                } else if let Some(existing) = gpus
+                    .iter_mut()
+                    .find(|g| g.vendor == GPUVendor::Intel && g.model_name == intel_gpu.model_name)
+                {
+                    existing.compute_capabilities.level_zero = intel_gpu.compute_capabilities.level_zero;
+                    existing.compute_capabilities.oneapi = intel_gpu.compute_capabilities.oneapi;
+                } else {
                     gpus.push(intel_gpu);
                 }
             }
         }
 
+        crate::gpu_api::apply_probes(&mut gpus);
+        crate::video::apply_probes(&mut gpus);
+        crate::rebar::apply_probes(&mut gpus);
+        crate::gpu_specs::apply_specs(&mut gpus);
+
         // If still no GPUs found, return a placeholder
         if gpus.is_empty() {
             gpus.push(Self::default_gpu());
@@ -195,6 +577,14 @@ impl GPUInfo {
         Ok(gpus)
     }
 
+    /// Compare optional PCI addresses (see [`same_pci_address`])
+    fn same_pci_bus(a: &Option<String>, b: &Option<String>) -> bool {
+        match (a, b) {
+            (Some(a), Some(b)) => same_pci_address(a, b),
+            _ => false,
+        }
+    }
+
     /// Get GPU vendor
     pub fn vendor(&self) -> &GPUVendor {
         &self.vendor
@@ -205,6 +595,45 @@ impl GPUInfo {
         &self.model_name
     }
 
+    /// Relative gaming performance from the GPU performance database
+    ///
+    /// Returns `None` for models the database does not know.
+    pub fn performance_entry(&self) -> Option<crate::GPUPerformanceEntry> {
+        crate::GPUPerformanceDatabase::global()
+            .lookup(&self.model_name)
+            .cloned()
+    }
+
+    /// Get published specifications from the GPU specs database
+    pub fn specs(&self) -> Option<crate::GPUSpecs> {
+        crate::GPUSpecsDatabase::global().lookup(&self.model_name).cloned()
+    }
+
+    /// Get theoretical peak FP32 throughput in TFLOPS
+    pub fn fp32_tflops(&self) -> Option<f64> {
+        Some(self.specs()?.fp32_tflops())
+    }
+
+    /// Get theoretical peak dense FP16 throughput in TFLOPS (tensor/matrix units where present)
+    pub fn fp16_tflops(&self) -> Option<f64> {
+        self.specs()?.fp16_tflops()
+    }
+
+    /// Get theoretical peak dense INT8 throughput in TOPS
+    pub fn int8_tops(&self) -> Option<f64> {
+        self.specs()?.int8_tops()
+    }
+
+    /// Get the tensor/matrix unit generation (`4th gen Tensor Cores`, `Xe-HPG XMX`)
+    pub fn tensor_core_generation(&self) -> Option<&'static str> {
+        self.specs()?.tensor_core_generation()
+    }
+
+    /// Gaming performance tier, if the model is in the performance database
+    pub fn performance_tier(&self) -> Option<crate::GPUPerformanceTier> {
+        self.performance_entry().map(|entry| entry.tier())
+    }
+
     /// Get GPU type
     pub fn gpu_type(&self) -> &GPUType {
         &self.gpu_type
@@ -220,6 +649,11 @@ impl GPUInfo {
         self.memory_mb
     }
 
+    /// Get GPU memory in bytes (MiB granularity)
+    pub fn memory_bytes(&self) -> u64 {
+        self.memory_mb * MIB
+    }
+
     /// Check if GPU supports CUDA
     pub fn supports_cuda(&self) -> bool {
         self.compute_capabilities.cuda.is_some()
@@ -255,6 +689,61 @@ impl GPUInfo {
         self.compute_capabilities.metal
     }
 
+    /// Check if GPU supports Intel Level Zero
+    pub fn supports_level_zero(&self) -> bool {
+        self.compute_capabilities.level_zero
+    }
+
+    /// Check if the Intel oneAPI toolkit is available for this GPU
+    pub fn supports_oneapi(&self) -> bool {
+        self.compute_capabilities.oneapi
+    }
+
+    /// Check if the GPU shares system memory with the CPU
+    pub fn has_unified_memory(&self) -> bool {
+        self.unified_memory
+    }
+
+    /// Get the hardware video encode/decode engine
+    pub fn video_engine(&self) -> Option<crate::VideoEngine> {
+        crate::VideoEngine::for_gpu(self)
+    }
+
+    /// Get the encode/decode codec matrix reported by the platform video API
+    pub fn video_capabilities(&self) -> Option<&crate::VideoCapabilities> {
+        self.video.as_ref()
+    }
+
+    /// Get the Resizable BAR state
+    pub fn resizable_bar(&self) -> Option<&crate::ResizableBar> {
+        self.resizable_bar.as_ref()
+    }
+
+    /// Get the highest supported Metal GPU family (macOS)
+    pub fn metal_family(&self) -> Option<&str> {
+        self.compute_capabilities.metal_family.as_deref()
+    }
+
+    /// Get Vulkan device details (requires the `vulkan` feature)
+    pub fn vulkan_device(&self) -> Option<&VulkanDeviceInfo> {
+        self.compute_capabilities.vulkan_device.as_ref()
+    }
+
+    /// Get OpenCL device details (requires the `opencl` feature)
+    pub fn opencl_device(&self) -> Option<&OpenCLDeviceInfo> {
+        self.compute_capabilities.opencl_device.as_ref()
+    }
+
+    /// Get Metal device details (macOS)
+    pub fn metal_device(&self) -> Option<&MetalDeviceInfo> {
+        self.compute_capabilities.metal_device.as_ref()
+    }
+
+    /// Get DirectX capabilities (Windows, requires the `directx` feature)
+    pub fn directx(&self) -> Option<&DirectXCapabilities> {
+        self.directx.as_ref()
+    }
+
     /// Get current GPU usage percentage
     pub fn usage_percent(&self) -> Option<f32> {
         self.usage_percent
@@ -265,6 +754,265 @@ impl GPUInfo {
         self.temperature
     }
 
+    /// Processes currently using this GPU, largest memory user first
+    ///
+    /// Queried live. NVIDIA GPUs use NVML's compute and graphics process lists;
+    /// otherwise Linux reads DRM `fdinfo` (amdgpu, i915, xe) and Windows reads
+    /// the GPU process memory and engine performance counters. Only processes
+    /// the caller may inspect are listed.
+    pub fn processes(&self) -> Vec<GPUProcess> {
+        let mut processes = self.query_processes();
+        processes.sort_by(|a, b| {
+            b.used_memory_mb
+                .cmp(&a.used_memory_mb)
+                .then(a.pid.cmp(&b.pid))
+        });
+        processes
+    }
+
+    fn query_processes(&self) -> Vec<GPUProcess> {
+        #[cfg(feature = "nvidia")]
+        if self.vendor == GPUVendor::NVIDIA {
+            if let Some(processes) = self.nvml_processes() {
+                return processes;
+            }
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            self.drm_processes()
+        }
+        #[cfg(target_os = "windows")]
+        {
+            self.windows_processes()
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        {
+            Vec::new()
+        }
+    }
+
+    #[cfg(feature = "nvidia")]
+    fn nvml_processes(&self) -> Option<Vec<GPUProcess>> {
+        use nvml_wrapper::enums::device::UsedGpuMemory;
+
+        let nvml = Nvml::init().ok()?;
+        let device = (0..nvml.device_count().ok()?)
+            .filter_map(|i| nvml.device_by_index(i).ok())
+            .find(|device| match &self.pci_bus_id {
+                Some(bus_id) => device
+                    .pci_info()
+                    .is_ok_and(|pci| same_pci_address(&pci.bus_id, bus_id)),
+                None => device.name().is_ok_and(|name| name == self.model_name),
+            })?;
+
+        let lists = [
+            (device.running_compute_processes(), GPUContextType::Compute),
+            (device.running_graphics_processes(), GPUContextType::Graphics),
+        ];
+        let mut processes: Vec<GPUProcess> = Vec::new();
+        for (list, context_type) in lists {
+            for info in list.unwrap_or_default() {
+                // Unavailable under WDDM, where Windows manages GPU memory
+                let used_memory_mb = match info.used_gpu_memory {
+                    UsedGpuMemory::Used(bytes) => Some(bytes / (1024 * 1024)),
+                    UsedGpuMemory::Unavailable => None,
+                };
+                match processes.iter_mut().find(|p| p.pid == info.pid) {
+                    Some(existing) => {
+                        existing.context_type = existing.context_type.merge(context_type);
+                        existing.used_memory_mb = existing.used_memory_mb.max(used_memory_mb);
+                    }
+                    None => processes.push(GPUProcess {
+                        pid: info.pid,
+                        name: String::new(),
+                        used_memory_mb,
+                        context_type,
+                    }),
+                }
+            }
+        }
+
+        fill_process_names(&mut processes);
+        for process in processes.iter_mut().filter(|p| p.name.is_empty()) {
+            process.name = nvml.sys_process_name(process.pid, 256).unwrap_or_default();
+        }
+        Some(processes)
+    }
+
+    /// Sum DRM `fdinfo` usage of the clients open on this GPU, per process
+    #[cfg(target_os = "linux")]
+    fn drm_processes(&self) -> Vec<GPUProcess> {
+        use std::collections::{HashMap, HashSet};
+        use std::fs;
+
+        let Some(bus_id) = self.pci_bus_id.as_deref() else {
+            return Vec::new();
+        };
+        let Ok(proc_entries) = fs::read_dir("/proc") else {
+            return Vec::new();
+        };
+
+        let mut processes: HashMap<u32, GPUProcess> = HashMap::new();
+        for proc_entry in proc_entries.flatten() {
+            let Some(pid) = proc_entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+                continue;
+            };
+            let Ok(fds) = fs::read_dir(proc_entry.path().join("fd")) else {
+                continue;
+            };
+
+            // A client opened several times (dup'd fds) is counted once
+            let mut seen_clients = HashSet::new();
+            for fd in fds.flatten() {
+                let is_drm = fs::read_link(fd.path()).is_ok_and(|target| target.starts_with("/dev/dri"));
+                if !is_drm {
+                    continue;
+                }
+                let Ok(info) = fs::read_to_string(proc_entry.path().join("fdinfo").join(fd.file_name())) else {
+                    continue;
+                };
+                let Some(client) = parse_drm_fdinfo(&info) else {
+                    continue;
+                };
+                if !same_pci_address(&client.pdev, bus_id) || !seen_clients.insert(client.client_id) {
+                    continue;
+                }
+
+                let process = processes.entry(pid).or_insert_with(|| GPUProcess {
+                    pid,
+                    name: String::new(),
+                    used_memory_mb: None,
+                    context_type: GPUContextType::Unknown,
+                });
+                if let Some(bytes) = client.vram_bytes {
+                    *process.used_memory_mb.get_or_insert(0) += bytes / (1024 * 1024);
+                }
+                process.context_type = process
+                    .context_type
+                    .merge(GPUContextType::from_flags(client.graphics, client.compute));
+            }
+        }
+
+        let mut processes: Vec<GPUProcess> = processes.into_values().collect();
+        fill_process_names(&mut processes);
+        processes
+    }
+
+    /// Read per-process GPU memory and engine use from the GPU performance counters
+    #[cfg(target_os = "windows")]
+    fn windows_processes(&self) -> Vec<GPUProcess> {
+        use std::collections::HashMap;
+        use wmi::Variant;
+
+        let Ok(wmi_con) = crate::probe::com_library().and_then(|com| Ok(WMIConnection::new(com)?)) else {
+            return Vec::new();
+        };
+        let query = |wql: &str| -> Vec<HashMap<String, Variant>> { wmi_con.raw_query(wql).unwrap_or_default() };
+        let number = |row: &HashMap<String, Variant>, key: &str| match row.get(key) {
+            Some(Variant::UI8(v)) => Some(*v),
+            Some(Variant::UI4(v)) => Some(*v as u64),
+            // Perf counters of type 64-bit are returned as strings
+            Some(Variant::String(v)) => v.parse().ok(),
+            _ => None,
+        };
+        let name = |row: &HashMap<String, Variant>| match row.get("Name") {
+            Some(Variant::String(name)) => Some(name.clone()),
+            _ => None,
+        };
+
+        let memory = query(
+            "SELECT Name, DedicatedUsage FROM Win32_PerfFormattedData_GPUPerformanceCounters_GPUProcessMemory",
+        );
+        let engines = query(
+            "SELECT Name, UtilizationPercentage FROM Win32_PerfFormattedData_GPUPerformanceCounters_GPUEngine",
+        );
+
+        // Counter instances are named pid_<pid>_luid_0x<high>_0x<low>_phys_0[_eng_<n>_engtype_<type>]
+        let luids: std::collections::HashSet<String> = memory
+            .iter()
+            .filter_map(|row| name(row).and_then(|n| parse_gpu_counter_instance(&n)).map(|i| i.1))
+            .collect();
+        let Some(luid) = self.dxgi_luid().or_else(|| {
+            // Without a DXGI match, a single adapter is unambiguous
+            (luids.len() == 1).then(|| luids.iter().next().cloned()).flatten()
+        }) else {
+            return Vec::new();
+        };
+
+        let mut processes: HashMap<u32, GPUProcess> = HashMap::new();
+        for row in &memory {
+            let Some((pid, instance_luid, _)) = name(row).and_then(|n| parse_gpu_counter_instance(&n)) else {
+                continue;
+            };
+            let used_bytes = number(row, "DedicatedUsage").unwrap_or(0);
+            if !instance_luid.eq_ignore_ascii_case(&luid) || used_bytes == 0 {
+                continue;
+            }
+            processes.insert(
+                pid,
+                GPUProcess {
+                    pid,
+                    name: String::new(),
+                    used_memory_mb: Some(used_bytes / (1024 * 1024)),
+                    context_type: GPUContextType::Unknown,
+                },
+            );
+        }
+        for row in &engines {
+            let Some((pid, instance_luid, engine)) = name(row).and_then(|n| parse_gpu_counter_instance(&n)) else {
+                continue;
+            };
+            if !instance_luid.eq_ignore_ascii_case(&luid) || number(row, "UtilizationPercentage").unwrap_or(0) == 0 {
+                continue;
+            }
+            let engine = engine.unwrap_or_default().to_ascii_lowercase();
+            let context_type = GPUContextType::from_flags(engine == "3d", engine.starts_with("compute"));
+            let process = processes.entry(pid).or_insert_with(|| GPUProcess {
+                pid,
+                name: String::new(),
+                used_memory_mb: None,
+                context_type: GPUContextType::Unknown,
+            });
+            process.context_type = process.context_type.merge(context_type);
+        }
+
+        let mut processes: Vec<GPUProcess> = processes.into_values().collect();
+        fill_process_names(&mut processes);
+        processes
+    }
+
+    /// LUID of the DXGI adapter matching this GPU's PCI vendor/device IDs,
+    /// formatted as in GPU counter instance names
+    #[cfg(target_os = "windows")]
+    fn dxgi_luid(&self) -> Option<String> {
+        use windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1};
+
+        // PNPDeviceID: PCI\VEN_10DE&DEV_2684&SUBSYS_...
+        let pnp_id = self.pci_device_id.as_deref()?.to_ascii_uppercase();
+        let id_after = |tag: &str| {
+            let start = pnp_id.find(tag)? + tag.len();
+            u32::from_str_radix(pnp_id.get(start..start + 4)?, 16).ok()
+        };
+        let (vendor_id, device_id) = (id_after("VEN_")?, id_after("DEV_")?);
+
+        let factory: IDXGIFactory1 = unsafe { CreateDXGIFactory1() }.ok()?;
+        let mut index = 0;
+        while let Ok(adapter) = unsafe { factory.EnumAdapters1(index) } {
+            index += 1;
+            let Ok(desc) = (unsafe { adapter.GetDesc1() }) else {
+                continue;
+            };
+            if desc.VendorId == vendor_id && desc.DeviceId == device_id {
+                return Some(format!(
+                    "luid_0x{:08X}_0x{:08X}",
+                    desc.AdapterLuid.HighPart as u32, desc.AdapterLuid.LowPart
+                ));
+            }
+        }
+        None
+    }
+
     /// Create a default/fallback GPU for systems where no GPUs are detected
     fn default_gpu() -> Self {
         Self {
@@ -289,6 +1037,12 @@ impl GPUInfo {
                 metal: cfg!(target_os = "macos"),
                 compute_units: None,
                 max_workgroup_size: None,
+                metal_family: None,
+                vulkan_device: None,
+                opencl_device: None,
+                metal_device: None,
+                level_zero: false,
+                oneapi: false,
             },
             usage_percent: None,
             temperature: None,
@@ -298,6 +1052,11 @@ impl GPUInfo {
             vbios_version: None,
             pci_device_id: None,
             pci_subsystem_id: None,
+            pci_bus_id: None,
+            unified_memory: false,
+            directx: None,
+            video: None,
+            resizable_bar: None,
         }
     }
 
@@ -306,7 +1065,7 @@ impl GPUInfo {
         {
             let nvml = match Nvml::init() {
                 Ok(nvml) => nvml,
-                Err(_) => return Ok(vec![]),
+                Err(_) => return Self::query_nvidia_procfs(),
             };
 
             let mut gpus = Vec::new();
@@ -324,7 +1083,7 @@ impl GPUInfo {
                         model_name: name,
                         gpu_type: GPUType::Discrete,
                         memory_mb: memory_info.map(|m| m.total / 1024 / 1024).unwrap_or(0),
-                        memory_type: Some("GDDR6".to_string()),
+                        memory_type: None,
                         memory_bandwidth: None,
                         base_clock: None,
                         boost_clock: None,
@@ -341,6 +1100,12 @@ impl GPUInfo {
                             metal: cfg!(target_os = "macos"),
                             compute_units: None,
                             max_workgroup_size: None,
+                            metal_family: None,
+                            vulkan_device: None,
+                            opencl_device: None,
+                            metal_device: None,
+                            level_zero: false,
+                            oneapi: false,
                         },
                         usage_percent: device.utilization_rates().ok().map(|u| u.gpu as f32),
                         temperature: device
@@ -358,6 +1123,18 @@ impl GPUInfo {
                         vbios_version: device.vbios_version().ok(),
                         pci_device_id: None,
                         pci_subsystem_id: None,
+                        pci_bus_id: device.pci_info().ok().map(|info| info.bus_id),
+                        unified_memory: false,
+                        directx: None,
+                        video: None,
+                        resizable_bar: device
+                            .bar1_memory_info()
+                            .ok()
+                            .map(|bar| crate::ResizableBar::from_bar_size(bar.total / MIB, None)),
+                    };
+                    let gpu = Self {
+                        video: crate::video::nvml_capabilities(&gpu, &device),
+                        ..gpu
                     };
 
                     gpus.push(gpu);
@@ -367,29 +1144,294 @@ impl GPUInfo {
             Ok(gpus)
         }
         #[cfg(not(feature = "nvidia"))]
+        {
+            Self::query_nvidia_procfs()
+        }
+    }
+
+    /// Detect NVIDIA GPUs from the kernel driver when NVML is unavailable
+    ///
+    /// The driver describes each GPU in `/proc/driver/nvidia/gpus/<bus>/information`
+    /// and its version in `/proc/driver/nvidia/version`. VRAM isn't exposed there,
+    /// so it comes from `nvidia-smi` when installed and is estimated otherwise.
+    fn query_nvidia_procfs() -> Result<Vec<Self>> {
+        #[cfg(target_os = "linux")]
+        {
+            use std::fs;
+
+            let entries = match fs::read_dir("/proc/driver/nvidia/gpus") {
+                Ok(entries) => entries,
+                Err(_) => return Ok(vec![]),
+            };
+            let mut dirs: Vec<std::path::PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+            dirs.sort();
+
+            let driver_version = fs::read_to_string("/sys/module/nvidia/version")
+                .ok()
+                .map(|version| version.trim().to_string())
+                .filter(|version| !version.is_empty())
+                .or_else(|| {
+                    fs::read_to_string("/proc/driver/nvidia/version")
+                        .ok()
+                        .and_then(|text| Self::nvidia_driver_version_from_procfs(&text))
+                });
+            let vram = Self::nvidia_smi_memory_mb();
+
+            let mut gpus = Vec::new();
+            for dir in dirs {
+                let Ok(information) = fs::read_to_string(dir.join("information")) else {
+                    continue;
+                };
+                let Some(mut gpu) = Self::from_nvidia_procfs(&information) else {
+                    continue;
+                };
+                if gpu.pci_bus_id.is_none() {
+                    gpu.pci_bus_id = dir.file_name().map(|name| name.to_string_lossy().into_owned());
+                }
+
+                let vram_mb = gpu.pci_bus_id.as_ref().and_then(|bus| {
+                    vram.iter()
+                        .find(|(address, _)| same_pci_address(address, bus))
+                        .map(|(_, memory_mb)| *memory_mb)
+                });
+                if let Some(memory_mb) = vram_mb {
+                    gpu.memory_mb = memory_mb;
+                    gpu.gpu_type = Self::classify_gpu_type(&gpu.model_name, &GPUVendor::NVIDIA, memory_mb * 1024 * 1024);
+                }
+                gpu.driver_version = driver_version.clone();
+                gpus.push(gpu);
+            }
+
+            Ok(gpus)
+        }
+        #[cfg(not(target_os = "linux"))]
         {
             Ok(vec![])
         }
     }
 
+    /// Describe an NVIDIA GPU from its `/proc/driver/nvidia/gpus/<bus>/information` text
+    ///
+    /// Reads the `Model`, `Bus Location` and `Video BIOS` fields. The file
+    /// doesn't report VRAM, so `memory_mb` is an estimate. Returns `None` if
+    /// there is no model name.
+    pub fn from_nvidia_procfs(information: &str) -> Option<Self> {
+        let field = |key: &str| {
+            information.lines().find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                (name.trim() == key).then(|| value.trim().to_string())
+            })
+        };
+        let model_name = field("Model").filter(|model| !model.is_empty())?;
+        let gpu_type = Self::classify_gpu_type(&model_name, &GPUVendor::NVIDIA, 0);
+
+        let mut gpu = Self::default_gpu();
+        gpu.vendor = GPUVendor::NVIDIA;
+        gpu.memory_mb = Self::estimate_memory_mb(&GPUVendor::NVIDIA, &gpu_type);
+        gpu.model_name = model_name;
+        gpu.gpu_type = gpu_type;
+        gpu.compute_capabilities.cuda = Some("Unknown".to_string());
+        gpu.compute_capabilities.opencl = true;
+        gpu.compute_capabilities.vulkan = true;
+        // Unreadable fields show as "??.??.??.??.??"
+        gpu.vbios_version = field("Video BIOS").filter(|version| !version.starts_with("??"));
+        gpu.pci_bus_id = field("Bus Location").filter(|bus| !bus.is_empty());
+        Some(gpu)
+    }
+
+    /// Extract the driver version from `/proc/driver/nvidia/version`
+    ///
+    /// `NVRM version: NVIDIA UNIX x86_64 Kernel Module  550.54.14  Thu Feb 22 01:44:30 UTC 2024`
+    pub fn nvidia_driver_version_from_procfs(text: &str) -> Option<String> {
+        let line = text.lines().find(|line| line.starts_with("NVRM version:"))?;
+        line.split_whitespace()
+            .find(|word| word.contains('.') && word.chars().all(|c| c.is_ascii_digit() || c == '.'))
+            .map(str::to_string)
+    }
+
+    /// Total VRAM per PCI address as reported by `nvidia-smi`
+    #[cfg(target_os = "linux")]
+    fn nvidia_smi_memory_mb() -> Vec<(String, u64)> {
+        let Ok(output) = Command::new("nvidia-smi")
+            .args(["--query-gpu=pci.bus_id,memory.total", "--format=csv,noheader,nounits"])
+            .output()
+        else {
+            return Vec::new();
+        };
+        if !output.status.success() {
+            return Vec::new();
+        }
+
+        // 00000000:01:00.0, 24564
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (bus, memory) = line.split_once(',')?;
+                Some((bus.trim().to_string(), memory.trim().parse().ok()?))
+            })
+            .collect()
+    }
+
     fn query_amd_gpus() -> Result<Vec<Self>> {
-        #[cfg(feature = "amd")]
+        #[cfg(all(feature = "amd", target_os = "linux"))]
         {
-            // ROCm/AMD GPU detection would require additional dependencies
-            // For now, return empty vector as AMD feature is not implemented
-            Ok(vec![])
+            // The amdgpu kernel driver exposes VRAM, utilization and hwmon sensors in sysfs
+            let rocm = std::path::Path::new("/dev/kfd").exists();
+            let mut gpus: Vec<Self> = Self::query_drm_gpus()?
+                .into_iter()
+                .filter(|gpu| gpu.vendor == GPUVendor::AMD)
+                .collect();
+
+            for gpu in &mut gpus {
+                gpu.compute_capabilities.rocm = rocm;
+                if let Some(bus_id) = &gpu.pci_bus_id {
+                    let device_dir = std::path::Path::new("/sys/bus/pci/devices").join(bus_id);
+                    gpu.apply_amdgpu_sysfs_metrics(&device_dir);
+                }
+            }
+
+            Ok(gpus)
+        }
+        #[cfg(all(feature = "amd", target_os = "windows"))]
+        {
+            // No ADL bindings are available; use WMI for inventory and the HIP SDK for ROCm support
+            let hip_sdk = std::env::var_os("HIP_PATH").is_some();
+            let wmi_con = match WMIConnection::new(crate::probe::com_library()?) {
+                Ok(wmi_con) => wmi_con,
+                Err(_) => return Ok(vec![]),
+            };
+            let results: Vec<std::collections::HashMap<String, wmi::Variant>> = wmi_con
+                .raw_query("SELECT Name, AdapterRAM, DriverVersion, PNPDeviceID FROM Win32_VideoController WHERE PNPDeviceID LIKE '%VEN_1002%'")
+                .unwrap_or_default();
+
+            let mut gpus = Vec::new();
+            for result in results {
+                let Some(wmi::Variant::String(name)) = result.get("Name") else {
+                    continue;
+                };
+                let adapter_ram = match result.get("AdapterRAM") {
+                    Some(wmi::Variant::UI4(ram)) => *ram as u64,
+                    Some(wmi::Variant::UI8(ram)) => *ram,
+                    _ => 0,
+                };
+                let gpu_type = Self::classify_gpu_type(name, &GPUVendor::AMD, adapter_ram);
+
+                let mut gpu = Self::default_gpu();
+                gpu.vendor = GPUVendor::AMD;
+                gpu.model_name = name.clone();
+                gpu.memory_mb = if adapter_ram > 0 {
+                    adapter_ram / (1024 * 1024)
+                } else {
+                    Self::estimate_memory_mb(&GPUVendor::AMD, &gpu_type)
+                };
+                gpu.compute_capabilities.rocm = hip_sdk && gpu_type != GPUType::Integrated;
+                gpu.compute_capabilities.opencl = true;
+                gpu.compute_capabilities.vulkan = true;
+                gpu.gpu_type = gpu_type;
+                gpu.driver_version = match result.get("DriverVersion") {
+                    Some(wmi::Variant::String(version)) => Some(version.clone()),
+                    _ => None,
+                };
+                gpu.pci_device_id = match result.get("PNPDeviceID") {
+                    Some(wmi::Variant::String(id)) => Some(id.clone()),
+                    _ => None,
+                };
+                gpus.push(gpu);
+            }
+
+            Ok(gpus)
         }
-        #[cfg(not(feature = "amd"))]
+        #[cfg(not(all(feature = "amd", any(target_os = "linux", target_os = "windows"))))]
         {
             Ok(vec![])
         }
     }
 
+    /// Fill live metrics from an amdgpu PCI device directory
+    ///
+    /// Reads `gpu_busy_percent`, `mem_info_vram_total`, the `pp_dpm_*` clock tables and
+    /// the device's hwmon node (temperature in m°C, power in µW). Missing files
+    /// leave the corresponding fields untouched.
+    pub fn apply_amdgpu_sysfs_metrics(&mut self, device_dir: &std::path::Path) {
+        use std::fs;
+
+        let read_u64 = |path: std::path::PathBuf| -> Option<u64> {
+            fs::read_to_string(path).ok()?.trim().parse().ok()
+        };
+        // pp_dpm tables list one state per line ("1: 2500Mhz *"); the last is the highest
+        let max_dpm_mhz = |path: std::path::PathBuf| -> Option<u32> {
+            fs::read_to_string(path)
+                .ok()?
+                .lines()
+                .filter_map(|line| {
+                    let freq = line.split_whitespace().nth(1)?;
+                    freq.to_lowercase().trim_end_matches("mhz").parse::<u32>().ok()
+                })
+                .max()
+        };
+
+        if let Some(busy) = read_u64(device_dir.join("gpu_busy_percent")) {
+            self.usage_percent = Some(busy as f32);
+        }
+        if let Some(total) = read_u64(device_dir.join("mem_info_vram_total")) {
+            if total > 0 {
+                self.memory_mb = total / (1024 * 1024);
+            }
+        }
+        self.boost_clock = max_dpm_mhz(device_dir.join("pp_dpm_sclk")).or(self.boost_clock);
+        self.memory_clock = max_dpm_mhz(device_dir.join("pp_dpm_mclk")).or(self.memory_clock);
+
+        if let Ok(entries) = fs::read_dir(device_dir.join("hwmon")) {
+            for hwmon in entries.flatten().map(|entry| entry.path()) {
+                if let Some(temp) = read_u64(hwmon.join("temp1_input")) {
+                    self.temperature = Some(temp as f32 / 1000.0);
+                }
+                if let Some(power) = read_u64(hwmon.join("power1_average"))
+                    .or_else(|| read_u64(hwmon.join("power1_input")))
+                {
+                    self.power_consumption = Some(power as f32 / 1_000_000.0);
+                }
+                if let Some(cap) = read_u64(hwmon.join("power1_cap")) {
+                    self.power_limit = Some(cap as f32 / 1_000_000.0);
+                }
+            }
+        }
+    }
+
     fn query_intel_gpus() -> Result<Vec<Self>> {
+        #[cfg(target_os = "linux")]
+        {
+            // i915 and xe expose the PCI device ID, local memory and frequency limits in sysfs
+            let level_zero = level_zero_installed();
+            let oneapi = oneapi_installed();
+            let mut gpus = Vec::new();
+            for mut gpu in Self::query_drm_gpus()? {
+                if gpu.vendor != GPUVendor::Intel {
+                    continue;
+                }
+                let Some(bus_id) = gpu.pci_bus_id.clone() else {
+                    continue;
+                };
+                let device_dir = std::path::Path::new("/sys/bus/pci/devices").join(bus_id);
+                let driver = std::fs::read_link(device_dir.join("driver"))
+                    .ok()
+                    .and_then(|link| link.file_name().map(|name| name.to_string_lossy().into_owned()));
+                if !matches!(driver.as_deref(), Some("i915" | "xe")) {
+                    continue;
+                }
+                gpu.compute_capabilities.level_zero = level_zero;
+                gpu.compute_capabilities.oneapi = oneapi;
+                gpu.apply_intel_sysfs_info(&device_dir);
+                gpus.push(gpu);
+            }
+            Ok(gpus)
+        }
         #[cfg(target_os = "windows")]
         {
+            let level_zero = level_zero_installed();
+            let oneapi = oneapi_installed();
             // Use WMI to query Intel GPUs
-            match WMIConnection::new(COMLibrary::new()?) {
+            match WMIConnection::new(crate::probe::com_library()?) {
                 Ok(wmi_con) => {
                     let results: Vec<std::collections::HashMap<String, wmi::Variant>> = wmi_con
                         .raw_query("SELECT Name, AdapterRAM FROM Win32_VideoController WHERE Name LIKE '%Intel%'")
@@ -422,6 +1464,12 @@ impl GPUInfo {
                                     metal: false,
                                     compute_units: None,
                                     max_workgroup_size: None,
+                                    metal_family: None,
+                                    vulkan_device: None,
+                                    opencl_device: None,
+                                    metal_device: None,
+                                    level_zero,
+                                    oneapi,
                                 },
                                 usage_percent: None,
                                 temperature: None,
@@ -431,6 +1479,11 @@ impl GPUInfo {
                                 vbios_version: None,
                                 pci_device_id: None,
                                 pci_subsystem_id: None,
+                                pci_bus_id: None,
+                                unified_memory: false,
+                                directx: None,
+                                video: None,
+                                resizable_bar: None,
                             };
 
                             gpus.push(gpu);
@@ -442,20 +1495,130 @@ impl GPUInfo {
                 Err(_) => Ok(vec![]),
             }
         }
-        #[cfg(not(target_os = "windows"))]
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
         {
             Ok(vec![])
         }
     }
 
+    /// Fill model, VRAM, clocks and power limit from an i915/xe PCI device directory
+    ///
+    /// The model is decoded from the PCI device ID. Discrete cards report VRAM
+    /// per tile (`tileN/physical_vram_size_bytes`, xe) or, with resizable BAR,
+    /// as the size of the local memory BAR; otherwise the stock size is assumed.
+    #[cfg(target_os = "linux")]
+    fn apply_intel_sysfs_info(&mut self, device_dir: &std::path::Path) {
+        use std::fs;
+
+        let read_u64 = |path: std::path::PathBuf| -> Option<u64> {
+            let value = fs::read_to_string(path).ok()?;
+            let value = value.trim();
+            match value.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16).ok(),
+                None => value.parse().ok(),
+            }
+        };
+
+        let Some(device_id) = fs::read_to_string(device_dir.join("device"))
+            .ok()
+            .and_then(|id| crate::ids::hex(&id))
+        else {
+            return;
+        };
+        let known = INTEL_GPU_MODELS.iter().find(|(id, ..)| *id == device_id);
+        if let Some((_, name, ..)) = known {
+            self.model_name = name.to_string();
+        } else if let Some(name) = crate::ids::pci_ids().device(0x8086, device_id) {
+            self.model_name = format!("Intel {name}");
+        }
+
+        if !is_intel_discrete(device_id) {
+            self.gpu_type = GPUType::Integrated;
+            self.unified_memory = true;
+            self.memory_type = Some("System".to_string());
+        } else {
+            self.gpu_type = match known {
+                Some((_, _, gpu_type, _)) => gpu_type.clone(),
+                None => Self::classify_gpu_type(&self.model_name, &GPUVendor::Intel, 0),
+            };
+            self.unified_memory = false;
+
+            let tile_vram: u64 = fs::read_dir(device_dir)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter(|entry| entry.file_name().to_string_lossy().starts_with("tile"))
+                .filter_map(|entry| read_u64(entry.path().join("physical_vram_size_bytes")))
+                .sum();
+            // `resource` lists "start end flags" per BAR; BAR2 maps local memory
+            let lmem_bar = fs::read_to_string(device_dir.join("resource"))
+                .ok()
+                .and_then(|resource| {
+                    let bar: Vec<u64> = resource
+                        .lines()
+                        .nth(2)?
+                        .split_whitespace()
+                        .filter_map(|field| u64::from_str_radix(field.trim_start_matches("0x"), 16).ok())
+                        .collect();
+                    match bar.as_slice() {
+                        [start, end, ..] if *end > *start => Some(end - start + 1),
+                        _ => None,
+                    }
+                })
+                // Without resizable BAR only a 256 MB window is mapped
+                .filter(|&size| size >= 1 << 30)
+                .unwrap_or(0);
+            let vram_bytes = if tile_vram > 0 { tile_vram } else { lmem_bar };
+            self.memory_mb = if vram_bytes > 0 {
+                vram_bytes / (1024 * 1024)
+            } else {
+                known
+                    .map(|(_, _, _, vram_mb)| *vram_mb)
+                    .filter(|&vram_mb| vram_mb > 0)
+                    .unwrap_or_else(|| Self::estimate_memory_mb(&GPUVendor::Intel, &self.gpu_type))
+            };
+        }
+
+        // i915 puts frequency limits on the DRM card, xe on each GT
+        let card_dir = fs::read_dir(device_dir.join("drm"))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| path.file_name().is_some_and(|name| name.to_string_lossy().starts_with("card")));
+        let max_freq = card_dir
+            .and_then(|card| read_u64(card.join("gt_RP0_freq_mhz")))
+            .or_else(|| read_u64(device_dir.join("tile0/gt0/freq0/rp0_freq")));
+        if let Some(mhz) = max_freq {
+            self.boost_clock = Some(mhz as u32);
+        }
+
+        // Discrete cards have a hwmon node with the power limit in µW
+        if let Ok(entries) = fs::read_dir(device_dir.join("hwmon")) {
+            for hwmon in entries.flatten().map(|entry| entry.path()) {
+                if let Some(limit) = read_u64(hwmon.join("power1_max"))
+                    .or_else(|| read_u64(hwmon.join("power1_cap")))
+                    .filter(|&limit| limit > 0)
+                {
+                    self.power_limit = Some(limit as f32 / 1_000_000.0);
+                }
+                if let Some(temp) = read_u64(hwmon.join("temp1_input"))
+                    .or_else(|| read_u64(hwmon.join("temp2_input")))
+                {
+                    self.temperature = Some(temp as f32 / 1000.0);
+                }
+            }
+        }
+    }
+
     fn query_generic_gpus() -> Result<Vec<Self>> {
         // Generic GPU detection using WMI on Windows
         #[cfg(target_os = "windows")]
         {
             use std::collections::HashMap;
-            use wmi::{COMLibrary, WMIConnection, Variant};
+            use wmi::{WMIConnection, Variant};
 
-            let com_con = COMLibrary::new()?;
+            let com_con = crate::probe::com_library()?;
             let wmi_con = WMIConnection::new(com_con)?;
 
             let results: Vec<HashMap<String, Variant>> = wmi_con
@@ -510,17 +1673,7 @@ impl GPUInfo {
                 let memory_mb = if adapter_ram > 0 {
                     adapter_ram / (1024 * 1024)
                 } else {
-                    // Fallback estimates based on GPU type and vendor
-                    match (&vendor, &gpu_type) {
-                        (GPUVendor::NVIDIA, GPUType::Datacenter) => 32768,    // 32GB for datacenter (A100, H100)
-                        (GPUVendor::NVIDIA, GPUType::Workstation) => 16384,   // 16GB for workstation (RTX A6000)
-                        (GPUVendor::NVIDIA, GPUType::Discrete) => 8192,       // 8GB for consumer RTX
-                        (GPUVendor::AMD, GPUType::Datacenter) => 32768,       // 32GB for Instinct
-                        (GPUVendor::AMD, GPUType::Workstation) => 16384,      // 16GB for Radeon Pro
-                        (GPUVendor::AMD, GPUType::Discrete) => 8192,          // 8GB for discrete AMD
-                        (_, GPUType::Integrated) => 512,                      // 512MB for integrated
-                        _ => 4096,                                            // Default 4GB
-                    }
+                    Self::estimate_memory_mb(&vendor, &gpu_type)
                 };
 
                 // Set compute capabilities based on vendor
@@ -533,6 +1686,12 @@ impl GPUInfo {
                     metal: false,   // Metal is macOS only
                     compute_units: None,
                     max_workgroup_size: None,
+                    metal_family: None,
+                    vulkan_device: None,
+                    opencl_device: None,
+                    metal_device: None,
+                    level_zero: false,
+                    oneapi: false,
                 };
 
                 gpus.push(Self {
@@ -557,18 +1716,349 @@ impl GPUInfo {
                     vbios_version: None,
                     pci_device_id: Some(device_id),
                     pci_subsystem_id: None,
+                    pci_bus_id: None,
+                    unified_memory: false,
+                    directx: None,
+                    video: None,
+                    resizable_bar: None,
                 });
             }
 
             Ok(gpus)
         }
-        #[cfg(not(target_os = "windows"))]
+        #[cfg(target_os = "linux")]
+        {
+            // Enumerate DRM devices so GPUs are found without vendor tools installed
+            Self::query_drm_gpus()
+        }
+        #[cfg(target_os = "macos")]
+        {
+            Self::query_metal_gpus()
+        }
+        #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
         {
-            // For non-Windows platforms, use system-specific detection
             Ok(vec![])
         }
     }
 
+    /// Detect GPUs on macOS through Metal
+    ///
+    /// `MTLCopyAllDevices` lists Apple Silicon, Intel integrated and AMD/eGPU
+    /// devices. Metal does not expose core counts, so those come from the IOKit
+    /// accelerator entries (`gpu-core-count`).
+    #[cfg(target_os = "macos")]
+    fn query_metal_gpus() -> Result<Vec<Self>> {
+        use metal::{Device, MTLGPUFamily};
+
+        // Newest first, so the first supported family is the highest
+        const FAMILIES: [(MTLGPUFamily, &str); 11] = [
+            (MTLGPUFamily::Apple9, "Apple9"),
+            (MTLGPUFamily::Apple8, "Apple8"),
+            (MTLGPUFamily::Apple7, "Apple7"),
+            (MTLGPUFamily::Apple6, "Apple6"),
+            (MTLGPUFamily::Apple5, "Apple5"),
+            (MTLGPUFamily::Apple4, "Apple4"),
+            (MTLGPUFamily::Apple3, "Apple3"),
+            (MTLGPUFamily::Apple2, "Apple2"),
+            (MTLGPUFamily::Apple1, "Apple1"),
+            (MTLGPUFamily::Mac2, "Mac2"),
+            (MTLGPUFamily::Mac1, "Mac1"),
+        ];
+
+        let core_counts = Self::iokit_gpu_core_counts();
+        let mut gpus = Vec::new();
+
+        for device in Device::all() {
+            let model_name = device.name().to_string();
+            let vendor = GPUVendor::from_device_name(&model_name);
+
+            let unified_memory = device.has_unified_memory();
+            let gpu_type = if unified_memory || device.is_low_power() {
+                GPUType::Integrated
+            } else {
+                Self::classify_gpu_type(&model_name, &vendor, 0)
+            };
+
+            // For unified memory this is the share of system RAM the GPU may use
+            let memory_mb = device.recommended_max_working_set_size() / (1024 * 1024);
+
+            let families: Vec<String> = FAMILIES
+                .iter()
+                .filter(|(family, _)| device.supports_family(*family))
+                .map(|(_, name)| name.to_string())
+                .collect();
+            let metal_family = families.first().cloned();
+            let threadgroup = device.max_threads_per_threadgroup();
+            let metal_device = MetalDeviceInfo {
+                name: model_name.clone(),
+                registry_id: device.registry_id(),
+                families,
+                max_threads_per_threadgroup: [threadgroup.width, threadgroup.height, threadgroup.depth],
+                max_buffer_length: device.max_buffer_length(),
+                recommended_max_working_set_size: device.recommended_max_working_set_size(),
+                low_power: device.is_low_power(),
+                headless: device.is_headless(),
+                removable: device.is_removable(),
+            };
+
+            let compute_units = if vendor == GPUVendor::Apple {
+                core_counts.first().copied()
+            } else {
+                None
+            };
+
+            gpus.push(Self {
+                vendor,
+                model_name,
+                gpu_type,
+                memory_mb,
+                memory_type: unified_memory.then(|| "Unified".to_string()),
+                memory_bandwidth: None,
+                base_clock: None,
+                boost_clock: None,
+                memory_clock: None,
+                shader_units: compute_units,
+                rt_cores: None,
+                tensor_cores: None,
+                compute_capabilities: ComputeCapabilities {
+                    cuda: None,
+                    rocm: false,
+                    directml: false,
+                    opencl: true,
+                    vulkan: false,
+                    metal: true,
+                    compute_units,
+                    max_workgroup_size: Some(device.max_threads_per_threadgroup().width as u32),
+                    metal_family,
+                    vulkan_device: None,
+                    opencl_device: None,
+                    metal_device: Some(metal_device),
+                    level_zero: false,
+                    oneapi: false,
+                },
+                usage_percent: None,
+                temperature: None,
+                power_consumption: None,
+                power_limit: None,
+                driver_version: None,
+                vbios_version: None,
+                pci_device_id: None,
+                pci_subsystem_id: None,
+                pci_bus_id: None,
+                unified_memory,
+                directx: None,
+                video: None,
+                resizable_bar: None,
+            });
+        }
+
+        Ok(gpus)
+    }
+
+    /// GPU core counts reported by IOKit accelerators (`ioreg -rc IOAccelerator`)
+    #[cfg(target_os = "macos")]
+    fn iokit_gpu_core_counts() -> Vec<u32> {
+        let Ok(output) = crate::probe::Command::new("ioreg")
+            .args(["-rc", "IOAccelerator", "-d", "1"])
+            .output()
+        else {
+            return Vec::new();
+        };
+        Self::core_counts_from_ioreg(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Extract the `gpu-core-count` of every accelerator in `ioreg` output
+    ///
+    /// Lines look like `"gpu-core-count" = 10`; accelerators without the
+    /// property (Intel, AMD) contribute nothing.
+    pub fn core_counts_from_ioreg(output: &str) -> Vec<u32> {
+        output
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("\"gpu-core-count\" = "))
+            .filter_map(|count| count.trim().parse().ok())
+            .collect()
+    }
+
+    /// Detect GPUs on Linux from the DRM subsystem in sysfs
+    ///
+    /// Each `/sys/class/drm/cardN/device` is a PCI device exposing `vendor` and
+    /// `device` IDs; amdgpu additionally reports VRAM via `mem_info_vram_total`.
+    #[cfg(target_os = "linux")]
+    fn query_drm_gpus() -> Result<Vec<Self>> {
+        use std::fs;
+        use std::path::Path;
+
+        let mut gpus = Vec::new();
+        let entries = match fs::read_dir("/sys/class/drm") {
+            Ok(entries) => entries,
+            Err(_) => return Ok(gpus),
+        };
+
+        let mut cards: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            // Skip connector entries such as card0-HDMI-A-1 and render nodes
+            .filter(|name| name.starts_with("card") && !name.contains('-'))
+            .collect();
+        cards.sort();
+
+        for card in cards {
+            let Some(gpu) = Self::from_drm_device(&Path::new("/sys/class/drm").join(&card).join("device")) else {
+                continue;
+            };
+            // Several cardN entries can point at the same PCI function
+            if gpu.pci_bus_id.is_some() && gpus.iter().any(|g: &Self| g.pci_bus_id == gpu.pci_bus_id) {
+                continue;
+            }
+            gpus.push(gpu);
+        }
+
+        Ok(gpus)
+    }
+
+    /// Describe the GPU behind a DRM card's PCI device directory
+    ///
+    /// `device_dir` is `/sys/class/drm/cardN/device` (or a copy of it): the
+    /// `vendor` and `device` IDs are required, `uevent` supplies the driver and
+    /// PCI address, and amdgpu's `mem_info_vram_total` the VRAM size. Returns
+    /// `None` if the directory is not a PCI device.
+    pub fn from_drm_device(device_dir: &std::path::Path) -> Option<Self> {
+        use std::fs;
+        use std::path::Path;
+
+        let read_trimmed = |path: &Path| -> Option<String> {
+            fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+        };
+
+        let (Some(vendor_id), Some(device_id)) = (
+            read_trimmed(&device_dir.join("vendor")),
+            read_trimmed(&device_dir.join("device")),
+        ) else {
+            return None;
+        };
+        let vendor_id = vendor_id.trim_start_matches("0x").to_lowercase();
+        let device_id = device_id.trim_start_matches("0x").to_lowercase();
+
+        let vendor = match vendor_id.as_str() {
+            "10de" => GPUVendor::NVIDIA,
+            "1002" => GPUVendor::AMD,
+            "8086" => GPUVendor::Intel,
+            "106b" => GPUVendor::Apple,
+            "5143" => GPUVendor::Qualcomm,
+            _ => GPUVendor::Unknown(format!("PCI vendor {vendor_id}")),
+        };
+
+        let mut driver = None;
+        let mut pci_bus_id = None;
+        if let Some(uevent) = read_trimmed(&device_dir.join("uevent")) {
+            for line in uevent.lines() {
+                if let Some(value) = line.strip_prefix("DRIVER=") {
+                    driver = Some(value.to_string());
+                } else if let Some(value) = line.strip_prefix("PCI_SLOT_NAME=") {
+                    pci_bus_id = Some(value.to_string());
+                }
+            }
+        }
+
+        let model_name = read_trimmed(&device_dir.join("product_name"))
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("{vendor} GPU [{vendor_id}:{device_id}]"));
+
+        let vram_bytes = read_trimmed(&device_dir.join("mem_info_vram_total"))
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(0);
+
+        let gpu_type = if vendor == GPUVendor::Intel && vram_bytes == 0 {
+            GPUType::Integrated
+        } else {
+            Self::classify_gpu_type(&model_name, &vendor, vram_bytes)
+        };
+
+        let memory_mb = if vram_bytes > 0 {
+            vram_bytes / (1024 * 1024)
+        } else {
+            Self::estimate_memory_mb(&vendor, &gpu_type)
+        };
+
+        let driver_version = driver.as_ref().and_then(|name| {
+            read_trimmed(&Path::new("/sys/module").join(name).join("version"))
+        });
+
+        let subsystem_id = match (
+            read_trimmed(&device_dir.join("subsystem_vendor")),
+            read_trimmed(&device_dir.join("subsystem_device")),
+        ) {
+            (Some(sv), Some(sd)) => Some(format!(
+                "{}:{}",
+                sv.trim_start_matches("0x"),
+                sd.trim_start_matches("0x")
+            )),
+            _ => None,
+        };
+
+        let compute_capabilities = ComputeCapabilities {
+            cuda: if vendor == GPUVendor::NVIDIA { Some("Unknown".to_string()) } else { None },
+            rocm: vendor == GPUVendor::AMD && gpu_type != GPUType::Integrated,
+            directml: false,
+            opencl: true,
+            vulkan: true,
+            metal: false,
+            compute_units: None,
+            max_workgroup_size: None,
+            metal_family: None,
+            vulkan_device: None,
+            opencl_device: None,
+            metal_device: None,
+            level_zero: false,
+            oneapi: false,
+        };
+
+        let unified_memory = gpu_type == GPUType::Integrated;
+
+        Some(Self {
+            vendor,
+            model_name,
+            gpu_type,
+            memory_mb,
+            memory_type: None,
+            memory_bandwidth: None,
+            base_clock: None,
+            boost_clock: None,
+            memory_clock: None,
+            shader_units: None,
+            rt_cores: None,
+            tensor_cores: None,
+            compute_capabilities,
+            usage_percent: None,
+            temperature: None,
+            power_consumption: None,
+            power_limit: None,
+            driver_version,
+            vbios_version: read_trimmed(&device_dir.join("vbios_version")),
+            pci_device_id: Some(format!("{vendor_id}:{device_id}")),
+            pci_subsystem_id: subsystem_id,
+            pci_bus_id,
+            unified_memory,
+            directx: None,
+            video: None,
+            resizable_bar: None,
+        })
+    }
+
+    /// Fallback memory estimate (MB) when the platform doesn't report VRAM
+    fn estimate_memory_mb(vendor: &GPUVendor, gpu_type: &GPUType) -> u64 {
+        match (vendor, gpu_type) {
+            (GPUVendor::NVIDIA, GPUType::Datacenter) => 32768,    // 32GB for datacenter (A100, H100)
+            (GPUVendor::NVIDIA, GPUType::Workstation) => 16384,   // 16GB for workstation (RTX A6000)
+            (GPUVendor::NVIDIA, GPUType::Discrete) => 8192,       // 8GB for consumer RTX
+            (GPUVendor::AMD, GPUType::Datacenter) => 32768,       // 32GB for Instinct
+            (GPUVendor::AMD, GPUType::Workstation) => 16384,      // 16GB for Radeon Pro
+            (GPUVendor::AMD, GPUType::Discrete) => 8192,          // 8GB for discrete AMD
+            (_, GPUType::Integrated) => 512,                      // 512MB for integrated
+            _ => 4096,                                            // Default 4GB
+        }
+    }
+
     /// Classify GPU type based on model name and characteristics
     fn classify_gpu_type(name: &str, vendor: &GPUVendor, adapter_ram: u64) -> GPUType {
         let name_lower = name.to_lowercase();
@@ -0,0 +1,634 @@
+//! Graphics and compute API probing (Vulkan, OpenCL, Metal, DirectX)
+//!
+//! Without probing, [`ComputeCapabilities`](crate::gpu::ComputeCapabilities)
+//! guesses OpenCL and Vulkan support from the vendor. With the `vulkan` or
+//! `opencl` feature the system loaders are opened at runtime (no link-time
+//! dependency) and every physical device they report is matched back to a
+//! detected GPU, replacing the guesses with the driver's answer. Metal
+//! devices are always enumerated on macOS. With the `directx` feature each
+//! GPU's D3D12 feature level and DirectX 12 Ultimate tiers are read on Windows.
+
+use crate::gpu::{GPUInfo, GPUType};
+#[cfg(any(feature = "vulkan", feature = "opencl"))]
+use crate::gpu::GPUVendor;
+use serde::{Deserialize, Serialize};
+
+/// A Vulkan physical device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VulkanDeviceInfo {
+    /// Device name reported by the driver
+    pub name: String,
+    /// Highest supported Vulkan version (e.g. "1.3.277")
+    pub api_version: String,
+    /// PCI vendor ID
+    pub vendor_id: u32,
+    /// PCI device ID
+    pub device_id: u32,
+    /// Discrete, integrated, virtual or CPU (`Unknown`) device
+    pub device_type: GPUType,
+    /// Maximum invocations in one compute workgroup
+    pub max_compute_workgroup_invocations: u32,
+    /// Maximum compute workgroup size per dimension
+    pub max_compute_workgroup_size: [u32; 3],
+    /// Supported device extensions
+    pub extensions: Vec<String>,
+}
+
+impl VulkanDeviceInfo {
+    /// Enumerate Vulkan physical devices
+    ///
+    /// Returns an empty list when no Vulkan loader is installed.
+    #[cfg(feature = "vulkan")]
+    pub fn query_all() -> crate::Result<Vec<Self>> {
+        vulkan::query_all()
+    }
+
+    /// Get device name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get highest supported Vulkan version
+    pub fn api_version(&self) -> &str {
+        &self.api_version
+    }
+
+    /// Check if the device supports an extension (e.g. "VK_KHR_ray_query")
+    pub fn supports_extension(&self, extension: &str) -> bool {
+        self.extensions.iter().any(|e| e == extension)
+    }
+}
+
+/// An OpenCL GPU device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenCLDeviceInfo {
+    /// Name of the platform (ICD) exposing the device
+    pub platform_name: String,
+    /// Platform version string (e.g. "OpenCL 3.0 CUDA 12.4.89")
+    pub platform_version: String,
+    /// Device name reported by the driver
+    pub name: String,
+    /// Device version string (e.g. "OpenCL 3.0")
+    pub version: String,
+    /// OpenCL C language version (e.g. "OpenCL C 1.2")
+    pub opencl_c_version: Option<String>,
+    /// Driver version
+    pub driver_version: Option<String>,
+    /// Vendor ID (the PCI vendor ID for most discrete GPUs)
+    pub vendor_id: u32,
+    /// Parallel compute units
+    pub compute_units: u32,
+    /// Maximum work-items in one workgroup
+    pub max_workgroup_size: u64,
+    /// Maximum work-items per dimension
+    pub max_work_item_sizes: Vec<u64>,
+    /// Supported device extensions
+    pub extensions: Vec<String>,
+}
+
+impl OpenCLDeviceInfo {
+    /// Enumerate OpenCL GPU devices on every platform
+    ///
+    /// Returns an empty list when no OpenCL ICD loader or platform is installed.
+    #[cfg(feature = "opencl")]
+    pub fn query_all() -> crate::Result<Vec<Self>> {
+        opencl::query_all()
+    }
+
+    /// Get device name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get device version string
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Check if the device supports an extension (e.g. "cl_khr_fp64")
+    pub fn supports_extension(&self, extension: &str) -> bool {
+        self.extensions.iter().any(|e| e == extension)
+    }
+}
+
+/// A Metal device (macOS)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetalDeviceInfo {
+    /// Device name
+    pub name: String,
+    /// IORegistry ID, stable for the lifetime of the device
+    pub registry_id: u64,
+    /// Supported GPU families, newest first (e.g. "Apple9", "Mac2")
+    pub families: Vec<String>,
+    /// Maximum threads per threadgroup per dimension
+    pub max_threads_per_threadgroup: [u64; 3],
+    /// Largest buffer the device can allocate in bytes
+    pub max_buffer_length: u64,
+    /// Memory the device can use without degrading performance, in bytes
+    pub recommended_max_working_set_size: u64,
+    /// Low-power (integrated) device
+    pub low_power: bool,
+    /// Device has no display attached
+    pub headless: bool,
+    /// External (eGPU) device
+    pub removable: bool,
+}
+
+impl MetalDeviceInfo {
+    /// Get device name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Check if the device supports a GPU family (e.g. "Apple7")
+    pub fn supports_family(&self, family: &str) -> bool {
+        self.families.iter().any(|f| f == family)
+    }
+}
+
+/// Direct3D 12 support of an adapter (Windows)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectXCapabilities {
+    /// Highest Direct3D feature level (e.g. "12_2")
+    pub feature_level: String,
+    /// Highest HLSL shader model (e.g. "6.7")
+    pub shader_model: Option<String>,
+    /// DirectX Raytracing tier ("1.0", "1.1"), `None` if unsupported
+    pub raytracing_tier: Option<String>,
+    /// Mesh shader tier (0 = unsupported)
+    pub mesh_shader_tier: u32,
+    /// Variable rate shading tier (0 = unsupported)
+    pub variable_rate_shading_tier: u32,
+    /// Sampler feedback tier ("0.9", "1.0"), `None` if unsupported
+    pub sampler_feedback_tier: Option<String>,
+    /// Resource binding tier (1-3)
+    pub resource_binding_tier: u32,
+}
+
+impl DirectXCapabilities {
+    /// Get highest Direct3D feature level
+    pub fn feature_level(&self) -> &str {
+        &self.feature_level
+    }
+
+    /// Check if DirectX Raytracing is supported
+    pub fn supports_raytracing(&self) -> bool {
+        self.raytracing_tier.is_some()
+    }
+
+    /// Check if mesh shaders are supported
+    pub fn supports_mesh_shaders(&self) -> bool {
+        self.mesh_shader_tier > 0
+    }
+
+    /// Check if variable rate shading is supported
+    pub fn supports_variable_rate_shading(&self) -> bool {
+        self.variable_rate_shading_tier > 0
+    }
+
+    /// Check for the DirectX 12 Ultimate feature set
+    ///
+    /// Feature level 12_2, or DXR 1.1, mesh shaders, VRS tier 2 and sampler
+    /// feedback on drivers that predate the 12_2 feature level.
+    pub fn is_dx12_ultimate(&self) -> bool {
+        self.feature_level.as_str() >= "12_2"
+            || (self.raytracing_tier.as_deref().is_some_and(|tier| tier >= "1.1")
+                && self.mesh_shader_tier >= 1
+                && self.variable_rate_shading_tier >= 2
+                && self.sampler_feedback_tier.is_some())
+    }
+}
+
+/// Replace guessed Vulkan/OpenCL support with what the enabled probes report
+///
+/// A probe that fails leaves the guesses untouched; a probe that succeeds
+/// marks every GPU it did not list as unsupported.
+#[cfg_attr(
+    not(any(feature = "vulkan", feature = "opencl", all(feature = "directx", target_os = "windows"))),
+    allow(unused_variables)
+)]
+pub(crate) fn apply_probes(gpus: &mut [GPUInfo]) {
+    #[cfg(all(feature = "directx", target_os = "windows"))]
+    for gpu in gpus.iter_mut() {
+        gpu.directx = pci_ids(gpu).and_then(|(vendor_id, device_id)| directx::query(vendor_id, device_id));
+    }
+
+    #[cfg(feature = "vulkan")]
+    if let Ok(devices) = VulkanDeviceInfo::query_all() {
+        let devices: Vec<VulkanDeviceInfo> = devices
+            .into_iter()
+            .filter(|d| d.device_type != GPUType::Unknown)
+            .collect();
+        let same_vendor = |gpu: &GPUInfo, device: &VulkanDeviceInfo| {
+            vendor_ids(&gpu.vendor).contains(&device.vendor_id)
+        };
+        let exact = |gpu: &GPUInfo, device: &VulkanDeviceInfo| {
+            pci_ids(gpu) == Some((device.vendor_id, device.device_id))
+                || (same_vendor(gpu, device) && same_name(&gpu.model_name, &device.name))
+        };
+        let assigned = assign(gpus, &devices, exact, same_vendor);
+
+        for (gpu, device) in gpus.iter_mut().zip(assigned) {
+            let capabilities = &mut gpu.compute_capabilities;
+            capabilities.vulkan = device.is_some();
+            if let Some(device) = device {
+                capabilities
+                    .max_workgroup_size
+                    .get_or_insert(device.max_compute_workgroup_invocations);
+                capabilities.vulkan_device = Some(device.clone());
+            }
+        }
+    }
+
+    #[cfg(feature = "opencl")]
+    if let Ok(devices) = OpenCLDeviceInfo::query_all() {
+        let same_vendor = |gpu: &GPUInfo, device: &OpenCLDeviceInfo| {
+            vendor_ids(&gpu.vendor).contains(&device.vendor_id)
+        };
+        let exact = |gpu: &GPUInfo, device: &OpenCLDeviceInfo| {
+            same_vendor(gpu, device) && same_name(&gpu.model_name, &device.name)
+        };
+        let assigned = assign(gpus, &devices, exact, same_vendor);
+
+        for (gpu, device) in gpus.iter_mut().zip(assigned) {
+            let capabilities = &mut gpu.compute_capabilities;
+            capabilities.opencl = device.is_some();
+            if let Some(device) = device {
+                capabilities.compute_units.get_or_insert(device.compute_units);
+                capabilities
+                    .max_workgroup_size
+                    .get_or_insert(u32::try_from(device.max_workgroup_size).unwrap_or(u32::MAX));
+                capabilities.opencl_device = Some(device.clone());
+            }
+        }
+    }
+}
+
+/// Pair each GPU with at most one API device
+///
+/// Exact matches (PCI IDs or names) are taken first; remaining devices go to
+/// the remaining GPUs of the same vendor in enumeration order.
+#[cfg(any(feature = "vulkan", feature = "opencl"))]
+fn assign<'a, T>(
+    gpus: &[GPUInfo],
+    devices: &'a [T],
+    exact: impl Fn(&GPUInfo, &T) -> bool,
+    same_vendor: impl Fn(&GPUInfo, &T) -> bool,
+) -> Vec<Option<&'a T>> {
+    let mut taken = vec![false; devices.len()];
+    let mut assigned: Vec<Option<&T>> = vec![None; gpus.len()];
+
+    for matcher in [&exact as &dyn Fn(&GPUInfo, &T) -> bool, &same_vendor] {
+        for (gpu, slot) in gpus.iter().zip(assigned.iter_mut()) {
+            if slot.is_some() {
+                continue;
+            }
+            if let Some(index) = (0..devices.len()).find(|&i| !taken[i] && matcher(gpu, &devices[i])) {
+                taken[index] = true;
+                *slot = Some(&devices[index]);
+            }
+        }
+    }
+
+    assigned
+}
+
+/// Vendor IDs the APIs report for a GPU vendor
+///
+/// PCI vendor IDs, plus the OpenCL ID Apple uses for its own GPUs.
+#[cfg(any(feature = "vulkan", feature = "opencl"))]
+fn vendor_ids(vendor: &GPUVendor) -> &'static [u32] {
+    match vendor {
+        GPUVendor::NVIDIA => &[0x10de],
+        GPUVendor::AMD => &[0x1002],
+        GPUVendor::Intel => &[0x8086],
+        GPUVendor::Apple => &[0x106b, 0x0102_7f00],
+        GPUVendor::ARM => &[0x13b5],
+        GPUVendor::Qualcomm => &[0x5143],
+        GPUVendor::Unknown(_) => &[],
+    }
+}
+
+/// PCI vendor and device ID from `pci_device_id`
+///
+/// Accepts the sysfs form (`10de:2684`) and Windows PnP IDs
+/// (`PCI\VEN_10DE&DEV_2684&...`).
+#[cfg(any(feature = "vulkan", all(feature = "directx", target_os = "windows")))]
+fn pci_ids(gpu: &GPUInfo) -> Option<(u32, u32)> {
+    let id = gpu.pci_device_id.as_deref()?;
+    let hex = |s: &str| u32::from_str_radix(s.get(..4)?, 16).ok();
+    let upper = id.to_ascii_uppercase();
+    match (upper.find("VEN_"), upper.find("DEV_")) {
+        (Some(ven), Some(dev)) => Some((hex(&upper[ven + 4..])?, hex(&upper[dev + 4..])?)),
+        _ => {
+            let (vendor, device) = id.split_once(':')?;
+            Some((hex(vendor)?, hex(device)?))
+        }
+    }
+}
+
+/// Whether two reported names describe the same model
+#[cfg(any(feature = "vulkan", feature = "opencl"))]
+fn same_name(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| {
+        s.to_lowercase()
+            .replace("(r)", "")
+            .replace("(tm)", "")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let (a, b) = (normalize(a), normalize(b));
+    !a.is_empty() && !b.is_empty() && (a.contains(&b) || b.contains(&a))
+}
+
+#[cfg(feature = "vulkan")]
+mod vulkan {
+    use super::VulkanDeviceInfo;
+    use crate::gpu::GPUType;
+    use crate::{ErrorCode, ErrorComponent, HardwareQueryError, Result};
+    use ash::vk;
+
+    const PORTABILITY_ENUMERATION: &std::ffi::CStr = c"VK_KHR_portability_enumeration";
+
+    pub(super) fn query_all() -> Result<Vec<VulkanDeviceInfo>> {
+        // SAFETY: opens the system Vulkan loader; the library stays loaded while `entry` lives
+        let Ok(entry) = (unsafe { ash::Entry::load() }) else {
+            return Ok(Vec::new());
+        };
+
+        // SAFETY: global commands only need a loaded entry
+        let (instance_version, portability) = unsafe {
+            let version = entry
+                .try_enumerate_instance_version()
+                .ok()
+                .flatten()
+                .unwrap_or(vk::API_VERSION_1_0);
+            let portability = entry
+                .enumerate_instance_extension_properties(None)
+                .unwrap_or_default()
+                .iter()
+                .any(|e| e.extension_name_as_c_str() == Ok(PORTABILITY_ENUMERATION));
+            (version, portability)
+        };
+
+        let app_info = vk::ApplicationInfo::default()
+            .application_name(c"hardware-query")
+            .api_version(instance_version);
+        // MoltenVK and other non-conformant drivers are only listed on request
+        let extensions = [PORTABILITY_ENUMERATION.as_ptr()];
+        let mut create_info = vk::InstanceCreateInfo::default().application_info(&app_info);
+        if portability {
+            create_info = create_info
+                .enabled_extension_names(&extensions)
+                .flags(vk::InstanceCreateFlags::ENUMERATE_PORTABILITY_KHR);
+        }
+
+        // SAFETY: create_info and everything it points to outlive the call
+        let instance = unsafe { entry.create_instance(&create_info, None) }
+            .map_err(|e| {
+                HardwareQueryError::with_source(ErrorComponent::Vulkan, ErrorCode::DriverError, "vkCreateInstance failed", e)
+            })?;
+
+        // SAFETY: physical device handles are only used while `instance` is alive
+        let devices = unsafe {
+            instance
+                .enumerate_physical_devices()
+                .map(|devices| {
+                    devices
+                        .into_iter()
+                        .map(|device| {
+                            let properties = instance.get_physical_device_properties(device);
+                            let extensions = instance
+                                .enumerate_device_extension_properties(device)
+                                .unwrap_or_default();
+                            from_properties(&properties, &extensions)
+                        })
+                        .collect()
+                })
+                .map_err(|e| {
+                    HardwareQueryError::with_source(
+                        ErrorComponent::Vulkan,
+                        ErrorCode::DriverError,
+                        "vkEnumeratePhysicalDevices failed",
+                        e,
+                    )
+                })
+        };
+
+        // SAFETY: no objects created from the instance are still alive
+        unsafe { instance.destroy_instance(None) };
+        devices
+    }
+
+    fn from_properties(
+        properties: &vk::PhysicalDeviceProperties,
+        extensions: &[vk::ExtensionProperties],
+    ) -> VulkanDeviceInfo {
+        let version = properties.api_version;
+        let limits = &properties.limits;
+
+        VulkanDeviceInfo {
+            name: properties
+                .device_name_as_c_str()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            api_version: format!(
+                "{}.{}.{}",
+                vk::api_version_major(version),
+                vk::api_version_minor(version),
+                vk::api_version_patch(version)
+            ),
+            vendor_id: properties.vendor_id,
+            device_id: properties.device_id,
+            device_type: match properties.device_type {
+                vk::PhysicalDeviceType::DISCRETE_GPU => GPUType::Discrete,
+                vk::PhysicalDeviceType::INTEGRATED_GPU => GPUType::Integrated,
+                vk::PhysicalDeviceType::VIRTUAL_GPU => GPUType::Virtual,
+                _ => GPUType::Unknown,
+            },
+            max_compute_workgroup_invocations: limits.max_compute_work_group_invocations,
+            max_compute_workgroup_size: limits.max_compute_work_group_size,
+            extensions: extensions
+                .iter()
+                .filter_map(|e| e.extension_name_as_c_str().ok())
+                .map(|name| name.to_string_lossy().into_owned())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "opencl")]
+mod opencl {
+    use super::OpenCLDeviceInfo;
+    use crate::{ErrorCode, ErrorComponent, HardwareQueryError, Result};
+    use opencl3::device::{CL_DEVICE_TYPE_GPU, Device};
+    use opencl3::error_codes::{CL_PLATFORM_NOT_FOUND_KHR, DLOPEN_RUNTIME_LOAD_FAILED};
+
+    pub(super) fn query_all() -> Result<Vec<OpenCLDeviceInfo>> {
+        let platforms = match opencl3::platform::get_platforms() {
+            Ok(platforms) => platforms,
+            // No ICD loader, or a loader without any installed platform
+            Err(e) if e.0 == DLOPEN_RUNTIME_LOAD_FAILED || e.0 == CL_PLATFORM_NOT_FOUND_KHR => {
+                return Ok(Vec::new());
+            }
+            Err(e) => {
+                return Err(HardwareQueryError::new(
+                    ErrorComponent::OpenCl,
+                    ErrorCode::DriverError,
+                    format!("clGetPlatformIDs failed: {e}"),
+                ));
+            }
+        };
+
+        let mut devices = Vec::new();
+        for platform in platforms {
+            // CL_DEVICE_NOT_FOUND when the platform has no GPU
+            let Ok(ids) = platform.get_devices(CL_DEVICE_TYPE_GPU) else {
+                continue;
+            };
+            let platform_name = platform.name().unwrap_or_default();
+            let platform_version = platform.version().unwrap_or_default();
+
+            for id in ids {
+                let device = Device::new(id);
+                let Ok(name) = device.name() else {
+                    continue;
+                };
+                devices.push(OpenCLDeviceInfo {
+                    platform_name: platform_name.clone(),
+                    platform_version: platform_version.clone(),
+                    name: name.trim().to_string(),
+                    version: device.version().unwrap_or_default(),
+                    opencl_c_version: device.opencl_c_version().ok(),
+                    driver_version: device.driver_version().ok(),
+                    vendor_id: device.vendor_id().unwrap_or(0),
+                    compute_units: device.max_compute_units().unwrap_or(0),
+                    max_workgroup_size: device.max_work_group_size().unwrap_or(0) as u64,
+                    max_work_item_sizes: device
+                        .max_work_item_sizes()
+                        .map(|sizes| sizes.into_iter().map(|s| s as u64).collect())
+                        .unwrap_or_default(),
+                    extensions: device
+                        .extensions()
+                        .map(|list| list.split_whitespace().map(str::to_string).collect())
+                        .unwrap_or_default(),
+                });
+            }
+        }
+
+        Ok(devices)
+    }
+}
+
+#[cfg(all(feature = "directx", target_os = "windows"))]
+mod directx {
+    use super::DirectXCapabilities;
+    use ::windows::Win32::Graphics::Direct3D::{
+        D3D_FEATURE_LEVEL, D3D_FEATURE_LEVEL_11_0, D3D_FEATURE_LEVEL_11_1, D3D_FEATURE_LEVEL_12_0,
+        D3D_FEATURE_LEVEL_12_1, D3D_FEATURE_LEVEL_12_2,
+    };
+    use ::windows::Win32::Graphics::Direct3D12::{
+        D3D_SHADER_MODEL, D3D12_FEATURE, D3D12_FEATURE_D3D12_OPTIONS, D3D12_FEATURE_D3D12_OPTIONS5,
+        D3D12_FEATURE_D3D12_OPTIONS6, D3D12_FEATURE_D3D12_OPTIONS7, D3D12_FEATURE_DATA_D3D12_OPTIONS,
+        D3D12_FEATURE_DATA_D3D12_OPTIONS5, D3D12_FEATURE_DATA_D3D12_OPTIONS6,
+        D3D12_FEATURE_DATA_D3D12_OPTIONS7, D3D12_FEATURE_DATA_FEATURE_LEVELS,
+        D3D12_FEATURE_DATA_SHADER_MODEL, D3D12_FEATURE_FEATURE_LEVELS, D3D12_FEATURE_SHADER_MODEL,
+        D3D12CreateDevice, ID3D12Device,
+    };
+    use ::windows::Win32::Graphics::Dxgi::{
+        CreateDXGIFactory1, DXGI_ADAPTER_FLAG_SOFTWARE, IDXGIAdapter1, IDXGIFactory1,
+    };
+
+    /// Feature levels to ask for, highest first
+    const FEATURE_LEVELS: [D3D_FEATURE_LEVEL; 5] = [
+        D3D_FEATURE_LEVEL_12_2,
+        D3D_FEATURE_LEVEL_12_1,
+        D3D_FEATURE_LEVEL_12_0,
+        D3D_FEATURE_LEVEL_11_1,
+        D3D_FEATURE_LEVEL_11_0,
+    ];
+
+    /// Capabilities of the hardware adapter with these PCI IDs
+    pub(super) fn query(vendor_id: u32, device_id: u32) -> Option<DirectXCapabilities> {
+        let factory: IDXGIFactory1 = unsafe { CreateDXGIFactory1() }.ok()?;
+        let mut index = 0;
+        while let Ok(adapter) = unsafe { factory.EnumAdapters1(index) } {
+            index += 1;
+            let Ok(desc) = (unsafe { adapter.GetDesc1() }) else {
+                continue;
+            };
+            let software = (desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE.0 as u32) != 0;
+            if !software && desc.VendorId == vendor_id && desc.DeviceId == device_id {
+                return from_adapter(&adapter);
+            }
+        }
+        None
+    }
+
+    fn from_adapter(adapter: &IDXGIAdapter1) -> Option<DirectXCapabilities> {
+        // D3D12 requires at least feature level 11_0
+        let mut device: Option<ID3D12Device> = None;
+        unsafe { D3D12CreateDevice(adapter, D3D_FEATURE_LEVEL_11_0, &mut device) }.ok()?;
+        let device = device?;
+
+        let levels = check(
+            &device,
+            D3D12_FEATURE_FEATURE_LEVELS,
+            D3D12_FEATURE_DATA_FEATURE_LEVELS {
+                NumFeatureLevels: FEATURE_LEVELS.len() as u32,
+                pFeatureLevelsRequested: FEATURE_LEVELS.as_ptr(),
+                MaxSupportedFeatureLevel: D3D_FEATURE_LEVEL_11_0,
+            },
+        )?;
+        let level = levels.MaxSupportedFeatureLevel.0;
+
+        // The runtime rejects shader models it does not know, so step down from the newest
+        let shader_model = (0x60..=0x69).rev().find_map(|model| {
+            let data = D3D12_FEATURE_DATA_SHADER_MODEL {
+                HighestShaderModel: D3D_SHADER_MODEL(model),
+            };
+            check(&device, D3D12_FEATURE_SHADER_MODEL, data)
+                .map(|data| data.HighestShaderModel.0)
+                .map(|model| format!("{}.{}", model >> 4, model & 0xf))
+        });
+
+        let options: D3D12_FEATURE_DATA_D3D12_OPTIONS =
+            check(&device, D3D12_FEATURE_D3D12_OPTIONS, Default::default()).unwrap_or_default();
+        let options5: D3D12_FEATURE_DATA_D3D12_OPTIONS5 =
+            check(&device, D3D12_FEATURE_D3D12_OPTIONS5, Default::default()).unwrap_or_default();
+        let options6: D3D12_FEATURE_DATA_D3D12_OPTIONS6 =
+            check(&device, D3D12_FEATURE_D3D12_OPTIONS6, Default::default()).unwrap_or_default();
+        let options7: D3D12_FEATURE_DATA_D3D12_OPTIONS7 =
+            check(&device, D3D12_FEATURE_D3D12_OPTIONS7, Default::default()).unwrap_or_default();
+
+        // Tiers are encoded as major * 10 + minor (raytracing, mesh shaders) or * 100 (sampler feedback)
+        let raytracing = options5.RaytracingTier.0;
+        let sampler_feedback = options7.SamplerFeedbackTier.0;
+
+        Some(DirectXCapabilities {
+            feature_level: format!("{}_{}", (level >> 12) & 0xf, (level >> 8) & 0xf),
+            shader_model,
+            raytracing_tier: (raytracing > 0).then(|| format!("{}.{}", raytracing / 10, raytracing % 10)),
+            mesh_shader_tier: (options7.MeshShaderTier.0 / 10) as u32,
+            variable_rate_shading_tier: options6.VariableShadingRateTier.0 as u32,
+            sampler_feedback_tier: (sampler_feedback > 0)
+                .then(|| format!("{}.{}", sampler_feedback / 100, sampler_feedback % 100 / 10)),
+            resource_binding_tier: options.ResourceBindingTier.0 as u32,
+        })
+    }
+
+    /// `CheckFeatureSupport` for one `D3D12_FEATURE_DATA_*` struct
+    fn check<T>(device: &ID3D12Device, feature: D3D12_FEATURE, mut data: T) -> Option<T> {
+        // SAFETY: `data` is the D3D12_FEATURE_DATA_* struct matching `feature` and outlives the call
+        unsafe {
+            device.CheckFeatureSupport(
+                feature,
+                &mut data as *mut T as *mut core::ffi::c_void,
+                std::mem::size_of::<T>() as u32,
+            )
+        }
+        .ok()?;
+        Some(data)
+    }
+}
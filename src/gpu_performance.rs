@@ -0,0 +1,232 @@
+//! Relative gaming performance of GPU models
+//!
+//! VRAM size says little about how fast a GPU is: an 8 GB GTX 1070 and an
+//! 8 GB RTX 4060 are years apart. This module maps model names to a
+//! relative performance score (GeForce RTX 4090 = 100) and a coarse tier.
+//!
+//! A database is embedded in the crate (`data/gpu_performance.txt`). Newer
+//! models can be added without a crate update by loading a file in the same
+//! format and installing it with [`GPUPerformanceDatabase::install`]; entries
+//! from the file override or extend the embedded ones.
+//!
+//! Each non-comment line reads `<model name> | <score> | <features>`, where
+//! features is an optional comma-separated list (`rt` = hardware ray tracing).
+
+use crate::{HardwareQueryError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Embedded performance database
+const EMBEDDED: &str = include_str!("../data/gpu_performance.txt");
+
+/// Coarse gaming performance class of a GPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GPUPerformanceTier {
+    /// Older or integrated graphics; light titles only
+    Legacy,
+    /// Entry level; 1080p at low to medium settings
+    Entry,
+    /// Mainstream; 1080p at high settings
+    Mainstream,
+    /// High end; 1440p at high settings
+    HighEnd,
+    /// Enthusiast; 1440p at ultra or 4K at medium settings
+    Enthusiast,
+    /// Current flagship; 4K at ultra settings
+    Flagship,
+}
+
+impl GPUPerformanceTier {
+    /// Tier of a relative performance score
+    pub fn from_score(score: u32) -> Self {
+        match score {
+            75.. => Self::Flagship,
+            50..=74 => Self::Enthusiast,
+            35..=49 => Self::HighEnd,
+            20..=34 => Self::Mainstream,
+            8..=19 => Self::Entry,
+            _ => Self::Legacy,
+        }
+    }
+}
+
+impl std::fmt::Display for GPUPerformanceTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Self::Legacy => "Legacy",
+            Self::Entry => "Entry",
+            Self::Mainstream => "Mainstream",
+            Self::HighEnd => "High-end",
+            Self::Enthusiast => "Enthusiast",
+            Self::Flagship => "Flagship",
+        };
+        f.write_str(&crate::l10n::localize("GPUPerformanceTier", text))
+    }
+}
+
+/// One GPU model in the performance database
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GPUPerformanceEntry {
+    /// Model name as listed in the database (e.g. "RTX 4070 Ti")
+    pub name: String,
+    /// Relative gaming performance (GeForce RTX 4090 = 100)
+    pub score: u32,
+    /// Hardware ray tracing support
+    pub raytracing: bool,
+}
+
+impl GPUPerformanceEntry {
+    /// Model name as listed in the database
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Relative gaming performance (GeForce RTX 4090 = 100)
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// Hardware ray tracing support
+    pub fn raytracing(&self) -> bool {
+        self.raytracing
+    }
+
+    /// Performance tier
+    pub fn tier(&self) -> GPUPerformanceTier {
+        GPUPerformanceTier::from_score(self.score)
+    }
+}
+
+/// GPU model to relative performance database
+#[derive(Debug, Clone, Default)]
+pub struct GPUPerformanceDatabase {
+    /// Entries with their normalized name tokens
+    entries: Vec<(Vec<String>, GPUPerformanceEntry)>,
+}
+
+impl GPUPerformanceDatabase {
+    /// The database embedded in the crate
+    pub fn embedded() -> Self {
+        Self::parse(EMBEDDED).unwrap_or_default()
+    }
+
+    /// Parse a database in the `<model name> | <score> | <features>` format
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut database = Self::default();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.split('|').map(str::trim);
+            let name = fields.next().unwrap_or_default();
+            let score = fields.next().and_then(|s| s.parse::<u32>().ok());
+            let (Some(score), false) = (score, name.is_empty()) else {
+                return Err(HardwareQueryError::invalid_configuration(format!(
+                    "GPU performance database line {}: expected '<model name> | <score> | <features>'",
+                    number + 1
+                )));
+            };
+            let raytracing = fields
+                .next()
+                .is_some_and(|features| features.split(',').any(|f| f.trim().eq_ignore_ascii_case("rt")));
+
+            database.insert(GPUPerformanceEntry {
+                name: name.to_string(),
+                score,
+                raytracing,
+            });
+        }
+
+        Ok(database)
+    }
+
+    /// Load a database file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Add an entry, replacing any entry with the same model name
+    pub fn insert(&mut self, entry: GPUPerformanceEntry) {
+        let tokens = tokenize(&entry.name);
+        if tokens.is_empty() {
+            return;
+        }
+        match self.entries.iter_mut().find(|(t, _)| *t == tokens) {
+            Some(existing) => existing.1 = entry,
+            None => self.entries.push((tokens, entry)),
+        }
+    }
+
+    /// Merge another database into this one; its entries take precedence
+    pub fn extend(&mut self, other: GPUPerformanceDatabase) {
+        for (_, entry) in other.entries {
+            self.insert(entry);
+        }
+    }
+
+    /// Number of models in the database
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the database has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All entries in the database
+    pub fn entries(&self) -> impl Iterator<Item = &GPUPerformanceEntry> {
+        self.entries.iter().map(|(_, entry)| entry)
+    }
+
+    /// Find the entry for a detected GPU model name
+    ///
+    /// Database names are matched as whole words anywhere in the model name,
+    /// so "NVIDIA GeForce RTX 4070 Ti SUPER" matches "RTX 4070 Ti Super". The
+    /// most specific (longest) match wins.
+    pub fn lookup(&self, model_name: &str) -> Option<&GPUPerformanceEntry> {
+        let model = tokenize(model_name);
+        self.entries
+            .iter()
+            .filter(|(tokens, _)| model.windows(tokens.len()).any(|w| w == tokens.as_slice()))
+            .max_by_key(|(tokens, _)| tokens.len())
+            .map(|(_, entry)| entry)
+    }
+
+    /// The database used by presets and [`crate::GPUInfo::performance_entry`]
+    ///
+    /// This is the embedded database unless another one has been installed.
+    pub fn global() -> Arc<GPUPerformanceDatabase> {
+        global_slot()
+            .read()
+            .map(|db| Arc::clone(&db))
+            .unwrap_or_else(|poisoned| Arc::clone(&poisoned.into_inner()))
+    }
+
+    /// Replace the global database
+    ///
+    /// To add newer models on top of the embedded list, start from
+    /// [`GPUPerformanceDatabase::embedded`] and [`extend`](Self::extend) it.
+    pub fn install(database: GPUPerformanceDatabase) {
+        let mut slot = global_slot().write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        *slot = Arc::new(database);
+    }
+}
+
+fn global_slot() -> &'static RwLock<Arc<GPUPerformanceDatabase>> {
+    static DATABASE: OnceLock<RwLock<Arc<GPUPerformanceDatabase>>> = OnceLock::new();
+    DATABASE.get_or_init(|| RwLock::new(Arc::new(GPUPerformanceDatabase::embedded())))
+}
+
+/// Lowercase alphanumeric words, without trademark markers ("(R)", "(TM)")
+pub(crate) fn tokenize(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .filter(|word| word != "r" && word != "tm")
+        .collect()
+}
@@ -0,0 +1,159 @@
+//! Choosing a GPU for a workload
+//!
+//! [`HardwareInfo::select_gpu`](crate::HardwareInfo::select_gpu) drops the
+//! GPUs that miss a [`GpuCriteria`]'s hard requirements and ranks the rest:
+//! first by the preferred vendor and device class, then by the ranking
+//! metric, then by GPU memory. Ties keep detection order.
+//!
+//! ```rust
+//! use hardware_query::{GPUVendor, GpuCriteria, HardwareInfo};
+//!
+//! # fn main() -> hardware_query::Result<()> {
+//! let hw_info = HardwareInfo::query()?;
+//! let criteria = GpuCriteria::most_vram()
+//!     .prefer_vendor(GPUVendor::NVIDIA)
+//!     .require_cuda("7.0");
+//! if let Some(selection) = hw_info.select_gpu(&criteria) {
+//!     println!("Using GPU {}: {}", selection.index, selection.gpu.model_name());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{GPUInfo, GPUType, GPUVendor, GpuRequirement};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Metric GPUs are ranked by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GpuRanking {
+    /// Largest GPU memory
+    #[default]
+    MostVram,
+    /// Highest FP32 throughput from the specs database
+    MostCompute,
+    /// Lowest current utilization
+    LeastLoaded,
+}
+
+/// Hard requirements and preferences for [`HardwareInfo::select_gpu`](crate::HardwareInfo::select_gpu)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GpuCriteria {
+    /// Metric the matching GPUs are ranked by
+    pub ranking: GpuRanking,
+    /// GPUs from this vendor rank ahead of the others
+    pub preferred_vendor: Option<GPUVendor>,
+    /// Skip integrated, virtual and unknown GPUs
+    pub dedicated_only: bool,
+    /// Requirements every candidate has to meet
+    pub requirement: GpuRequirement,
+}
+
+impl GpuCriteria {
+    /// Rank by GPU memory
+    pub fn most_vram() -> Self {
+        Self::default()
+    }
+
+    /// Rank by FP32 throughput
+    pub fn most_compute() -> Self {
+        Self {
+            ranking: GpuRanking::MostCompute,
+            ..Self::default()
+        }
+    }
+
+    /// Rank by current utilization, least busy first
+    pub fn least_loaded() -> Self {
+        Self {
+            ranking: GpuRanking::LeastLoaded,
+            ..Self::default()
+        }
+    }
+
+    /// Rank GPUs from `vendor` first, without excluding the others
+    pub fn prefer_vendor(mut self, vendor: GPUVendor) -> Self {
+        self.preferred_vendor = Some(vendor);
+        self
+    }
+
+    /// Only consider GPUs from `vendor`
+    pub fn require_vendor(mut self, vendor: GPUVendor) -> Self {
+        self.requirement = self.requirement.with_vendor(vendor);
+        self
+    }
+
+    /// Only consider GPUs with at least CUDA compute capability `minimum`
+    pub fn require_cuda(mut self, minimum: impl Into<String>) -> Self {
+        self.requirement = self.requirement.with_cuda_capability(minimum);
+        self
+    }
+
+    /// Only consider GPUs with at least `gb` of memory
+    pub fn require_memory_gb(mut self, gb: f64) -> Self {
+        self.requirement = self.requirement.with_memory_gb(gb);
+        self
+    }
+
+    /// Skip integrated, virtual and unknown GPUs
+    pub fn dedicated_only(mut self) -> Self {
+        self.dedicated_only = true;
+        self
+    }
+
+    /// Check if `gpu` meets the hard requirements
+    pub fn matches(&self, gpu: &GPUInfo) -> bool {
+        self.requirement.matches(gpu) && (!self.dedicated_only || is_dedicated(gpu))
+    }
+
+    /// Matching GPUs, best first, with their positions in `gpus`
+    pub fn rank<'a>(&self, gpus: &'a [GPUInfo]) -> Vec<GpuSelection<'a>> {
+        let mut candidates: Vec<GpuSelection<'a>> = gpus
+            .iter()
+            .enumerate()
+            .filter(|(_, gpu)| self.matches(gpu))
+            .map(|(index, gpu)| GpuSelection { index, gpu })
+            .collect();
+        // Stable, so equal GPUs keep detection order
+        candidates.sort_by(|a, b| self.compare(b.gpu, a.gpu));
+        candidates
+    }
+
+    /// Ordering where `Greater` means the better candidate
+    fn compare(&self, a: &GPUInfo, b: &GPUInfo) -> Ordering {
+        let preferred = |gpu: &GPUInfo| self.preferred_vendor.as_ref() == Some(gpu.vendor());
+        let metric = |gpu: &GPUInfo| -> Option<f64> {
+            match self.ranking {
+                GpuRanking::MostVram => Some(gpu.memory_mb() as f64),
+                GpuRanking::MostCompute => gpu.fp32_tflops(),
+                GpuRanking::LeastLoaded => gpu.usage_percent().map(|usage| -(usage as f64)),
+            }
+        };
+        let by_metric = match (metric(a), metric(b)) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            // A known value beats an unknown one
+            (a, b) => a.is_some().cmp(&b.is_some()),
+        };
+
+        preferred(a)
+            .cmp(&preferred(b))
+            .then(is_dedicated(a).cmp(&is_dedicated(b)))
+            .then(by_metric)
+            .then(a.memory_mb().cmp(&b.memory_mb()))
+    }
+}
+
+/// Discrete, workstation and datacenter GPUs
+fn is_dedicated(gpu: &GPUInfo) -> bool {
+    matches!(gpu.gpu_type(), GPUType::Discrete | GPUType::Workstation | GPUType::Datacenter)
+}
+
+/// GPU chosen by [`GpuCriteria`]
+#[derive(Debug, Clone, Copy)]
+pub struct GpuSelection<'a> {
+    /// Position in [`HardwareInfo::gpus`](crate::HardwareInfo::gpus)
+    pub index: usize,
+    /// The GPU itself
+    pub gpu: &'a GPUInfo,
+}
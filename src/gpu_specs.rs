@@ -0,0 +1,262 @@
+//! GPU specifications and theoretical throughput
+//!
+//! Drivers rarely report shader counts or memory bus widths, but these are
+//! fixed per model. This module maps model names to published
+//! specifications and derives memory bandwidth, FP32/FP16/INT8 peak
+//! throughput and the tensor (matrix) unit generation from them.
+//!
+//! A database is embedded in the crate (`data/gpu_specs.txt`). As with
+//! [`GPUPerformanceDatabase`](crate::GPUPerformanceDatabase), newer models can
+//! be added by loading a file in the same format and installing it with
+//! [`GPUSpecsDatabase::install`].
+//!
+//! Each non-comment line reads `<model name> | <architecture> | <shader units>
+//! | <boost clock MHz> | <memory bus bits> | <memory Gbps per pin> | <memory
+//! type> | <tensor cores>`.
+
+use crate::gpu_performance::tokenize;
+use crate::{GPUInfo, HardwareQueryError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Embedded specifications database
+const EMBEDDED: &str = include_str!("../data/gpu_specs.txt");
+
+/// Per-architecture throughput rates
+struct ArchitectureRates {
+    /// Architecture key used in the database
+    key: &'static str,
+    /// FP32 instructions issued per shader per clock (2 with RDNA 3/4 dual issue)
+    dual_issue: f64,
+    /// Dense FP16 throughput relative to FP32, on tensor/matrix units where present
+    fp16_ratio: f64,
+    /// Dense INT8 throughput relative to FP32 (DP4A where there are no matrix units)
+    int8_ratio: f64,
+    /// Tensor/matrix unit generation
+    matrix_units: Option<&'static str>,
+}
+
+const ARCHITECTURES: &[ArchitectureRates] = &[
+    ArchitectureRates { key: "blackwell", dual_issue: 1.0, fp16_ratio: 4.0, int8_ratio: 8.0, matrix_units: Some("5th gen Tensor Cores") },
+    ArchitectureRates { key: "hopper", dual_issue: 1.0, fp16_ratio: 14.8, int8_ratio: 29.6, matrix_units: Some("4th gen Tensor Cores") },
+    ArchitectureRates { key: "ada", dual_issue: 1.0, fp16_ratio: 4.0, int8_ratio: 8.0, matrix_units: Some("4th gen Tensor Cores") },
+    ArchitectureRates { key: "ampere-dc", dual_issue: 1.0, fp16_ratio: 16.0, int8_ratio: 32.0, matrix_units: Some("3rd gen Tensor Cores") },
+    ArchitectureRates { key: "ampere", dual_issue: 1.0, fp16_ratio: 4.0, int8_ratio: 8.0, matrix_units: Some("3rd gen Tensor Cores") },
+    ArchitectureRates { key: "turing", dual_issue: 1.0, fp16_ratio: 8.0, int8_ratio: 16.0, matrix_units: Some("2nd gen Tensor Cores") },
+    ArchitectureRates { key: "turing-gtx", dual_issue: 1.0, fp16_ratio: 2.0, int8_ratio: 4.0, matrix_units: None },
+    ArchitectureRates { key: "volta", dual_issue: 1.0, fp16_ratio: 8.0, int8_ratio: 4.0, matrix_units: Some("1st gen Tensor Cores") },
+    ArchitectureRates { key: "pascal", dual_issue: 1.0, fp16_ratio: 1.0 / 64.0, int8_ratio: 4.0, matrix_units: None },
+    ArchitectureRates { key: "rdna4", dual_issue: 2.0, fp16_ratio: 4.0, int8_ratio: 8.0, matrix_units: Some("RDNA 4 AI Accelerators") },
+    ArchitectureRates { key: "rdna3", dual_issue: 2.0, fp16_ratio: 2.0, int8_ratio: 2.0, matrix_units: Some("RDNA 3 AI Accelerators") },
+    ArchitectureRates { key: "rdna2", dual_issue: 1.0, fp16_ratio: 2.0, int8_ratio: 4.0, matrix_units: None },
+    ArchitectureRates { key: "cdna3", dual_issue: 1.0, fp16_ratio: 16.0, int8_ratio: 32.0, matrix_units: Some("CDNA 3 Matrix Cores") },
+    ArchitectureRates { key: "cdna2", dual_issue: 1.0, fp16_ratio: 8.0, int8_ratio: 8.0, matrix_units: Some("CDNA 2 Matrix Cores") },
+    ArchitectureRates { key: "xe2", dual_issue: 1.0, fp16_ratio: 8.0, int8_ratio: 16.0, matrix_units: Some("Xe2 XMX") },
+    ArchitectureRates { key: "xe-hpg", dual_issue: 1.0, fp16_ratio: 8.0, int8_ratio: 16.0, matrix_units: Some("Xe-HPG XMX") },
+    ArchitectureRates { key: "apple", dual_issue: 1.0, fp16_ratio: 1.0, int8_ratio: 1.0, matrix_units: None },
+];
+
+/// Published specifications of one GPU model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GPUSpecs {
+    /// Model name as listed in the database (e.g. "RTX 4070 Ti")
+    pub name: String,
+    /// Architecture key (`ada`, `rdna3`, `xe-hpg`, `apple`, ...)
+    pub architecture: String,
+    /// CUDA cores / stream processors / ALUs
+    pub shader_units: u32,
+    /// Boost clock in MHz
+    pub boost_clock_mhz: u32,
+    /// Memory bus width in bits
+    pub memory_bus_width: u32,
+    /// Memory data rate in Gbps per pin
+    pub memory_data_rate_gbps: f64,
+    /// Memory type (GDDR6X, HBM3, LPDDR5, ...)
+    pub memory_type: String,
+    /// NVIDIA tensor cores, if the GPU has them
+    pub tensor_cores: Option<u32>,
+}
+
+impl GPUSpecs {
+    fn rates(&self) -> Option<&'static ArchitectureRates> {
+        ARCHITECTURES
+            .iter()
+            .find(|rates| rates.key.eq_ignore_ascii_case(&self.architecture))
+    }
+
+    /// Peak memory bandwidth in GB/s
+    pub fn memory_bandwidth_gbps(&self) -> f64 {
+        self.memory_bus_width as f64 / 8.0 * self.memory_data_rate_gbps
+    }
+
+    /// Peak FP32 throughput in TFLOPS (one FMA per shader per clock)
+    pub fn fp32_tflops(&self) -> f64 {
+        let dual_issue = self.rates().map_or(1.0, |rates| rates.dual_issue);
+        self.shader_units as f64 * 2.0 * self.boost_clock_mhz as f64 * dual_issue / 1e6
+    }
+
+    /// Peak dense FP16 throughput in TFLOPS, for known architectures
+    pub fn fp16_tflops(&self) -> Option<f64> {
+        Some(self.fp32_tflops() * self.rates()?.fp16_ratio)
+    }
+
+    /// Peak dense INT8 throughput in TOPS, for known architectures
+    pub fn int8_tops(&self) -> Option<f64> {
+        Some(self.fp32_tflops() * self.rates()?.int8_ratio)
+    }
+
+    /// Tensor/matrix unit generation (`4th gen Tensor Cores`, `Xe-HPG XMX`)
+    pub fn tensor_core_generation(&self) -> Option<&'static str> {
+        self.rates()?.matrix_units
+    }
+}
+
+/// GPU model to specifications database
+#[derive(Debug, Clone, Default)]
+pub struct GPUSpecsDatabase {
+    /// Entries with their normalized name tokens
+    entries: Vec<(Vec<String>, GPUSpecs)>,
+}
+
+impl GPUSpecsDatabase {
+    /// The database embedded in the crate
+    pub fn embedded() -> Self {
+        Self::parse(EMBEDDED).unwrap_or_default()
+    }
+
+    /// Parse a database in the `<model name> | <architecture> | ... | <tensor cores>` format
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut database = Self::default();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+            let Some(specs) = parse_fields(&fields) else {
+                return Err(HardwareQueryError::invalid_configuration(format!(
+                    "GPU specs database line {}: expected '<model name> | <architecture> | <shader units> | \
+                     <boost clock MHz> | <memory bus bits> | <memory Gbps> | <memory type> | <tensor cores>'",
+                    number + 1
+                )));
+            };
+            database.insert(specs);
+        }
+
+        Ok(database)
+    }
+
+    /// Load a database file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Add an entry, replacing any entry with the same model name
+    pub fn insert(&mut self, specs: GPUSpecs) {
+        let tokens = tokenize(&specs.name);
+        if tokens.is_empty() {
+            return;
+        }
+        match self.entries.iter_mut().find(|(t, _)| *t == tokens) {
+            Some(existing) => existing.1 = specs,
+            None => self.entries.push((tokens, specs)),
+        }
+    }
+
+    /// Merge another database into this one; its entries take precedence
+    pub fn extend(&mut self, other: GPUSpecsDatabase) {
+        for (_, specs) in other.entries {
+            self.insert(specs);
+        }
+    }
+
+    /// Number of models in the database
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the database has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All entries in the database
+    pub fn entries(&self) -> impl Iterator<Item = &GPUSpecs> {
+        self.entries.iter().map(|(_, specs)| specs)
+    }
+
+    /// Find the specifications of a detected GPU model name
+    ///
+    /// Matching works like [`GPUPerformanceDatabase::lookup`](crate::GPUPerformanceDatabase::lookup):
+    /// whole words anywhere in the model name, longest match wins.
+    pub fn lookup(&self, model_name: &str) -> Option<&GPUSpecs> {
+        let model = tokenize(model_name);
+        self.entries
+            .iter()
+            .filter(|(tokens, _)| model.windows(tokens.len()).any(|w| w == tokens.as_slice()))
+            .max_by_key(|(tokens, _)| tokens.len())
+            .map(|(_, specs)| specs)
+    }
+
+    /// The database used by [`crate::GPUInfo::specs`] and GPU detection
+    ///
+    /// This is the embedded database unless another one has been installed.
+    pub fn global() -> Arc<GPUSpecsDatabase> {
+        global_slot()
+            .read()
+            .map(|db| Arc::clone(&db))
+            .unwrap_or_else(|poisoned| Arc::clone(&poisoned.into_inner()))
+    }
+
+    /// Replace the global database
+    pub fn install(database: GPUSpecsDatabase) {
+        let mut slot = global_slot().write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        *slot = Arc::new(database);
+    }
+}
+
+/// One database line split into its fields
+fn parse_fields(fields: &[&str]) -> Option<GPUSpecs> {
+    let [name, architecture, shaders, clock, bus, rate, memory_type, tensor_cores] = fields else {
+        return None;
+    };
+    if name.is_empty() {
+        return None;
+    }
+    Some(GPUSpecs {
+        name: name.to_string(),
+        architecture: architecture.to_ascii_lowercase(),
+        shader_units: shaders.parse().ok()?,
+        boost_clock_mhz: clock.parse().ok()?,
+        memory_bus_width: bus.parse().ok()?,
+        memory_data_rate_gbps: rate.parse().ok()?,
+        memory_type: memory_type.to_string(),
+        tensor_cores: Some(tensor_cores.parse::<u32>().ok()?).filter(|&cores| cores > 0),
+    })
+}
+
+fn global_slot() -> &'static RwLock<Arc<GPUSpecsDatabase>> {
+    static DATABASE: OnceLock<RwLock<Arc<GPUSpecsDatabase>>> = OnceLock::new();
+    DATABASE.get_or_init(|| RwLock::new(Arc::new(GPUSpecsDatabase::embedded())))
+}
+
+/// Fill in bandwidth, shader/tensor counts, boost clock and memory type that
+/// the drivers did not report
+pub(crate) fn apply_specs(gpus: &mut [GPUInfo]) {
+    let database = GPUSpecsDatabase::global();
+    for gpu in gpus.iter_mut() {
+        let Some(specs) = database.lookup(&gpu.model_name) else {
+            continue;
+        };
+        gpu.memory_bandwidth.get_or_insert(specs.memory_bandwidth_gbps() as f32);
+        gpu.shader_units.get_or_insert(specs.shader_units);
+        gpu.boost_clock.get_or_insert(specs.boost_clock_mhz);
+        gpu.memory_type.get_or_insert_with(|| specs.memory_type.clone());
+        if gpu.tensor_cores.is_none() {
+            gpu.tensor_cores = specs.tensor_cores;
+        }
+    }
+}
@@ -0,0 +1,384 @@
+//! Multi-GPU topology: PCIe links, NVLink / Infinity Fabric peers and NUMA placement
+//!
+//! Collective operations in multi-GPU training are limited by the slowest path
+//! between devices. This module reports how each GPU is attached (PCIe
+//! generation and width, NUMA node) and which GPUs have direct peer links, so
+//! callers can group devices or estimate peer-to-peer bandwidth.
+//! GPU indices match [`HardwareInfo::gpus`](crate::HardwareInfo::gpus).
+
+use crate::GPUInfo;
+use serde::{Deserialize, Serialize};
+
+/// Number of NVLink slots NVML exposes per device
+#[cfg(feature = "nvidia")]
+const MAX_NVLINKS: u32 = 18;
+
+/// PCIe link of a GPU or other PCIe device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PCIeLink {
+    /// Negotiated PCIe generation
+    pub generation: u8,
+    /// Negotiated lane count
+    pub width: u8,
+    /// Highest generation supported by both the device and the slot (if known)
+    pub max_generation: Option<u8>,
+    /// Widest lane count supported (if known)
+    pub max_width: Option<u8>,
+}
+
+impl PCIeLink {
+    /// Usable one-direction bandwidth of the negotiated link in GB/s
+    pub fn bandwidth_gbps(&self) -> f64 {
+        // Per-lane throughput after line encoding (8b/10b up to gen 2, 128b/130b after)
+        let per_lane = match self.generation {
+            1 => 0.25,
+            2 => 0.5,
+            3 => 0.985,
+            4 => 1.969,
+            5 => 3.938,
+            _ => 7.563,
+        };
+        per_lane * self.width as f64
+    }
+
+    /// Read the link of a PCI device from its sysfs directory
+    /// (`/sys/bus/pci/devices/<address>`)
+    #[cfg(target_os = "linux")]
+    pub(crate) fn from_sysfs(device: &std::path::Path) -> Option<Self> {
+        let read = |name: &str| {
+            std::fs::read_to_string(device.join(name))
+                .ok()
+                .map(|value| value.trim().to_string())
+        };
+
+        Some(Self {
+            generation: read("current_link_speed").and_then(|speed| Self::generation_from_speed(&speed))?,
+            width: read("current_link_width").and_then(|width| width.parse().ok())?,
+            max_generation: read("max_link_speed").and_then(|speed| Self::generation_from_speed(&speed)),
+            max_width: read("max_link_width").and_then(|width| width.parse().ok()),
+        })
+    }
+
+    /// Map "16.0 GT/s PCIe" to a PCIe generation
+    #[cfg(target_os = "linux")]
+    fn generation_from_speed(speed: &str) -> Option<u8> {
+        let rate: f32 = speed.split_whitespace().next()?.parse().ok()?;
+        Some(match rate {
+            r if r < 4.0 => 1,
+            r if r < 6.0 => 2,
+            r if r < 12.0 => 3,
+            r if r < 24.0 => 4,
+            r if r < 48.0 => 5,
+            _ => 6,
+        })
+    }
+
+    /// Whether the link trained below what the device and slot support
+    ///
+    /// GPUs commonly drop to a lower generation when idle, so this is only
+    /// meaningful under load.
+    pub fn is_degraded(&self) -> bool {
+        self.max_generation.is_some_and(|max| self.generation < max)
+            || self.max_width.is_some_and(|max| self.width < max)
+    }
+}
+
+/// Direct GPU-to-GPU link technology
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GPUInterconnect {
+    /// NVIDIA NVLink (bandwidth summed over all links to the peer)
+    NVLink,
+    /// AMD Infinity Fabric (xGMI) within a hive
+    InfinityFabric,
+    /// PCIe, both devices under the same NUMA node
+    PCIe,
+    /// PCIe through the inter-socket interconnect
+    CrossSocket,
+}
+
+impl std::fmt::Display for GPUInterconnect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GPUInterconnect::NVLink => write!(f, "NVLink"),
+            GPUInterconnect::InfinityFabric => write!(f, "Infinity Fabric"),
+            GPUInterconnect::PCIe => write!(f, "PCIe"),
+            GPUInterconnect::CrossSocket => write!(f, "PCIe (cross-socket)"),
+        }
+    }
+}
+
+/// An active NVLink from one GPU to another device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NVLinkConnection {
+    /// Link slot on the local GPU
+    pub link: u32,
+    /// NVLink version
+    pub version: u32,
+    /// PCI bus address of the remote end (a GPU or an NVSwitch)
+    pub peer_pci_bus_id: String,
+}
+
+impl NVLinkConnection {
+    /// One-direction bandwidth of this link in GB/s
+    pub fn bandwidth_gbps(&self) -> f64 {
+        match self.version {
+            1 => 20.0,
+            _ => 25.0,
+        }
+    }
+}
+
+/// Placement and links of a single GPU
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GPUNode {
+    /// Index into [`HardwareInfo::gpus`](crate::HardwareInfo::gpus)
+    pub index: usize,
+    /// PCI bus address (domain:bus:device.function)
+    pub pci_bus_id: Option<String>,
+    /// PCIe link state (if known)
+    pub pcie: Option<PCIeLink>,
+    /// NUMA node the GPU is attached to (if known)
+    pub numa_node: Option<u32>,
+    /// Active NVLinks
+    pub nvlinks: Vec<NVLinkConnection>,
+    /// AMD xGMI hive id; GPUs sharing a hive are linked by Infinity Fabric
+    pub xgmi_hive_id: Option<u64>,
+}
+
+/// How the system's GPUs are connected to the host and to each other
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GPUTopology {
+    /// One entry per GPU, in the same order as the GPU list it was built from
+    pub gpus: Vec<GPUNode>,
+}
+
+impl GPUTopology {
+    /// Query link and placement information for `gpus`
+    ///
+    /// Uses sysfs on Linux and NVML for NVIDIA GPUs (with the `nvidia`
+    /// feature). Fields that cannot be determined are left empty.
+    pub fn query(gpus: &[GPUInfo]) -> Self {
+        #[allow(unused_mut)]
+        let mut nodes: Vec<GPUNode> = gpus
+            .iter()
+            .enumerate()
+            .map(|(index, gpu)| GPUNode {
+                index,
+                pci_bus_id: gpu.pci_bus_id.clone(),
+                pcie: None,
+                numa_node: None,
+                nvlinks: Vec::new(),
+                xgmi_hive_id: None,
+            })
+            .collect();
+
+        #[cfg(target_os = "linux")]
+        {
+            for node in &mut nodes {
+                if let Some(bus_id) = node.pci_bus_id.clone() {
+                    linux::fill_node(node, &bus_id);
+                }
+            }
+        }
+
+        #[cfg(feature = "nvidia")]
+        Self::fill_from_nvml(&mut nodes);
+
+        Self { gpus: nodes }
+    }
+
+    /// Get per-GPU placement information
+    pub fn gpus(&self) -> &[GPUNode] {
+        &self.gpus
+    }
+
+    /// Whether any GPU has an active NVLink
+    pub fn has_nvlink(&self) -> bool {
+        self.gpus.iter().any(|gpu| !gpu.nvlinks.is_empty())
+    }
+
+    /// Whether two GPUs sit on the same NUMA node (`None` if unknown)
+    pub fn same_numa_node(&self, a: usize, b: usize) -> Option<bool> {
+        let a = self.gpus.get(a)?.numa_node?;
+        let b = self.gpus.get(b)?.numa_node?;
+        Some(a == b)
+    }
+
+    /// Fastest path between two GPUs (`None` for the same GPU or an unknown index)
+    pub fn interconnect(&self, a: usize, b: usize) -> Option<GPUInterconnect> {
+        if a == b {
+            return None;
+        }
+        let (first, second) = (self.gpus.get(a)?, self.gpus.get(b)?);
+
+        if self.nvlink_bandwidth(first, second) > 0.0 {
+            Some(GPUInterconnect::NVLink)
+        } else if first.xgmi_hive_id.is_some() && first.xgmi_hive_id == second.xgmi_hive_id {
+            Some(GPUInterconnect::InfinityFabric)
+        } else if self.same_numa_node(a, b) == Some(false) {
+            Some(GPUInterconnect::CrossSocket)
+        } else {
+            Some(GPUInterconnect::PCIe)
+        }
+    }
+
+    /// Estimated one-direction bandwidth from GPU `a` to GPU `b` in GB/s
+    ///
+    /// PCIe paths are limited by the slower of the two links and halved when
+    /// they cross sockets. Returns zero for the same GPU or unknown links.
+    pub fn peer_bandwidth_gbps(&self, a: usize, b: usize) -> f64 {
+        let (Some(first), Some(second)) = (self.gpus.get(a), self.gpus.get(b)) else {
+            return 0.0;
+        };
+        let pcie = match (first.pcie, second.pcie) {
+            (Some(x), Some(y)) => x.bandwidth_gbps().min(y.bandwidth_gbps()),
+            _ => 0.0,
+        };
+
+        match self.interconnect(a, b) {
+            None => 0.0,
+            Some(GPUInterconnect::NVLink) => self.nvlink_bandwidth(first, second),
+            // xGMI links run at roughly 50 GB/s per direction
+            Some(GPUInterconnect::InfinityFabric) => pcie.max(50.0),
+            Some(GPUInterconnect::PCIe) => pcie,
+            Some(GPUInterconnect::CrossSocket) => pcie / 2.0,
+        }
+    }
+
+    /// Estimated peer-to-peer bandwidth between every GPU pair in GB/s
+    ///
+    /// `matrix[a][b]` is [`peer_bandwidth_gbps(a, b)`](Self::peer_bandwidth_gbps);
+    /// the diagonal is zero.
+    pub fn peer_bandwidth_matrix(&self) -> Vec<Vec<f64>> {
+        (0..self.gpus.len())
+            .map(|a| (0..self.gpus.len()).map(|b| self.peer_bandwidth_gbps(a, b)).collect())
+            .collect()
+    }
+
+    /// NVLink bandwidth from `from` to `to` in GB/s
+    ///
+    /// Direct links to the peer are summed. Links that end at a device which is
+    /// not one of our GPUs are NVSwitch ports; two GPUs that both reach the
+    /// switch fabric communicate at the slower side's switch bandwidth.
+    fn nvlink_bandwidth(&self, from: &GPUNode, to: &GPUNode) -> f64 {
+        let Some(target) = &to.pci_bus_id else {
+            return 0.0;
+        };
+        let direct: f64 = from
+            .nvlinks
+            .iter()
+            .filter(|link| crate::gpu::same_pci_address(&link.peer_pci_bus_id, target))
+            .map(NVLinkConnection::bandwidth_gbps)
+            .sum();
+        if direct > 0.0 {
+            return direct;
+        }
+
+        let switch_bandwidth = |node: &GPUNode| -> f64 {
+            node.nvlinks
+                .iter()
+                .filter(|link| !self.is_gpu(&link.peer_pci_bus_id))
+                .map(NVLinkConnection::bandwidth_gbps)
+                .sum()
+        };
+        switch_bandwidth(from).min(switch_bandwidth(to))
+    }
+
+    /// Whether a PCI address belongs to one of the GPUs in this topology
+    fn is_gpu(&self, bus_id: &str) -> bool {
+        self.gpus.iter().any(|gpu| {
+            gpu.pci_bus_id
+                .as_deref()
+                .is_some_and(|own| crate::gpu::same_pci_address(own, bus_id))
+        })
+    }
+
+    #[cfg(feature = "nvidia")]
+    fn fill_from_nvml(nodes: &mut [GPUNode]) {
+        use nvml_wrapper::Nvml;
+
+        let Ok(nvml) = Nvml::init() else {
+            return;
+        };
+        let count = nvml.device_count().unwrap_or(0);
+
+        for i in 0..count {
+            let Ok(device) = nvml.device_by_index(i) else {
+                continue;
+            };
+            let Ok(pci) = device.pci_info() else {
+                continue;
+            };
+            let Some(node) = nodes.iter_mut().find(|node| {
+                node.pci_bus_id
+                    .as_deref()
+                    .is_some_and(|bus_id| crate::gpu::same_pci_address(bus_id, &pci.bus_id))
+            }) else {
+                continue;
+            };
+
+            if node.pcie.is_none() {
+                if let (Ok(generation), Ok(width)) =
+                    (device.current_pcie_link_gen(), device.current_pcie_link_width())
+                {
+                    node.pcie = Some(PCIeLink {
+                        generation: generation as u8,
+                        width: width as u8,
+                        max_generation: device.max_pcie_link_gen().ok().map(|g| g as u8),
+                        max_width: device.max_pcie_link_width().ok().map(|w| w as u8),
+                    });
+                }
+            }
+
+            for link in 0..MAX_NVLINKS {
+                let nvlink = device.link_wrapper_for(link);
+                if !nvlink.is_active().unwrap_or(false) {
+                    continue;
+                }
+                let Ok(remote) = nvlink.remote_pci_info() else {
+                    continue;
+                };
+                node.nvlinks.push(NVLinkConnection {
+                    link,
+                    version: nvlink.version().unwrap_or(0),
+                    peer_pci_bus_id: remote.bus_id,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{GPUNode, PCIeLink};
+    use std::fs;
+    use std::path::Path;
+
+    pub(super) fn fill_node(node: &mut GPUNode, bus_id: &str) {
+        // sysfs uses a 4-digit domain; NVML reports 8
+        let bus_id = match bus_id.split_once(':') {
+            Some((domain, rest)) if domain.len() > 4 => {
+                format!("{}:{}", &domain[domain.len() - 4..], rest)
+            }
+            _ => bus_id.to_string(),
+        }
+        .to_lowercase();
+        let device = Path::new("/sys/bus/pci/devices").join(bus_id);
+
+        let read = |name: &str| {
+            fs::read_to_string(device.join(name))
+                .ok()
+                .map(|value| value.trim().to_string())
+        };
+
+        node.numa_node = read("numa_node")
+            .and_then(|value| value.parse::<i32>().ok())
+            .and_then(|node| u32::try_from(node).ok());
+
+        node.pcie = PCIeLink::from_sysfs(&device);
+
+        node.xgmi_hive_id = read("xgmi_hive_info/xgmi_hive_id")
+            .and_then(|id| id.parse::<u64>().ok())
+            .filter(|&id| id != 0);
+    }
+}
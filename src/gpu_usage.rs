@@ -0,0 +1,257 @@
+//! Live GPU utilization, VRAM, clock and power readings
+//!
+//! [`GPUInfo`](crate::GPUInfo) is an inventory query and only carries a
+//! utilization snapshot taken during detection. [`GPUUsage::query_all`] reads
+//! just the live counters, cheaply enough to run on every monitoring update:
+//! NVML for NVIDIA GPUs (`nvidia` feature) and the amdgpu / i915 / xe sysfs
+//! attributes on Linux. Fields a backend can't provide are `None`.
+
+use crate::GPUVendor;
+use serde::{Deserialize, Serialize};
+
+/// Live counters of one GPU
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GPUUsage {
+    /// PCI address, e.g. `0000:01:00.0`
+    pub device: String,
+    /// Marketing name when the backend reports one
+    pub name: Option<String>,
+    /// GPU vendor
+    pub vendor: GPUVendor,
+    /// Busy percentage of the graphics/compute engine
+    pub utilization_percent: Option<f32>,
+    /// VRAM in use in MB
+    pub memory_used_mb: Option<u64>,
+    /// Total VRAM in MB
+    pub memory_total_mb: Option<u64>,
+    /// Current graphics clock in MHz
+    pub core_clock_mhz: Option<u32>,
+    /// Current memory clock in MHz
+    pub memory_clock_mhz: Option<u32>,
+    /// Board power draw in watts
+    pub power_draw_watts: Option<f32>,
+    /// Power limit currently enforced by the driver in watts
+    #[serde(default)]
+    pub power_limit_watts: Option<f32>,
+    /// GPU temperature in Celsius
+    pub temperature: Option<f32>,
+}
+
+impl GPUUsage {
+    /// Read live counters of every GPU with a usage backend
+    ///
+    /// Returns an empty list on platforms without one.
+    pub fn query_all() -> Vec<Self> {
+        #[allow(unused_mut)]
+        let mut gpus: Vec<Self> = Vec::new();
+
+        #[cfg(feature = "nvidia")]
+        gpus.extend(nvidia_gpus());
+
+        #[cfg(target_os = "linux")]
+        for gpu in linux::drm_gpus() {
+            if !gpus.iter().any(|known| known.device.eq_ignore_ascii_case(&gpu.device)) {
+                gpus.push(gpu);
+            }
+        }
+
+        gpus
+    }
+
+    /// Read the live counters of an amdgpu PCI device directory
+    ///
+    /// `device_dir` is `/sys/class/drm/cardN/device` (or a copy of it) and
+    /// `device` the PCI address to report.
+    #[cfg(target_os = "linux")]
+    pub fn from_amdgpu_sysfs(device_dir: &std::path::Path, device: impl Into<String>) -> Self {
+        linux::amdgpu(device_dir, device.into())
+    }
+
+    /// Get device identifier
+    pub fn device(&self) -> &str {
+        &self.device
+    }
+
+    /// VRAM in use as a percentage of the total
+    pub fn memory_used_percent(&self) -> Option<f64> {
+        match (self.memory_used_mb, self.memory_total_mb) {
+            (Some(used), Some(total)) if total > 0 => Some(used as f64 / total as f64 * 100.0),
+            _ => None,
+        }
+    }
+
+    /// Free VRAM in MB
+    pub fn memory_free_mb(&self) -> Option<u64> {
+        Some(self.memory_total_mb?.saturating_sub(self.memory_used_mb?))
+    }
+}
+
+#[cfg(feature = "nvidia")]
+fn nvidia_gpus() -> Vec<GPUUsage> {
+    use nvml_wrapper::enum_wrappers::device::{Clock, TemperatureSensor};
+
+    let Ok(nvml) = nvml_wrapper::Nvml::init() else {
+        return Vec::new();
+    };
+    let mut gpus = Vec::new();
+    for index in 0..nvml.device_count().unwrap_or(0) {
+        let Ok(device) = nvml.device_by_index(index) else {
+            continue;
+        };
+        let Ok(pci) = device.pci_info() else {
+            continue;
+        };
+        let memory = device.memory_info().ok();
+
+        gpus.push(GPUUsage {
+            // NVML pads the PCI domain to eight digits ("00000000:01:00.0")
+            device: normalize_bus_id(&pci.bus_id),
+            name: device.name().ok(),
+            vendor: GPUVendor::NVIDIA,
+            utilization_percent: device.utilization_rates().ok().map(|u| u.gpu as f32),
+            memory_used_mb: memory.as_ref().map(|m| m.used / (1024 * 1024)),
+            memory_total_mb: memory.as_ref().map(|m| m.total / (1024 * 1024)),
+            core_clock_mhz: device.clock_info(Clock::Graphics).ok(),
+            memory_clock_mhz: device.clock_info(Clock::Memory).ok(),
+            power_draw_watts: device.power_usage().ok().map(|mw| mw as f32 / 1000.0),
+            power_limit_watts: device.enforced_power_limit().ok().map(|mw| mw as f32 / 1000.0),
+            temperature: device.temperature(TemperatureSensor::Gpu).ok().map(|t| t as f32),
+        });
+    }
+    gpus
+}
+
+/// Shorten an eight-digit PCI domain to the four digits sysfs uses
+#[cfg(feature = "nvidia")]
+fn normalize_bus_id(bus_id: &str) -> String {
+    match bus_id.split_once(':') {
+        Some((domain, rest)) if domain.len() > 4 => {
+            format!("{}:{rest}", &domain[domain.len() - 4..]).to_lowercase()
+        }
+        _ => bus_id.to_lowercase(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::GPUUsage;
+    use crate::GPUVendor;
+    use std::fs;
+    use std::path::Path;
+
+    pub(super) fn drm_gpus() -> Vec<GPUUsage> {
+        let Ok(entries) = fs::read_dir("/sys/class/drm") else {
+            return Vec::new();
+        };
+        let mut cards: Vec<_> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(|name| name.strip_prefix("card"))
+                    .is_some_and(|index| index.chars().all(|c| c.is_ascii_digit()))
+            })
+            .collect();
+        cards.sort();
+
+        let mut gpus = Vec::new();
+        for card in cards {
+            let device_dir = card.join("device");
+            let Some(device) = fs::canonicalize(&device_dir)
+                .ok()
+                .and_then(|path| path.file_name()?.to_str().map(str::to_string))
+            else {
+                continue;
+            };
+            let gpu = match read_string(&device_dir.join("vendor")).as_deref() {
+                Some("0x1002") => amdgpu(&device_dir, device),
+                Some("0x8086") => intel(&card, &device_dir, device),
+                // NVIDIA's driver publishes nothing live in sysfs; NVML covers it
+                _ => continue,
+            };
+            gpus.push(gpu);
+        }
+        gpus
+    }
+
+    /// `gpu_busy_percent`, `mem_info_vram_*`, the active `pp_dpm_*` state and hwmon
+    pub(super) fn amdgpu(device_dir: &Path, device: String) -> GPUUsage {
+        let mut gpu = empty(device, GPUVendor::AMD);
+        gpu.utilization_percent = read_u64(&device_dir.join("gpu_busy_percent")).map(|busy| busy as f32);
+        gpu.memory_used_mb = read_u64(&device_dir.join("mem_info_vram_used")).map(|b| b / (1024 * 1024));
+        gpu.memory_total_mb = read_u64(&device_dir.join("mem_info_vram_total")).map(|b| b / (1024 * 1024));
+        gpu.core_clock_mhz = active_dpm_mhz(&device_dir.join("pp_dpm_sclk"));
+        gpu.memory_clock_mhz = active_dpm_mhz(&device_dir.join("pp_dpm_mclk"));
+        apply_hwmon(&mut gpu, device_dir);
+        gpu
+    }
+
+    /// i915 `gt_act_freq_mhz` / xe `tile0/gt0/freq0/act_freq`; no busy counter without perf
+    fn intel(card: &Path, device_dir: &Path, device: String) -> GPUUsage {
+        let mut gpu = empty(device, GPUVendor::Intel);
+        gpu.core_clock_mhz = read_u64(&card.join("gt_act_freq_mhz"))
+            .or_else(|| read_u64(&device_dir.join("tile0/gt0/freq0/act_freq")))
+            .map(|mhz| mhz as u32);
+        // Discrete Arc cards (xe/i915 with local memory) report VRAM like amdgpu
+        gpu.memory_used_mb = read_u64(&device_dir.join("mem_info_vram_used")).map(|b| b / (1024 * 1024));
+        gpu.memory_total_mb = read_u64(&device_dir.join("mem_info_vram_total")).map(|b| b / (1024 * 1024));
+        apply_hwmon(&mut gpu, device_dir);
+        gpu
+    }
+
+    fn empty(device: String, vendor: GPUVendor) -> GPUUsage {
+        GPUUsage {
+            device,
+            name: None,
+            vendor,
+            utilization_percent: None,
+            memory_used_mb: None,
+            memory_total_mb: None,
+            core_clock_mhz: None,
+            memory_clock_mhz: None,
+            power_draw_watts: None,
+            power_limit_watts: None,
+            temperature: None,
+        }
+    }
+
+    /// Temperature in m°C, power and power cap in µW from the device's hwmon node
+    fn apply_hwmon(gpu: &mut GPUUsage, device_dir: &Path) {
+        let Ok(entries) = fs::read_dir(device_dir.join("hwmon")) else {
+            return;
+        };
+        for hwmon in entries.flatten().map(|entry| entry.path()) {
+            if let Some(temp) = read_u64(&hwmon.join("temp1_input")) {
+                gpu.temperature = Some(temp as f32 / 1000.0);
+            }
+            if let Some(power) = read_u64(&hwmon.join("power1_average"))
+                .or_else(|| read_u64(&hwmon.join("power1_input")))
+            {
+                gpu.power_draw_watts = Some(power as f32 / 1_000_000.0);
+            }
+            if let Some(cap) = read_u64(&hwmon.join("power1_cap")).filter(|cap| *cap > 0) {
+                gpu.power_limit_watts = Some(cap as f32 / 1_000_000.0);
+            }
+        }
+    }
+
+    /// The state marked `*` in a pp_dpm table ("1: 1800Mhz *")
+    fn active_dpm_mhz(path: &Path) -> Option<u32> {
+        fs::read_to_string(path).ok()?.lines().find_map(|line| {
+            if !line.trim_end().ends_with('*') {
+                return None;
+            }
+            let freq = line.split_whitespace().nth(1)?;
+            freq.to_lowercase().trim_end_matches("mhz").parse().ok()
+        })
+    }
+
+    fn read_string(path: &Path) -> Option<String> {
+        Some(fs::read_to_string(path).ok()?.trim().to_string())
+    }
+
+    fn read_u64(path: &Path) -> Option<u64> {
+        read_string(path)?.parse().ok()
+    }
+}
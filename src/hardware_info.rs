@@ -1,15 +1,121 @@
 use crate::{
-    BatteryInfo, CPUInfo, GPUInfo, HardwareQueryError,
-    MemoryInfo, NetworkInfo, NPUInfo, PCIDevice, Result, StorageInfo, ThermalInfo, TPUInfo, USBDevice,
-    ARMHardwareInfo, FPGAInfo, PowerProfile, VirtualizationInfo,
+    BatteryInfo, CPUInfo, DeviceTopology, ErrorCode, GPUInfo, GPUTopology, HardwareQueryError, IOMMUGroup,
+    MemoryInfo, NetworkInfo, NPUInfo, PCIDevice, PhysicalDisk, Result, StorageInfo, SystemInfo, ThermalInfo, TPUInfo, USBDevice,
+    USBTopology, ARMHardwareInfo, FPGAInfo, PowerProfile, VirtualizationInfo, Volume,
 };
+use crate::storage::StorageLayout;
 use serde::{Deserialize, Serialize};
-use std::time::{SystemTime, UNIX_EPOCH};
+use crate::cache::{CacheConfig, HardwareComponent, QueryCache};
+use crate::options::{self, Probe, QueryOptions};
+use crate::provider::{HardwareProvider, SystemProvider};
+use std::sync::Arc;
+use std::time::Duration;
 use std::collections::HashMap;
 
+/// Version of the serialized [`HardwareInfo`] format
+///
+/// Bumped whenever a serialized field is renamed, removed or changes meaning.
+/// Adding optional fields does not change the version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+fn current_schema_version() -> u32 {
+    SCHEMA_VERSION
+}
+
+/// Live readings that [`HardwareInfo::refresh`] can update without re-running detection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DynamicComponent {
+    /// Per-core CPU usage
+    CpuUsage,
+    /// Memory and swap usage
+    Memory,
+    /// Temperature sensors, fans and the thermal status
+    Thermal,
+    /// Battery charge and status
+    Battery,
+    /// Power source, power plan and power draw
+    Power,
+}
+
+impl DynamicComponent {
+    /// All refreshable readings
+    pub const ALL: [DynamicComponent; 5] = [
+        DynamicComponent::CpuUsage,
+        DynamicComponent::Memory,
+        DynamicComponent::Thermal,
+        DynamicComponent::Battery,
+        DynamicComponent::Power,
+    ];
+}
+
+impl std::fmt::Display for DynamicComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DynamicComponent::CpuUsage => write!(f, "CPU usage"),
+            DynamicComponent::Memory => write!(f, "Memory"),
+            DynamicComponent::Thermal => write!(f, "Thermal"),
+            DynamicComponent::Battery => write!(f, "Battery"),
+            DynamicComponent::Power => write!(f, "Power"),
+        }
+    }
+}
+
+/// Why a component is missing from a [`HardwareInfo`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComponentErrorKind {
+    /// The component's query returned an error
+    Failed,
+    /// The component's query exceeded its [`QueryOptions`] timeout
+    TimedOut,
+    /// The platform has no way to detect the component (for example in a browser)
+    Unsupported,
+}
+
+/// A component that could not be detected
+///
+/// The corresponding [`HardwareInfo`] field holds a placeholder (empty list,
+/// `None`, or zeroed readings) rather than real data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentError {
+    /// Component group affected
+    pub component: HardwareComponent,
+    /// Whether the query failed or timed out
+    pub kind: ComponentErrorKind,
+    /// Description of the problem
+    pub message: String,
+    /// Machine-readable reason, e.g. to tell missing privileges from absent hardware
+    #[serde(default)]
+    pub code: ErrorCode,
+}
+
+impl ComponentError {
+    /// Record a component whose query returned `error`
+    pub(crate) fn from_error(component: HardwareComponent, error: &crate::HardwareQueryError) -> Self {
+        let kind = match error.code() {
+            ErrorCode::Unsupported => ComponentErrorKind::Unsupported,
+            _ => ComponentErrorKind::Failed,
+        };
+        Self {
+            component,
+            kind,
+            message: error.to_string(),
+            code: error.code(),
+        }
+    }
+}
+
+impl std::fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.component, self.message)
+    }
+}
+
 /// Complete system hardware information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HardwareInfo {
+    /// Serialized format version (see [`SCHEMA_VERSION`])
+    #[serde(default = "current_schema_version")]
+    pub schema_version: u32,
     /// Timestamp when the hardware information was collected
     pub timestamp: u64,
     /// CPU information
@@ -28,6 +134,12 @@ pub struct HardwareInfo {
     pub memory: MemoryInfo,
     /// Storage devices
     pub storage_devices: Vec<StorageInfo>,
+    /// Physical disks, independent of partitions and mounts
+    #[serde(default)]
+    pub physical_disks: Vec<PhysicalDisk>,
+    /// Mounted filesystems
+    #[serde(default)]
+    pub volumes: Vec<Volume>,
     /// Network interfaces
     pub network_interfaces: Vec<NetworkInfo>,
     /// Battery information (if available)
@@ -42,36 +154,197 @@ pub struct HardwareInfo {
     pub power_profile: Option<PowerProfile>,
     /// Virtualization environment information
     pub virtualization: VirtualizationInfo,
+    /// System vendor, motherboard, firmware and chassis
+    #[serde(default)]
+    pub system: SystemInfo,
+    /// Required components that could not be detected
+    #[serde(default)]
+    pub errors: Vec<ComponentError>,
+    /// Optional components (battery, power profile) that could not be read
+    #[serde(default)]
+    pub warnings: Vec<ComponentError>,
 }
 
 impl HardwareInfo {
     /// Query all available hardware information
+    ///
+    /// A component that fails to query does not fail the whole call: its field
+    /// holds a placeholder and the failure is listed in [`errors`](Self::errors)
+    /// (or [`warnings`](Self::warnings) for optional components).
     pub fn query() -> Result<Self> {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| HardwareQueryError::unknown(format!("Failed to get timestamp: {e}")))?
-            .as_secs();
+        Self::query_with_options(&QueryOptions::new())
+    }
+
+    /// Query hardware information with time limits
+    ///
+    /// Component groups are queried concurrently. Any group that fails or
+    /// exceeds its timeout is listed in [`errors`](Self::errors) and reported
+    /// with placeholder values, so the rest of the result can still be used.
+    ///
+    /// With the `sim` feature, setting `HARDWARE_QUERY_SIM` to a
+    /// [`MachineProfile`](crate::MachineProfile) name or snapshot file replays
+    /// that fixture instead of probing the machine.
+    pub fn query_with_options(options: &QueryOptions) -> Result<Self> {
+        #[cfg(feature = "sim")]
+        if let Some(provider) = crate::sim::provider_from_env()? {
+            return Self::query_with_provider_and_options(provider, options);
+        }
+        // Browsers have no file system, processes or clock for the native probers
+        if cfg!(target_arch = "wasm32") {
+            return Err(crate::HardwareQueryError::platform_not_supported(
+                "Native hardware probing is unavailable on wasm32; use HardwareInfo::query_browser (`wasm` feature)",
+            ));
+        }
+        Self::query_with_provider_and_options(SystemProvider, options)
+    }
+
+    /// Query hardware information from a custom [`HardwareProvider`]
+    ///
+    /// Use [`FixtureProvider`](crate::FixtureProvider) to replay a recorded
+    /// snapshot in tests.
+    pub fn query_with_provider(provider: impl HardwareProvider + 'static) -> Result<Self> {
+        Self::query_with_provider_and_options(provider, &QueryOptions::new())
+    }
+
+    /// Query hardware information from a custom [`HardwareProvider`] with time limits
+    pub fn query_with_provider_and_options(
+        provider: impl HardwareProvider + 'static,
+        options: &QueryOptions,
+    ) -> Result<Self> {
+        let started = options::query_started();
+        let _restriction = options.restrict();
+        let provider = Arc::new(provider);
+        let timestamp = provider.timestamp()?;
+
+        let cpu = spawn(&provider, HardwareComponent::CPU, |p| p.cpu());
+        let gpus = spawn(&provider, HardwareComponent::GPU, |p| p.gpus());
+        let accelerators = spawn(&provider, HardwareComponent::Accelerators, |p| {
+            Ok((p.npus()?, p.tpus()?, p.arm_hardware()?, p.fpgas()?))
+        });
+        let memory = spawn(&provider, HardwareComponent::Memory, |p| p.memory());
+        let storage = spawn(&provider, HardwareComponent::Storage, |p| p.storage());
+        let network = spawn(&provider, HardwareComponent::Network, |p| p.network_interfaces());
+        let battery = spawn(&provider, HardwareComponent::Battery, |p| p.battery());
+        let thermal = spawn(&provider, HardwareComponent::Thermal, |p| p.thermal());
+        let devices = spawn(&provider, HardwareComponent::Devices, |p| {
+            Ok((p.pci_devices()?, p.usb_devices()?))
+        });
+        let power = spawn(&provider, HardwareComponent::Power, |p| p.power_profile());
+        let virtualization = spawn(&provider, HardwareComponent::Virtualization, |p| p.virtualization());
+        let system = spawn(&provider, HardwareComponent::System, |p| p.system());
+
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let (npus, tpus, arm_hardware, fpgas) =
+            accelerators.finish(options, started, &mut errors, Default::default);
+        let (pci_devices, usb_devices) = devices.finish(options, started, &mut errors, Default::default);
+        let storage = storage.finish(options, started, &mut errors, StorageLayout::default);
 
         Ok(Self {
+            schema_version: SCHEMA_VERSION,
             timestamp,
-            cpu: CPUInfo::query()?,
-            gpus: GPUInfo::query_all()?,
-            npus: NPUInfo::query_all()?,
-            tpus: TPUInfo::query_all()?,
-            arm_hardware: ARMHardwareInfo::detect().ok().flatten(),
-            fpgas: FPGAInfo::detect_fpgas().unwrap_or_default(),
-            memory: MemoryInfo::query()?,
-            storage_devices: StorageInfo::query_all()?,
-            network_interfaces: NetworkInfo::query_all()?,
-            battery: BatteryInfo::query().ok(),
-            thermal: ThermalInfo::query()?,
-            pci_devices: PCIDevice::query_all()?,
-            usb_devices: USBDevice::query_all()?,
-            power_profile: PowerProfile::query().ok(),
-            virtualization: VirtualizationInfo::detect()?,
+            cpu: cpu.finish(options, started, &mut errors, CPUInfo::unknown),
+            gpus: gpus.finish(options, started, &mut errors, Vec::new),
+            npus,
+            tpus,
+            arm_hardware,
+            fpgas,
+            memory: memory.finish(options, started, &mut errors, MemoryInfo::unknown),
+            storage_devices: storage.devices,
+            physical_disks: storage.disks,
+            volumes: storage.volumes,
+            network_interfaces: network.finish(options, started, &mut errors, Vec::new),
+            battery: battery.finish(options, started, &mut warnings, || None),
+            thermal: thermal.finish(options, started, &mut errors, ThermalInfo::unknown),
+            pci_devices,
+            usb_devices,
+            power_profile: power.finish(options, started, &mut warnings, || None),
+            virtualization: virtualization.finish(
+                options,
+                started,
+                &mut errors,
+                VirtualizationInfo::unknown,
+            ),
+            system: system.finish(options, started, &mut errors, SystemInfo::default),
+            errors,
+            warnings,
         })
     }
 
+    /// Query hardware information through a process-wide cache
+    ///
+    /// Static components (CPU model, GPU inventory, PCI devices, etc.) are reused
+    /// for up to `ttl`, while dynamic readings such as memory usage and temperatures
+    /// are refreshed on every call. Use [`QueryCache`](crate::QueryCache) directly for
+    /// per-component TTL control.
+    pub fn query_cached(ttl: Duration) -> Result<Self> {
+        let config = CacheConfig::default()
+            .with_static_ttl(ttl)
+            .with_dynamic_ttl(Duration::ZERO);
+        QueryCache::global().query_with(&config)
+    }
+
+    /// Re-read only the given live readings, keeping everything else
+    ///
+    /// Much cheaper than [`query`](Self::query): CPU usage comes from a
+    /// process-wide sampler (usage since the previous sample) and memory
+    /// usage from the kernel's counters, so this is suitable for one-second
+    /// polling loops. Readings that fail keep their previous values; the
+    /// first error is returned after all requested readings were attempted.
+    ///
+    /// ```rust
+    /// use hardware_query::{DynamicComponent, HardwareInfo};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut hw_info = HardwareInfo::query()?;
+    /// hw_info.refresh(&[DynamicComponent::CpuUsage, DynamicComponent::Memory])?;
+    /// println!("memory {:.0}%", hw_info.memory().usage_percent());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn refresh(&mut self, components: &[DynamicComponent]) -> Result<()> {
+        let mut first_error = None;
+        let mut record = |result: Result<()>| {
+            if let Err(error) = result {
+                first_error.get_or_insert(error);
+            }
+        };
+
+        for component in components {
+            match component {
+                DynamicComponent::CpuUsage => self.cpu.core_usage = CPUInfo::sample_core_usage(),
+                DynamicComponent::Memory => self.memory.sample_usage(),
+                DynamicComponent::Thermal => record(ThermalInfo::query().map(|thermal| self.thermal = thermal)),
+                DynamicComponent::Battery => {
+                    // A missing battery is not an error; it was unplugged or never present
+                    self.battery = BatteryInfo::query().ok();
+                }
+                DynamicComponent::Power => record(PowerProfile::query().map(|power| self.power_profile = Some(power))),
+            }
+        }
+
+        self.timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(self.timestamp);
+
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Query all available hardware information without blocking the async executor
+    ///
+    /// Detection runs on tokio's blocking thread pool, so WMI queries, sysfs reads
+    /// and external commands don't stall other tasks.
+    #[cfg(feature = "async")]
+    pub async fn query_async() -> Result<Self> {
+        tokio::task::spawn_blocking(Self::query)
+            .await
+            .map_err(|e| HardwareQueryError::unknown(format!("Hardware query task failed: {e}")))?
+    }
+
     /// Get CPU information
     pub fn cpu(&self) -> &CPUInfo {
         &self.cpu
@@ -82,6 +355,34 @@ impl HardwareInfo {
         &self.gpus
     }
 
+    /// Pick the GPU that best matches `criteria`
+    ///
+    /// Returns `None` when no GPU meets the criteria's requirements.
+    pub fn select_gpu(&self, criteria: &crate::GpuCriteria) -> Option<crate::GpuSelection<'_>> {
+        criteria.rank(&self.gpus).into_iter().next()
+    }
+
+    /// GPUs matching `criteria`, best first
+    pub fn rank_gpus(&self, criteria: &crate::GpuCriteria) -> Vec<crate::GpuSelection<'_>> {
+        criteria.rank(&self.gpus)
+    }
+
+    /// Query how the detected GPUs are linked to the host and to each other
+    ///
+    /// Reads PCIe link state, NUMA placement and NVLink/xGMI peers for the GPUs
+    /// in this snapshot.
+    pub fn gpu_topology(&self) -> GPUTopology {
+        GPUTopology::query(&self.gpus)
+    }
+
+    /// Query where the detected PCI devices are attached
+    ///
+    /// Reads NUMA placement, IOMMU groups, PCIe link state and interrupt
+    /// routing for the PCI devices in this snapshot.
+    pub fn device_topology(&self) -> DeviceTopology {
+        DeviceTopology::query(&self.pci_devices)
+    }
+
     /// Get NPU information
     pub fn npus(&self) -> &[NPUInfo] {
         &self.npus
@@ -112,6 +413,30 @@ impl HardwareInfo {
         &self.storage_devices
     }
 
+    /// Get physical disks
+    pub fn physical_disks(&self) -> &[PhysicalDisk] {
+        &self.physical_disks
+    }
+
+    /// Get mounted volumes
+    pub fn volumes(&self) -> &[Volume] {
+        &self.volumes
+    }
+
+    /// Get the volumes on a physical disk
+    pub fn volumes_on(&self, disk: &PhysicalDisk) -> Vec<&Volume> {
+        self.volumes
+            .iter()
+            .filter(|volume| volume.disk.as_deref() == Some(disk.device.as_str()))
+            .collect()
+    }
+
+    /// Get the physical disk holding a volume
+    pub fn disk_for(&self, volume: &Volume) -> Option<&PhysicalDisk> {
+        let device = volume.disk.as_deref()?;
+        self.physical_disks.iter().find(|disk| disk.device == device)
+    }
+
     /// Get network interfaces
     pub fn network_interfaces(&self) -> &[NetworkInfo] {
         &self.network_interfaces
@@ -132,21 +457,73 @@ impl HardwareInfo {
         &self.pci_devices
     }
 
+    /// Get IOMMU groups of the PCI devices, for planning VFIO passthrough
+    pub fn iommu_groups(&self) -> Vec<IOMMUGroup> {
+        IOMMUGroup::from_devices(&self.pci_devices)
+    }
+
     /// Get USB devices
     pub fn usb_devices(&self) -> &[USBDevice] {
         &self.usb_devices
     }
 
+    /// Get the USB hub tree of the devices in this snapshot
+    pub fn usb_topology(&self) -> USBTopology {
+        USBTopology::from_devices(self.usb_devices.clone())
+    }
+
     /// Get power profile information (if available)
     pub fn power_profile(&self) -> Option<&PowerProfile> {
         self.power_profile.as_ref()
     }
 
+    /// Get system vendor, motherboard and firmware information
+    pub fn system(&self) -> &SystemInfo {
+        &self.system
+    }
+
     /// Get virtualization information
     pub fn virtualization(&self) -> &VirtualizationInfo {
         &self.virtualization
     }
 
+    /// Required components that could not be detected
+    ///
+    /// Fields for these components hold placeholder values.
+    pub fn errors(&self) -> &[ComponentError] {
+        &self.errors
+    }
+
+    /// Optional components (battery, power profile) that are present but could not be read
+    pub fn warnings(&self) -> &[ComponentError] {
+        &self.warnings
+    }
+
+    /// Components that were skipped because they exceeded a query timeout
+    pub fn timed_out_components(&self) -> Vec<HardwareComponent> {
+        self.errors
+            .iter()
+            .chain(&self.warnings)
+            .filter(|error| error.kind == ComponentErrorKind::TimedOut)
+            .map(|error| error.component)
+            .collect()
+    }
+
+    /// Components the platform cannot detect at all
+    pub fn unsupported_components(&self) -> Vec<HardwareComponent> {
+        self.errors
+            .iter()
+            .chain(&self.warnings)
+            .filter(|error| error.kind == ComponentErrorKind::Unsupported)
+            .map(|error| error.component)
+            .collect()
+    }
+
+    /// Whether every required component was detected
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
     /// Check if system is ARM-based
     pub fn is_arm_system(&self) -> bool {
         self.arm_hardware.is_some()
@@ -194,14 +571,97 @@ impl HardwareInfo {
         summary
     }
 
-    /// Export hardware information as JSON
+    /// Copy with serial numbers, UUIDs, MAC and IP addresses, WiFi network
+    /// names and user names in mount paths masked
+    ///
+    /// Use this before attaching hardware information to a bug report.
+    pub fn redacted(&self) -> Self {
+        self.redacted_with(&crate::RedactionOptions::default())
+    }
+
+    /// Copy with identifying values masked or hashed according to `options`
+    pub fn redacted_with(&self, options: &crate::RedactionOptions) -> Self {
+        crate::redact::redact(self, options)
+    }
+
+    /// Export hardware information as indented JSON (same as [`to_json_pretty`](Self::to_json_pretty))
     pub fn to_json(&self) -> Result<String> {
+        self.to_json_pretty()
+    }
+
+    /// Export hardware information as indented, human-readable JSON
+    pub fn to_json_pretty(&self) -> Result<String> {
         serde_json::to_string_pretty(self).map_err(Into::into)
     }
 
+    /// Export hardware information as compact JSON, without indentation
+    pub fn to_json_compact(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(Into::into)
+    }
+
     /// Import hardware information from JSON
+    ///
+    /// Snapshots written by a newer, incompatible schema version are rejected.
     pub fn from_json(json: &str) -> Result<Self> {
-        serde_json::from_str(json).map_err(Into::into)
+        let info: Self = serde_json::from_str(json)?;
+        info.check_schema_version()
+    }
+
+    /// Export hardware information as YAML
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml(&self) -> Result<String> {
+        serde_yaml::to_string(self).map_err(Into::into)
+    }
+
+    /// Import hardware information from YAML
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        let info: Self = serde_yaml::from_str(yaml)?;
+        info.check_schema_version()
+    }
+
+    /// Export hardware information as MessagePack (field names are preserved)
+    #[cfg(feature = "msgpack")]
+    pub fn to_msgpack(&self) -> Result<Vec<u8>> {
+        rmp_serde::to_vec_named(self)
+            .map_err(|e| HardwareQueryError::MessagePackError(e.to_string()))
+    }
+
+    /// Import hardware information from MessagePack
+    #[cfg(feature = "msgpack")]
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Self> {
+        let info: Self = rmp_serde::from_slice(bytes)
+            .map_err(|e| HardwareQueryError::MessagePackError(e.to_string()))?;
+        info.check_schema_version()
+    }
+
+    fn check_schema_version(self) -> Result<Self> {
+        if self.schema_version > SCHEMA_VERSION {
+            return Err(HardwareQueryError::UnsupportedSchemaVersion {
+                found: self.schema_version,
+                supported: SCHEMA_VERSION,
+            });
+        }
+        Ok(self)
+    }
+
+    /// Build a human-readable report at the given detail level
+    ///
+    /// Add assessment sections with
+    /// [`HardwareReport::with_section`](crate::HardwareReport::with_section).
+    pub fn report(&self, detail: crate::ReportDetail) -> crate::HardwareReport {
+        crate::HardwareReport::new(self, detail)
+    }
+
+    /// Render a Markdown report (tables for CPU, GPU, memory and storage,
+    /// health summary and warnings)
+    pub fn to_markdown(&self, detail: crate::ReportDetail) -> String {
+        self.report(detail).to_markdown()
+    }
+
+    /// Render a standalone HTML report
+    pub fn to_html(&self, detail: crate::ReportDetail) -> String {
+        self.report(detail).to_html()
     }
 
     /// Get a summary of the most important hardware information
@@ -226,6 +686,23 @@ impl HardwareInfo {
                 .sum(),
         }
     }
+
+    /// Compare this snapshot with a newer one
+    ///
+    /// Reports added and removed devices and changed configuration values
+    /// (memory size, driver, firmware and BIOS versions). Live readings such
+    /// as usage and temperatures are not compared.
+    pub fn diff(&self, newer: &HardwareInfo) -> crate::HardwareDiff {
+        crate::HardwareDiff::between(self, newer)
+    }
+
+    /// Stable machine ID hashed from the identifiers selected in `options`
+    ///
+    /// The ID survives reboots and driver updates but changes when the CPU,
+    /// motherboard, network adapters or disks are replaced.
+    pub fn fingerprint(&self, options: &crate::FingerprintOptions) -> Result<crate::Fingerprint> {
+        crate::Fingerprint::compute(self, options)
+    }
 }
 
 /// Summary of key hardware specifications
@@ -255,3 +732,13 @@ impl std::fmt::Display for HardwareSummary {
         Ok(())
     }
 }
+
+/// Run one provider method on its own thread
+fn spawn<P: HardwareProvider + 'static, T: Send + 'static>(
+    provider: &Arc<P>,
+    component: HardwareComponent,
+    query: impl FnOnce(&P) -> Result<T> + Send + 'static,
+) -> Probe<T> {
+    let provider = Arc::clone(provider);
+    Probe::spawn(component, move || query(&provider))
+}
@@ -0,0 +1,353 @@
+//! Sustained thermal and power headroom
+//!
+//! [`ThermalInfo::calculate_sustained_performance`](crate::ThermalInfo::calculate_sustained_performance)
+//! maps the hottest sensor onto fixed bands. [`ThermalHeadroom`] works from
+//! the actual limits instead: the CPU's long-term power limit (RAPL PL1 on
+//! Linux), each GPU's enforced power limit, the measured power draw, and a
+//! thermal resistance estimated from how far the current draw lifts each
+//! component above ambient. The lower of the power and thermal budgets is
+//! what the component can sustain without throttling; on a laptop that is
+//! also the duty cycle at which it can run flat out.
+//!
+//! ```no_run
+//! use hardware_query::ThermalHeadroom;
+//!
+//! # fn main() -> hardware_query::Result<()> {
+//! let headroom = ThermalHeadroom::query()?;
+//! println!(
+//!     "CPU can sustain {:.0}% load ({:?} W to spare, limited by {})",
+//!     headroom.cpu.sustainable_load_percent, headroom.cpu.headroom_watts, headroom.cpu.limited_by
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{GPUUsage, Result, ThermalInfo};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// CPU temperature at which throttling is assumed when no sensor reports a critical threshold
+const DEFAULT_CPU_THROTTLE_TEMPERATURE: f32 = 95.0;
+/// GPU temperature at which throttling is assumed (NVIDIA's default slowdown target region)
+const DEFAULT_GPU_THROTTLE_TEMPERATURE: f32 = 87.0;
+/// Ambient temperature assumed when no ambient sensor exists
+const DEFAULT_AMBIENT_TEMPERATURE: f32 = 25.0;
+/// Degrees below a sensor's critical threshold where firmware starts throttling
+const CRITICAL_MARGIN: f32 = 5.0;
+/// How long [`ThermalHeadroom::query`] measures power draw and load
+const MEASUREMENT_WINDOW: Duration = Duration::from_millis(500);
+
+/// Power limits the CPU enforces
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CPUPowerLimits {
+    /// Long-term package power limit (PL1) in watts, summed over sockets
+    pub sustained_watts: Option<f32>,
+    /// Short-term boost power limit (PL2) in watts, summed over sockets
+    pub burst_watts: Option<f32>,
+    /// How long the CPU may stay above the sustained limit (tau)
+    pub burst_window: Option<Duration>,
+}
+
+impl CPUPowerLimits {
+    /// Read the CPU power limits
+    ///
+    /// Linux reads the RAPL package constraints from `/sys/class/powercap`,
+    /// which are world-readable. Other platforms don't expose them without
+    /// a kernel driver and return empty limits.
+    pub fn query() -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            linux::rapl_limits()
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Self::default()
+        }
+    }
+
+    /// Check if no limit is known
+    pub fn is_empty(&self) -> bool {
+        self.sustained_watts.is_none() && self.burst_watts.is_none()
+    }
+}
+
+/// What bounds a component's sustained load
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HeadroomLimit {
+    /// The power limit is reached before the throttle temperature
+    Power,
+    /// The cooling reaches the throttle temperature before the power limit
+    Thermal,
+    /// Not enough readings; the estimate comes from the temperature bands
+    Unknown,
+}
+
+impl std::fmt::Display for HeadroomLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeadroomLimit::Power => write!(f, "power"),
+            HeadroomLimit::Thermal => write!(f, "thermal"),
+            HeadroomLimit::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Current readings of one component, as input to [`ComponentHeadroom::assess`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComponentLoad {
+    /// Current temperature in Celsius
+    pub temperature: Option<f32>,
+    /// Temperature at which the component throttles
+    pub throttle_temperature: f32,
+    /// Ambient (intake) temperature in Celsius
+    pub ambient_temperature: f32,
+    /// Sustained power limit in watts
+    pub power_limit_watts: Option<f32>,
+    /// Power at full load in watts, used when `load_percent` is unknown
+    pub full_load_watts: Option<f32>,
+    /// Current power draw in watts
+    pub power_draw_watts: Option<f32>,
+    /// Current utilization in percent
+    pub load_percent: Option<f32>,
+}
+
+/// Sustainable load of one component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentHeadroom {
+    /// `CPU`, or the GPU's PCI address
+    pub device: String,
+    /// Marketing name when known
+    pub name: Option<String>,
+    /// Degrees left before the throttle temperature
+    pub thermal_margin: Option<f32>,
+    /// Highest power the cooling can hold below the throttle temperature, in watts
+    pub thermal_limit_watts: Option<f32>,
+    /// Highest power that can be sustained: the lower of the power and thermal limits
+    pub sustained_watts: Option<f32>,
+    /// Sustained watts minus current draw; negative while running on boost budget
+    pub headroom_watts: Option<f32>,
+    /// Load in percent of full load that can be held indefinitely without
+    /// throttling; for bursty work, the share of time it can run flat out
+    pub sustainable_load_percent: f64,
+    /// What bounds the sustainable load
+    pub limited_by: HeadroomLimit,
+}
+
+impl ComponentHeadroom {
+    /// Work out the sustainable load from one component's readings
+    ///
+    /// Temperature is modelled as ambient plus a thermal resistance times the
+    /// power draw, with the resistance taken from the current reading. Full
+    /// load power is extrapolated from the current draw and utilization,
+    /// falling back to `full_load_watts`. Without enough readings, `fallback`
+    /// (a 0.0-1.0 performance factor) becomes the sustainable load.
+    ///
+    /// ```rust
+    /// use hardware_query::{ComponentHeadroom, ComponentLoad, HeadroomLimit};
+    ///
+    /// let load = ComponentLoad {
+    ///     temperature: Some(85.0),
+    ///     throttle_temperature: 95.0,
+    ///     ambient_temperature: 25.0,
+    ///     power_limit_watts: Some(28.0),
+    ///     full_load_watts: None,
+    ///     power_draw_watts: Some(24.0),
+    ///     load_percent: Some(50.0),
+    /// };
+    /// let headroom = ComponentHeadroom::assess("CPU", None, &load, 1.0);
+    /// assert_eq!(headroom.limited_by, HeadroomLimit::Power);
+    /// assert_eq!(headroom.headroom_watts, Some(4.0));
+    /// assert!((headroom.sustainable_load_percent - 58.3).abs() < 0.1);
+    /// ```
+    pub fn assess(device: &str, name: Option<String>, load: &ComponentLoad, fallback: f64) -> Self {
+        let thermal_margin = load.temperature.map(|temperature| load.throttle_temperature - temperature);
+        let thermal_limit_watts = match (load.temperature, load.power_draw_watts) {
+            // Near ambient or near idle the resistance estimate is noise
+            (Some(temperature), Some(draw)) if temperature > load.ambient_temperature + 5.0 && draw > 1.0 => {
+                let resistance = (temperature - load.ambient_temperature) / draw;
+                Some(((load.throttle_temperature - load.ambient_temperature) / resistance).max(0.0))
+            }
+            _ => None,
+        };
+
+        let (sustained_watts, limited_by) = match (load.power_limit_watts, thermal_limit_watts) {
+            (Some(power), Some(thermal)) if thermal < power => (Some(thermal), HeadroomLimit::Thermal),
+            (Some(power), _) => (Some(power), HeadroomLimit::Power),
+            (None, Some(thermal)) => (Some(thermal), HeadroomLimit::Thermal),
+            (None, None) => (None, HeadroomLimit::Unknown),
+        };
+        let headroom_watts = sustained_watts.zip(load.power_draw_watts).map(|(limit, draw)| limit - draw);
+
+        let full_load_watts = match (load.power_draw_watts, load.load_percent) {
+            (Some(draw), Some(percent)) if percent >= 5.0 => Some(draw * 100.0 / percent),
+            _ => load.full_load_watts,
+        };
+        let (sustainable_load_percent, limited_by) = match (sustained_watts, full_load_watts) {
+            (Some(limit), Some(full)) if full > 0.0 => ((limit / full * 100.0).min(100.0) as f64, limited_by),
+            _ => ((fallback * 100.0).clamp(0.0, 100.0), HeadroomLimit::Unknown),
+        };
+
+        Self {
+            device: device.to_string(),
+            name,
+            thermal_margin,
+            thermal_limit_watts,
+            sustained_watts,
+            headroom_watts,
+            sustainable_load_percent,
+            limited_by,
+        }
+    }
+}
+
+/// Sustainable CPU and GPU load of the machine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThermalHeadroom {
+    /// CPU package
+    pub cpu: ComponentHeadroom,
+    /// Each GPU with a usage backend (see [`GPUUsage`])
+    pub gpus: Vec<ComponentHeadroom>,
+}
+
+impl ThermalHeadroom {
+    /// Measure power draw and load for half a second and assess the headroom
+    ///
+    /// CPU power comes from the energy counters ([`EnergyMeter`](crate::EnergyMeter)),
+    /// which may need root; without them the CPU falls back to its
+    /// temperature and power limit.
+    pub fn query() -> Result<Self> {
+        let thermal = ThermalInfo::query()?;
+        let limits = CPUPowerLimits::query();
+
+        // Prime the usage sampler so the second reading covers the same window
+        crate::CPUInfo::sample_core_usage();
+        let cpu_power = crate::EnergyMeter::new()
+            .and_then(|meter| meter.measure(MEASUREMENT_WINDOW))
+            .ok()
+            .map(|reading| reading.watts(crate::EnergyDomain::Package) as f32)
+            .filter(|watts| *watts > 0.0);
+        if cpu_power.is_none() {
+            std::thread::sleep(MEASUREMENT_WINDOW);
+        }
+        let usage = crate::CPUInfo::sample_core_usage();
+        let cpu_load = (!usage.is_empty()).then(|| usage.iter().sum::<f32>() / usage.len() as f32);
+
+        Ok(Self::assess(&thermal, &limits, cpu_power, cpu_load, &GPUUsage::query_all()))
+    }
+
+    /// Assess the headroom from readings taken elsewhere, e.g. by a monitor
+    pub fn assess(
+        thermal: &ThermalInfo,
+        limits: &CPUPowerLimits,
+        cpu_power_watts: Option<f32>,
+        cpu_load_percent: Option<f32>,
+        gpus: &[GPUUsage],
+    ) -> Self {
+        let fallback = thermal.calculate_sustained_performance();
+        let ambient_temperature = thermal.ambient_temperature.unwrap_or(DEFAULT_AMBIENT_TEMPERATURE);
+        let throttle_temperature = |sensor_type: &str, default: f32| {
+            thermal
+                .sensors()
+                .iter()
+                .filter(|sensor| sensor.sensor_type == sensor_type)
+                .filter_map(|sensor| sensor.critical_temperature)
+                .reduce(f32::min)
+                .map(|critical| critical - CRITICAL_MARGIN)
+                .unwrap_or(default)
+        };
+
+        let cpu = ComponentLoad {
+            temperature: thermal.cpu_temperature(),
+            throttle_temperature: throttle_temperature("CPU", DEFAULT_CPU_THROTTLE_TEMPERATURE),
+            ambient_temperature,
+            power_limit_watts: limits
+                .sustained_watts
+                .or_else(|| thermal.tdp_info.as_ref().and_then(|tdp| tdp.cpu_tdp)),
+            // All-core load runs at the boost limit until tau expires
+            full_load_watts: limits.burst_watts,
+            power_draw_watts: cpu_power_watts,
+            load_percent: cpu_load_percent,
+        };
+        let gpu_throttle_temperature = throttle_temperature("GPU", DEFAULT_GPU_THROTTLE_TEMPERATURE);
+
+        Self {
+            cpu: ComponentHeadroom::assess("CPU", None, &cpu, fallback),
+            gpus: gpus
+                .iter()
+                .map(|gpu| {
+                    let load = ComponentLoad {
+                        temperature: gpu.temperature,
+                        throttle_temperature: gpu_throttle_temperature,
+                        ambient_temperature,
+                        power_limit_watts: gpu.power_limit_watts,
+                        full_load_watts: gpu.power_limit_watts,
+                        power_draw_watts: gpu.power_draw_watts,
+                        load_percent: gpu.utilization_percent,
+                    };
+                    ComponentHeadroom::assess(&gpu.device, gpu.name.clone(), &load, fallback)
+                })
+                .collect(),
+        }
+    }
+
+    /// The component with the least sustainable load
+    pub fn bottleneck(&self) -> &ComponentHeadroom {
+        self.gpus
+            .iter()
+            .chain(std::iter::once(&self.cpu))
+            .min_by(|a, b| a.sustainable_load_percent.total_cmp(&b.sustainable_load_percent))
+            .unwrap_or(&self.cpu)
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::CPUPowerLimits;
+    use std::fs;
+    use std::path::Path;
+    use std::time::Duration;
+
+    /// Sum the `long_term` and `short_term` constraints of each RAPL package zone
+    pub(super) fn rapl_limits() -> CPUPowerLimits {
+        let mut limits = CPUPowerLimits::default();
+        let Ok(entries) = fs::read_dir("/sys/class/powercap") else {
+            return limits;
+        };
+        for zone in entries.flatten().map(|entry| entry.path()) {
+            // Package zones are intel-rapl:<n>; subzones add a second colon
+            let is_package = zone
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix("intel-rapl:"))
+                .is_some_and(|rest| !rest.contains(':'));
+            if !is_package || read(&zone.join("enabled")).as_deref() == Some("0") {
+                continue;
+            }
+            for constraint in 0..3 {
+                let name = read(&zone.join(format!("constraint_{constraint}_name")));
+                let Some(watts) = read(&zone.join(format!("constraint_{constraint}_power_limit_uw")))
+                    .and_then(|uw| uw.parse::<u64>().ok())
+                    .filter(|uw| *uw > 0)
+                    .map(|uw| uw as f32 / 1_000_000.0)
+                else {
+                    continue;
+                };
+                match name.as_deref() {
+                    Some("long_term") => {
+                        *limits.sustained_watts.get_or_insert(0.0) += watts;
+                        limits.burst_window = read(&zone.join(format!("constraint_{constraint}_time_window_us")))
+                            .and_then(|us| us.parse().ok())
+                            .map(Duration::from_micros);
+                    }
+                    Some("short_term") => *limits.burst_watts.get_or_insert(0.0) += watts,
+                    _ => {}
+                }
+            }
+        }
+        limits
+    }
+
+    fn read(path: &Path) -> Option<String> {
+        Some(fs::read_to_string(path).ok()?.trim().to_string())
+    }
+}
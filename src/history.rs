@@ -0,0 +1,340 @@
+//! Metric history kept by [`HardwareMonitor`](crate::HardwareMonitor)
+//!
+//! Each monitoring update appends one sample per metric to a fixed-length ring
+//! buffer, so callers can look at recent trends (is the GPU still heating up?
+//! how busy was the CPU over the last ten minutes?) without storing every
+//! `MetricsUpdate` event themselves.
+
+#[cfg(feature = "monitoring")]
+use crate::{HardwareInfo, PowerProfile, ThermalInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, SystemTime};
+
+/// A metric recorded in the history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Metric {
+    /// Hottest CPU sensor in Celsius
+    CpuTemperature,
+    /// Hottest GPU sensor in Celsius
+    GpuTemperature,
+    /// Average CPU usage across cores in percent
+    CpuUsage,
+    /// Average effective CPU frequency across cores in MHz
+    CpuFrequency,
+    /// Highest GPU usage in percent
+    GpuUsage,
+    /// Highest VRAM usage across GPUs in percent
+    GpuMemoryUsage,
+    /// System memory usage in percent
+    MemoryUsage,
+    /// Total power draw in watts
+    PowerDraw,
+    /// Battery charge in percent
+    BatteryLevel,
+    /// Battery drain in percentage points per hour while discharging
+    BatteryDrainRate,
+    /// Read plus write throughput summed over all disks in MB/s
+    DiskThroughput,
+    /// Busiest disk's utilization in percent
+    DiskUtilization,
+    /// Received traffic summed over non-loopback interfaces in Mbit/s
+    NetworkReceive,
+    /// Transmitted traffic summed over non-loopback interfaces in Mbit/s
+    NetworkTransmit,
+}
+
+impl std::fmt::Display for Metric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Metric::CpuTemperature => write!(f, "CPU temperature"),
+            Metric::GpuTemperature => write!(f, "GPU temperature"),
+            Metric::CpuUsage => write!(f, "CPU usage"),
+            Metric::CpuFrequency => write!(f, "CPU frequency"),
+            Metric::GpuUsage => write!(f, "GPU usage"),
+            Metric::GpuMemoryUsage => write!(f, "GPU memory usage"),
+            Metric::MemoryUsage => write!(f, "Memory usage"),
+            Metric::PowerDraw => write!(f, "Power draw"),
+            Metric::BatteryLevel => write!(f, "Battery level"),
+            Metric::BatteryDrainRate => write!(f, "Battery drain rate"),
+            Metric::DiskThroughput => write!(f, "Disk throughput"),
+            Metric::DiskUtilization => write!(f, "Disk utilization"),
+            Metric::NetworkReceive => write!(f, "Network receive"),
+            Metric::NetworkTransmit => write!(f, "Network transmit"),
+        }
+    }
+}
+
+/// A single recorded value
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Sample {
+    /// When the value was recorded
+    pub timestamp: SystemTime,
+    /// Recorded value
+    pub value: f64,
+}
+
+/// Samples of one metric over a time window, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimeSeries {
+    /// Samples in chronological order
+    pub samples: Vec<Sample>,
+}
+
+impl TimeSeries {
+    /// Get the samples
+    pub fn samples(&self) -> &[Sample] {
+        &self.samples
+    }
+
+    /// Number of samples
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Whether the window holds no samples
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Most recent value
+    pub fn latest(&self) -> Option<f64> {
+        self.samples.last().map(|sample| sample.value)
+    }
+
+    /// Smallest value
+    pub fn min(&self) -> Option<f64> {
+        self.samples.iter().map(|sample| sample.value).reduce(f64::min)
+    }
+
+    /// Largest value
+    pub fn max(&self) -> Option<f64> {
+        self.samples.iter().map(|sample| sample.value).reduce(f64::max)
+    }
+
+    /// Mean value
+    pub fn avg(&self) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        Some(self.samples.iter().map(|sample| sample.value).sum::<f64>() / self.samples.len() as f64)
+    }
+
+    /// Least-squares trend in units per second (positive when rising)
+    ///
+    /// Needs at least two samples taken at different times.
+    pub fn slope(&self) -> Option<f64> {
+        let first = self.samples.first()?.timestamp;
+        let points: Vec<(f64, f64)> = self
+            .samples
+            .iter()
+            .map(|sample| {
+                let seconds = sample
+                    .timestamp
+                    .duration_since(first)
+                    .unwrap_or_default()
+                    .as_secs_f64();
+                (seconds, sample.value)
+            })
+            .collect();
+
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let covariance: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+        if variance > 0.0 {
+            Some(covariance / variance)
+        } else {
+            None
+        }
+    }
+}
+
+/// Fixed-length history of monitored metrics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryBuffer {
+    capacity: usize,
+    series: HashMap<Metric, VecDeque<Sample>>,
+}
+
+impl HistoryBuffer {
+    /// Create a buffer keeping up to `capacity` samples per metric
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            series: HashMap::new(),
+        }
+    }
+
+    /// Maximum samples kept per metric
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Append a sample, dropping the oldest one when the buffer is full
+    pub fn record(&mut self, metric: Metric, value: f64, timestamp: SystemTime) {
+        if self.capacity == 0 {
+            return;
+        }
+        let series = self.series.entry(metric).or_default();
+        if series.len() == self.capacity {
+            series.pop_front();
+        }
+        series.push_back(Sample { timestamp, value });
+    }
+
+    /// Samples of `metric` recorded within the last `window`
+    pub fn series(&self, metric: Metric, window: Duration) -> TimeSeries {
+        let since = SystemTime::now().checked_sub(window).unwrap_or(SystemTime::UNIX_EPOCH);
+        let samples = self
+            .series
+            .get(&metric)
+            .map(|series| {
+                series
+                    .iter()
+                    .filter(|sample| sample.timestamp >= since)
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default();
+        TimeSeries { samples }
+    }
+
+    /// Hottest CPU sensor over the last `window`
+    pub fn cpu_temperature(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::CpuTemperature, window)
+    }
+
+    /// Hottest GPU sensor over the last `window`
+    pub fn gpu_temperature(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::GpuTemperature, window)
+    }
+
+    /// Average CPU usage over the last `window`
+    pub fn cpu_usage(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::CpuUsage, window)
+    }
+
+    /// Average CPU frequency over the last `window`
+    pub fn cpu_frequency(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::CpuFrequency, window)
+    }
+
+    /// Highest GPU usage over the last `window`
+    pub fn gpu_usage(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::GpuUsage, window)
+    }
+
+    /// Highest VRAM usage over the last `window`
+    pub fn gpu_memory_usage(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::GpuMemoryUsage, window)
+    }
+
+    /// Memory usage over the last `window`
+    pub fn memory_usage(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::MemoryUsage, window)
+    }
+
+    /// Total power draw over the last `window`
+    pub fn power_draw(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::PowerDraw, window)
+    }
+
+    /// Battery charge over the last `window`
+    pub fn battery_level(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::BatteryLevel, window)
+    }
+
+    /// Battery drain rate over the last `window`
+    pub fn battery_drain_rate(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::BatteryDrainRate, window)
+    }
+
+    /// Total disk throughput over the last `window`
+    pub fn disk_throughput(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::DiskThroughput, window)
+    }
+
+    /// Busiest disk's utilization over the last `window`
+    pub fn disk_utilization(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::DiskUtilization, window)
+    }
+
+    /// Received network traffic over the last `window`
+    pub fn network_receive(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::NetworkReceive, window)
+    }
+
+    /// Transmitted network traffic over the last `window`
+    pub fn network_transmit(&self, window: Duration) -> TimeSeries {
+        self.series(Metric::NetworkTransmit, window)
+    }
+
+    /// Remove all samples
+    pub fn clear(&mut self) {
+        self.series.clear();
+    }
+
+    /// Record every metric available from one monitoring update
+    #[cfg(feature = "monitoring")]
+    pub(crate) fn record_values(&mut self, values: &[(Metric, f64)], timestamp: SystemTime) {
+        for &(metric, value) in values {
+            self.record(metric, value, timestamp);
+        }
+    }
+}
+
+/// Extract every metric available from one monitoring update
+#[cfg(feature = "monitoring")]
+pub(crate) fn current_values(
+    hardware_info: Option<&HardwareInfo>,
+    thermal_info: Option<&ThermalInfo>,
+    power_profile: Option<&PowerProfile>,
+) -> Vec<(Metric, f64)> {
+    let mut values = Vec::new();
+
+    if let Some(thermal) = thermal_info {
+        let hottest = |kind: &str| {
+            thermal
+                .sensors()
+                .iter()
+                .filter(|sensor| sensor.sensor_type.eq_ignore_ascii_case(kind))
+                .map(|sensor| sensor.temperature as f64)
+                .reduce(f64::max)
+        };
+        if let Some(temperature) = hottest("CPU") {
+            values.push((Metric::CpuTemperature, temperature));
+        }
+        if let Some(temperature) = hottest("GPU") {
+            values.push((Metric::GpuTemperature, temperature));
+        }
+    }
+
+    if let Some(info) = hardware_info {
+        let usage = info.cpu().core_usage();
+        if !usage.is_empty() {
+            let average = usage.iter().map(|u| *u as f64).sum::<f64>() / usage.len() as f64;
+            values.push((Metric::CpuUsage, average));
+        }
+        if let Some(gpu_usage) = info
+            .gpus()
+            .iter()
+            .filter_map(|gpu| gpu.usage_percent())
+            .map(f64::from)
+            .reduce(f64::max)
+        {
+            values.push((Metric::GpuUsage, gpu_usage));
+        }
+        if info.memory().total_mb > 0 {
+            values.push((Metric::MemoryUsage, info.memory().usage_percent() as f64));
+        }
+    }
+
+    if let Some(power) = power_profile.and_then(|profile| profile.total_power_draw) {
+        values.push((Metric::PowerDraw, power as f64));
+    }
+
+    values
+}
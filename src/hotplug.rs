@@ -0,0 +1,578 @@
+//! Device hotplug detection
+//!
+//! Watches for USB, GPU and storage devices being connected or removed, and for
+//! cards and other removable media being inserted or ejected, and feeds
+//! the changes to [`HardwareMonitor`](crate::HardwareMonitor) as
+//! [`MonitoringEvent::HardwareChanged`](crate::MonitoringEvent) events.
+//!
+//! On Linux the watcher listens to kernel uevents over netlink, so changes are
+//! reported as soon as the kernel announces them. On Windows it subscribes to
+//! `Win32_DeviceChangeEvent`, WMI's relay of `WM_DEVICECHANGE`, and on macOS
+//! it registers IOKit matching and termination notifications; both re-scan the
+//! devices when notified. Where no notification source is available the
+//! watcher falls back to diffing device snapshots on the monitoring interval.
+//! Scans and waits run on a blocking thread, never on the async runtime.
+
+use crate::monitoring::HardwareChangeType;
+use crate::{GPUInfo, StorageInfo, USBDevice};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, RwLock};
+
+/// Class of device involved in a hotplug change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeviceClass {
+    USB,
+    GPU,
+    Storage,
+    /// SD cards, USB drives and other media that can be inserted or ejected
+    RemovableMedia,
+}
+
+impl std::fmt::Display for DeviceClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeviceClass::USB => write!(f, "USB"),
+            DeviceClass::GPU => write!(f, "GPU"),
+            DeviceClass::Storage => write!(f, "Storage"),
+            DeviceClass::RemovableMedia => write!(f, "Removable media"),
+        }
+    }
+}
+
+/// A single device connection change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceChange {
+    /// Connected or disconnected
+    pub change_type: HardwareChangeType,
+    /// Kind of device
+    pub device_class: DeviceClass,
+    /// Human-readable device description
+    pub description: String,
+}
+
+impl DeviceChange {
+    /// Describe the change for a monitoring event
+    pub fn summary(&self) -> String {
+        let action = match self.change_type {
+            HardwareChangeType::DeviceConnected => "connected",
+            HardwareChangeType::DeviceDisconnected => "disconnected",
+            _ => "changed",
+        };
+        format!("{} device {}: {}", self.device_class, action, self.description)
+    }
+
+    /// Parse a kernel uevent (`action@devpath\0KEY=value\0...`) into a device change
+    ///
+    /// Cards inserted into or ejected from a reader arrive as `change` events
+    /// on the reader's disk; `media_present` tells which of the two happened.
+    pub fn from_uevent(message: &[u8], media_present: impl Fn(&str) -> bool) -> Option<Self> {
+        let fields: HashMap<&str, &str> = message
+            .split(|b| *b == 0)
+            .filter_map(|field| std::str::from_utf8(field).ok())
+            .filter_map(|field| field.split_once('='))
+            .collect();
+
+        let devname = fields.get("DEVNAME").copied().unwrap_or_default();
+        let change_type = match *fields.get("ACTION")? {
+            "add" => HardwareChangeType::DeviceConnected,
+            "remove" => HardwareChangeType::DeviceDisconnected,
+            "change" if fields.get("DISK_MEDIA_CHANGE") == Some(&"1") => {
+                if media_present(devname) {
+                    HardwareChangeType::DeviceConnected
+                } else {
+                    HardwareChangeType::DeviceDisconnected
+                }
+            }
+            _ => return None,
+        };
+
+        // eMMC disks also show up as mmcblk, but only once, at boot
+        let removable_disk = fields.contains_key("DISK_MEDIA_CHANGE")
+            || devname.starts_with("mmcblk")
+            || fields.get("DEVPATH").is_some_and(|path| path.contains("/usb"));
+
+        let device_class = match (*fields.get("SUBSYSTEM")?, fields.get("DEVTYPE").copied()) {
+            ("usb", Some("usb_device")) => DeviceClass::USB,
+            ("block", Some("disk")) if removable_disk => DeviceClass::RemovableMedia,
+            ("block", Some("disk")) => DeviceClass::Storage,
+            ("drm", _) if fields.get("DEVNAME").is_some_and(|name| name.starts_with("dri/card")) => {
+                DeviceClass::GPU
+            }
+            _ => return None,
+        };
+
+        let description = fields
+            .get("DEVNAME")
+            .or_else(|| fields.get("PRODUCT"))
+            .or_else(|| fields.get("DEVPATH"))
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+
+        Some(Self {
+            change_type,
+            device_class,
+            description,
+        })
+    }
+
+}
+
+/// Point-in-time inventory of hotpluggable devices, keyed by a stable identifier
+///
+/// Platforms without device notifications diff two snapshots to find what
+/// was connected or removed in between.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceSnapshot {
+    devices: HashMap<String, (DeviceClass, String)>,
+}
+
+impl DeviceSnapshot {
+    /// Capture the current USB, GPU and storage inventory
+    pub fn capture() -> Self {
+        let mut devices = HashMap::new();
+
+        for usb in USBDevice::query_all().unwrap_or_default() {
+            devices.insert(
+                format!(
+                    "usb:{}:{}@{}-{}",
+                    usb.vendor_id, usb.product_id, usb.bus_number, usb.device_address
+                ),
+                (DeviceClass::USB, usb.product_name.clone()),
+            );
+        }
+
+        for gpu in GPUInfo::query_all().unwrap_or_default() {
+            let id = gpu.pci_bus_id.clone().unwrap_or_else(|| gpu.model_name.clone());
+            devices.insert(format!("gpu:{id}"), (DeviceClass::GPU, gpu.model_name.clone()));
+        }
+
+        for storage in StorageInfo::query_all().unwrap_or_default() {
+            let class = if storage.removable { DeviceClass::RemovableMedia } else { DeviceClass::Storage };
+            devices.insert(
+                format!("storage:{}:{}", storage.model, storage.mount_point),
+                (class, format!("{} ({})", storage.model, storage.mount_point)),
+            );
+        }
+
+        Self { devices }
+    }
+
+    /// Record a device under a stable identifier
+    pub fn insert(&mut self, id: impl Into<String>, class: DeviceClass, description: impl Into<String>) {
+        self.devices.insert(id.into(), (class, description.into()));
+    }
+
+    /// Number of devices in the snapshot
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    /// Check if the snapshot holds no devices
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    /// Changes needed to go from `self` to `newer`
+    pub fn diff(&self, newer: &DeviceSnapshot) -> Vec<DeviceChange> {
+        let mut changes = Vec::new();
+
+        for (key, (class, description)) in &newer.devices {
+            if !self.devices.contains_key(key) {
+                changes.push(DeviceChange {
+                    change_type: HardwareChangeType::DeviceConnected,
+                    device_class: class.clone(),
+                    description: description.clone(),
+                });
+            }
+        }
+
+        for (key, (class, description)) in &self.devices {
+            if !newer.devices.contains_key(key) {
+                changes.push(DeviceChange {
+                    change_type: HardwareChangeType::DeviceDisconnected,
+                    device_class: class.clone(),
+                    description: description.clone(),
+                });
+            }
+        }
+
+        changes
+    }
+}
+
+/// Start watching for device changes until `running` becomes false
+///
+/// Changes are delivered on `sender`.
+pub(crate) fn spawn_watcher(
+    sender: mpsc::UnboundedSender<DeviceChange>,
+    running: Arc<RwLock<bool>>,
+    poll_interval: Duration,
+) {
+    tokio::task::spawn_blocking(move || {
+        #[cfg(target_os = "linux")]
+        {
+            match linux::UeventSocket::open() {
+                Ok(socket) => return socket.listen(&sender, &running),
+                Err(_) => {
+                    // Netlink unavailable (e.g. restricted container); fall back to polling
+                }
+            }
+        }
+        #[cfg(target_os = "windows")]
+        {
+            use std::sync::mpsc::RecvTimeoutError;
+
+            if let Some(events) = windows::device_events() {
+                let wait = || match events.recv_timeout(Duration::from_secs(1)) {
+                    Ok(()) => Some(true),
+                    Err(RecvTimeoutError::Timeout) => Some(false),
+                    Err(RecvTimeoutError::Disconnected) => None,
+                };
+                return rescan_on_notification(wait, &sender, &running);
+            }
+        }
+        #[cfg(target_os = "macos")]
+        {
+            if let Some(notifications) = macos::DeviceNotifications::register() {
+                return rescan_on_notification(|| Some(notifications.wait()), &sender, &running);
+            }
+        }
+
+        poll(&sender, &running, poll_interval);
+    });
+}
+
+/// Pause after a notification so the device finishes enumerating before the re-scan
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Re-scan devices whenever the OS reports a change
+///
+/// `wait_for_change` blocks until a notification arrives (`Some(true)`) or a
+/// timeout expires (`Some(false)`), and returns `None` once the notification
+/// source has closed.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn rescan_on_notification(
+    mut wait_for_change: impl FnMut() -> Option<bool>,
+    sender: &mpsc::UnboundedSender<DeviceChange>,
+    running: &Arc<RwLock<bool>>,
+) {
+    let mut previous = DeviceSnapshot::capture();
+    while *running.blocking_read() && !sender.is_closed() {
+        match wait_for_change() {
+            Some(true) => {}
+            Some(false) => continue,
+            None => return,
+        }
+        std::thread::sleep(SETTLE_DELAY);
+        let current = DeviceSnapshot::capture();
+        for change in previous.diff(&current) {
+            if sender.send(change).is_err() {
+                return;
+            }
+        }
+        previous = current;
+    }
+}
+
+/// Diff device snapshots every `interval`
+fn poll(sender: &mpsc::UnboundedSender<DeviceChange>, running: &Arc<RwLock<bool>>, interval: Duration) {
+    let mut previous = DeviceSnapshot::capture();
+    loop {
+        std::thread::sleep(interval);
+        if !*running.blocking_read() || sender.is_closed() {
+            return;
+        }
+        let current = DeviceSnapshot::capture();
+        for change in previous.diff(&current) {
+            if sender.send(change).is_err() {
+                return;
+            }
+        }
+        previous = current;
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::DeviceChange;
+    use std::io;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::sync::Arc;
+    use tokio::sync::{mpsc, RwLock};
+
+    /// Kernel uevent netlink socket (`NETLINK_KOBJECT_UEVENT`)
+    pub(super) struct UeventSocket {
+        fd: OwnedFd,
+    }
+
+    impl UeventSocket {
+        pub(super) fn open() -> io::Result<Self> {
+            // SAFETY: plain socket syscalls; the descriptor is owned by OwnedFd once valid
+            unsafe {
+                let raw = libc::socket(
+                    libc::AF_NETLINK,
+                    libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                    libc::NETLINK_KOBJECT_UEVENT,
+                );
+                if raw < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                let fd = OwnedFd::from_raw_fd(raw);
+
+                let mut addr: libc::sockaddr_nl = std::mem::zeroed();
+                addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+                addr.nl_groups = 1; // kernel broadcast group
+                if libc::bind(
+                    fd.as_raw_fd(),
+                    &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+                    std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+                ) < 0
+                {
+                    return Err(io::Error::last_os_error());
+                }
+
+                // Wake up periodically so the watcher notices when monitoring stops
+                let timeout = libc::timeval { tv_sec: 1, tv_usec: 0 };
+                if libc::setsockopt(
+                    fd.as_raw_fd(),
+                    libc::SOL_SOCKET,
+                    libc::SO_RCVTIMEO,
+                    &timeout as *const libc::timeval as *const libc::c_void,
+                    std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+                ) < 0
+                {
+                    return Err(io::Error::last_os_error());
+                }
+
+                Ok(Self { fd })
+            }
+        }
+
+        pub(super) fn listen(
+            self,
+            sender: &mpsc::UnboundedSender<DeviceChange>,
+            running: &Arc<RwLock<bool>>,
+        ) {
+            let mut buf = vec![0u8; 8192];
+
+            while *running.blocking_read() {
+                // SAFETY: buf is valid for buf.len() bytes
+                let received = unsafe {
+                    libc::recv(
+                        self.fd.as_raw_fd(),
+                        buf.as_mut_ptr() as *mut libc::c_void,
+                        buf.len(),
+                        0,
+                    )
+                };
+                if received <= 0 {
+                    // Timeout or interrupted; re-check the running flag
+                    continue;
+                }
+
+                if let Some(change) = DeviceChange::from_uevent(&buf[..received as usize], media_present) {
+                    if sender.send(change).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether the block device `name` currently holds media
+    fn media_present(name: &str) -> bool {
+        std::fs::read_to_string(format!("/sys/class/block/{name}/size"))
+            .ok()
+            .and_then(|size| size.trim().parse::<u64>().ok())
+            .is_some_and(|sectors| sectors > 0)
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use std::collections::HashMap;
+    use std::sync::mpsc;
+    use wmi::{Variant, WMIConnection};
+
+    /// Subscribe to `Win32_DeviceChangeEvent`, WMI's relay of `WM_DEVICECHANGE`
+    ///
+    /// WMI blocks until the next event arrives, so the subscription lives on
+    /// its own thread, which forwards one message per event and exits at the
+    /// first event after the receiver is dropped. Returns `None` if the
+    /// subscription cannot be set up.
+    pub(super) fn device_events() -> Option<mpsc::Receiver<()>> {
+        let (ready_sender, ready) = mpsc::sync_channel(1);
+        let (event_sender, events) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("hardware-query-hotplug".to_string())
+            .spawn(move || {
+                // COM is initialized per thread, so connect on the subscription thread
+                let Some(connection) = crate::probe::com_library()
+                    .ok()
+                    .and_then(|com_lib| WMIConnection::new(com_lib).ok())
+                else {
+                    let _ = ready_sender.send(false);
+                    return;
+                };
+                let Ok(mut device_events) = connection
+                    .raw_notification::<HashMap<String, Variant>>("SELECT * FROM Win32_DeviceChangeEvent")
+                else {
+                    let _ = ready_sender.send(false);
+                    return;
+                };
+                let _ = ready_sender.send(true);
+                while let Some(Ok(_)) = device_events.next() {
+                    if event_sender.send(()).is_err() {
+                        return;
+                    }
+                }
+            })
+            .ok()?;
+        ready.recv().ok()?.then_some(events)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use core_foundation_sys::runloop::{
+        kCFRunLoopDefaultMode, CFRunLoopAddSource, CFRunLoopGetCurrent, CFRunLoopRunInMode, CFRunLoopSourceRef,
+    };
+    use std::ffi::{c_char, c_void, CStr};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    type MachPort = u32;
+    type KernReturn = i32;
+    type NotificationCallback = extern "C" fn(refcon: *mut c_void, iterator: MachPort);
+
+    #[link(name = "IOKit", kind = "framework")]
+    unsafe extern "C" {
+        fn IONotificationPortCreate(main_port: MachPort) -> *mut c_void;
+        fn IONotificationPortDestroy(port: *mut c_void);
+        fn IONotificationPortGetRunLoopSource(port: *mut c_void) -> CFRunLoopSourceRef;
+        fn IOServiceMatching(name: *const c_char) -> *mut c_void;
+        fn IOServiceAddMatchingNotification(
+            port: *mut c_void,
+            notification_type: *const c_char,
+            matching: *mut c_void,
+            callback: NotificationCallback,
+            refcon: *mut c_void,
+            iterator: *mut MachPort,
+        ) -> KernReturn;
+        fn IOIteratorNext(iterator: MachPort) -> MachPort;
+        fn IOObjectRelease(object: MachPort) -> KernReturn;
+    }
+
+    /// `kIOMainPortDefault`
+    const MAIN_PORT_DEFAULT: MachPort = 0;
+    /// `kIOFirstMatchNotification` and `kIOTerminatedNotification`
+    const NOTIFICATION_TYPES: [&CStr; 2] = [c"IOServiceFirstMatch", c"IOServiceTerminate"];
+    /// USB devices, disks and media, and PCIe devices (Thunderbolt eGPUs)
+    const SERVICE_CLASSES: [&CStr; 3] = [c"IOUSBHostDevice", c"IOMedia", c"IOPCIDevice"];
+
+    /// IOKit notifications for devices appearing and going away
+    pub(super) struct DeviceNotifications {
+        port: *mut c_void,
+        iterators: Vec<MachPort>,
+        /// Set by the callback; boxed so its address stays stable
+        changed: Box<AtomicBool>,
+    }
+
+    impl DeviceNotifications {
+        /// Register the notifications on the current thread's run loop
+        pub(super) fn register() -> Option<Self> {
+            // SAFETY: creating a notification port has no preconditions
+            let port = unsafe { IONotificationPortCreate(MAIN_PORT_DEFAULT) };
+            if port.is_null() {
+                return None;
+            }
+            let mut notifications = Self {
+                port,
+                iterators: Vec::new(),
+                changed: Box::new(AtomicBool::new(false)),
+            };
+            let refcon = &*notifications.changed as *const AtomicBool as *mut c_void;
+
+            for class in SERVICE_CLASSES {
+                for notification_type in NOTIFICATION_TYPES {
+                    let mut iterator = 0;
+                    // SAFETY: the names are NUL-terminated, the matching dictionary is
+                    // consumed by the call and refcon outlives the port (see Drop)
+                    let result = unsafe {
+                        IOServiceAddMatchingNotification(
+                            port,
+                            notification_type.as_ptr(),
+                            IOServiceMatching(class.as_ptr()),
+                            on_notification,
+                            refcon,
+                            &mut iterator,
+                        )
+                    };
+                    if result == 0 {
+                        // Draining the existing matches arms the notification
+                        drain(iterator);
+                        notifications.iterators.push(iterator);
+                    }
+                }
+            }
+            if notifications.iterators.is_empty() {
+                return None;
+            }
+
+            // SAFETY: the port is valid and the source is removed when it is destroyed
+            unsafe {
+                CFRunLoopAddSource(
+                    CFRunLoopGetCurrent(),
+                    IONotificationPortGetRunLoopSource(port),
+                    kCFRunLoopDefaultMode,
+                );
+            }
+            Some(notifications)
+        }
+
+        /// Run the run loop for up to a second and report whether a device came or went
+        pub(super) fn wait(&self) -> bool {
+            // SAFETY: runs the current thread's run loop, where the port was registered
+            unsafe {
+                CFRunLoopRunInMode(kCFRunLoopDefaultMode, 1.0, 1);
+            }
+            self.changed.swap(false, Ordering::Relaxed)
+        }
+    }
+
+    impl Drop for DeviceNotifications {
+        fn drop(&mut self) {
+            // SAFETY: the iterators and port were created in register and are released once
+            unsafe {
+                for iterator in &self.iterators {
+                    IOObjectRelease(*iterator);
+                }
+                IONotificationPortDestroy(self.port);
+            }
+        }
+    }
+
+    extern "C" fn on_notification(refcon: *mut c_void, iterator: MachPort) {
+        // SAFETY: refcon points to the AtomicBool owned by DeviceNotifications
+        let changed = unsafe { &*(refcon as *const AtomicBool) };
+        changed.store(true, Ordering::Relaxed);
+        drain(iterator);
+    }
+
+    /// Release pending matches so the notification fires again
+    fn drain(iterator: MachPort) {
+        // SAFETY: iterator is a valid IOKit iterator; each object is released once
+        unsafe {
+            loop {
+                let object = IOIteratorNext(iterator);
+                if object == 0 {
+                    break;
+                }
+                IOObjectRelease(object);
+            }
+        }
+    }
+}
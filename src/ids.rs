@@ -0,0 +1,235 @@
+//! Vendor and device name lookup from the `usb.ids` / `pci.ids` databases
+//!
+//! Both files share one format: vendor lines (`vvvv  Name`), device lines
+//! indented by a tab and, in `pci.ids`, subsystem lines indented by two tabs.
+//! A trailing class section (`C 03  Display controller`) names class and
+//! subclass codes. The databases are read from the locations distributions
+//! install them to (hwdata, usbutils, pciutils) the first time a name is
+//! needed.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+#[derive(Debug, Default)]
+struct DeviceEntry {
+    name: String,
+    subsystems: HashMap<(u16, u16), String>,
+}
+
+#[derive(Debug, Default)]
+struct VendorEntry {
+    name: String,
+    devices: HashMap<u16, DeviceEntry>,
+}
+
+#[derive(Debug, Default)]
+struct ClassEntry {
+    name: String,
+    subclasses: HashMap<u8, String>,
+}
+
+/// Section of the file the parser is in
+enum Section {
+    Vendor(u16),
+    Class(u8),
+    Other,
+}
+
+/// Parsed ID database
+#[derive(Debug, Default)]
+pub(crate) struct IdDatabase {
+    vendors: HashMap<u16, VendorEntry>,
+    classes: HashMap<u8, ClassEntry>,
+}
+
+impl IdDatabase {
+    /// Parse a `usb.ids` or `pci.ids` file
+    ///
+    /// Sections other than vendors and classes (`AT ...`, `HID ...`) are skipped.
+    pub(crate) fn parse(text: &str) -> Self {
+        let mut database = Self::default();
+        let mut section = Section::Other;
+        let mut device: Option<u16> = None;
+
+        for line in text.lines() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix("\t\t") {
+                // "\t\tssss dddd  Subsystem name" (class protocols are not kept)
+                let (Section::Vendor(vendor), Some(device)) = (&section, device) else {
+                    continue;
+                };
+                let Some((ids, name)) = rest.split_once("  ") else {
+                    continue;
+                };
+                let Some((sub_vendor, sub_device)) = ids.split_once(' ') else {
+                    continue;
+                };
+                let (Some(sub_vendor), Some(sub_device)) = (hex(sub_vendor), hex(sub_device)) else {
+                    continue;
+                };
+                if let Some(entry) = database
+                    .vendors
+                    .get_mut(vendor)
+                    .and_then(|v| v.devices.get_mut(&device))
+                {
+                    entry
+                        .subsystems
+                        .insert((sub_vendor, sub_device), name.trim().to_string());
+                }
+            } else if let Some(rest) = line.strip_prefix('\t') {
+                device = None;
+                let Some((id, name)) = rest.split_once("  ") else {
+                    continue;
+                };
+                match section {
+                    Section::Vendor(vendor) => {
+                        let (Some(id), Some(entry)) = (hex(id), database.vendors.get_mut(&vendor)) else {
+                            continue;
+                        };
+                        entry.devices.insert(
+                            id,
+                            DeviceEntry {
+                                name: name.trim().to_string(),
+                                ..Default::default()
+                            },
+                        );
+                        device = Some(id);
+                    }
+                    Section::Class(class) => {
+                        let (Some(id), Some(entry)) = (byte(id), database.classes.get_mut(&class)) else {
+                            continue;
+                        };
+                        entry.subclasses.insert(id, name.trim().to_string());
+                    }
+                    Section::Other => {}
+                }
+            } else {
+                device = None;
+                section = Section::Other;
+                let Some((id, name)) = line.split_once("  ") else {
+                    continue;
+                };
+                let name = name.trim().to_string();
+                if let Some(class) = id.strip_prefix("C ").and_then(byte) {
+                    database.classes.insert(
+                        class,
+                        ClassEntry {
+                            name,
+                            ..Default::default()
+                        },
+                    );
+                    section = Section::Class(class);
+                } else if let Some(vendor) = hex(id).filter(|_| id.len() == 4) {
+                    database.vendors.insert(
+                        vendor,
+                        VendorEntry {
+                            name,
+                            ..Default::default()
+                        },
+                    );
+                    section = Section::Vendor(vendor);
+                }
+            }
+        }
+
+        database
+    }
+
+    /// Load the first database found among `paths`
+    fn load(paths: &[&str]) -> Self {
+        paths
+            .iter()
+            .find_map(|path| std::fs::read(path).ok())
+            .map(|bytes| Self::parse(&String::from_utf8_lossy(&bytes)))
+            .unwrap_or_default()
+    }
+
+    /// Vendor name
+    pub(crate) fn vendor(&self, vendor: u16) -> Option<&str> {
+        self.vendors.get(&vendor).map(|v| v.name.as_str())
+    }
+
+    /// Device (USB product) name
+    pub(crate) fn device(&self, vendor: u16, device: u16) -> Option<&str> {
+        self.vendors
+            .get(&vendor)?
+            .devices
+            .get(&device)
+            .map(|d| d.name.as_str())
+    }
+
+    /// Subsystem name (PCI only)
+    pub(crate) fn subsystem(
+        &self,
+        vendor: u16,
+        device: u16,
+        sub_vendor: u16,
+        sub_device: u16,
+    ) -> Option<&str> {
+        self.vendors
+            .get(&vendor)?
+            .devices
+            .get(&device)?
+            .subsystems
+            .get(&(sub_vendor, sub_device))
+            .map(String::as_str)
+    }
+
+    /// Subclass name, or the class name when the subclass is not listed
+    pub(crate) fn class(&self, class: u8, subclass: u8) -> Option<&str> {
+        let entry = self.classes.get(&class)?;
+        Some(entry.subclasses.get(&subclass).unwrap_or(&entry.name))
+    }
+}
+
+/// Parse a hexadecimal ID, with or without a `0x` prefix
+pub(crate) fn hex(value: &str) -> Option<u16> {
+    let value = value.trim();
+    let value = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+    u16::from_str_radix(value, 16).ok()
+}
+
+/// Parse a two-digit hexadecimal class code
+fn byte(value: &str) -> Option<u8> {
+    let value = value.trim();
+    if value.len() != 2 {
+        return None;
+    }
+    u8::from_str_radix(value, 16).ok()
+}
+
+/// The system's `usb.ids` database (empty if none is installed)
+pub(crate) fn usb_ids() -> &'static IdDatabase {
+    static DATABASE: OnceLock<IdDatabase> = OnceLock::new();
+    DATABASE.get_or_init(|| {
+        IdDatabase::load(&[
+            "/usr/share/hwdata/usb.ids",
+            "/usr/share/misc/usb.ids",
+            "/usr/share/usb.ids",
+            "/var/lib/usbutils/usb.ids",
+            "/usr/local/share/hwdata/usb.ids",
+            "/opt/homebrew/share/hwdata/usb.ids",
+        ])
+    })
+}
+
+/// The system's `pci.ids` database (empty if none is installed)
+pub(crate) fn pci_ids() -> &'static IdDatabase {
+    static DATABASE: OnceLock<IdDatabase> = OnceLock::new();
+    DATABASE.get_or_init(|| {
+        IdDatabase::load(&[
+            "/usr/share/hwdata/pci.ids",
+            "/usr/share/misc/pci.ids",
+            "/usr/share/pci.ids",
+            "/var/lib/pciutils/pci.ids",
+            "/usr/local/share/hwdata/pci.ids",
+            "/opt/homebrew/share/hwdata/pci.ids",
+        ])
+    })
+}
@@ -0,0 +1,248 @@
+//! Flattened hardware inventory for IT asset management
+//!
+//! [`AssetInventory`] reduces a hardware scan to what a CMDB records: one
+//! row of system identification and a flat list of [`AssetComponent`]s, each
+//! with a category, manufacturer, model, serial number and an identifier
+//! (slot, device path, MAC address, PCI address, connector or USB ID).
+//! Identifying values can be masked or hashed with the same
+//! [`RedactionOptions`] as [`HardwareInfo::redacted_with`].
+
+use crate::{GPUType, HardwareInfo, MonitorInfo, NetworkType, RedactionOptions};
+use serde::{Deserialize, Serialize};
+
+/// USB base class of hubs, which are plumbing rather than peripherals
+const USB_CLASS_HUB: u8 = 0x09;
+
+/// Kind of inventory component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AssetCategory {
+    Processor,
+    MemoryModule,
+    Disk,
+    NetworkAdapter,
+    GPU,
+    Monitor,
+    USBPeripheral,
+}
+
+impl std::fmt::Display for AssetCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssetCategory::Processor => write!(f, "Processor"),
+            AssetCategory::MemoryModule => write!(f, "Memory module"),
+            AssetCategory::Disk => write!(f, "Disk"),
+            AssetCategory::NetworkAdapter => write!(f, "Network adapter"),
+            AssetCategory::GPU => write!(f, "GPU"),
+            AssetCategory::Monitor => write!(f, "Monitor"),
+            AssetCategory::USBPeripheral => write!(f, "USB peripheral"),
+        }
+    }
+}
+
+/// One component of an asset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetComponent {
+    /// Kind of component
+    pub category: AssetCategory,
+    /// Manufacturer or vendor
+    pub manufacturer: Option<String>,
+    /// Model or product name
+    pub model: String,
+    /// Serial number
+    pub serial_number: Option<String>,
+    /// Where the component sits: DIMM slot, device path, MAC address, PCI
+    /// address, display connector or USB `vendor:product` ID
+    pub identifier: Option<String>,
+    /// Capacity in bytes (memory modules, disks, GPU memory)
+    pub capacity_bytes: Option<u64>,
+    /// Short technical description (`DDR5 4800 MT/s`, `NVMe`, `1000 Mb/s`)
+    pub details: Option<String>,
+}
+
+/// Flattened inventory of one machine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetInventory {
+    /// Host name
+    pub hostname: Option<String>,
+    /// System manufacturer
+    pub manufacturer: Option<String>,
+    /// System model
+    pub model: Option<String>,
+    /// System serial number
+    pub serial_number: Option<String>,
+    /// System UUID
+    pub uuid: Option<String>,
+    /// Asset tag stored in the firmware
+    pub asset_tag: Option<String>,
+    /// Chassis form factor
+    pub chassis_type: String,
+    /// BIOS / UEFI version
+    pub firmware_version: Option<String>,
+    /// Operating system name
+    pub os_name: Option<String>,
+    /// Operating system version
+    pub os_version: Option<String>,
+    /// Installed memory in bytes
+    pub memory_total_bytes: u64,
+    /// Processors, memory modules, disks, network adapters, GPUs, monitors and USB peripherals
+    pub components: Vec<AssetComponent>,
+}
+
+impl AssetInventory {
+    /// Inventory of already-detected hardware and monitors
+    pub fn from_hardware(hardware: &HardwareInfo, monitors: &[MonitorInfo]) -> Self {
+        let mut components = Vec::new();
+        let system = hardware.system();
+        let cpu = hardware.cpu();
+
+        components.push(AssetComponent {
+            category: AssetCategory::Processor,
+            manufacturer: Some(cpu.vendor().to_string()),
+            model: cpu.model_name().to_string(),
+            serial_number: None,
+            identifier: None,
+            capacity_bytes: None,
+            details: Some(format!("{} cores, {} threads", cpu.physical_cores(), cpu.logical_cores())),
+        });
+
+        for module in &hardware.memory().modules {
+            let speed = module.configured_speed_mhz.unwrap_or(module.speed_mhz);
+            components.push(AssetComponent {
+                category: AssetCategory::MemoryModule,
+                manufacturer: module.manufacturer.clone(),
+                model: module.part_number.clone().unwrap_or_else(|| module.memory_type.to_string()),
+                serial_number: module.serial_number.clone(),
+                identifier: module.slot.clone(),
+                capacity_bytes: Some(module.size_mb * 1024 * 1024),
+                details: Some(match speed {
+                    0 => module.memory_type.to_string(),
+                    speed => format!("{} {speed} MT/s", module.memory_type),
+                }),
+            });
+        }
+
+        for disk in hardware.physical_disks() {
+            components.push(AssetComponent {
+                category: AssetCategory::Disk,
+                manufacturer: None,
+                model: disk.model.clone(),
+                serial_number: disk.serial_number.clone(),
+                identifier: Some(disk.device.clone()),
+                capacity_bytes: Some(disk.capacity_bytes),
+                details: Some(format!("{} over {}", disk.storage_type, disk.bus)),
+            });
+        }
+
+        for interface in hardware.network_interfaces() {
+            if matches!(interface.network_type, NetworkType::Loopback | NetworkType::VPN) || interface.mac_address.is_empty() {
+                continue;
+            }
+            components.push(AssetComponent {
+                category: AssetCategory::NetworkAdapter,
+                manufacturer: None,
+                model: interface.name.clone(),
+                serial_number: None,
+                identifier: Some(interface.mac_address.clone()),
+                capacity_bytes: None,
+                details: Some(match interface.speed_mbps {
+                    Some(speed) => format!("{} {speed} Mb/s", interface.network_type),
+                    None => interface.network_type.to_string(),
+                }),
+            });
+        }
+
+        for gpu in hardware.gpus() {
+            components.push(AssetComponent {
+                category: AssetCategory::GPU,
+                manufacturer: Some(gpu.vendor.to_string()),
+                model: gpu.model_name.clone(),
+                serial_number: None,
+                identifier: gpu.pci_bus_id.clone(),
+                // Integrated GPUs report a share of system memory
+                capacity_bytes: (gpu.gpu_type != GPUType::Integrated && gpu.memory_mb > 0)
+                    .then_some(gpu.memory_mb * 1024 * 1024),
+                details: gpu.driver_version.as_ref().map(|version| format!("Driver {version}")),
+            });
+        }
+
+        for monitor in monitors {
+            components.push(AssetComponent {
+                category: AssetCategory::Monitor,
+                manufacturer: monitor.manufacturer().map(str::to_string),
+                model: monitor.model.clone().unwrap_or_else(|| "Monitor".to_string()),
+                serial_number: monitor.serial_number.clone(),
+                identifier: monitor.connector.clone(),
+                capacity_bytes: None,
+                details: match (monitor.native_width, monitor.native_height, monitor.diagonal_inches()) {
+                    (Some(width), Some(height), Some(diagonal)) => Some(format!("{width}x{height}, {diagonal:.1}\"")),
+                    (Some(width), Some(height), None) => Some(format!("{width}x{height}")),
+                    (_, _, Some(diagonal)) => Some(format!("{diagonal:.1}\"")),
+                    _ => None,
+                },
+            });
+        }
+
+        for device in hardware.usb_devices() {
+            // Root hubs report the host controller driver as their vendor
+            if device.class_code == USB_CLASS_HUB || device.vendor_id.eq_ignore_ascii_case("1d6b") {
+                continue;
+            }
+            components.push(AssetComponent {
+                category: AssetCategory::USBPeripheral,
+                manufacturer: Some(device.vendor_name.clone()).filter(|name| !name.is_empty()),
+                model: device.product_name.clone(),
+                serial_number: device.serial_number.clone(),
+                identifier: Some(format!("{}:{}", device.vendor_id, device.product_id)),
+                capacity_bytes: None,
+                details: Some(device.device_class.clone()).filter(|class| !class.is_empty()),
+            });
+        }
+
+        Self {
+            hostname: sysinfo::System::host_name(),
+            manufacturer: system.manufacturer.clone(),
+            model: system.product_name.clone(),
+            serial_number: system.serial_number.clone(),
+            uuid: system.uuid.clone(),
+            asset_tag: system.baseboard.asset_tag.clone(),
+            chassis_type: system.chassis_type.to_string(),
+            firmware_version: system.firmware.version.clone(),
+            os_name: sysinfo::System::name(),
+            os_version: sysinfo::System::os_version(),
+            memory_total_bytes: hardware.memory().total_mb() * 1024 * 1024,
+            components,
+        }
+    }
+
+    /// Components of one category
+    pub fn components_of(&self, category: AssetCategory) -> impl Iterator<Item = &AssetComponent> {
+        self.components.iter().filter(move |component| component.category == category)
+    }
+
+    /// Copy with host name, serial numbers, UUID, asset tag and MAC addresses masked
+    pub fn redacted(&self) -> Self {
+        self.redacted_with(&RedactionOptions::default())
+    }
+
+    /// Copy with identifying values masked or hashed according to `options`
+    ///
+    /// Hashes match those of [`HardwareInfo::redacted_with`] for the same salt,
+    /// so redacted inventories and reports can still be joined.
+    pub fn redacted_with(&self, options: &RedactionOptions) -> Self {
+        let mut inventory = self.clone();
+        let mut values = vec![
+            &mut inventory.hostname,
+            &mut inventory.serial_number,
+            &mut inventory.uuid,
+            &mut inventory.asset_tag,
+        ];
+        for component in &mut inventory.components {
+            values.push(&mut component.serial_number);
+            if component.category == AssetCategory::NetworkAdapter {
+                values.push(&mut component.identifier);
+            }
+        }
+        crate::redact::redact_values(values, options);
+        inventory
+    }
+}
@@ -0,0 +1,57 @@
+//! Translation hook for human-readable strings
+//!
+//! `Display` implementations for statuses, tiers and other descriptive enums
+//! produce English text. A host application can install a [`Localizer`] with
+//! [`set_localizer`] to translate that text: each string is looked up by the
+//! name of the type that produced it (the context) and its English text, the
+//! same pair gettext uses as `msgctxt`/`msgid`. Strings the localizer does not
+//! know stay in English.
+//!
+//! Identifiers that are parsed back or used as keys (component names, vendor
+//! and product names, storage types) are never translated.
+
+use std::borrow::Cow;
+use std::sync::{Arc, RwLock};
+
+/// Translates English strings produced by this crate
+pub trait Localizer: Send + Sync {
+    /// Translate `text`, produced by the type named `context`
+    ///
+    /// Return `None` to keep the English text.
+    fn translate(&self, context: &str, text: &str) -> Option<String>;
+}
+
+impl<F> Localizer for F
+where
+    F: Fn(&str, &str) -> Option<String> + Send + Sync,
+{
+    fn translate(&self, context: &str, text: &str) -> Option<String> {
+        self(context, text)
+    }
+}
+
+static LOCALIZER: RwLock<Option<Arc<dyn Localizer>>> = RwLock::new(None);
+
+/// Install a localizer for the whole process, replacing any previous one
+pub fn set_localizer(localizer: impl Localizer + 'static) {
+    let mut current = LOCALIZER.write().unwrap_or_else(|e| e.into_inner());
+    *current = Some(Arc::new(localizer));
+}
+
+/// Remove the installed localizer, restoring English text
+pub fn clear_localizer() {
+    let mut current = LOCALIZER.write().unwrap_or_else(|e| e.into_inner());
+    *current = None;
+}
+
+/// Translate `text` through the installed localizer, if any
+pub fn localize<'a>(context: &str, text: &'a str) -> Cow<'a, str> {
+    let localizer = LOCALIZER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    match localizer.and_then(|localizer| localizer.translate(context, text)) {
+        Some(translated) => Cow::Owned(translated),
+        None => Cow::Borrowed(text),
+    }
+}
@@ -168,6 +168,13 @@
 //!
 //! - **Default**: Basic hardware detection (CPU, Memory, GPU, Storage)
 //! - **`monitoring`**: Real-time monitoring capabilities, thermal sensors, power management
+//! - **`async`**: Async variants of the top-level queries (`HardwareInfo::query_async`, etc.) backed by tokio
+//! - **`yaml`** / **`msgpack`**: YAML and MessagePack export of `HardwareInfo`
+//! - **`cli`**: Builds the `hardware-query` command-line tool (`hardware-query overview`, `ai`, `gpu --json`, `monitor --interval 2s`, ...)
+//! - **`wasm`**: On `wasm32-unknown-unknown`, `HardwareInfo::query_browser` reports what the browser exposes (core count, device memory, WebGPU adapter)
+//! - **`fan-control`**: `FanController` for setting fan duty cycles and applying fan curves (root/Administrator)
+//! - **`librehardwaremonitor`**: On Windows, read temperatures and fan speeds from a running LibreHardwareMonitor/OpenHardwareMonitor instance
+//! - **`smc`**: On macOS, read temperatures, fan speeds and power rails from the SMC (power via `powermetrics` as root on Apple Silicon)
 //! - **`serde`**: Serialization/deserialization support (automatically enabled)
 //!
 //! ## Platform Support
@@ -178,57 +185,181 @@
 //!
 //! All APIs work consistently across platforms, with graceful degradation when specific hardware isn't available.
 
+mod affinity;
 mod battery;
+mod benchmark;
+mod cache;
+mod capabilities;
 mod cpu;
+mod diff;
+mod disk_io;
+mod display;
+mod energy;
 mod error;
+mod fingerprint;
 mod gpu;
+mod gpu_api;
+mod gpu_performance;
+mod gpu_selection;
+mod gpu_specs;
+mod device_topology;
+mod gpu_topology;
+mod gpu_usage;
+mod headroom;
 mod hardware_info;
+mod ids;
+mod inventory;
+mod l10n;
+mod llama_cpp;
 mod memory;
+mod model_fit;
 mod network;
 mod npu;
+mod onnx;
+mod options;
 mod pci;
 pub mod platform;
+mod probe;
+mod provider;
+mod rebar;
+mod redact;
+mod report;
+mod requirements;
+mod scoring;
+mod parallelism;
+mod perf_counters;
+mod shared;
+mod smc;
 mod storage;
+mod system;
+mod sensor_map;
 mod thermal;
+mod throttle;
+mod topology;
+mod units;
 mod tpu;
 mod usb;
+mod video;
 mod arm;
 mod fpga;
 mod power;
+mod snapshot;
+mod software;
 mod virtualization;
 
 #[cfg(feature = "monitoring")]
 mod monitoring;
+#[cfg(feature = "monitoring")]
+mod sinks;
+mod history;
+#[cfg(feature = "monitoring")]
+mod rules;
+#[cfg(feature = "monitoring")]
+mod sampling;
+#[cfg(feature = "monitoring")]
+mod hotplug;
+#[cfg(feature = "bluetooth")]
+mod bluetooth;
+#[cfg(feature = "sim")]
+mod sim;
+#[cfg(feature = "fan-control")]
+mod fan_control;
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+mod browser;
 
 // Simplified API modules
 pub mod simple;
 pub mod builder;
 pub mod presets;
 
-pub use battery::{BatteryInfo, BatteryStatus};
-pub use cpu::{CPUFeature, CPUInfo, CPUVendor};
-pub use error::{HardwareQueryError, Result};
-pub use gpu::{GPUInfo, GPUType, GPUVendor};
-pub use hardware_info::HardwareInfo;
-pub use memory::{MemoryInfo, MemoryType};
-pub use network::{NetworkInfo, NetworkType};
+pub use affinity::{AffinityCriteria, CpuSet, GroupAffinity};
+pub use battery::{BatteryDrain, BatteryInfo, BatteryStatus};
+pub use benchmark::{BenchmarkResults, EstimateValidation};
+pub use cache::{CacheConfig, HardwareComponent, QueryCache};
+pub use capabilities::{AccessLevel, Capabilities, Requirement, Subsystem, SubsystemAccess};
+pub use cpu::{CPUFeature, CPUFrequencyInfo, CPUInfo, CPUVendor};
+pub use diff::{ChangeKind, HardwareChange, HardwareDiff};
+pub use disk_io::{DiskIoCounters, DiskIoStats};
+pub use display::MonitorInfo;
+pub use energy::{DomainEnergy, EnergyCounter, EnergyDomain, EnergyMeter, EnergyReading, EnergySample};
+pub use error::{ErrorCode, ErrorComponent, HardwareQueryError, Result};
+pub use fingerprint::{Fingerprint, FingerprintOptions, FingerprintSource};
+pub use gpu::{ComputeCapabilities, GPUContextType, GPUInfo, GPUProcess, GPUType, GPUVendor};
+pub use gpu_api::{DirectXCapabilities, MetalDeviceInfo, OpenCLDeviceInfo, VulkanDeviceInfo};
+pub use gpu_performance::{GPUPerformanceDatabase, GPUPerformanceEntry, GPUPerformanceTier};
+pub use gpu_specs::{GPUSpecs, GPUSpecsDatabase};
+pub use device_topology::{DeviceInterrupt, DeviceNode, DeviceTopology};
+pub use gpu_topology::{GPUInterconnect, GPUNode, GPUTopology, NVLinkConnection, PCIeLink};
+pub use gpu_selection::{GpuCriteria, GpuRanking, GpuSelection};
+pub use gpu_usage::GPUUsage;
+pub use headroom::{CPUPowerLimits, ComponentHeadroom, ComponentLoad, HeadroomLimit, ThermalHeadroom};
+pub use hardware_info::{ComponentError, ComponentErrorKind, DynamicComponent, HardwareInfo, SCHEMA_VERSION};
+pub use inventory::{AssetCategory, AssetComponent, AssetInventory};
+pub use memory::{EccDimmErrors, EccStatus, MemoryInfo, MemoryModule, MemoryType};
+pub use model_fit::{ModelFit, ModelFitPlanner, ModelPlacement, Quantization, ThroughputClass};
+pub use network::{Duplex, NetworkInfo, NetworkIoStats, NetworkType, WirelessInfo};
 pub use npu::{NPUInfo, NPUVendor, NPUType, NPUArchitecture};
-pub use pci::PCIDevice;
-pub use storage::{StorageInfo, StorageType};
-pub use thermal::{FanInfo, ThermalInfo, ThermalSensor, ThrottlingPrediction, CoolingRecommendation, ThrottlingSeverity};
-pub use tpu::{TPUInfo, TPUVendor, TPUArchitecture, TPUConnectionType};
-pub use usb::USBDevice;
+pub use onnx::{OnnxExecutionProvider, OnnxProviderRecommendation};
+pub use l10n::{clear_localizer, localize, set_localizer, Localizer};
+pub use llama_cpp::{GgmlBackend, GgmlBackendSupport, GgmlCpuPath, LayerOffload, LlamaCppReport};
+pub use options::QueryOptions;
+pub use probe::NO_EXTERNAL_COMMANDS_ENV_VAR;
+pub use pci::{IOMMUGroup, PCIDevice};
+pub use storage::{BusType, NVMeInfo, NVMeNamespace, PhysicalDisk, SdCard, SdCardReader, SdCardType, SdSpeedClass, SmartHealth, StorageInfo, StorageLayout, StoragePerformanceClass, StorageType, Volume};
+pub use system::{BaseboardInfo, ChassisType, FirmwareInfo, SystemInfo};
+pub use thermal::{CurvePoint, FanCurve, FanInfo, PredictedTemperature, TemperatureReading, ThermalInfo, ThermalSensor, ThrottlingPrediction, CoolingRecommendation, ThrottlingSeverity};
+pub use sensor_map::{MappingConfidence, SensorComponent, SensorMapping};
+pub use throttle::{ThrottleCause, ThrottleEvent, ThrottleStatus};
+pub use topology::{CPUTopology, CacheDomain, CacheType, CoreCluster, CoreType, LogicalProcessor, NumaNode};
+pub use tpu::{EdgeTpuValidation, TPUInfo, TPUVendor, TPUArchitecture, TPUConnectionType};
+pub use usb::{USBDevice, USBSpeed, USBTopology};
+pub use video::{CodecSupport, VideoCapabilities, VideoCodec, VideoEngine};
 pub use arm::{ARMHardwareInfo, ARMSystemType, PowerInfo};
 pub use fpga::{FPGAInfo, FPGAVendor, FPGAFamily, FPGAInterface};
-pub use power::{PowerProfile, PowerState, ThrottlingRisk, PowerOptimization, OptimizationCategory};
-pub use virtualization::{VirtualizationInfo, VirtualizationType, ContainerRuntime, ResourceLimits};
+pub use provider::{FixtureProvider, HardwareProvider, SystemProvider};
+pub use rebar::ResizableBar;
+pub use redact::{RedactionMode, RedactionOptions};
+pub use snapshot::SnapshotStore;
+pub use report::{HardwareReport, ReportBlock, ReportDetail, ReportSection};
+pub use requirements::{ComplianceCheck, ComplianceReport, GpuRequirement, HardwareRequirement, Requirements};
+pub use scoring::{CustomAssessment, NamedScore, ScoringPlugin};
+pub use parallelism::{ParallelismRecommendation, WorkloadKind};
+pub use perf_counters::{DiskQueueLength, GPUEngineUsage, PerformanceCounterSampler, PerformanceCounters};
+pub use shared::SharedHardwareInfo;
+pub use smc::PowerRails;
+pub use units::UnitSystem;
+pub use software::{AIRuntime, RuntimeVersion, SoftwareStack};
+pub use power::{PowerProfile, PowerState, PowerSource, PowerMode, ThrottlingRisk, PowerOptimization, OptimizationCategory};
+pub use virtualization::{VirtualizationInfo, VirtualizationType, ContainerRuntime, KubernetesInfo, ResourceLimits};
 
 #[cfg(feature = "monitoring")]
-pub use monitoring::{HardwareMonitor, MonitoringConfig, MonitoringEvent, MonitoringStats, MonitoringCallback};
+pub use monitoring::{HardwareMonitor, MonitoringConfig, MonitoringEvent, MonitoringStats, MonitoringCallback, HardwareChangeType, EventSeverity, MonitorHealth, MonitorState};
+#[cfg(feature = "monitoring")]
+pub use sinks::{JsonlFileSink, SystemLogSink};
+#[cfg(feature = "webhook")]
+pub use sinks::WebhookSink;
+#[cfg(feature = "monitoring")]
+pub use hotplug::{DeviceChange, DeviceClass, DeviceSnapshot};
+pub use history::{HistoryBuffer, Metric, Sample, TimeSeries};
+#[cfg(feature = "monitoring")]
+pub use rules::{AlertRule, Comparison};
+#[cfg(feature = "monitoring")]
+pub use sampling::{AdaptiveSampling, SamplingReading};
+#[cfg(feature = "bluetooth")]
+pub use bluetooth::{BluetoothAdapter, BluetoothDevice, BluetoothInfo};
+#[cfg(feature = "sim")]
+pub use sim::{MachineProfile, SIM_ENV_VAR};
+#[cfg(feature = "fan-control")]
+pub use fan_control::{ControllableFan, FanController};
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub use browser::{BrowserProvider, WebGpuAdapterInfo};
 
 // Simplified API exports - these are the recommended entry points for most users
-pub use simple::{SystemOverview, SimpleCPU, SimpleGPU, SimpleStorage, SystemHealth, 
+pub use simple::{SystemOverview, SimpleAccelerator, SimpleCPU, SimpleGPU, SimpleStorage, SystemHealth, 
                  HealthStatus, TemperatureStatus, PowerStatus};
-pub use builder::{HardwareQueryBuilder, CustomHardwareInfo};
+pub use builder::{Component, HardwareQueryBuilder, CustomHardwareInfo};
 pub use presets::{HardwarePresets, AIHardwareAssessment, GamingHardwareAssessment, 
-                  DeveloperHardwareAssessment, ServerHardwareAssessment};
+                  DeveloperHardwareAssessment, ServerHardwareAssessment, BenchmarkAssessment,
+                  FrameworkRequirement, RequirementCheck, LLMHardwareAssessment, LLMModelSize,
+                  ContextMemory, LLMThroughput, LLMRuntimeSupport, ImageGenerationAssessment,
+                  ImageModelFit, ImageAcceleration, ContentCreationAssessment, TimelineSupport};
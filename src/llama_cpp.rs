@@ -0,0 +1,350 @@
+//! llama.cpp / GGML backend capabilities
+//!
+//! llama.cpp is built with one GPU backend (CUDA, HIP, Metal or Vulkan) on
+//! top of its CPU code, which picks the widest SIMD path the CPU supports.
+//! The report lists which backends the detected hardware can use, the GGML
+//! CPU path, and how many layers to offload with `-ngl` so a model's weights
+//! and KV cache fit the GPU memory of the chosen backend.
+//!
+//! ```rust
+//! use hardware_query::{HardwarePresets, ModelFitPlanner, Quantization};
+//!
+//! # fn main() -> hardware_query::Result<()> {
+//! let report = HardwarePresets::llama_cpp_report()?;
+//! let offload = report.gpu_layers(&ModelFitPlanner::from_billions(8.0).quantization(Quantization::Q4));
+//! println!("{} backend, CPU path {}: {}", offload.backend, report.cpu_path, offload.ngl_arg());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{CPUFeature, CPUInfo, GPUType, GPUVendor, HardwareInfo, ModelFitPlanner, Quantization};
+use serde::{Deserialize, Serialize};
+
+/// Oldest CUDA compute capability llama.cpp builds kernels for
+const MIN_CUDA_CAPABILITY: f64 = 5.0;
+/// Runtime buffers that stay on the GPU regardless of the offload count, in GB
+const GPU_OVERHEAD_GB: f64 = 0.5;
+
+/// GGML compute backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GgmlBackend {
+    /// NVIDIA CUDA
+    CUDA,
+    /// AMD ROCm / HIP
+    HIP,
+    /// Apple Metal
+    Metal,
+    /// Vulkan on any vendor's GPU
+    Vulkan,
+    /// CPU only
+    CPU,
+}
+
+impl GgmlBackend {
+    /// CMake option that enables the backend, `None` for the always-built CPU backend
+    pub fn cmake_flag(&self) -> Option<&'static str> {
+        match self {
+            GgmlBackend::CUDA => Some("-DGGML_CUDA=ON"),
+            GgmlBackend::HIP => Some("-DGGML_HIP=ON"),
+            GgmlBackend::Metal => Some("-DGGML_METAL=ON"),
+            GgmlBackend::Vulkan => Some("-DGGML_VULKAN=ON"),
+            GgmlBackend::CPU => None,
+        }
+    }
+}
+
+impl std::fmt::Display for GgmlBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GgmlBackend::CUDA => write!(f, "CUDA"),
+            GgmlBackend::HIP => write!(f, "HIP"),
+            GgmlBackend::Metal => write!(f, "Metal"),
+            GgmlBackend::Vulkan => write!(f, "Vulkan"),
+            GgmlBackend::CPU => write!(f, "CPU"),
+        }
+    }
+}
+
+/// Widest SIMD path GGML's CPU kernels can use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GgmlCpuPath {
+    /// Plain scalar code
+    Scalar,
+    /// 128-bit ARM NEON
+    NEON,
+    /// 256-bit AVX without integer support
+    AVX,
+    /// 256-bit AVX2 with FMA
+    AVX2,
+    /// 512-bit AVX-512
+    AVX512,
+}
+
+impl GgmlCpuPath {
+    /// Path for `cpu`
+    pub fn from_cpu(cpu: &CPUInfo) -> Self {
+        let features = cpu.features();
+        if cpu.architecture().starts_with("aarch64") || cpu.architecture().starts_with("arm64") || cpu.has_feature("neon") {
+            GgmlCpuPath::NEON
+        } else if features.contains(&CPUFeature::AVX512) {
+            GgmlCpuPath::AVX512
+        } else if features.contains(&CPUFeature::AVX2) {
+            GgmlCpuPath::AVX2
+        } else if features.contains(&CPUFeature::AVX) {
+            GgmlCpuPath::AVX
+        } else {
+            GgmlCpuPath::Scalar
+        }
+    }
+}
+
+impl std::fmt::Display for GgmlCpuPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GgmlCpuPath::Scalar => write!(f, "scalar"),
+            GgmlCpuPath::NEON => write!(f, "NEON"),
+            GgmlCpuPath::AVX => write!(f, "AVX"),
+            GgmlCpuPath::AVX2 => write!(f, "AVX2"),
+            GgmlCpuPath::AVX512 => write!(f, "AVX-512"),
+        }
+    }
+}
+
+/// Whether a backend works on the detected hardware
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GgmlBackendSupport {
+    /// Backend
+    pub backend: GgmlBackend,
+    /// Whether llama.cpp built with this backend can use the hardware
+    pub supported: bool,
+    /// Indices into [`HardwareInfo::gpus`] of the GPUs it would run on
+    pub gpu_indices: Vec<usize>,
+    /// GPU memory the backend can fill with layers, in GB
+    pub memory_gb: f64,
+    /// Why it is or isn't supported
+    pub notes: String,
+}
+
+/// Suggested `-ngl` (`--n-gpu-layers`) value for a model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerOffload {
+    /// Model the suggestion is for, e.g. `8B Q4`
+    pub model: String,
+    /// Backend the layers are offloaded to
+    pub backend: GgmlBackend,
+    /// Transformer blocks in the model
+    pub total_layers: u32,
+    /// Value to pass as `-ngl`; one more than `total_layers` also offloads the output layer
+    pub gpu_layers: u32,
+    /// Whether the whole model runs on the GPU
+    pub full_offload: bool,
+    /// Memory the model needs with its KV cache, in GB
+    pub memory_required_gb: f64,
+    /// GPU memory available to the backend, in GB
+    pub gpu_memory_gb: f64,
+}
+
+impl LayerOffload {
+    /// Command-line argument, e.g. `-ngl 33`
+    pub fn ngl_arg(&self) -> String {
+        format!("-ngl {}", self.gpu_layers)
+    }
+}
+
+/// llama.cpp capabilities of the detected hardware
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlamaCppReport {
+    /// Every GPU backend plus the CPU backend, in preference order
+    pub backends: Vec<GgmlBackendSupport>,
+    /// SIMD path of the CPU kernels
+    pub cpu_path: GgmlCpuPath,
+    /// CPU extensions GGML uses on top of the path (F16C, FMA, VNNI, AMX, ...)
+    pub cpu_extensions: Vec<String>,
+    /// Suggested `-t` value: one thread per physical core
+    pub threads: u32,
+    /// `-ngl` suggestions for common model sizes at Q4 with a 4096-token context
+    pub reference_offload: Vec<LayerOffload>,
+}
+
+impl LlamaCppReport {
+    /// Report for already-detected hardware
+    pub fn from_hardware(hardware: &HardwareInfo) -> Self {
+        let cpu = hardware.cpu();
+        let cpu_extensions = cpu
+            .features()
+            .iter()
+            .filter(|feature| {
+                matches!(
+                    feature,
+                    CPUFeature::F16C
+                        | CPUFeature::FMA
+                        | CPUFeature::AVX512VNNI
+                        | CPUFeature::AVX512VBMI
+                        | CPUFeature::AVX512BF16
+                        | CPUFeature::AVXVNNI
+                        | CPUFeature::AMXINT8
+                        | CPUFeature::AMXBF16
+                )
+            })
+            .map(ToString::to_string)
+            .chain(
+                ["dotprod", "i8mm", "sve"]
+                    .into_iter()
+                    .filter(|feature| cpu.has_feature(feature))
+                    .map(str::to_string),
+            )
+            .collect();
+
+        let mut report = Self {
+            backends: backends(hardware),
+            cpu_path: GgmlCpuPath::from_cpu(cpu),
+            cpu_extensions,
+            threads: cpu.physical_cores().max(1),
+            reference_offload: Vec::new(),
+        };
+        report.reference_offload = [8.0, 14.0, 32.0, 70.0]
+            .into_iter()
+            .map(|billions| report.gpu_layers(&ModelFitPlanner::from_billions(billions).quantization(Quantization::Q4)))
+            .collect();
+        report
+    }
+
+    /// First supported backend; the CPU backend when no GPU backend is
+    pub fn best_backend(&self) -> &GgmlBackendSupport {
+        self.backends
+            .iter()
+            .find(|backend| backend.supported)
+            .unwrap_or_else(|| self.backends.last().expect("the CPU backend is always listed"))
+    }
+
+    /// Check whether `backend` works here
+    pub fn supports(&self, backend: GgmlBackend) -> bool {
+        self.backends.iter().any(|support| support.backend == backend && support.supported)
+    }
+
+    /// How many layers of `model` to offload on the best backend
+    ///
+    /// Each offloaded layer takes its share of the weights and KV cache; the
+    /// rest of the GPU memory budget is kept for compute buffers. When
+    /// everything fits, the output layer is offloaded too.
+    pub fn gpu_layers(&self, model: &ModelFitPlanner) -> LayerOffload {
+        let backend = self.best_backend();
+        let total_layers = model.layer_count();
+        let memory_required_gb = model.memory_required_gb();
+        let gpu_memory_gb = backend.memory_gb;
+
+        let (gpu_layers, full_offload) = if backend.backend == GgmlBackend::CPU || gpu_memory_gb <= GPU_OVERHEAD_GB {
+            (0, false)
+        } else if gpu_memory_gb >= memory_required_gb {
+            (total_layers + 1, true)
+        } else {
+            let per_layer_gb = (memory_required_gb - GPU_OVERHEAD_GB) / (total_layers + 1) as f64;
+            let layers = ((gpu_memory_gb - GPU_OVERHEAD_GB) / per_layer_gb).floor() as u32;
+            (layers.min(total_layers), false)
+        };
+
+        LayerOffload {
+            model: model.label(),
+            backend: backend.backend,
+            total_layers,
+            gpu_layers,
+            full_offload,
+            memory_required_gb,
+            gpu_memory_gb,
+        }
+    }
+}
+
+/// GPU backends in preference order, then the CPU backend
+fn backends(hardware: &HardwareInfo) -> Vec<GgmlBackendSupport> {
+    let gpus = hardware.gpus();
+    let total_memory_gb = hardware.memory().total_gb();
+    // Metal may wire about three quarters of unified memory; drivers keep part of dedicated VRAM
+    let usable_gb = |index: usize| {
+        let gpu = &gpus[index];
+        if gpu.vendor == GPUVendor::Apple {
+            total_memory_gb * 0.75
+        } else if gpu.has_unified_memory() || matches!(gpu.gpu_type, GPUType::Integrated | GPUType::Virtual) {
+            0.0
+        } else {
+            gpu.memory_gb() * 0.92
+        }
+    };
+    let support = |backend, gpu_indices: Vec<usize>, notes: String| {
+        let memory_gb = gpu_indices.iter().map(|&index| usable_gb(index)).sum();
+        GgmlBackendSupport {
+            backend,
+            supported: !gpu_indices.is_empty(),
+            gpu_indices,
+            memory_gb,
+            notes,
+        }
+    };
+    let matching = |predicate: &dyn Fn(&crate::GPUInfo) -> bool| -> Vec<usize> {
+        gpus.iter().enumerate().filter(|(_, gpu)| predicate(gpu)).map(|(index, _)| index).collect()
+    };
+    let names = |indices: &[usize]| {
+        indices.iter().map(|&index| gpus[index].model_name()).collect::<Vec<_>>().join(", ")
+    };
+
+    let nvidia = matching(&|gpu| gpu.vendor == GPUVendor::NVIDIA);
+    let cuda = if cfg!(target_os = "macos") {
+        Vec::new()
+    } else {
+        matching(&|gpu| {
+            gpu.cuda_capability()
+                .and_then(|capability| capability.parse::<f64>().ok())
+                .is_some_and(|capability| capability >= MIN_CUDA_CAPABILITY)
+        })
+    };
+    let cuda_notes = match (cuda.is_empty(), nvidia.is_empty()) {
+        (false, _) => format!("CUDA kernels on {}", names(&cuda)),
+        (true, false) if cfg!(target_os = "macos") => "CUDA is not available on macOS".to_string(),
+        (true, false) => format!("{} needs compute capability {MIN_CUDA_CAPABILITY:.1}+", names(&nvidia)),
+        (true, true) => "No NVIDIA GPU".to_string(),
+    };
+
+    let hip = if cfg!(any(target_os = "linux", target_os = "windows")) {
+        matching(&|gpu| gpu.vendor == GPUVendor::AMD && gpu.supports_rocm())
+    } else {
+        Vec::new()
+    };
+    let hip_notes = if hip.is_empty() {
+        "No ROCm-supported AMD GPU".to_string()
+    } else {
+        format!("HIP kernels on {}", names(&hip))
+    };
+
+    let metal = if cfg!(target_os = "macos") {
+        matching(&|gpu| gpu.supports_metal())
+    } else {
+        Vec::new()
+    };
+    let metal_notes = if metal.is_empty() {
+        "Needs a Metal GPU on macOS".to_string()
+    } else {
+        format!("Metal on {}; built by default on macOS", names(&metal))
+    };
+
+    let vulkan = matching(&|gpu| gpu.supports_vulkan() && gpu.gpu_type != GPUType::Virtual);
+    let vulkan_notes = if vulkan.is_empty() {
+        "No Vulkan-capable GPU".to_string()
+    } else {
+        format!("Vulkan on {}; works across vendors but trails the native backends", names(&vulkan))
+    };
+
+    let cpu = hardware.cpu();
+    vec![
+        support(GgmlBackend::CUDA, cuda, cuda_notes),
+        support(GgmlBackend::HIP, hip, hip_notes),
+        support(GgmlBackend::Metal, metal, metal_notes),
+        support(GgmlBackend::Vulkan, vulkan, vulkan_notes),
+        GgmlBackendSupport {
+            backend: GgmlBackend::CPU,
+            supported: true,
+            gpu_indices: Vec::new(),
+            memory_gb: 0.0,
+            notes: format!("{} with the {} path", cpu.model_name(), GgmlCpuPath::from_cpu(cpu)),
+        },
+    ]
+}
@@ -1,3 +1,4 @@
+use crate::units::MIB;
 use crate::Result;
 use serde::{Deserialize, Serialize};
 use sysinfo::System;
@@ -28,6 +29,21 @@ impl std::fmt::Display for MemoryType {
     }
 }
 
+impl MemoryType {
+    /// Classify a memory type name as reported by SMBIOS, WMI or system_profiler
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_ascii_uppercase().as_str() {
+            "DDR3" => MemoryType::DDR3,
+            "DDR4" => MemoryType::DDR4,
+            "DDR5" => MemoryType::DDR5,
+            "LPDDR3" => MemoryType::LPDDR3,
+            "LPDDR4" | "LPDDR4X" => MemoryType::LPDDR4,
+            "LPDDR5" | "LPDDR5X" => MemoryType::LPDDR5,
+            _ => MemoryType::Unknown(name.trim().to_string()),
+        }
+    }
+}
+
 /// Memory module information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryModule {
@@ -45,6 +61,286 @@ pub struct MemoryModule {
     pub slot: Option<String>,
     /// Memory voltage
     pub voltage: Option<f32>,
+    /// Configured (running) speed in MHz, if different from the rated speed
+    #[serde(default)]
+    pub configured_speed_mhz: Option<u32>,
+    /// Bank label
+    #[serde(default)]
+    pub bank: Option<String>,
+    /// Module serial number
+    #[serde(default)]
+    pub serial_number: Option<String>,
+    /// Whether the module carries ECC check bits
+    #[serde(default)]
+    pub ecc: Option<bool>,
+}
+
+impl MemoryModule {
+    /// Get module size in MB
+    pub fn size_mb(&self) -> u64 {
+        self.size_mb
+    }
+
+    /// Get module size in GB
+    pub fn size_gb(&self) -> f64 {
+        self.size_mb as f64 / 1024.0
+    }
+
+    /// Get module size in bytes
+    pub fn size_bytes(&self) -> u64 {
+        self.size_mb * MIB
+    }
+
+    /// Get memory type
+    pub fn memory_type(&self) -> &MemoryType {
+        &self.memory_type
+    }
+
+    /// Get rated speed in MHz (0 if unknown)
+    pub fn speed_mhz(&self) -> u32 {
+        self.speed_mhz
+    }
+
+    /// Get configured speed in MHz
+    pub fn configured_speed_mhz(&self) -> Option<u32> {
+        self.configured_speed_mhz
+    }
+
+    /// Get manufacturer
+    pub fn manufacturer(&self) -> Option<&str> {
+        self.manufacturer.as_deref()
+    }
+
+    /// Get part number
+    pub fn part_number(&self) -> Option<&str> {
+        self.part_number.as_deref()
+    }
+
+    /// Get slot locator
+    pub fn slot(&self) -> Option<&str> {
+        self.slot.as_deref()
+    }
+
+    /// Get bank label
+    pub fn bank(&self) -> Option<&str> {
+        self.bank.as_deref()
+    }
+
+    /// Get serial number
+    pub fn serial_number(&self) -> Option<&str> {
+        self.serial_number.as_deref()
+    }
+
+    /// Get module voltage
+    pub fn voltage(&self) -> Option<f32> {
+        self.voltage
+    }
+
+    /// Check if the module supports ECC (None if unknown)
+    pub fn ecc(&self) -> Option<bool> {
+        self.ecc
+    }
+
+    /// Speed the module is actually running at
+    fn effective_speed_mhz(&self) -> u32 {
+        self.configured_speed_mhz
+            .filter(|speed| *speed > 0)
+            .unwrap_or(self.speed_mhz)
+    }
+}
+
+/// Error counters for a single DIMM (or chip-select row on older EDAC drivers)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EccDimmErrors {
+    /// Slot label as reported by the memory controller
+    pub label: String,
+    /// Corrected errors
+    pub correctable_errors: u64,
+    /// Uncorrected errors
+    pub uncorrectable_errors: u64,
+}
+
+/// ECC state and error counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EccStatus {
+    /// ECC is active on the memory controller
+    pub enabled: bool,
+    /// Correction mode (e.g. "SECDED", "Multi-bit ECC")
+    pub mode: Option<String>,
+    /// Corrected errors since boot, if the platform counts them
+    pub correctable_errors: Option<u64>,
+    /// Uncorrected errors since boot, if the platform counts them
+    pub uncorrectable_errors: Option<u64>,
+    /// Per-DIMM counters (Linux EDAC only)
+    pub dimms: Vec<EccDimmErrors>,
+}
+
+impl EccStatus {
+    /// Read the current ECC state and error counters
+    ///
+    /// Linux reads the EDAC counters under `/sys/devices/system/edac/mc`.
+    /// Windows reports the correction mode from `Win32_PhysicalMemoryArray`
+    /// and counts corrected memory errors logged by WHEA; uncorrectable errors
+    /// halt the machine there and are not counted.
+    pub fn query() -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            Self::query_linux(std::path::Path::new("/sys/devices/system/edac/mc"))
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            Self::query_windows()
+        }
+
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        {
+            Self::default()
+        }
+    }
+
+    /// Check if ECC is enabled
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Get the correction mode
+    pub fn mode(&self) -> Option<&str> {
+        self.mode.as_deref()
+    }
+
+    /// Get corrected error count
+    pub fn correctable_errors(&self) -> Option<u64> {
+        self.correctable_errors
+    }
+
+    /// Get uncorrected error count
+    pub fn uncorrectable_errors(&self) -> Option<u64> {
+        self.uncorrectable_errors
+    }
+
+    /// Get per-DIMM error counters
+    pub fn dimms(&self) -> &[EccDimmErrors] {
+        &self.dimms
+    }
+
+    /// Check if any uncorrected error has been recorded
+    pub fn has_uncorrectable_errors(&self) -> bool {
+        self.uncorrectable_errors.is_some_and(|count| count > 0)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn query_linux(edac_root: &std::path::Path) -> Self {
+        use std::fs;
+
+        let read = |path: std::path::PathBuf| {
+            fs::read_to_string(path)
+                .ok()
+                .map(|value| value.trim().to_string())
+        };
+        let count = |path: std::path::PathBuf| read(path).and_then(|value| value.parse::<u64>().ok());
+
+        let Ok(entries) = fs::read_dir(edac_root) else {
+            return Self::default();
+        };
+
+        let mut status = Self::default();
+        let mut controllers = 0;
+        for entry in entries.flatten() {
+            let controller = entry.path();
+            if !entry.file_name().to_string_lossy().starts_with("mc") {
+                continue;
+            }
+            controllers += 1;
+
+            if let Some(errors) = count(controller.join("ce_count")) {
+                *status.correctable_errors.get_or_insert(0) += errors;
+            }
+            if let Some(errors) = count(controller.join("ue_count")) {
+                *status.uncorrectable_errors.get_or_insert(0) += errors;
+            }
+
+            // dimmN/ on current kernels, csrowN/ on drivers predating the DIMM API
+            let Ok(ranks) = fs::read_dir(&controller) else {
+                continue;
+            };
+            for rank in ranks.flatten() {
+                let name = rank.file_name().to_string_lossy().into_owned();
+                let path = rank.path();
+                let (prefix, label_file) = if name.starts_with("dimm") || name.starts_with("rank") {
+                    ("dimm_", "dimm_label")
+                } else if name.starts_with("csrow") {
+                    ("", "ch0_dimm_label")
+                } else {
+                    continue;
+                };
+
+                let Some(correctable_errors) = count(path.join(format!("{prefix}ce_count"))) else {
+                    continue;
+                };
+                if status.mode.is_none() {
+                    status.mode = read(path.join(format!("{prefix}edac_mode")))
+                        .or_else(|| read(path.join("edac_mode")))
+                        .filter(|mode| mode != "Unknown");
+                }
+                status.dimms.push(EccDimmErrors {
+                    label: read(path.join(label_file))
+                        .filter(|label| !label.is_empty())
+                        .unwrap_or_else(|| format!("{}/{name}", entry.file_name().to_string_lossy())),
+                    correctable_errors,
+                    uncorrectable_errors: count(path.join(format!("{prefix}ue_count"))).unwrap_or(0),
+                });
+            }
+        }
+
+        // EDAC drivers only register controllers running with ECC
+        status.enabled = controllers > 0 && status.mode.as_deref() != Some("None");
+        status.dimms.sort_by(|a, b| a.label.cmp(&b.label));
+        status
+    }
+
+    #[cfg(target_os = "windows")]
+    fn query_windows() -> Self {
+        use std::collections::HashMap;
+        use wmi::{Variant, WMIConnection};
+
+        let Ok(wmi_con) = crate::probe::com_library().and_then(|com| Ok(WMIConnection::new(com)?)) else {
+            return Self::default();
+        };
+
+        let mut status = Self::default();
+        let arrays: Vec<HashMap<String, Variant>> = wmi_con
+            .raw_query("SELECT MemoryErrorCorrection FROM Win32_PhysicalMemoryArray")
+            .unwrap_or_default();
+        let mode = arrays.iter().find_map(|array| match array.get("MemoryErrorCorrection") {
+            Some(Variant::UI2(code)) => Some(*code),
+            _ => None,
+        });
+        if let Some(code) = mode {
+            status.enabled = matches!(code, 5..=7);
+            status.mode = match code {
+                3 => Some("None".to_string()),
+                4 => Some("Parity".to_string()),
+                5 => Some("Single-bit ECC".to_string()),
+                6 => Some("Multi-bit ECC".to_string()),
+                7 => Some("CRC".to_string()),
+                _ => None,
+            };
+        }
+
+        // WHEA logs event 47 for every corrected memory error
+        if status.enabled {
+            let events: Vec<HashMap<String, Variant>> = wmi_con
+                .raw_query(
+                    "SELECT RecordNumber FROM Win32_NTLogEvent WHERE Logfile = 'System' \
+                     AND SourceName = 'Microsoft-Windows-WHEA-Logger' AND EventCode = 47",
+                )
+                .unwrap_or_default();
+            status.correctable_errors = Some(events.len() as u64);
+        }
+
+        status
+    }
 }
 
 /// System memory information
@@ -88,21 +384,55 @@ impl MemoryInfo {
         let swap_total_mb = system.total_swap() / (1024 * 1024);
         let swap_used_mb = system.used_swap() / (1024 * 1024);
 
+        let modules = Self::detect_memory_modules();
+        let channels = Self::detect_memory_channels()?;
+        let speed_mhz = Self::detect_memory_speed(&modules);
+
         Ok(Self {
             total_mb,
             available_mb,
             used_mb,
             usage_percent,
-            modules: Self::detect_memory_modules()?,
-            channels: Self::detect_memory_channels()?,
-            ecc_support: Self::detect_ecc_support()?,
-            speed_mhz: Self::detect_memory_speed()?,
-            bandwidth_gb_s: Self::calculate_bandwidth(),
+            ecc_support: Self::detect_ecc_support(&modules),
+            bandwidth_gb_s: Self::calculate_bandwidth(speed_mhz, channels),
+            modules,
+            channels,
+            speed_mhz,
             swap_total_mb,
             swap_used_mb,
         })
     }
 
+    /// Re-read memory and swap usage, keeping module and channel details
+    pub(crate) fn sample_usage(&mut self) {
+        let mut system = System::new();
+        system.refresh_memory();
+
+        self.total_mb = system.total_memory() / (1024 * 1024);
+        self.available_mb = system.available_memory() / (1024 * 1024);
+        self.used_mb = system.used_memory() / (1024 * 1024);
+        self.usage_percent = (self.used_mb as f32 / self.total_mb as f32) * 100.0;
+        self.swap_total_mb = system.total_swap() / (1024 * 1024);
+        self.swap_used_mb = system.used_swap() / (1024 * 1024);
+    }
+
+    /// Placeholder used when memory detection did not complete
+    pub(crate) fn unknown() -> Self {
+        Self {
+            total_mb: 0,
+            available_mb: 0,
+            used_mb: 0,
+            usage_percent: 0.0,
+            modules: Vec::new(),
+            channels: 0,
+            ecc_support: false,
+            speed_mhz: 0,
+            bandwidth_gb_s: None,
+            swap_total_mb: 0,
+            swap_used_mb: 0,
+        }
+    }
+
     /// Get total memory in GB
     pub fn total_gb(&self) -> f64 {
         self.total_mb as f64 / 1024.0
@@ -133,6 +463,21 @@ impl MemoryInfo {
         self.used_mb
     }
 
+    /// Get total memory in bytes (MiB granularity)
+    pub fn total_bytes(&self) -> u64 {
+        self.total_mb * MIB
+    }
+
+    /// Get available memory in bytes (MiB granularity)
+    pub fn available_bytes(&self) -> u64 {
+        self.available_mb * MIB
+    }
+
+    /// Get used memory in bytes (MiB granularity)
+    pub fn used_bytes(&self) -> u64 {
+        self.used_mb * MIB
+    }
+
     /// Get memory usage percentage
     pub fn usage_percent(&self) -> f32 {
         self.usage_percent
@@ -158,6 +503,19 @@ impl MemoryInfo {
         self.speed_mhz
     }
 
+    /// Read the current ECC state and error counters
+    ///
+    /// Counters are read live rather than cached, so repeated calls observe new
+    /// errors. Falls back to the module-level ECC support when the platform
+    /// exposes no controller state.
+    pub fn ecc_status(&self) -> EccStatus {
+        let mut status = EccStatus::query();
+        if status.mode.is_none() {
+            status.enabled |= self.ecc_support;
+        }
+        status
+    }
+
     /// Get memory bandwidth in GB/s
     pub fn bandwidth_gb_s(&self) -> Option<f32> {
         self.bandwidth_gb_s
@@ -173,23 +531,94 @@ impl MemoryInfo {
         self.swap_used_mb as f64 / 1024.0
     }
 
+    /// Get swap total in bytes (MiB granularity)
+    pub fn swap_total_bytes(&self) -> u64 {
+        self.swap_total_mb * MIB
+    }
+
+    /// Get swap used in bytes (MiB granularity)
+    pub fn swap_used_bytes(&self) -> u64 {
+        self.swap_used_mb * MIB
+    }
+
     /// Check if system has sufficient memory for a workload
     pub fn has_sufficient_memory(&self, required_gb: f64) -> bool {
         self.available_gb() >= required_gb
     }
 
-    fn detect_memory_modules() -> Result<Vec<MemoryModule>> {
-        // Platform-specific implementation would go here
-        // For now, return a placeholder module
-        Ok(vec![MemoryModule {
-            size_mb: 8192,
-            memory_type: MemoryType::DDR4,
-            speed_mhz: 3200,
-            manufacturer: Some("Unknown".to_string()),
-            part_number: None,
-            slot: Some("DIMM1".to_string()),
-            voltage: Some(1.35),
-        }])
+    fn detect_memory_modules() -> Vec<MemoryModule> {
+        #[cfg(target_os = "linux")]
+        {
+            crate::platform::LinuxMemoryInfo::get_memory_modules()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|module| MemoryModule {
+                    size_mb: module.size_mb,
+                    memory_type: MemoryType::from_name(&module.memory_type),
+                    speed_mhz: module.speed_mhz.unwrap_or(0),
+                    ecc: module.has_ecc(),
+                    manufacturer: module.manufacturer,
+                    part_number: module.part_number,
+                    slot: Some(module.locator).filter(|locator| !locator.is_empty()),
+                    voltage: module.voltage,
+                    configured_speed_mhz: module.configured_speed_mhz,
+                    bank: module.bank_locator,
+                    serial_number: module.serial_number,
+                })
+                .collect()
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            let modules = crate::probe::com_library().and_then(|com| {
+                crate::platform::WindowsMemoryInfo::query_modules(&wmi::WMIConnection::new(com)?)
+            });
+            modules
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|module| module.capacity_mb > 0)
+                .map(|module| MemoryModule {
+                    size_mb: module.capacity_mb,
+                    memory_type: MemoryType::from_name(&module.memory_type),
+                    speed_mhz: module.speed_mhz,
+                    ecc: module.has_ecc(),
+                    manufacturer: module.manufacturer,
+                    part_number: module.part_number,
+                    slot: module.device_locator,
+                    voltage: module.voltage,
+                    configured_speed_mhz: module.configured_speed_mhz,
+                    bank: module.bank_label,
+                    serial_number: module.serial_number,
+                })
+                .collect()
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let non_empty = |value: String| Some(value).filter(|v| !v.is_empty());
+            crate::platform::MacOSMemoryInfo::get_memory_modules()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|module| MemoryModule {
+                    size_mb: module.size_mb,
+                    memory_type: MemoryType::from_name(&module.memory_type),
+                    speed_mhz: module.speed_mhz,
+                    ecc: module.ecc,
+                    manufacturer: non_empty(module.manufacturer),
+                    part_number: non_empty(module.part_number),
+                    slot: non_empty(module.slot),
+                    voltage: None,
+                    configured_speed_mhz: None,
+                    bank: None,
+                    serial_number: non_empty(module.serial_number),
+                })
+                .collect()
+        }
+
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            Vec::new()
+        }
     }
 
     fn detect_memory_channels() -> Result<u32> {
@@ -197,19 +626,27 @@ impl MemoryInfo {
         Ok(2) // Assume dual channel
     }
 
-    fn detect_ecc_support() -> Result<bool> {
-        // Platform-specific implementation would go here
-        Ok(false)
+    /// ECC is reported only when every detected module carries check bits
+    fn detect_ecc_support(modules: &[MemoryModule]) -> bool {
+        !modules.is_empty() && modules.iter().all(|module| module.ecc == Some(true))
     }
 
-    fn detect_memory_speed() -> Result<u32> {
-        // Platform-specific implementation would go here
-        Ok(3200)
+    /// Slowest module speed, since all channels run at the lowest common speed
+    fn detect_memory_speed(modules: &[MemoryModule]) -> u32 {
+        modules
+            .iter()
+            .map(MemoryModule::effective_speed_mhz)
+            .filter(|speed| *speed > 0)
+            .min()
+            .unwrap_or(0)
     }
 
-    fn calculate_bandwidth() -> Option<f32> {
-        // Calculate theoretical bandwidth
-        // For DDR4-3200 dual channel: 3200 MHz * 2 channels * 8 bytes = 51.2 GB/s
-        Some(51.2)
+    fn calculate_bandwidth(speed_mhz: u32, channels: u32) -> Option<f32> {
+        // Theoretical bandwidth: transfers/s * channels * 8 bytes per 64-bit channel
+        // (DDR4-3200 dual channel: 3200 * 2 * 8 = 51.2 GB/s)
+        if speed_mhz == 0 || channels == 0 {
+            return None;
+        }
+        Some(speed_mhz as f32 * channels as f32 * 8.0 / 1000.0)
     }
 }
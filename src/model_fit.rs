@@ -0,0 +1,643 @@
+//! Quantization-aware planning of where a language model fits
+//!
+//! Given a model's parameter count, weight format and context length, the
+//! planner estimates the memory needed for the weights and the KV cache and
+//! matches it against the detected GPUs, unified memory and system RAM. Decode
+//! speed of a transformer is bound by memory bandwidth (every weight is read
+//! once per generated token), so the tokens/sec estimate is derived from the
+//! bandwidth of wherever the weights end up.
+
+use crate::{CPUFeature, GPUInfo, GPUType, GPUVendor, HardwareInfo, HardwareQueryError, Result};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Weight storage format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Quantization {
+    /// 32-bit float
+    FP32,
+    /// 16-bit float
+    FP16,
+    /// bfloat16
+    BF16,
+    /// 8-bit float (E4M3/E5M2)
+    FP8,
+    /// 8-bit integer weights (LLM.int8, Q8_0)
+    INT8,
+    /// 6-bit block quantization (Q6_K)
+    Q6,
+    /// 5-bit block quantization (Q5_K_M)
+    Q5,
+    /// 4-bit block quantization (Q4_K_M, GPTQ, AWQ)
+    Q4,
+    /// 3-bit block quantization (Q3_K_M)
+    Q3,
+    /// 2-bit block quantization (Q2_K)
+    Q2,
+}
+
+impl Quantization {
+    /// Every format, highest precision first
+    pub const ALL: [Quantization; 10] = [
+        Quantization::FP32,
+        Quantization::FP16,
+        Quantization::BF16,
+        Quantization::FP8,
+        Quantization::INT8,
+        Quantization::Q6,
+        Quantization::Q5,
+        Quantization::Q4,
+        Quantization::Q3,
+        Quantization::Q2,
+    ];
+
+    /// Average storage per weight in bits, including block scales
+    pub fn bits_per_weight(self) -> f64 {
+        match self {
+            Quantization::FP32 => 32.0,
+            Quantization::FP16 | Quantization::BF16 => 16.0,
+            Quantization::FP8 => 8.0,
+            Quantization::INT8 => 8.5,
+            Quantization::Q6 => 6.6,
+            Quantization::Q5 => 5.7,
+            Quantization::Q4 => 4.8,
+            Quantization::Q3 => 3.9,
+            Quantization::Q2 => 3.0,
+        }
+    }
+}
+
+impl std::fmt::Display for Quantization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Quantization::FP32 => write!(f, "FP32"),
+            Quantization::FP16 => write!(f, "FP16"),
+            Quantization::BF16 => write!(f, "BF16"),
+            Quantization::FP8 => write!(f, "FP8"),
+            Quantization::INT8 => write!(f, "INT8"),
+            Quantization::Q6 => write!(f, "Q6"),
+            Quantization::Q5 => write!(f, "Q5"),
+            Quantization::Q4 => write!(f, "Q4"),
+            Quantization::Q3 => write!(f, "Q3"),
+            Quantization::Q2 => write!(f, "Q2"),
+        }
+    }
+}
+
+impl FromStr for Quantization {
+    type Err = HardwareQueryError;
+
+    /// Parse names such as `fp16`, `bf16`, `int8`, `q8_0`, `q4_k_m` or `4bit`
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.trim().to_ascii_lowercase().replace(['-', ' '], "_");
+        let quantization = match name.as_str() {
+            "fp32" | "f32" | "float32" => Quantization::FP32,
+            "fp16" | "f16" | "float16" | "half" => Quantization::FP16,
+            "bf16" | "bfloat16" => Quantization::BF16,
+            "fp8" | "f8" | "e4m3" | "e5m2" => Quantization::FP8,
+            "int8" | "i8" | "8bit" => Quantization::INT8,
+            "int4" | "i4" | "4bit" | "gptq" | "awq" | "nf4" => Quantization::Q4,
+            _ => match name.strip_prefix('q').and_then(|rest| rest.chars().next()) {
+                Some('8') => Quantization::INT8,
+                Some('6') => Quantization::Q6,
+                Some('5') => Quantization::Q5,
+                Some('4') => Quantization::Q4,
+                Some('3') => Quantization::Q3,
+                Some('2') => Quantization::Q2,
+                _ => {
+                    return Err(HardwareQueryError::invalid_configuration(format!(
+                        "Unknown quantization: {s}"
+                    )));
+                }
+            },
+        };
+        Ok(quantization)
+    }
+}
+
+/// Where a model's weights and KV cache are placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModelPlacement {
+    /// Entirely in the VRAM of one GPU
+    Gpu,
+    /// Split across the VRAM of several GPUs
+    MultiGpu,
+    /// In memory shared between CPU and GPU (Apple Silicon)
+    UnifiedMemory,
+    /// Some layers in VRAM, the rest in system RAM
+    PartialOffload,
+    /// Entirely in system RAM, run on the CPU
+    Cpu,
+    /// Larger than all usable memory
+    DoesNotFit,
+}
+
+impl std::fmt::Display for ModelPlacement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            ModelPlacement::Gpu => "GPU",
+            ModelPlacement::MultiGpu => "Multi-GPU",
+            ModelPlacement::UnifiedMemory => "Unified memory",
+            ModelPlacement::PartialOffload => "Partial GPU offload",
+            ModelPlacement::Cpu => "CPU",
+            ModelPlacement::DoesNotFit => "Does not fit",
+        };
+        f.write_str(&crate::l10n::localize("ModelPlacement", text))
+    }
+}
+
+/// Rough single-stream generation speed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ThroughputClass {
+    /// Does not run, or under 2 tokens/sec
+    Impractical,
+    /// 2-10 tokens/sec, fine for batch jobs
+    Slow,
+    /// 10-30 tokens/sec, faster than reading speed
+    Interactive,
+    /// 30+ tokens/sec
+    Fast,
+}
+
+impl ThroughputClass {
+    /// Classify an estimated tokens/sec rate
+    pub fn from_tokens_per_second(tokens_per_second: f64) -> Self {
+        if tokens_per_second >= 30.0 {
+            ThroughputClass::Fast
+        } else if tokens_per_second >= 10.0 {
+            ThroughputClass::Interactive
+        } else if tokens_per_second >= 2.0 {
+            ThroughputClass::Slow
+        } else {
+            ThroughputClass::Impractical
+        }
+    }
+
+    /// Classify an estimated prompt processing (prefill) rate
+    ///
+    /// Prefill is compute bound and far faster than generation: `Fast` reads a
+    /// 4096-token prompt in about 4 seconds or less, `Impractical` takes over a
+    /// minute.
+    pub fn from_prefill_tokens_per_second(tokens_per_second: f64) -> Self {
+        Self::from_tokens_per_second(tokens_per_second / 33.0)
+    }
+}
+
+impl std::fmt::Display for ThroughputClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            ThroughputClass::Fast => "Fast",
+            ThroughputClass::Interactive => "Interactive",
+            ThroughputClass::Slow => "Slow",
+            ThroughputClass::Impractical => "Impractical",
+        };
+        f.write_str(&crate::l10n::localize("ThroughputClass", text))
+    }
+}
+
+/// Result of planning a model against the detected hardware
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelFit {
+    /// Weight format that was planned
+    pub quantization: Quantization,
+    /// Memory for the weights in GB
+    pub weights_gb: f64,
+    /// Memory for the KV cache at the full context length in GB
+    pub kv_cache_gb: f64,
+    /// Total memory needed, including runtime buffers, in GB
+    pub memory_required_gb: f64,
+    /// Where the model would run
+    pub placement: ModelPlacement,
+    /// Model fits entirely in GPU memory (VRAM or unified memory)
+    pub fits_in_vram: bool,
+    /// Model fits entirely in available system RAM
+    pub fits_in_system_memory: bool,
+    /// Usable memory on the largest GPU in GB
+    pub gpu_memory_gb: f64,
+    /// Available system RAM (respecting container limits) in GB
+    pub system_memory_gb: f64,
+    /// Share of the model placed in GPU memory (0.0 - 1.0)
+    pub gpu_fraction: f64,
+    /// Estimated single-stream generation speed
+    pub estimated_tokens_per_second: Option<f64>,
+    /// Speed class of the estimate
+    pub throughput: ThroughputClass,
+    /// Formats that fit the fastest available placement, highest precision first
+    pub suggested_quantizations: Vec<Quantization>,
+    /// Explanations and hints
+    pub notes: Vec<String>,
+}
+
+impl ModelFit {
+    /// Get weight format
+    pub fn quantization(&self) -> Quantization {
+        self.quantization
+    }
+
+    /// Get total memory required in GB
+    pub fn memory_required_gb(&self) -> f64 {
+        self.memory_required_gb
+    }
+
+    /// Get placement
+    pub fn placement(&self) -> ModelPlacement {
+        self.placement
+    }
+
+    /// Check if the model fits in GPU memory
+    pub fn fits_in_vram(&self) -> bool {
+        self.fits_in_vram
+    }
+
+    /// Check if the model fits in system RAM
+    pub fn fits_in_system_memory(&self) -> bool {
+        self.fits_in_system_memory
+    }
+
+    /// Check if the model can run at all
+    pub fn is_runnable(&self) -> bool {
+        self.placement != ModelPlacement::DoesNotFit
+    }
+
+    /// Get estimated tokens/sec
+    pub fn estimated_tokens_per_second(&self) -> Option<f64> {
+        self.estimated_tokens_per_second
+    }
+
+    /// Get speed class
+    pub fn throughput(&self) -> ThroughputClass {
+        self.throughput
+    }
+
+    /// Get suggested formats
+    pub fn suggested_quantizations(&self) -> &[Quantization] {
+        &self.suggested_quantizations
+    }
+
+    /// Get notes
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+}
+
+/// Plans whether a model fits the detected GPUs, unified memory or system RAM
+///
+/// ```no_run
+/// use hardware_query::{ModelFitPlanner, Quantization};
+///
+/// # fn main() -> hardware_query::Result<()> {
+/// let fit = ModelFitPlanner::from_billions(8.0)
+///     .quantization(Quantization::Q4)
+///     .context_length(8192)
+///     .plan()?;
+/// println!("{} on {}, ~{:?} tok/s", fit.quantization(), fit.placement(), fit.estimated_tokens_per_second());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelFitPlanner {
+    parameters: u64,
+    quantization: Quantization,
+    context_length: u32,
+    kv_cache_bytes_per_token: Option<u64>,
+    #[serde(default)]
+    layers: Option<u32>,
+}
+
+impl ModelFitPlanner {
+    /// Plan a model with `parameters` weights, in FP16 with a 4096-token context
+    pub fn new(parameters: u64) -> Self {
+        Self {
+            parameters,
+            quantization: Quantization::FP16,
+            context_length: 4096,
+            kv_cache_bytes_per_token: None,
+            layers: None,
+        }
+    }
+
+    /// Plan a model with `billions` × 10⁹ weights
+    pub fn from_billions(billions: f64) -> Self {
+        Self::new((billions.max(0.0) * 1e9) as u64)
+    }
+
+    /// Set the weight format
+    pub fn quantization(mut self, quantization: Quantization) -> Self {
+        self.quantization = quantization;
+        self
+    }
+
+    /// Set the context length in tokens
+    pub fn context_length(mut self, tokens: u32) -> Self {
+        self.context_length = tokens;
+        self
+    }
+
+    /// Set the exact KV cache size per token, overriding the estimate
+    ///
+    /// This is `2 × layers × kv_heads × head_dim × bytes per element`, e.g.
+    /// 131072 for Llama 3 8B with an FP16 cache.
+    pub fn kv_cache_bytes_per_token(mut self, bytes: u64) -> Self {
+        self.kv_cache_bytes_per_token = Some(bytes);
+        self
+    }
+
+    /// Set the number of transformer blocks, overriding the estimate
+    pub fn layers(mut self, layers: u32) -> Self {
+        self.layers = Some(layers);
+        self
+    }
+
+    /// Number of transformer blocks
+    ///
+    /// Without an explicit count this uses the same Llama-style estimate as
+    /// [`kv_cache_gb`](Self::kv_cache_gb): one block per 128 of model width.
+    pub fn layer_count(&self) -> u32 {
+        self.layers.unwrap_or_else(|| {
+            let width = (self.parameters as f64 * 128.0 / 12.0).cbrt();
+            ((width / 128.0).round() as u32).max(1)
+        })
+    }
+
+    /// Short description such as `8B Q4`
+    pub(crate) fn label(&self) -> String {
+        let billions = self.parameters as f64 / 1e9;
+        if billions.fract() == 0.0 {
+            format!("{billions:.0}B {}", self.quantization)
+        } else {
+            format!("{billions:.1}B {}", self.quantization)
+        }
+    }
+
+    /// Memory for the weights in GB
+    pub fn weights_gb(&self) -> f64 {
+        Self::weights_gb_for(self.parameters, self.quantization)
+    }
+
+    /// Memory for the KV cache at the full context length in GB
+    ///
+    /// Without an explicit size the cache is estimated for a Llama-style
+    /// architecture (width ≈ ∛(params × 128 / 12), one layer per 128 of width)
+    /// using grouped-query attention with 4 query heads per KV head and an
+    /// FP16 cache.
+    pub fn kv_cache_gb(&self) -> f64 {
+        let per_token = self.kv_cache_bytes_per_token.unwrap_or_else(|| {
+            let width = (self.parameters as f64 * 128.0 / 12.0).cbrt();
+            // 2 (K and V) × layers (width / 128) × width / 4 (GQA) × 2 bytes
+            (width * width / 128.0) as u64
+        });
+        per_token as f64 * self.context_length as f64 / 1e9
+    }
+
+    /// Total memory needed in GB: weights, KV cache, and runtime buffers
+    /// (0.5 GB plus 5% of the weights)
+    pub fn memory_required_gb(&self) -> f64 {
+        Self::memory_required_gb_for(self.weights_gb(), self.kv_cache_gb())
+    }
+
+    /// Detect the hardware and plan the model against it
+    pub fn plan(&self) -> Result<ModelFit> {
+        Ok(self.plan_for(&HardwareInfo::query()?))
+    }
+
+    /// Plan the model against already-detected hardware
+    pub fn plan_for(&self, hardware: &HardwareInfo) -> ModelFit {
+        let budget = MemoryBudget::from_hardware(hardware);
+        let kv_cache_gb = self.kv_cache_gb();
+        let mut fit = self.fit(&budget, self.quantization, kv_cache_gb);
+
+        // Suggest the highest-precision formats that reach the fastest placement any format reaches
+        let placements: Vec<(Quantization, u8)> = Quantization::ALL
+            .into_iter()
+            .filter(|q| *q != Quantization::FP32 && budget.supports(*q))
+            .map(|q| (q, placement_rank(self.fit(&budget, q, kv_cache_gb).placement)))
+            .collect();
+        let best_rank = placements.iter().map(|(_, rank)| *rank).min();
+        if let Some(best_rank) = best_rank.filter(|rank| *rank < placement_rank(ModelPlacement::DoesNotFit)) {
+            fit.suggested_quantizations = placements
+                .into_iter()
+                .filter(|(_, rank)| *rank == best_rank)
+                .map(|(q, _)| q)
+                .take(3)
+                .collect();
+        }
+
+        if !budget.supports(self.quantization) {
+            fit.notes.push(format!(
+                "{} is not natively supported by the detected hardware and will be emulated or converted",
+                self.quantization
+            ));
+        }
+        let gpu_memory_gb = budget.largest_gpu_gb.max(budget.unified_gb);
+        if !fit.fits_in_vram && Self::memory_required_gb_for(fit.weights_gb, 0.0) <= gpu_memory_gb {
+            fit.notes.push(format!(
+                "Weights fit in GPU memory but the {kv_cache_gb:.1} GB KV cache does not; \
+                 a shorter context would avoid offloading"
+            ));
+        }
+        if !fit.fits_in_vram {
+            if let Some(npu) = hardware.npus().first() {
+                fit.notes.push(format!(
+                    "{} can run INT8/Q4 models through its vendor runtime (OpenVINO, QNN, Core ML)",
+                    npu.model_name
+                ));
+            }
+        }
+        if fit.placement == ModelPlacement::Cpu && !budget.cpu_has_vnni {
+            fit.notes.push("CPU lacks AVX-512 VNNI / AVX-VNNI, so quantized matrix math runs slower".to_string());
+        }
+        fit
+    }
+
+    fn weights_gb_for(parameters: u64, quantization: Quantization) -> f64 {
+        parameters as f64 * quantization.bits_per_weight() / 8.0 / 1e9
+    }
+
+    fn memory_required_gb_for(weights_gb: f64, kv_cache_gb: f64) -> f64 {
+        weights_gb + kv_cache_gb + 0.5 + weights_gb * 0.05
+    }
+
+    fn fit(&self, budget: &MemoryBudget, quantization: Quantization, kv_cache_gb: f64) -> ModelFit {
+        let weights_gb = Self::weights_gb_for(self.parameters, quantization);
+        let required = Self::memory_required_gb_for(weights_gb, kv_cache_gb);
+
+        let (placement, gpu_fraction) = if budget.largest_gpu_gb >= required {
+            (ModelPlacement::Gpu, 1.0)
+        } else if budget.unified_gb >= required {
+            (ModelPlacement::UnifiedMemory, 1.0)
+        } else if budget.gpu_count > 1 && budget.gpu_total_gb >= required {
+            (ModelPlacement::MultiGpu, 1.0)
+        } else if budget.gpu_total_gb > 0.0 && budget.gpu_total_gb + budget.system_gb >= required {
+            (ModelPlacement::PartialOffload, budget.gpu_total_gb / required)
+        } else if budget.system_gb >= required {
+            (ModelPlacement::Cpu, 0.0)
+        } else {
+            (ModelPlacement::DoesNotFit, 0.0)
+        };
+
+        // Each generated token reads every weight plus the KV cache once
+        let bytes_per_token_gb = weights_gb + kv_cache_gb;
+        let seconds_per_token = match placement {
+            ModelPlacement::Gpu => Some(bytes_per_token_gb / budget.largest_gpu_bandwidth),
+            ModelPlacement::MultiGpu => Some(bytes_per_token_gb / budget.slowest_gpu_bandwidth),
+            ModelPlacement::UnifiedMemory => Some(bytes_per_token_gb / budget.unified_bandwidth),
+            ModelPlacement::PartialOffload => Some(
+                bytes_per_token_gb * gpu_fraction / budget.slowest_gpu_bandwidth
+                    + bytes_per_token_gb * (1.0 - gpu_fraction) / budget.system_bandwidth,
+            ),
+            ModelPlacement::Cpu => Some(bytes_per_token_gb / budget.system_bandwidth),
+            ModelPlacement::DoesNotFit => None,
+        };
+        let estimated_tokens_per_second = seconds_per_token
+            .filter(|seconds| *seconds > 0.0)
+            .map(|seconds| (1.0 / seconds * 10.0).round() / 10.0);
+
+        ModelFit {
+            quantization,
+            weights_gb,
+            kv_cache_gb,
+            memory_required_gb: required,
+            placement,
+            fits_in_vram: matches!(
+                placement,
+                ModelPlacement::Gpu | ModelPlacement::MultiGpu | ModelPlacement::UnifiedMemory
+            ),
+            fits_in_system_memory: budget.system_gb >= required,
+            gpu_memory_gb: budget.largest_gpu_gb.max(budget.unified_gb),
+            system_memory_gb: budget.system_gb,
+            gpu_fraction,
+            estimated_tokens_per_second,
+            throughput: estimated_tokens_per_second
+                .map(ThroughputClass::from_tokens_per_second)
+                .unwrap_or(ThroughputClass::Impractical),
+            suggested_quantizations: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+}
+
+/// Fastest placements first
+fn placement_rank(placement: ModelPlacement) -> u8 {
+    match placement {
+        ModelPlacement::Gpu => 0,
+        ModelPlacement::UnifiedMemory => 1,
+        ModelPlacement::MultiGpu => 2,
+        ModelPlacement::PartialOffload => 3,
+        ModelPlacement::Cpu => 4,
+        ModelPlacement::DoesNotFit => 5,
+    }
+}
+
+/// Usable memory and effective bandwidth (GB/s) of each place a model can live
+#[derive(Debug, Default)]
+struct MemoryBudget {
+    gpu_count: usize,
+    largest_gpu_gb: f64,
+    largest_gpu_bandwidth: f64,
+    gpu_total_gb: f64,
+    slowest_gpu_bandwidth: f64,
+    unified_gb: f64,
+    unified_bandwidth: f64,
+    system_gb: f64,
+    system_bandwidth: f64,
+    cuda_capability: Option<f64>,
+    rocm: bool,
+    metal: bool,
+    cpu_has_bf16: bool,
+    cpu_has_vnni: bool,
+}
+
+impl MemoryBudget {
+    fn from_hardware(hardware: &HardwareInfo) -> Self {
+        // Sustained bandwidth during decode is well below the theoretical peak
+        const GPU_EFFICIENCY: f64 = 0.7;
+        const CPU_EFFICIENCY: f64 = 0.6;
+
+        let memory = hardware.memory();
+        let mut system_gb = memory.available_gb();
+        if let Some(limit) = hardware.virtualization().resource_limits.memory_limit_bytes() {
+            system_gb = system_gb.min(limit as f64 / (1024.0 * 1024.0 * 1024.0));
+        }
+        let system_bandwidth = memory.bandwidth_gb_s().map(f64::from).unwrap_or(50.0) * CPU_EFFICIENCY;
+
+        let mut budget = Self {
+            system_gb,
+            system_bandwidth,
+            ..Self::default()
+        };
+
+        let gpu_bandwidth = |gpu: &GPUInfo| {
+            let peak = gpu.memory_bandwidth.map(f64::from).unwrap_or(match gpu.gpu_type {
+                GPUType::Datacenter => 2000.0,
+                GPUType::Workstation => 700.0,
+                GPUType::Discrete => 450.0,
+                _ => 100.0,
+            });
+            peak * GPU_EFFICIENCY
+        };
+
+        for gpu in hardware.gpus() {
+            budget.rocm |= gpu.supports_rocm();
+            budget.metal |= gpu.supports_metal();
+            if let Some(capability) = gpu.cuda_capability().and_then(|c| c.parse::<f64>().ok()) {
+                budget.cuda_capability = Some(budget.cuda_capability.unwrap_or(0.0).max(capability));
+            }
+
+            // Apple GPUs address most of system memory; other integrated GPUs are no faster than the CPU
+            if gpu.has_unified_memory() || gpu.gpu_type == GPUType::Integrated {
+                if gpu.vendor == GPUVendor::Apple {
+                    budget.unified_gb = memory.total_gb() * 0.75;
+                    budget.unified_bandwidth = gpu
+                        .memory_bandwidth
+                        .map(|peak| peak as f64 * GPU_EFFICIENCY)
+                        .unwrap_or(system_bandwidth.max(100.0 * GPU_EFFICIENCY));
+                }
+                continue;
+            }
+            if gpu.memory_mb == 0 || gpu.gpu_type == GPUType::Virtual {
+                continue;
+            }
+
+            // Drivers and the CUDA/ROCm context reserve part of the VRAM
+            let usable_gb = gpu.memory_gb() * 0.92;
+            let bandwidth = gpu_bandwidth(gpu);
+            budget.gpu_count += 1;
+            budget.gpu_total_gb += usable_gb;
+            if usable_gb > budget.largest_gpu_gb {
+                budget.largest_gpu_gb = usable_gb;
+                budget.largest_gpu_bandwidth = bandwidth;
+            }
+            budget.slowest_gpu_bandwidth = if budget.gpu_count == 1 {
+                bandwidth
+            } else {
+                budget.slowest_gpu_bandwidth.min(bandwidth)
+            };
+        }
+
+        let features = hardware.cpu().features();
+        budget.cpu_has_bf16 = features
+            .iter()
+            .any(|f| matches!(f, CPUFeature::AVX512BF16 | CPUFeature::AMXBF16));
+        budget.cpu_has_vnni = features
+            .iter()
+            .any(|f| matches!(f, CPUFeature::AVX512VNNI | CPUFeature::AVXVNNI | CPUFeature::AMXINT8))
+            || !cfg!(target_arch = "x86_64");
+        budget
+    }
+
+    /// Whether the fastest available device handles `quantization` natively
+    fn supports(&self, quantization: Quantization) -> bool {
+        let cuda_at_least = |version: f64| self.cuda_capability.is_some_and(|c| c >= version);
+        match quantization {
+            // Hopper and Ada tensor cores
+            Quantization::FP8 => cuda_at_least(8.9),
+            Quantization::BF16 => {
+                cuda_at_least(8.0)
+                    || self.rocm
+                    || self.metal
+                    || (self.gpu_count == 0 && self.unified_gb == 0.0 && self.cpu_has_bf16)
+            }
+            _ => true,
+        }
+    }
+}
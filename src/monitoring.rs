@@ -3,13 +3,21 @@
 //! This module provides continuous monitoring capabilities for hardware metrics,
 //! with configurable update intervals and event-driven notifications.
 
-use crate::{HardwareInfo, ThermalInfo, PowerProfile, Result, HardwareQueryError};
+use crate::history::{self, HistoryBuffer, Metric};
+use crate::rules::{AlertRule, RuleEngine};
+use crate::sampling::{AdaptiveSampling, SamplingReading};
+use crate::hotplug::{self, DeviceChange};
+use crate::{BatteryDrain, BatteryInfo, BatteryStatus, CPUFrequencyInfo, DiskIoCounters, DiskIoStats, EccStatus, GPUUsage, HardwareInfo, NetworkInfo, NetworkIoStats, NetworkType, PerformanceCounterSampler, PerformanceCounters, ThermalInfo, HardwareComponent, ThrottleCause, ThrottleEvent, ThrottleStatus, PowerProfile, PowerSource, PowerState, Result, HardwareQueryError};
 use async_trait::async_trait;
+use futures::stream::{self, Stream};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{broadcast, RwLock, Mutex};
-use tokio::time::interval;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc, watch, Mutex, Notify, RwLock};
+use tokio::task::JoinHandle;
+use tokio::time::{interval_at, MissedTickBehavior};
 
 /// Hardware monitoring configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,8 +36,133 @@ pub struct MonitoringConfig {
     pub power_threshold: Option<f32>,
     /// Enable background monitoring
     pub background_monitoring: bool,
+    /// Emit `HardwareChanged` events when USB, GPU or storage devices are connected or removed,
+    /// or removable media is inserted or ejected
+    #[serde(default = "default_enable_hotplug")]
+    pub enable_hotplug: bool,
+    /// Samples kept per metric in the monitor's [`HistoryBuffer`]
+    #[serde(default = "default_history_length")]
+    pub history_length: usize,
+    /// Threshold rules evaluated on every update
+    #[serde(default)]
+    pub rules: Vec<AlertRule>,
+    /// Emit `MemoryErrors` events when the uncorrectable ECC error count rises
+    #[serde(default = "default_enable_ecc")]
+    pub enable_ecc: bool,
+    /// Sample per-disk throughput, IOPS and queue depth
+    #[serde(default = "default_enable_disk_io")]
+    pub enable_disk_io: bool,
+    /// Utilization (percent) at which a disk counts as saturated
+    #[serde(default = "default_disk_saturation_percent")]
+    pub disk_saturation_percent: f64,
+    /// How long a disk must stay saturated before `DiskSaturated` is emitted
+    #[serde(default = "default_disk_saturation_duration")]
+    pub disk_saturation_duration: Duration,
+    /// Sample per-interface traffic counters and report link up/down transitions
+    #[serde(default = "default_enable_network_io")]
+    pub enable_network_io: bool,
+    /// Minimum time between network samples (`None` samples on every update)
+    #[serde(default)]
+    pub network_sample_interval: Option<Duration>,
+    /// Read CPU and GPU throttle counters and emit `ThrottlingDetected` when clocks are held down
+    #[serde(default = "default_enable_throttling")]
+    pub enable_throttling: bool,
+    /// Sample per-GPU utilization, VRAM, clocks and power draw
+    #[serde(default = "default_enable_gpu")]
+    pub enable_gpu: bool,
+    /// VRAM usage (percent) at which `VramExhausted` is emitted
+    #[serde(default = "default_vram_exhaustion_percent")]
+    pub vram_exhaustion_percent: f64,
+    /// Read CPU, disk and GPU engine usage from Windows performance counters (PDH),
+    /// replacing the sysinfo CPU usage and frequency figures; ignored elsewhere
+    #[serde(default = "default_enable_performance_counters")]
+    pub enable_performance_counters: bool,
+    /// Track battery charge and drain, and report charger and low-battery changes
+    #[serde(default = "default_enable_battery")]
+    pub enable_battery: bool,
+    /// Charge (percent) below which `LowBattery` is emitted while discharging
+    #[serde(default = "default_low_battery_percent")]
+    pub low_battery_percent: f32,
+    /// Charge (percent) below which `LowBattery` is emitted again as critical
+    #[serde(default = "default_critical_battery_percent")]
+    pub critical_battery_percent: f32,
+    /// Drain (percentage points per hour) above which `AbnormalBatteryDrain` is emitted
+    #[serde(default = "default_abnormal_drain_percent_per_hour")]
+    pub abnormal_drain_percent_per_hour: f64,
+    /// Stretch the interval while readings are stable and shorten it under load
+    /// (`None` always samples at `update_interval`)
+    #[serde(default)]
+    pub adaptive_sampling: Option<AdaptiveSampling>,
 }
 
+/// One hour of history at the default 5 second interval
+fn default_enable_hotplug() -> bool {
+    true
+}
+
+fn default_history_length() -> usize {
+    720
+}
+
+fn default_enable_ecc() -> bool {
+    true
+}
+
+fn default_enable_disk_io() -> bool {
+    true
+}
+
+fn default_disk_saturation_percent() -> f64 {
+    90.0
+}
+
+fn default_disk_saturation_duration() -> Duration {
+    Duration::from_secs(30)
+}
+
+fn default_enable_network_io() -> bool {
+    true
+}
+
+fn default_enable_throttling() -> bool {
+    true
+}
+
+fn default_enable_gpu() -> bool {
+    true
+}
+
+fn default_vram_exhaustion_percent() -> f64 {
+    95.0
+}
+
+fn default_enable_performance_counters() -> bool {
+    true
+}
+
+fn default_enable_battery() -> bool {
+    true
+}
+
+fn default_low_battery_percent() -> f32 {
+    20.0
+}
+
+fn default_critical_battery_percent() -> f32 {
+    5.0
+}
+
+/// A full battery lasting under two and a half hours
+fn default_abnormal_drain_percent_per_hour() -> f64 {
+    40.0
+}
+
+/// Readings older than this drop out of the battery drain window
+const BATTERY_DRAIN_WINDOW: Duration = Duration::from_secs(300);
+
+/// Shortest window a drain rate is computed over; charge is reported in whole percent
+const MIN_BATTERY_DRAIN_SPAN: Duration = Duration::from_secs(60);
+
 impl Default for MonitoringConfig {
     fn default() -> Self {
         Self {
@@ -40,6 +173,52 @@ impl Default for MonitoringConfig {
             thermal_threshold: 80.0,
             power_threshold: None,
             background_monitoring: true,
+            enable_hotplug: default_enable_hotplug(),
+            history_length: default_history_length(),
+            rules: Vec::new(),
+            enable_ecc: default_enable_ecc(),
+            enable_disk_io: default_enable_disk_io(),
+            disk_saturation_percent: default_disk_saturation_percent(),
+            disk_saturation_duration: default_disk_saturation_duration(),
+            enable_network_io: default_enable_network_io(),
+            network_sample_interval: None,
+            enable_throttling: default_enable_throttling(),
+            enable_gpu: default_enable_gpu(),
+            vram_exhaustion_percent: default_vram_exhaustion_percent(),
+            enable_performance_counters: default_enable_performance_counters(),
+            enable_battery: default_enable_battery(),
+            low_battery_percent: default_low_battery_percent(),
+            critical_battery_percent: default_critical_battery_percent(),
+            abnormal_drain_percent_per_hour: default_abnormal_drain_percent_per_hour(),
+            adaptive_sampling: None,
+        }
+    }
+}
+
+impl MonitoringConfig {
+    /// Register an alert rule
+    pub fn add_rule(&mut self, rule: AlertRule) -> &mut Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Builder-style variant of [`add_rule`](Self::add_rule)
+    pub fn with_rule(mut self, rule: AlertRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Enable adaptive sampling with the given bounds
+    pub fn with_adaptive_sampling(mut self, sampling: AdaptiveSampling) -> Self {
+        self.adaptive_sampling = Some(sampling);
+        self
+    }
+
+    /// Interval the monitor starts sampling at
+    fn initial_interval(&self) -> Duration {
+        match &self.adaptive_sampling {
+            Some(sampling) => sampling.clamp(self.update_interval),
+            None => self.update_interval,
         }
     }
 }
@@ -71,15 +250,255 @@ pub enum MonitoringEvent {
         error: String,
         timestamp: std::time::SystemTime,
     },
+    /// An [`AlertRule`] crossed its threshold for the required duration
+    RuleTriggered {
+        rule_id: String,
+        metric: Metric,
+        value: f64,
+        threshold: f64,
+        timestamp: std::time::SystemTime,
+    },
+    /// A previously triggered rule returned to normal (past its hysteresis margin)
+    RuleCleared {
+        rule_id: String,
+        metric: Metric,
+        value: f64,
+        timestamp: std::time::SystemTime,
+    },
+    /// Uncorrectable ECC memory errors increased since the previous update
+    MemoryErrors {
+        new_uncorrectable: u64,
+        uncorrectable_errors: u64,
+        correctable_errors: Option<u64>,
+        timestamp: std::time::SystemTime,
+    },
+    /// A disk stayed saturated for the configured duration
+    DiskSaturated {
+        device: String,
+        utilization_percent: Option<f64>,
+        queue_depth: f64,
+        duration: Duration,
+        timestamp: std::time::SystemTime,
+    },
+    /// A network interface's link went up or down
+    LinkStateChanged {
+        interface: String,
+        is_up: bool,
+        speed_mbps: Option<u32>,
+        timestamp: std::time::SystemTime,
+    },
+    /// The machine switched between AC and battery, or the OS power plan changed
+    PowerStateChanged {
+        power_source: PowerSource,
+        power_state: PowerState,
+        power_plan: Option<String>,
+        timestamp: std::time::SystemTime,
+    },
+    /// A CPU package or GPU lowered its clocks since the previous update
+    ThrottlingDetected {
+        component: HardwareComponent,
+        device: String,
+        causes: Vec<ThrottleCause>,
+        new_events: u64,
+        timestamp: std::time::SystemTime,
+    },
+    /// A GPU's VRAM usage crossed `vram_exhaustion_percent`
+    VramExhausted {
+        device: String,
+        name: Option<String>,
+        memory_used_mb: u64,
+        memory_total_mb: u64,
+        used_percent: f64,
+        timestamp: std::time::SystemTime,
+    },
+    /// The charger was plugged in or unplugged
+    ChargerStateChanged {
+        plugged_in: bool,
+        percentage: f32,
+        timestamp: std::time::SystemTime,
+    },
+    /// Battery charge fell below the low (or critical) threshold while discharging
+    LowBattery {
+        percentage: f32,
+        critical: bool,
+        time_remaining_minutes: Option<u32>,
+        timestamp: std::time::SystemTime,
+    },
+    /// The battery is draining faster than `abnormal_drain_percent_per_hour`
+    AbnormalBatteryDrain {
+        percent_per_hour: f64,
+        watts: Option<f64>,
+        percentage: f32,
+        timestamp: std::time::SystemTime,
+    },
     /// Regular update with current metrics
     MetricsUpdate {
         hardware_info: Option<HardwareInfo>,
         thermal_info: Option<ThermalInfo>,
         power_profile: Option<PowerProfile>,
+        /// Live counters of each GPU (empty when GPU sampling is disabled)
+        #[serde(default)]
+        gpu_usage: Vec<GPUUsage>,
+        /// Windows performance counter reading (`None` on other platforms)
+        #[serde(default)]
+        performance_counters: Option<PerformanceCounters>,
         timestamp: std::time::SystemTime,
     },
 }
 
+/// How urgent a [`MonitoringEvent`] is, for filtering and log levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum EventSeverity {
+    /// Routine updates and state changes
+    Info,
+    /// Something needs attention
+    Warning,
+    /// Data loss or hardware damage is possible
+    Critical,
+}
+
+impl std::fmt::Display for EventSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EventSeverity::Info => write!(f, "info"),
+            EventSeverity::Warning => write!(f, "warning"),
+            EventSeverity::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+impl MonitoringEvent {
+    /// Get the variant name, e.g. `ThermalAlert`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MonitoringEvent::ThermalAlert { .. } => "ThermalAlert",
+            MonitoringEvent::PowerAlert { .. } => "PowerAlert",
+            MonitoringEvent::HardwareChanged { .. } => "HardwareChanged",
+            MonitoringEvent::MonitoringError { .. } => "MonitoringError",
+            MonitoringEvent::RuleTriggered { .. } => "RuleTriggered",
+            MonitoringEvent::RuleCleared { .. } => "RuleCleared",
+            MonitoringEvent::MemoryErrors { .. } => "MemoryErrors",
+            MonitoringEvent::DiskSaturated { .. } => "DiskSaturated",
+            MonitoringEvent::LinkStateChanged { .. } => "LinkStateChanged",
+            MonitoringEvent::PowerStateChanged { .. } => "PowerStateChanged",
+            MonitoringEvent::ThrottlingDetected { .. } => "ThrottlingDetected",
+            MonitoringEvent::VramExhausted { .. } => "VramExhausted",
+            MonitoringEvent::ChargerStateChanged { .. } => "ChargerStateChanged",
+            MonitoringEvent::LowBattery { .. } => "LowBattery",
+            MonitoringEvent::AbnormalBatteryDrain { .. } => "AbnormalBatteryDrain",
+            MonitoringEvent::MetricsUpdate { .. } => "MetricsUpdate",
+        }
+    }
+
+    /// Get when the event was generated
+    pub fn timestamp(&self) -> std::time::SystemTime {
+        match self {
+            MonitoringEvent::ThermalAlert { timestamp, .. }
+            | MonitoringEvent::PowerAlert { timestamp, .. }
+            | MonitoringEvent::HardwareChanged { timestamp, .. }
+            | MonitoringEvent::MonitoringError { timestamp, .. }
+            | MonitoringEvent::RuleTriggered { timestamp, .. }
+            | MonitoringEvent::RuleCleared { timestamp, .. }
+            | MonitoringEvent::MemoryErrors { timestamp, .. }
+            | MonitoringEvent::DiskSaturated { timestamp, .. }
+            | MonitoringEvent::LinkStateChanged { timestamp, .. }
+            | MonitoringEvent::PowerStateChanged { timestamp, .. }
+            | MonitoringEvent::ThrottlingDetected { timestamp, .. }
+            | MonitoringEvent::VramExhausted { timestamp, .. }
+            | MonitoringEvent::ChargerStateChanged { timestamp, .. }
+            | MonitoringEvent::LowBattery { timestamp, .. }
+            | MonitoringEvent::AbnormalBatteryDrain { timestamp, .. }
+            | MonitoringEvent::MetricsUpdate { timestamp, .. } => *timestamp,
+        }
+    }
+
+    /// Get how urgent the event is
+    pub fn severity(&self) -> EventSeverity {
+        match self {
+            MonitoringEvent::MemoryErrors { .. } | MonitoringEvent::LowBattery { critical: true, .. } => {
+                EventSeverity::Critical
+            }
+            MonitoringEvent::ThermalAlert { .. }
+            | MonitoringEvent::PowerAlert { .. }
+            | MonitoringEvent::MonitoringError { .. }
+            | MonitoringEvent::RuleTriggered { .. }
+            | MonitoringEvent::DiskSaturated { .. }
+            | MonitoringEvent::ThrottlingDetected { .. }
+            | MonitoringEvent::VramExhausted { .. }
+            | MonitoringEvent::LowBattery { critical: false, .. }
+            | MonitoringEvent::AbnormalBatteryDrain { .. }
+            | MonitoringEvent::LinkStateChanged { is_up: false, .. } => EventSeverity::Warning,
+            MonitoringEvent::HardwareChanged { .. }
+            | MonitoringEvent::RuleCleared { .. }
+            | MonitoringEvent::LinkStateChanged { is_up: true, .. }
+            | MonitoringEvent::PowerStateChanged { .. }
+            | MonitoringEvent::ChargerStateChanged { .. }
+            | MonitoringEvent::MetricsUpdate { .. } => EventSeverity::Info,
+        }
+    }
+
+    /// Get a one-line description for logs and notifications
+    pub fn summary(&self) -> String {
+        match self {
+            MonitoringEvent::ThermalAlert { sensor_name, temperature, threshold, .. } => {
+                format!("{sensor_name} at {temperature:.1}°C (threshold {threshold:.1}°C)")
+            }
+            MonitoringEvent::PowerAlert { current_power, threshold, .. } => {
+                format!("Power draw {current_power:.1} W (threshold {threshold:.1} W)")
+            }
+            MonitoringEvent::HardwareChanged { change_type, description, .. } => {
+                format!("{change_type:?}: {description}")
+            }
+            MonitoringEvent::MonitoringError { error, .. } => format!("Monitoring error: {error}"),
+            MonitoringEvent::RuleTriggered { rule_id, metric, value, threshold, .. } => {
+                format!("Rule {rule_id}: {metric} is {value:.2} (threshold {threshold:.2})")
+            }
+            MonitoringEvent::RuleCleared { rule_id, metric, value, .. } => {
+                format!("Rule {rule_id} cleared: {metric} is {value:.2}")
+            }
+            MonitoringEvent::MemoryErrors { new_uncorrectable, uncorrectable_errors, .. } => {
+                format!("{new_uncorrectable} new uncorrectable ECC errors ({uncorrectable_errors} total)")
+            }
+            MonitoringEvent::DiskSaturated { device, queue_depth, duration, .. } => {
+                format!("{device} saturated for {}s (queue depth {queue_depth:.1})", duration.as_secs())
+            }
+            MonitoringEvent::LinkStateChanged { interface, is_up, speed_mbps, .. } => match (is_up, speed_mbps) {
+                (true, Some(speed)) => format!("{interface} link up at {speed} Mbit/s"),
+                (true, None) => format!("{interface} link up"),
+                (false, _) => format!("{interface} link down"),
+            },
+            MonitoringEvent::PowerStateChanged { power_source, power_state, power_plan, .. } => match power_plan {
+                Some(plan) => format!("Power source {power_source}, {power_state}, plan {plan}"),
+                None => format!("Power source {power_source}, {power_state}"),
+            },
+            MonitoringEvent::ThrottlingDetected { device, causes, new_events, .. } => {
+                let causes: Vec<String> = causes.iter().map(ToString::to_string).collect();
+                format!("{device} throttled {new_events} times ({})", causes.join(", "))
+            }
+            MonitoringEvent::VramExhausted { device, name, memory_used_mb, memory_total_mb, used_percent, .. } => {
+                let gpu = name.as_deref().unwrap_or(device);
+                format!("{gpu} VRAM at {used_percent:.0}% ({memory_used_mb} of {memory_total_mb} MB)")
+            }
+            MonitoringEvent::ChargerStateChanged { plugged_in, percentage, .. } => {
+                let state = if *plugged_in { "plugged in" } else { "unplugged" };
+                format!("Charger {state} at {percentage:.0}%")
+            }
+            MonitoringEvent::LowBattery { percentage, critical, time_remaining_minutes, .. } => {
+                let level = if *critical { "Critical battery" } else { "Low battery" };
+                match time_remaining_minutes {
+                    Some(minutes) => format!("{level}: {percentage:.0}% ({minutes} min left)"),
+                    None => format!("{level}: {percentage:.0}%"),
+                }
+            }
+            MonitoringEvent::AbnormalBatteryDrain { percent_per_hour, watts, .. } => match watts {
+                Some(watts) => format!("Battery draining at {percent_per_hour:.0}%/h ({watts:.1} W)"),
+                None => format!("Battery draining at {percent_per_hour:.0}%/h"),
+            },
+            MonitoringEvent::MetricsUpdate { .. } => "Metrics update".to_string(),
+        }
+    }
+}
+
 /// Type of hardware configuration change
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HardwareChangeType {
@@ -108,6 +527,45 @@ pub struct MonitoringStats {
     pub hardware_changes: u64,
     /// Monitoring errors encountered
     pub errors: u64,
+    /// Alert rules triggered
+    #[serde(default)]
+    pub rule_alerts: u64,
+    /// Uncorrectable memory error events
+    #[serde(default)]
+    pub memory_errors: u64,
+    /// Disk saturation events
+    #[serde(default)]
+    pub disk_saturations: u64,
+    /// Per-disk I/O rates from the latest update
+    #[serde(default)]
+    pub disk_io: Vec<DiskIoStats>,
+    /// Network link up/down transitions
+    #[serde(default)]
+    pub link_changes: u64,
+    /// Per-interface traffic rates from the latest network sample
+    #[serde(default)]
+    pub network_io: Vec<NetworkIoStats>,
+    /// Power source and power plan changes
+    #[serde(default)]
+    pub power_state_changes: u64,
+    /// Observed throttling events
+    #[serde(default)]
+    pub throttling_events: u64,
+    /// VRAM exhaustion events
+    #[serde(default)]
+    pub vram_alerts: u64,
+    /// Per-GPU counters from the latest update
+    #[serde(default)]
+    pub gpu_usage: Vec<GPUUsage>,
+    /// Windows performance counters from the latest update
+    #[serde(default)]
+    pub performance_counters: Option<PerformanceCounters>,
+    /// Charger, low-battery and abnormal drain events
+    #[serde(default)]
+    pub battery_events: u64,
+    /// Battery drain over the recent window while discharging
+    #[serde(default)]
+    pub battery_drain: Option<BatteryDrain>,
     /// Monitoring uptime
     pub uptime: Duration,
     /// Last update timestamp
@@ -116,6 +574,103 @@ pub struct MonitoringStats {
     pub average_update_interval: Duration,
 }
 
+impl MonitoringStats {
+    /// Per-disk I/O rates from the latest update
+    pub fn disk_io(&self) -> &[DiskIoStats] {
+        &self.disk_io
+    }
+
+    /// Latest I/O rates of one disk
+    pub fn disk_io_for(&self, device: &str) -> Option<&DiskIoStats> {
+        self.disk_io.iter().find(|stats| stats.device == device)
+    }
+
+    /// Read plus write throughput summed over all disks in MB/s
+    pub fn total_disk_throughput_mb_s(&self) -> f64 {
+        self.disk_io.iter().map(DiskIoStats::throughput_mb_s).sum()
+    }
+
+    /// Per-interface traffic rates from the latest network sample
+    pub fn network_io(&self) -> &[NetworkIoStats] {
+        &self.network_io
+    }
+
+    /// Latest traffic rates of one interface
+    pub fn network_io_for(&self, interface: &str) -> Option<&NetworkIoStats> {
+        self.network_io.iter().find(|stats| stats.interface == interface)
+    }
+
+    /// Per-GPU counters from the latest update
+    pub fn gpu_usage(&self) -> &[GPUUsage] {
+        &self.gpu_usage
+    }
+
+    /// Latest counters of one GPU, by PCI address
+    pub fn gpu_usage_for(&self, device: &str) -> Option<&GPUUsage> {
+        self.gpu_usage.iter().find(|gpu| gpu.device == device)
+    }
+}
+
+/// Lifecycle state of a [`HardwareMonitor`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MonitorState {
+    /// No sampling loop is running
+    Stopped,
+    /// The sampling loop is collecting metrics
+    Running,
+    /// The sampling loop is alive but skips samples and events until resumed
+    Paused,
+}
+
+impl std::fmt::Display for MonitorState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MonitorState::Stopped => write!(f, "stopped"),
+            MonitorState::Running => write!(f, "running"),
+            MonitorState::Paused => write!(f, "paused"),
+        }
+    }
+}
+
+/// Whether the sampling loop is keeping up with its configured interval
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorHealth {
+    /// Current lifecycle state
+    pub state: MonitorState,
+    /// Interval the loop is configured to sample at
+    pub configured_interval: Duration,
+    /// Interval currently in effect, which differs from `configured_interval`
+    /// under adaptive sampling
+    pub current_interval: Duration,
+    /// Samples taken since monitoring started
+    pub samples: u64,
+    /// Samples that took longer than the interval or started more than one interval late
+    pub late_samples: u64,
+    /// How long the latest sample took to collect
+    pub last_sample_duration: Option<Duration>,
+    /// Average sample duration over the last 100 samples
+    pub average_sample_duration: Duration,
+    /// How far the latest sample started behind schedule
+    pub lag: Duration,
+    /// Time since the latest sample finished
+    pub since_last_sample: Option<Duration>,
+    /// `false` when the latest sample overran the interval or the loop has
+    /// not produced a sample for two intervals
+    pub keeping_up: bool,
+}
+
+/// Sampling loop timings behind [`MonitorHealth`]
+#[derive(Debug, Clone, Default)]
+struct LoopTiming {
+    samples: u64,
+    late_samples: u64,
+    last_sample_duration: Option<Duration>,
+    average_sample_duration: Duration,
+    lag: Duration,
+    last_sample_at: Option<Instant>,
+    current_interval: Option<Duration>,
+}
+
 /// Hardware monitoring callback trait
 #[async_trait]
 pub trait MonitoringCallback: Send + Sync {
@@ -151,15 +706,21 @@ where
 
 /// Real-time hardware monitor
 pub struct HardwareMonitor {
-    config: MonitoringConfig,
+    config: watch::Sender<MonitoringConfig>,
     callbacks: Arc<Mutex<Vec<Box<dyn MonitoringCallback>>>>,
     event_sender: broadcast::Sender<MonitoringEvent>,
     stats: Arc<RwLock<MonitoringStats>>,
     running: Arc<RwLock<bool>>,
+    paused: Arc<RwLock<bool>>,
+    /// Wakes the sampling loop early on stop, resume and reconfiguration
+    wake: Arc<Notify>,
+    task: Mutex<Option<JoinHandle<()>>>,
+    timing: Arc<RwLock<LoopTiming>>,
     start_time: Instant,
     last_hardware_info: Arc<RwLock<Option<HardwareInfo>>>,
     last_thermal_info: Arc<RwLock<Option<ThermalInfo>>>,
     last_power_profile: Arc<RwLock<Option<PowerProfile>>>,
+    history: Arc<RwLock<HistoryBuffer>>,
 }
 
 impl HardwareMonitor {
@@ -171,9 +732,10 @@ impl HardwareMonitor {
     /// Create a new hardware monitor with custom configuration
     pub fn with_config(config: MonitoringConfig) -> Self {
         let (event_sender, _) = broadcast::channel(1000);
+        let history = HistoryBuffer::new(config.history_length);
         
         Self {
-            config,
+            config: watch::Sender::new(config),
             callbacks: Arc::new(Mutex::new(Vec::new())),
             event_sender,
             stats: Arc::new(RwLock::new(MonitoringStats {
@@ -182,15 +744,33 @@ impl HardwareMonitor {
                 power_alerts: 0,
                 hardware_changes: 0,
                 errors: 0,
+                rule_alerts: 0,
+                memory_errors: 0,
+                disk_saturations: 0,
+                disk_io: Vec::new(),
+                link_changes: 0,
+                network_io: Vec::new(),
+                power_state_changes: 0,
+                throttling_events: 0,
+                vram_alerts: 0,
+                gpu_usage: Vec::new(),
+                performance_counters: None,
+                battery_events: 0,
+                battery_drain: None,
                 uptime: Duration::from_secs(0),
                 last_update: std::time::SystemTime::now(),
                 average_update_interval: Duration::from_secs(0),
             })),
             running: Arc::new(RwLock::new(false)),
+            paused: Arc::new(RwLock::new(false)),
+            wake: Arc::new(Notify::new()),
+            task: Mutex::new(None),
+            timing: Arc::new(RwLock::new(LoopTiming::default())),
             start_time: Instant::now(),
             last_hardware_info: Arc::new(RwLock::new(None)),
             last_thermal_info: Arc::new(RwLock::new(None)),
             last_power_profile: Arc::new(RwLock::new(None)),
+            history: Arc::new(RwLock::new(history)),
         }
     }
 
@@ -242,6 +822,51 @@ impl HardwareMonitor {
         self.event_sender.subscribe()
     }
 
+    /// Stream of monitoring events emitted after this call
+    ///
+    /// A consumer that falls more than the channel capacity behind skips the
+    /// events it missed. The stream ends once monitoring has stopped and the
+    /// monitor is dropped.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(monitor: &hardware_query::HardwareMonitor) {
+    /// use futures::StreamExt;
+    /// use std::time::Duration;
+    ///
+    /// let mut events = monitor.events();
+    /// while let Ok(Some(event)) = tokio::time::timeout(Duration::from_secs(30), events.next()).await {
+    ///     println!("{event:?}");
+    /// }
+    /// # }
+    /// ```
+    pub fn events(&self) -> impl Stream<Item = MonitoringEvent> + Send + Unpin + 'static {
+        let receiver = self.event_sender.subscribe();
+        Box::pin(stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }))
+    }
+
+    /// Blocking iterator over monitoring events, for use outside async code
+    ///
+    /// Behaves like [`events`](Self::events). Each `next()` blocks the calling
+    /// thread, so it must not be driven from a tokio worker thread.
+    pub fn iter(&self) -> impl Iterator<Item = MonitoringEvent> + Send + 'static {
+        let mut receiver = self.event_sender.subscribe();
+        std::iter::from_fn(move || loop {
+            match receiver.blocking_recv() {
+                Ok(event) => return Some(event),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        })
+    }
+
     /// Start monitoring in the background
     pub async fn start_monitoring(&self) -> Result<()> {
         {
@@ -253,22 +878,88 @@ impl HardwareMonitor {
             }
             *running = true;
         }
+        *self.paused.write().await = false;
+        *self.timing.write().await = LoopTiming::default();
 
-        let config = self.config.clone();
+        let mut config_receiver = self.config.subscribe();
+        let mut config = config_receiver.borrow_and_update().clone();
         let callbacks = Arc::clone(&self.callbacks);
         let event_sender = self.event_sender.clone();
         let stats = Arc::clone(&self.stats);
         let running = Arc::clone(&self.running);
+        let paused = Arc::clone(&self.paused);
+        let wake = Arc::clone(&self.wake);
+        let timing = Arc::clone(&self.timing);
         let last_hardware_info = Arc::clone(&self.last_hardware_info);
         let last_thermal_info = Arc::clone(&self.last_thermal_info);
         let last_power_profile = Arc::clone(&self.last_power_profile);
+        let history = Arc::clone(&self.history);
 
-        tokio::spawn(async move {
-            let mut interval = interval(config.update_interval);
+        if config.enable_hotplug {
+            self.start_hotplug_watcher();
+        }
+
+        let task = tokio::spawn(async move {
+            let mut current_interval = config.initial_interval();
+            let mut interval = sampling_interval(current_interval, Duration::ZERO);
             let mut update_times = Vec::new();
+            let mut rule_engine = RuleEngine::new(config.rules.clone());
+            // Baseline taken on the first update so errors from before monitoring started don't alert
+            let mut last_uncorrectable: Option<u64> = None;
+            let mut last_disk_counters: Option<Vec<DiskIoCounters>> = None;
+            // Start of each disk's current saturation episode, and whether it was reported
+            let mut saturated_since: HashMap<String, (std::time::SystemTime, bool)> = HashMap::new();
+            let mut disk_io = Vec::new();
+            let mut last_network: Option<(Instant, Vec<NetworkInfo>)> = None;
+            let mut network_io: Vec<NetworkIoStats> = Vec::new();
+            let mut last_power_state: Option<(PowerSource, Option<String>)> = None;
+            let mut last_throttle: Option<Vec<ThrottleStatus>> = None;
+            let mut gpu_usage: Vec<GPUUsage> = Vec::new();
+            // GPUs above the VRAM threshold; re-armed once they drop below it
+            let mut vram_exhausted: HashSet<String> = HashSet::new();
+            // Readings since the charger state last changed, oldest first
+            let mut battery_samples: VecDeque<(Instant, BatteryInfo)> = VecDeque::new();
+            let mut battery_drain: Option<BatteryDrain> = None;
+            let mut last_plugged_in: Option<bool> = None;
+            // Whether the reported low-battery alert was the critical one
+            let mut low_battery_reported: Option<bool> = None;
+            let mut drain_reported = false;
+            let mut perf_sampler: Option<PerformanceCounterSampler> = None;
+            // Set once opening the PDH query failed, so it isn't retried every update
+            let mut perf_unavailable = false;
+            let mut last_update_start: Option<Instant> = None;
+            let mut last_reading: Option<SamplingReading> = None;
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = wake.notified() => {}
+                }
+                if !*running.read().await {
+                    break;
+                }
 
-            while *running.read().await {
-                interval.tick().await;
+                // Pick up configuration changes made through update_config
+                if config_receiver.has_changed().unwrap_or(false) {
+                    let new_config = config_receiver.borrow_and_update().clone();
+                    if new_config.update_interval != config.update_interval
+                        || new_config.adaptive_sampling != config.adaptive_sampling
+                    {
+                        current_interval = new_config.initial_interval();
+                        interval = sampling_interval(current_interval, current_interval);
+                    }
+                    if new_config.rules != config.rules {
+                        rule_engine = RuleEngine::new(new_config.rules.clone());
+                    }
+                    config = new_config;
+                }
+
+                if *paused.read().await {
+                    // Lag would otherwise count the whole pause as falling behind
+                    last_update_start = None;
+                    last_reading = None;
+                    continue;
+                }
                 let update_start = Instant::now();
 
                 // Query hardware information
@@ -330,6 +1021,16 @@ impl HardwareMonitor {
                                     });
                                 }
                             }
+                            let power_state = (profile.power_source, profile.power_plan.clone());
+                            if last_power_state.as_ref().is_some_and(|previous| *previous != power_state) {
+                                events.push(MonitoringEvent::PowerStateChanged {
+                                    power_source: profile.power_source,
+                                    power_state: profile.power_state.clone(),
+                                    power_plan: profile.power_plan.clone(),
+                                    timestamp: std::time::SystemTime::now(),
+                                });
+                            }
+                            last_power_state = Some(power_state);
                             power_profile = Some(profile);
                         }
                         Err(e) => {
@@ -341,14 +1042,321 @@ impl HardwareMonitor {
                     }
                 }
 
+                if config.enable_ecc {
+                    let ecc = EccStatus::query();
+                    if let Some(uncorrectable_errors) = ecc.uncorrectable_errors {
+                        if let Some(previous) = last_uncorrectable {
+                            if uncorrectable_errors > previous {
+                                events.push(MonitoringEvent::MemoryErrors {
+                                    new_uncorrectable: uncorrectable_errors - previous,
+                                    uncorrectable_errors,
+                                    correctable_errors: ecc.correctable_errors,
+                                    timestamp: std::time::SystemTime::now(),
+                                });
+                            }
+                        }
+                        last_uncorrectable = Some(uncorrectable_errors);
+                    }
+                }
+
+                if config.enable_throttling {
+                    let statuses = ThrottleStatus::query_all();
+                    if let Some(previous) = &last_throttle {
+                        for throttle in ThrottleEvent::between(previous, &statuses) {
+                            events.push(MonitoringEvent::ThrottlingDetected {
+                                component: throttle.component,
+                                device: throttle.device,
+                                causes: throttle.causes,
+                                new_events: throttle.new_events,
+                                timestamp: std::time::SystemTime::now(),
+                            });
+                        }
+                    }
+                    last_throttle = Some(statuses);
+                }
+
+                if config.enable_gpu {
+                    gpu_usage = GPUUsage::query_all();
+                    for gpu in &gpu_usage {
+                        let Some(used_percent) = gpu.memory_used_percent() else {
+                            continue;
+                        };
+                        if used_percent < config.vram_exhaustion_percent {
+                            vram_exhausted.remove(&gpu.device);
+                        } else if vram_exhausted.insert(gpu.device.clone()) {
+                            events.push(MonitoringEvent::VramExhausted {
+                                device: gpu.device.clone(),
+                                name: gpu.name.clone(),
+                                memory_used_mb: gpu.memory_used_mb.unwrap_or_default(),
+                                memory_total_mb: gpu.memory_total_mb.unwrap_or_default(),
+                                used_percent,
+                                timestamp: std::time::SystemTime::now(),
+                            });
+                        }
+                    }
+                } else {
+                    gpu_usage.clear();
+                    vram_exhausted.clear();
+                }
+
+                let mut performance_counters = None;
+                if config.enable_performance_counters && !perf_unavailable {
+                    match perf_sampler.as_mut() {
+                        Some(sampler) => match sampler.collect() {
+                            Ok(counters) => performance_counters = Some(counters),
+                            Err(e) => events.push(MonitoringEvent::MonitoringError {
+                                error: format!("Failed to collect performance counters: {}", e),
+                                timestamp: std::time::SystemTime::now(),
+                            }),
+                        },
+                        // The first reading covers the time until the next update
+                        None => match PerformanceCounterSampler::new() {
+                            Ok(sampler) => perf_sampler = Some(sampler),
+                            Err(HardwareQueryError::PlatformNotSupported(_)) => perf_unavailable = true,
+                            Err(e) => {
+                                perf_unavailable = true;
+                                events.push(MonitoringEvent::MonitoringError {
+                                    error: format!("Failed to open performance counters: {}", e),
+                                    timestamp: std::time::SystemTime::now(),
+                                });
+                            }
+                        },
+                    }
+                } else if !config.enable_performance_counters {
+                    perf_sampler = None;
+                }
+
+                // Machines without a battery fail the query; that isn't worth an error event
+                let battery = if config.enable_battery { BatteryInfo::query().ok() } else { None };
+                if let Some(battery) = &battery {
+                    let now = std::time::SystemTime::now();
+                    let plugged_in = match battery.status {
+                        BatteryStatus::Discharging => Some(false),
+                        BatteryStatus::Charging | BatteryStatus::Full | BatteryStatus::NotCharging => Some(true),
+                        BatteryStatus::Unknown => None,
+                    };
+                    if let Some(plugged_in) = plugged_in {
+                        if last_plugged_in.is_some_and(|previous| previous != plugged_in) {
+                            events.push(MonitoringEvent::ChargerStateChanged {
+                                plugged_in,
+                                percentage: battery.percentage,
+                                timestamp: now,
+                            });
+                            battery_samples.clear();
+                            battery_drain = None;
+                            drain_reported = false;
+                        }
+                        if plugged_in {
+                            low_battery_reported = None;
+                        }
+                        last_plugged_in = Some(plugged_in);
+                    }
+
+                    if battery.is_discharging() {
+                        let critical = battery.percentage <= config.critical_battery_percent;
+                        let low = critical || battery.percentage <= config.low_battery_percent;
+                        // Report once at the low threshold and once more on reaching critical
+                        if low && low_battery_reported.is_none_or(|reported_critical| critical && !reported_critical) {
+                            low_battery_reported = Some(critical);
+                            events.push(MonitoringEvent::LowBattery {
+                                percentage: battery.percentage,
+                                critical,
+                                time_remaining_minutes: battery.time_remaining_minutes,
+                                timestamp: now,
+                            });
+                        }
+
+                        let sampled_at = Instant::now();
+                        battery_samples.push_back((sampled_at, battery.clone()));
+                        while battery_samples
+                            .front()
+                            .is_some_and(|(at, _)| sampled_at.duration_since(*at) > BATTERY_DRAIN_WINDOW)
+                        {
+                            battery_samples.pop_front();
+                        }
+                        if let Some((oldest_at, oldest)) = battery_samples.front() {
+                            let span = sampled_at.duration_since(*oldest_at);
+                            if span >= MIN_BATTERY_DRAIN_SPAN {
+                                battery_drain = BatteryDrain::between(oldest, battery, span);
+                            }
+                        }
+
+                        match &battery_drain {
+                            Some(drain) if drain.percent_per_hour > config.abnormal_drain_percent_per_hour => {
+                                if !drain_reported {
+                                    drain_reported = true;
+                                    events.push(MonitoringEvent::AbnormalBatteryDrain {
+                                        percent_per_hour: drain.percent_per_hour,
+                                        watts: drain.watts,
+                                        percentage: battery.percentage,
+                                        timestamp: now,
+                                    });
+                                }
+                            }
+                            _ => drain_reported = false,
+                        }
+                    }
+                }
+
+                if config.enable_disk_io {
+                    match DiskIoCounters::query_all() {
+                        Ok(counters) => {
+                            if let Some(previous) = &last_disk_counters {
+                                disk_io = DiskIoStats::between(previous, &counters);
+                            }
+                            last_disk_counters = Some(counters);
+                        }
+                        Err(e) => {
+                            events.push(MonitoringEvent::MonitoringError {
+                                error: format!("Failed to read disk I/O counters: {}", e),
+                                timestamp: std::time::SystemTime::now(),
+                            });
+                        }
+                    }
+
+                    let now = std::time::SystemTime::now();
+                    saturated_since.retain(|device, _| {
+                        disk_io.iter().any(|stats| {
+                            &stats.device == device && stats.is_saturated(config.disk_saturation_percent)
+                        })
+                    });
+                    for stats in disk_io.iter().filter(|s| s.is_saturated(config.disk_saturation_percent)) {
+                        let (since, reported) = saturated_since
+                            .entry(stats.device.clone())
+                            .or_insert((now, false));
+                        let duration = now.duration_since(*since).unwrap_or_default();
+                        if !*reported && duration >= config.disk_saturation_duration {
+                            *reported = true;
+                            events.push(MonitoringEvent::DiskSaturated {
+                                device: stats.device.clone(),
+                                utilization_percent: stats.utilization_percent,
+                                queue_depth: stats.queue_depth,
+                                duration,
+                                timestamp: now,
+                            });
+                        }
+                    }
+                }
+
+                let mut network_sampled = false;
+                let network_due = last_network.as_ref().is_none_or(|(at, _)| {
+                    at.elapsed() >= config.network_sample_interval.unwrap_or(Duration::ZERO)
+                });
+                if config.enable_network_io && network_due {
+                    match NetworkInfo::query_all() {
+                        Ok(interfaces) => {
+                            let sampled_at = Instant::now();
+                            if let Some((previous_at, previous)) = &last_network {
+                                network_io = NetworkIoStats::between(
+                                    previous,
+                                    &interfaces,
+                                    sampled_at.duration_since(*previous_at),
+                                );
+                                network_sampled = true;
+                                for current in &interfaces {
+                                    let Some(before) = previous.iter().find(|n| n.name == current.name) else {
+                                        continue;
+                                    };
+                                    if before.is_up != current.is_up {
+                                        events.push(MonitoringEvent::LinkStateChanged {
+                                            interface: current.name.clone(),
+                                            is_up: current.is_up,
+                                            speed_mbps: current.speed_mbps,
+                                            timestamp: std::time::SystemTime::now(),
+                                        });
+                                    }
+                                }
+                            }
+                            last_network = Some((sampled_at, interfaces));
+                        }
+                        Err(e) => {
+                            events.push(MonitoringEvent::MonitoringError {
+                                error: format!("Failed to query network interfaces: {}", e),
+                                timestamp: std::time::SystemTime::now(),
+                            });
+                        }
+                    }
+                }
+
                 // Generate metrics update event
                 events.push(MonitoringEvent::MetricsUpdate {
                     hardware_info: hardware_info.clone(),
                     thermal_info: thermal_info.clone(),
                     power_profile: power_profile.clone(),
+                    gpu_usage: gpu_usage.clone(),
+                    performance_counters: performance_counters.clone(),
                     timestamp: std::time::SystemTime::now(),
                 });
 
+                let now = std::time::SystemTime::now();
+                let mut values = history::current_values(
+                    hardware_info.as_ref(),
+                    thermal_info.as_ref(),
+                    power_profile.as_ref(),
+                );
+                // Read separately since CPU detection only samples the first core's clock
+                if config.enable_hardware {
+                    if let Some(frequency) = CPUFrequencyInfo::query().average_frequency_mhz() {
+                        values.push((Metric::CpuFrequency, frequency));
+                    }
+                }
+                // PDH figures match Task Manager; prefer them over sysinfo's
+                if let Some(counters) = &performance_counters {
+                    if let Some(usage) = counters.average_cpu_usage() {
+                        values.retain(|(metric, _)| *metric != Metric::CpuUsage);
+                        values.push((Metric::CpuUsage, usage as f64));
+                    }
+                    if let Some(frequency) = counters.average_frequency_mhz() {
+                        values.retain(|(metric, _)| *metric != Metric::CpuFrequency);
+                        values.push((Metric::CpuFrequency, frequency as f64));
+                    }
+                }
+                if !values.iter().any(|(metric, _)| *metric == Metric::GpuUsage) {
+                    if let Some(busiest) = gpu_usage.iter().filter_map(|gpu| gpu.utilization_percent).reduce(f32::max) {
+                        values.push((Metric::GpuUsage, busiest as f64));
+                    }
+                }
+                if let Some(fullest) = gpu_usage.iter().filter_map(GPUUsage::memory_used_percent).reduce(f64::max) {
+                    values.push((Metric::GpuMemoryUsage, fullest));
+                }
+                if let Some(battery) = &battery {
+                    values.push((Metric::BatteryLevel, battery.percentage as f64));
+                    if let Some(drain) = battery_drain.as_ref().filter(|_| battery.is_discharging()) {
+                        values.push((Metric::BatteryDrainRate, drain.percent_per_hour));
+                    }
+                }
+                if !disk_io.is_empty() {
+                    let throughput = disk_io.iter().map(DiskIoStats::throughput_mb_s).sum();
+                    values.push((Metric::DiskThroughput, throughput));
+                    if let Some(busiest) = disk_io
+                        .iter()
+                        .filter_map(|stats| stats.utilization_percent)
+                        .reduce(f64::max)
+                    {
+                        values.push((Metric::DiskUtilization, busiest));
+                    }
+                }
+                if network_sampled {
+                    let interfaces = last_network.as_ref().map(|(_, interfaces)| interfaces.as_slice());
+                    let external = network_io.iter().filter(|stats| {
+                        interfaces
+                            .and_then(|list| list.iter().find(|n| n.name == stats.interface))
+                            .is_none_or(|n| n.network_type != NetworkType::Loopback)
+                    });
+                    let (rx, tx) = external.fold((0.0, 0.0), |(rx, tx), stats| {
+                        (rx + stats.rx_mbps(), tx + stats.tx_mbps())
+                    });
+                    values.push((Metric::NetworkReceive, rx));
+                    values.push((Metric::NetworkTransmit, tx));
+                }
+                history.write().await.record_values(&values, now);
+                events.extend(rule_engine.evaluate(&values, now));
+
+                let reading = SamplingReading {
+                    temperature: thermal_info.as_ref().and_then(ThermalInfo::max_temperature),
+                    power: power_profile.as_ref().and_then(|profile| profile.total_power_draw),
+                };
+
                 // Update cached information
                 if let Some(info) = hardware_info {
                     *last_hardware_info.write().await = Some(info);
@@ -383,9 +1391,24 @@ impl HardwareMonitor {
                             MonitoringEvent::PowerAlert { .. } => stats.power_alerts += 1,
                             MonitoringEvent::HardwareChanged { .. } => stats.hardware_changes += 1,
                             MonitoringEvent::MonitoringError { .. } => stats.errors += 1,
+                            MonitoringEvent::RuleTriggered { .. } => stats.rule_alerts += 1,
+                            MonitoringEvent::MemoryErrors { .. } => stats.memory_errors += 1,
+                            MonitoringEvent::DiskSaturated { .. } => stats.disk_saturations += 1,
+                            MonitoringEvent::LinkStateChanged { .. } => stats.link_changes += 1,
+                            MonitoringEvent::PowerStateChanged { .. } => stats.power_state_changes += 1,
+                            MonitoringEvent::ThrottlingDetected { .. } => stats.throttling_events += 1,
+                            MonitoringEvent::VramExhausted { .. } => stats.vram_alerts += 1,
+                            MonitoringEvent::ChargerStateChanged { .. }
+                            | MonitoringEvent::LowBattery { .. }
+                            | MonitoringEvent::AbnormalBatteryDrain { .. } => stats.battery_events += 1,
                             _ => {}
                         }
                     }
+                    stats.disk_io = disk_io.clone();
+                    stats.network_io = network_io.clone();
+                    stats.gpu_usage = gpu_usage.clone();
+                    stats.battery_drain = battery_drain.clone();
+                    stats.performance_counters = performance_counters;
 
                     stats.last_update = std::time::SystemTime::now();
                     let update_duration = update_start.elapsed();
@@ -400,23 +1423,194 @@ impl HardwareMonitor {
                         let total_time: Duration = update_times.iter().sum();
                         stats.average_update_interval = total_time / update_times.len() as u32;
                     }
+
+                    let mut timing = timing.write().await;
+                    let lag = last_update_start
+                        .map(|previous| update_start.duration_since(previous).saturating_sub(current_interval))
+                        .unwrap_or_default();
+                    timing.samples += 1;
+                    if update_duration > current_interval || lag > current_interval {
+                        timing.late_samples += 1;
+                    }
+                    timing.last_sample_duration = Some(update_duration);
+                    timing.average_sample_duration = stats.average_update_interval;
+                    timing.lag = lag;
+                    timing.last_sample_at = Some(Instant::now());
+
+                    if let Some(sampling) = &config.adaptive_sampling {
+                        if let (Some(previous), Some(previous_start)) = (&last_reading, last_update_start) {
+                            let limits = SamplingReading {
+                                temperature: Some(config.thermal_threshold),
+                                power: config.power_threshold,
+                            };
+                            let next = sampling.next_interval(
+                                current_interval,
+                                update_start.duration_since(previous_start),
+                                previous,
+                                &reading,
+                                &limits,
+                            );
+                            if next != current_interval {
+                                current_interval = next;
+                                interval = sampling_interval(next, next);
+                            }
+                        }
+                        last_reading = Some(reading);
+                    }
+                    timing.current_interval = Some(current_interval);
                 }
+                last_update_start = Some(update_start);
             }
         });
+        *self.task.lock().await = Some(task);
 
         Ok(())
     }
 
+    /// Forward device hotplug changes as `HardwareChanged` events
+    fn start_hotplug_watcher(&self) {
+        let (change_sender, mut change_receiver) = mpsc::unbounded_channel::<DeviceChange>();
+        hotplug::spawn_watcher(change_sender, Arc::clone(&self.running), self.config.borrow().update_interval);
+
+        let callbacks = Arc::clone(&self.callbacks);
+        let event_sender = self.event_sender.clone();
+        let stats = Arc::clone(&self.stats);
+        let paused = Arc::clone(&self.paused);
+
+        tokio::spawn(async move {
+            while let Some(change) = change_receiver.recv().await {
+                if *paused.read().await {
+                    continue;
+                }
+                let event = MonitoringEvent::HardwareChanged {
+                    description: change.summary(),
+                    change_type: change.change_type,
+                    timestamp: std::time::SystemTime::now(),
+                };
+
+                let _ = event_sender.send(event.clone());
+                for callback in callbacks.lock().await.iter() {
+                    callback.on_event(&event).await;
+                }
+
+                let mut stats = stats.write().await;
+                stats.total_events += 1;
+                stats.hardware_changes += 1;
+            }
+        });
+    }
+
     /// Stop monitoring
+    ///
+    /// Returns immediately; the sampling loop exits without finishing another
+    /// sample. Use [`stop`](Self::stop) to wait until it has.
     pub async fn stop_monitoring(&self) {
         *self.running.write().await = false;
+        self.wake.notify_one();
+    }
+
+    /// Stop monitoring and wait for the sampling loop to finish
+    ///
+    /// A sample already in progress completes and its events are delivered
+    /// before this returns. Stopping a monitor that isn't running is a no-op.
+    pub async fn stop(&self) -> Result<()> {
+        self.stop_monitoring().await;
+        *self.paused.write().await = false;
+        let task = self.task.lock().await.take();
+        if let Some(task) = task {
+            task.await.map_err(|e| {
+                HardwareQueryError::MonitoringError(format!("Sampling loop ended abnormally: {e}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Suspend sampling without tearing down the loop
+    ///
+    /// No metrics are collected and no events are emitted until
+    /// [`resume`](Self::resume) is called. History and statistics are kept.
+    pub async fn pause(&self) -> Result<()> {
+        if !*self.running.read().await {
+            return Err(HardwareQueryError::InvalidConfiguration(
+                "Monitoring is not running".to_string()
+            ));
+        }
+        *self.paused.write().await = true;
+        Ok(())
+    }
+
+    /// Continue sampling after [`pause`](Self::pause), starting with an immediate sample
+    pub async fn resume(&self) -> Result<()> {
+        if !*self.running.read().await {
+            return Err(HardwareQueryError::InvalidConfiguration(
+                "Monitoring is not running".to_string()
+            ));
+        }
+        let was_paused = std::mem::replace(&mut *self.paused.write().await, false);
+        if was_paused {
+            self.wake.notify_one();
+        }
+        Ok(())
     }
 
-    /// Check if monitoring is currently running
+    /// Check if monitoring is currently running (including while paused)
     pub async fn is_monitoring(&self) -> bool {
         *self.running.read().await
     }
 
+    /// Get the current lifecycle state
+    pub async fn state(&self) -> MonitorState {
+        if !*self.running.read().await {
+            MonitorState::Stopped
+        } else if *self.paused.read().await {
+            MonitorState::Paused
+        } else {
+            MonitorState::Running
+        }
+    }
+
+    /// Report whether the sampling loop is keeping up with the configured interval
+    ///
+    /// ```rust,no_run
+    /// # async fn example(monitor: &hardware_query::HardwareMonitor) {
+    /// let health = monitor.health().await;
+    /// if !health.keeping_up {
+    ///     eprintln!(
+    ///         "monitor is {:?} behind ({} of {} samples late)",
+    ///         health.lag, health.late_samples, health.samples
+    ///     );
+    /// }
+    /// # }
+    /// ```
+    pub async fn health(&self) -> MonitorHealth {
+        let state = self.state().await;
+        let configured_interval = self.config.borrow().update_interval;
+        let timing = self.timing.read().await.clone();
+        let current_interval = timing.current_interval.unwrap_or_else(|| self.config.borrow().initial_interval());
+        let since_last_sample = timing.last_sample_at.map(|at| at.elapsed());
+
+        let keeping_up = match state {
+            MonitorState::Running => {
+                timing.last_sample_duration.is_none_or(|duration| duration <= current_interval)
+                    && since_last_sample.is_none_or(|since| since <= current_interval * 2)
+            }
+            MonitorState::Paused | MonitorState::Stopped => true,
+        };
+
+        MonitorHealth {
+            state,
+            configured_interval,
+            current_interval,
+            samples: timing.samples,
+            late_samples: timing.late_samples,
+            last_sample_duration: timing.last_sample_duration,
+            average_sample_duration: timing.average_sample_duration,
+            lag: timing.lag,
+            since_last_sample,
+            keeping_up,
+        }
+    }
+
     /// Get current monitoring statistics
     pub async fn get_stats(&self) -> MonitoringStats {
         let mut stats = self.stats.read().await.clone();
@@ -439,9 +1633,43 @@ impl HardwareMonitor {
         self.last_power_profile.read().await.clone()
     }
 
+    /// Get a snapshot of the recorded metric history
+    ///
+    /// ```rust,no_run
+    /// # async fn example(monitor: &hardware_query::HardwareMonitor) {
+    /// use std::time::Duration;
+    ///
+    /// let cpu_temp = monitor.history().await.cpu_temperature(Duration::from_secs(600));
+    /// if let (Some(avg), Some(slope)) = (cpu_temp.avg(), cpu_temp.slope()) {
+    ///     println!("CPU averaged {avg:.1}°C, trending {:+.2}°C/min", slope * 60.0);
+    /// }
+    /// # }
+    /// ```
+    pub async fn history(&self) -> HistoryBuffer {
+        self.history.read().await.clone()
+    }
+
+    /// Get a copy of the active configuration
+    pub fn config(&self) -> MonitoringConfig {
+        self.config.borrow().clone()
+    }
+
     /// Update monitoring configuration
-    pub async fn update_config(&mut self, new_config: MonitoringConfig) {
-        self.config = new_config;
+    ///
+    /// A running monitor applies the new intervals, thresholds and rules from
+    /// its next sample without restarting. Rule state is reset only when the
+    /// rules themselves change. `enable_hotplug` takes effect on the next
+    /// [`start_monitoring`](Self::start_monitoring).
+    pub async fn update_config(&self, new_config: MonitoringConfig) {
+        if new_config.history_length != self.config.borrow().history_length {
+            let mut history = self.history.write().await;
+            *history = HistoryBuffer::new(new_config.history_length);
+        }
+        let interval_changed = new_config.update_interval != self.config.borrow().update_interval;
+        self.config.send_replace(new_config);
+        if interval_changed {
+            self.wake.notify_one();
+        }
     }
 
     /// Clear all callbacks
@@ -456,3 +1684,13 @@ impl Default for HardwareMonitor {
         Self::new()
     }
 }
+
+/// Sampling ticker whose first tick fires after `first_delay`
+///
+/// Ticks missed while a slow sample runs are delayed rather than fired in a
+/// burst, so an overloaded loop shows up as lag in [`MonitorHealth`].
+fn sampling_interval(period: Duration, first_delay: Duration) -> tokio::time::Interval {
+    let mut interval = interval_at(tokio::time::Instant::now() + first_delay, period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    interval
+}
@@ -1,5 +1,6 @@
 use crate::Result;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use sysinfo::Networks;
 
 /// Network interface type
@@ -28,6 +29,35 @@ impl std::fmt::Display for NetworkType {
     }
 }
 
+/// Link duplex mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Duplex {
+    Full,
+    Half,
+}
+
+impl std::fmt::Display for Duplex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Duplex::Full => write!(f, "Full"),
+            Duplex::Half => write!(f, "Half"),
+        }
+    }
+}
+
+/// Wireless link details
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WirelessInfo {
+    /// Connected network name
+    pub ssid: Option<String>,
+    /// Signal strength in dBm
+    pub signal_dbm: Option<i32>,
+    /// Signal quality in percent
+    pub signal_quality: Option<u8>,
+    /// Channel frequency in MHz
+    pub frequency_mhz: Option<u32>,
+}
+
 /// Network interface information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkInfo {
@@ -55,6 +85,108 @@ pub struct NetworkInfo {
     pub receive_errors: u64,
     /// Transmit errors
     pub transmit_errors: u64,
+    /// Negotiated duplex mode (if available)
+    #[serde(default)]
+    pub duplex: Option<Duplex>,
+    /// Maximum transmission unit in bytes
+    #[serde(default)]
+    pub mtu: Option<u32>,
+    /// Kernel driver name
+    #[serde(default)]
+    pub driver: Option<String>,
+    /// Driver version
+    #[serde(default)]
+    pub driver_version: Option<String>,
+    /// Wireless details for Wi-Fi interfaces
+    #[serde(default)]
+    pub wireless: Option<WirelessInfo>,
+    /// Software interface (bridge, veth, tun/tap, VPN, container networking)
+    #[serde(default)]
+    pub is_virtual: bool,
+}
+
+/// Traffic rates of one interface between two [`NetworkInfo`] readings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkIoStats {
+    /// Interface name
+    pub interface: String,
+    /// Link state at the end of the interval
+    pub is_up: bool,
+    /// Bytes received per second
+    pub rx_bytes_per_sec: f64,
+    /// Bytes transmitted per second
+    pub tx_bytes_per_sec: f64,
+    /// Packets received per second
+    pub rx_packets_per_sec: f64,
+    /// Packets transmitted per second
+    pub tx_packets_per_sec: f64,
+    /// Receive errors during the interval
+    pub rx_errors: u64,
+    /// Transmit errors during the interval
+    pub tx_errors: u64,
+    /// Length of the interval
+    pub interval: Duration,
+}
+
+impl NetworkIoStats {
+    /// Measure every interface over `interval`
+    ///
+    /// Blocks for `interval` between the two readings.
+    pub fn sample(interval: Duration) -> Result<Vec<Self>> {
+        let before = NetworkInfo::query_all()?;
+        let started = std::time::Instant::now();
+        std::thread::sleep(interval);
+        let after = NetworkInfo::query_all()?;
+        Ok(Self::between(&before, &after, started.elapsed()))
+    }
+
+    /// Rates between two readings taken `elapsed` apart, for interfaces present in both
+    pub fn between(before: &[NetworkInfo], after: &[NetworkInfo], elapsed: Duration) -> Vec<Self> {
+        let seconds = elapsed.as_secs_f64();
+        if seconds <= 0.0 {
+            return Vec::new();
+        }
+        // Counters reset when an interface is recreated; treat that as zero traffic
+        let rate = |before: u64, after: u64| after.saturating_sub(before) as f64 / seconds;
+
+        after
+            .iter()
+            .filter_map(|current| {
+                let previous = before.iter().find(|n| n.name == current.name)?;
+                Some(Self {
+                    interface: current.name.clone(),
+                    is_up: current.is_up,
+                    rx_bytes_per_sec: rate(previous.bytes_received, current.bytes_received),
+                    tx_bytes_per_sec: rate(previous.bytes_transmitted, current.bytes_transmitted),
+                    rx_packets_per_sec: rate(previous.packets_received, current.packets_received),
+                    tx_packets_per_sec: rate(previous.packets_transmitted, current.packets_transmitted),
+                    rx_errors: current.receive_errors.saturating_sub(previous.receive_errors),
+                    tx_errors: current.transmit_errors.saturating_sub(previous.transmit_errors),
+                    interval: elapsed,
+                })
+            })
+            .collect()
+    }
+
+    /// Get interface name
+    pub fn interface(&self) -> &str {
+        &self.interface
+    }
+
+    /// Receive throughput in Mbit/s
+    pub fn rx_mbps(&self) -> f64 {
+        self.rx_bytes_per_sec * 8.0 / 1_000_000.0
+    }
+
+    /// Transmit throughput in Mbit/s
+    pub fn tx_mbps(&self) -> f64 {
+        self.tx_bytes_per_sec * 8.0 / 1_000_000.0
+    }
+
+    /// Share of the link speed in use by the busier direction, in percent
+    pub fn link_utilization_percent(&self, speed_mbps: u32) -> Option<f64> {
+        (speed_mbps > 0).then(|| self.rx_mbps().max(self.tx_mbps()) / speed_mbps as f64 * 100.0)
+    }
 }
 
 impl NetworkInfo {
@@ -65,24 +197,51 @@ impl NetworkInfo {
         let mut network_interfaces = Vec::new();
 
         for (interface_name, network_data) in &networks {
-            let network_info = Self {
+            let mac = network_data.mac_address();
+            #[allow(unused_mut)]
+            let mut network_info = Self {
                 name: interface_name.clone(),
                 network_type: Self::detect_network_type(interface_name),
-                mac_address: Self::get_mac_address(interface_name),
-                ip_addresses: Self::get_ip_addresses(interface_name),
-                speed_mbps: None, // Would need platform-specific implementation
+                mac_address: if mac.is_unspecified() {
+                    String::new()
+                } else {
+                    mac.to_string()
+                },
+                ip_addresses: network_data
+                    .ip_networks()
+                    .iter()
+                    .map(|network| network.addr.to_string())
+                    .collect(),
+                speed_mbps: None,
                 is_up: network_data.received() > 0 || network_data.transmitted() > 0,
-                bytes_received: network_data.received(),
-                bytes_transmitted: network_data.transmitted(),
-                packets_received: network_data.packets_received(),
-                packets_transmitted: network_data.packets_transmitted(),
-                receive_errors: network_data.errors_on_received(),
-                transmit_errors: network_data.errors_on_transmitted(),
+                bytes_received: network_data.total_received(),
+                bytes_transmitted: network_data.total_transmitted(),
+                packets_received: network_data.total_packets_received(),
+                packets_transmitted: network_data.total_packets_transmitted(),
+                receive_errors: network_data.total_errors_on_received(),
+                transmit_errors: network_data.total_errors_on_transmitted(),
+                duplex: None,
+                mtu: u32::try_from(network_data.mtu()).ok().filter(|mtu| *mtu > 0),
+                driver: None,
+                driver_version: None,
+                wireless: None,
+                is_virtual: Self::is_virtual_name(interface_name),
             };
 
+            #[cfg(target_os = "linux")]
+            network_info.fill_linux();
+
+            #[cfg(target_os = "macos")]
+            network_info.fill_macos();
+
             network_interfaces.push(network_info);
         }
 
+        #[cfg(target_os = "windows")]
+        Self::fill_windows(&mut network_interfaces);
+
+        network_interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
         Ok(network_interfaces)
     }
 
@@ -106,6 +265,41 @@ impl NetworkInfo {
         &self.ip_addresses
     }
 
+    /// Get negotiated link speed in Mbps
+    pub fn speed_mbps(&self) -> Option<u32> {
+        self.speed_mbps
+    }
+
+    /// Get duplex mode
+    pub fn duplex(&self) -> Option<Duplex> {
+        self.duplex
+    }
+
+    /// Get MTU in bytes
+    pub fn mtu(&self) -> Option<u32> {
+        self.mtu
+    }
+
+    /// Get driver name
+    pub fn driver(&self) -> Option<&str> {
+        self.driver.as_deref()
+    }
+
+    /// Get driver version
+    pub fn driver_version(&self) -> Option<&str> {
+        self.driver_version.as_deref()
+    }
+
+    /// Get wireless details
+    pub fn wireless(&self) -> Option<&WirelessInfo> {
+        self.wireless.as_ref()
+    }
+
+    /// Check if this is a software interface rather than a physical NIC
+    pub fn is_virtual(&self) -> bool {
+        self.is_virtual
+    }
+
     /// Check if interface is active
     pub fn is_active(&self) -> bool {
         self.is_up
@@ -149,13 +343,319 @@ impl NetworkInfo {
         }
     }
 
-    fn get_mac_address(_interface_name: &str) -> String {
-        // Platform-specific implementation would go here
-        "00:00:00:00:00:00".to_string()
+    /// Name prefixes used by bridges, container networking, tunnels and VPNs
+    fn is_virtual_name(name: &str) -> bool {
+        const PREFIXES: &[&str] = &[
+            "veth", "docker", "br-", "virbr", "vmnet", "vboxnet", "tun", "tap", "wg",
+            "tailscale", "utun", "zt", "cni", "flannel", "cali", "vxlan", "podman", "awdl", "llw",
+            "bridge", "gif", "stf", "anpi",
+        ];
+        let lower = name.to_lowercase();
+        PREFIXES.iter().any(|prefix| lower.starts_with(prefix))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn fill_linux(&mut self) {
+        use std::fs;
+        use std::path::Path;
+        use crate::probe::Command;
+
+        let base = Path::new("/sys/class/net").join(&self.name);
+        let read = |attr: &str| {
+            fs::read_to_string(base.join(attr))
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        if let Some(state) = read("operstate") {
+            // "unknown" is reported by loopback and some tunnels that are up
+            self.is_up = state == "up" || (state == "unknown" && read("carrier").as_deref() == Some("1"));
+        }
+        // Reading speed/duplex fails with EINVAL while the link is down; -1 means unknown
+        self.speed_mbps = read("speed")
+            .and_then(|speed| speed.parse::<i64>().ok())
+            .filter(|speed| *speed > 0)
+            .and_then(|speed| u32::try_from(speed).ok());
+        self.duplex = match read("duplex").as_deref() {
+            Some("full") => Some(Duplex::Full),
+            Some("half") => Some(Duplex::Half),
+            _ => None,
+        };
+        if let Some(mtu) = read("mtu").and_then(|mtu| mtu.parse().ok()) {
+            self.mtu = Some(mtu);
+        }
+        if self.mac_address.is_empty() {
+            self.mac_address = read("address").unwrap_or_default();
+        }
+
+        // Physical NICs have a bus device; everything under /sys/devices/virtual is software
+        let canonical = fs::canonicalize(&base).unwrap_or_default();
+        self.is_virtual = canonical.starts_with("/sys/devices/virtual")
+            || base.join("bridge").is_dir()
+            || Self::is_virtual_name(&self.name);
+
+        // Predictable names (eno1, enp3s0, wlo1) defeat the name heuristics, so
+        // prefer the ARPHRD type: 1 = Ethernet, 772 = loopback
+        match read("type").as_deref() {
+            Some("772") => self.network_type = NetworkType::Loopback,
+            Some("1") if !self.is_virtual => self.network_type = NetworkType::Ethernet,
+            _ => {}
+        }
+
+        self.driver = fs::read_link(base.join("device/driver"))
+            .ok()
+            .and_then(|link| link.file_name().map(|name| name.to_string_lossy().into_owned()));
+        self.driver_version = self.driver.as_ref().and_then(|driver| {
+            fs::read_to_string(format!("/sys/module/{driver}/version"))
+                .ok()
+                .map(|version| version.trim().to_string())
+        });
+
+        // Fall back to ethtool for drivers without a module version (built-in or virtual)
+        if self.network_type != NetworkType::Loopback
+            && (self.driver.is_none() || self.driver_version.is_none())
+        {
+            if let Some((driver, version)) = Command::new("ethtool")
+                .args(["-i", &self.name])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .and_then(|output| parse_ethtool_driver(&String::from_utf8_lossy(&output.stdout)))
+            {
+                self.driver.get_or_insert(driver);
+                if version.is_some() {
+                    self.driver_version = version;
+                }
+            }
+        }
+
+        if base.join("wireless").is_dir() || base.join("phy80211").exists() {
+            self.network_type = NetworkType::WiFi;
+            self.wireless = Some(Self::wireless_linux(&self.name));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn wireless_linux(name: &str) -> WirelessInfo {
+        use crate::probe::Command;
+
+        let mut wireless = WirelessInfo::default();
+
+        // "wlan0: 0000   70.  -40.  -256        0      0      0      0      0        0"
+        if let Ok(proc) = std::fs::read_to_string("/proc/net/wireless") {
+            if let Some(fields) = proc.lines().find_map(|line| {
+                let (iface, rest) = line.trim().split_once(':')?;
+                (iface == name).then(|| rest.split_whitespace().collect::<Vec<_>>())
+            }) {
+                let number = |i: usize| fields.get(i)?.trim_end_matches('.').parse::<f64>().ok();
+                // Link quality is out of 70 for most cfg80211 drivers
+                wireless.signal_quality = number(1).map(|q| (q / 70.0 * 100.0).clamp(0.0, 100.0) as u8);
+                wireless.signal_dbm = number(2).map(|level| level as i32).filter(|level| *level < 0);
+            }
+        }
+
+        if let Ok(output) = Command::new("iw").args(["dev", name, "link"]).output() {
+            let text = String::from_utf8_lossy(&output.stdout);
+            for line in text.lines().map(str::trim) {
+                if let Some(ssid) = line.strip_prefix("SSID:") {
+                    wireless.ssid = Some(ssid.trim().to_string());
+                } else if let Some(signal) = line.strip_prefix("signal:") {
+                    wireless.signal_dbm = signal.split_whitespace().next().and_then(|v| v.parse().ok());
+                } else if let Some(freq) = line.strip_prefix("freq:") {
+                    wireless.frequency_mhz = freq.trim().parse::<f64>().ok().map(|f| f as u32);
+                }
+            }
+        }
+
+        wireless
+    }
+
+    #[cfg(target_os = "windows")]
+    fn fill_windows(interfaces: &mut [Self]) {
+        use std::collections::HashMap;
+        use wmi::{Variant, WMIConnection};
+
+        let Ok(com) = crate::probe::com_library() else {
+            return;
+        };
+        // MSFT_NetAdapter carries link and driver details Win32_NetworkAdapter lacks
+        let Ok(wmi_con) = WMIConnection::with_namespace_path(r"root\StandardCimv2", com) else {
+            return;
+        };
+        let adapters: Vec<HashMap<String, Variant>> = wmi_con
+            .raw_query(
+                "SELECT Name, InterfaceDescription, PermanentAddress, ReceiveLinkSpeed, FullDuplex, \
+                 MtuSize, DriverName, DriverVersionString, Virtual, NdisPhysicalMedium, MediaConnectState \
+                 FROM MSFT_NetAdapter",
+            )
+            .unwrap_or_default();
+
+        let string = |adapter: &HashMap<String, Variant>, key: &str| match adapter.get(key) {
+            Some(Variant::String(value)) if !value.is_empty() => Some(value.clone()),
+            _ => None,
+        };
+        let number = |adapter: &HashMap<String, Variant>, key: &str| match adapter.get(key) {
+            Some(Variant::UI1(v)) => Some(*v as u64),
+            Some(Variant::UI4(v)) => Some(*v as u64),
+            Some(Variant::UI8(v)) => Some(*v),
+            Some(Variant::I4(v)) => u64::try_from(*v).ok(),
+            _ => None,
+        };
+
+        let wlan = Self::wireless_windows();
+
+        for interface in interfaces.iter_mut() {
+            // sysinfo names interfaces after the adapter description on Windows
+            let Some(adapter) = adapters.iter().find(|adapter| {
+                string(adapter, "Name").as_deref() == Some(interface.name.as_str())
+                    || string(adapter, "InterfaceDescription").as_deref() == Some(interface.name.as_str())
+            }) else {
+                continue;
+            };
+
+            interface.speed_mbps = number(adapter, "ReceiveLinkSpeed")
+                .filter(|bps| *bps > 0)
+                .map(|bps| (bps / 1_000_000) as u32);
+            interface.duplex = match adapter.get("FullDuplex") {
+                Some(Variant::Bool(true)) => Some(Duplex::Full),
+                Some(Variant::Bool(false)) => Some(Duplex::Half),
+                _ => None,
+            };
+            interface.mtu = number(adapter, "MtuSize").map(|mtu| mtu as u32).or(interface.mtu);
+            interface.driver = string(adapter, "DriverName")
+                .map(|path| path.rsplit('\\').next().unwrap_or(&path).to_string());
+            interface.driver_version = string(adapter, "DriverVersionString");
+            if let Some(Variant::Bool(is_virtual)) = adapter.get("Virtual") {
+                interface.is_virtual = *is_virtual || interface.is_virtual;
+            }
+            // MediaConnectState: 1 = connected
+            if let Some(state) = number(adapter, "MediaConnectState") {
+                interface.is_up = state == 1;
+            }
+            // NdisPhysicalMedium: 9 = native 802.11, 14 = 802.3
+            match number(adapter, "NdisPhysicalMedium") {
+                Some(9) => {
+                    interface.network_type = NetworkType::WiFi;
+                    interface.wireless = Some(wlan.clone());
+                }
+                Some(14) => interface.network_type = NetworkType::Ethernet,
+                _ => {}
+            }
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn wireless_windows() -> WirelessInfo {
+        use crate::probe::Command;
+
+        let mut wireless = WirelessInfo::default();
+        let Ok(output) = Command::new("netsh").args(["wlan", "show", "interfaces"]).output() else {
+            return wireless;
+        };
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            // Labels other than SSID are localized, but only the signal line
+            // carries a percentage
+            if key.trim() == "SSID" {
+                wireless.ssid = Some(value.to_string());
+            } else if let Some(quality) = value.strip_suffix('%').and_then(|q| q.trim().parse().ok()) {
+                wireless.signal_quality = Some(quality);
+                // netsh reports quality; map it back to dBm the way Windows does
+                wireless.signal_dbm = Some(quality as i32 / 2 - 100);
+            }
+        }
+        wireless
     }
 
-    fn get_ip_addresses(_interface_name: &str) -> Vec<String> {
-        // Platform-specific implementation would go here
-        vec![]
+    #[cfg(target_os = "macos")]
+    fn fill_macos(&mut self) {
+        use crate::probe::Command;
+
+        use system_configuration::network_configuration::{SCNetworkInterfaceType, get_interfaces};
+
+        // SystemConfiguration knows the hardware type behind each BSD name
+        let interfaces = get_interfaces();
+        if let Some(interface) = interfaces
+            .iter()
+            .find(|interface| interface.bsd_name().is_some_and(|name| name.to_string() == self.name))
+        {
+            match interface.interface_type() {
+                Some(SCNetworkInterfaceType::IEEE80211) => self.network_type = NetworkType::WiFi,
+                Some(SCNetworkInterfaceType::Ethernet) => self.network_type = NetworkType::Ethernet,
+                Some(SCNetworkInterfaceType::Bluetooth) => self.network_type = NetworkType::Bluetooth,
+                Some(SCNetworkInterfaceType::WWAN) => self.network_type = NetworkType::Cellular,
+                Some(SCNetworkInterfaceType::Bridge)
+                | Some(SCNetworkInterfaceType::Bond)
+                | Some(SCNetworkInterfaceType::VLAN) => self.is_virtual = true,
+                _ => {}
+            }
+        }
+
+        if let Ok(output) = Command::new("ifconfig").arg(&self.name).output() {
+            let text = String::from_utf8_lossy(&output.stdout);
+            for line in text.lines().map(str::trim) {
+                if let Some(status) = line.strip_prefix("status: ") {
+                    self.is_up = status == "active";
+                } else if let Some(media) = line.strip_prefix("media: ") {
+                    // "autoselect (1000baseT <full-duplex>)"
+                    let (speed, duplex) = parse_media(media);
+                    self.speed_mbps = speed.or(self.speed_mbps);
+                    self.duplex = duplex.or(self.duplex);
+                } else if let Some(mtu) = line.split("mtu ").nth(1) {
+                    self.mtu = mtu.split_whitespace().next().and_then(|v| v.parse().ok()).or(self.mtu);
+                }
+            }
+        }
+
+        if self.network_type == NetworkType::WiFi {
+            let mut wireless = WirelessInfo::default();
+            if let Ok(output) = Command::new("networksetup")
+                .args(["-getairportnetwork", &self.name])
+                .output()
+            {
+                wireless.ssid = String::from_utf8_lossy(&output.stdout)
+                    .split_once("Network: ")
+                    .map(|(_, ssid)| ssid.trim().to_string());
+            }
+            self.wireless = Some(wireless);
+        }
     }
 }
+
+/// Parse driver name and version from `ethtool -i` output
+#[cfg(target_os = "linux")]
+fn parse_ethtool_driver(output: &str) -> Option<(String, Option<String>)> {
+    let field = |key: &str| {
+        output.lines().find_map(|line| {
+            let value = line.strip_prefix(key)?.trim();
+            (!value.is_empty()).then(|| value.to_string())
+        })
+    };
+    Some((field("driver:")?, field("version:")))
+}
+
+/// Parse speed and duplex from an ifconfig media line
+#[cfg(target_os = "macos")]
+fn parse_media(media: &str) -> (Option<u32>, Option<Duplex>) {
+    // "1000baseT", "10Gbase-T", "2500Base-T"
+    let speed = media.split(['(', ' ']).find_map(|word| {
+        let lower = word.to_lowercase();
+        let (number, _) = lower.split_once("base")?;
+        match number.strip_suffix('g') {
+            Some(gigabits) => gigabits.parse::<u32>().ok().map(|g| g * 1000),
+            None => number.parse().ok(),
+        }
+    });
+    let duplex = if media.contains("full-duplex") {
+        Some(Duplex::Full)
+    } else if media.contains("half-duplex") {
+        Some(Duplex::Half)
+    } else {
+        None
+    };
+    (speed, duplex)
+}
@@ -1,12 +1,12 @@
-use crate::Result;
+use crate::{PCIDevice, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 #[cfg(target_os = "windows")]
-use wmi::{COMLibrary, WMIConnection};
+use wmi::WMIConnection;
 
 #[cfg(target_os = "linux")]
-use std::process::Command;
+use crate::probe::Command;
 
 /// NPU vendor information
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -208,7 +208,7 @@ impl NPUInfo {
         #[cfg(target_os = "windows")]
         {
             // Windows detection via WMI and device manager
-            if let Ok(com_con) = COMLibrary::new() {
+            if let Ok(com_con) = crate::probe::com_library() {
                 if let Ok(wmi_con) = WMIConnection::new(com_con) {
                     let query = "SELECT * FROM Win32_USBHub WHERE DeviceID LIKE '%VID_03E7%'";
                     if let Ok(results) = wmi_con.raw_query(query) {
@@ -285,7 +285,7 @@ impl NPUInfo {
         #[cfg(target_os = "windows")]
         {
             // Windows GNA detection via device manager
-            if let Ok(com_con) = COMLibrary::new() {
+            if let Ok(com_con) = crate::probe::com_library() {
                 if let Ok(wmi_con) = WMIConnection::new(com_con) {
                     let query = "SELECT * FROM Win32_PnPEntity WHERE Description LIKE '%GNA%' OR Name LIKE '%Gaussian%'";
                     if let Ok(results) = wmi_con.raw_query(query) {
@@ -329,7 +329,7 @@ impl NPUInfo {
         // Intel XDNA (Meteor Lake and newer integrated NPU)
         #[cfg(target_os = "windows")]
         {
-            if let Ok(com_con) = COMLibrary::new() {
+            if let Ok(com_con) = crate::probe::com_library() {
                 if let Ok(wmi_con) = WMIConnection::new(com_con) {
                     let query = "SELECT * FROM Win32_PnPEntity WHERE Description LIKE '%NPU%' OR Name LIKE '%Neural%'";
                     if let Ok(results) = wmi_con.raw_query(query) {
@@ -485,8 +485,56 @@ impl NPUInfo {
     }
     
     fn detect_pcie_npus() -> Result<Vec<NPUInfo>> {
-        // Note: PCIe NPU detection requires device-specific identification methods
-        Ok(Vec::new())
+        let mut npus = Vec::new();
+        
+        for device in PCIDevice::query_all().unwrap_or_default() {
+            let (Some(vendor_id), Some(device_id)) = (device.vendor_id(), device.product_id()) else {
+                continue;
+            };
+            
+            let (vendor, npu_type, architecture, tops, frameworks): (_, _, _, _, &[&str]) = match (vendor_id, device_id) {
+                // Hailo-8 / Hailo-8L M.2 and PCIe modules
+                (0x1E60, _) => (NPUVendor::Hailo, NPUType::M2, NPUArchitecture::HailoNPU, Some(26.0), &["Hailo Dataflow Compiler", "TensorFlow Lite", "ONNX"]),
+                // Ryzen AI (XDNA) in Phoenix / Hawk Point and Strix Point
+                (0x1022, 0x1502) => (NPUVendor::AMD, NPUType::Integrated, NPUArchitecture::AMDRyzenAI, Some(16.0), &["ONNX Runtime", "Vitis AI"]),
+                (0x1022, 0x17F0) => (NPUVendor::AMD, NPUType::Integrated, NPUArchitecture::AMDRyzenAI, Some(50.0), &["ONNX Runtime", "Vitis AI"]),
+                // Intel NPU in Meteor Lake, Arrow Lake and Lunar Lake (Windows reports it through WMI)
+                (0x8086, 0x7D1D | 0xAD1D | 0x643E) if !cfg!(target_os = "windows") => (
+                    NPUVendor::Intel,
+                    NPUType::Integrated,
+                    NPUArchitecture::IntelXDNA,
+                    if device_id == 0x643E { Some(48.0) } else { Some(11.5) },
+                    &["OpenVINO", "ONNX Runtime"],
+                ),
+                _ => continue,
+            };
+            
+            // Model names come from the PCI ID database
+            let model_name = Some(device.device_name())
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{vendor} NPU {}", device.device_id()));
+            
+            npus.push(NPUInfo {
+                vendor,
+                model_name,
+                npu_type,
+                architecture,
+                tops_performance: tops,
+                memory_mb: None,
+                driver_version: None,
+                firmware_version: None,
+                pci_device_id: Some(device.device_id().to_string()),
+                usb_device_id: None,
+                supported_frameworks: frameworks.iter().map(|f| f.to_string()).collect(),
+                power_consumption: None,
+                temperature: None,
+                clock_frequency: None,
+                capabilities: HashMap::new(),
+            });
+        }
+        
+        Ok(npus)
     }
     
     /// Get NPU vendor
@@ -514,4 +562,34 @@ impl NPUInfo {
         self.supported_frameworks.iter()
             .any(|f| f.to_lowercase().contains(&framework.to_lowercase()))
     }
+
+    /// Get the data types the NPU computes natively
+    ///
+    /// Uses a comma-separated `dtypes` entry in [`capabilities`](Self::capabilities)
+    /// when the driver reported one, otherwise the architecture's published
+    /// precisions.
+    pub fn supported_dtypes(&self) -> Vec<String> {
+        if let Some(dtypes) = self.capabilities.get("dtypes") {
+            return dtypes.split(',').map(|d| d.trim().to_lowercase()).filter(|d| !d.is_empty()).collect();
+        }
+        let dtypes: &[&str] = match self.architecture {
+            NPUArchitecture::IntelGNA => &["int8", "int16"],
+            NPUArchitecture::IntelMovidius => &["float16", "int8"],
+            NPUArchitecture::IntelXDNA => &["float16", "bfloat16", "int8", "int4"],
+            NPUArchitecture::QualcommHexagon => &["float16", "int16", "int8", "int4"],
+            NPUArchitecture::AppleNeuralEngine => &["float16", "int8"],
+            NPUArchitecture::GoogleTPU => &["int8"],
+            NPUArchitecture::AMDRyzenAI => &["bfloat16", "int8", "int4"],
+            NPUArchitecture::MediaTekAPU | NPUArchitecture::SamsungNPU => &["float16", "int8"],
+            NPUArchitecture::HailoNPU => &["int8", "int4"],
+            NPUArchitecture::KneronKL => &["int8"],
+            NPUArchitecture::Unknown(_) => &[],
+        };
+        dtypes.iter().map(|d| d.to_string()).collect()
+    }
+
+    /// Check if NPU supports a specific data type
+    pub fn supports_dtype(&self, dtype: &str) -> bool {
+        self.supported_dtypes().iter().any(|d| d.eq_ignore_ascii_case(dtype))
+    }
 }
@@ -0,0 +1,190 @@
+//! ONNX Runtime execution provider selection
+//!
+//! ONNX Runtime tries execution providers in the order a session lists
+//! them and falls back to the next one when a provider can't be created.
+//! [`HardwarePresets::onnx_execution_providers`](crate::HardwarePresets::onnx_execution_providers)
+//! builds that list from the detected accelerators and installed runtimes:
+//! providers whose hardware is present come first, ordered by expected
+//! throughput, with the ones whose runtime libraries were not found after
+//! them. The CPU provider always closes the list.
+
+use crate::{AIRuntime, CPUVendor, GPUVendor, GgmlCpuPath, GpuCriteria, HardwareInfo, NPUVendor, SoftwareStack};
+use serde::{Deserialize, Serialize};
+
+/// ONNX Runtime execution provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OnnxExecutionProvider {
+    /// NVIDIA TensorRT
+    TensorRT,
+    /// NVIDIA CUDA
+    CUDA,
+    /// AMD ROCm
+    ROCm,
+    /// Intel OpenVINO (CPU, integrated/Arc GPU, NPU)
+    OpenVINO,
+    /// DirectML on any Direct3D 12 GPU (Windows)
+    DirectML,
+    /// Apple Core ML (Neural Engine, GPU, CPU)
+    CoreML,
+    /// Default CPU provider
+    CPU,
+}
+
+impl OnnxExecutionProvider {
+    /// Provider name as passed to ONNX Runtime (`CUDAExecutionProvider`)
+    pub fn ort_name(&self) -> &'static str {
+        match self {
+            OnnxExecutionProvider::TensorRT => "TensorrtExecutionProvider",
+            OnnxExecutionProvider::CUDA => "CUDAExecutionProvider",
+            OnnxExecutionProvider::ROCm => "ROCMExecutionProvider",
+            OnnxExecutionProvider::OpenVINO => "OpenVINOExecutionProvider",
+            OnnxExecutionProvider::DirectML => "DmlExecutionProvider",
+            OnnxExecutionProvider::CoreML => "CoreMLExecutionProvider",
+            OnnxExecutionProvider::CPU => "CPUExecutionProvider",
+        }
+    }
+}
+
+impl std::fmt::Display for OnnxExecutionProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OnnxExecutionProvider::TensorRT => write!(f, "TensorRT"),
+            OnnxExecutionProvider::CUDA => write!(f, "CUDA"),
+            OnnxExecutionProvider::ROCm => write!(f, "ROCm"),
+            OnnxExecutionProvider::OpenVINO => write!(f, "OpenVINO"),
+            OnnxExecutionProvider::DirectML => write!(f, "DirectML"),
+            OnnxExecutionProvider::CoreML => write!(f, "Core ML"),
+            OnnxExecutionProvider::CPU => write!(f, "CPU"),
+        }
+    }
+}
+
+/// One entry of the recommended provider list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OnnxProviderRecommendation {
+    /// Execution provider
+    pub provider: OnnxExecutionProvider,
+    /// Index into [`HardwareInfo::gpus`] of the GPU it should run on
+    pub gpu_index: Option<usize>,
+    /// Device type option for providers that take one (OpenVINO `GPU`/`NPU`/`CPU`)
+    pub device_type: Option<String>,
+    /// Whether the runtime libraries the provider loads were found
+    pub runtime_ready: bool,
+    /// Why the provider is listed, and what is missing if it isn't ready
+    pub reason: String,
+}
+
+/// Ordered provider list for `hardware` with the runtimes in `software`
+pub(crate) fn recommend(hardware: &HardwareInfo, software: &SoftwareStack) -> Vec<OnnxProviderRecommendation> {
+    let mut recommendations = Vec::new();
+    let mut push = |provider, gpu_index, device_type: Option<&str>, runtime_ready, reason: String| {
+        recommendations.push(OnnxProviderRecommendation {
+            provider,
+            gpu_index,
+            device_type: device_type.map(str::to_string),
+            runtime_ready,
+            reason,
+        });
+    };
+    let version = |runtime: AIRuntime| software.version(runtime).unwrap_or("installed");
+    let gpus = hardware.gpus();
+
+    // TensorRT 10 dropped everything before Turing
+    if let Some(selection) = hardware.select_gpu(&GpuCriteria::most_vram().require_cuda("7.5").dedicated_only()) {
+        let gpu = selection.gpu;
+        let capability = gpu.cuda_capability().unwrap_or_default();
+        let (ready, reason) = match (software.has(AIRuntime::TensorRT), software.has(AIRuntime::CUDA)) {
+            (true, true) => (
+                true,
+                format!("{} (compute {capability}) with TensorRT {}", gpu.model_name(), version(AIRuntime::TensorRT)),
+            ),
+            _ => (
+                false,
+                format!("{} (compute {capability}) supports TensorRT; install TensorRT and the CUDA toolkit", gpu.model_name()),
+            ),
+        };
+        push(OnnxExecutionProvider::TensorRT, Some(selection.index), None, ready, reason);
+    }
+
+    if let Some(selection) = hardware.select_gpu(&GpuCriteria::most_vram().require_cuda("6.0")) {
+        let gpu = selection.gpu;
+        let (ready, reason) = match (software.has(AIRuntime::CUDA), software.has(AIRuntime::CuDNN)) {
+            (true, true) => (
+                true,
+                format!(
+                    "{} with CUDA {} and cuDNN {}",
+                    gpu.model_name(),
+                    version(AIRuntime::CUDA),
+                    version(AIRuntime::CuDNN)
+                ),
+            ),
+            (true, false) => (false, format!("{} with CUDA {}; install cuDNN", gpu.model_name(), version(AIRuntime::CUDA))),
+            _ => (false, format!("{}; install the CUDA toolkit and cuDNN", gpu.model_name())),
+        };
+        push(OnnxExecutionProvider::CUDA, Some(selection.index), None, ready, reason);
+    }
+
+    if cfg!(target_os = "linux") {
+        if let Some((index, gpu)) = gpus.iter().enumerate().find(|(_, gpu)| gpu.vendor == GPUVendor::AMD && gpu.supports_rocm()) {
+            let (ready, reason) = if software.has(AIRuntime::ROCm) {
+                (true, format!("{} with ROCm {}", gpu.model_name(), version(AIRuntime::ROCm)))
+            } else {
+                (false, format!("{} is ROCm-capable; install ROCm", gpu.model_name()))
+            };
+            push(OnnxExecutionProvider::ROCm, Some(index), None, ready, reason);
+        }
+    }
+
+    // OpenVINO targets the Intel NPU, then an Intel GPU, then the CPU
+    let intel_npu = hardware.npus().iter().find(|npu| npu.vendor == NPUVendor::Intel);
+    let intel_gpu = gpus.iter().enumerate().find(|(_, gpu)| gpu.vendor == GPUVendor::Intel);
+    let intel_cpu = *hardware.cpu().vendor() == CPUVendor::Intel;
+    let openvino_target = match (intel_npu, intel_gpu) {
+        (Some(npu), _) => Some((None, "NPU", npu.model_name.clone())),
+        (None, Some((index, gpu))) => Some((Some(index), "GPU", gpu.model_name().to_string())),
+        (None, None) if intel_cpu => Some((None, "CPU", hardware.cpu().model_name().to_string())),
+        _ => None,
+    };
+    if let Some((gpu_index, device_type, device)) = openvino_target {
+        let (ready, reason) = if software.has(AIRuntime::OpenVINO) {
+            (true, format!("{device} with OpenVINO {}", version(AIRuntime::OpenVINO)))
+        } else {
+            (false, format!("{device} is supported by OpenVINO; install the OpenVINO runtime"))
+        };
+        push(OnnxExecutionProvider::OpenVINO, gpu_index, Some(device_type), ready, reason);
+    }
+
+    if cfg!(target_os = "windows") {
+        let criteria = GpuCriteria::most_vram();
+        if let Some(selection) = criteria.rank(gpus).into_iter().find(|selection| selection.gpu.supports_directml()) {
+            // onnxruntime-directml bundles its own DirectML.dll
+            push(
+                OnnxExecutionProvider::DirectML,
+                Some(selection.index),
+                None,
+                true,
+                format!("{} through Direct3D 12", selection.gpu.model_name()),
+            );
+        }
+    }
+
+    if cfg!(target_os = "macos") {
+        let apple_silicon = gpus.iter().position(|gpu| gpu.vendor == GPUVendor::Apple);
+        let reason = match apple_silicon {
+            Some(index) => format!("Neural Engine and {} GPU through Core ML", gpus[index].model_name()),
+            None => "GPU and CPU through Core ML; no Neural Engine on Intel Macs".to_string(),
+        };
+        push(OnnxExecutionProvider::CoreML, apple_silicon, None, true, reason);
+    }
+
+    let cpu = hardware.cpu();
+    let reason = match GgmlCpuPath::from_cpu(cpu) {
+        GgmlCpuPath::Scalar => format!("{} ({} cores)", cpu.model_name(), cpu.physical_cores()),
+        simd => format!("{} ({} cores, {simd})", cpu.model_name(), cpu.physical_cores()),
+    };
+    push(OnnxExecutionProvider::CPU, None, None, true, reason);
+
+    // Stable, so each group keeps the throughput order
+    recommendations.sort_by_key(|recommendation| !recommendation.runtime_ready);
+    recommendations
+}
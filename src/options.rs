@@ -0,0 +1,194 @@
+//! Time-bounded hardware queries
+//!
+//! Some detection paths can stall for a long time: WMI providers that never
+//! answer, sysfs attributes on a wedged driver, or external tools waiting on a
+//! device. [`QueryOptions`] lets callers put an upper bound on how long
+//! [`HardwareInfo::query_with_options`](crate::HardwareInfo::query_with_options)
+//! waits, trading completeness for a predictable response time, and whether
+//! it may spawn external tools at all.
+
+use crate::hardware_info::{ComponentError, ComponentErrorKind};
+use crate::probe::{self, ProbePolicy};
+use crate::{ErrorCode, HardwareComponent, Result};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, RecvTimeoutError};
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Timeouts for [`HardwareInfo::query_with_options`](crate::HardwareInfo::query_with_options)
+///
+/// Each component group is queried on its own thread. Components that do not
+/// finish in time are reported through
+/// [`HardwareInfo::errors`](crate::HardwareInfo::errors) and filled with
+/// placeholder values. Their threads are left to finish in the background and
+/// their results are discarded.
+#[derive(Debug, Clone)]
+pub struct QueryOptions {
+    /// Upper bound for the whole query (`None` waits for every component)
+    pub timeout: Option<Duration>,
+    /// Upper bounds for individual component groups
+    pub component_timeouts: HashMap<HardwareComponent, Duration>,
+    /// Allow spawning external tools (`lsusb`, `dmidecode`, `nvidia-smi`, ...)
+    pub external_commands: bool,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            component_timeouts: HashMap::new(),
+            external_commands: true,
+        }
+    }
+}
+
+impl QueryOptions {
+    /// Options without any timeouts
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow or forbid spawning external tools
+    ///
+    /// With `false`, components are detected through native APIs and files
+    /// only. [`NO_EXTERNAL_COMMANDS_ENV_VAR`](crate::NO_EXTERNAL_COMMANDS_ENV_VAR)
+    /// forbids them for the whole process regardless of this setting.
+    pub fn allow_external_commands(mut self, allow: bool) -> Self {
+        self.external_commands = allow;
+        self
+    }
+
+    /// Restrict the current thread to the detection mechanisms these options allow
+    pub(crate) fn restrict(&self) -> probe::PolicyGuard {
+        let current = probe::policy();
+        probe::restrict(ProbePolicy {
+            external_commands: current.external_commands && self.external_commands,
+            ..current
+        })
+    }
+
+    /// Set the overall timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout for a single component group
+    pub fn with_component_timeout(mut self, component: HardwareComponent, timeout: Duration) -> Self {
+        self.component_timeouts.insert(component, timeout);
+        self
+    }
+
+    /// Effective timeout for a component: the tighter of its own and the overall timeout
+    pub fn timeout_for(&self, component: HardwareComponent) -> Option<Duration> {
+        match (self.timeout, self.component_timeouts.get(&component).copied()) {
+            (Some(overall), Some(own)) => Some(overall.min(own)),
+            (overall, own) => overall.or(own),
+        }
+    }
+}
+
+/// A component query running on its own thread
+pub(crate) struct Probe<T> {
+    component: HardwareComponent,
+    receiver: mpsc::Receiver<Result<T>>,
+}
+
+impl<T: Send + 'static> Probe<T> {
+    /// Start `query` on a background thread under the current thread's probe policy
+    ///
+    /// wasm32 has no threads, so the query runs to completion here.
+    pub(crate) fn spawn(
+        component: HardwareComponent,
+        query: impl FnOnce() -> Result<T> + Send + 'static,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        #[cfg(target_arch = "wasm32")]
+        let _ = sender.send(query());
+        // If the thread cannot be spawned the sender is dropped and `finish`
+        // reports the component as failed
+        #[cfg(not(target_arch = "wasm32"))]
+        let policy = probe::policy();
+        #[cfg(not(target_arch = "wasm32"))]
+        let _ = thread::Builder::new()
+            .name(format!("hardware-query-{component}"))
+            .spawn(move || {
+                let _restriction = probe::restrict(policy);
+                let _ = sender.send(query());
+            });
+        Self { component, receiver }
+    }
+
+    /// Wait for the result until the component's deadline
+    ///
+    /// If the query fails or times out the problem is appended to `errors`
+    /// and `fallback` provides the component's value instead.
+    pub(crate) fn finish(
+        self,
+        options: &QueryOptions,
+        started: Option<Instant>,
+        errors: &mut Vec<ComponentError>,
+        fallback: impl FnOnce() -> T,
+    ) -> T {
+        let received = match (options.timeout_for(self.component), started) {
+            (Some(timeout), Some(started)) => {
+                let remaining = (started + timeout).saturating_duration_since(Instant::now());
+                self.receiver.recv_timeout(remaining)
+            }
+            _ => self.receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        let (kind, code, message) = match received {
+            Ok(Ok(value)) => return value,
+            Ok(Err(e)) => {
+                errors.push(ComponentError::from_error(self.component, &e));
+                return fallback();
+            }
+            Err(RecvTimeoutError::Timeout) => (
+                ComponentErrorKind::TimedOut,
+                ErrorCode::Timeout,
+                format!("{} query did not finish in time", self.component),
+            ),
+            Err(RecvTimeoutError::Disconnected) => (
+                ComponentErrorKind::Failed,
+                ErrorCode::Unknown,
+                format!("{} query thread exited without a result", self.component),
+            ),
+        };
+        errors.push(ComponentError {
+            component: self.component,
+            kind,
+            message,
+            code,
+        });
+        fallback()
+    }
+}
+
+/// Start time for [`Probe::finish`] deadlines
+///
+/// `None` on wasm32, which has no monotonic clock (`Instant::now` panics)
+/// and runs probes inline, so there is nothing to time out.
+pub(crate) fn query_started() -> Option<Instant> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        None
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        Some(Instant::now())
+    }
+}
+
+/// Treat "not present" errors from an optional component as `None`
+///
+/// A missing battery or an unsupported power interface is not a detection
+/// problem, so only other errors are reported.
+pub(crate) fn optional<T>(result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.is_not_present() => Ok(None),
+        Err(e) => Err(e),
+    }
+}
@@ -0,0 +1,189 @@
+//! Thread pool sizing
+//!
+//! `num_cpus`-style logical CPU counts over-subscribe compute pools on SMT
+//! machines, let straggling efficiency cores hold up evenly split work on
+//! hybrid CPUs, and ignore the CPU quota of a container. A
+//! [`ParallelismRecommendation`] takes all of these into account, along with
+//! the NUMA and L3 cache layout a pool should follow.
+//!
+//! ```rust
+//! use hardware_query::{HardwareInfo, WorkloadKind};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let hw_info = HardwareInfo::query()?;
+//! let compute = hw_info.cpu().recommended_parallelism(WorkloadKind::Compute);
+//! println!("{} compute threads", compute.threads);
+//! for note in &compute.notes {
+//!     println!("  {note}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{CPUInfo, CacheType, CoreType, ResourceLimits};
+use serde::{Deserialize, Serialize};
+
+/// What the threads of a pool spend their time on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorkloadKind {
+    /// CPU-bound work (compilation, number crunching, compression)
+    Compute,
+    /// Blocking I/O (disk, network, database calls)
+    Io,
+    /// A mix of CPU work and blocking, e.g. request handlers
+    Mixed,
+}
+
+impl std::fmt::Display for WorkloadKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkloadKind::Compute => write!(f, "Compute"),
+            WorkloadKind::Io => write!(f, "I/O"),
+            WorkloadKind::Mixed => write!(f, "Mixed"),
+        }
+    }
+}
+
+/// Recommended thread pool size for a [`WorkloadKind`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParallelismRecommendation {
+    /// Workload the recommendation is for
+    pub kind: WorkloadKind,
+    /// Recommended number of threads
+    pub threads: u32,
+    /// Threads for work split into equal chunks up front; leaves out
+    /// efficiency cores on hybrid CPUs, otherwise the same as `threads`
+    pub performance_threads: u32,
+    /// Suggested `(node id, threads)` split for one pool per NUMA node;
+    /// empty on single-node machines
+    pub per_numa_node: Vec<(u32, u32)>,
+    /// Number of L3 cache domains; threads sharing data run best within one
+    pub cache_domains: u32,
+    /// CPUs' worth of time the process may use after quotas and affinity
+    pub cpu_budget: f64,
+    /// Why the numbers came out the way they did
+    pub notes: Vec<String>,
+}
+
+/// Threads I/O-bound pools run per CPU of budget, since most are blocked at any time
+const IO_THREADS_PER_CPU: u32 = 2;
+
+/// Smallest I/O pool worth recommending, even on a single CPU
+const MIN_IO_THREADS: u32 = 4;
+
+impl ParallelismRecommendation {
+    /// Size a pool on `cpu` for a process that may use `cpu_budget` CPUs
+    pub(crate) fn new(cpu: &CPUInfo, kind: WorkloadKind, cpu_budget: Option<f64>) -> Self {
+        let topology = cpu.topology();
+        let logical = cpu.logical_cores().max(1);
+        let physical = if topology.clusters().is_empty() {
+            cpu.physical_cores()
+        } else {
+            topology.clusters().iter().map(|cluster| cluster.physical_cores()).sum()
+        }
+        .clamp(1, logical);
+        let performance = if topology.is_heterogeneous() {
+            topology.cores_of_type(CoreType::Performance).clamp(1, physical)
+        } else {
+            physical
+        };
+
+        let budget = cpu_budget.map_or(logical as f64, |budget| budget.min(logical as f64));
+        let whole = (budget.floor() as u32).max(1);
+        let mut notes = Vec::new();
+        if budget < logical as f64 {
+            notes.push(format!("CPU quota/affinity allows {budget:.1} of {logical} logical CPUs"));
+        }
+
+        let (threads, performance_threads) = match kind {
+            WorkloadKind::Compute => {
+                if logical > physical {
+                    notes.push(format!(
+                        "One thread per physical core ({physical}); SMT siblings add little to CPU-bound throughput"
+                    ));
+                }
+                (physical.min(whole), performance.min(whole))
+            }
+            WorkloadKind::Io => {
+                let threads = (whole * IO_THREADS_PER_CPU).max(MIN_IO_THREADS);
+                notes.push(format!(
+                    "{IO_THREADS_PER_CPU} threads per available CPU, since I/O-bound threads spend most of their time blocked"
+                ));
+                (threads, threads)
+            }
+            WorkloadKind::Mixed => (logical.min(whole), performance.min(whole)),
+        };
+        if performance_threads < threads && kind != WorkloadKind::Io {
+            notes.push(format!(
+                "{} efficiency cores: use {performance_threads} threads for evenly split work so they don't become stragglers",
+                physical - performance
+            ));
+        }
+
+        let per_numa_node = Self::split_across_nodes(cpu, threads);
+        if !per_numa_node.is_empty() {
+            notes.push(format!(
+                "{} NUMA nodes: run one pool per node and allocate memory on the local node",
+                per_numa_node.len()
+            ));
+        }
+
+        let mut l3_domains: Vec<&[u32]> = topology
+            .caches()
+            .iter()
+            .filter(|cache| cache.level == 3 && cache.cache_type != CacheType::Instruction)
+            .map(|cache| cache.shared_cpus.as_slice())
+            .collect();
+        l3_domains.sort();
+        l3_domains.dedup();
+        let cache_domains = (l3_domains.len() as u32).max(1);
+        if cache_domains > 1 && kind != WorkloadKind::Io {
+            notes.push(format!(
+                "{cache_domains} L3 cache domains: keep threads that share data within one domain"
+            ));
+        }
+
+        Self {
+            kind,
+            threads,
+            performance_threads,
+            per_numa_node,
+            cache_domains,
+            cpu_budget: budget,
+            notes,
+        }
+    }
+
+    /// Split `threads` over NUMA nodes in proportion to their CPUs
+    fn split_across_nodes(cpu: &CPUInfo, threads: u32) -> Vec<(u32, u32)> {
+        let topology = cpu.topology();
+        let nodes: Vec<_> = topology.numa_nodes().iter().filter(|node| !node.cpus.is_empty()).collect();
+        if nodes.len() < 2 {
+            return Vec::new();
+        }
+        let total: usize = nodes.iter().map(|node| node.cpus.len()).sum();
+        nodes
+            .iter()
+            .map(|node| {
+                let share = threads as f64 * node.cpus.len() as f64 / total as f64;
+                (node.id, (share.round() as u32).max(1))
+            })
+            .collect()
+    }
+}
+
+impl CPUInfo {
+    /// Get a thread pool size for `kind` within this process's CPU budget
+    ///
+    /// The budget comes from [`std::thread::available_parallelism`], which
+    /// honours cgroup CPU quotas and the process's affinity mask.
+    pub fn recommended_parallelism(&self, kind: WorkloadKind) -> ParallelismRecommendation {
+        let budget = std::thread::available_parallelism().ok().map(|threads| threads.get() as f64);
+        ParallelismRecommendation::new(self, kind, budget)
+    }
+
+    /// Get a thread pool size for `kind` within explicit container limits
+    pub fn recommended_parallelism_within(&self, kind: WorkloadKind, limits: &ResourceLimits) -> ParallelismRecommendation {
+        ParallelismRecommendation::new(self, kind, limits.cpu_limit_cores())
+    }
+}
@@ -24,14 +24,39 @@ pub struct PCIDevice {
     pub irq: Option<u32>,
     /// Memory regions
     pub memory_regions: Vec<String>,
+    /// Subsystem (board) name
+    #[serde(default)]
+    pub subsystem_name: Option<String>,
+    /// 24-bit class code (base class, subclass, programming interface)
+    #[serde(default)]
+    pub class_code: Option<u32>,
+    /// IOMMU group (if the IOMMU is enabled)
+    #[serde(default)]
+    pub iommu_group: Option<u32>,
 }
 
 impl PCIDevice {
     /// Query all PCI devices
+    ///
+    /// Names missing from the platform are resolved through the system
+    /// `pci.ids` database (installed by pciutils / hwdata).
     pub fn query_all() -> Result<Vec<Self>> {
-        // Platform-specific implementation would go here
-        // For now, return empty vector
-        Ok(vec![])
+        #[cfg(target_os = "linux")]
+        {
+            Self::query_linux()
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Self::query_windows()
+        }
+        #[cfg(target_os = "macos")]
+        {
+            Self::query_macos()
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            Ok(Vec::new())
+        }
     }
 
     /// Get device ID
@@ -49,11 +74,48 @@ impl PCIDevice {
         &self.device_name
     }
 
+    /// Get subsystem name
+    pub fn subsystem_name(&self) -> Option<&str> {
+        self.subsystem_name.as_deref()
+    }
+
     /// Get device class
     pub fn device_class(&self) -> &str {
         &self.device_class
     }
 
+    /// Get numeric vendor ID
+    pub fn vendor_id(&self) -> Option<u16> {
+        crate::ids::hex(self.device_id.split(':').next()?)
+    }
+
+    /// Get numeric device ID
+    pub fn product_id(&self) -> Option<u16> {
+        crate::ids::hex(self.device_id.split(':').nth(1)?)
+    }
+
+    /// Get PCI base class (e.g. 0x03 for display controllers)
+    pub fn base_class(&self) -> Option<u8> {
+        self.class_code.map(|class| (class >> 16) as u8)
+    }
+
+    /// Get IOMMU group
+    pub fn iommu_group(&self) -> Option<u32> {
+        self.iommu_group
+    }
+
+    /// Get the slot (domain:bus:device) shared by all functions of a physical device
+    pub fn slot(&self) -> &str {
+        self.bus_location
+            .rsplit_once('.')
+            .map_or(self.bus_location.as_str(), |(slot, _)| slot)
+    }
+
+    /// Check if device is a PCI bridge or root port
+    pub fn is_bridge(&self) -> bool {
+        self.base_class() == Some(0x06)
+    }
+
     /// Check if device is a graphics card
     pub fn is_graphics_device(&self) -> bool {
         self.device_class.to_lowercase().contains("vga")
@@ -75,4 +137,355 @@ impl PCIDevice {
             || self.device_class.to_lowercase().contains("nvme")
             || self.device_class.to_lowercase().contains("scsi")
     }
+
+    /// Look up a vendor name in the PCI ID database
+    pub fn lookup_vendor_name(vendor_id: u16) -> Option<String> {
+        crate::ids::pci_ids().vendor(vendor_id).map(str::to_string)
+    }
+
+    /// Look up a device name in the PCI ID database
+    pub fn lookup_device_name(vendor_id: u16, device_id: u16) -> Option<String> {
+        crate::ids::pci_ids()
+            .device(vendor_id, device_id)
+            .map(str::to_string)
+    }
+
+    /// Look up a subsystem name in the PCI ID database
+    pub fn lookup_subsystem_name(
+        vendor_id: u16,
+        device_id: u16,
+        subsystem_vendor_id: u16,
+        subsystem_device_id: u16,
+    ) -> Option<String> {
+        crate::ids::pci_ids()
+            .subsystem(vendor_id, device_id, subsystem_vendor_id, subsystem_device_id)
+            .map(str::to_string)
+    }
+
+    /// Look up a class name in the PCI ID database from a 24-bit class code
+    pub fn lookup_class_name(class_code: u32) -> Option<String> {
+        crate::ids::pci_ids()
+            .class((class_code >> 16) as u8, (class_code >> 8) as u8)
+            .map(str::to_string)
+    }
+
+    /// Build a device from raw IDs, filling names from the PCI ID database
+    pub(crate) fn from_ids(
+        vendor: u16,
+        device: u16,
+        subsystem: Option<(u16, u16)>,
+        class_code: Option<u32>,
+    ) -> Self {
+        Self {
+            device_id: format!("{vendor:04x}:{device:04x}"),
+            vendor_name: Self::lookup_vendor_name(vendor).unwrap_or_default(),
+            device_name: Self::lookup_device_name(vendor, device).unwrap_or_default(),
+            bus_location: String::new(),
+            device_class: class_code
+                .map(|class| {
+                    Self::lookup_class_name(class)
+                        .unwrap_or_else(|| base_class_name((class >> 16) as u8).to_string())
+                })
+                .unwrap_or_default(),
+            subsystem_id: subsystem.map(|(sv, sd)| format!("{sv:04x}:{sd:04x}")),
+            driver: None,
+            revision: None,
+            irq: None,
+            memory_regions: Vec::new(),
+            subsystem_name: subsystem
+                .and_then(|(sv, sd)| Self::lookup_subsystem_name(vendor, device, sv, sd)),
+            class_code,
+            iommu_group: None,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn query_linux() -> Result<Vec<Self>> {
+        use std::fs;
+
+        let Ok(entries) = fs::read_dir("/sys/bus/pci/devices") else {
+            return Ok(Vec::new());
+        };
+
+        let mut devices = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let read = |attr: &str| {
+                fs::read_to_string(path.join(attr))
+                    .ok()
+                    .map(|value| value.trim().to_string())
+            };
+            let id = |attr: &str| read(attr).and_then(|value| crate::ids::hex(&value));
+
+            let (Some(vendor), Some(device)) = (id("vendor"), id("device")) else {
+                continue;
+            };
+            let subsystem = id("subsystem_vendor").zip(id("subsystem_device"));
+            let class_code = read("class")
+                .and_then(|value| u32::from_str_radix(value.trim_start_matches("0x"), 16).ok());
+
+            let mut pci = Self::from_ids(vendor, device, subsystem, class_code);
+            pci.bus_location = entry.file_name().to_string_lossy().into_owned();
+            pci.driver = fs::read_link(path.join("driver"))
+                .ok()
+                .and_then(|link| link.file_name().map(|n| n.to_string_lossy().into_owned()));
+            pci.revision = read("revision");
+            pci.irq = read("irq").and_then(|irq| irq.parse().ok()).filter(|irq| *irq > 0);
+            pci.iommu_group = fs::read_link(path.join("iommu_group"))
+                .ok()
+                .and_then(|link| link.file_name()?.to_str()?.parse().ok());
+            // "start end flags" per BAR; unused BARs are all zero
+            pci.memory_regions = read("resource")
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|line| {
+                    let mut fields = line.split_whitespace();
+                    let start = u64::from_str_radix(fields.next()?.trim_start_matches("0x"), 16).ok()?;
+                    let end = u64::from_str_radix(fields.next()?.trim_start_matches("0x"), 16).ok()?;
+                    (start != 0 || end != 0).then(|| format!("{start:#x}-{end:#x}"))
+                })
+                .collect();
+            devices.push(pci);
+        }
+
+        devices.sort_by(|a, b| a.bus_location.cmp(&b.bus_location));
+        Ok(devices)
+    }
+
+    #[cfg(target_os = "windows")]
+    fn query_windows() -> Result<Vec<Self>> {
+        use std::collections::HashMap;
+        use wmi::{Variant, WMIConnection};
+
+        let wmi_con = WMIConnection::new(crate::probe::com_library()?)?;
+        let entities: Vec<HashMap<String, Variant>> = wmi_con.raw_query(
+            "SELECT DeviceID, Name, Manufacturer, Service, CompatibleID FROM Win32_PnPEntity \
+             WHERE DeviceID LIKE 'PCI\\\\%'",
+        )?;
+
+        let string = |entity: &HashMap<String, Variant>, key: &str| match entity.get(key) {
+            Some(Variant::String(value)) if !value.is_empty() => Some(value.clone()),
+            _ => None,
+        };
+
+        let mut devices = Vec::new();
+        for entity in &entities {
+            let Some(device_id) = string(entity, "DeviceID") else {
+                continue;
+            };
+            // "PCI\VEN_8086&DEV_7D1D&SUBSYS_00000000&REV_04\3&11583659&0&58"
+            let Some(hardware) = device_id.split('\\').nth(1) else {
+                continue;
+            };
+            let field = |prefix: &str| {
+                hardware
+                    .split('&')
+                    .find_map(|part| part.strip_prefix(prefix))
+                    .map(str::to_string)
+            };
+            let (Some(vendor), Some(device)) = (
+                field("VEN_").and_then(|v| crate::ids::hex(&v)),
+                field("DEV_").and_then(|v| crate::ids::hex(&v)),
+            ) else {
+                continue;
+            };
+            // SUBSYS_ssssvvvv: subsystem device ID first, then subsystem vendor
+            let subsystem = field("SUBSYS_")
+                .filter(|s| s.len() == 8 && s != "00000000")
+                .and_then(|s| Some((crate::ids::hex(&s[4..])?, crate::ids::hex(&s[..4])?)));
+            // CompatibleID holds "PCI\CC_030000"
+            let class_code = match entity.get("CompatibleID") {
+                Some(Variant::Array(ids)) => ids.iter().find_map(|id| match id {
+                    Variant::String(id) => id
+                        .split("CC_")
+                        .nth(1)
+                        .filter(|cc| cc.len() == 6)
+                        .and_then(|cc| u32::from_str_radix(cc, 16).ok()),
+                    _ => None,
+                }),
+                _ => None,
+            };
+
+            let mut pci = Self::from_ids(vendor, device, subsystem, class_code);
+            // Prefer the names Windows shows in Device Manager
+            if let Some(name) = string(entity, "Name") {
+                pci.device_name = name;
+            }
+            if let Some(manufacturer) = string(entity, "Manufacturer").filter(|m| !m.starts_with('(')) {
+                pci.vendor_name = manufacturer;
+            }
+            pci.bus_location = device_id.clone();
+            pci.driver = string(entity, "Service");
+            pci.revision = field("REV_");
+            devices.push(pci);
+        }
+
+        Ok(devices)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn query_macos() -> Result<Vec<Self>> {
+        use crate::probe::Command;
+
+        let output = Command::new("system_profiler")
+            .args(["SPPCIDataType", "-json"])
+            .output()?;
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+
+        let mut devices = Vec::new();
+        for item in json["SPPCIDataType"].as_array().into_iter().flatten() {
+            let text = |key: &str| item[key].as_str().map(str::to_string);
+            let id = |key: &str| text(key).and_then(|v| crate::ids::hex(&v));
+            let (Some(vendor), Some(device)) = (id("sppci_vendor-id"), id("sppci_device-id")) else {
+                continue;
+            };
+            let subsystem = id("sppci_subsystem-vendor-id").zip(id("sppci_subsystem-id"));
+
+            let mut pci = Self::from_ids(vendor, device, subsystem, None);
+            if let Some(name) = text("_name") {
+                pci.device_name = name;
+            }
+            if let Some(class) = text("sppci_device_type") {
+                pci.device_class = class;
+            }
+            pci.bus_location = text("sppci_slot_name").unwrap_or_default();
+            pci.revision = text("sppci_revision-id");
+            devices.push(pci);
+        }
+
+        Ok(devices)
+    }
+}
+
+/// Drivers that leave a device free for VFIO passthrough
+const PASSTHROUGH_DRIVERS: &[&str] = &["vfio-pci", "pci-stub"];
+
+/// PCI devices the IOMMU isolates together
+///
+/// VFIO can only assign a whole group to a virtual machine, so every device
+/// sharing a group with a GPU has to be passed through along with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IOMMUGroup {
+    /// Group number (`/sys/kernel/iommu_groups/<id>`)
+    pub id: u32,
+    /// Devices in the group, sorted by bus location
+    pub devices: Vec<PCIDevice>,
+}
+
+impl IOMMUGroup {
+    /// Query all IOMMU groups
+    ///
+    /// Empty when the IOMMU is disabled (`intel_iommu=on` / `amd_iommu=on`
+    /// missing) or on platforms other than Linux.
+    pub fn query_all() -> Result<Vec<Self>> {
+        Ok(Self::from_devices(&PCIDevice::query_all()?))
+    }
+
+    /// Group already-queried PCI devices by their IOMMU group
+    pub fn from_devices(devices: &[PCIDevice]) -> Vec<Self> {
+        let mut groups: Vec<Self> = Vec::new();
+        for device in devices {
+            let Some(id) = device.iommu_group else {
+                continue;
+            };
+            match groups.iter_mut().find(|group| group.id == id) {
+                Some(group) => group.devices.push(device.clone()),
+                None => groups.push(Self { id, devices: vec![device.clone()] }),
+            }
+        }
+        for group in &mut groups {
+            group.devices.sort_by(|a, b| a.bus_location.cmp(&b.bus_location));
+        }
+        groups.sort_by_key(|group| group.id);
+        groups
+    }
+
+    /// Get group number
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Get devices in the group
+    pub fn devices(&self) -> &[PCIDevice] {
+        &self.devices
+    }
+
+    /// Get the devices a VM would receive, i.e. everything but bridges
+    pub fn endpoints(&self) -> Vec<&PCIDevice> {
+        self.devices.iter().filter(|device| !device.is_bridge()).collect()
+    }
+
+    /// Check if the group contains the device at `bus_location`
+    pub fn contains(&self, bus_location: &str) -> bool {
+        self.devices
+            .iter()
+            .any(|device| crate::gpu::same_pci_address(&device.bus_location, bus_location))
+    }
+
+    /// Check if the group can be passed through as one physical device
+    ///
+    /// True when every endpoint is a function of the same slot (a GPU and
+    /// its HDMI audio, say). Groups spanning several devices usually need
+    /// the ACS override patch or a different slot.
+    pub fn is_viable(&self) -> bool {
+        self.blocking_devices().is_empty() && !self.endpoints().is_empty()
+    }
+
+    /// Get endpoints from other slots that share the group with the first one
+    pub fn blocking_devices(&self) -> Vec<&PCIDevice> {
+        let endpoints = self.endpoints();
+        let Some(first) = endpoints.first() else {
+            return Vec::new();
+        };
+        endpoints
+            .iter()
+            .filter(|device| device.slot() != first.slot())
+            .copied()
+            .collect()
+    }
+
+    /// Check if every endpoint is bound to `vfio-pci` / `pci-stub` or has no driver
+    pub fn is_bound_to_vfio(&self) -> bool {
+        self.endpoints().iter().all(|device| {
+            device
+                .driver
+                .as_deref()
+                .is_none_or(|driver| PASSTHROUGH_DRIVERS.contains(&driver))
+        })
+    }
+
+    /// Check if any endpoint is a graphics device
+    pub fn has_gpu(&self) -> bool {
+        self.endpoints()
+            .iter()
+            .any(|device| device.is_graphics_device() || device.base_class() == Some(0x03))
+    }
+}
+
+/// Name of a PCI base class, used when no `pci.ids` database is installed
+fn base_class_name(base: u8) -> &'static str {
+    match base {
+        0x00 => "Unclassified device",
+        0x01 => "Mass storage controller",
+        0x02 => "Network controller",
+        0x03 => "Display controller",
+        0x04 => "Multimedia controller",
+        0x05 => "Memory controller",
+        0x06 => "Bridge",
+        0x07 => "Communication controller",
+        0x08 => "Generic system peripheral",
+        0x09 => "Input device controller",
+        0x0a => "Docking station",
+        0x0b => "Processor",
+        0x0c => "Serial bus controller",
+        0x0d => "Wireless controller",
+        0x0e => "Intelligent controller",
+        0x0f => "Satellite communications controller",
+        0x10 => "Encryption controller",
+        0x11 => "Signal processing controller",
+        0x12 => "Processing accelerators",
+        0x13 => "Non-Essential Instrumentation",
+        0x40 => "Coprocessor",
+        _ => "Unassigned class",
+    }
 }
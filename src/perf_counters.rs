@@ -0,0 +1,380 @@
+//! Windows performance counters (PDH) for usage metrics
+//!
+//! sysinfo derives CPU usage from `GetSystemTimes` deltas, which is coarse and
+//! disagrees with Task Manager on hybrid and boosting CPUs. On Windows
+//! [`PerformanceCounterSampler`] reads the same Performance Data Helper
+//! counters Task Manager does: `% Processor Utility` and effective frequency
+//! per logical processor, DPC and interrupt time, physical disk queue length
+//! and per-engine GPU utilization. Counters are added by their English names
+//! so the backend works on localized installs.
+//!
+//! Most of these are rate counters, so a reading covers the time since the
+//! previous [`collect`](PerformanceCounterSampler::collect). Other platforms
+//! return [`HardwareQueryError::PlatformNotSupported`](crate::HardwareQueryError::PlatformNotSupported).
+
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Average queue length of one physical disk over the sampling interval
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiskQueueLength {
+    /// Device identifier, matching [`DiskIoStats::device`](crate::DiskIoStats::device)
+    pub device: String,
+    /// Average number of requests queued or in service
+    pub queue_length: f64,
+}
+
+/// Utilization of one engine type on one GPU adapter, summed over processes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GPUEngineUsage {
+    /// Adapter LUID as it appears in the counter instance (`0x00000000_0x0000D1B5`)
+    pub adapter_luid: String,
+    /// Engine type, e.g. `3D`, `Copy`, `VideoDecode`, `Compute_0`
+    pub engine_type: String,
+    /// Busy percentage of the engine
+    pub utilization_percent: f64,
+}
+
+/// One reading of the performance counters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PerformanceCounters {
+    /// Usage of each logical processor in percent, in processor group order
+    pub per_core_usage: Vec<f32>,
+    /// Effective clock of each logical processor in MHz
+    pub per_core_frequency_mhz: Vec<f32>,
+    /// Share of CPU time spent in deferred procedure calls, in percent
+    pub dpc_time_percent: Option<f32>,
+    /// Share of CPU time spent servicing hardware interrupts, in percent
+    pub interrupt_time_percent: Option<f32>,
+    /// Hardware interrupts per second across all processors
+    pub interrupts_per_sec: Option<f64>,
+    /// Average queue length per physical disk
+    pub disk_queue_lengths: Vec<DiskQueueLength>,
+    /// Utilization per GPU adapter and engine type
+    pub gpu_engines: Vec<GPUEngineUsage>,
+    /// Time covered by the reading
+    pub interval: Duration,
+}
+
+impl PerformanceCounters {
+    /// Read the counters over `interval`
+    ///
+    /// Blocks for `interval` between the two collections.
+    pub fn sample(interval: Duration) -> Result<Self> {
+        let mut sampler = PerformanceCounterSampler::new()?;
+        std::thread::sleep(interval);
+        sampler.collect()
+    }
+
+    /// Average usage across logical processors in percent
+    pub fn average_cpu_usage(&self) -> Option<f32> {
+        if self.per_core_usage.is_empty() {
+            return None;
+        }
+        Some(self.per_core_usage.iter().sum::<f32>() / self.per_core_usage.len() as f32)
+    }
+
+    /// Average effective clock across logical processors in MHz
+    pub fn average_frequency_mhz(&self) -> Option<f32> {
+        if self.per_core_frequency_mhz.is_empty() {
+            return None;
+        }
+        Some(self.per_core_frequency_mhz.iter().sum::<f32>() / self.per_core_frequency_mhz.len() as f32)
+    }
+
+    /// Busiest engine of each adapter, the figure Task Manager shows per GPU
+    pub fn gpu_utilization(&self) -> Vec<(String, f64)> {
+        let mut adapters: Vec<(String, f64)> = Vec::new();
+        for engine in &self.gpu_engines {
+            match adapters.iter_mut().find(|(luid, _)| *luid == engine.adapter_luid) {
+                Some((_, busiest)) => *busiest = busiest.max(engine.utilization_percent),
+                None => adapters.push((engine.adapter_luid.clone(), engine.utilization_percent)),
+            }
+        }
+        adapters
+    }
+}
+
+/// Open PDH query that yields a [`PerformanceCounters`] reading per collection
+pub struct PerformanceCounterSampler {
+    #[cfg(target_os = "windows")]
+    query: windows::Query,
+    last_collected: Instant,
+}
+
+impl PerformanceCounterSampler {
+    /// Open the query and take the baseline collection
+    pub fn new() -> Result<Self> {
+        #[cfg(target_os = "windows")]
+        {
+            Ok(Self {
+                query: windows::Query::open()?,
+                last_collected: Instant::now(),
+            })
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            Err(crate::HardwareQueryError::platform_not_supported(
+                "Performance counters (PDH) are only available on Windows",
+            ))
+        }
+    }
+
+    /// Read the counters accumulated since the previous collection
+    pub fn collect(&mut self) -> Result<PerformanceCounters> {
+        let now = Instant::now();
+        let interval = now.duration_since(self.last_collected);
+        self.last_collected = now;
+
+        #[cfg(target_os = "windows")]
+        {
+            self.query.collect(interval)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = interval;
+            Err(crate::HardwareQueryError::platform_not_supported(
+                "Performance counters (PDH) are only available on Windows",
+            ))
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use super::{DiskQueueLength, GPUEngineUsage, PerformanceCounters};
+    use crate::{HardwareQueryError, Result};
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+    use winapi::shared::minwindef::DWORD;
+    use winapi::um::pdh::{
+        PdhAddEnglishCounterW, PdhCloseQuery, PdhCollectQueryData, PdhGetFormattedCounterArrayW,
+        PdhOpenQueryW, PDH_FMT_COUNTERVALUE_ITEM_W, PDH_FMT_DOUBLE, PDH_FMT_NOCAP100,
+    };
+    use winapi::um::winnt::HANDLE;
+
+    // pdhmsg.h
+    const PDH_CSTATUS_VALID_DATA: DWORD = 0x0000_0000;
+    const PDH_CSTATUS_NEW_DATA: DWORD = 0x0000_0001;
+    const PDH_MORE_DATA: i32 = 0x8000_07D2_u32 as i32;
+    const ERROR_SUCCESS: i32 = 0;
+
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Counter {
+        CoreUsage,
+        CoreFrequency,
+        CorePerformance,
+        DpcTime,
+        InterruptTime,
+        Interrupts,
+        DiskQueue,
+        GpuEngine,
+    }
+
+    /// `% Processor Utility` matches Task Manager on Windows 8 and later;
+    /// `% Processor Time` is the fallback on older builds
+    const COUNTERS: &[(Counter, &[&str])] = &[
+        (
+            Counter::CoreUsage,
+            &[
+                r"\Processor Information(*)\% Processor Utility",
+                r"\Processor Information(*)\% Processor Time",
+            ],
+        ),
+        (Counter::CoreFrequency, &[r"\Processor Information(*)\Processor Frequency"]),
+        (Counter::CorePerformance, &[r"\Processor Information(*)\% Processor Performance"]),
+        (Counter::DpcTime, &[r"\Processor Information(_Total)\% DPC Time"]),
+        (Counter::InterruptTime, &[r"\Processor Information(_Total)\% Interrupt Time"]),
+        (Counter::Interrupts, &[r"\Processor Information(_Total)\Interrupts/sec"]),
+        (Counter::DiskQueue, &[r"\PhysicalDisk(*)\Avg. Disk Queue Length"]),
+        (Counter::GpuEngine, &[r"\GPU Engine(*)\Utilization Percentage"]),
+    ];
+
+    pub(super) struct Query {
+        handle: HANDLE,
+        counters: Vec<(Counter, HANDLE)>,
+    }
+
+    // SAFETY: PDH query and counter handles may be used from any thread, and
+    // `Query` is only accessed through `&mut self`
+    unsafe impl Send for Query {}
+
+    impl Query {
+        pub(super) fn open() -> Result<Self> {
+            let mut handle: HANDLE = std::ptr::null_mut();
+            // SAFETY: a null data source selects live data; `handle` receives the query
+            let status = unsafe { PdhOpenQueryW(std::ptr::null(), 0, &mut handle) };
+            if status != ERROR_SUCCESS {
+                return Err(HardwareQueryError::system_info_unavailable(format!(
+                    "PdhOpenQueryW failed with status {status:#x}"
+                )));
+            }
+
+            let mut query = Self { handle, counters: Vec::new() };
+            for (counter, paths) in COUNTERS {
+                // Counter sets can be missing (no GPU Engine set before WDDM 2.4)
+                for path in *paths {
+                    let wide: Vec<u16> = path.encode_utf16().chain(std::iter::once(0)).collect();
+                    let mut counter_handle: HANDLE = std::ptr::null_mut();
+                    // SAFETY: `wide` is NUL-terminated and outlives the call
+                    let status = unsafe {
+                        PdhAddEnglishCounterW(query.handle, wide.as_ptr(), 0, &mut counter_handle)
+                    };
+                    if status == ERROR_SUCCESS {
+                        query.counters.push((*counter, counter_handle));
+                        break;
+                    }
+                }
+            }
+            if query.counters.is_empty() {
+                return Err(HardwareQueryError::system_info_unavailable(
+                    "No processor, disk or GPU performance counters are available",
+                ));
+            }
+
+            // Rate counters need a baseline collection
+            // SAFETY: the query handle is open
+            unsafe { PdhCollectQueryData(query.handle) };
+            Ok(query)
+        }
+
+        pub(super) fn collect(&mut self, interval: Duration) -> Result<PerformanceCounters> {
+            // SAFETY: the query handle is open
+            let status = unsafe { PdhCollectQueryData(self.handle) };
+            if status != ERROR_SUCCESS {
+                return Err(HardwareQueryError::system_info_unavailable(format!(
+                    "PdhCollectQueryData failed with status {status:#x}"
+                )));
+            }
+
+            let values = |wanted: Counter| -> Vec<(String, f64)> {
+                self.counters
+                    .iter()
+                    .filter(|(counter, _)| *counter == wanted)
+                    .flat_map(|(_, handle)| formatted_array(*handle))
+                    .collect()
+            };
+            let total = |wanted: Counter| values(wanted).first().map(|(_, value)| *value);
+
+            // Instances are "<group>,<processor>" plus "_Total" rows per group and overall
+            let per_processor = |wanted: Counter| -> BTreeMap<(u32, u32), f64> {
+                values(wanted)
+                    .into_iter()
+                    .filter_map(|(name, value)| {
+                        let (group, index) = name.split_once(',')?;
+                        Some(((group.parse().ok()?, index.parse().ok()?), value))
+                    })
+                    .collect()
+            };
+
+            let usage = per_processor(Counter::CoreUsage);
+            let frequency = per_processor(Counter::CoreFrequency);
+            let performance = per_processor(Counter::CorePerformance);
+            let per_core_frequency_mhz = frequency
+                .iter()
+                .map(|(processor, mhz)| {
+                    let scale = performance.get(processor).map_or(1.0, |percent| percent / 100.0);
+                    (mhz * scale) as f32
+                })
+                .collect();
+
+            // Instances look like "0 C:"; "_Total" is skipped
+            let disk_queue_lengths = values(Counter::DiskQueue)
+                .into_iter()
+                .filter_map(|(name, queue_length)| {
+                    let disk_number: u32 = name.split_whitespace().next()?.parse().ok()?;
+                    Some(DiskQueueLength {
+                        device: format!("PhysicalDrive{disk_number}"),
+                        queue_length,
+                    })
+                })
+                .collect();
+
+            // Instances look like "pid_1234_luid_0x00000000_0x0000D1B5_phys_0_eng_3_engtype_VideoDecode"
+            let mut engines: BTreeMap<(String, String), f64> = BTreeMap::new();
+            for (name, value) in values(Counter::GpuEngine) {
+                let Some((_, rest)) = name.split_once("luid_") else {
+                    continue;
+                };
+                let Some((luid, _)) = rest.split_once("_phys") else {
+                    continue;
+                };
+                let Some((_, engine_type)) = rest.split_once("engtype_") else {
+                    continue;
+                };
+                *engines.entry((luid.to_string(), engine_type.to_string())).or_default() += value;
+            }
+            let gpu_engines = engines
+                .into_iter()
+                .map(|((adapter_luid, engine_type), utilization)| GPUEngineUsage {
+                    adapter_luid,
+                    engine_type,
+                    utilization_percent: utilization.min(100.0),
+                })
+                .collect();
+
+            Ok(PerformanceCounters {
+                per_core_usage: usage.values().map(|percent| percent.clamp(0.0, 100.0) as f32).collect(),
+                per_core_frequency_mhz,
+                dpc_time_percent: total(Counter::DpcTime).map(|percent| percent as f32),
+                interrupt_time_percent: total(Counter::InterruptTime).map(|percent| percent as f32),
+                interrupts_per_sec: total(Counter::Interrupts),
+                disk_queue_lengths,
+                gpu_engines,
+                interval,
+            })
+        }
+    }
+
+    impl Drop for Query {
+        fn drop(&mut self) {
+            // SAFETY: closing the query also frees its counters
+            unsafe { PdhCloseQuery(self.handle) };
+        }
+    }
+
+    /// Instance name and value of every valid item of a counter
+    fn formatted_array(counter: HANDLE) -> Vec<(String, f64)> {
+        let format = PDH_FMT_DOUBLE | PDH_FMT_NOCAP100;
+        let mut buffer_size: DWORD = 0;
+        let mut item_count: DWORD = 0;
+        // SAFETY: a null buffer with zero size only queries the required size
+        let status = unsafe {
+            PdhGetFormattedCounterArrayW(counter, format, &mut buffer_size, &mut item_count, std::ptr::null_mut())
+        };
+        if status != PDH_MORE_DATA || buffer_size == 0 {
+            return Vec::new();
+        }
+
+        // The items are followed by their instance name strings; u64 keeps the items aligned
+        let mut buffer = vec![0u64; (buffer_size as usize).div_ceil(8)];
+        let items = buffer.as_mut_ptr() as *mut PDH_FMT_COUNTERVALUE_ITEM_W;
+        // SAFETY: `buffer` holds at least `buffer_size` bytes, as requested by the previous call
+        let status = unsafe {
+            PdhGetFormattedCounterArrayW(counter, format, &mut buffer_size, &mut item_count, items)
+        };
+        if status != ERROR_SUCCESS {
+            return Vec::new();
+        }
+
+        // SAFETY: PDH wrote `item_count` items at the start of `buffer`
+        let items = unsafe { std::slice::from_raw_parts(items, item_count as usize) };
+        items
+            .iter()
+            .filter(|item| {
+                matches!(item.FmtValue.CStatus, PDH_CSTATUS_VALID_DATA | PDH_CSTATUS_NEW_DATA)
+            })
+            .map(|item| {
+                // SAFETY: szName points to a NUL-terminated string inside `buffer`
+                let name = unsafe {
+                    let length = (0..).take_while(|&i| *item.szName.add(i) != 0).count();
+                    String::from_utf16_lossy(std::slice::from_raw_parts(item.szName, length))
+                };
+                // SAFETY: PDH_FMT_DOUBLE fills the double member of the union
+                let value = unsafe { *item.FmtValue.u.doubleValue() };
+                (name, value)
+            })
+            .collect()
+    }
+}
@@ -2,7 +2,7 @@
 use crate::{HardwareQueryError, Result};
 use std::collections::HashMap;
 use std::fs;
-use std::process::Command;
+use crate::probe::Command;
 
 /// Linux-specific CPU information
 #[derive(Debug, Clone)]
@@ -331,12 +331,30 @@ pub struct LinuxMemoryInfo {
     pub modules: Vec<LinuxMemoryModule>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct LinuxMemoryModule {
     pub size_mb: u64,
     pub speed_mhz: Option<u32>,
+    pub configured_speed_mhz: Option<u32>,
     pub memory_type: String,
     pub locator: String,
+    pub bank_locator: Option<String>,
+    pub manufacturer: Option<String>,
+    pub part_number: Option<String>,
+    pub serial_number: Option<String>,
+    pub total_width: Option<u32>,
+    pub data_width: Option<u32>,
+    pub voltage: Option<f32>,
+}
+
+impl LinuxMemoryModule {
+    /// Module carries check bits (wider total than data bus)
+    pub fn has_ecc(&self) -> Option<bool> {
+        match (self.total_width, self.data_width) {
+            (Some(total), Some(data)) => Some(total > data),
+            _ => None,
+        }
+    }
 }
 
 impl LinuxMemoryInfo {
@@ -384,64 +402,102 @@ impl LinuxMemoryInfo {
         Ok(mem_info)
     }
 
-    /// Get memory module information using dmidecode
-    fn get_memory_modules() -> Result<Vec<LinuxMemoryModule>> {
+    /// Get memory module information using dmidecode (needs root)
+    pub fn get_memory_modules() -> Result<Vec<LinuxMemoryModule>> {
+        match Command::new("dmidecode").args(["-t", "memory"]).output() {
+            Ok(output) => Ok(Self::parse_dmidecode_memory(&String::from_utf8_lossy(&output.stdout))),
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+
+    /// Parse the "Memory Device" (type 17) records of `dmidecode -t memory`
+    pub fn parse_dmidecode_memory(output: &str) -> Vec<LinuxMemoryModule> {
+        // Values vendors leave in unused fields
+        fn meaningful(value: &str) -> Option<String> {
+            let placeholder = matches!(
+                value.to_ascii_lowercase().as_str(),
+                "" | "unknown" | "not specified" | "not provided" | "none" | "undefined"
+            ) || value.chars().all(|c| c == '0' || c == ' ')
+                || value.to_ascii_lowercase().starts_with("sernum")
+                || value.to_ascii_lowercase().starts_with("partnum");
+            (!placeholder).then(|| value.to_string())
+        }
+        // "3200 MT/s", "2666 MHz"
+        fn speed(value: &str) -> Option<u32> {
+            value.split_whitespace().next()?.parse().ok().filter(|s| *s > 0)
+        }
+        // "72 bits"
+        fn bits(value: &str) -> Option<u32> {
+            value.split_whitespace().next()?.parse().ok()
+        }
+
         let mut modules = Vec::new();
+        let mut current_module: Option<LinuxMemoryModule> = None;
 
-        if let Ok(output) = Command::new("dmidecode").args(["-t", "memory"]).output() {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            let mut current_module: Option<LinuxMemoryModule> = None;
+        for line in output.lines() {
+            let line = line.trim();
 
-            for line in output_str.lines() {
-                let line = line.trim();
+            if line == "Memory Device" {
+                if let Some(module) = current_module.take() {
+                    modules.push(module);
+                }
+                current_module = Some(LinuxMemoryModule::default());
+            } else if line.starts_with("Handle ") {
+                // A new record ends the current one
+                if let Some(module) = current_module.take() {
+                    modules.push(module);
+                }
+            } else if let Some(ref mut module) = current_module {
+                if let Some((key, value)) = line.split_once(':') {
+                    let key = key.trim();
+                    let value = value.trim();
 
-                if line.starts_with("Memory Device") {
-                    if let Some(module) = current_module.take() {
-                        modules.push(module);
-                    }
-                    current_module = Some(LinuxMemoryModule {
-                        size_mb: 0,
-                        speed_mhz: None,
-                        memory_type: String::new(),
-                        locator: String::new(),
-                    });
-                } else if let Some(ref mut module) = current_module {
-                    if let Some((key, value)) = line.split_once(':') {
-                        let key = key.trim();
-                        let value = value.trim();
-
-                        match key {
-                            "Size" => {
-                                if value.contains("MB") {
-                                    let size_str = value.replace(" MB", "");
-                                    module.size_mb = size_str.parse().unwrap_or(0);
-                                } else if value.contains("GB") {
-                                    let size_str = value.replace(" GB", "");
-                                    if let Ok(size_gb) = size_str.parse::<u64>() {
-                                        module.size_mb = size_gb * 1024;
-                                    }
-                                }
-                            }
-                            "Speed" => {
-                                let speed_str = value.replace(" MHz", "");
-                                module.speed_mhz = speed_str.parse().ok();
-                            }
-                            "Type" => module.memory_type = value.to_string(),
-                            "Locator" => module.locator = value.to_string(),
-                            _ => {}
+                    match key {
+                        "Size" => {
+                            let mut parts = value.split_whitespace();
+                            let amount = parts.next().and_then(|v| v.parse::<u64>().ok());
+                            module.size_mb = match (amount, parts.next()) {
+                                (Some(kb), Some("kB")) => kb / 1024,
+                                (Some(mb), Some("MB")) => mb,
+                                (Some(gb), Some("GB")) => gb * 1024,
+                                (Some(tb), Some("TB")) => tb * 1024 * 1024,
+                                // "No Module Installed"
+                                _ => 0,
+                            };
+                        }
+                        "Speed" => module.speed_mhz = speed(value),
+                        // "Configured Clock Speed" in dmidecode < 3.2
+                        "Configured Memory Speed" | "Configured Clock Speed" => {
+                            module.configured_speed_mhz = speed(value)
+                        }
+                        "Type" => module.memory_type = value.to_string(),
+                        "Locator" => module.locator = value.to_string(),
+                        "Bank Locator" => module.bank_locator = meaningful(value),
+                        "Manufacturer" => module.manufacturer = meaningful(value),
+                        "Part Number" => module.part_number = meaningful(value),
+                        "Serial Number" => module.serial_number = meaningful(value),
+                        "Total Width" => module.total_width = bits(value),
+                        "Data Width" => module.data_width = bits(value),
+                        "Configured Voltage" => {
+                            module.voltage = value
+                                .split_whitespace()
+                                .next()
+                                .and_then(|v| v.parse().ok())
+                                .filter(|v| *v > 0.0)
                         }
+                        _ => {}
                     }
                 }
             }
+        }
 
-            if let Some(module) = current_module {
-                modules.push(module);
-            }
+        if let Some(module) = current_module {
+            modules.push(module);
         }
 
-        // Filter out empty modules
+        // Filter out empty slots
         modules.retain(|m| m.size_mb > 0);
 
-        Ok(modules)
+        modules
     }
 }
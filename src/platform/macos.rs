@@ -1,7 +1,7 @@
 /// Enhanced platform-specific hardware detection for macOS
 use crate::{HardwareQueryError, Result};
 use std::collections::HashMap;
-use std::process::Command;
+use crate::probe::Command;
 
 /// macOS-specific CPU information
 #[derive(Debug, Clone)]
@@ -317,30 +317,94 @@ impl MacOSMemoryInfo {
 
     /// Get detailed memory module information using system_profiler
     pub fn get_memory_modules() -> Result<Vec<MacOSMemoryModule>> {
+        let output = Command::new("system_profiler")
+            .args(["SPMemoryDataType", "-json"])
+            .output()?;
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+        Ok(Self::parse_memory_modules(&json))
+    }
+
+    /// Parse `system_profiler SPMemoryDataType -json` output
+    ///
+    /// Intel Macs list one `_items` entry per slot; Apple Silicon reports the
+    /// unified memory package as a single top-level entry.
+    pub fn parse_memory_modules(json: &serde_json::Value) -> Vec<MacOSMemoryModule> {
+        let text = |value: &serde_json::Value, key: &str| {
+            value
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty() && v != "Empty" && v != "empty")
+        };
+
         let mut modules = Vec::new();
+        let Some(entries) = json.get("SPMemoryDataType").and_then(|v| v.as_array()) else {
+            return modules;
+        };
 
-        if let Ok(output) = Command::new("system_profiler")
-            .args(["SPMemoryDataType", "-xml"])
-            .output()
-        {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            // Parse system_profiler XML output for memory modules
-            // This would require proper XML parsing
+        for entry in entries {
+            let ecc = text(entry, "global_ecc_state").map(|state| state == "ecc_enabled");
+
+            if let Some(items) = entry.get("_items").and_then(|v| v.as_array()) {
+                for item in items {
+                    let Some(size_mb) = text(item, "dimm_size").and_then(|s| parse_size_mb(&s)) else {
+                        continue;
+                    };
+                    modules.push(MacOSMemoryModule {
+                        size_mb,
+                        speed_mhz: text(item, "dimm_speed")
+                            .and_then(|s| s.split_whitespace().next()?.parse().ok())
+                            .unwrap_or(0),
+                        memory_type: text(item, "dimm_type").unwrap_or_default(),
+                        manufacturer: text(item, "dimm_manufacturer").unwrap_or_default(),
+                        part_number: text(item, "dimm_part_number").unwrap_or_default(),
+                        serial_number: text(item, "dimm_serial_number").unwrap_or_default(),
+                        slot: text(item, "_name").unwrap_or_default(),
+                        ecc,
+                    });
+                }
+            } else if let Some(size_mb) = text(entry, "SPMemoryDataType").and_then(|s| parse_size_mb(&s)) {
+                // Apple Silicon unified memory
+                modules.push(MacOSMemoryModule {
+                    size_mb,
+                    speed_mhz: 0,
+                    memory_type: text(entry, "dimm_type").unwrap_or_default(),
+                    manufacturer: text(entry, "dimm_manufacturer").unwrap_or_default(),
+                    part_number: String::new(),
+                    serial_number: String::new(),
+                    slot: "Unified Memory".to_string(),
+                    ecc,
+                });
+            }
         }
 
-        Ok(modules)
+        modules
     }
 }
 
+/// Parse a size such as "16 GB" into megabytes
+fn parse_size_mb(size: &str) -> Option<u64> {
+    let mut parts = size.split_whitespace();
+    let value: u64 = parts.next()?.parse().ok()?;
+    let size_mb = match parts.next()? {
+        "MB" => value,
+        "GB" => value * 1024,
+        "TB" => value * 1024 * 1024,
+        _ => return None,
+    };
+    (size_mb > 0).then_some(size_mb)
+}
+
 #[derive(Debug, Clone)]
 pub struct MacOSMemoryModule {
-    pub size_gb: u64,
+    pub size_mb: u64,
     pub speed_mhz: u32,
     pub memory_type: String,
     pub manufacturer: String,
     pub part_number: String,
     pub serial_number: String,
     pub slot: String,
+    pub ecc: Option<bool>,
 }
 
 /// macOS-specific system information
@@ -1,7 +1,7 @@
 /// Enhanced platform-specific hardware detection for Windows
 use crate::{HardwareQueryError, Result};
 use std::collections::HashMap;
-use wmi::{COMLibrary, Variant, WMIConnection};
+use wmi::{Variant, WMIConnection};
 
 /// Windows-specific CPU information
 #[derive(Debug, Clone)]
@@ -28,7 +28,7 @@ pub struct WindowsCPUInfo {
 impl WindowsCPUInfo {
     /// Query detailed CPU information from Windows WMI
     pub fn query() -> Result<Self> {
-        let com_lib = COMLibrary::new()?;
+        let com_lib = crate::probe::com_library()?;
         let wmi_con = WMIConnection::new(com_lib)?;
 
         // Query processor information
@@ -66,7 +66,7 @@ impl WindowsCPUInfo {
 
     /// Get CPU temperature from thermal sensors
     pub fn get_temperature(&self) -> Result<Option<f32>> {
-        let com_lib = COMLibrary::new()?;
+        let com_lib = crate::probe::com_library()?;
         let wmi_con = WMIConnection::new(com_lib)?;
 
         // Try to get temperature from thermal zone
@@ -86,7 +86,7 @@ impl WindowsCPUInfo {
 
     /// Get CPU power consumption
     pub fn get_power_consumption(&self) -> Result<Option<f32>> {
-        let com_lib = COMLibrary::new()?;
+        let com_lib = crate::probe::com_library()?;
         let wmi_con = WMIConnection::new(com_lib)?;
 
         // Try to get power information from performance counters
@@ -167,7 +167,7 @@ pub struct WindowsGPUInfo {
 impl WindowsGPUInfo {
     /// Query GPU information from Windows WMI
     pub fn query_all() -> Result<Vec<Self>> {
-        let com_lib = COMLibrary::new()?;
+        let com_lib = crate::probe::com_library()?;
         let wmi_con = WMIConnection::new(com_lib)?;
 
         let gpu_query = "SELECT Name, AdapterCompatibility, AdapterRAM, DriverVersion, DeviceID, DedicatedVideoMemory, SharedSystemMemory FROM Win32_VideoController";
@@ -238,12 +238,27 @@ pub struct WindowsMemoryModule {
     pub part_number: Option<String>,
     pub bank_label: Option<String>,
     pub device_locator: Option<String>,
+    pub configured_speed_mhz: Option<u32>,
+    pub serial_number: Option<String>,
+    pub total_width: Option<u32>,
+    pub data_width: Option<u32>,
+    pub voltage: Option<f32>,
+}
+
+impl WindowsMemoryModule {
+    /// Module carries check bits (wider total than data bus)
+    pub fn has_ecc(&self) -> Option<bool> {
+        match (self.total_width, self.data_width) {
+            (Some(total), Some(data)) => Some(total > data),
+            _ => None,
+        }
+    }
 }
 
 impl WindowsMemoryInfo {
     /// Query memory information from Windows WMI
     pub fn query() -> Result<Self> {
-        let com_lib = COMLibrary::new()?;
+        let com_lib = crate::probe::com_library()?;
         let wmi_con = WMIConnection::new(com_lib)?;
 
         // Query physical memory
@@ -270,34 +285,76 @@ impl WindowsMemoryInfo {
                 / (1024 * 1024);
         }
 
-        // Query memory modules
-        let modules_query = "SELECT Capacity, Speed, MemoryType, Manufacturer, PartNumber, BankLabel, DeviceLocator FROM Win32_PhysicalMemory";
+        let memory_modules = Self::query_modules(&wmi_con)?;
+
+        Ok(Self {
+            total_physical_mb,
+            available_physical_mb,
+            total_virtual_mb,
+            available_virtual_mb,
+            modules: memory_modules,
+        })
+    }
+
+    /// Query installed memory modules from Win32_PhysicalMemory
+    pub fn query_modules(wmi_con: &WMIConnection) -> Result<Vec<WindowsMemoryModule>> {
+        let modules_query = "SELECT Capacity, Speed, ConfiguredClockSpeed, MemoryType, SMBIOSMemoryType, \
+                             Manufacturer, PartNumber, SerialNumber, BankLabel, DeviceLocator, \
+                             TotalWidth, DataWidth, ConfiguredVoltage FROM Win32_PhysicalMemory";
         let modules: Vec<HashMap<String, Variant>> = wmi_con.raw_query(modules_query)?;
 
         let mut memory_modules = Vec::new();
         for module in modules {
+            let text = |key: &str| {
+                WindowsGPUInfo::get_string_value(&module, key)
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty() && value != "Unknown")
+            };
+            let number = |key: &str| WindowsGPUInfo::get_u64_value(&module, key).filter(|v| *v > 0);
+
+            // SMBIOSMemoryType knows DDR5/LPDDR types; MemoryType is 0 on modern systems
+            let memory_type = match number("SMBIOSMemoryType").filter(|code| *code > 2) {
+                Some(code) => Self::parse_smbios_memory_type(code),
+                None => Self::parse_memory_type(number("MemoryType").unwrap_or(0)),
+            };
+
             let module_info = WindowsMemoryModule {
-                capacity_mb: WindowsGPUInfo::get_u64_value(&module, "Capacity").unwrap_or(0)
-                    / (1024 * 1024),
-                speed_mhz: WindowsGPUInfo::get_u64_value(&module, "Speed").unwrap_or(0) as u32,
-                memory_type: Self::parse_memory_type(
-                    WindowsGPUInfo::get_u64_value(&module, "MemoryType").unwrap_or(0),
-                ),
-                manufacturer: WindowsGPUInfo::get_string_value(&module, "Manufacturer"),
-                part_number: WindowsGPUInfo::get_string_value(&module, "PartNumber"),
-                bank_label: WindowsGPUInfo::get_string_value(&module, "BankLabel"),
-                device_locator: WindowsGPUInfo::get_string_value(&module, "DeviceLocator"),
+                capacity_mb: number("Capacity").unwrap_or(0) / (1024 * 1024),
+                speed_mhz: number("Speed").unwrap_or(0) as u32,
+                memory_type,
+                manufacturer: text("Manufacturer"),
+                part_number: text("PartNumber"),
+                bank_label: text("BankLabel"),
+                device_locator: text("DeviceLocator"),
+                configured_speed_mhz: number("ConfiguredClockSpeed").map(|v| v as u32),
+                serial_number: text("SerialNumber"),
+                total_width: number("TotalWidth").map(|v| v as u32),
+                data_width: number("DataWidth").map(|v| v as u32),
+                // Reported in millivolts
+                voltage: number("ConfiguredVoltage").map(|mv| mv as f32 / 1000.0),
             };
             memory_modules.push(module_info);
         }
 
-        Ok(Self {
-            total_physical_mb,
-            available_physical_mb,
-            total_virtual_mb,
-            available_virtual_mb,
-            modules: memory_modules,
-        })
+        Ok(memory_modules)
+    }
+
+    /// Parse an SMBIOS (type 17) memory type code
+    fn parse_smbios_memory_type(type_code: u64) -> String {
+        match type_code {
+            0x12 => "DDR".to_string(),
+            0x13 => "DDR2".to_string(),
+            0x14 => "DDR2 FB-DIMM".to_string(),
+            0x18 => "DDR3".to_string(),
+            0x1A => "DDR4".to_string(),
+            0x1B => "LPDDR".to_string(),
+            0x1C => "LPDDR2".to_string(),
+            0x1D => "LPDDR3".to_string(),
+            0x1E => "LPDDR4".to_string(),
+            0x22 => "DDR5".to_string(),
+            0x23 => "LPDDR5".to_string(),
+            _ => format!("Unknown ({type_code})"),
+        }
     }
 
     /// Parse memory type from WMI integer value
@@ -32,6 +32,13 @@ pub struct PowerProfile {
     pub power_state: PowerState,
     /// Available power saving modes
     pub available_power_modes: Vec<PowerMode>,
+    /// Whether the machine runs on AC or battery
+    #[serde(default)]
+    pub power_source: PowerSource,
+    /// Name of the active OS power plan (Windows power scheme, Linux
+    /// `platform_profile`, macOS power mode)
+    #[serde(default)]
+    pub power_plan: Option<String>,
 }
 
 /// Risk level for thermal throttling
@@ -66,6 +73,18 @@ pub enum PowerState {
     Unknown,
 }
 
+/// Where the machine draws its power from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PowerSource {
+    /// Mains power (also reported for machines without a battery)
+    AC,
+    /// Running on battery
+    Battery,
+    /// The platform doesn't report a power source
+    #[default]
+    Unknown,
+}
+
 /// Available power management modes
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PowerMode {
@@ -133,9 +152,16 @@ pub enum OptimizationPriority {
 impl PowerProfile {
     /// Query current power profile
     pub fn query() -> Result<Self> {
-        let total_power_draw = Self::query_total_power_draw()?;
-        let cpu_power = Self::query_cpu_power()?;
-        let gpu_power = Self::query_gpu_power()?;
+        #[allow(unused_mut)]
+        let (mut total_power_draw, mut cpu_power, mut gpu_power) =
+            (Self::query_total_power_draw()?, Self::query_cpu_power()?, Self::query_gpu_power()?);
+        // One SMC/powermetrics read covers all three on macOS
+        #[cfg(all(target_os = "macos", feature = "smc"))]
+        if let Some(rails) = crate::PowerRails::query() {
+            total_power_draw = total_power_draw.or(rails.total());
+            cpu_power = cpu_power.or(rails.cpu_watts);
+            gpu_power = gpu_power.or(rails.gpu_watts);
+        }
         let memory_power = Self::query_memory_power()?;
         let storage_power = Self::query_storage_power()?;
         let network_power = Self::query_network_power()?;
@@ -143,8 +169,7 @@ impl PowerProfile {
         
         let efficiency_score = Self::calculate_efficiency_score(total_power_draw);
         let thermal_throttling_risk = Self::assess_throttling_risk()?;
-        let power_state = Self::query_power_state()?;
-        let available_power_modes = Self::query_available_power_modes()?;
+        let plan = Self::query_power_plan();
 
         Ok(Self {
             total_power_draw,
@@ -156,11 +181,18 @@ impl PowerProfile {
             other_power,
             efficiency_score,
             thermal_throttling_risk,
-            power_state,
-            available_power_modes,
+            power_state: plan.state,
+            available_power_modes: plan.modes,
+            power_source: PowerSource::query(),
+            power_plan: plan.name,
         })
     }
 
+    /// Check if the machine is running on battery
+    pub fn is_on_battery(&self) -> bool {
+        self.power_source == PowerSource::Battery
+    }
+
     /// Estimate battery life based on current power consumption
     pub fn estimate_battery_life(&self, battery: &BatteryInfo) -> Option<Duration> {
         if let (Some(power_draw), Some(capacity_wh)) = (self.total_power_draw, battery.capacity_wh()) {
@@ -305,38 +337,291 @@ impl PowerProfile {
         Ok(ThrottlingRisk::None)
     }
 
-    fn query_power_state() -> Result<PowerState> {
-        // Platform-specific implementation would go here
-        Ok(PowerState::Unknown)
+    /// Read the active OS power plan and the plans that can be selected
+    fn query_power_plan() -> PowerPlan {
+        #[cfg(target_os = "linux")]
+        {
+            use std::fs;
+
+            let read = |path: &str| {
+                fs::read_to_string(path)
+                    .ok()
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+            // power-profiles-daemon and the firmware both drive the ACPI platform
+            // profile; without one, the CPU's energy/performance preference is the plan
+            let sources = [
+                ("/sys/firmware/acpi/platform_profile", "/sys/firmware/acpi/platform_profile_choices"),
+                (
+                    "/sys/devices/system/cpu/cpu0/cpufreq/energy_performance_preference",
+                    "/sys/devices/system/cpu/cpu0/cpufreq/energy_performance_available_preferences",
+                ),
+            ];
+            for (current, choices) in sources {
+                let Some(active) = read(current) else {
+                    continue;
+                };
+                let modes = read(choices)
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(|choice| PowerMode::new(choice, choice == active))
+                    .collect();
+                return PowerPlan::new(&active, modes);
+            }
+            PowerPlan::unknown()
+        }
+        #[cfg(target_os = "windows")]
+        {
+            // Power Scheme GUID: 381b4222-f694-41f0-9685-ff5bb260df2e  (Balanced) *
+            let Ok(output) = crate::probe::Command::new("powercfg").arg("/list").output() else {
+                return PowerPlan::unknown();
+            };
+            let mut plan = PowerPlan::unknown();
+            for line in String::from_utf8_lossy(&output.stdout).lines() {
+                // The "Power Scheme GUID" label is localized; find the GUID itself
+                let Some(start) = line
+                    .split_whitespace()
+                    .find(|word| is_guid(word))
+                    .and_then(|guid| line.find(guid))
+                else {
+                    continue;
+                };
+                let scheme = line[start..].trim();
+                let guid = scheme.split_whitespace().next().unwrap_or_default().to_ascii_lowercase();
+                let name = match (scheme.find('('), scheme.rfind(')')) {
+                    (Some(start), Some(end)) if end > start => scheme[start + 1..end].to_string(),
+                    _ => guid.clone(),
+                };
+                let is_active = scheme.ends_with('*');
+                if is_active {
+                    // Scheme names are localized; the built-in GUIDs are not
+                    plan.state = match guid.as_str() {
+                        "8c5e7fda-e8bf-4a96-9a85-a6e23a8c635c" | "e9a42b02-d5df-448d-aa00-03f14749eb61" => {
+                            PowerState::HighPerformance
+                        }
+                        "381b4222-f694-41f0-9685-ff5bb260df2e" => PowerState::Balanced,
+                        "a1841308-3541-4fab-bc81-f71556f20b4a" => PowerState::PowerSaver,
+                        _ => PowerState::from_plan_name(&name),
+                    };
+                    plan.name = Some(name.clone());
+                }
+                plan.modes.push(PowerMode::new(&name, is_active));
+            }
+            plan
+        }
+        #[cfg(target_os = "macos")]
+        {
+            // pmset lists " lowpowermode 1", or " powermode 0|1|2" (automatic, low, high)
+            // on machines that also have a High Power Mode
+            let Ok(output) = crate::probe::Command::new("pmset").arg("-g").output() else {
+                return PowerPlan::unknown();
+            };
+            let text = String::from_utf8_lossy(&output.stdout);
+            let setting = |key: &str| {
+                text.lines().find_map(|line| {
+                    let mut fields = line.split_whitespace();
+                    if fields.next()? != key {
+                        return None;
+                    }
+                    fields.next()?.parse::<u32>().ok()
+                })
+            };
+            let (active, names): (&str, &[&str]) = match (setting("powermode"), setting("lowpowermode")) {
+                (Some(2), _) => ("High Power", &["Automatic", "Low Power", "High Power"]),
+                (Some(1), _) => ("Low Power", &["Automatic", "Low Power", "High Power"]),
+                (Some(_), _) => ("Automatic", &["Automatic", "Low Power", "High Power"]),
+                (None, Some(1)) => ("Low Power", &["Automatic", "Low Power"]),
+                (None, Some(_)) => ("Automatic", &["Automatic", "Low Power"]),
+                (None, None) => return PowerPlan::unknown(),
+            };
+            let modes = names.iter().map(|name| PowerMode::new(name, *name == active)).collect();
+            PowerPlan::new(active, modes)
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            PowerPlan::unknown()
+        }
     }
+}
 
-    fn query_available_power_modes() -> Result<Vec<PowerMode>> {
-        // Platform-specific implementation would go here
-        Ok(vec![])
+/// Active power plan as read from the OS
+struct PowerPlan {
+    state: PowerState,
+    name: Option<String>,
+    modes: Vec<PowerMode>,
+}
+
+impl PowerPlan {
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn new(name: &str, modes: Vec<PowerMode>) -> Self {
+        Self {
+            state: PowerState::from_plan_name(name),
+            name: Some(name.to_string()),
+            modes,
+        }
+    }
+
+    fn unknown() -> Self {
+        Self {
+            state: PowerState::Unknown,
+            name: None,
+            modes: Vec::new(),
+        }
+    }
+}
+
+impl PowerMode {
+    #[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+    fn new(name: &str, is_active: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            description: format!("OS power plan \"{name}\""),
+            is_active,
+            power_savings_percent: None,
+            performance_impact_percent: None,
+        }
+    }
+}
+
+impl PowerState {
+    /// Classify an OS power plan name
+    ///
+    /// Understands Windows scheme names, ACPI platform profiles
+    /// (`low-power`, `balanced`, `performance`, ...), CPU energy/performance
+    /// preferences (`balance_power`, ...) and macOS power modes. Other names
+    /// become [`PowerState::Custom`].
+    pub fn from_plan_name(name: &str) -> Self {
+        let normalized = name.to_lowercase().replace(['-', '_'], " ");
+        let normalized = normalized.trim();
+        if normalized.contains("balance") || matches!(normalized, "default" | "automatic") {
+            PowerState::Balanced
+        } else if normalized.contains("performance") || normalized == "high power" {
+            PowerState::HighPerformance
+        } else if normalized.contains("battery") {
+            PowerState::BatteryOptimized
+        } else if normalized.contains("saver")
+            || matches!(normalized, "low power" | "power" | "quiet" | "cool")
+        {
+            PowerState::PowerSaver
+        } else {
+            PowerState::Custom(name.to_string())
+        }
+    }
+}
+
+impl PowerSource {
+    /// Detect whether the machine is on AC or battery
+    pub fn query() -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            use std::fs;
+
+            let Ok(entries) = fs::read_dir("/sys/class/power_supply") else {
+                return PowerSource::AC;
+            };
+            // Offline adapters alone prove nothing: desktops expose idle USB-C
+            // ports (UCSI) as USB supplies without having a battery
+            let mut discharging = false;
+            for path in entries.flatten().map(|entry| entry.path()) {
+                let read = |name: &str| fs::read_to_string(path.join(name)).ok().map(|v| v.trim().to_string());
+                match read("type").as_deref() {
+                    Some("Mains" | "USB") if read("online").as_deref() == Some("1") => {
+                        return PowerSource::AC;
+                    }
+                    // Peripheral batteries (mice, keyboards) have device scope
+                    Some("Battery") if read("scope").as_deref() != Some("Device") => {
+                        discharging |= read("status").as_deref() == Some("Discharging");
+                    }
+                    _ => {}
+                }
+            }
+            if discharging {
+                PowerSource::Battery
+            } else {
+                PowerSource::AC
+            }
+        }
+        #[cfg(target_os = "windows")]
+        {
+            use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+            let mut status = SYSTEM_POWER_STATUS::default();
+            if unsafe { GetSystemPowerStatus(&mut status) }.is_err() {
+                return PowerSource::Unknown;
+            }
+            match status.ACLineStatus {
+                0 => PowerSource::Battery,
+                1 => PowerSource::AC,
+                _ => PowerSource::Unknown,
+            }
+        }
+        #[cfg(target_os = "macos")]
+        {
+            // Now drawing from 'AC Power'
+            let Ok(output) = crate::probe::Command::new("pmset").args(["-g", "batt"]).output() else {
+                return PowerSource::Unknown;
+            };
+            let text = String::from_utf8_lossy(&output.stdout);
+            if text.contains("'AC Power'") {
+                PowerSource::AC
+            } else if text.contains("'Battery Power'") || text.contains("'UPS Power'") {
+                PowerSource::Battery
+            } else {
+                PowerSource::Unknown
+            }
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            PowerSource::Unknown
+        }
     }
 }
 
 impl std::fmt::Display for PowerState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            PowerState::HighPerformance => write!(f, "High Performance"),
-            PowerState::Balanced => write!(f, "Balanced"),
-            PowerState::PowerSaver => write!(f, "Power Saver"),
-            PowerState::BatteryOptimized => write!(f, "Battery Optimized"),
-            PowerState::Custom(name) => write!(f, "Custom: {}", name),
-            PowerState::Unknown => write!(f, "Unknown"),
-        }
+        let text = match self {
+            PowerState::HighPerformance => "High Performance",
+            PowerState::Balanced => "Balanced",
+            PowerState::PowerSaver => "Power Saver",
+            PowerState::BatteryOptimized => "Battery Optimized",
+            PowerState::Custom(name) => {
+                return write!(f, "{}: {}", crate::l10n::localize("PowerState", "Custom"), name);
+            }
+            PowerState::Unknown => "Unknown",
+        };
+        f.write_str(&crate::l10n::localize("PowerState", text))
     }
 }
 
 impl std::fmt::Display for ThrottlingRisk {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ThrottlingRisk::None => write!(f, "None"),
-            ThrottlingRisk::Low => write!(f, "Low"),
-            ThrottlingRisk::Moderate => write!(f, "Moderate"),
-            ThrottlingRisk::High => write!(f, "High"),
-            ThrottlingRisk::Critical => write!(f, "Critical"),
-        }
+        let text = match self {
+            ThrottlingRisk::None => "None",
+            ThrottlingRisk::Low => "Low",
+            ThrottlingRisk::Moderate => "Moderate",
+            ThrottlingRisk::High => "High",
+            ThrottlingRisk::Critical => "Critical",
+        };
+        f.write_str(&crate::l10n::localize("ThrottlingRisk", text))
     }
 }
+
+impl std::fmt::Display for PowerSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            PowerSource::AC => "AC",
+            PowerSource::Battery => "Battery",
+            PowerSource::Unknown => "Unknown",
+        };
+        f.write_str(&crate::l10n::localize("PowerSource", text))
+    }
+}
+
+/// Whether `word` has the `8-4-4-4-12` hex shape of a GUID
+#[cfg(target_os = "windows")]
+fn is_guid(word: &str) -> bool {
+    let groups: Vec<&str> = word.split('-').collect();
+    groups.iter().map(|group| group.len()).eq([8, 4, 4, 4, 12])
+        && groups.iter().all(|group| group.chars().all(|c| c.is_ascii_hexdigit()))
+}
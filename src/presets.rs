@@ -4,7 +4,8 @@
 //! making it extremely easy for developers to get the information they need
 //! without having to understand all the available hardware types.
 
-use crate::{simple::SystemOverview, builder::HardwareQueryBuilder, Result};
+use crate::{simple::{SimpleAccelerator, SystemOverview}, builder::HardwareQueryBuilder, AIRuntime, AssetInventory, BenchmarkResults, CustomAssessment, GPUInfo, GPUPerformanceDatabase, GPUPerformanceEntry, GPUPerformanceTier, GPUType, GPUVendor, HardwareInfo, LlamaCppReport, ModelFitPlanner, ModelPlacement, MonitorInfo, OnnxProviderRecommendation, Quantization, RedactionOptions, Result, ScoringPlugin, SoftwareStack, StorageType, ThroughputClass, VideoCodec, VideoEngine, Volume};
+use std::time::Duration;
 use serde::{Serialize, Deserialize};
 
 /// AI/ML hardware assessment result
@@ -22,6 +23,168 @@ pub struct AIHardwareAssessment {
     pub performance: AIPerformanceEstimate,
     /// Optimization suggestions
     pub optimizations: Vec<String>,
+    /// Installed GPU compute / AI runtimes (CUDA, cuDNN, ROCm, ...)
+    #[serde(default)]
+    pub runtime_versions: SoftwareStack,
+}
+
+/// Local LLM inference assessment result
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LLMHardwareAssessment {
+    /// System overview
+    pub overview: SystemOverview,
+    /// Context length the size and throughput estimates assume
+    pub context_length: u32,
+    /// Reference model the throughput estimates use (8B parameters at Q4)
+    pub reference_model: String,
+    /// Largest model that fits, per weight format
+    pub max_model_sizes: Vec<LLMModelSize>,
+    /// KV cache memory of the reference model at common context lengths
+    pub context_memory: Vec<ContextMemory>,
+    /// Prompt processing speed, bound by compute
+    pub prefill: LLMThroughput,
+    /// Token generation speed, bound by memory bandwidth
+    pub decode: LLMThroughput,
+    /// Whether the common inference engines can run here
+    pub runtimes: Vec<LLMRuntimeSupport>,
+    /// Suggestions for running models locally
+    pub recommendations: Vec<String>,
+}
+
+/// Largest model that fits in a given weight format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LLMModelSize {
+    /// Weight format
+    pub quantization: Quantization,
+    /// Largest model held entirely in GPU or unified memory, in billions of parameters
+    pub max_parameters_in_gpu_billions: f64,
+    /// Largest model that runs at all (with offload or on the CPU), in billions of parameters
+    pub max_parameters_billions: f64,
+}
+
+/// KV cache cost of a context length
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextMemory {
+    /// Context length in tokens
+    pub context_length: u32,
+    /// KV cache size in GB
+    pub kv_cache_gb: f64,
+    /// Reference model plus this cache still fits in GPU memory
+    pub fits_in_gpu: bool,
+}
+
+/// Estimated single-stream throughput
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LLMThroughput {
+    /// Estimated tokens/sec
+    pub tokens_per_second: Option<f64>,
+    /// Speed class of the estimate
+    pub class: ThroughputClass,
+}
+
+/// Viability of an inference engine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LLMRuntimeSupport {
+    /// Engine name (llama.cpp, vLLM, TensorRT-LLM)
+    pub name: String,
+    /// Whether the engine can run on the detected hardware
+    pub viable: bool,
+    /// Backend it would use (CUDA, ROCm, Metal, CPU, ...)
+    pub backend: Option<String>,
+    /// Why it is or is not viable
+    pub notes: String,
+}
+
+/// Stable Diffusion / image generation assessment result
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageGenerationAssessment {
+    /// System overview
+    pub overview: SystemOverview,
+    /// GPU the models would run on, if any
+    pub gpu: Option<String>,
+    /// GPU memory usable for models in GB (VRAM, or unified memory on Apple Silicon)
+    pub usable_vram_gb: f64,
+    /// Each model at its common resolutions
+    pub models: Vec<ImageModelFit>,
+    /// Half-precision (fp16) inference runs natively
+    pub fp16_supported: bool,
+    /// bfloat16 inference runs natively (needed for Flux without quality loss)
+    pub bf16_supported: bool,
+    /// Memory-efficient attention and compiler backends
+    pub accelerations: Vec<ImageAcceleration>,
+    /// Suggestions for running image generation locally
+    pub recommendations: Vec<String>,
+}
+
+/// VRAM fit of one model at one resolution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageModelFit {
+    /// Model family (SD 1.5, SDXL, Flux.1)
+    pub model: String,
+    /// Square output size in pixels
+    pub resolution: u32,
+    /// VRAM needed for one image in GB
+    pub vram_required_gb: f64,
+    /// One image fits in VRAM
+    pub fits: bool,
+    /// Images per batch that fit in VRAM (0 when it does not fit)
+    pub recommended_batch_size: u32,
+    /// How to run it when it does not fit, or caveats
+    pub notes: Option<String>,
+}
+
+/// Availability of an image generation speed-up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageAcceleration {
+    /// Name (xformers, TensorRT)
+    pub name: String,
+    /// The detected GPU can use it
+    pub supported: bool,
+    /// Why, or what to use instead
+    pub notes: String,
+}
+
+/// Video editing / content creation assessment result
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentCreationAssessment {
+    /// System overview
+    pub overview: SystemOverview,
+    /// Hardware encode/decode engines of every GPU
+    pub video_engines: Vec<VideoEngine>,
+    /// GPU memory available to the editor in GB (VRAM, or unified memory on Apple Silicon)
+    pub gpu_memory_gb: f64,
+    /// Installed system memory in GB
+    pub memory_gb: f64,
+    /// Fastest internal drive (model and type)
+    pub media_drive: Option<String>,
+    /// Sequential read speed of that drive in MB/s
+    pub storage_throughput_mb_s: f64,
+    /// The read speed was reported by the drive rather than estimated from its type
+    #[serde(default)]
+    pub storage_throughput_measured: bool,
+    /// Editing at common timeline resolutions
+    pub timelines: Vec<TimelineSupport>,
+    /// Codec choices and proxy workflow suggestions
+    pub recommendations: Vec<String>,
+}
+
+/// Whether a timeline resolution can be edited without proxies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineSupport {
+    /// Timeline resolution (1080p, 4K, 6K, 8K)
+    pub resolution: String,
+    /// GPU memory the editor needs for effects and playback in GB
+    pub vram_required_gb: f64,
+    /// System memory needed in GB
+    pub memory_required_gb: f64,
+    /// Read speed for two streams of ProRes 422 HQ at 30 fps in MB/s
+    pub storage_required_mb_s: f64,
+    /// Original media can be edited directly
+    pub native_editing: bool,
+    /// Edit with proxy media instead
+    pub proxy_recommended: bool,
+    /// What falls short
+    pub limitations: Vec<String>,
 }
 
 /// Gaming hardware assessment result
@@ -37,6 +200,29 @@ pub struct GamingHardwareAssessment {
     pub bottlenecks: Vec<String>,
     /// Upgrade recommendations
     pub upgrade_recommendations: Vec<String>,
+    /// Relative GPU performance (GeForce RTX 4090 = 100), if the model is known
+    #[serde(default)]
+    pub gpu_performance_score: Option<u32>,
+    /// GPU performance tier, if the model is known
+    #[serde(default)]
+    pub gpu_tier: Option<GPUPerformanceTier>,
+}
+
+/// Scores calibrated with measured micro-benchmark results
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BenchmarkAssessment {
+    /// System overview (heuristic scores)
+    pub overview: SystemOverview,
+    /// Raw benchmark measurements
+    pub results: BenchmarkResults,
+    /// Score derived purely from measurements (0-100)
+    pub measured_score: u8,
+    /// Overall performance score blending specs and measurements (0-100)
+    pub performance_score: u8,
+    /// AI readiness score blending specs and measurements (0-100)
+    pub ai_score: u8,
+    /// Gaming score blending specs and measurements (0-100)
+    pub gaming_score: u8,
 }
 
 /// Developer hardware assessment result
@@ -76,6 +262,154 @@ pub struct AIFramework {
     pub performance_estimate: PerformanceLevel,
     pub requirements_met: bool,
     pub notes: String,
+    /// Every requirement from the framework's matrix that applies to this system
+    #[serde(default)]
+    pub requirements: Vec<RequirementCheck>,
+    /// The requirements that failed
+    #[serde(default)]
+    pub unmet_requirements: Vec<RequirementCheck>,
+}
+
+impl AIFramework {
+    fn new(name: impl Into<String>, compatibility: CompatibilityLevel, performance_estimate: PerformanceLevel, notes: String, requirements: Vec<RequirementCheck>) -> Self {
+        let unmet_requirements: Vec<RequirementCheck> =
+            requirements.iter().filter(|check| !check.met).cloned().collect();
+        Self {
+            name: name.into(),
+            compatibility,
+            performance_estimate,
+            requirements_met: unmet_requirements.is_empty(),
+            notes,
+            requirements,
+            unmet_requirements,
+        }
+    }
+}
+
+/// One row of a framework's requirements matrix
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FrameworkRequirement {
+    /// Minimum system memory in GB
+    SystemMemoryGb(f64),
+    /// Minimum GPU memory in GB, checked when a GPU is present
+    VramGb(f64),
+    /// Minimum CUDA compute capability, checked on NVIDIA GPUs
+    ComputeCapability(String),
+    /// Instruction set extension, checked on x86 CPUs
+    CpuFeature(String),
+    /// Minimum driver version, checked on that vendor's GPUs
+    DriverVersion { vendor: String, minimum: String },
+    /// Runtime that has to be installed
+    Runtime(AIRuntime),
+}
+
+impl std::fmt::Display for FrameworkRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrameworkRequirement::SystemMemoryGb(gb) => write!(f, "{gb:.0} GB system memory"),
+            FrameworkRequirement::VramGb(gb) => write!(f, "{gb:.0} GB VRAM"),
+            FrameworkRequirement::ComputeCapability(minimum) => write!(f, "CUDA compute capability {minimum}"),
+            FrameworkRequirement::CpuFeature(feature) => write!(f, "{} CPU support", feature.to_uppercase()),
+            FrameworkRequirement::DriverVersion { vendor, minimum } => write!(f, "{vendor} driver {minimum} or newer"),
+            FrameworkRequirement::Runtime(runtime) => write!(f, "{runtime} installed"),
+        }
+    }
+}
+
+impl FrameworkRequirement {
+    /// Evaluate the requirement, or `None` when it does not apply to this system
+    ///
+    /// Values the system does not report (an unknown driver version, say) count
+    /// as met, with the detail saying so.
+    pub fn check(&self, overview: &SystemOverview, runtimes: &SoftwareStack) -> Option<RequirementCheck> {
+        let (met, detail) = match self {
+            FrameworkRequirement::SystemMemoryGb(minimum) => (
+                overview.memory_gb >= *minimum,
+                format!("{:.1} GB installed", overview.memory_gb),
+            ),
+            FrameworkRequirement::VramGb(minimum) => {
+                let gpu = overview.gpu.as_ref()?;
+                (gpu.vram_gb >= *minimum, format!("{} has {:.1} GB", gpu.name, gpu.vram_gb))
+            }
+            FrameworkRequirement::ComputeCapability(minimum) => {
+                let gpu = overview.gpu.as_ref().filter(|gpu| gpu.vendor == "NVIDIA")?;
+                match gpu.compute_capability.as_deref() {
+                    Some(capability) => (
+                        version_at_least(capability, minimum),
+                        format!("{} is compute capability {capability}", gpu.name),
+                    ),
+                    None => (true, format!("{} did not report its compute capability", gpu.name)),
+                }
+            }
+            FrameworkRequirement::CpuFeature(feature) => {
+                if !matches!(overview.cpu.vendor.as_str(), "Intel" | "AMD") {
+                    return None;
+                }
+                let supported = overview.cpu.has_feature(feature);
+                let detail = if supported {
+                    format!("{} supports {}", overview.cpu.name, feature.to_uppercase())
+                } else {
+                    format!("{} lacks {}", overview.cpu.name, feature.to_uppercase())
+                };
+                (supported, detail)
+            }
+            FrameworkRequirement::DriverVersion { vendor, minimum } => {
+                let gpu = overview.gpu.as_ref().filter(|gpu| gpu.vendor == *vendor)?;
+                match gpu.driver_version.as_deref() {
+                    Some(version) => {
+                        let version = normalize_driver_version(vendor, version);
+                        (version_at_least(&version, minimum), format!("driver {version} installed"))
+                    }
+                    None => (true, "driver version not reported".to_string()),
+                }
+            }
+            FrameworkRequirement::Runtime(runtime) => match runtimes.get(*runtime) {
+                Some(installed) => (
+                    true,
+                    format!("{runtime} {} installed", installed.version.as_deref().unwrap_or("(unknown version)")),
+                ),
+                None => (false, format!("{runtime} not found")),
+            },
+        };
+        Some(RequirementCheck { requirement: self.clone(), met, detail })
+    }
+}
+
+/// Outcome of checking one framework requirement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequirementCheck {
+    /// What was checked
+    pub requirement: FrameworkRequirement,
+    /// Whether the system satisfies it
+    pub met: bool,
+    /// What was found, e.g. `RTX 3080 has 10.0 GB`
+    pub detail: String,
+}
+
+/// Compare dotted numeric versions (`8.6` >= `5.0`, `550.54.14` >= `525.60`)
+pub(crate) fn version_at_least(version: &str, minimum: &str) -> bool {
+    fn parts(version: &str) -> Vec<u32> {
+        version
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    }
+    parts(version) >= parts(minimum)
+}
+
+/// Windows reports NVIDIA drivers as `31.0.15.3640`; the public version is
+/// the last five digits, `536.40`
+fn normalize_driver_version(vendor: &str, version: &str) -> String {
+    let parts: Vec<&str> = version.split('.').collect();
+    if vendor == "NVIDIA" && parts.len() == 4 {
+        let digits: String = format!("{}{}", parts[2], parts[3]);
+        if digits.len() >= 5 && digits.chars().all(|c| c.is_ascii_digit()) {
+            let digits = &digits[digits.len() - 5..];
+            return format!("{}.{}", &digits[..3], &digits[3..]);
+        }
+    }
+    version.to_string()
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -191,26 +525,492 @@ impl HardwarePresets {
     /// Comprehensive AI/ML hardware assessment
     pub fn ai_assessment() -> Result<AIHardwareAssessment> {
         let overview = SystemOverview::quick()?;
+        Ok(Self::ai_assessment_from(overview, SoftwareStack::detect()))
+    }
+
+    /// AI/ML assessment of an existing overview and set of installed runtimes
+    ///
+    /// Use with [`SystemOverview::from_hardware_info`] to assess a recorded
+    /// snapshot. NPUs and TPUs in the overview count towards the score,
+    /// framework list and model recommendations.
+    pub fn ai_assessment_from(overview: SystemOverview, runtime_versions: SoftwareStack) -> AIHardwareAssessment {
         let ai_score = overview.ai_score();
-        
-        let frameworks = Self::assess_ai_frameworks(&overview);
+        let frameworks = Self::assess_ai_frameworks(&overview, &runtime_versions);
         let model_recommendations = Self::get_model_recommendations(&overview);
         let performance = Self::estimate_ai_performance(&overview);
-        let optimizations = Self::get_ai_optimizations(&overview);
+        let optimizations = Self::get_ai_optimizations(&overview, &runtime_versions);
 
-        Ok(AIHardwareAssessment {
+        AIHardwareAssessment {
             overview,
             ai_score,
             frameworks,
             model_recommendations,
             performance,
             optimizations,
+            runtime_versions,
+        }
+    }
+
+    /// Local LLM inference assessment
+    ///
+    /// Estimates the largest model per quantization, the memory cost of longer
+    /// contexts, prefill and decode speed, and which of llama.cpp, vLLM and
+    /// TensorRT-LLM can run on the detected hardware.
+    pub fn llm_assessment() -> Result<LLMHardwareAssessment> {
+        Self::llm_assessment_for(&HardwareInfo::query()?)
+    }
+
+    /// LLM inference assessment of already-detected hardware
+    pub fn llm_assessment_for(hardware: &HardwareInfo) -> Result<LLMHardwareAssessment> {
+        const CONTEXT_LENGTH: u32 = 4096;
+        const REFERENCE_BILLIONS: f64 = 8.0;
+
+        let overview = SystemOverview::from_hardware_info(hardware.clone())?;
+        let planner = |billions: f64, quantization: Quantization| {
+            ModelFitPlanner::from_billions(billions)
+                .quantization(quantization)
+                .context_length(CONTEXT_LENGTH)
+        };
+
+        let max_model_sizes = Quantization::ALL
+            .into_iter()
+            .filter(|q| *q != Quantization::FP32)
+            .map(|quantization| LLMModelSize {
+                quantization,
+                max_parameters_in_gpu_billions: Self::largest_model(|billions| {
+                    planner(billions, quantization).plan_for(hardware).fits_in_vram()
+                }),
+                max_parameters_billions: Self::largest_model(|billions| {
+                    planner(billions, quantization).plan_for(hardware).is_runnable()
+                }),
+            })
+            .collect();
+
+        let reference = planner(REFERENCE_BILLIONS, Quantization::Q4).plan_for(hardware);
+        let context_memory = [4096, 8192, 32768, 131072]
+            .into_iter()
+            .map(|context_length| {
+                let fit = planner(REFERENCE_BILLIONS, Quantization::Q4)
+                    .context_length(context_length)
+                    .plan_for(hardware);
+                ContextMemory { context_length, kv_cache_gb: fit.kv_cache_gb, fits_in_gpu: fit.fits_in_vram() }
+            })
+            .collect();
+
+        let decode = LLMThroughput {
+            tokens_per_second: reference.estimated_tokens_per_second(),
+            class: reference.throughput(),
+        };
+        let prefill_tokens_per_second = reference.is_runnable().then(|| {
+            // Prefill does 2 FLOPs per weight per prompt token at ~40% utilization
+            let tflops = Self::prefill_tflops(hardware, reference.placement());
+            (tflops * 1e12 * 0.4 / (2.0 * REFERENCE_BILLIONS * 1e9)).round()
+        });
+        let prefill = LLMThroughput {
+            tokens_per_second: prefill_tokens_per_second,
+            class: prefill_tokens_per_second
+                .map(ThroughputClass::from_prefill_tokens_per_second)
+                .unwrap_or(ThroughputClass::Impractical),
+        };
+
+        let runtimes = Self::assess_llm_runtimes(hardware);
+        let mut recommendations = Vec::new();
+        if let Some(quantization) = reference.suggested_quantizations().first() {
+            recommendations.push(format!(
+                "7-8B models run best at {quantization} ({})",
+                reference.placement()
+            ));
+        }
+        recommendations.extend(reference.notes().iter().cloned());
+        if decode.class < ThroughputClass::Interactive && overview.gpu.is_none() {
+            recommendations.push(
+                "Token generation is bound by memory bandwidth; a GPU with 12 GB+ VRAM or \
+                 faster (more channels of) RAM raises it the most"
+                    .to_string(),
+            );
+        }
+        if let Some(engine) = runtimes.iter().filter(|r| r.viable).max_by_key(|r| Self::llm_runtime_rank(&r.name)) {
+            recommendations.push(format!("Use {} for the best throughput on this hardware", engine.name));
+        }
+
+        Ok(LLMHardwareAssessment {
+            overview,
+            context_length: CONTEXT_LENGTH,
+            reference_model: format!("{REFERENCE_BILLIONS:.0}B {}", Quantization::Q4),
+            max_model_sizes,
+            context_memory,
+            prefill,
+            decode,
+            runtimes,
+            recommendations,
+        })
+    }
+
+    /// Stable Diffusion / image generation assessment
+    ///
+    /// Checks VRAM against SD 1.5, SDXL and Flux.1 at their common resolutions,
+    /// fp16/bf16 support and xformers/TensorRT availability, and recommends
+    /// batch sizes.
+    pub fn image_generation_assessment() -> Result<ImageGenerationAssessment> {
+        Self::image_generation_assessment_for(&HardwareInfo::query()?)
+    }
+
+    /// Image generation assessment of already-detected hardware
+    pub fn image_generation_assessment_for(hardware: &HardwareInfo) -> Result<ImageGenerationAssessment> {
+        // (model, fp16 weights incl. text encoders and VAE in GB, activations per megapixel in GB, resolutions)
+        const MODELS: [(&str, f64, f64, [u32; 2]); 3] = [
+            ("SD 1.5", 2.1, 3.0, [512, 768]),
+            ("SDXL", 6.9, 1.5, [1024, 1536]),
+            // FP8 transformer with the T5 text encoder offloaded to system RAM
+            ("Flux.1", 12.0, 1.5, [1024, 1536]),
+        ];
+        // CUDA/ROCm context, VAE decode workspace
+        const OVERHEAD_GB: f64 = 0.8;
+
+        let overview = SystemOverview::from_hardware_info(hardware.clone())?;
+        let gpu = hardware
+            .gpus()
+            .iter()
+            .filter(|gpu| gpu.gpu_type != GPUType::Virtual)
+            .filter(|gpu| gpu.vendor == GPUVendor::Apple || gpu.gpu_type != GPUType::Integrated)
+            .max_by_key(|gpu| gpu.memory_mb);
+        let usable_vram_gb = match gpu {
+            Some(gpu) if gpu.vendor == GPUVendor::Apple => hardware.memory().total_gb() * 0.75,
+            Some(gpu) => gpu.memory_gb() * 0.92,
+            None => 0.0,
+        };
+        let cuda_capability = gpu
+            .and_then(|gpu| gpu.cuda_capability())
+            .and_then(|capability| capability.parse::<f64>().ok());
+        let cuda_at_least = |version: f64| cuda_capability.is_some_and(|c| c >= version);
+        let rocm = gpu.is_some_and(|gpu| gpu.supports_rocm());
+        let metal = gpu.is_some_and(|gpu| gpu.vendor == GPUVendor::Apple);
+        let intel_arc = gpu.is_some_and(|gpu| gpu.vendor == GPUVendor::Intel);
+        let fp16_supported = cuda_at_least(6.0) || rocm || metal || intel_arc;
+        let bf16_supported = cuda_at_least(8.0) || rocm || metal || intel_arc;
+
+        let system_gb = hardware.memory().total_gb();
+        let mut models = Vec::new();
+        for (model, weights_gb, activations_gb, resolutions) in MODELS {
+            // Without fp16 the weights are held in fp32
+            let weights_gb = if fp16_supported { weights_gb } else { weights_gb * 2.0 };
+            for resolution in resolutions {
+                let megapixels = (resolution * resolution) as f64 / 1_000_000.0;
+                let per_image_gb = activations_gb * megapixels;
+                let vram_required_gb = ((weights_gb + OVERHEAD_GB + per_image_gb) * 10.0).round() / 10.0;
+                let recommended_batch_size = if usable_vram_gb >= vram_required_gb {
+                    (((usable_vram_gb - weights_gb - OVERHEAD_GB) / per_image_gb) as u32).clamp(1, 8)
+                } else {
+                    0
+                };
+                let fits = recommended_batch_size > 0;
+                let notes = if fits {
+                    None
+                } else if gpu.is_some() && system_gb >= weights_gb * 1.5 {
+                    Some("Runs with model CPU offload (--medvram / --lowvram), several times slower".to_string())
+                } else if gpu.is_none() && system_gb >= weights_gb * 1.5 {
+                    Some("Runs on the CPU only, minutes per image".to_string())
+                } else {
+                    Some("Not enough memory".to_string())
+                };
+                models.push(ImageModelFit {
+                    model: model.to_string(),
+                    resolution,
+                    vram_required_gb,
+                    fits,
+                    recommended_batch_size,
+                    notes,
+                });
+            }
+        }
+
+        let accelerations = vec![
+            ImageAcceleration {
+                name: "xformers".to_string(),
+                supported: cuda_at_least(5.0),
+                notes: if cuda_at_least(5.0) {
+                    "Memory-efficient attention cuts VRAM use at high resolutions".to_string()
+                } else {
+                    "CUDA only; PyTorch 2 scaled-dot-product attention gives similar savings".to_string()
+                },
+            },
+            ImageAcceleration {
+                name: "TensorRT".to_string(),
+                supported: cuda_at_least(7.5),
+                notes: if cuda_at_least(7.5) {
+                    "Compiled UNet engines roughly double SD 1.5/SDXL speed".to_string()
+                } else {
+                    "Needs an NVIDIA GPU with compute capability 7.5+ (Turing or newer)".to_string()
+                },
+            },
+        ];
+
+        let mut recommendations = Vec::new();
+        if let Some(best) = models.iter().rev().find(|fit| fit.fits) {
+            recommendations.push(format!(
+                "{} at {}x{} fits with batch size {}",
+                best.model, best.resolution, best.resolution, best.recommended_batch_size
+            ));
+        }
+        if gpu.is_some() && !fp16_supported {
+            recommendations.push("GPU lacks fast fp16; run with --no-half (fp32), which doubles VRAM use".to_string());
+        }
+        if fp16_supported && !bf16_supported {
+            recommendations.push("bf16 is not native; keep the SDXL VAE in fp32 (--no-half-vae) to avoid black images".to_string());
+        }
+        if models.iter().any(|fit| fit.model == "SDXL" && !fit.fits) && gpu.is_some() {
+            recommendations.push("SDXL needs 10 GB+ VRAM at 1024x1024; use SD 1.5 or --medvram".to_string());
+        }
+        if gpu.is_none() {
+            recommendations.push("Add a GPU with 12 GB+ VRAM for SDXL, 16 GB+ for Flux.1".to_string());
+        }
+
+        Ok(ImageGenerationAssessment {
+            overview,
+            gpu: gpu.map(|gpu| gpu.model_name().to_string()),
+            usable_vram_gb,
+            models,
+            fp16_supported,
+            bf16_supported,
+            accelerations,
+            recommendations,
+        })
+    }
+
+    /// Video editing / content creation assessment
+    ///
+    /// Reports hardware encode/decode support (NVENC, Quick Sync, VCN,
+    /// VideoToolbox), GPU memory, storage throughput and system memory against
+    /// common timeline resolutions, and suggests codecs and proxy workflows.
+    pub fn content_creation_assessment() -> Result<ContentCreationAssessment> {
+        Self::content_creation_assessment_for(&HardwareInfo::query()?)
+    }
+
+    /// Content creation assessment of already-detected hardware
+    pub fn content_creation_assessment_for(hardware: &HardwareInfo) -> Result<ContentCreationAssessment> {
+        // (resolution, VRAM GB, RAM GB, two streams of ProRes 422 HQ at 30 fps in MB/s)
+        const TIMELINES: [(&str, f64, f64, f64); 4] = [
+            ("1080p", 2.0, 16.0, 56.0),
+            ("4K", 6.0, 32.0, 220.0),
+            ("6K", 10.0, 32.0, 500.0),
+            ("8K", 16.0, 64.0, 880.0),
+        ];
+
+        let overview = SystemOverview::from_hardware_info(hardware.clone())?;
+        let video_engines: Vec<VideoEngine> = hardware.gpus().iter().filter_map(GPUInfo::video_engine).collect();
+        let memory_gb = hardware.memory().total_gb();
+        let gpu_memory_gb = hardware
+            .gpus()
+            .iter()
+            .filter(|gpu| gpu.gpu_type != GPUType::Virtual)
+            .map(|gpu| {
+                if gpu.vendor == GPUVendor::Apple {
+                    memory_gb * 0.75
+                } else {
+                    gpu.memory_gb()
+                }
+            })
+            .fold(0.0, f64::max);
+
+        let drive_speed = |drive: &crate::StorageInfo| match drive.read_speed_mb_s {
+            Some(speed) => (speed as f64, true),
+            None => (
+                match drive.drive_type() {
+                    StorageType::NVMe => 2000.0,
+                    StorageType::SSD => 500.0,
+                    StorageType::EMmc => 250.0,
+                    StorageType::HDD => 150.0,
+                    StorageType::USB => 100.0,
+                    StorageType::SD => 80.0,
+                    StorageType::Unknown => 200.0,
+                },
+                false,
+            ),
+        };
+        let media_drive = hardware
+            .storage_devices()
+            .iter()
+            .filter(|drive| !drive.removable)
+            .max_by(|a, b| drive_speed(a).0.total_cmp(&drive_speed(b).0));
+        let (storage_throughput_mb_s, storage_throughput_measured) =
+            media_drive.map(drive_speed).unwrap_or((0.0, false));
+
+        let hevc_decode = video_engines.iter().any(|engine| engine.can_decode(VideoCodec::HEVC));
+        let timelines: Vec<TimelineSupport> = TIMELINES
+            .into_iter()
+            .map(|(resolution, vram_required_gb, memory_required_gb, storage_required_mb_s)| {
+                let mut limitations = Vec::new();
+                if gpu_memory_gb < vram_required_gb {
+                    limitations.push(format!("{vram_required_gb:.0} GB GPU memory needed, {gpu_memory_gb:.1} GB available"));
+                }
+                if memory_gb < memory_required_gb {
+                    limitations.push(format!("{memory_required_gb:.0} GB RAM needed, {memory_gb:.0} GB installed"));
+                }
+                if storage_throughput_mb_s < storage_required_mb_s {
+                    limitations.push(format!(
+                        "{storage_required_mb_s:.0} MB/s storage needed, {storage_throughput_mb_s:.0} MB/s available"
+                    ));
+                }
+                if !hevc_decode && resolution != "1080p" {
+                    limitations.push("No hardware HEVC decode for camera originals".to_string());
+                }
+                let native_editing = limitations.is_empty();
+                TimelineSupport {
+                    resolution: resolution.to_string(),
+                    vram_required_gb,
+                    memory_required_gb,
+                    storage_required_mb_s,
+                    native_editing,
+                    proxy_recommended: !native_editing,
+                    limitations,
+                }
+            })
+            .collect();
+
+        let mut recommendations = Vec::new();
+        let engine_with = |codec: VideoCodec| video_engines.iter().find(|engine| engine.can_encode(codec));
+        if let Some(engine) = engine_with(VideoCodec::AV1) {
+            recommendations.push(format!("Export AV1 with {} on the {} for the smallest files", engine.name, engine.gpu));
+        } else if let Some(engine) = engine_with(VideoCodec::HEVC) {
+            recommendations.push(format!("Export HEVC with {} on the {}; AV1 needs a newer GPU", engine.name, engine.gpu));
+        } else if let Some(engine) = engine_with(VideoCodec::H264) {
+            recommendations.push(format!("Export H.264 with {} on the {}", engine.name, engine.gpu));
+        } else {
+            recommendations.push("No hardware encoder; exports use the CPU (x264/x265) and render slower".to_string());
+        }
+        if let Some(engine) = engine_with(VideoCodec::ProRes) {
+            recommendations.push(format!("{} encodes ProRes in hardware; use it for intermediates and proxies", engine.name));
+        }
+        if hevc_decode {
+            recommendations.push("H.264/HEVC camera footage can be edited directly with hardware decode".to_string());
+        } else {
+            recommendations.push("Transcode H.264/HEVC camera footage to ProRes or DNxHR before editing".to_string());
+        }
+        let proxied: Vec<&str> = timelines
+            .iter()
+            .filter(|timeline| timeline.proxy_recommended)
+            .map(|timeline| timeline.resolution.as_str())
+            .collect();
+        if !proxied.is_empty() {
+            recommendations.push(format!(
+                "Edit {} timelines with proxy media (ProRes Proxy / DNxHR LB)",
+                proxied.join(", ")
+            ));
+        }
+        if media_drive.is_some_and(|drive| drive.drive_type() == &StorageType::HDD) {
+            recommendations.push("Keep media, cache and proxies on an SSD; hard disks stall multi-stream playback".to_string());
+        }
+
+        Ok(ContentCreationAssessment {
+            overview,
+            video_engines,
+            gpu_memory_gb,
+            memory_gb,
+            media_drive: media_drive.map(|drive| format!("{} ({})", drive.model(), drive.drive_type())),
+            storage_throughput_mb_s,
+            storage_throughput_measured,
+            timelines,
+            recommendations,
+        })
+    }
+
+    /// Assessment using a third-party [`ScoringPlugin`]
+    pub fn custom_assessment(plugin: &dyn ScoringPlugin) -> Result<CustomAssessment> {
+        Self::custom_assessment_for(plugin, &HardwareInfo::query()?)
+    }
+
+    /// Custom assessment of already-detected hardware
+    pub fn custom_assessment_for(plugin: &dyn ScoringPlugin, hardware: &HardwareInfo) -> Result<CustomAssessment> {
+        let overview = SystemOverview::from_hardware_info(hardware.clone())?;
+        let scores = plugin.scores(hardware);
+        let recommendations = plugin.recommendations(hardware, &scores);
+
+        Ok(CustomAssessment {
+            name: plugin.name().to_string(),
+            overview,
+            overall_score: plugin.overall_score(&scores).min(100),
+            scores,
+            recommendations,
         })
     }
 
+    /// llama.cpp backend support, CPU path and `-ngl` suggestions
+    pub fn llama_cpp_report() -> Result<LlamaCppReport> {
+        Ok(Self::llama_cpp_report_for(&HardwareInfo::query()?))
+    }
+
+    /// llama.cpp report for already-detected hardware
+    pub fn llama_cpp_report_for(hardware: &HardwareInfo) -> LlamaCppReport {
+        LlamaCppReport::from_hardware(hardware)
+    }
+
+    /// Flattened inventory for CMDB ingestion: system identification, CPU,
+    /// DIMMs, disks, NICs, GPUs, monitors and USB peripherals with their serials
+    ///
+    /// ```rust
+    /// use hardware_query::{HardwarePresets, RedactionOptions};
+    ///
+    /// # fn main() -> hardware_query::Result<()> {
+    /// let inventory = HardwarePresets::asset_inventory()?;
+    /// println!("{}", serde_json::to_string_pretty(&inventory)?);
+    ///
+    /// // Serials, UUID, asset tag, host name and MACs as salted hashes
+    /// let shared = HardwarePresets::asset_inventory_redacted(&RedactionOptions::hashed("fleet-salt"))?;
+    /// # let _ = shared;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn asset_inventory() -> Result<AssetInventory> {
+        let hardware = HardwareInfo::query()?;
+        Ok(Self::asset_inventory_for(&hardware, &MonitorInfo::query_all().unwrap_or_default(), None))
+    }
+
+    /// Asset inventory with identifying values redacted according to `options`
+    pub fn asset_inventory_redacted(options: &RedactionOptions) -> Result<AssetInventory> {
+        let hardware = HardwareInfo::query()?;
+        Ok(Self::asset_inventory_for(&hardware, &MonitorInfo::query_all().unwrap_or_default(), Some(options)))
+    }
+
+    /// Asset inventory for already-detected hardware and monitors, optionally redacted
+    pub fn asset_inventory_for(
+        hardware: &HardwareInfo,
+        monitors: &[MonitorInfo],
+        redaction: Option<&RedactionOptions>,
+    ) -> AssetInventory {
+        let inventory = AssetInventory::from_hardware(hardware, monitors);
+        match redaction {
+            Some(options) => inventory.redacted_with(options),
+            None => inventory,
+        }
+    }
+
+    /// ONNX Runtime execution providers to register, in order
+    ///
+    /// Providers whose runtime libraries were found come first; the CPU
+    /// provider is always included.
+    ///
+    /// ```rust
+    /// use hardware_query::HardwarePresets;
+    ///
+    /// # fn main() -> hardware_query::Result<()> {
+    /// for recommendation in HardwarePresets::onnx_execution_providers()? {
+    ///     println!("{}: {}", recommendation.provider.ort_name(), recommendation.reason);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn onnx_execution_providers() -> Result<Vec<OnnxProviderRecommendation>> {
+        Ok(Self::onnx_execution_providers_for(&HardwareInfo::query()?, &SoftwareStack::detect()))
+    }
+
+    /// ONNX Runtime execution providers for already-detected hardware and runtimes
+    pub fn onnx_execution_providers_for(hardware: &HardwareInfo, software: &SoftwareStack) -> Vec<OnnxProviderRecommendation> {
+        crate::onnx::recommend(hardware, software)
+    }
+
     /// Gaming hardware assessment and recommendations
     pub fn gaming_assessment() -> Result<GamingHardwareAssessment> {
-        let _hw_info = HardwareQueryBuilder::new()
+        let hw_info = HardwareQueryBuilder::new()
             .with_gaming_focused()
             .query()?;
         
@@ -218,7 +1018,9 @@ impl HardwarePresets {
         let gaming_score = Self::calculate_gaming_score(&overview);
         let recommended_settings = Self::get_game_settings(&overview);
         let bottlenecks = Self::identify_gaming_bottlenecks(&overview);
-        let upgrade_recommendations = Self::get_gaming_upgrades(&overview);
+        let mut upgrade_recommendations = Self::get_gaming_upgrades(&overview);
+        upgrade_recommendations.extend(Self::get_resizable_bar_recommendations(&hw_info.gpus));
+        let gpu_performance = Self::gpu_performance(&overview);
 
         Ok(GamingHardwareAssessment {
             overview,
@@ -226,6 +1028,8 @@ impl HardwarePresets {
             recommended_settings,
             bottlenecks,
             upgrade_recommendations,
+            gpu_performance_score: gpu_performance.as_ref().map(|entry| entry.score),
+            gpu_tier: gpu_performance.as_ref().map(|entry| entry.tier()),
         })
     }
 
@@ -235,7 +1039,9 @@ impl HardwarePresets {
         let dev_score = Self::calculate_dev_score(&overview);
         let environments = Self::assess_dev_environments(&overview);
         let virtualization_support = Self::assess_virtualization(&overview);
-        let tool_recommendations = Self::get_dev_tool_recommendations(&overview);
+        let mut tool_recommendations = Self::get_dev_tool_recommendations(&overview);
+        let volumes = Volume::query_all().unwrap_or_default();
+        tool_recommendations.extend(Self::get_filesystem_recommendations(&volumes));
 
         Ok(DeveloperHardwareAssessment {
             overview,
@@ -267,7 +1073,39 @@ impl HardwarePresets {
         })
     }
 
+    /// Run short CPU, memory and storage micro-benchmarks and calibrate the
+    /// heuristic scores with the measured numbers
+    ///
+    /// This takes roughly `duration` and loads every CPU core while it runs.
+    pub fn benchmark_assessment(duration: Duration) -> Result<BenchmarkAssessment> {
+        let overview = SystemOverview::quick()?;
+        let results = BenchmarkResults::run(duration)?;
+        let measured_score = results.overall_score();
+
+        // AI and gaming scores are mostly GPU-driven and no GPU probe runs, so only
+        // a third of each is replaced by the measured CPU/memory numbers
+        let heuristic_ai = overview.ai_score() as u16;
+        let measured_ai = (results.cpu_score() as u16 + results.memory_score() as u16) / 2;
+        let ai_score = ((heuristic_ai * 2 + measured_ai) / 3) as u8;
+
+        let heuristic_gaming = Self::calculate_gaming_score(&overview) as u16;
+        let gaming_score = ((heuristic_gaming * 2 + results.cpu_score() as u16) / 3) as u8;
+
+        Ok(BenchmarkAssessment {
+            performance_score: crate::benchmark::calibrate(overview.performance_score, measured_score),
+            overview,
+            results,
+            measured_score,
+            ai_score,
+            gaming_score,
+        })
+    }
+
     /// Check if system is ready for a specific AI model
+    ///
+    /// Only compares `memory_gb` against VRAM (or 70% of RAM); use
+    /// [`ModelFitPlanner`](crate::ModelFitPlanner) to account for quantization
+    /// and context length.
     pub fn check_ai_model_compatibility(_model_name: &str, _params: &str, memory_gb: f64) -> Result<bool> {
         let overview = SystemOverview::quick()?;
         
@@ -285,8 +1123,11 @@ impl HardwarePresets {
     pub fn gaming_fps_estimate(resolution: &str, quality: &str) -> Result<u32> {
         let overview = SystemOverview::quick()?;
         
-        // Simple FPS estimation based on hardware
-        let base_fps = if let Some(gpu) = &overview.gpu {
+        // 1080p Medium FPS from the GPU's relative performance, falling back
+        // to a VRAM heuristic for models the performance database lacks
+        let base_fps = if let Some(entry) = Self::gpu_performance(&overview) {
+            20 + entry.score * 8 / 5
+        } else if let Some(gpu) = &overview.gpu {
             if gpu.vram_gb >= 8.0 {
                 120
             } else if gpu.vram_gb >= 4.0 {
@@ -318,61 +1159,371 @@ impl HardwarePresets {
         Ok(estimated_fps.max(15)) // Minimum 15 FPS
     }
 
+    /// Requirements matrix for an AI framework
+    ///
+    /// Covers the prebuilt CUDA 12 packages for PyTorch and TensorFlow; GPU
+    /// runtimes the primary GPU needs are checked separately. Unknown
+    /// frameworks have no requirements.
+    pub fn framework_requirements(framework: &str) -> Vec<FrameworkRequirement> {
+        let nvidia_driver = || FrameworkRequirement::DriverVersion {
+            vendor: "NVIDIA".to_string(),
+            minimum: "525.60".to_string(),
+        };
+        match framework.to_lowercase().as_str() {
+            "pytorch" => vec![
+                FrameworkRequirement::SystemMemoryGb(4.0),
+                FrameworkRequirement::VramGb(2.0),
+                FrameworkRequirement::ComputeCapability("5.0".to_string()),
+                nvidia_driver(),
+            ],
+            "tensorflow" => vec![
+                FrameworkRequirement::SystemMemoryGb(4.0),
+                FrameworkRequirement::VramGb(2.0),
+                FrameworkRequirement::ComputeCapability("3.5".to_string()),
+                FrameworkRequirement::CpuFeature("avx".to_string()),
+                nvidia_driver(),
+            ],
+            "onnx runtime" => vec![FrameworkRequirement::SystemMemoryGb(2.0)],
+            "openvino" => vec![
+                FrameworkRequirement::SystemMemoryGb(2.0),
+                FrameworkRequirement::CpuFeature("sse4.2".to_string()),
+                FrameworkRequirement::Runtime(AIRuntime::OpenVINO),
+            ],
+            _ => Vec::new(),
+        }
+    }
+
     // Private implementation methods
-    fn assess_ai_frameworks(overview: &SystemOverview) -> Vec<AIFramework> {
+    fn assess_ai_frameworks(overview: &SystemOverview, runtimes: &SoftwareStack) -> Vec<AIFramework> {
         let mut frameworks = Vec::new();
+        let missing = Self::missing_gpu_runtimes(overview, runtimes);
+        let with_runtime_note = |notes: &str, missing: &[AIRuntime]| {
+            if missing.is_empty() {
+                notes.to_string()
+            } else {
+                let names: Vec<String> = missing.iter().map(ToString::to_string).collect();
+                format!("{notes} (GPU acceleration needs {})", names.join(", "))
+            }
+        };
+        // The matrix plus the runtimes the primary GPU needs
+        let check = |framework: &str, gpu_runtimes: &[AIRuntime]| -> Vec<RequirementCheck> {
+            Self::framework_requirements(framework)
+                .into_iter()
+                .chain(gpu_runtimes.iter().copied().map(FrameworkRequirement::Runtime))
+                .filter_map(|requirement| requirement.check(overview, runtimes))
+                .collect()
+        };
 
-        // PyTorch
-        frameworks.push(AIFramework {
-            name: "PyTorch".to_string(),
-            compatibility: if overview.gpu.is_some() { 
+        // PyTorch wheels bundle cuDNN, so only the platform runtime is required
+        let gpu_runtimes = Self::required_gpu_runtimes(overview);
+        let torch_runtimes: Vec<AIRuntime> =
+            gpu_runtimes.iter().copied().filter(|r| *r != AIRuntime::CuDNN).collect();
+        let torch_missing: Vec<AIRuntime> =
+            missing.iter().copied().filter(|r| *r != AIRuntime::CuDNN).collect();
+        frameworks.push(AIFramework::new(
+            "PyTorch",
+            if overview.gpu.is_some() { 
                 CompatibilityLevel::Excellent 
             } else { 
                 CompatibilityLevel::Good 
             },
-            performance_estimate: if overview.gpu.as_ref().map_or(false, |g| g.ai_capable) {
+            if overview.gpu.as_ref().is_some_and(|g| g.ai_capable) {
                 PerformanceLevel::Excellent
             } else {
                 PerformanceLevel::Fair
             },
-            requirements_met: overview.memory_gb >= 4.0,
-            notes: "Popular deep learning framework with excellent GPU support".to_string(),
-        });
+            with_runtime_note(
+                "Popular deep learning framework with excellent GPU support",
+                &torch_missing,
+            ),
+            check("PyTorch", &torch_runtimes),
+        ));
 
         // TensorFlow
-        frameworks.push(AIFramework {
-            name: "TensorFlow".to_string(),
-            compatibility: if overview.gpu.is_some() { 
+        frameworks.push(AIFramework::new(
+            "TensorFlow",
+            if overview.gpu.is_some() { 
                 CompatibilityLevel::Excellent 
             } else { 
                 CompatibilityLevel::Good 
             },
-            performance_estimate: if overview.gpu.as_ref().map_or(false, |g| g.ai_capable) {
+            if overview.gpu.as_ref().is_some_and(|g| g.ai_capable) {
                 PerformanceLevel::Excellent
             } else {
                 PerformanceLevel::Fair
             },
-            requirements_met: overview.memory_gb >= 4.0,
-            notes: "Google's ML framework with strong production support".to_string(),
-        });
+            with_runtime_note("Google's ML framework with strong production support", &missing),
+            check("TensorFlow", gpu_runtimes),
+        ));
 
         // ONNX Runtime
-        frameworks.push(AIFramework {
-            name: "ONNX Runtime".to_string(),
-            compatibility: CompatibilityLevel::Excellent,
-            performance_estimate: PerformanceLevel::Good,
-            requirements_met: true,
-            notes: "Cross-platform inference with broad hardware support".to_string(),
-        });
+        frameworks.push(AIFramework::new(
+            "ONNX Runtime",
+            CompatibilityLevel::Excellent,
+            PerformanceLevel::Good,
+            "Cross-platform inference with broad hardware support".to_string(),
+            check("ONNX Runtime", &[]),
+        ));
+
+        // OpenVINO is listed when it is installed or an accelerator (Intel NPU) needs it
+        let openvino = runtimes.get(AIRuntime::OpenVINO);
+        let openvino_targets: Vec<&SimpleAccelerator> = overview
+            .accelerators
+            .iter()
+            .filter(|accelerator| accelerator.supports_framework("OpenVINO"))
+            .collect();
+        if openvino.is_some() || !openvino_targets.is_empty() {
+            let notes = match openvino {
+                Some(openvino) => format!(
+                    "Intel inference toolkit {} installed",
+                    openvino.version.as_deref().unwrap_or("(unknown version)")
+                ),
+                None => "Install the Intel inference toolkit to offload models to the NPU".to_string(),
+            };
+            frameworks.push(AIFramework::new(
+                "OpenVINO",
+                if openvino_targets.is_empty() {
+                    CompatibilityLevel::Good
+                } else {
+                    CompatibilityLevel::Excellent
+                },
+                if openvino_targets.is_empty() {
+                    PerformanceLevel::Good
+                } else {
+                    Self::accelerator_performance(
+                        openvino_targets.iter().filter_map(|accelerator| accelerator.tops).reduce(f32::max),
+                    )
+                },
+                notes,
+                check("OpenVINO", &[]),
+            ));
+        }
+
+        // Frameworks already listed note which accelerators they can target
+        for framework in &mut frameworks {
+            let targets: Vec<String> = overview
+                .accelerators
+                .iter()
+                .filter(|accelerator| accelerator.frameworks.iter().any(|f| f.eq_ignore_ascii_case(&framework.name)))
+                .map(Self::describe_accelerator)
+                .collect();
+            if !targets.is_empty() {
+                framework.notes = format!("{}; runs on {}", framework.notes, targets.join(", "));
+            }
+        }
+
+        // Vendor toolchains that only the accelerators support (Core ML, TensorFlow Lite, ...)
+        for accelerator in &overview.accelerators {
+            for name in &accelerator.frameworks {
+                if frameworks.iter().any(|f| f.name.eq_ignore_ascii_case(name)) {
+                    continue;
+                }
+                frameworks.push(AIFramework::new(
+                    name.clone(),
+                    CompatibilityLevel::Good,
+                    Self::accelerator_performance(accelerator.tops),
+                    format!("Targets the {}", Self::describe_accelerator(accelerator)),
+                    check(name, &[]),
+                ));
+            }
+        }
 
         frameworks
     }
 
+    /// Largest size in billions (to 0.1B, up to 1000B) for which `fits` holds
+    fn largest_model(fits: impl Fn(f64) -> bool) -> f64 {
+        let (mut low, mut high) = (0.0, 1000.0);
+        if !fits(0.1) {
+            return 0.0;
+        }
+        while high - low > 0.05 {
+            let mid = (low + high) / 2.0;
+            if fits(mid) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        (low * 10.0).floor() / 10.0
+    }
+
+    /// Rough dense FP16 TFLOPS of wherever the model runs
+    fn prefill_tflops(hardware: &HardwareInfo, placement: ModelPlacement) -> f64 {
+        let gpu_tflops = |gpu: &GPUInfo| match (gpu.fp16_tflops(), gpu.performance_entry()) {
+            // Kernels fall back to FP32 where FP16 is slower (Pascal)
+            (Some(tflops), _) => tflops.max(gpu.fp32_tflops().unwrap_or(0.0)),
+            // GeForce RTX 4090 (score 100) has ~165 dense FP16 tensor TFLOPS
+            (None, Some(entry)) => entry.score() as f64 * 1.65,
+            (None, None) if gpu.vendor == GPUVendor::Apple => 10.0,
+            (None, None) => match gpu.gpu_type {
+                GPUType::Datacenter => 300.0,
+                GPUType::Workstation => 80.0,
+                GPUType::Discrete => 40.0,
+                _ => 4.0,
+            },
+        };
+        let gpu = hardware.gpus().iter().max_by_key(|gpu| gpu.memory_mb);
+        match (placement, gpu) {
+            (ModelPlacement::Gpu | ModelPlacement::MultiGpu | ModelPlacement::UnifiedMemory, Some(gpu)) => {
+                gpu_tflops(gpu)
+            }
+            // Offloaded layers run at GPU speed, the rest on the CPU
+            (ModelPlacement::PartialOffload, Some(gpu)) => gpu_tflops(gpu) / 4.0,
+            _ => {
+                let cpu = hardware.cpu();
+                // Assume 2 GHz when the clock is unknown
+                let gflops = cpu
+                    .estimated_gflops()
+                    .unwrap_or(cpu.physical_cores().max(1) as f64 * 2.0 * cpu.flops_per_cycle());
+                gflops / 1000.0
+            }
+        }
+    }
+
+    /// llama.cpp, vLLM and TensorRT-LLM viability
+    fn assess_llm_runtimes(hardware: &HardwareInfo) -> Vec<LLMRuntimeSupport> {
+        let gpus = hardware.gpus();
+        let cuda_capability = gpus
+            .iter()
+            .filter_map(|gpu| gpu.cuda_capability())
+            .filter_map(|capability| capability.parse::<f64>().ok())
+            .reduce(f64::max);
+        let largest_vram_gb = |vendor: GPUVendor| {
+            gpus.iter()
+                .filter(|gpu| gpu.vendor == vendor)
+                .map(|gpu| gpu.memory_gb())
+                .reduce(f64::max)
+        };
+        let nvidia_vram = largest_vram_gb(GPUVendor::NVIDIA);
+        let rocm = gpus.iter().any(|gpu| gpu.supports_rocm());
+        let linux = cfg!(target_os = "linux");
+
+        let llama_cpp_backend = if gpus.iter().any(|gpu| gpu.vendor == GPUVendor::Apple) {
+            "Metal"
+        } else if nvidia_vram.is_some() {
+            "CUDA"
+        } else if rocm {
+            "ROCm"
+        } else if gpus.iter().any(|gpu| gpu.supports_vulkan() && gpu.gpu_type != GPUType::Virtual) {
+            "Vulkan"
+        } else {
+            "CPU"
+        };
+        let llama_cpp = LLMRuntimeSupport {
+            name: "llama.cpp".to_string(),
+            viable: hardware.memory().total_gb() >= 4.0,
+            backend: Some(llama_cpp_backend.to_string()),
+            notes: "Runs GGUF models on any CPU and offloads layers to the GPU".to_string(),
+        };
+
+        let (vllm_viable, vllm_backend, vllm_notes) = match (cuda_capability, nvidia_vram) {
+            _ if !linux => (false, None, "Requires Linux (or WSL 2)".to_string()),
+            (Some(capability), Some(vram)) if capability >= 7.0 && vram >= 8.0 => (
+                true,
+                Some("CUDA"),
+                format!("Compute capability {capability:.1} with {vram:.0} GB VRAM"),
+            ),
+            (Some(capability), Some(_)) if capability < 7.0 => {
+                (false, None, format!("Needs compute capability 7.0+, found {capability:.1}"))
+            }
+            (_, Some(vram)) if vram < 8.0 => (false, None, format!("Needs 8 GB+ VRAM, found {vram:.0} GB")),
+            _ if rocm => (true, Some("ROCm"), "Runs on ROCm-supported AMD GPUs".to_string()),
+            _ => (false, None, "Needs an NVIDIA (compute capability 7.0+) or ROCm GPU".to_string()),
+        };
+        let vllm = LLMRuntimeSupport {
+            name: "vLLM".to_string(),
+            viable: vllm_viable,
+            backend: vllm_backend.map(str::to_string),
+            notes: vllm_notes,
+        };
+
+        let (trt_viable, trt_notes) = match (cuda_capability, nvidia_vram) {
+            _ if !(linux || cfg!(target_os = "windows")) => (false, "Requires Linux or Windows".to_string()),
+            (Some(capability), Some(vram)) if capability >= 8.0 && vram >= 8.0 => (
+                true,
+                format!("Compute capability {capability:.1} with {vram:.0} GB VRAM"),
+            ),
+            (Some(capability), Some(_)) if capability < 8.0 => {
+                (false, format!("Needs compute capability 8.0+ (Ampere or newer), found {capability:.1}"))
+            }
+            (_, Some(vram)) if vram < 8.0 => (false, format!("Needs 8 GB+ VRAM, found {vram:.0} GB")),
+            _ => (false, "Needs an NVIDIA GPU with compute capability 8.0+".to_string()),
+        };
+        let tensorrt_llm = LLMRuntimeSupport {
+            name: "TensorRT-LLM".to_string(),
+            viable: trt_viable,
+            backend: trt_viable.then(|| "CUDA".to_string()),
+            notes: trt_notes,
+        };
+
+        vec![llama_cpp, vllm, tensorrt_llm]
+    }
+
+    /// Preference among viable engines, by throughput
+    fn llm_runtime_rank(name: &str) -> u8 {
+        match name {
+            "TensorRT-LLM" => 2,
+            "vLLM" => 1,
+            _ => 0,
+        }
+    }
+
+    /// Expected inference performance of an NPU/TPU from its peak TOPS
+    fn accelerator_performance(tops: Option<f32>) -> PerformanceLevel {
+        match tops {
+            Some(tops) if tops >= 40.0 => PerformanceLevel::Excellent,
+            Some(tops) if tops >= 10.0 => PerformanceLevel::Good,
+            Some(tops) if tops >= 2.0 => PerformanceLevel::Fair,
+            Some(_) => PerformanceLevel::Poor,
+            None => PerformanceLevel::Fair,
+        }
+    }
+
+    /// `Intel NPU (11 TOPS)`
+    fn describe_accelerator(accelerator: &SimpleAccelerator) -> String {
+        match accelerator.tops {
+            Some(tops) => format!("{} ({tops:.0} TOPS)", accelerator.name),
+            None => accelerator.name.clone(),
+        }
+    }
+
+    /// Best quantized-inference accelerator, by TOPS
+    fn best_quantized_accelerator(overview: &SystemOverview) -> Option<&SimpleAccelerator> {
+        overview
+            .accelerators
+            .iter()
+            .filter(|accelerator| accelerator.runs_quantized())
+            .max_by(|a, b| a.tops.unwrap_or(0.0).total_cmp(&b.tops.unwrap_or(0.0)))
+    }
+
+    /// Runtimes the primary GPU needs for acceleration
+    fn required_gpu_runtimes(overview: &SystemOverview) -> &'static [AIRuntime] {
+        let Some(gpu) = &overview.gpu else {
+            return &[];
+        };
+        match gpu.vendor.as_str() {
+            "NVIDIA" => &[AIRuntime::CUDA, AIRuntime::CuDNN],
+            "AMD" if gpu.ai_capable => &[AIRuntime::ROCm],
+            "Apple" => &[AIRuntime::MetalPerformanceShaders],
+            _ => &[],
+        }
+    }
+
+    /// Runtimes the primary GPU needs for acceleration that are not installed
+    fn missing_gpu_runtimes(overview: &SystemOverview, runtimes: &SoftwareStack) -> Vec<AIRuntime> {
+        Self::required_gpu_runtimes(overview)
+            .iter()
+            .copied()
+            .filter(|r| !runtimes.has(*r))
+            .collect()
+    }
+
     fn get_model_recommendations(overview: &SystemOverview) -> ModelRecommendations {
         let available_vram = overview.gpu.as_ref().map_or(0.0, |g| g.vram_gb);
         let available_ram = overview.memory_gb;
 
-        ModelRecommendations {
+        let mut recommendations = ModelRecommendations {
             small_models: vec![
                 ModelRecommendation {
                     name: "BERT-base".to_string(),
@@ -415,15 +1566,46 @@ impl HardwarePresets {
                     },
                 },
             ],
+        };
+
+        // NPUs/TPUs run quantized models out of host memory
+        if let Some(accelerator) = Self::best_quantized_accelerator(overview) {
+            let performance = Self::accelerator_performance(accelerator.tops);
+            recommendations.small_models.push(ModelRecommendation {
+                name: format!("MobileNetV3 (INT8, {})", accelerator.kind),
+                parameter_count: "5.4M".to_string(),
+                memory_required_gb: 0.1,
+                feasible: true,
+                performance_estimate: performance.clone(),
+            });
+            let tops = accelerator.tops.unwrap_or(0.0);
+            if accelerator.supports_dtype("int4") || tops >= 40.0 {
+                recommendations.medium_models.push(ModelRecommendation {
+                    name: format!("Phi-3-mini (INT4, {})", accelerator.kind),
+                    parameter_count: "3.8B".to_string(),
+                    memory_required_gb: 2.5,
+                    feasible: tops >= 10.0 && available_ram >= 8.0,
+                    performance_estimate: if tops >= 40.0 { performance } else { PerformanceLevel::Fair },
+                });
+            }
         }
+
+        recommendations
     }
 
     fn estimate_ai_performance(overview: &SystemOverview) -> AIPerformanceEstimate {
         let has_gpu = overview.gpu.is_some();
         let gpu_ai_capable = overview.gpu.as_ref().map_or(false, |g| g.ai_capable);
         let sufficient_memory = overview.memory_gb >= 16.0;
+        // NPUs/TPUs speed up inference but not training
+        let accelerator = Self::best_quantized_accelerator(overview)
+            .map(|accelerator| Self::accelerator_performance(accelerator.tops));
+        let with_accelerator = |level: PerformanceLevel| match &accelerator {
+            Some(boost) if (boost.clone() as u8) < (level.clone() as u8) => boost.clone(),
+            _ => level,
+        };
 
-        AIPerformanceEstimate {
+        let mut estimate = AIPerformanceEstimate {
             training_capability: if gpu_ai_capable && sufficient_memory {
                 PerformanceLevel::Good
             } else if has_gpu {
@@ -448,13 +1630,33 @@ impl HardwarePresets {
             } else {
                 PerformanceLevel::Fair
             },
-        }
+        };
+        estimate.inference_capability = with_accelerator(estimate.inference_capability);
+        estimate.real_time_processing = with_accelerator(estimate.real_time_processing);
+        estimate
     }
 
-    fn get_ai_optimizations(overview: &SystemOverview) -> Vec<String> {
+    fn get_ai_optimizations(overview: &SystemOverview, runtimes: &SoftwareStack) -> Vec<String> {
         let mut optimizations = Vec::new();
 
-        if overview.gpu.is_none() {
+        for runtime in Self::missing_gpu_runtimes(overview, runtimes) {
+            optimizations.push(format!("Install {runtime} to enable GPU acceleration"));
+        }
+
+        for accelerator in &overview.accelerators {
+            let toolkit = ["OpenVINO", "Core ML", "TensorFlow Lite", "ONNX Runtime"]
+                .into_iter()
+                .find(|framework| accelerator.supports_framework(framework));
+            let precision = if accelerator.runs_quantized() { "quantized " } else { "" };
+            if let Some(toolkit) = toolkit {
+                optimizations.push(format!(
+                    "Use {toolkit} to run {precision}inference on the {}",
+                    Self::describe_accelerator(accelerator)
+                ));
+            }
+        }
+
+        if overview.gpu.is_none() && overview.accelerators.is_empty() {
             optimizations.push("Consider adding a dedicated GPU for AI acceleration".to_string());
         }
 
@@ -472,11 +1674,21 @@ impl HardwarePresets {
         optimizations
     }
 
+    /// Performance database entry for the primary GPU
+    fn gpu_performance(overview: &SystemOverview) -> Option<GPUPerformanceEntry> {
+        let gpu = overview.gpu.as_ref()?;
+        GPUPerformanceDatabase::global().lookup(&gpu.name).cloned()
+    }
+
     fn calculate_gaming_score(overview: &SystemOverview) -> u8 {
         let mut score = 0;
 
         // GPU is most important for gaming (60 points)
-        if let Some(gpu) = &overview.gpu {
+        if let Some(entry) = Self::gpu_performance(overview) {
+            // Square root keeps mid-range cards from being crushed by the flagship scale
+            let relative = (entry.score.min(100) as f64 / 100.0).sqrt();
+            score += (60.0 * relative).round() as u8;
+        } else if let Some(gpu) = &overview.gpu {
             if gpu.vram_gb >= 12.0 {
                 score += 60;
             } else if gpu.vram_gb >= 8.0 {
@@ -517,7 +1729,27 @@ impl HardwarePresets {
 
     fn get_game_settings(overview: &SystemOverview) -> GameSettings {
         let vram = overview.gpu.as_ref().map_or(0.0, |g| g.vram_gb);
-        
+
+        if let Some(entry) = Self::gpu_performance(overview) {
+            let (resolution, quality, target_fps) = match entry.score {
+                75.. => ("4K", QualityLevel::Ultra, 60),
+                50..=74 => ("1440p", QualityLevel::Ultra, 75),
+                35..=49 => ("1440p", QualityLevel::High, 60),
+                22..=34 => ("1080p", QualityLevel::High, 60),
+                12..=21 => ("1080p", QualityLevel::Medium, 60),
+                6..=11 => ("1080p", QualityLevel::Low, 45),
+                _ => ("720p", QualityLevel::Low, 30),
+            };
+
+            return GameSettings {
+                resolution: resolution.to_string(),
+                quality_preset: quality,
+                raytracing_support: entry.raytracing && entry.score >= 30,
+                target_fps,
+                vram_usage_percent: 85,
+            };
+        }
+
         let (resolution, quality, target_fps) = if vram >= 12.0 {
             ("4K", QualityLevel::Ultra, 60)
         } else if vram >= 8.0 {
@@ -545,6 +1777,15 @@ impl HardwarePresets {
         if overview.gpu.is_none() {
             bottlenecks.push("No dedicated GPU - severely limits gaming performance".to_string());
         } else if let Some(gpu) = &overview.gpu {
+            if let Some(entry) = Self::gpu_performance(overview) {
+                if entry.tier() <= GPUPerformanceTier::Entry {
+                    bottlenecks.push(format!(
+                        "{} is a {} tier GPU - limits settings in modern games",
+                        gpu.name,
+                        entry.tier()
+                    ));
+                }
+            }
             if gpu.vram_gb < 4.0 {
                 bottlenecks.push("Low GPU VRAM limits texture quality and resolution".to_string());
             }
@@ -558,7 +1799,7 @@ impl HardwarePresets {
             bottlenecks.push("Low system RAM may cause stuttering in memory-intensive games".to_string());
         }
 
-        if overview.storage.drive_type.to_lowercase().contains("hdd") {
+        if overview.storage.performance_class.is_rotational() {
             bottlenecks.push("HDD storage may cause slow loading times".to_string());
         }
 
@@ -569,7 +1810,10 @@ impl HardwarePresets {
         let mut upgrades = Vec::new();
 
         if let Some(gpu) = &overview.gpu {
-            if gpu.vram_gb < 8.0 {
+            let tier = Self::gpu_performance(overview).map(|entry| entry.tier());
+            if tier.is_some_and(|tier| tier <= GPUPerformanceTier::Entry) {
+                upgrades.push("Upgrade to a mainstream or better GPU (e.g. RTX 4060 / RX 7600 class)".to_string());
+            } else if gpu.vram_gb < 8.0 {
                 upgrades.push("Upgrade to GPU with 8GB+ VRAM for modern games".to_string());
             }
         } else {
@@ -580,13 +1824,47 @@ impl HardwarePresets {
             upgrades.push("Upgrade to 16GB+ RAM".to_string());
         }
 
-        if overview.storage.drive_type.to_lowercase().contains("hdd") {
+        if overview.storage.performance_class.is_rotational() {
             upgrades.push("Upgrade to NVMe SSD for faster loading".to_string());
         }
 
         upgrades
     }
 
+    /// Firmware settings that would let each discrete GPU map all of its VRAM
+    fn get_resizable_bar_recommendations(gpus: &[GPUInfo]) -> Vec<String> {
+        let mut recommendations = Vec::new();
+        for gpu in gpus {
+            let Some(rebar) = gpu.resizable_bar() else {
+                continue;
+            };
+            if !rebar.enabled {
+                let setting = if rebar.above_4g_decoding == Some(false) {
+                    "Above 4G Decoding and Resizable BAR"
+                } else {
+                    "Resizable BAR"
+                };
+                let impact = if gpu.vendor == GPUVendor::Intel {
+                    "; Arc GPUs lose much of their performance without it"
+                } else {
+                    ""
+                };
+                recommendations.push(format!(
+                    "Enable {setting} in the firmware setup so the {} can map all of its VRAM (currently {} MB){impact}",
+                    gpu.model_name, rebar.bar_size_mb
+                ));
+            } else if rebar.is_limited() {
+                recommendations.push(format!(
+                    "Resizable BAR on the {} maps {} of {} MB; a motherboard firmware update may allow the full size",
+                    gpu.model_name,
+                    rebar.bar_size_mb,
+                    rebar.max_bar_size_mb.unwrap_or_default()
+                ));
+            }
+        }
+        recommendations
+    }
+
     fn calculate_dev_score(overview: &SystemOverview) -> u8 {
         let mut score = 0;
 
@@ -613,9 +1891,9 @@ impl HardwarePresets {
         }
 
         // Storage for fast builds (25 points)
-        if overview.storage.drive_type.to_lowercase().contains("nvme") {
+        if overview.storage.performance_class.is_nvme() {
             score += 25;
-        } else if overview.storage.drive_type.to_lowercase().contains("ssd") {
+        } else if overview.storage.performance_class.is_ssd() {
             score += 20;
         } else {
             score += 10;
@@ -694,6 +1972,15 @@ impl HardwarePresets {
         recommendations
     }
 
+    /// Flag network mounts, atime updates and inode exhaustion on fixed volumes
+    fn get_filesystem_recommendations(volumes: &[Volume]) -> Vec<String> {
+        volumes
+            .iter()
+            .filter(|volume| !volume.removable)
+            .flat_map(Volume::performance_warnings)
+            .collect()
+    }
+
     fn calculate_server_score(overview: &SystemOverview) -> u8 {
         // Server scoring focuses on stability, multiple cores, and adequate memory
         let mut score = 0;
@@ -721,9 +2008,9 @@ impl HardwarePresets {
         }
 
         // Storage reliability and speed (25 points)
-        if overview.storage.drive_type.to_lowercase().contains("nvme") {
+        if overview.storage.performance_class.is_nvme() {
             score += 25;
-        } else if overview.storage.drive_type.to_lowercase().contains("ssd") {
+        } else if overview.storage.performance_class.is_ssd() {
             score += 20;
         } else {
             score += 10;
@@ -0,0 +1,161 @@
+//! Restrictions on the detection mechanisms a query may use
+//!
+//! [`HardwareQueryBuilder`](crate::HardwareQueryBuilder) can forbid spawning
+//! external tools (`lspci`, `dmidecode`, `system_profiler`, ...) or opening WMI
+//! connections. Detection code goes through [`Command`] and [`com_library`],
+//! which fail as if the tool or service were unavailable while a restriction
+//! is active, so every probe falls back the same way it does on a system
+//! without that tool.
+//!
+//! Restrictions are scoped to the current thread, except for
+//! [`NO_EXTERNAL_COMMANDS_ENV_VAR`], which disables external commands for the
+//! whole process.
+
+use std::cell::Cell;
+use std::ffi::OsStr;
+use std::io;
+use std::process::{Child, Output, Stdio};
+
+/// Which detection mechanisms are allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ProbePolicy {
+    /// Spawning external commands
+    pub external_commands: bool,
+    /// Opening WMI connections (Windows)
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    pub wmi: bool,
+}
+
+impl Default for ProbePolicy {
+    fn default() -> Self {
+        Self {
+            external_commands: true,
+            wmi: true,
+        }
+    }
+}
+
+/// Environment variable that disables external commands for the whole process
+///
+/// Any value other than empty, `0` or `false` leaves only native and
+/// file-based probing, for hardened and audited deployments.
+pub const NO_EXTERNAL_COMMANDS_ENV_VAR: &str = "HARDWARE_QUERY_NO_EXTERNAL_COMMANDS";
+
+thread_local! {
+    static POLICY: Cell<ProbePolicy> = Cell::new(ProbePolicy::default());
+}
+
+/// Restores the previous policy when dropped
+pub(crate) struct PolicyGuard {
+    previous: ProbePolicy,
+}
+
+impl Drop for PolicyGuard {
+    fn drop(&mut self) {
+        POLICY.with(|policy| policy.set(self.previous));
+    }
+}
+
+/// Apply `policy` to the current thread until the returned guard is dropped
+pub(crate) fn restrict(policy: ProbePolicy) -> PolicyGuard {
+    PolicyGuard {
+        previous: POLICY.with(|current| current.replace(policy)),
+    }
+}
+
+/// Policy in effect on the current thread
+pub(crate) fn policy() -> ProbePolicy {
+    POLICY.with(Cell::get)
+}
+
+/// `std::process::Command` subset that honours [`ProbePolicy::external_commands`]
+#[derive(Debug)]
+pub(crate) struct Command(std::process::Command);
+
+impl Command {
+    /// Prepare `program` to run in the C locale, so parsers see untranslated
+    /// labels and `.` decimal separators whatever the user's locale
+    pub(crate) fn new(program: impl AsRef<OsStr>) -> Self {
+        let mut command = std::process::Command::new(program);
+        command.env("LC_ALL", "C").env("LANG", "C").env_remove("LANGUAGE");
+        Self(command)
+    }
+
+    pub(crate) fn arg(&mut self, arg: impl AsRef<OsStr>) -> &mut Self {
+        self.0.arg(arg);
+        self
+    }
+
+    pub(crate) fn args<I, S>(&mut self, args: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.0.args(args);
+        self
+    }
+
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub(crate) fn stdout(&mut self, cfg: Stdio) -> &mut Self {
+        self.0.stdout(cfg);
+        self
+    }
+
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub(crate) fn stderr(&mut self, cfg: Stdio) -> &mut Self {
+        self.0.stderr(cfg);
+        self
+    }
+
+    /// Run the command, or fail with `PermissionDenied` if external commands are disabled
+    pub(crate) fn output(&mut self) -> io::Result<Output> {
+        check_external_commands()?;
+        self.0.output()
+    }
+
+    /// Start the command in the background, subject to the same policy as [`output`](Self::output)
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub(crate) fn spawn(&mut self) -> io::Result<Child> {
+        check_external_commands()?;
+        self.0.spawn()
+    }
+}
+
+/// Whether external commands may be spawned on the current thread
+pub(crate) fn external_commands_allowed() -> bool {
+    policy().external_commands && !external_commands_disabled_by_env()
+}
+
+fn external_commands_disabled_by_env() -> bool {
+    std::env::var(NO_EXTERNAL_COMMANDS_ENV_VAR)
+        .is_ok_and(|value| !matches!(value.trim(), "" | "0" | "false"))
+}
+
+fn check_external_commands() -> io::Result<()> {
+    if external_commands_disabled_by_env() {
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("external commands are disabled by {NO_EXTERNAL_COMMANDS_ENV_VAR}"),
+        ))
+    } else if !policy().external_commands {
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "external commands are disabled for this query",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Initialize COM for WMI, unless WMI is disabled for this query
+#[cfg(target_os = "windows")]
+pub(crate) fn com_library() -> crate::Result<wmi::COMLibrary> {
+    if !policy().wmi {
+        return Err(crate::HardwareQueryError::new(
+            crate::ErrorComponent::Wmi,
+            crate::ErrorCode::PermissionDenied,
+            "WMI is disabled for this query",
+        ));
+    }
+    Ok(wmi::COMLibrary::new()?)
+}
@@ -0,0 +1,298 @@
+//! Pluggable detection backends
+//!
+//! [`HardwareInfo::query_with_provider`](crate::HardwareInfo::query_with_provider)
+//! assembles a [`HardwareInfo`] from any [`HardwareProvider`]. The crate's own
+//! platform probers are [`SystemProvider`]; [`FixtureProvider`] replays a
+//! serialized snapshot so code that consumes hardware information can be
+//! tested without the hardware it targets.
+//!
+//! ```
+//! use hardware_query::{FixtureProvider, HardwareComponent, HardwareInfo};
+//!
+//! # fn main() -> hardware_query::Result<()> {
+//! let snapshot = HardwareInfo::query()?;
+//! let provider = FixtureProvider::new(snapshot).with_failure(HardwareComponent::GPU, "driver hung");
+//!
+//! let info = HardwareInfo::query_with_provider(provider)?;
+//! assert!(info.gpus().is_empty());
+//! assert_eq!(info.errors().len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::options::optional;
+use crate::{
+    ARMHardwareInfo, BatteryInfo, CPUInfo, FPGAInfo, GPUInfo, HardwareComponent, HardwareInfo,
+    HardwareQueryError, MemoryInfo, NPUInfo, NetworkInfo, PCIDevice, PowerProfile, Result,
+    StorageLayout, SystemInfo, TPUInfo, ThermalInfo, USBDevice, VirtualizationInfo,
+};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of the component data that makes up a [`HardwareInfo`]
+///
+/// Each method corresponds to one [`HardwareComponent`] group and is called
+/// on its own thread. An error only affects its component: the query records
+/// it in [`HardwareInfo::errors`] and fills in a placeholder.
+pub trait HardwareProvider: Send + Sync {
+    /// Snapshot time in seconds since the Unix epoch
+    fn timestamp(&self) -> Result<u64> {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .map_err(|e| HardwareQueryError::unknown(format!("Failed to get timestamp: {e}")))
+    }
+
+    /// CPU information
+    fn cpu(&self) -> Result<CPUInfo>;
+
+    /// GPUs
+    fn gpus(&self) -> Result<Vec<GPUInfo>>;
+
+    /// NPUs
+    fn npus(&self) -> Result<Vec<NPUInfo>>;
+
+    /// TPUs
+    fn tpus(&self) -> Result<Vec<TPUInfo>>;
+
+    /// ARM board details, on ARM systems
+    fn arm_hardware(&self) -> Result<Option<ARMHardwareInfo>>;
+
+    /// FPGA accelerators
+    fn fpgas(&self) -> Result<Vec<FPGAInfo>>;
+
+    /// Memory information
+    fn memory(&self) -> Result<MemoryInfo>;
+
+    /// Disks and volumes
+    fn storage(&self) -> Result<StorageLayout>;
+
+    /// Network interfaces
+    fn network_interfaces(&self) -> Result<Vec<NetworkInfo>>;
+
+    /// Battery, if the machine has one
+    fn battery(&self) -> Result<Option<BatteryInfo>>;
+
+    /// Temperatures and fans
+    fn thermal(&self) -> Result<ThermalInfo>;
+
+    /// PCI devices
+    fn pci_devices(&self) -> Result<Vec<PCIDevice>>;
+
+    /// USB devices
+    fn usb_devices(&self) -> Result<Vec<USBDevice>>;
+
+    /// Power profile, where the platform reports one
+    fn power_profile(&self) -> Result<Option<PowerProfile>>;
+
+    /// Virtualization and container environment
+    fn virtualization(&self) -> Result<VirtualizationInfo>;
+
+    /// System, baseboard and firmware identification
+    fn system(&self) -> Result<SystemInfo>;
+}
+
+/// The platform probers used by [`HardwareInfo::query`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemProvider;
+
+impl HardwareProvider for SystemProvider {
+    fn cpu(&self) -> Result<CPUInfo> {
+        CPUInfo::query()
+    }
+
+    fn gpus(&self) -> Result<Vec<GPUInfo>> {
+        GPUInfo::query_all()
+    }
+
+    fn npus(&self) -> Result<Vec<NPUInfo>> {
+        NPUInfo::query_all()
+    }
+
+    fn tpus(&self) -> Result<Vec<TPUInfo>> {
+        TPUInfo::query_all()
+    }
+
+    fn arm_hardware(&self) -> Result<Option<ARMHardwareInfo>> {
+        Ok(ARMHardwareInfo::detect().ok().flatten())
+    }
+
+    fn fpgas(&self) -> Result<Vec<FPGAInfo>> {
+        Ok(FPGAInfo::detect_fpgas().unwrap_or_default())
+    }
+
+    fn memory(&self) -> Result<MemoryInfo> {
+        MemoryInfo::query()
+    }
+
+    fn storage(&self) -> Result<StorageLayout> {
+        StorageLayout::query()
+    }
+
+    fn network_interfaces(&self) -> Result<Vec<NetworkInfo>> {
+        NetworkInfo::query_all()
+    }
+
+    fn battery(&self) -> Result<Option<BatteryInfo>> {
+        optional(BatteryInfo::query())
+    }
+
+    fn thermal(&self) -> Result<ThermalInfo> {
+        ThermalInfo::query()
+    }
+
+    fn pci_devices(&self) -> Result<Vec<PCIDevice>> {
+        PCIDevice::query_all()
+    }
+
+    fn usb_devices(&self) -> Result<Vec<USBDevice>> {
+        USBDevice::query_all()
+    }
+
+    fn power_profile(&self) -> Result<Option<PowerProfile>> {
+        optional(PowerProfile::query())
+    }
+
+    fn virtualization(&self) -> Result<VirtualizationInfo> {
+        VirtualizationInfo::detect()
+    }
+
+    fn system(&self) -> Result<SystemInfo> {
+        SystemInfo::query()
+    }
+}
+
+/// Replays a recorded [`HardwareInfo`] snapshot
+///
+/// Components can be made to fail with [`with_failure`](Self::with_failure)
+/// or [`with_unsupported`](Self::with_unsupported) to exercise error handling.
+#[derive(Debug, Clone)]
+pub struct FixtureProvider {
+    snapshot: HardwareInfo,
+    failures: HashMap<HardwareComponent, String>,
+    unsupported: HashMap<HardwareComponent, String>,
+}
+
+impl FixtureProvider {
+    /// Replay `snapshot`
+    pub fn new(snapshot: HardwareInfo) -> Self {
+        Self {
+            snapshot,
+            failures: HashMap::new(),
+            unsupported: HashMap::new(),
+        }
+    }
+
+    /// Replay a snapshot serialized with [`HardwareInfo::to_json`]
+    pub fn from_json(json: &str) -> Result<Self> {
+        HardwareInfo::from_json(json).map(Self::new)
+    }
+
+    /// Replay a JSON snapshot file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Make every query of `component` fail with `message`
+    pub fn with_failure(mut self, component: HardwareComponent, message: impl Into<String>) -> Self {
+        self.failures.insert(component, message.into());
+        self
+    }
+
+    /// Report `component` as unavailable on this platform
+    pub fn with_unsupported(mut self, component: HardwareComponent, message: impl Into<String>) -> Self {
+        self.unsupported.insert(component, message.into());
+        self
+    }
+
+    /// Get the replayed snapshot
+    pub fn snapshot(&self) -> &HardwareInfo {
+        &self.snapshot
+    }
+
+    fn get<T: Clone>(&self, component: HardwareComponent, value: &T) -> Result<T> {
+        match self.failures.get(&component) {
+            Some(message) => Err(HardwareQueryError::system_info_unavailable(message.clone())),
+            None => match self.unsupported.get(&component) {
+                Some(message) => Err(HardwareQueryError::platform_not_supported(message.clone())),
+                None => Ok(value.clone()),
+            },
+        }
+    }
+}
+
+impl HardwareProvider for FixtureProvider {
+    fn timestamp(&self) -> Result<u64> {
+        Ok(self.snapshot.timestamp)
+    }
+
+    fn cpu(&self) -> Result<CPUInfo> {
+        self.get(HardwareComponent::CPU, &self.snapshot.cpu)
+    }
+
+    fn gpus(&self) -> Result<Vec<GPUInfo>> {
+        self.get(HardwareComponent::GPU, &self.snapshot.gpus)
+    }
+
+    fn npus(&self) -> Result<Vec<NPUInfo>> {
+        self.get(HardwareComponent::Accelerators, &self.snapshot.npus)
+    }
+
+    fn tpus(&self) -> Result<Vec<TPUInfo>> {
+        self.get(HardwareComponent::Accelerators, &self.snapshot.tpus)
+    }
+
+    fn arm_hardware(&self) -> Result<Option<ARMHardwareInfo>> {
+        self.get(HardwareComponent::Accelerators, &self.snapshot.arm_hardware)
+    }
+
+    fn fpgas(&self) -> Result<Vec<FPGAInfo>> {
+        self.get(HardwareComponent::Accelerators, &self.snapshot.fpgas)
+    }
+
+    fn memory(&self) -> Result<MemoryInfo> {
+        self.get(HardwareComponent::Memory, &self.snapshot.memory)
+    }
+
+    fn storage(&self) -> Result<StorageLayout> {
+        let layout = StorageLayout {
+            devices: self.snapshot.storage_devices.clone(),
+            disks: self.snapshot.physical_disks.clone(),
+            volumes: self.snapshot.volumes.clone(),
+        };
+        self.get(HardwareComponent::Storage, &layout)
+    }
+
+    fn network_interfaces(&self) -> Result<Vec<NetworkInfo>> {
+        self.get(HardwareComponent::Network, &self.snapshot.network_interfaces)
+    }
+
+    fn battery(&self) -> Result<Option<BatteryInfo>> {
+        self.get(HardwareComponent::Battery, &self.snapshot.battery)
+    }
+
+    fn thermal(&self) -> Result<ThermalInfo> {
+        self.get(HardwareComponent::Thermal, &self.snapshot.thermal)
+    }
+
+    fn pci_devices(&self) -> Result<Vec<PCIDevice>> {
+        self.get(HardwareComponent::Devices, &self.snapshot.pci_devices)
+    }
+
+    fn usb_devices(&self) -> Result<Vec<USBDevice>> {
+        self.get(HardwareComponent::Devices, &self.snapshot.usb_devices)
+    }
+
+    fn power_profile(&self) -> Result<Option<PowerProfile>> {
+        self.get(HardwareComponent::Power, &self.snapshot.power_profile)
+    }
+
+    fn virtualization(&self) -> Result<VirtualizationInfo> {
+        self.get(HardwareComponent::Virtualization, &self.snapshot.virtualization)
+    }
+
+    fn system(&self) -> Result<SystemInfo> {
+        self.get(HardwareComponent::System, &self.snapshot.system)
+    }
+}
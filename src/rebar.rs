@@ -0,0 +1,223 @@
+//! PCIe Resizable BAR and Above 4G Decoding
+//!
+//! Without Resizable BAR the CPU sees a GPU's memory through a 256 MB
+//! window; with it (and Above 4G Decoding, which lets firmware place 64-bit
+//! BARs above the 4 GiB boundary) the window covers all of VRAM. The size
+//! and placement of that window are read per GPU:
+//!
+//! - Linux: the device's BAR table in sysfs (`resource`, world-readable) and
+//!   the Resizable BAR extended capability in `config`, which is only
+//!   readable beyond the first 64 bytes as root.
+//! - Windows: the memory ranges the PnP manager assigned to the adapter
+//!   (`Win32_DeviceMemoryAddress`).
+//! - NVIDIA GPUs with the `nvidia` feature: NVML's BAR1 size on every platform.
+
+use crate::{GPUInfo, GPUType};
+use serde::{Deserialize, Serialize};
+
+/// Default size of the VRAM aperture without Resizable BAR
+const LEGACY_BAR_SIZE_MB: u64 = 256;
+/// Resizable BAR extended capability ID
+const REBAR_CAPABILITY_ID: u32 = 0x15;
+/// Linux `IORESOURCE_MEM`
+const IORESOURCE_MEM: u64 = 0x200;
+/// Linux `IORESOURCE_PREFETCH`
+const IORESOURCE_PREFETCH: u64 = 0x2000;
+const FOUR_GIB: u64 = 1 << 32;
+
+/// Resizable BAR state of one GPU
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResizableBar {
+    /// Index of the BAR that maps VRAM, when read from the BAR table
+    pub bar_index: Option<u8>,
+    /// Current size of the VRAM aperture in MB
+    pub bar_size_mb: u64,
+    /// Largest aperture the device supports in MB (needs config space access)
+    pub max_bar_size_mb: Option<u64>,
+    /// Whether the device has the Resizable BAR capability (needs config space access)
+    pub supported: Option<bool>,
+    /// Whether the aperture is larger than the legacy 256 MB window
+    pub enabled: bool,
+    /// Whether the aperture is mapped above 4 GiB
+    pub above_4g_decoding: Option<bool>,
+}
+
+impl ResizableBar {
+    /// State from an aperture size alone, as reported by NVML or Windows
+    pub(crate) fn from_bar_size(bar_size_mb: u64, above_4g_decoding: Option<bool>) -> Self {
+        Self {
+            bar_index: None,
+            bar_size_mb,
+            max_bar_size_mb: None,
+            supported: (bar_size_mb > LEGACY_BAR_SIZE_MB).then_some(true),
+            enabled: bar_size_mb > LEGACY_BAR_SIZE_MB,
+            above_4g_decoding,
+        }
+    }
+
+    /// Read the state from a Linux sysfs `resource` table and the device's config space
+    ///
+    /// The VRAM aperture is the largest prefetchable memory BAR. `config` may
+    /// be the 64-byte unprivileged view, in which case support and the
+    /// maximum size stay unknown.
+    ///
+    /// ```rust
+    /// use hardware_query::ResizableBar;
+    ///
+    /// // BAR0 registers, BAR1 32 GB of prefetchable VRAM mapped high, BAR3 32 MB
+    /// let resource = "\
+    /// 0x00000000fb000000 0x00000000fbffffff 0x0000000000040200
+    /// 0x0000006000000000 0x00000067ffffffff 0x000000000014220c
+    /// 0x0000000000000000 0x0000000000000000 0x0000000000000000
+    /// 0x0000006800000000 0x0000006801ffffff 0x000000000014220c
+    /// ";
+    /// let rebar = ResizableBar::from_pci_resources(resource, &[]).unwrap();
+    /// assert_eq!(rebar.bar_index, Some(1));
+    /// assert_eq!(rebar.bar_size_mb, 32_768);
+    /// assert!(rebar.enabled);
+    /// assert_eq!(rebar.above_4g_decoding, Some(true));
+    /// ```
+    pub fn from_pci_resources(resource: &str, config: &[u8]) -> Option<Self> {
+        let (index, start, size) = resource
+            .lines()
+            .take(6)
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let mut fields = line.split_whitespace().map(|field| {
+                    u64::from_str_radix(field.trim_start_matches("0x"), 16).ok()
+                });
+                let (start, end, flags) = (fields.next()??, fields.next()??, fields.next()??);
+                let prefetchable_memory = flags & IORESOURCE_MEM != 0 && flags & IORESOURCE_PREFETCH != 0;
+                (prefetchable_memory && end > start).then_some((index, start, end - start + 1))
+            })
+            .max_by_key(|&(_, _, size)| size)?;
+
+        let mut rebar = Self::from_bar_size(size >> 20, Some(start >= FOUR_GIB));
+        rebar.bar_index = Some(index as u8);
+        // Only root sees the extended capabilities past the first 256 bytes
+        if config.len() > 0x100 {
+            let max = rebar_capability(config, index as u32);
+            rebar.supported = Some(max.is_some());
+            rebar.max_bar_size_mb = max.flatten();
+        }
+        Some(rebar)
+    }
+
+    /// Check if the device could map more VRAM than it currently does
+    ///
+    /// True when Resizable BAR is supported but disabled in firmware, or when
+    /// the aperture is smaller than the device's maximum.
+    pub fn is_limited(&self) -> bool {
+        match self.max_bar_size_mb {
+            Some(max) => self.bar_size_mb < max,
+            None => self.supported == Some(true) && !self.enabled,
+        }
+    }
+}
+
+/// Walk the extended capability list for Resizable BAR
+///
+/// `None` when the capability is absent; `Some(max)` with the largest size
+/// in MB the entry for `bar` supports, if it has one.
+fn rebar_capability(config: &[u8], bar: u32) -> Option<Option<u64>> {
+    let dword = |offset: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(config.get(offset..offset + 4)?.try_into().ok()?))
+    };
+
+    let mut offset = 0x100;
+    // The list is at most (4096 - 256) / 4 entries long; bound it against loops
+    for _ in 0..960 {
+        let header = dword(offset)?;
+        if header == 0 || header == u32::MAX {
+            return None;
+        }
+        if header & 0xffff == REBAR_CAPABILITY_ID {
+            let entries = (dword(offset + 8)? >> 5) & 0x7;
+            let max = (0..entries as usize).find_map(|entry| {
+                let capability = dword(offset + 4 + entry * 8)?;
+                let control = dword(offset + 8 + entry * 8)?;
+                if control & 0x7 != bar {
+                    return None;
+                }
+                // Bit n of bits 4-31 advertises a size of 2^n MB
+                let sizes = capability >> 4;
+                (sizes != 0).then(|| 1u64 << (31 - sizes.leading_zeros()))
+            });
+            return Some(max);
+        }
+        let next = ((header >> 20) & 0xffc) as usize;
+        if next < 0x100 {
+            return None;
+        }
+        offset = next;
+    }
+    None
+}
+
+/// Fill [`GPUInfo::resizable_bar`] for discrete GPUs
+pub(crate) fn apply_probes(gpus: &mut [GPUInfo]) {
+    for gpu in gpus.iter_mut().filter(|gpu| gpu.gpu_type == GPUType::Discrete) {
+        #[cfg(target_os = "linux")]
+        if let Some(rebar) = gpu.pci_bus_id.as_deref().and_then(linux::query) {
+            gpu.resizable_bar = Some(rebar);
+        }
+        #[cfg(target_os = "windows")]
+        if gpu.resizable_bar.is_none() {
+            gpu.resizable_bar = gpu.pci_device_id.as_deref().and_then(windows::query);
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        let _ = gpu;
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::ResizableBar;
+    use std::path::Path;
+
+    pub(super) fn query(pci_bus_id: &str) -> Option<ResizableBar> {
+        // sysfs uses a 4-digit domain; NVML reports 8
+        let bus_id = match pci_bus_id.split_once(':') {
+            Some((domain, rest)) if domain.len() > 4 => format!("{}:{rest}", &domain[domain.len() - 4..]),
+            _ => pci_bus_id.to_string(),
+        }
+        .to_lowercase();
+        let dir = Path::new("/sys/bus/pci/devices").join(bus_id);
+        let resource = std::fs::read_to_string(dir.join("resource")).ok()?;
+        let config = std::fs::read(dir.join("config")).unwrap_or_default();
+        ResizableBar::from_pci_resources(&resource, &config)
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use super::{FOUR_GIB, ResizableBar};
+    use std::collections::HashMap;
+    use wmi::{Variant, WMIConnection};
+
+    /// Largest memory range assigned to the PnP device
+    pub(super) fn query(pnp_device_id: &str) -> Option<ResizableBar> {
+        let com = crate::probe::com_library().ok()?;
+        let wmi_con = WMIConnection::new(com).ok()?;
+        let query = format!(
+            "ASSOCIATORS OF {{Win32_PnPEntity.DeviceID='{}'}} WHERE ResultClass = Win32_DeviceMemoryAddress",
+            pnp_device_id.replace('\\', "\\\\")
+        );
+        let rows: Vec<HashMap<String, Variant>> = wmi_con.raw_query(&query).ok()?;
+
+        // uint64 properties arrive as strings
+        let address = |row: &HashMap<String, Variant>, key: &str| match row.get(key) {
+            Some(Variant::UI8(value)) => Some(*value),
+            Some(Variant::String(value)) => value.parse().ok(),
+            _ => None,
+        };
+        let (start, size) = rows
+            .iter()
+            .filter_map(|row| {
+                let (start, end) = (address(row, "StartingAddress")?, address(row, "EndingAddress")?);
+                (end > start).then_some((start, end - start + 1))
+            })
+            .max_by_key(|&(_, size)| size)?;
+        Some(ResizableBar::from_bar_size(size >> 20, Some(start >= FOUR_GIB)))
+    }
+}
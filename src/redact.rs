@@ -0,0 +1,184 @@
+//! Privacy filter for sharing hardware reports
+//!
+//! [`HardwareInfo::redacted`](crate::HardwareInfo::redacted) replaces values
+//! that identify a machine or its owner (serial numbers, UUIDs, MAC and IP
+//! addresses, WiFi network names, user names in mount paths) while keeping
+//! everything needed to debug a hardware problem.
+
+use crate::fingerprint::{hex, salted};
+use crate::HardwareInfo;
+use sha2::Digest;
+
+/// Replacement for redacted values
+const REDACTED: &str = "[redacted]";
+
+/// How redacted values are replaced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RedactionMode {
+    /// Replace with `[redacted]`
+    #[default]
+    Mask,
+    /// Replace with a short salted hash such as `[redacted:3f2a9c41d07e]`
+    ///
+    /// Equal values get equal hashes, so a report can still show that two
+    /// entries refer to the same device.
+    Hash,
+}
+
+/// Options for [`HardwareInfo::redacted_with`](crate::HardwareInfo::redacted_with)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RedactionOptions {
+    /// How values are replaced
+    pub mode: RedactionMode,
+    /// Salt for [`RedactionMode::Hash`], so hashes cannot be matched against known serials
+    pub salt: Option<String>,
+}
+
+impl RedactionOptions {
+    /// Mask every identifying value
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace identifying values with salted hashes
+    pub fn hashed(salt: impl Into<String>) -> Self {
+        Self {
+            mode: RedactionMode::Hash,
+            salt: Some(salt.into()),
+        }
+    }
+}
+
+pub(crate) fn redact(info: &HardwareInfo, options: &RedactionOptions) -> HardwareInfo {
+    let mut info = info.clone();
+    let mut redactor = Redactor {
+        options,
+        secrets: Vec::new(),
+    };
+
+    let system = &mut info.system;
+    redactor.option(&mut system.serial_number);
+    redactor.option(&mut system.uuid);
+    redactor.option(&mut system.baseboard.serial_number);
+    redactor.option(&mut system.baseboard.asset_tag);
+    if let Some(arm) = &mut info.arm_hardware {
+        redactor.option(&mut arm.serial_number);
+    }
+    if let Some(battery) = &mut info.battery {
+        redactor.option(&mut battery.serial_number);
+    }
+    for module in &mut info.memory.modules {
+        redactor.option(&mut module.serial_number);
+    }
+    for disk in &mut info.physical_disks {
+        redactor.option(&mut disk.serial_number);
+        if let Some(nvme) = &mut disk.nvme {
+            redactor.option(&mut nvme.serial_number);
+        }
+    }
+    for interface in &mut info.network_interfaces {
+        if !interface.mac_address.is_empty() {
+            redactor.value(&mut interface.mac_address);
+        }
+        for address in &mut interface.ip_addresses {
+            redactor.value(address);
+        }
+        if let Some(wireless) = &mut interface.wireless {
+            redactor.option(&mut wireless.ssid);
+        }
+    }
+    for device in &mut info.usb_devices {
+        redactor.option(&mut device.serial_number);
+    }
+    if let Some(container) = &mut info.virtualization.container_runtime {
+        redactor.option(&mut container.container_id);
+    }
+
+    // Windows device instance IDs embed the serial number
+    for device in &mut info.usb_devices {
+        redactor.scrub(&mut device.id);
+        if let Some(parent) = &mut device.parent_id {
+            redactor.scrub(parent);
+        }
+    }
+    for storage in &mut info.storage_devices {
+        storage.mount_point = without_user_names(&storage.mount_point);
+    }
+    for volume in &mut info.volumes {
+        volume.mount_point = without_user_names(&volume.mount_point);
+    }
+
+    info
+}
+
+/// Redact values that are not part of a [`HardwareInfo`], hashing them the same way
+pub(crate) fn redact_values<'a>(values: impl IntoIterator<Item = &'a mut Option<String>>, options: &RedactionOptions) {
+    let mut redactor = Redactor {
+        options,
+        secrets: Vec::new(),
+    };
+    for value in values {
+        redactor.option(value);
+    }
+}
+
+struct Redactor<'a> {
+    options: &'a RedactionOptions,
+    /// Original values and their replacements
+    secrets: Vec<(String, String)>,
+}
+
+impl Redactor<'_> {
+    fn value(&mut self, value: &mut String) {
+        let replacement = match self.options.mode {
+            RedactionMode::Mask => REDACTED.to_string(),
+            RedactionMode::Hash => {
+                let mut hasher = salted(self.options.salt.as_deref().unwrap_or(""));
+                hasher.update(value.trim().to_uppercase().as_bytes());
+                format!("[redacted:{}]", &hex(&hasher.finalize())[..12])
+            }
+        };
+        let original = std::mem::replace(value, replacement.clone());
+        // Very short values would match unrelated text
+        if original.trim().len() >= 4 {
+            self.secrets.push((original.trim().to_string(), replacement));
+        }
+    }
+
+    fn option(&mut self, value: &mut Option<String>) {
+        if let Some(value) = value {
+            self.value(value);
+        }
+    }
+
+    /// Replace redacted values embedded in a longer string
+    fn scrub(&self, text: &mut String) {
+        for (secret, replacement) in &self.secrets {
+            if text.contains(secret.as_str()) {
+                *text = text.replace(secret.as_str(), replacement);
+            }
+        }
+    }
+}
+
+/// Replace the user name in `/home/<user>`, `/media/<user>/...`,
+/// `/run/media/<user>/...`, `/Users/<user>` and `C:\Users\<user>`
+fn without_user_names(path: &str) -> String {
+    let separator = if path.contains('\\') { '\\' } else { '/' };
+    let segments: Vec<&str> = path.split(separator).collect();
+    segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            let parent = index.checked_sub(1).map(|i| segments[i].to_ascii_lowercase());
+            let user = match parent.as_deref() {
+                Some("home" | "users") => true,
+                // Removable media is mounted per user below /media/<user>/<label>
+                Some("media") => index + 1 < segments.len(),
+                _ => false,
+            };
+            if user && !segment.is_empty() { REDACTED } else { segment }
+        })
+        .collect::<Vec<_>>()
+        .join(&separator.to_string())
+}
@@ -0,0 +1,865 @@
+//! Markdown and HTML hardware reports
+//!
+//! [`HardwareReport`] turns a [`HardwareInfo`] snapshot, and optionally the
+//! results of [`HardwarePresets`](crate::HardwarePresets) assessments, into a
+//! document meant for people: a summary, a health overview, warnings and
+//! per-component tables. It renders as Markdown for issue trackers or as a
+//! standalone HTML page for attaching to support tickets.
+//!
+//! ```no_run
+//! use hardware_query::{HardwareInfo, ReportDetail};
+//!
+//! # fn main() -> hardware_query::Result<()> {
+//! // Mask serial numbers and addresses before sharing the report
+//! let hw_info = HardwareInfo::query()?.redacted();
+//! std::fs::write("hardware.md", hw_info.to_markdown(ReportDetail::Standard))?;
+//! std::fs::write("hardware.html", hw_info.to_html(ReportDetail::Full))?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::presets::{
+    AIHardwareAssessment, ContentCreationAssessment, DeveloperHardwareAssessment, GamingHardwareAssessment,
+    ImageGenerationAssessment, LLMHardwareAssessment, ServerHardwareAssessment,
+};
+use crate::HardwareInfo;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+/// How much of a snapshot a report includes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ReportDetail {
+    /// Summary, health and warnings only
+    Summary,
+    /// Adds CPU, GPU, memory, storage, network and battery tables
+    #[default]
+    Standard,
+    /// Adds memory modules, physical disks, sensors, PCI and USB devices
+    Full,
+}
+
+/// One block of report content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ReportBlock {
+    /// Paragraph of text
+    Text(String),
+    /// Bulleted list
+    List(Vec<String>),
+    /// Table with a header row
+    Table {
+        /// Column headers
+        headers: Vec<String>,
+        /// Rows, each with one cell per header
+        rows: Vec<Vec<String>>,
+    },
+}
+
+/// Titled part of a report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportSection {
+    /// Section heading
+    pub title: String,
+    /// Content in display order
+    pub blocks: Vec<ReportBlock>,
+}
+
+impl ReportSection {
+    /// Empty section
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Append a paragraph
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.blocks.push(ReportBlock::Text(text.into()));
+        self
+    }
+
+    /// Append a bulleted list (skipped when empty)
+    pub fn with_list(mut self, items: Vec<String>) -> Self {
+        if !items.is_empty() {
+            self.blocks.push(ReportBlock::List(items));
+        }
+        self
+    }
+
+    /// Append a table (skipped when it has no rows)
+    pub fn with_table(mut self, headers: &[&str], rows: Vec<Vec<String>>) -> Self {
+        if !rows.is_empty() {
+            self.blocks.push(ReportBlock::Table {
+                headers: headers.iter().map(|header| header.to_string()).collect(),
+                rows,
+            });
+        }
+        self
+    }
+
+    /// Append a two-column property table
+    pub fn with_properties(self, properties: Vec<(&str, String)>) -> Self {
+        let rows = properties
+            .into_iter()
+            .map(|(name, value)| vec![name.to_string(), value])
+            .collect();
+        self.with_table(&["Property", "Value"], rows)
+    }
+
+    /// Check if the section has no content
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+}
+
+/// Human-readable report of a hardware snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HardwareReport {
+    /// Document title
+    pub title: String,
+    /// Detail level the hardware sections were built with
+    pub detail: ReportDetail,
+    /// Sections in display order
+    pub sections: Vec<ReportSection>,
+}
+
+impl HardwareReport {
+    /// Build a report of `info` at the given detail level
+    pub fn new(info: &HardwareInfo, detail: ReportDetail) -> Self {
+        let mut sections = vec![summary_section(info), health_section(info), warnings_section(info)];
+        if detail >= ReportDetail::Standard {
+            sections.extend([
+                cpu_section(info),
+                gpu_section(info),
+                memory_section(info, detail),
+                storage_section(info, detail),
+                network_section(info),
+                battery_section(info),
+            ]);
+        }
+        if detail >= ReportDetail::Full {
+            sections.extend([thermal_section(info), pci_section(info), usb_section(info)]);
+        }
+        sections.retain(|section| !section.is_empty());
+
+        Self {
+            title: "Hardware Report".to_string(),
+            detail,
+            sections,
+        }
+    }
+
+    /// Set the document title
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Append a section, such as an assessment
+    pub fn with_section(mut self, section: impl Into<ReportSection>) -> Self {
+        self.sections.push(section.into());
+        self
+    }
+
+    /// Get the sections
+    pub fn sections(&self) -> &[ReportSection] {
+        &self.sections
+    }
+
+    /// Render as GitHub-flavored Markdown
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n", escape_markdown(&self.title));
+        for section in &self.sections {
+            let _ = write!(out, "\n## {}\n", escape_markdown(&section.title));
+            for block in &section.blocks {
+                out.push('\n');
+                match block {
+                    ReportBlock::Text(text) => {
+                        let _ = writeln!(out, "{}", escape_markdown(text));
+                    }
+                    ReportBlock::List(items) => {
+                        for item in items {
+                            let _ = writeln!(out, "- {}", escape_markdown(item));
+                        }
+                    }
+                    ReportBlock::Table { headers, rows } => {
+                        let cells = |row: &[String]| {
+                            row.iter().map(|cell| escape_markdown_cell(cell)).collect::<Vec<_>>().join(" | ")
+                        };
+                        let _ = writeln!(out, "| {} |", cells(headers));
+                        let _ = writeln!(out, "|{}", " --- |".repeat(headers.len()));
+                        for row in rows {
+                            let _ = writeln!(out, "| {} |", cells(row));
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Render as a standalone HTML page with inline styles
+    pub fn to_html(&self) -> String {
+        let title = escape_html(&self.title);
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{HTML_STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+        );
+        for section in &self.sections {
+            let _ = writeln!(out, "<section>\n<h2>{}</h2>", escape_html(&section.title));
+            for block in &section.blocks {
+                match block {
+                    ReportBlock::Text(text) => {
+                        let _ = writeln!(out, "<p>{}</p>", escape_html(text));
+                    }
+                    ReportBlock::List(items) => {
+                        out.push_str("<ul>\n");
+                        for item in items {
+                            let _ = writeln!(out, "<li>{}</li>", escape_html(item));
+                        }
+                        out.push_str("</ul>\n");
+                    }
+                    ReportBlock::Table { headers, rows } => {
+                        out.push_str("<table>\n<thead><tr>");
+                        for header in headers {
+                            let _ = write!(out, "<th>{}</th>", escape_html(header));
+                        }
+                        out.push_str("</tr></thead>\n<tbody>\n");
+                        for row in rows {
+                            out.push_str("<tr>");
+                            for cell in row {
+                                let _ = write!(out, "<td>{}</td>", escape_html(cell));
+                            }
+                            out.push_str("</tr>\n");
+                        }
+                        out.push_str("</tbody>\n</table>\n");
+                    }
+                }
+            }
+            out.push_str("</section>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+const HTML_STYLE: &str = "body { font-family: system-ui, sans-serif; margin: 2em auto; max-width: 60em; color: #222; }
+table { border-collapse: collapse; margin: 0.5em 0 1em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; vertical-align: top; }
+th { background: #f3f3f3; }
+";
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape characters that would start HTML or emphasis in Markdown text
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '<' | '>' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escape a table cell, which must also stay on one line and not contain `|`
+fn escape_markdown_cell(text: &str) -> String {
+    escape_markdown(text).replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+fn gb(value: f64) -> String {
+    format!("{value:.1} GB")
+}
+
+/// Prefix `model` with `vendor` unless the model name already includes it
+fn with_vendor(vendor: impl std::fmt::Display, model: &str) -> String {
+    let vendor = vendor.to_string();
+    if model.to_lowercase().contains(&vendor.to_lowercase()) {
+        model.to_string()
+    } else {
+        format!("{vendor} {model}")
+    }
+}
+
+fn optional<T: std::fmt::Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| value.to_string())
+}
+
+fn summary_section(info: &HardwareInfo) -> ReportSection {
+    let system = info.system();
+    let machine = [system.manufacturer(), system.product_name()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let cpu = info.cpu();
+    let gpus = info
+        .gpus()
+        .iter()
+        .map(|gpu| format!("{} ({})", with_vendor(gpu.vendor(), gpu.model_name()), gb(gpu.memory_gb())))
+        .collect::<Vec<_>>();
+    let environment = match &info.virtualization().hypervisor {
+        Some(hypervisor) => format!("{} ({hypervisor})", info.virtualization().environment_type),
+        None => info.virtualization().environment_type.to_string(),
+    };
+
+    let mut properties = Vec::new();
+    if !machine.is_empty() {
+        properties.push(("Machine", format!("{machine} ({})", system.chassis_type())));
+    }
+    properties.extend([
+        (
+            "CPU",
+            format!(
+                "{} ({} cores, {} threads)",
+                with_vendor(cpu.vendor(), cpu.model_name()),
+                cpu.physical_cores(),
+                cpu.logical_cores()
+            ),
+        ),
+        ("Memory", gb(info.memory().total_gb())),
+        (
+            "GPU",
+            if gpus.is_empty() { "None detected".to_string() } else { gpus.join(", ") },
+        ),
+        (
+            "Storage",
+            gb(info.physical_disks().iter().map(|disk| disk.capacity_gb()).sum()),
+        ),
+        ("Environment", environment),
+    ]);
+    if info.accelerator_count() > 0 {
+        let mut accelerators: Vec<_> = info.accelerator_summary().into_iter().collect();
+        accelerators.sort();
+        let accelerators = accelerators
+            .into_iter()
+            .map(|(kind, count)| format!("{count} {kind}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        properties.push(("Accelerators", accelerators));
+    }
+    ReportSection::new("Summary").with_properties(properties)
+}
+
+fn health_section(info: &HardwareInfo) -> ReportSection {
+    let thermal = info.thermal();
+    let mut rows = vec![vec![
+        "Thermal".to_string(),
+        match thermal.max_temperature() {
+            Some(max) => format!("{} (max {max:.0} °C)", thermal.thermal_status()),
+            None => thermal.thermal_status().to_string(),
+        },
+    ]];
+
+    let failing: Vec<_> = info
+        .physical_disks()
+        .iter()
+        .filter(|disk| disk.is_failing())
+        .map(|disk| disk.device().to_string())
+        .collect();
+    if !info.physical_disks().is_empty() {
+        rows.push(vec![
+            "Disks".to_string(),
+            if failing.is_empty() {
+                "No SMART failures reported".to_string()
+            } else {
+                format!("Failing: {}", failing.join(", "))
+            },
+        ]);
+    }
+
+    rows.push(vec![
+        "Memory".to_string(),
+        format!("{:.0}% in use", info.memory().usage_percent()),
+    ]);
+    if info.memory().ecc_status().has_uncorrectable_errors() {
+        rows.push(vec!["ECC".to_string(), "Uncorrectable memory errors reported".to_string()]);
+    }
+
+    if let Some(battery) = info.battery() {
+        rows.push(vec![
+            "Battery".to_string(),
+            match battery.health_percent() {
+                Some(health) => format!("{}, {health:.0}% of design capacity", battery.status()),
+                None => battery.status().to_string(),
+            },
+        ]);
+    }
+
+    ReportSection::new("Health").with_table(&["Check", "Status"], rows)
+}
+
+fn warnings_section(info: &HardwareInfo) -> ReportSection {
+    let mut warnings: Vec<String> = info
+        .errors()
+        .iter()
+        .chain(info.warnings())
+        .map(ToString::to_string)
+        .collect();
+    if info.thermal().has_critical_temperature() {
+        warnings.push("A sensor is at or above its critical temperature".to_string());
+    }
+    for disk in info.physical_disks().iter().filter(|disk| disk.is_failing()) {
+        warnings.push(format!("{} ({}) reports SMART failure indicators", disk.device(), disk.model()));
+    }
+    if info.memory().ecc_status().has_uncorrectable_errors() {
+        warnings.push("Uncorrectable ECC memory errors have been logged".to_string());
+    }
+    warnings.extend(
+        info.volumes()
+            .iter()
+            .filter(|volume| !volume.removable)
+            .flat_map(|volume| volume.performance_warnings()),
+    );
+    if warnings.is_empty() {
+        ReportSection::new("Warnings").with_text("No warnings.")
+    } else {
+        ReportSection::new("Warnings").with_list(warnings)
+    }
+}
+
+fn cpu_section(info: &HardwareInfo) -> ReportSection {
+    let cpu = info.cpu();
+    let mut properties = vec![
+        ("Model", with_vendor(cpu.vendor(), cpu.model_name())),
+        ("Architecture", cpu.architecture().to_string()),
+        ("Cores / threads", format!("{} / {}", cpu.physical_cores(), cpu.logical_cores())),
+        (
+            "Frequency",
+            format!("{} MHz base, {} MHz max", cpu.base_frequency(), cpu.max_frequency()),
+        ),
+    ];
+    if cpu.performance_cores() > 0 && cpu.efficiency_cores() > 0 {
+        properties.push((
+            "Hybrid cores",
+            format!("{} performance, {} efficiency", cpu.performance_cores(), cpu.efficiency_cores()),
+        ));
+    }
+    if let Some(temperature) = cpu.temperature() {
+        properties.push(("Temperature", format!("{temperature:.0} °C")));
+    }
+    ReportSection::new("CPU").with_properties(properties)
+}
+
+fn gpu_section(info: &HardwareInfo) -> ReportSection {
+    let rows = info
+        .gpus()
+        .iter()
+        .map(|gpu| {
+            vec![
+                with_vendor(gpu.vendor(), gpu.model_name()),
+                gpu.gpu_type().to_string(),
+                gb(gpu.memory_gb()),
+                optional(gpu.driver_version.as_deref()),
+                optional(gpu.temperature().map(|t| format!("{t:.0} °C"))),
+            ]
+        })
+        .collect();
+    ReportSection::new("GPUs").with_table(&["Model", "Type", "Memory", "Driver", "Temperature"], rows)
+}
+
+fn memory_section(info: &HardwareInfo, detail: ReportDetail) -> ReportSection {
+    let memory = info.memory();
+    let section = ReportSection::new("Memory").with_properties(vec![
+        ("Total", gb(memory.total_gb())),
+        ("Available", gb(memory.available_gb())),
+        ("Swap", format!("{} used of {}", gb(memory.swap_used_gb()), gb(memory.swap_total_gb()))),
+        ("Modules", memory.modules().len().to_string()),
+        ("ECC", if memory.ecc_support() { "Yes" } else { "No" }.to_string()),
+    ]);
+    if detail < ReportDetail::Full {
+        return section;
+    }
+    let rows = memory
+        .modules()
+        .iter()
+        .map(|module| {
+            vec![
+                optional(module.slot()),
+                gb(module.size_gb()),
+                module.memory_type().to_string(),
+                format!("{} MHz", module.speed_mhz()),
+                optional(module.manufacturer()),
+                optional(module.part_number()),
+            ]
+        })
+        .collect();
+    section.with_table(&["Slot", "Size", "Type", "Speed", "Manufacturer", "Part number"], rows)
+}
+
+fn storage_section(info: &HardwareInfo, detail: ReportDetail) -> ReportSection {
+    let volumes = info
+        .volumes()
+        .iter()
+        .map(|volume| {
+            let mut row = vec![
+                volume.mount_point().to_string(),
+                optional(volume.file_system()),
+                gb(volume.total_gb()),
+                gb(volume.available_gb()),
+                format!("{:.0}%", volume.usage_percent()),
+            ];
+            if detail >= ReportDetail::Full {
+                row.push(volume.mount_options().join(","));
+            }
+            row
+        })
+        .collect();
+    let headers: &[&str] = if detail >= ReportDetail::Full {
+        &["Mount", "File system", "Size", "Free", "Used", "Options"]
+    } else {
+        &["Mount", "File system", "Size", "Free", "Used"]
+    };
+    let section = ReportSection::new("Storage").with_table(headers, volumes);
+    if detail < ReportDetail::Full {
+        return section;
+    }
+    let disks = info
+        .physical_disks()
+        .iter()
+        .map(|disk| {
+            vec![
+                disk.device().to_string(),
+                disk.model().to_string(),
+                disk.drive_type().to_string(),
+                gb(disk.capacity_gb()),
+                match disk.smart_health() {
+                    Some(_) if disk.is_failing() => "Failing".to_string(),
+                    Some(_) => "OK".to_string(),
+                    None => "-".to_string(),
+                },
+            ]
+        })
+        .collect();
+    section.with_table(&["Disk", "Model", "Type", "Capacity", "SMART"], disks)
+}
+
+fn network_section(info: &HardwareInfo) -> ReportSection {
+    let rows = info
+        .network_interfaces()
+        .iter()
+        .filter(|interface| !interface.is_virtual())
+        .map(|interface| {
+            vec![
+                interface.name().to_string(),
+                interface.network_type().to_string(),
+                optional(interface.speed_mbps().map(|speed| format!("{speed} Mb/s"))),
+                if interface.is_active() { "Up" } else { "Down" }.to_string(),
+                optional(interface.driver()),
+            ]
+        })
+        .collect();
+    ReportSection::new("Network").with_table(&["Interface", "Type", "Speed", "State", "Driver"], rows)
+}
+
+fn battery_section(info: &HardwareInfo) -> ReportSection {
+    let Some(battery) = info.battery() else {
+        return ReportSection::new("Battery");
+    };
+    let mut properties = vec![
+        ("Charge", format!("{:.0}%", battery.percentage())),
+        ("Status", battery.status().to_string()),
+    ];
+    if let Some(health) = battery.health_percent() {
+        properties.push(("Health", format!("{health:.0}%")));
+    }
+    if let Some(cycles) = battery.cycle_count() {
+        properties.push(("Cycles", cycles.to_string()));
+    }
+    ReportSection::new("Battery").with_properties(properties)
+}
+
+fn thermal_section(info: &HardwareInfo) -> ReportSection {
+    let rows = info
+        .thermal()
+        .sensors()
+        .iter()
+        .map(|sensor| {
+            vec![
+                sensor.name.clone(),
+                sensor.sensor_type.clone(),
+                format!("{:.0} °C", sensor.temperature),
+                optional(sensor.critical_temperature.map(|t| format!("{t:.0} °C"))),
+            ]
+        })
+        .collect();
+    ReportSection::new("Sensors").with_table(&["Sensor", "Type", "Temperature", "Critical"], rows)
+}
+
+fn pci_section(info: &HardwareInfo) -> ReportSection {
+    let rows = info
+        .pci_devices()
+        .iter()
+        .map(|device| {
+            vec![
+                device.bus_location.clone(),
+                device.device_class.clone(),
+                match (device.vendor_name.trim(), device.device_name.trim()) {
+                    ("", "") => device.device_id.clone(),
+                    (vendor, name) => with_vendor(vendor, name),
+                },
+                optional(device.driver.as_deref()),
+            ]
+        })
+        .collect();
+    ReportSection::new("PCI Devices").with_table(&["Slot", "Class", "Device", "Driver"], rows)
+}
+
+fn usb_section(info: &HardwareInfo) -> ReportSection {
+    let rows = info
+        .usb_devices()
+        .iter()
+        .map(|device| {
+            vec![
+                format!("{}:{}", device.vendor_id, device.product_id),
+                with_vendor(&device.vendor_name, &device.product_name).trim().to_string(),
+                device.device_class.clone(),
+                device.usb_version.clone(),
+            ]
+        })
+        .collect();
+    ReportSection::new("USB Devices").with_table(&["ID", "Device", "Class", "USB version"], rows)
+}
+
+impl From<&AIHardwareAssessment> for ReportSection {
+    fn from(assessment: &AIHardwareAssessment) -> Self {
+        let frameworks = assessment
+            .frameworks
+            .iter()
+            .map(|framework| {
+                vec![
+                    framework.name.clone(),
+                    format!("{:?}", framework.compatibility),
+                    format!("{:?}", framework.performance_estimate),
+                    framework.notes.clone(),
+                    framework
+                        .unmet_requirements
+                        .iter()
+                        .map(|check| format!("{} ({})", check.requirement, check.detail))
+                        .collect::<Vec<_>>()
+                        .join("; "),
+                ]
+            })
+            .collect();
+        ReportSection::new("AI/ML Assessment")
+            .with_properties(vec![
+                ("AI score", format!("{}/100", assessment.ai_score)),
+                ("Inference", format!("{:?}", assessment.performance.inference_capability)),
+                ("Training", format!("{:?}", assessment.performance.training_capability)),
+            ])
+            .with_table(&["Framework", "Compatibility", "Performance", "Notes", "Unmet requirements"], frameworks)
+            .with_list(assessment.optimizations.clone())
+    }
+}
+
+impl From<&LLMHardwareAssessment> for ReportSection {
+    fn from(assessment: &LLMHardwareAssessment) -> Self {
+        let rate = |rate: Option<f64>| rate.map_or("-".to_string(), |rate| format!("{rate:.0} tokens/s"));
+        let sizes = assessment
+            .max_model_sizes
+            .iter()
+            .map(|size| {
+                vec![
+                    size.quantization.to_string(),
+                    format!("{:.1}B", size.max_parameters_in_gpu_billions),
+                    format!("{:.1}B", size.max_parameters_billions),
+                ]
+            })
+            .collect();
+        let runtimes = assessment
+            .runtimes
+            .iter()
+            .map(|runtime| {
+                vec![
+                    runtime.name.clone(),
+                    if runtime.viable { "Yes" } else { "No" }.to_string(),
+                    runtime.backend.clone().unwrap_or_default(),
+                    runtime.notes.clone(),
+                ]
+            })
+            .collect();
+        ReportSection::new("LLM Inference Assessment")
+            .with_properties(vec![
+                ("Reference model", assessment.reference_model.clone()),
+                ("Prefill", format!("{} ({})", rate(assessment.prefill.tokens_per_second), assessment.prefill.class)),
+                ("Decode", format!("{} ({})", rate(assessment.decode.tokens_per_second), assessment.decode.class)),
+            ])
+            .with_table(&["Format", "Largest in GPU memory", "Largest overall"], sizes)
+            .with_table(&["Engine", "Viable", "Backend", "Notes"], runtimes)
+            .with_list(assessment.recommendations.clone())
+    }
+}
+
+impl From<&ImageGenerationAssessment> for ReportSection {
+    fn from(assessment: &ImageGenerationAssessment) -> Self {
+        let models = assessment
+            .models
+            .iter()
+            .map(|fit| {
+                vec![
+                    fit.model.clone(),
+                    format!("{0}x{0}", fit.resolution),
+                    format!("{:.1} GB", fit.vram_required_gb),
+                    if fit.fits { fit.recommended_batch_size.to_string() } else { "-".to_string() },
+                    fit.notes.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        let yes_no = |value: bool| if value { "Yes" } else { "No" }.to_string();
+        let mut properties = vec![
+            ("GPU", assessment.gpu.clone().unwrap_or_else(|| "None".to_string())),
+            ("Usable VRAM", format!("{:.1} GB", assessment.usable_vram_gb)),
+            ("fp16", yes_no(assessment.fp16_supported)),
+            ("bf16", yes_no(assessment.bf16_supported)),
+        ];
+        for acceleration in &assessment.accelerations {
+            properties.push((acceleration.name.as_str(), yes_no(acceleration.supported)));
+        }
+        ReportSection::new("Image Generation Assessment")
+            .with_properties(properties)
+            .with_table(&["Model", "Resolution", "VRAM", "Batch size", "Notes"], models)
+            .with_list(assessment.recommendations.clone())
+    }
+}
+
+impl From<&ContentCreationAssessment> for ReportSection {
+    fn from(assessment: &ContentCreationAssessment) -> Self {
+        let codecs = |codecs: &[crate::VideoCodec]| {
+            codecs.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+        };
+        let engines = assessment
+            .video_engines
+            .iter()
+            .map(|engine| {
+                vec![
+                    engine.gpu.clone(),
+                    engine.generation.clone().map_or(engine.name.clone(), |generation| {
+                        format!("{} {generation}", engine.name)
+                    }),
+                    codecs(&engine.encode),
+                    codecs(&engine.decode),
+                ]
+            })
+            .collect();
+        let timelines = assessment
+            .timelines
+            .iter()
+            .map(|timeline| {
+                vec![
+                    timeline.resolution.clone(),
+                    if timeline.native_editing { "Native" } else { "Proxies" }.to_string(),
+                    timeline.limitations.join("; "),
+                ]
+            })
+            .collect();
+        ReportSection::new("Content Creation Assessment")
+            .with_properties(vec![
+                ("GPU memory", format!("{:.1} GB", assessment.gpu_memory_gb)),
+                ("System memory", format!("{:.0} GB", assessment.memory_gb)),
+                (
+                    "Media drive",
+                    format!(
+                        "{} ({:.0} MB/s)",
+                        assessment.media_drive.as_deref().unwrap_or("Unknown"),
+                        assessment.storage_throughput_mb_s
+                    ),
+                ),
+            ])
+            .with_table(&["GPU", "Engine", "Encode", "Decode"], engines)
+            .with_table(&["Timeline", "Editing", "Limitations"], timelines)
+            .with_list(assessment.recommendations.clone())
+    }
+}
+
+impl From<&GamingHardwareAssessment> for ReportSection {
+    fn from(assessment: &GamingHardwareAssessment) -> Self {
+        let settings = &assessment.recommended_settings;
+        let mut properties = vec![
+            ("Gaming score", format!("{}/100", assessment.gaming_score)),
+            (
+                "Recommended settings",
+                format!("{} {:?}, {} FPS target", settings.resolution, settings.quality_preset, settings.target_fps),
+            ),
+        ];
+        if let Some(tier) = assessment.gpu_tier {
+            properties.push(("GPU tier", tier.to_string()));
+        }
+        ReportSection::new("Gaming Assessment")
+            .with_properties(properties)
+            .with_list(
+                assessment
+                    .bottlenecks
+                    .iter()
+                    .chain(&assessment.upgrade_recommendations)
+                    .cloned()
+                    .collect(),
+            )
+    }
+}
+
+impl From<&DeveloperHardwareAssessment> for ReportSection {
+    fn from(assessment: &DeveloperHardwareAssessment) -> Self {
+        let environments = assessment
+            .environments
+            .iter()
+            .map(|environment| {
+                vec![
+                    environment.name.clone(),
+                    format!("{:?}", environment.suitability),
+                    environment.recommended_config.clone(),
+                ]
+            })
+            .collect();
+        let virtualization = &assessment.virtualization_support;
+        ReportSection::new("Developer Assessment")
+            .with_properties(vec![
+                ("Development score", format!("{}/100", assessment.dev_score)),
+                (
+                    "Virtualization",
+                    format!(
+                        "{} (up to {} VMs)",
+                        if virtualization.hardware_acceleration { "Hardware accelerated" } else { "Software only" },
+                        virtualization.max_recommended_vms
+                    ),
+                ),
+            ])
+            .with_table(&["Environment", "Suitability", "Notes"], environments)
+            .with_list(assessment.tool_recommendations.clone())
+    }
+}
+
+impl From<&ServerHardwareAssessment> for ReportSection {
+    fn from(assessment: &ServerHardwareAssessment) -> Self {
+        let workloads = assessment
+            .workload_suitability
+            .iter()
+            .map(|workload| {
+                vec![
+                    workload.workload_type.clone(),
+                    format!("{}/100", workload.suitability_score),
+                    workload.notes.clone(),
+                ]
+            })
+            .collect();
+        ReportSection::new("Server Assessment")
+            .with_properties(vec![
+                ("Server score", format!("{}/100", assessment.server_score)),
+                ("Estimated uptime", format!("{:.2}%", assessment.reliability.uptime_estimate)),
+            ])
+            .with_table(&["Workload", "Suitability", "Notes"], workloads)
+            .with_list(assessment.reliability.maintenance_requirements.clone())
+    }
+}
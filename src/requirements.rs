@@ -0,0 +1,287 @@
+//! Application hardware requirements
+//!
+//! A [`Requirements`] spec lists what an application needs from the machine
+//! it runs on. It serializes to JSON/TOML, so it can ship alongside the
+//! application, and [`Requirements::check`] evaluates it against a
+//! [`HardwareInfo`] snapshot.
+//!
+//! ```rust
+//! use hardware_query::{GpuRequirement, HardwareInfo, Requirements};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let requirements = Requirements::new()
+//!     .with_physical_cores(4)
+//!     .with_memory_gb(16.0)
+//!     .with_gpu(GpuRequirement::new().with_memory_gb(8.0).with_cuda_capability("7.5"))
+//!     .with_cpu_feature("avx2")
+//!     .with_free_disk_gb(20.0);
+//!
+//! let report = requirements.check(&HardwareInfo::query()?);
+//! for failure in report.failures() {
+//!     println!("{}: {}", failure.requirement, failure.detail);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::presets::version_at_least;
+use crate::{GPUInfo, GPUVendor, HardwareInfo};
+use serde::{Deserialize, Serialize};
+
+/// GPU an application needs; every set field has to hold for the same GPU
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GpuRequirement {
+    /// Required vendor
+    pub vendor: Option<GPUVendor>,
+    /// Minimum GPU memory in GB
+    pub min_memory_gb: Option<f64>,
+    /// Minimum CUDA compute capability (`7.5`); implies an NVIDIA GPU
+    pub min_cuda_capability: Option<String>,
+}
+
+impl GpuRequirement {
+    /// Any GPU
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require a GPU from `vendor`
+    pub fn with_vendor(mut self, vendor: GPUVendor) -> Self {
+        self.vendor = Some(vendor);
+        self
+    }
+
+    /// Require at least `gb` of GPU memory
+    pub fn with_memory_gb(mut self, gb: f64) -> Self {
+        self.min_memory_gb = Some(gb);
+        self
+    }
+
+    /// Require at least CUDA compute capability `minimum`
+    pub fn with_cuda_capability(mut self, minimum: impl Into<String>) -> Self {
+        self.min_cuda_capability = Some(minimum.into());
+        self
+    }
+
+    pub(crate) fn matches(&self, gpu: &GPUInfo) -> bool {
+        self.vendor.as_ref().is_none_or(|vendor| gpu.vendor() == vendor)
+            && self.min_memory_gb.is_none_or(|minimum| gpu.memory_gb() >= minimum)
+            && self.min_cuda_capability.as_deref().is_none_or(|minimum| {
+                gpu.cuda_capability()
+                    .is_some_and(|capability| version_at_least(capability, minimum))
+            })
+    }
+}
+
+impl std::fmt::Display for GpuRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(vendor) = &self.vendor {
+            parts.push(format!("{vendor}"));
+        }
+        if let Some(gb) = self.min_memory_gb {
+            parts.push(format!("{gb:.0} GB+"));
+        }
+        if let Some(minimum) = &self.min_cuda_capability {
+            parts.push(format!("CUDA {minimum}+"));
+        }
+        if parts.is_empty() {
+            write!(f, "GPU")
+        } else {
+            write!(f, "GPU ({})", parts.join(", "))
+        }
+    }
+}
+
+/// One line of a [`Requirements`] spec
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HardwareRequirement {
+    /// Minimum physical CPU cores
+    PhysicalCores(u32),
+    /// Minimum logical CPU cores (hardware threads)
+    LogicalCores(u32),
+    /// Minimum system memory in GB
+    MemoryGb(f64),
+    /// Instruction set extension (`avx2`, `neon`)
+    CpuFeature(String),
+    /// A GPU matching every field
+    Gpu(GpuRequirement),
+    /// Minimum free space in GB on some non-removable volume
+    FreeDiskGb(f64),
+}
+
+impl std::fmt::Display for HardwareRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HardwareRequirement::PhysicalCores(cores) => write!(f, "{cores} physical cores"),
+            HardwareRequirement::LogicalCores(threads) => write!(f, "{threads} logical cores"),
+            HardwareRequirement::MemoryGb(gb) => write!(f, "{gb:.0} GB system memory"),
+            HardwareRequirement::CpuFeature(feature) => write!(f, "{} CPU support", feature.to_uppercase()),
+            HardwareRequirement::Gpu(gpu) => write!(f, "{gpu}"),
+            HardwareRequirement::FreeDiskGb(gb) => write!(f, "{gb:.0} GB free disk space"),
+        }
+    }
+}
+
+impl HardwareRequirement {
+    /// Evaluate the requirement against a hardware snapshot
+    pub fn check(&self, hardware: &HardwareInfo) -> ComplianceCheck {
+        let cpu = hardware.cpu();
+        let (passed, detail) = match self {
+            HardwareRequirement::PhysicalCores(minimum) => (
+                cpu.physical_cores() >= *minimum,
+                format!("{} has {} physical cores", cpu.model_name(), cpu.physical_cores()),
+            ),
+            HardwareRequirement::LogicalCores(minimum) => (
+                cpu.logical_cores() >= *minimum,
+                format!("{} has {} logical cores", cpu.model_name(), cpu.logical_cores()),
+            ),
+            HardwareRequirement::MemoryGb(minimum) => {
+                let installed = hardware.memory().total_gb();
+                (installed >= *minimum, format!("{installed:.1} GB installed"))
+            }
+            HardwareRequirement::CpuFeature(feature) => {
+                let supported = cpu.has_feature(feature);
+                let verb = if supported { "supports" } else { "lacks" };
+                (supported, format!("{} {verb} {}", cpu.model_name(), feature.to_uppercase()))
+            }
+            HardwareRequirement::Gpu(requirement) => {
+                match hardware.gpus().iter().find(|gpu| requirement.matches(gpu)) {
+                    Some(gpu) => (true, format!("{} matches", gpu.model_name())),
+                    None if hardware.gpus().is_empty() => (false, "no GPU detected".to_string()),
+                    None => {
+                        let found: Vec<String> = hardware
+                            .gpus()
+                            .iter()
+                            .map(|gpu| match gpu.cuda_capability() {
+                                Some(capability) => {
+                                    format!("{} ({:.1} GB, CUDA {capability})", gpu.model_name(), gpu.memory_gb())
+                                }
+                                None => format!("{} ({:.1} GB)", gpu.model_name(), gpu.memory_gb()),
+                            })
+                            .collect();
+                        (false, format!("no match among {}", found.join(", ")))
+                    }
+                }
+            }
+            HardwareRequirement::FreeDiskGb(minimum) => {
+                let largest = hardware
+                    .volumes()
+                    .iter()
+                    .filter(|volume| !volume.removable)
+                    .max_by(|a, b| a.available_gb().total_cmp(&b.available_gb()));
+                match largest {
+                    Some(volume) => (
+                        volume.has_free_space(*minimum),
+                        format!("{:.1} GB free on {}", volume.available_gb(), volume.mount_point()),
+                    ),
+                    None => (false, "no volumes detected".to_string()),
+                }
+            }
+        };
+        ComplianceCheck {
+            requirement: self.clone(),
+            passed,
+            detail,
+        }
+    }
+}
+
+/// Hardware an application needs, evaluated with [`Requirements::check`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Requirements {
+    /// Individual requirements, all of which have to pass
+    #[serde(default)]
+    pub requirements: Vec<HardwareRequirement>,
+}
+
+impl Requirements {
+    /// Empty spec that every machine satisfies
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a requirement
+    pub fn with(mut self, requirement: HardwareRequirement) -> Self {
+        self.requirements.push(requirement);
+        self
+    }
+
+    /// Require at least `cores` physical CPU cores
+    pub fn with_physical_cores(self, cores: u32) -> Self {
+        self.with(HardwareRequirement::PhysicalCores(cores))
+    }
+
+    /// Require at least `threads` logical CPU cores
+    pub fn with_logical_cores(self, threads: u32) -> Self {
+        self.with(HardwareRequirement::LogicalCores(threads))
+    }
+
+    /// Require at least `gb` of system memory
+    pub fn with_memory_gb(self, gb: f64) -> Self {
+        self.with(HardwareRequirement::MemoryGb(gb))
+    }
+
+    /// Require a CPU instruction set extension
+    pub fn with_cpu_feature(self, feature: impl Into<String>) -> Self {
+        self.with(HardwareRequirement::CpuFeature(feature.into()))
+    }
+
+    /// Require a GPU
+    pub fn with_gpu(self, gpu: GpuRequirement) -> Self {
+        self.with(HardwareRequirement::Gpu(gpu))
+    }
+
+    /// Require at least `gb` of free space on a non-removable volume
+    pub fn with_free_disk_gb(self, gb: f64) -> Self {
+        self.with(HardwareRequirement::FreeDiskGb(gb))
+    }
+
+    /// Evaluate every requirement against a hardware snapshot
+    pub fn check(&self, hardware: &HardwareInfo) -> ComplianceReport {
+        ComplianceReport {
+            checks: self.requirements.iter().map(|requirement| requirement.check(hardware)).collect(),
+        }
+    }
+}
+
+/// Outcome of checking one [`HardwareRequirement`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComplianceCheck {
+    /// What was checked
+    pub requirement: HardwareRequirement,
+    /// Whether the machine satisfies it
+    pub passed: bool,
+    /// What was found, e.g. `62.7 GB installed`
+    pub detail: String,
+}
+
+/// Pass/fail result of every requirement in a [`Requirements`] spec
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComplianceReport {
+    /// One entry per requirement, in spec order
+    pub checks: Vec<ComplianceCheck>,
+}
+
+impl ComplianceReport {
+    /// Check if every requirement passed
+    pub fn is_compliant(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    /// Get the requirements that failed
+    pub fn failures(&self) -> Vec<&ComplianceCheck> {
+        self.checks.iter().filter(|check| !check.passed).collect()
+    }
+}
+
+impl std::fmt::Display for ComplianceReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for check in &self.checks {
+            let mark = if check.passed { "PASS" } else { "FAIL" };
+            writeln!(f, "[{mark}] {}: {}", check.requirement, check.detail)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,257 @@
+//! Threshold rules for monitoring alerts
+//!
+//! A rule watches one [`Metric`] and fires a
+//! [`MonitoringEvent::RuleTriggered`](crate::MonitoringEvent::RuleTriggered)
+//! once the value has stayed past its threshold for the configured duration.
+//! It clears (with a
+//! [`MonitoringEvent::RuleCleared`](crate::MonitoringEvent::RuleCleared)) only
+//! after the value has moved back past the threshold by the hysteresis margin,
+//! so readings hovering around the threshold don't flap.
+//!
+//! ```rust
+//! use hardware_query::{AlertRule, MonitoringConfig};
+//! use std::time::Duration;
+//!
+//! let mut config = MonitoringConfig::default();
+//! config.add_rule(AlertRule::gpu_temp().above(83.0).for_duration(Duration::from_secs(30)));
+//! config.add_rule(
+//!     AlertRule::memory_usage()
+//!         .above(90.0)
+//!         .with_hysteresis(5.0)
+//!         .with_id("memory-pressure"),
+//! );
+//! ```
+
+use crate::history::Metric;
+use crate::MonitoringEvent;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+/// Direction in which a rule's threshold is crossed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparison {
+    /// Fire when the value rises above the threshold
+    Above,
+    /// Fire when the value falls below the threshold
+    Below,
+}
+
+/// A threshold alert on a monitored metric
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Explicit rule id (see [`AlertRule::id`])
+    pub id: Option<String>,
+    /// Metric to watch
+    pub metric: Metric,
+    /// Direction of the threshold
+    pub comparison: Comparison,
+    /// Threshold in the metric's unit
+    pub threshold: f64,
+    /// How long the threshold must stay crossed before the rule fires
+    pub sustained: Duration,
+    /// Margin the value must move back past the threshold before the rule clears
+    pub hysteresis: f64,
+}
+
+impl AlertRule {
+    /// Rule on `metric`; defaults to firing above zero immediately
+    pub fn new(metric: Metric) -> Self {
+        Self {
+            id: None,
+            metric,
+            comparison: Comparison::Above,
+            threshold: 0.0,
+            sustained: Duration::ZERO,
+            hysteresis: 0.0,
+        }
+    }
+
+    /// Rule on the hottest CPU sensor (Celsius)
+    pub fn cpu_temp() -> Self {
+        Self::new(Metric::CpuTemperature)
+    }
+
+    /// Rule on the hottest GPU sensor (Celsius)
+    pub fn gpu_temp() -> Self {
+        Self::new(Metric::GpuTemperature)
+    }
+
+    /// Rule on average CPU usage (percent)
+    pub fn cpu_usage() -> Self {
+        Self::new(Metric::CpuUsage)
+    }
+
+    /// Rule on average effective CPU frequency (MHz), e.g. to catch throttling
+    pub fn cpu_frequency() -> Self {
+        Self::new(Metric::CpuFrequency)
+    }
+
+    /// Rule on the busiest GPU's usage (percent)
+    pub fn gpu_usage() -> Self {
+        Self::new(Metric::GpuUsage)
+    }
+
+    /// Rule on the fullest GPU's VRAM usage (percent)
+    pub fn gpu_memory_usage() -> Self {
+        Self::new(Metric::GpuMemoryUsage)
+    }
+
+    /// Rule on memory usage (percent)
+    pub fn memory_usage() -> Self {
+        Self::new(Metric::MemoryUsage)
+    }
+
+    /// Rule on total power draw (watts)
+    pub fn power_draw() -> Self {
+        Self::new(Metric::PowerDraw)
+    }
+
+    /// Rule on battery charge (percent)
+    pub fn battery_level() -> Self {
+        Self::new(Metric::BatteryLevel)
+    }
+
+    /// Rule on battery drain while discharging (percent per hour)
+    pub fn battery_drain_rate() -> Self {
+        Self::new(Metric::BatteryDrainRate)
+    }
+
+    /// Rule on read plus write throughput across all disks (MB/s)
+    pub fn disk_throughput() -> Self {
+        Self::new(Metric::DiskThroughput)
+    }
+
+    /// Rule on the busiest disk's utilization (percent)
+    pub fn disk_utilization() -> Self {
+        Self::new(Metric::DiskUtilization)
+    }
+
+    /// Rule on received traffic across non-loopback interfaces (Mbit/s)
+    pub fn network_receive() -> Self {
+        Self::new(Metric::NetworkReceive)
+    }
+
+    /// Rule on transmitted traffic across non-loopback interfaces (Mbit/s)
+    pub fn network_transmit() -> Self {
+        Self::new(Metric::NetworkTransmit)
+    }
+
+    /// Fire when the value rises above `threshold`
+    pub fn above(mut self, threshold: f64) -> Self {
+        self.comparison = Comparison::Above;
+        self.threshold = threshold;
+        self
+    }
+
+    /// Fire when the value falls below `threshold`
+    pub fn below(mut self, threshold: f64) -> Self {
+        self.comparison = Comparison::Below;
+        self.threshold = threshold;
+        self
+    }
+
+    /// Require the threshold to stay crossed for `duration` before firing
+    pub fn for_duration(mut self, duration: Duration) -> Self {
+        self.sustained = duration;
+        self
+    }
+
+    /// Clear only once the value is `margin` back on the safe side of the threshold
+    pub fn with_hysteresis(mut self, margin: f64) -> Self {
+        self.hysteresis = margin.abs();
+        self
+    }
+
+    /// Set the id reported in events
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Id reported in events: the explicit id, or one derived from the rule
+    /// (e.g. `"GPU temperature > 83"`)
+    pub fn id(&self) -> String {
+        match &self.id {
+            Some(id) => id.clone(),
+            None => {
+                let op = match self.comparison {
+                    Comparison::Above => '>',
+                    Comparison::Below => '<',
+                };
+                format!("{} {op} {}", self.metric, self.threshold)
+            }
+        }
+    }
+
+    fn is_breached(&self, value: f64) -> bool {
+        match self.comparison {
+            Comparison::Above => value > self.threshold,
+            Comparison::Below => value < self.threshold,
+        }
+    }
+
+    fn is_cleared(&self, value: f64) -> bool {
+        match self.comparison {
+            Comparison::Above => value <= self.threshold - self.hysteresis,
+            Comparison::Below => value >= self.threshold + self.hysteresis,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct RuleState {
+    breached_since: Option<SystemTime>,
+    active: bool,
+}
+
+/// Tracks sustained breaches for a set of rules across monitoring updates
+pub(crate) struct RuleEngine {
+    rules: Vec<AlertRule>,
+    states: Vec<RuleState>,
+}
+
+impl RuleEngine {
+    pub(crate) fn new(rules: Vec<AlertRule>) -> Self {
+        let states = vec![RuleState::default(); rules.len()];
+        Self { rules, states }
+    }
+
+    /// Evaluate every rule against the latest metric values
+    pub(crate) fn evaluate(&mut self, values: &[(Metric, f64)], now: SystemTime) -> Vec<MonitoringEvent> {
+        let mut events = Vec::new();
+
+        for (rule, state) in self.rules.iter().zip(&mut self.states) {
+            let Some(&(_, value)) = values.iter().find(|(metric, _)| *metric == rule.metric) else {
+                continue;
+            };
+
+            if state.active {
+                if rule.is_cleared(value) {
+                    *state = RuleState::default();
+                    events.push(MonitoringEvent::RuleCleared {
+                        rule_id: rule.id(),
+                        metric: rule.metric,
+                        value,
+                        timestamp: now,
+                    });
+                }
+            } else if rule.is_breached(value) {
+                let since = *state.breached_since.get_or_insert(now);
+                if now.duration_since(since).unwrap_or_default() >= rule.sustained {
+                    state.active = true;
+                    events.push(MonitoringEvent::RuleTriggered {
+                        rule_id: rule.id(),
+                        metric: rule.metric,
+                        value,
+                        threshold: rule.threshold,
+                        timestamp: now,
+                    });
+                }
+            } else {
+                state.breached_since = None;
+            }
+        }
+
+        events
+    }
+}
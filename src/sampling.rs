@@ -0,0 +1,154 @@
+//! Adaptive sampling intervals for [`HardwareMonitor`](crate::HardwareMonitor)
+//!
+//! With [`MonitoringConfig::adaptive_sampling`](crate::MonitoringConfig::adaptive_sampling)
+//! set, the monitor stretches its interval while temperatures and power draw
+//! are stable and drops back to the minimum as soon as they climb quickly or
+//! approach the configured thermal/power thresholds. An idle laptop then
+//! wakes up every `max_interval` instead of every second.
+//!
+//! ```rust
+//! use hardware_query::{AdaptiveSampling, MonitoringConfig};
+//! use std::time::Duration;
+//!
+//! let config = MonitoringConfig {
+//!     adaptive_sampling: Some(AdaptiveSampling {
+//!         min_interval: Duration::from_millis(500),
+//!         max_interval: Duration::from_secs(60),
+//!         ..AdaptiveSampling::default()
+//!     }),
+//!     ..MonitoringConfig::default()
+//! };
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Bounds and sensitivity of adaptive sampling
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdaptiveSampling {
+    /// Shortest interval, used while readings change rapidly or near a threshold
+    pub min_interval: Duration,
+    /// Longest interval, reached after a run of stable readings
+    pub max_interval: Duration,
+    /// Temperature rise in °C per minute that counts as rapid
+    pub temperature_rate: f32,
+    /// Power draw rise in watts per minute that counts as rapid
+    pub power_rate: f32,
+    /// Within this fraction of a threshold (0.1 = 10%) the minimum interval is used
+    pub threshold_margin: f32,
+    /// Factor the interval grows by after each stable sample
+    pub backoff: f64,
+}
+
+impl Default for AdaptiveSampling {
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::from_secs(1),
+            max_interval: Duration::from_secs(30),
+            temperature_rate: 10.0,
+            power_rate: 30.0,
+            threshold_margin: 0.1,
+            backoff: 1.5,
+        }
+    }
+}
+
+/// Readings adaptive sampling reacts to
+///
+/// Also used for the thresholds those readings are compared against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SamplingReading {
+    /// Hottest sensor in °C
+    pub temperature: Option<f32>,
+    /// Total power draw in watts
+    pub power: Option<f32>,
+}
+
+/// How quickly readings are moving
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Activity {
+    Stable,
+    Changing,
+    Urgent,
+}
+
+impl AdaptiveSampling {
+    /// Clamp `interval` into `[min_interval, max_interval]`
+    pub fn clamp(&self, interval: Duration) -> Duration {
+        interval.clamp(self.min_interval, self.max_interval.max(self.min_interval))
+    }
+
+    /// Interval to use after a sample
+    ///
+    /// `elapsed` is the time between `previous` and `latest`; `limits` holds
+    /// the thermal and power alert thresholds. Rapid rises or readings within
+    /// [`threshold_margin`](Self::threshold_margin) of a limit jump straight
+    /// to the minimum, moderate changes halve the interval, and stable
+    /// readings grow it by [`backoff`](Self::backoff).
+    ///
+    /// ```rust
+    /// use hardware_query::{AdaptiveSampling, SamplingReading};
+    /// use std::time::Duration;
+    ///
+    /// let sampling = AdaptiveSampling::default();
+    /// let limits = SamplingReading { temperature: Some(85.0), power: None };
+    /// let idle = SamplingReading { temperature: Some(45.0), power: Some(8.0) };
+    /// let next = sampling.next_interval(Duration::from_secs(2), Duration::from_secs(2), &idle, &idle, &limits);
+    /// assert_eq!(next, Duration::from_secs(3));
+    /// ```
+    pub fn next_interval(
+        &self,
+        current: Duration,
+        elapsed: Duration,
+        previous: &SamplingReading,
+        latest: &SamplingReading,
+        limits: &SamplingReading,
+    ) -> Duration {
+        let minutes = elapsed.as_secs_f32() / 60.0;
+        let activity = [
+            self.activity(previous.temperature, latest.temperature, limits.temperature, self.temperature_rate, minutes),
+            self.activity(previous.power, latest.power, limits.power, self.power_rate, minutes),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(Activity::Stable);
+
+        let next = match activity {
+            Activity::Urgent => self.min_interval,
+            Activity::Changing => current / 2,
+            Activity::Stable => current.mul_f64(self.backoff.max(1.0)),
+        };
+        self.clamp(next)
+    }
+
+    fn activity(
+        &self,
+        previous: Option<f32>,
+        latest: Option<f32>,
+        limit: Option<f32>,
+        rapid_rate: f32,
+        minutes: f32,
+    ) -> Activity {
+        let Some(latest) = latest else {
+            return Activity::Stable;
+        };
+        if limit.is_some_and(|limit| latest >= limit * (1.0 - self.threshold_margin)) {
+            return Activity::Urgent;
+        }
+        let Some(previous) = previous else {
+            return Activity::Stable;
+        };
+        if minutes <= 0.0 {
+            return Activity::Stable;
+        }
+
+        let rate = (latest - previous) / minutes;
+        if rate >= rapid_rate {
+            Activity::Urgent
+        } else if rate.abs() >= rapid_rate / 4.0 {
+            Activity::Changing
+        } else {
+            Activity::Stable
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! Custom scoring plugins
+//!
+//! The built-in presets score hardware for AI, gaming, development and the
+//! like. A [`ScoringPlugin`] supplies the formulas for any other domain, and
+//! [`HardwarePresets::custom_assessment`](crate::HardwarePresets::custom_assessment)
+//! runs it against the detected hardware, the same way the built-in presets
+//! are run.
+//!
+//! ```rust
+//! use hardware_query::{HardwareInfo, HardwarePresets, NamedScore, ScoringPlugin};
+//!
+//! struct AudioDsp;
+//!
+//! impl ScoringPlugin for AudioDsp {
+//!     fn name(&self) -> &str {
+//!         "Audio DSP"
+//!     }
+//!
+//!     fn scores(&self, hardware: &HardwareInfo) -> Vec<NamedScore> {
+//!         let cpu = hardware.cpu();
+//!         let clock = cpu.max_frequency() as f64 / 50.0;
+//!         vec![
+//!             NamedScore::new("Single-thread", clock),
+//!             NamedScore::new("Plugin instances", cpu.physical_cores() as f64 * 6.0),
+//!         ]
+//!     }
+//! }
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let assessment = HardwarePresets::custom_assessment(&AudioDsp)?;
+//! println!("{}: {}/100", assessment.name, assessment.overall_score);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::simple::SystemOverview;
+use crate::HardwareInfo;
+use serde::{Deserialize, Serialize};
+
+/// One named score of a custom assessment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedScore {
+    /// What the score measures, e.g. `Single-thread`
+    pub name: String,
+    /// Score (0-100)
+    pub score: u8,
+    /// How the score was derived, if the plugin says
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+impl NamedScore {
+    /// Score named `name`, rounded and clamped to 0-100
+    pub fn new(name: impl Into<String>, score: f64) -> Self {
+        Self {
+            name: name.into(),
+            score: score.round().clamp(0.0, 100.0) as u8,
+            detail: None,
+        }
+    }
+
+    /// Explain how the score was derived
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// Domain-specific scoring formulas over the crate's hardware detection
+///
+/// Only [`name`](ScoringPlugin::name) and [`scores`](ScoringPlugin::scores)
+/// are required. The overall score defaults to the mean of the named scores.
+pub trait ScoringPlugin {
+    /// Assessment name, e.g. `Audio DSP`
+    fn name(&self) -> &str;
+
+    /// Named scores for the hardware
+    fn scores(&self, hardware: &HardwareInfo) -> Vec<NamedScore>;
+
+    /// Recommendations given the hardware and its scores; none by default
+    fn recommendations(&self, hardware: &HardwareInfo, scores: &[NamedScore]) -> Vec<String> {
+        let _ = (hardware, scores);
+        Vec::new()
+    }
+
+    /// Overall score (0-100); the mean of the named scores by default
+    fn overall_score(&self, scores: &[NamedScore]) -> u8 {
+        if scores.is_empty() {
+            return 0;
+        }
+        let total: u32 = scores.iter().map(|score| u32::from(score.score)).sum();
+        (total as f64 / scores.len() as f64).round() as u8
+    }
+}
+
+/// Result of running a [`ScoringPlugin`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomAssessment {
+    /// Assessment name, from [`ScoringPlugin::name`]
+    pub name: String,
+    /// System overview
+    pub overview: SystemOverview,
+    /// Overall score (0-100)
+    pub overall_score: u8,
+    /// Named scores, in the order the plugin returned them
+    pub scores: Vec<NamedScore>,
+    /// Recommendations
+    pub recommendations: Vec<String>,
+}
+
+impl CustomAssessment {
+    /// Get a named score
+    pub fn score(&self, name: &str) -> Option<u8> {
+        self.scores.iter().find(|score| score.name == name).map(|score| score.score)
+    }
+}
@@ -0,0 +1,218 @@
+//! Mapping raw sensors and fans to the components they belong to
+//!
+//! Sensor names come straight from the driver (`k10temp Tctl`,
+//! `nct6798 SYSTIN`, `amdgpu junction`) and mean little to end users. This
+//! module maps each one to a logical [`SensorComponent`] such as the CPU
+//! package, GPU 0 or the chassis, with a [`MappingConfidence`] saying how
+//! much the mapping can be trusted: dedicated drivers (`coretemp`, `amdgpu`,
+//! `nvme`) map with high confidence, Super I/O labels with medium
+//! confidence, and generic ACPI zones with low confidence.
+//!
+//! ```rust
+//! use hardware_query::{HardwareInfo, SensorComponent};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let thermal = HardwareInfo::query()?.thermal().clone();
+//! for (sensor, mapping) in thermal.sensor_mappings() {
+//!     println!("{} -> {} ({:?})", sensor.name, mapping.component, mapping.confidence);
+//! }
+//! if let Some(temperature) = thermal.component_temperature(SensorComponent::Gpu(0)) {
+//!     println!("GPU 0: {temperature:.1}°C");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{FanInfo, ThermalInfo, ThermalSensor};
+use serde::{Deserialize, Serialize};
+
+/// Logical component a sensor measures or a fan cools
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SensorComponent {
+    /// CPU package (Tctl/Tdie, package id, socket temperature)
+    CpuPackage,
+    /// A single CPU core
+    CpuCore(u32),
+    /// GPU by detection order
+    Gpu(u32),
+    /// Drive by detection order
+    Storage(u32),
+    /// CPU voltage regulators
+    Vrm,
+    /// Chipset / PCH
+    Chipset,
+    /// Memory modules
+    Memory,
+    /// Motherboard, case and ambient sensors, and case fans
+    Chassis,
+    /// Battery
+    Battery,
+    /// Network adapter
+    Network,
+    /// Could not be mapped
+    Unknown,
+}
+
+impl std::fmt::Display for SensorComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SensorComponent::CpuPackage => write!(f, "CPU Package"),
+            SensorComponent::CpuCore(core) => write!(f, "CPU Core {core}"),
+            SensorComponent::Gpu(index) => write!(f, "GPU {index}"),
+            SensorComponent::Storage(index) => write!(f, "Drive {index}"),
+            SensorComponent::Vrm => write!(f, "VRM"),
+            SensorComponent::Chipset => write!(f, "Chipset"),
+            SensorComponent::Memory => write!(f, "Memory"),
+            SensorComponent::Chassis => write!(f, "Chassis"),
+            SensorComponent::Battery => write!(f, "Battery"),
+            SensorComponent::Network => write!(f, "Network"),
+            SensorComponent::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// How much a [`SensorMapping`] can be trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum MappingConfidence {
+    /// Guessed from a generic name (ACPI zones, unlabeled channels)
+    Low,
+    /// Inferred from a board-specific label (`CPUTIN`, `SYS_FAN`)
+    Medium,
+    /// Reported by a driver dedicated to the component
+    High,
+}
+
+/// Component a sensor or fan was mapped to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SensorMapping {
+    /// Component the reading belongs to
+    pub component: SensorComponent,
+    /// How much the mapping can be trusted
+    pub confidence: MappingConfidence,
+}
+
+impl SensorMapping {
+    fn new(component: SensorComponent, confidence: MappingConfidence) -> Self {
+        Self { component, confidence }
+    }
+
+    /// Map a temperature sensor
+    ///
+    /// `instance` tells apart identically named sensors of several GPUs or
+    /// drives: 0 for the first `amdgpu edge`, 1 for the second, and so on.
+    pub fn for_sensor(sensor: &ThermalSensor, instance: u32) -> Self {
+        use MappingConfidence::{High, Low, Medium};
+        use SensorComponent::*;
+
+        let name = sensor.name.to_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|needle| name.contains(needle));
+        match sensor.sensor_type.as_str() {
+            "CPU" => match core_number(&name) {
+                Some(core) => Self::new(CpuCore(core), High),
+                None if has(&["package", "tctl", "tdie", "cpu_thermal", "cpu package"]) => Self::new(CpuPackage, High),
+                None => Self::new(CpuPackage, Medium),
+            },
+            "GPU" => Self::new(Gpu(instance), High),
+            "Storage" => Self::new(Storage(instance), High),
+            "Network" => Self::new(Network, High),
+            "Battery" => Self::new(Battery, High),
+            _ if has(&["spd5118", "jc42", "dimm", "memory"]) => Self::new(Memory, Medium),
+            _ if has(&["vrm", "vcore", "mos", "vr mos", "soc vr"]) => Self::new(Vrm, Medium),
+            _ if has(&["pch", "chipset"]) => Self::new(Chipset, Medium),
+            _ if has(&["cputin", "cpu", "x86_pkg", "peci"]) => Self::new(CpuPackage, Medium),
+            _ if has(&["gpu"]) => Self::new(Gpu(instance), Medium),
+            _ if has(&["systin", "auxtin", "system", "chassis", "ambient", "motherboard", "case"]) => {
+                Self::new(Chassis, Medium)
+            }
+            _ if has(&["acpitz", "thermal zone", "tz0", "soc"]) => Self::new(Chassis, Low),
+            _ => Self::new(Unknown, Low),
+        }
+    }
+
+    /// Map a fan to the component it cools
+    ///
+    /// `instance` tells apart identically named fans of several GPUs.
+    pub fn for_fan(fan: &FanInfo, instance: u32) -> Self {
+        use MappingConfidence::{High, Low, Medium};
+        use SensorComponent::*;
+
+        let name = fan.name.to_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|needle| name.contains(needle));
+        if has(&["amdgpu", "nouveau", "radeon", "gpu"]) {
+            Self::new(Gpu(instance), High)
+        } else if has(&["cpu_fan", "cpu fan", "cpu_opt", "cpu opt", "processor"]) {
+            Self::new(CpuPackage, High)
+        } else if has(&["pump", "aio", "cpu"]) {
+            Self::new(CpuPackage, Medium)
+        } else if has(&["sys", "cha", "case", "chassis", "rear", "front", "top", "exhaust", "intake"]) {
+            Self::new(Chassis, Medium)
+        } else if has(&["thinkpad", "dell_smm", "asus", "hp_wmi", "applesmc", "macsmc"]) {
+            // Laptop fans mostly cool the CPU/GPU heat pipe
+            Self::new(CpuPackage, Low)
+        } else {
+            Self::new(Chassis, Low)
+        }
+    }
+}
+
+/// Core number from labels like `coretemp Core 3` or `CPU Core #3`
+fn core_number(name: &str) -> Option<u32> {
+    let (_, rest) = name.rsplit_once("core")?;
+    let digits: String = rest
+        .trim_start_matches([' ', '#'])
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// Pair each item with its mapping, counting repeated names as separate instances
+fn map_all<T>(items: &[T], name: impl Fn(&T) -> &str, map: impl Fn(&T, u32) -> SensorMapping) -> Vec<(&T, SensorMapping)> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(position, item)| {
+            let instance = items[..position].iter().filter(|earlier| name(earlier) == name(item)).count() as u32;
+            (item, map(item, instance))
+        })
+        .collect()
+}
+
+impl ThermalInfo {
+    /// Get every temperature sensor with the component it belongs to
+    pub fn sensor_mappings(&self) -> Vec<(&ThermalSensor, SensorMapping)> {
+        map_all(&self.sensors, |sensor| sensor.name.as_str(), SensorMapping::for_sensor)
+    }
+
+    /// Get every fan with the component it cools
+    pub fn fan_mappings(&self) -> Vec<(&FanInfo, SensorMapping)> {
+        map_all(&self.fans, |fan| fan.name.as_str(), SensorMapping::for_fan)
+    }
+
+    /// Get sensors mapped to a component, most trustworthy first
+    pub fn sensors_for(&self, component: SensorComponent) -> Vec<&ThermalSensor> {
+        let mut matches: Vec<(&ThermalSensor, SensorMapping)> = self
+            .sensor_mappings()
+            .into_iter()
+            .filter(|(_, mapping)| mapping.component == component)
+            .collect();
+        matches.sort_by_key(|(_, mapping)| std::cmp::Reverse(mapping.confidence));
+        matches.into_iter().map(|(sensor, _)| sensor).collect()
+    }
+
+    /// Get fans mapped to a component, most trustworthy first
+    pub fn fans_for(&self, component: SensorComponent) -> Vec<&FanInfo> {
+        let mut matches: Vec<(&FanInfo, SensorMapping)> = self
+            .fan_mappings()
+            .into_iter()
+            .filter(|(_, mapping)| mapping.component == component)
+            .collect();
+        matches.sort_by_key(|(_, mapping)| std::cmp::Reverse(mapping.confidence));
+        matches.into_iter().map(|(fan, _)| fan).collect()
+    }
+
+    /// Get the temperature of a component from its most trustworthy sensor
+    pub fn component_temperature(&self, component: SensorComponent) -> Option<f32> {
+        self.sensors_for(component).first().map(|sensor| sensor.temperature)
+    }
+}
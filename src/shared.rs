@@ -0,0 +1,96 @@
+//! Shared hardware snapshot
+//!
+//! Servers often detect hardware once and hand the result to many request
+//! handlers. [`SharedHardwareInfo`] wraps a [`HardwareInfo`] in an
+//! `Arc<RwLock<_>>` so clones are cheap and readers on any thread see the
+//! same snapshot, while the dynamic readings (per-core usage, temperatures
+//! and fans) can be refreshed in place without re-running full detection.
+
+use crate::{CPUInfo, HardwareInfo, Result, ThermalInfo};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Thread-safe, cheaply clonable handle to one [`HardwareInfo`]
+///
+/// Refreshes sample the hardware before taking the write lock, so readers
+/// are only blocked while the new values are swapped in.
+///
+/// ```rust
+/// use hardware_query::SharedHardwareInfo;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let shared = SharedHardwareInfo::query()?;
+///
+/// let worker = shared.clone();
+/// std::thread::spawn(move || worker.refresh_cpu_usage()).join().unwrap()?;
+///
+/// println!("{} cores", shared.read().cpu().logical_cores());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedHardwareInfo {
+    inner: Arc<RwLock<HardwareInfo>>,
+}
+
+impl SharedHardwareInfo {
+    /// Share an existing snapshot
+    pub fn new(info: HardwareInfo) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(info)),
+        }
+    }
+
+    /// Run full detection and share the result
+    pub fn query() -> Result<Self> {
+        HardwareInfo::query().map(Self::new)
+    }
+
+    /// Borrow the current snapshot
+    ///
+    /// Refreshes wait until the guard is dropped, so keep it short-lived.
+    pub fn read(&self) -> RwLockReadGuard<'_, HardwareInfo> {
+        // Refreshes replace whole fields, so a poisoned lock still holds a consistent snapshot
+        self.inner.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Get a copy of the current snapshot
+    pub fn snapshot(&self) -> HardwareInfo {
+        self.read().clone()
+    }
+
+    /// Replace the whole snapshot, e.g. with the result of a new full detection
+    pub fn replace(&self, info: HardwareInfo) {
+        *self.write() = info;
+    }
+
+    /// Re-sample per-core CPU usage
+    pub fn refresh_cpu_usage(&self) -> Result<()> {
+        let usage = CPUInfo::sample_core_usage();
+        self.write().cpu.core_usage = usage;
+        Ok(())
+    }
+
+    /// Re-read temperature sensors, fans and the thermal status
+    ///
+    /// On error the previous readings are kept.
+    pub fn refresh_thermal(&self) -> Result<()> {
+        let thermal = ThermalInfo::query()?;
+        self.write().thermal = thermal;
+        Ok(())
+    }
+
+    /// Number of handles sharing this snapshot
+    pub fn handle_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HardwareInfo> {
+        self.inner.write().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl From<HardwareInfo> for SharedHardwareInfo {
+    fn from(info: HardwareInfo) -> Self {
+        Self::new(info)
+    }
+}
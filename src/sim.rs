@@ -0,0 +1,122 @@
+//! Canned machine profiles for deterministic tests
+//!
+//! Enabled by the `sim` feature. Each [`MachineProfile`] is a complete
+//! [`HardwareInfo`] snapshot shipped with the crate, so hardware-dependent
+//! logic can be tested in CI against machines the runners don't have:
+//!
+//! ```
+//! use hardware_query::{HardwareInfo, MachineProfile};
+//!
+//! # fn main() -> hardware_query::Result<()> {
+//! let dgx = HardwareInfo::query_with_provider(MachineProfile::DgxH100.provider()?)?;
+//! assert_eq!(dgx.gpus().len(), 8);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Code that calls [`HardwareInfo::query`] internally can be pointed at a
+//! profile without changes by setting `HARDWARE_QUERY_SIM` to a profile name
+//! (`raspberry_pi_5`) or the path of a JSON snapshot.
+
+use crate::{FixtureProvider, HardwareInfo, HardwareQueryError, Result};
+
+/// Environment variable that switches [`HardwareInfo::query`] to a fixture
+pub const SIM_ENV_VAR: &str = "HARDWARE_QUERY_SIM";
+
+/// A machine profile shipped with the `sim` feature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MachineProfile {
+    /// Dell XPS 13 laptop: Intel Core Ultra 7 155H, Arc iGPU, Intel AI Boost NPU, 32 GB LPDDR5, battery
+    LaptopIntegratedGpu,
+    /// NVIDIA DGX H100: 2x Xeon Platinum 8480C, 8x H100 80GB, 2 TB DDR5 ECC, ConnectX-7 InfiniBand
+    DgxH100,
+    /// Raspberry Pi 5: 4x Cortex-A76, VideoCore VII, 8 GB LPDDR4X, SD card
+    RaspberryPi5,
+    /// Windows gaming desktop: Ryzen 7 7800X3D, GeForce RTX 4090 (DirectX 12 Ultimate), 32 GB DDR5
+    GamingDesktopWindows,
+}
+
+impl MachineProfile {
+    /// Every shipped profile
+    pub const ALL: [MachineProfile; 4] = [
+        MachineProfile::LaptopIntegratedGpu,
+        MachineProfile::DgxH100,
+        MachineProfile::RaspberryPi5,
+        MachineProfile::GamingDesktopWindows,
+    ];
+
+    /// Profile name, as accepted by `HARDWARE_QUERY_SIM`
+    pub fn name(&self) -> &'static str {
+        match self {
+            MachineProfile::LaptopIntegratedGpu => "laptop_igpu",
+            MachineProfile::DgxH100 => "dgx_h100",
+            MachineProfile::RaspberryPi5 => "raspberry_pi_5",
+            MachineProfile::GamingDesktopWindows => "gaming_desktop_windows",
+        }
+    }
+
+    /// Find a profile by name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|profile| profile.name() == name)
+    }
+
+    /// The profile's serialized snapshot
+    pub fn json(&self) -> &'static str {
+        match self {
+            MachineProfile::LaptopIntegratedGpu => include_str!("sim/laptop_igpu.json"),
+            MachineProfile::DgxH100 => include_str!("sim/dgx_h100.json"),
+            MachineProfile::RaspberryPi5 => include_str!("sim/raspberry_pi_5.json"),
+            MachineProfile::GamingDesktopWindows => include_str!("sim/gaming_desktop_windows.json"),
+        }
+    }
+
+    /// Load the profile's snapshot
+    pub fn hardware_info(&self) -> Result<HardwareInfo> {
+        HardwareInfo::from_json(self.json())
+    }
+
+    /// A provider replaying the profile
+    pub fn provider(&self) -> Result<FixtureProvider> {
+        FixtureProvider::from_json(self.json())
+    }
+}
+
+impl std::fmt::Display for MachineProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl std::str::FromStr for MachineProfile {
+    type Err = HardwareQueryError;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Self::from_name(name).ok_or_else(|| {
+            let known: Vec<&str> = Self::ALL.iter().map(|profile| profile.name()).collect();
+            HardwareQueryError::invalid_configuration(format!(
+                "Unknown machine profile `{name}` (expected one of: {})",
+                known.join(", ")
+            ))
+        })
+    }
+}
+
+/// The fixture selected by `HARDWARE_QUERY_SIM`, if set
+pub(crate) fn provider_from_env() -> Result<Option<FixtureProvider>> {
+    let Some(value) = std::env::var_os(SIM_ENV_VAR) else {
+        return Ok(None);
+    };
+    let value = value.to_string_lossy();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match MachineProfile::from_name(&value) {
+        Some(profile) => profile.provider().map(Some),
+        None if std::path::Path::new(value.as_ref()).is_file() => {
+            FixtureProvider::from_file(value.as_ref()).map(Some)
+        }
+        None => Err(HardwareQueryError::invalid_configuration(format!(
+            "{SIM_ENV_VAR}=`{value}` is neither a machine profile nor a snapshot file"
+        ))),
+    }
+}
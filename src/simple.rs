@@ -19,7 +19,7 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
-use crate::{HardwareInfo, Result};
+use crate::{HardwareInfo, Result, StoragePerformanceClass};
 use serde::{Deserialize, Serialize};
 
 /// Simplified system overview with the most commonly needed information
@@ -39,6 +39,15 @@ pub struct SystemOverview {
     pub environment: String,
     /// Overall performance score (0-100)
     pub performance_score: u8,
+    /// CPUs' worth of time allowed by container limits (cgroup quota / cpuset)
+    #[serde(default)]
+    pub cpu_limit: Option<f64>,
+    /// Memory limit imposed by the container in GB
+    #[serde(default)]
+    pub memory_limit_gb: Option<f64>,
+    /// NPUs and TPUs
+    #[serde(default)]
+    pub accelerators: Vec<SimpleAccelerator>,
 }
 
 /// Simplified CPU information
@@ -54,6 +63,27 @@ pub struct SimpleCPU {
     pub vendor: String,
     /// Supports AI acceleration features
     pub ai_capable: bool,
+    /// Instruction set extensions (`avx2`, `avx512f`, `neon`, ...)
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+impl SimpleCPU {
+    /// Check if the CPU supports an instruction set extension
+    ///
+    /// Matching ignores case and `_`, `-` and `.` separators, like
+    /// [`CPUInfo::has_feature`](crate::CPUInfo::has_feature).
+    pub fn has_feature(&self, feature: &str) -> bool {
+        fn normalize(name: &str) -> String {
+            name.chars()
+                .filter(|c| !matches!(c, '_' | '-' | '.'))
+                .collect::<String>()
+                .to_lowercase()
+        }
+
+        let wanted = normalize(feature);
+        self.features.iter().any(|f| normalize(f) == wanted)
+    }
 }
 
 /// Simplified GPU information
@@ -67,6 +97,48 @@ pub struct SimpleGPU {
     pub vendor: String,
     /// Supports hardware acceleration for AI/ML
     pub ai_capable: bool,
+    /// CUDA compute capability (NVIDIA only)
+    #[serde(default)]
+    pub compute_capability: Option<String>,
+    /// Driver version
+    #[serde(default)]
+    pub driver_version: Option<String>,
+}
+
+/// Simplified NPU or TPU information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimpleAccelerator {
+    /// `NPU` or `TPU`
+    pub kind: String,
+    /// Model name
+    pub name: String,
+    /// Vendor (Intel, Qualcomm, Apple, Google, etc.)
+    pub vendor: String,
+    /// Peak performance in TOPS, if known
+    pub tops: Option<f32>,
+    /// Frameworks that can target it (OpenVINO, Core ML, TensorFlow Lite, ...)
+    pub frameworks: Vec<String>,
+    /// Native data types (`int8`, `float16`, ...)
+    pub dtypes: Vec<String>,
+}
+
+impl SimpleAccelerator {
+    /// Check if a framework can target this accelerator
+    pub fn supports_framework(&self, framework: &str) -> bool {
+        self.frameworks
+            .iter()
+            .any(|f| f.to_lowercase().contains(&framework.to_lowercase()))
+    }
+
+    /// Check if the accelerator computes a data type natively
+    pub fn supports_dtype(&self, dtype: &str) -> bool {
+        self.dtypes.iter().any(|d| d.eq_ignore_ascii_case(dtype))
+    }
+
+    /// Check if quantized (`int8` or narrower) models run natively
+    pub fn runs_quantized(&self) -> bool {
+        self.supports_dtype("int8") || self.supports_dtype("int4")
+    }
 }
 
 /// Simplified storage summary
@@ -78,6 +150,9 @@ pub struct SimpleStorage {
     pub available_gb: f64,
     /// Primary drive type (SSD, HDD, NVMe, etc.)
     pub drive_type: String,
+    /// Expected performance of the primary drive
+    #[serde(default)]
+    pub performance_class: StoragePerformanceClass,
     /// Storage health (Good, Warning, Critical)
     pub health: String,
 }
@@ -176,6 +251,23 @@ impl SystemOverview {
         Self::from_hardware_info(hw_info)
     }
 
+    /// Get a quick system overview, reusing cached static hardware data for up to `ttl`
+    ///
+    /// See [`HardwareInfo::query_cached`] for what is cached.
+    pub fn quick_cached(ttl: std::time::Duration) -> Result<Self> {
+        let hw_info = HardwareInfo::query_cached(ttl)?;
+        Self::from_hardware_info(hw_info)
+    }
+
+    /// Get a quick system overview without blocking the async executor
+    ///
+    /// Async counterpart of [`SystemOverview::quick`].
+    #[cfg(feature = "async")]
+    pub async fn quick_async() -> Result<Self> {
+        let hw_info = HardwareInfo::query_async().await?;
+        Self::from_hardware_info(hw_info)
+    }
+
     /// Create a system overview from detailed hardware information
     pub fn from_hardware_info(hw_info: HardwareInfo) -> Result<Self> {
         let cpu = SimpleCPU {
@@ -184,6 +276,7 @@ impl SystemOverview {
             threads: hw_info.cpu().logical_cores(),
             vendor: hw_info.cpu().vendor().to_string(),
             ai_capable: Self::check_cpu_ai_capabilities(&hw_info),
+            features: hw_info.cpu().features().iter().map(|f| f.to_string().to_lowercase()).collect(),
         };
 
         let memory_gb = hw_info.memory().total_gb();
@@ -195,6 +288,8 @@ impl SystemOverview {
                 vram_gb: primary_gpu.memory_gb(),
                 vendor: primary_gpu.vendor().to_string(),
                 ai_capable: Self::check_gpu_ai_capabilities(primary_gpu),
+                compute_capability: primary_gpu.cuda_capability().map(str::to_string),
+                driver_version: primary_gpu.driver_version.clone(),
             })
         } else {
             None
@@ -204,6 +299,31 @@ impl SystemOverview {
         let health = Self::assess_system_health(&hw_info)?;
         let environment = hw_info.virtualization().environment_type.to_string();
         let performance_score = Self::calculate_performance_score(&hw_info);
+        let limits = &hw_info.virtualization().resource_limits;
+        let cpu_limit = limits.cpu_limit_cores();
+        let memory_limit_gb = limits
+            .memory_limit_bytes()
+            .map(|bytes| bytes as f64 / (1024.0 * 1024.0 * 1024.0));
+        let accelerators = hw_info
+            .npus()
+            .iter()
+            .map(|npu| SimpleAccelerator {
+                kind: "NPU".to_string(),
+                name: npu.model_name().to_string(),
+                vendor: npu.vendor().to_string(),
+                tops: npu.tops_performance(),
+                frameworks: npu.supported_frameworks.clone(),
+                dtypes: npu.supported_dtypes(),
+            })
+            .chain(hw_info.tpus().iter().map(|tpu| SimpleAccelerator {
+                kind: "TPU".to_string(),
+                name: tpu.model_name().to_string(),
+                vendor: tpu.vendor().to_string(),
+                tops: tpu.tops_performance(),
+                frameworks: tpu.supported_frameworks.clone(),
+                dtypes: tpu.supported_dtypes.clone(),
+            }))
+            .collect();
 
         Ok(Self {
             cpu,
@@ -213,18 +333,49 @@ impl SystemOverview {
             health,
             environment,
             performance_score,
+            cpu_limit,
+            memory_limit_gb,
+            accelerators,
         })
     }
 
+    /// Combined peak TOPS of the NPUs and TPUs that report it
+    pub fn accelerator_tops(&self) -> f32 {
+        self.accelerators.iter().filter_map(|accelerator| accelerator.tops).sum()
+    }
+
+    /// Number of threads worth running, respecting container CPU limits
+    ///
+    /// Inside a container `cpu.threads` reports the host; this is capped by the
+    /// cgroup quota and cpuset (rounded up, at least 1).
+    pub fn effective_cores(&self) -> u32 {
+        match self.cpu_limit {
+            Some(limit) => (limit.ceil() as u32).clamp(1, self.cpu.threads.max(1)),
+            None => self.cpu.threads,
+        }
+    }
+
+    /// Memory available to this process in GB, respecting container memory limits
+    pub fn effective_memory_gb(&self) -> f64 {
+        match self.memory_limit_gb {
+            Some(limit) => limit.min(self.memory_gb),
+            None => self.memory_gb,
+        }
+    }
+
     /// Check if the system is suitable for AI/ML workloads
     pub fn is_ai_ready(&self) -> bool {
         // Basic AI readiness check
         self.cpu.ai_capable || 
         self.gpu.as_ref().map_or(false, |gpu| gpu.ai_capable) ||
+        !self.accelerators.is_empty() ||
         self.memory_gb >= 8.0
     }
 
     /// Get AI/ML suitability score (0-100)
+    ///
+    /// GPUs, NPUs/TPUs (by TOPS and quantized data type support), CPU
+    /// features and memory each contribute.
     pub fn ai_score(&self) -> u8 {
         let mut score = 0;
 
@@ -242,6 +393,21 @@ impl SystemOverview {
             }
         }
 
+        // NPU/TPU contribution (15 points max), for quantized inference
+        if !self.accelerators.is_empty() {
+            let tops = self.accelerator_tops();
+            score += if tops >= 40.0 {
+                10
+            } else if tops >= 10.0 {
+                7
+            } else {
+                4
+            };
+            if self.accelerators.iter().any(SimpleAccelerator::runs_quantized) {
+                score += 5;
+            }
+        }
+
         // CPU contribution (25 points max)
         if self.cpu.ai_capable {
             score += 15;
@@ -285,7 +451,7 @@ impl SystemOverview {
         }
 
         // Storage recommendations
-        if self.storage.drive_type.to_lowercase().contains("hdd") {
+        if self.storage.performance_class.is_rotational() {
             recommendations.push("Upgrade to SSD for faster data access".to_string());
         }
 
@@ -337,6 +503,7 @@ impl SystemOverview {
                 total_gb: 0.0,
                 available_gb: 0.0,
                 drive_type: "Unknown".to_string(),
+                performance_class: StoragePerformanceClass::Unknown,
                 health: "Unknown".to_string(),
             });
         }
@@ -351,9 +518,18 @@ impl SystemOverview {
 
         // Get primary drive type
         let drive_type = storage_devices[0].drive_type().to_string();
-
-        // Simple health assessment
-        let health = if available_gb / total_gb < 0.1 {
+        let performance_class = Self::primary_storage_class(hw_info);
+
+        // SMART data takes precedence over free-space heuristics
+        let smart_reports: Vec<_> = storage_devices.iter()
+            .filter_map(|device| device.smart_health())
+            .collect();
+
+        let health = if smart_reports.iter().any(|smart| smart.predicted_failure) {
+            "Critical - Disk failure predicted".to_string()
+        } else if smart_reports.iter().any(|smart| smart.is_degraded()) {
+            "Warning - Disk wear or errors detected".to_string()
+        } else if available_gb / total_gb < 0.1 {
             "Critical - Low space".to_string()
         } else if available_gb / total_gb < 0.2 {
             "Warning - Low space".to_string()
@@ -365,10 +541,27 @@ impl SystemOverview {
             total_gb,
             available_gb,
             drive_type,
+            performance_class,
             health,
         })
     }
 
+    /// Performance class of the physical disk behind the first volume
+    fn primary_storage_class(hw_info: &HardwareInfo) -> StoragePerformanceClass {
+        let Some(primary) = hw_info.storage_devices().first() else {
+            return StoragePerformanceClass::Unknown;
+        };
+        if primary.file_system.as_deref().is_some_and(crate::storage::is_network_file_system) {
+            return StoragePerformanceClass::Network;
+        }
+        hw_info
+            .physical_disks()
+            .iter()
+            .find(|disk| disk.volumes().contains(&primary.mount_point))
+            .map(|disk| disk.performance_class())
+            .unwrap_or_else(|| StoragePerformanceClass::from_storage_type(primary.drive_type()))
+    }
+
     fn assess_system_health(hw_info: &HardwareInfo) -> Result<SystemHealth> {
         let mut warnings = Vec::new();
         
@@ -390,6 +583,23 @@ impl SystemOverview {
             TemperatureStatus::Normal
         };
 
+        // Disk health assessment
+        for device in hw_info.storage_devices() {
+            if let Some(smart) = device.smart_health() {
+                if smart.predicted_failure {
+                    warnings.push(format!(
+                        "Disk {} reports imminent failure; back up data and replace it",
+                        device.model()
+                    ));
+                } else if smart.is_degraded() {
+                    warnings.push(format!("Disk {} shows wear or media errors", device.model()));
+                }
+                if smart.temperature_warning {
+                    warnings.push(format!("Disk {} is outside its temperature thresholds", device.model()));
+                }
+            }
+        }
+
         // Power assessment
         let power = if let Some(power_profile) = hw_info.power_profile() {
             if let Some(power_draw) = power_profile.total_power_draw {
@@ -463,12 +673,11 @@ impl SystemOverview {
         }
 
         // Storage score (15 points)
-        let storage_devices = hw_info.storage_devices();
-        if !storage_devices.is_empty() {
-            let storage_type = storage_devices[0].drive_type().to_string().to_lowercase();
-            score += if storage_type.contains("nvme") {
+        if !hw_info.storage_devices().is_empty() {
+            let storage_class = Self::primary_storage_class(hw_info);
+            score += if storage_class.is_nvme() {
                 15
-            } else if storage_type.contains("ssd") {
+            } else if storage_class.is_ssd() {
                 12
             } else {
                 5
@@ -486,34 +695,37 @@ impl SystemOverview {
 // Display implementations for better debugging
 impl std::fmt::Display for HealthStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            HealthStatus::Excellent => write!(f, "Excellent"),
-            HealthStatus::Good => write!(f, "Good"),
-            HealthStatus::Fair => write!(f, "Fair"),
-            HealthStatus::Poor => write!(f, "Poor"),
-            HealthStatus::Critical => write!(f, "Critical"),
-        }
+        let text = match self {
+            HealthStatus::Excellent => "Excellent",
+            HealthStatus::Good => "Good",
+            HealthStatus::Fair => "Fair",
+            HealthStatus::Poor => "Poor",
+            HealthStatus::Critical => "Critical",
+        };
+        f.write_str(&crate::l10n::localize("HealthStatus", text))
     }
 }
 
 impl std::fmt::Display for TemperatureStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            TemperatureStatus::Normal => write!(f, "Normal"),
-            TemperatureStatus::Warm => write!(f, "Warm"),
-            TemperatureStatus::Hot => write!(f, "Hot"),
-            TemperatureStatus::Critical => write!(f, "Critical"),
-        }
+        let text = match self {
+            TemperatureStatus::Normal => "Normal",
+            TemperatureStatus::Warm => "Warm",
+            TemperatureStatus::Hot => "Hot",
+            TemperatureStatus::Critical => "Critical",
+        };
+        f.write_str(&crate::l10n::localize("TemperatureStatus", text))
     }
 }
 
 impl std::fmt::Display for PowerStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            PowerStatus::Low => write!(f, "Low"),
-            PowerStatus::Normal => write!(f, "Normal"),
-            PowerStatus::High => write!(f, "High"),
-            PowerStatus::VeryHigh => write!(f, "Very High"),
-        }
+        let text = match self {
+            PowerStatus::Low => "Low",
+            PowerStatus::Normal => "Normal",
+            PowerStatus::High => "High",
+            PowerStatus::VeryHigh => "Very High",
+        };
+        f.write_str(&crate::l10n::localize("PowerStatus", text))
     }
 }
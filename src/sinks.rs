@@ -0,0 +1,400 @@
+//! Built-in [`MonitoringCallback`] outputs
+//!
+//! These sinks let a [`HardwareMonitor`](crate::HardwareMonitor) run as a
+//! lightweight agent without custom glue code:
+//!
+//! - [`JsonlFileSink`] appends one JSON object per event to a file and
+//!   rotates it once it grows past a size limit
+//! - [`SystemLogSink`] writes to syslog on Unix and the Windows Event Log
+//! - [`WebhookSink`] POSTs each event as JSON to an HTTP endpoint
+//!   (`webhook` feature)
+//!
+//! Every sink skips the periodic `MetricsUpdate` events unless asked for
+//! them, and can drop events below a minimum [`EventSeverity`].
+//!
+//! ```rust,no_run
+//! use hardware_query::{EventSeverity, HardwareMonitor, JsonlFileSink, SystemLogSink};
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let monitor = HardwareMonitor::new();
+//! monitor.add_callback(JsonlFileSink::new("/var/log/hardware/events.jsonl")?.with_max_bytes(10 << 20)).await;
+//! monitor.add_callback(SystemLogSink::new("hardware-query")?.with_min_severity(EventSeverity::Warning)).await;
+//! monitor.start_monitoring().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{EventSeverity, MonitoringCallback, MonitoringEvent, Result};
+use async_trait::async_trait;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+/// Which events a sink passes on
+#[derive(Debug, Clone, Copy)]
+struct EventFilter {
+    min_severity: EventSeverity,
+    metrics_updates: bool,
+}
+
+impl Default for EventFilter {
+    fn default() -> Self {
+        Self {
+            min_severity: EventSeverity::Info,
+            metrics_updates: false,
+        }
+    }
+}
+
+impl EventFilter {
+    fn accepts(&self, event: &MonitoringEvent) -> bool {
+        if matches!(event, MonitoringEvent::MetricsUpdate { .. }) && !self.metrics_updates {
+            return false;
+        }
+        event.severity() >= self.min_severity
+    }
+}
+
+/// JSON record written by the file and webhook sinks
+fn event_record(event: &MonitoringEvent) -> serde_json::Value {
+    let timestamp_ms = event
+        .timestamp()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_millis() as u64)
+        .unwrap_or_default();
+    serde_json::json!({
+        "timestamp_ms": timestamp_ms,
+        "kind": event.kind(),
+        "severity": event.severity().to_string(),
+        "summary": event.summary(),
+        "event": event,
+    })
+}
+
+/// Default size at which [`JsonlFileSink`] rotates its file
+const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Default number of rotated files [`JsonlFileSink`] keeps
+const DEFAULT_MAX_FILES: usize = 5;
+
+/// Appends events to a JSON Lines file, rotating it by size
+///
+/// When the file would grow past the size limit it is renamed to
+/// `<path>.1`, older files shift to `<path>.2` and so on, and the oldest
+/// beyond the retention count is deleted.
+pub struct JsonlFileSink {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    filter: EventFilter,
+    file: Mutex<Option<(File, u64)>>,
+}
+
+impl JsonlFileSink {
+    /// Append to `path`, creating it and its directory if needed
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let file = Self::open(&path)?;
+        Ok(Self {
+            path,
+            max_bytes: DEFAULT_MAX_BYTES,
+            max_files: DEFAULT_MAX_FILES,
+            filter: EventFilter::default(),
+            file: Mutex::new(Some(file)),
+        })
+    }
+
+    /// Rotate once the file would exceed `bytes` (10 MiB by default)
+    pub fn with_max_bytes(mut self, bytes: u64) -> Self {
+        self.max_bytes = bytes;
+        self
+    }
+
+    /// Keep `files` rotated files besides the active one (5 by default)
+    pub fn with_max_files(mut self, files: usize) -> Self {
+        self.max_files = files;
+        self
+    }
+
+    /// Drop events less severe than `severity`
+    pub fn with_min_severity(mut self, severity: EventSeverity) -> Self {
+        self.filter.min_severity = severity;
+        self
+    }
+
+    /// Also write the periodic `MetricsUpdate` events (off by default)
+    pub fn with_metrics_updates(mut self, enabled: bool) -> Self {
+        self.filter.metrics_updates = enabled;
+        self
+    }
+
+    /// Get the active file's path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn open(path: &Path) -> std::io::Result<(File, u64)> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok((file, size))
+    }
+
+    fn rotated(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&self) -> std::io::Result<()> {
+        if self.max_files == 0 {
+            return fs::remove_file(&self.path);
+        }
+        let _ = fs::remove_file(self.rotated(self.max_files));
+        for index in (1..self.max_files).rev() {
+            let from = self.rotated(index);
+            if from.exists() {
+                fs::rename(&from, self.rotated(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated(1))
+    }
+
+    fn write_line(&self, line: &str) -> std::io::Result<()> {
+        let mut file = self.file.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let needed = line.len() as u64 + 1;
+        if file.as_ref().is_some_and(|(_, size)| *size > 0 && size + needed > self.max_bytes) {
+            *file = None;
+            self.rotate()?;
+        }
+        if file.is_none() {
+            *file = Some(Self::open(&self.path)?);
+        }
+        if let Some((handle, size)) = file.as_mut() {
+            writeln!(handle, "{line}")?;
+            *size += needed;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MonitoringCallback for JsonlFileSink {
+    async fn on_event(&self, event: &MonitoringEvent) {
+        if !self.filter.accepts(event) {
+            return;
+        }
+        // A full disk or a removed directory must not stop the monitor
+        let _ = self.write_line(&event_record(event).to_string());
+    }
+}
+
+/// Writes events to syslog (Linux, macOS) or the Windows Event Log
+///
+/// Event severity maps to the `LOG_INFO` / `LOG_WARNING` / `LOG_CRIT`
+/// priorities and to information / warning / error Event Log entries.
+pub struct SystemLogSink {
+    filter: EventFilter,
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    _ident: std::ffi::CString,
+    #[cfg(target_os = "windows")]
+    source: usize,
+}
+
+impl SystemLogSink {
+    /// Log under `ident` (the syslog tag or Event Log source name)
+    pub fn new(ident: &str) -> Result<Self> {
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        {
+            let ident = std::ffi::CString::new(ident)
+                .map_err(|_| crate::HardwareQueryError::invalid_configuration("syslog ident contains a NUL byte"))?;
+            // openlog keeps the pointer, so the CString lives as long as the sink
+            unsafe { libc::openlog(ident.as_ptr(), libc::LOG_PID, libc::LOG_DAEMON) };
+            Ok(Self {
+                filter: EventFilter::default(),
+                _ident: ident,
+            })
+        }
+        #[cfg(target_os = "windows")]
+        {
+            use windows::core::{HSTRING, PCWSTR};
+            use windows::Win32::System::EventLog::RegisterEventSourceW;
+
+            let name = HSTRING::from(ident);
+            let handle = unsafe { RegisterEventSourceW(PCWSTR::null(), PCWSTR(name.as_ptr())) }
+                .map_err(|error| crate::HardwareQueryError::monitoring_error(format!("RegisterEventSource failed: {error}")))?;
+            Ok(Self {
+                filter: EventFilter::default(),
+                source: handle.0 as usize,
+            })
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+        {
+            let _ = ident;
+            Err(crate::HardwareQueryError::platform_not_supported("no system log on this platform"))
+        }
+    }
+
+    /// Drop events less severe than `severity`
+    pub fn with_min_severity(mut self, severity: EventSeverity) -> Self {
+        self.filter.min_severity = severity;
+        self
+    }
+
+    /// Also log the periodic `MetricsUpdate` events (off by default)
+    pub fn with_metrics_updates(mut self, enabled: bool) -> Self {
+        self.filter.metrics_updates = enabled;
+        self
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn write(&self, severity: EventSeverity, message: &str) {
+        let priority = match severity {
+            EventSeverity::Info => libc::LOG_INFO,
+            EventSeverity::Warning => libc::LOG_WARNING,
+            EventSeverity::Critical => libc::LOG_CRIT,
+        };
+        let Ok(message) = std::ffi::CString::new(message.replace('\0', "")) else {
+            return;
+        };
+        unsafe { libc::syslog(priority, c"%s".as_ptr(), message.as_ptr()) };
+    }
+
+    #[cfg(target_os = "windows")]
+    fn write(&self, severity: EventSeverity, message: &str) {
+        use windows::core::{HSTRING, PCWSTR};
+        use windows::Win32::Foundation::HANDLE;
+        use windows::Win32::System::EventLog::{
+            ReportEventW, EVENTLOG_ERROR_TYPE, EVENTLOG_INFORMATION_TYPE, EVENTLOG_WARNING_TYPE,
+        };
+
+        let event_type = match severity {
+            EventSeverity::Info => EVENTLOG_INFORMATION_TYPE,
+            EventSeverity::Warning => EVENTLOG_WARNING_TYPE,
+            EventSeverity::Critical => EVENTLOG_ERROR_TYPE,
+        };
+        let message = HSTRING::from(message);
+        let strings = [PCWSTR(message.as_ptr())];
+        let _ = unsafe {
+            ReportEventW(HANDLE(self.source as *mut _), event_type, 0, 0, None, 0, Some(&strings), None)
+        };
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    fn write(&self, _severity: EventSeverity, _message: &str) {}
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl Drop for SystemLogSink {
+    fn drop(&mut self) {
+        // The ident is about to be freed; syslog must stop using it
+        unsafe { libc::closelog() };
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for SystemLogSink {
+    fn drop(&mut self) {
+        use windows::Win32::Foundation::HANDLE;
+        use windows::Win32::System::EventLog::DeregisterEventSource;
+
+        let _ = unsafe { DeregisterEventSource(HANDLE(self.source as *mut _)) };
+    }
+}
+
+#[async_trait]
+impl MonitoringCallback for SystemLogSink {
+    async fn on_event(&self, event: &MonitoringEvent) {
+        if self.filter.accepts(event) {
+            self.write(event.severity(), &format!("[{}] {}", event.kind(), event.summary()));
+        }
+    }
+}
+
+/// POSTs each event as JSON to an HTTP endpoint
+///
+/// The body is the same object [`JsonlFileSink`] writes per line. Failed
+/// deliveries are counted but not retried.
+#[cfg(feature = "webhook")]
+pub struct WebhookSink {
+    url: String,
+    client: reqwest::Client,
+    headers: Vec<(String, String)>,
+    filter: EventFilter,
+    failures: std::sync::atomic::AtomicU64,
+}
+
+#[cfg(feature = "webhook")]
+impl WebhookSink {
+    /// Deliver events to `url` with a 10 second timeout
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_timeout(url, std::time::Duration::from_secs(10))
+    }
+
+    /// Deliver events to `url`, giving up on each request after `timeout`
+    pub fn with_timeout(url: impl Into<String>, timeout: std::time::Duration) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::Client::builder()
+                .timeout(timeout)
+                .build()
+                .unwrap_or_default(),
+            headers: Vec::new(),
+            filter: EventFilter::default(),
+            failures: std::sync::atomic::AtomicU64::new(0),
+        }
+    }
+
+    /// Send a header with every request, e.g. `Authorization`
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Drop events less severe than `severity`
+    pub fn with_min_severity(mut self, severity: EventSeverity) -> Self {
+        self.filter.min_severity = severity;
+        self
+    }
+
+    /// Also deliver the periodic `MetricsUpdate` events (off by default)
+    pub fn with_metrics_updates(mut self, enabled: bool) -> Self {
+        self.filter.metrics_updates = enabled;
+        self
+    }
+
+    /// Get the endpoint URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get the number of events that could not be delivered
+    pub fn failed_deliveries(&self) -> u64 {
+        self.failures.load(std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
+#[cfg(feature = "webhook")]
+#[async_trait]
+impl MonitoringCallback for WebhookSink {
+    async fn on_event(&self, event: &MonitoringEvent) {
+        if !self.filter.accepts(event) {
+            return;
+        }
+        let mut request = self.client.post(&self.url).json(&event_record(event));
+        for (name, value) in &self.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        let delivered = match request.send().await {
+            Ok(response) => response.status().is_success(),
+            Err(_) => false,
+        };
+        if !delivered {
+            self.failures.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+}
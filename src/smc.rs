@@ -0,0 +1,402 @@
+//! macOS System Management Controller sensors
+//!
+//! macOS has no hwmon or WMI equivalent for temperatures and fans; they live
+//! in the SMC, which IOKit exposes through the `AppleSMC` user client. With
+//! the `smc` feature, [`ThermalInfo`](crate::ThermalInfo) reads the SMC
+//! temperature keys (`TC0P`, `TG0P`, ... on Intel Macs, `Tp09`, `Tg05`, ...
+//! on Apple Silicon) and the fan tachometers (`F0Ac`, `F0Mx`), and
+//! [`PowerProfile`](crate::PowerProfile) fills CPU, GPU and system power from
+//! [`PowerRails`].
+//!
+//! Intel Macs publish power rails as SMC keys (`PCPC`, `PCPG`, `PSTR`). Apple
+//! Silicon doesn't, so there the rails come from `powermetrics`, which reads
+//! the IOReport energy counters but only runs as root. Without the feature,
+//! or on other platforms, [`PowerRails::query`] returns `None`.
+
+use serde::{Deserialize, Serialize};
+
+/// Power draw of the main rails in watts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PowerRails {
+    /// CPU cores
+    pub cpu_watts: Option<f32>,
+    /// Integrated GPU
+    pub gpu_watts: Option<f32>,
+    /// Apple Neural Engine
+    pub ane_watts: Option<f32>,
+    /// Whole SoC / CPU package
+    pub package_watts: Option<f32>,
+    /// Whole system, measured at the DC input
+    pub system_watts: Option<f32>,
+}
+
+impl PowerRails {
+    /// Read the power rails from the SMC or `powermetrics`
+    ///
+    /// Returns `None` when no rail could be read.
+    pub fn query() -> Option<Self> {
+        #[cfg(all(target_os = "macos", feature = "smc"))]
+        {
+            let rails = iokit::Smc::open()
+                .map(|smc| smc.power_rails())
+                .filter(|rails| !rails.is_empty())
+                .or_else(powermetrics)?;
+            Some(rails)
+        }
+        #[cfg(not(all(target_os = "macos", feature = "smc")))]
+        {
+            None
+        }
+    }
+
+    /// Parse the text output of `powermetrics --samplers cpu_power,gpu_power`
+    ///
+    /// Understands the Apple Silicon lines (`CPU Power: 1234 mW`) and the
+    /// Intel package line (`... package power (CPUs+GT+SA): 3.45W`).
+    ///
+    /// ```rust
+    /// use hardware_query::PowerRails;
+    ///
+    /// let rails = PowerRails::from_powermetrics("CPU Power: 1500 mW\nGPU Power: 250 mW\n");
+    /// assert_eq!(rails.cpu_watts, Some(1.5));
+    /// assert_eq!(rails.total(), Some(1.75));
+    /// ```
+    pub fn from_powermetrics(output: &str) -> Self {
+        let mut rails = Self::default();
+        for line in output.lines() {
+            let Some((label, value)) = line.rsplit_once(':') else {
+                continue;
+            };
+            let Some(watts) = parse_watts(value) else {
+                continue;
+            };
+            let label = label.trim();
+            match label {
+                "CPU Power" => rails.cpu_watts = Some(watts),
+                "GPU Power" => rails.gpu_watts = Some(watts),
+                "ANE Power" => rails.ane_watts = Some(watts),
+                _ if label.starts_with("Combined Power") || label.contains("package power") => {
+                    rails.package_watts = Some(watts)
+                }
+                _ => {}
+            }
+        }
+        rails
+    }
+
+    /// Best estimate of total draw: system, then package, then the sum of the rails
+    pub fn total(&self) -> Option<f32> {
+        self.system_watts.or(self.package_watts).or_else(|| {
+            let rails = [self.cpu_watts, self.gpu_watts, self.ane_watts];
+            rails.iter().any(Option::is_some).then(|| rails.iter().flatten().sum())
+        })
+    }
+
+    /// Check if no rail was read
+    pub fn is_empty(&self) -> bool {
+        self.total().is_none()
+    }
+}
+
+/// "1234 mW" or "3.45W" in watts
+fn parse_watts(value: &str) -> Option<f32> {
+    let value = value.trim();
+    if let Some(milliwatts) = value.strip_suffix("mW") {
+        return milliwatts.trim().parse::<f32>().ok().map(|mw| mw / 1000.0);
+    }
+    value.strip_suffix('W')?.trim().parse().ok()
+}
+
+/// Rails from a single 200 ms `powermetrics` sample; needs root
+#[cfg(all(target_os = "macos", feature = "smc"))]
+fn powermetrics() -> Option<PowerRails> {
+    // SAFETY: geteuid has no preconditions and cannot fail
+    if unsafe { libc::geteuid() } != 0 {
+        return None;
+    }
+    let output = crate::probe::Command::new("powermetrics")
+        .args(["--samplers", "cpu_power,gpu_power", "-n", "1", "-i", "200"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let rails = PowerRails::from_powermetrics(&String::from_utf8_lossy(&output.stdout));
+    (!rails.is_empty()).then_some(rails)
+}
+
+#[cfg(all(target_os = "macos", feature = "smc"))]
+pub(crate) use iokit::{smc_fans, smc_sensors};
+
+#[cfg(all(target_os = "macos", feature = "smc"))]
+mod iokit {
+    use super::PowerRails;
+    use crate::{FanInfo, ThermalSensor};
+    use std::ffi::{c_char, c_void};
+
+    type MachPort = u32;
+    type KernReturn = i32;
+
+    #[link(name = "IOKit", kind = "framework")]
+    unsafe extern "C" {
+        fn IOServiceMatching(name: *const c_char) -> *mut c_void;
+        fn IOServiceGetMatchingService(main_port: MachPort, matching: *mut c_void) -> MachPort;
+        fn IOServiceOpen(service: MachPort, owning_task: MachPort, kind: u32, connect: *mut MachPort) -> KernReturn;
+        fn IOServiceClose(connect: MachPort) -> KernReturn;
+        fn IOObjectRelease(object: MachPort) -> KernReturn;
+        fn IOConnectCallStructMethod(
+            connection: MachPort,
+            selector: u32,
+            input: *const c_void,
+            input_size: usize,
+            output: *mut c_void,
+            output_size: *mut usize,
+        ) -> KernReturn;
+    }
+
+    unsafe extern "C" {
+        static mach_task_self_: MachPort;
+    }
+
+    /// `kIOMainPortDefault`
+    const MAIN_PORT_DEFAULT: MachPort = 0;
+    /// Selector of the AppleSMC user client's struct method
+    const KERNEL_INDEX_SMC: u32 = 2;
+    const SMC_CMD_READ_BYTES: u8 = 5;
+    const SMC_CMD_READ_KEYINFO: u8 = 9;
+
+    // The layouts mirror the kernel's; most fields are never read back
+    #[repr(C)]
+    #[derive(Default, Clone, Copy)]
+    #[allow(dead_code)]
+    struct KeyDataVersion {
+        major: u8,
+        minor: u8,
+        build: u8,
+        reserved: u8,
+        release: u16,
+    }
+
+    #[repr(C)]
+    #[derive(Default, Clone, Copy)]
+    #[allow(dead_code)]
+    struct KeyDataPowerLimit {
+        version: u16,
+        length: u16,
+        cpu_limit: u32,
+        gpu_limit: u32,
+        memory_limit: u32,
+    }
+
+    #[repr(C)]
+    #[derive(Default, Clone, Copy)]
+    #[allow(dead_code)]
+    struct KeyInfo {
+        data_size: u32,
+        data_type: u32,
+        data_attributes: u8,
+    }
+
+    /// `SMCKeyData_t` exchanged with the AppleSMC user client
+    #[repr(C)]
+    #[derive(Default, Clone, Copy)]
+    #[allow(dead_code)]
+    struct KeyData {
+        key: u32,
+        version: KeyDataVersion,
+        power_limit: KeyDataPowerLimit,
+        key_info: KeyInfo,
+        result: u8,
+        status: u8,
+        data8: u8,
+        data32: u32,
+        bytes: [u8; 32],
+    }
+
+    /// Temperature keys with their display names and sensor types
+    ///
+    /// Missing keys read as errors or zero and are skipped.
+    const TEMPERATURE_KEYS: &[(&str, &str, &str)] = &[
+        // Intel Macs
+        ("TC0P", "CPU Proximity", "CPU"),
+        ("TC0D", "CPU Die", "CPU"),
+        ("TC0E", "CPU Die (Virtual)", "CPU"),
+        ("TCXC", "CPU PECI", "CPU"),
+        ("TG0P", "GPU Proximity", "GPU"),
+        ("TG0D", "GPU Die", "GPU"),
+        ("Tm0P", "Memory Proximity", "Memory"),
+        ("TH0P", "SSD Proximity", "Storage"),
+        ("Th0H", "Heatsink", "System"),
+        ("Ts0P", "Palm Rest", "System"),
+        ("TA0P", "Ambient", "Ambient"),
+        ("TB0T", "Battery", "Battery"),
+        // Apple Silicon
+        ("Tp01", "CPU Performance Core 1", "CPU"),
+        ("Tp05", "CPU Performance Core 2", "CPU"),
+        ("Tp09", "CPU Performance Core 3", "CPU"),
+        ("Tp0D", "CPU Performance Core 4", "CPU"),
+        ("Tp0H", "CPU Performance Core 5", "CPU"),
+        ("Tp0L", "CPU Performance Core 6", "CPU"),
+        ("Tp0P", "CPU Performance Core 7", "CPU"),
+        ("Tp0T", "CPU Performance Core 8", "CPU"),
+        ("Tp0X", "CPU Performance Core 9", "CPU"),
+        ("Tp0b", "CPU Performance Core 10", "CPU"),
+        ("Te05", "CPU Efficiency Core 1", "CPU"),
+        ("Te0L", "CPU Efficiency Core 2", "CPU"),
+        ("Te0P", "CPU Efficiency Core 3", "CPU"),
+        ("Te0S", "CPU Efficiency Core 4", "CPU"),
+        ("Tg05", "GPU Cluster 1", "GPU"),
+        ("Tg0D", "GPU Cluster 2", "GPU"),
+        ("Tg0L", "GPU Cluster 3", "GPU"),
+        ("Tg0T", "GPU Cluster 4", "GPU"),
+    ];
+
+    /// Open connection to the AppleSMC user client
+    pub(super) struct Smc {
+        connection: MachPort,
+    }
+
+    impl Smc {
+        pub(super) fn open() -> Option<Self> {
+            // SAFETY: the service name is NUL-terminated; the matching dictionary
+            // is consumed by IOServiceGetMatchingService
+            let service = unsafe {
+                IOServiceGetMatchingService(MAIN_PORT_DEFAULT, IOServiceMatching(c"AppleSMC".as_ptr()))
+            };
+            if service == 0 {
+                return None;
+            }
+            let mut connection = 0;
+            // SAFETY: service is a valid io_service_t, released right after opening
+            let result = unsafe {
+                let result = IOServiceOpen(service, mach_task_self_, 0, &mut connection);
+                IOObjectRelease(service);
+                result
+            };
+            (result == 0 && connection != 0).then_some(Self { connection })
+        }
+
+        fn call(&self, input: &KeyData) -> Option<KeyData> {
+            let mut output = KeyData::default();
+            let mut output_size = std::mem::size_of::<KeyData>();
+            // SAFETY: both buffers are SMCKeyData_t sized structs
+            let result = unsafe {
+                IOConnectCallStructMethod(
+                    self.connection,
+                    KERNEL_INDEX_SMC,
+                    input as *const KeyData as *const c_void,
+                    std::mem::size_of::<KeyData>(),
+                    &mut output as *mut KeyData as *mut c_void,
+                    &mut output_size,
+                )
+            };
+            (result == 0 && output.result == 0).then_some(output)
+        }
+
+        /// Read a key and decode it as a number
+        fn read(&self, key: &str) -> Option<f32> {
+            let key = fourcc(key)?;
+            let info = self.call(&KeyData {
+                key,
+                data8: SMC_CMD_READ_KEYINFO,
+                ..KeyData::default()
+            })?;
+            let data = self.call(&KeyData {
+                key,
+                key_info: info.key_info,
+                data8: SMC_CMD_READ_BYTES,
+                ..KeyData::default()
+            })?;
+            let size = (info.key_info.data_size as usize).min(data.bytes.len());
+            decode(&info.key_info.data_type.to_be_bytes(), &data.bytes[..size])
+        }
+
+        fn sensors(&self) -> Vec<ThermalSensor> {
+            TEMPERATURE_KEYS
+                .iter()
+                .filter_map(|&(key, name, sensor_type)| {
+                    // Absent sensors read 0 or a sentinel like -127
+                    let temperature = self.read(key).filter(|t| *t > 0.0 && *t < 150.0)?;
+                    Some(ThermalSensor {
+                        name: name.to_string(),
+                        temperature,
+                        critical_temperature: None,
+                        max_temperature: None,
+                        sensor_type: sensor_type.to_string(),
+                        temperature_history: Vec::new(),
+                    })
+                })
+                .collect()
+        }
+
+        fn fans(&self) -> Vec<FanInfo> {
+            let count = self.read("FNum").unwrap_or(0.0) as u32;
+            (0..count)
+                .filter_map(|index| {
+                    let speed = self.read(&format!("F{index}Ac"))?;
+                    let min = self.read(&format!("F{index}Mn")).unwrap_or(0.0);
+                    let max = self.read(&format!("F{index}Mx")).filter(|max| *max > min);
+                    Some(FanInfo {
+                        name: format!("Fan {}", index + 1),
+                        speed_rpm: speed.max(0.0) as u32,
+                        max_speed_rpm: max.map(|max| max as u32),
+                        speed_percent: max.map(|max| (speed / max * 100.0).clamp(0.0, 100.0)),
+                        // FanController has no SMC backend
+                        controllable: false,
+                        fan_curve: None,
+                    })
+                })
+                .collect()
+        }
+
+        pub(super) fn power_rails(&self) -> PowerRails {
+            let watts = |key: &str| self.read(key).filter(|w| *w > 0.0);
+            PowerRails {
+                cpu_watts: watts("PCPC"),
+                gpu_watts: watts("PCPG"),
+                ane_watts: None,
+                package_watts: watts("PCPT").or_else(|| watts("PC0C")),
+                system_watts: watts("PSTR").or_else(|| watts("PDTR")),
+            }
+        }
+    }
+
+    impl Drop for Smc {
+        fn drop(&mut self) {
+            // SAFETY: the connection was opened by IOServiceOpen and is closed once
+            unsafe {
+                IOServiceClose(self.connection);
+            }
+        }
+    }
+
+    pub(crate) fn smc_sensors() -> Vec<ThermalSensor> {
+        Smc::open().map(|smc| smc.sensors()).unwrap_or_default()
+    }
+
+    pub(crate) fn smc_fans() -> Vec<FanInfo> {
+        Smc::open().map(|smc| smc.fans()).unwrap_or_default()
+    }
+
+    fn fourcc(key: &str) -> Option<u32> {
+        let bytes: [u8; 4] = key.as_bytes().try_into().ok()?;
+        Some(u32::from_be_bytes(bytes))
+    }
+
+    /// Decode SMC data types: `flt `, `ui8 `/`ui16`/`ui32` and the fixed
+    /// point `fpXY` (unsigned) and `spXY` (signed) with `Y` fraction bits
+    fn decode(data_type: &[u8; 4], bytes: &[u8]) -> Option<f32> {
+        match data_type {
+            b"flt " => Some(f32::from_le_bytes(bytes.get(..4)?.try_into().ok()?)),
+            b"ui8 " | b"ui16" | b"ui32" => {
+                let value = bytes.iter().take(4).fold(0u32, |acc, byte| (acc << 8) | *byte as u32);
+                Some(value as f32)
+            }
+            [kind @ (b'f' | b's'), b'p', _, fraction] => {
+                let fraction_bits = (*fraction as char).to_digit(16)?;
+                let raw = u16::from_be_bytes(bytes.get(..2)?.try_into().ok()?);
+                let value = if *kind == b's' { raw as i16 as f32 } else { raw as f32 };
+                Some(value / (1u32 << fraction_bits) as f32)
+            }
+            _ => None,
+        }
+    }
+}
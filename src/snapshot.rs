@@ -0,0 +1,270 @@
+//! Persistent history of hardware snapshots
+//!
+//! [`SnapshotStore`] keeps timestamped [`HardwareInfo`] snapshots so fleet
+//! tooling can answer "when did this machine's drivers or hardware change?".
+//! Snapshots are stored as JSON, either one file per snapshot in a directory
+//! or, with the `sqlite` feature, as rows of a single SQLite database.
+//!
+//! ```no_run
+//! use hardware_query::{HardwareInfo, SnapshotStore};
+//!
+//! # fn main() -> hardware_query::Result<()> {
+//! let store = SnapshotStore::open_dir("/var/lib/hardware-query")?;
+//! store.save(&HardwareInfo::query()?)?;
+//!
+//! let timestamps = store.list()?;
+//! if let [.., previous, latest] = timestamps.as_slice() {
+//!     print!("{}", store.diff(*previous, *latest)?);
+//! }
+//! store.prune(30)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{HardwareDiff, HardwareInfo, HardwareQueryError, Result};
+use std::path::{Path, PathBuf};
+
+/// Timestamped [`HardwareInfo`] snapshots on disk
+///
+/// Snapshots are keyed by [`HardwareInfo::timestamp`] (seconds since the
+/// Unix epoch); saving a snapshot with an existing timestamp replaces it.
+#[derive(Debug)]
+pub struct SnapshotStore {
+    backend: Backend,
+}
+
+#[derive(Debug)]
+enum Backend {
+    Directory(PathBuf),
+    #[cfg(feature = "sqlite")]
+    Sqlite(std::sync::Mutex<rusqlite::Connection>),
+}
+
+impl SnapshotStore {
+    /// Open a store that keeps one JSON file per snapshot in `path`
+    ///
+    /// The directory is created if it does not exist.
+    pub fn open_dir(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        std::fs::create_dir_all(path)?;
+        Ok(Self {
+            backend: Backend::Directory(path.to_path_buf()),
+        })
+    }
+
+    /// Open a store backed by the SQLite database at `path`
+    ///
+    /// The database and its `snapshots` table are created if needed.
+    #[cfg(feature = "sqlite")]
+    pub fn open_sqlite(path: impl AsRef<Path>) -> Result<Self> {
+        let connection = rusqlite::Connection::open(path)?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS snapshots (
+                 timestamp INTEGER PRIMARY KEY,
+                 data TEXT NOT NULL
+             )",
+        )?;
+        Ok(Self {
+            backend: Backend::Sqlite(std::sync::Mutex::new(connection)),
+        })
+    }
+
+    /// Store a snapshot and return its timestamp
+    pub fn save(&self, info: &HardwareInfo) -> Result<u64> {
+        let json = info.to_json_compact()?;
+        match &self.backend {
+            Backend::Directory(dir) => {
+                // Write then rename so readers never see a partial file
+                let path = snapshot_path(dir, info.timestamp);
+                let partial = path.with_extension("json.tmp");
+                std::fs::write(&partial, json)?;
+                std::fs::rename(&partial, &path)?;
+            }
+            #[cfg(feature = "sqlite")]
+            Backend::Sqlite(connection) => {
+                lock(connection)?.execute(
+                    "INSERT OR REPLACE INTO snapshots (timestamp, data) VALUES (?1, ?2)",
+                    rusqlite::params![to_sql(info.timestamp)?, json],
+                )?;
+            }
+        }
+        Ok(info.timestamp)
+    }
+
+    /// Query the current hardware and store it
+    pub fn capture(&self) -> Result<HardwareInfo> {
+        let info = HardwareInfo::query()?;
+        self.save(&info)?;
+        Ok(info)
+    }
+
+    /// Timestamps of all stored snapshots, oldest first
+    pub fn list(&self) -> Result<Vec<u64>> {
+        let mut timestamps = match &self.backend {
+            Backend::Directory(dir) => {
+                let mut timestamps = Vec::new();
+                for entry in std::fs::read_dir(dir)? {
+                    let path = entry?.path();
+                    if path.extension().is_some_and(|ext| ext == "json") {
+                        if let Some(timestamp) = path
+                            .file_stem()
+                            .and_then(|stem| stem.to_str())
+                            .and_then(|stem| stem.parse().ok())
+                        {
+                            timestamps.push(timestamp);
+                        }
+                    }
+                }
+                timestamps
+            }
+            #[cfg(feature = "sqlite")]
+            Backend::Sqlite(connection) => {
+                let connection = lock(connection)?;
+                let mut statement = connection.prepare("SELECT timestamp FROM snapshots")?;
+                let rows = statement.query_map([], |row| row.get::<_, i64>(0))?;
+                rows.map(|row| Ok(row? as u64)).collect::<Result<Vec<u64>>>()?
+            }
+        };
+        timestamps.sort_unstable();
+        Ok(timestamps)
+    }
+
+    /// Number of stored snapshots
+    pub fn len(&self) -> Result<usize> {
+        Ok(self.list()?.len())
+    }
+
+    /// Check if the store holds no snapshots
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.list()?.is_empty())
+    }
+
+    /// Load the snapshot taken at `timestamp`
+    pub fn load(&self, timestamp: u64) -> Result<HardwareInfo> {
+        let json = match &self.backend {
+            Backend::Directory(dir) => match std::fs::read_to_string(snapshot_path(dir, timestamp)) {
+                Ok(json) => Some(json),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => return Err(e.into()),
+            },
+            #[cfg(feature = "sqlite")]
+            Backend::Sqlite(connection) => {
+                use rusqlite::OptionalExtension;
+                lock(connection)?
+                    .query_row(
+                        "SELECT data FROM snapshots WHERE timestamp = ?1",
+                        [to_sql(timestamp)?],
+                        |row| row.get::<_, String>(0),
+                    )
+                    .optional()?
+            }
+        };
+        match json {
+            Some(json) => HardwareInfo::from_json(&json),
+            None => Err(HardwareQueryError::device_not_found(format!(
+                "No snapshot with timestamp {timestamp}"
+            ))),
+        }
+    }
+
+    /// Load the most recent snapshot, if any
+    pub fn latest(&self) -> Result<Option<HardwareInfo>> {
+        match self.list()?.last() {
+            Some(&timestamp) => self.load(timestamp).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Delete the snapshot taken at `timestamp`, returning whether it existed
+    pub fn remove(&self, timestamp: u64) -> Result<bool> {
+        match &self.backend {
+            Backend::Directory(dir) => match std::fs::remove_file(snapshot_path(dir, timestamp)) {
+                Ok(()) => Ok(true),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+                Err(e) => Err(e.into()),
+            },
+            #[cfg(feature = "sqlite")]
+            Backend::Sqlite(connection) => {
+                let removed = lock(connection)?.execute(
+                    "DELETE FROM snapshots WHERE timestamp = ?1",
+                    [to_sql(timestamp)?],
+                )?;
+                Ok(removed > 0)
+            }
+        }
+    }
+
+    /// Keep the `keep` most recent snapshots and delete the rest
+    ///
+    /// Returns the number of snapshots deleted.
+    pub fn prune(&self, keep: usize) -> Result<usize> {
+        let timestamps = self.list()?;
+        let excess = timestamps.len().saturating_sub(keep);
+        self.remove_all(&timestamps[..excess])
+    }
+
+    /// Delete snapshots taken before `timestamp`
+    ///
+    /// Returns the number of snapshots deleted.
+    pub fn prune_before(&self, timestamp: u64) -> Result<usize> {
+        let timestamps = self.list()?;
+        let older = timestamps.partition_point(|&t| t < timestamp);
+        self.remove_all(&timestamps[..older])
+    }
+
+    fn remove_all(&self, timestamps: &[u64]) -> Result<usize> {
+        let mut removed = 0;
+        for &timestamp in timestamps {
+            if self.remove(timestamp)? {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Compare the snapshots taken at `before` and `after`
+    pub fn diff(&self, before: u64, after: u64) -> Result<HardwareDiff> {
+        Ok(self.load(before)?.diff(&self.load(after)?))
+    }
+
+    /// Changes between each pair of consecutive snapshots, oldest first
+    ///
+    /// Pairs without changes are skipped, so the result reads as a log of
+    /// when the machine's configuration changed.
+    pub fn history(&self) -> Result<Vec<HardwareDiff>> {
+        let mut changes = Vec::new();
+        let mut previous: Option<HardwareInfo> = None;
+        for timestamp in self.list()? {
+            let current = self.load(timestamp)?;
+            if let Some(previous) = &previous {
+                let diff = previous.diff(&current);
+                if !diff.is_empty() {
+                    changes.push(diff);
+                }
+            }
+            previous = Some(current);
+        }
+        Ok(changes)
+    }
+}
+
+fn snapshot_path(dir: &Path, timestamp: u64) -> PathBuf {
+    dir.join(format!("{timestamp}.json"))
+}
+
+#[cfg(feature = "sqlite")]
+fn lock(
+    connection: &std::sync::Mutex<rusqlite::Connection>,
+) -> Result<std::sync::MutexGuard<'_, rusqlite::Connection>> {
+    connection
+        .lock()
+        .map_err(|_| HardwareQueryError::unknown("Snapshot database lock poisoned"))
+}
+
+/// SQLite integers are signed
+#[cfg(feature = "sqlite")]
+fn to_sql(timestamp: u64) -> Result<i64> {
+    i64::try_from(timestamp).map_err(|_| {
+        HardwareQueryError::invalid_configuration(format!("Timestamp {timestamp} out of range"))
+    })
+}
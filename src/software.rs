@@ -0,0 +1,312 @@
+//! Detection of installed GPU compute and AI runtimes
+//!
+//! Hardware support alone does not mean a framework can use an accelerator:
+//! an NVIDIA GPU is only useful to TensorFlow with the CUDA toolkit and cuDNN
+//! installed. This module looks for the common runtimes in their standard
+//! install locations and reports their versions.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::probe::Command;
+
+/// GPU compute / AI runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AIRuntime {
+    /// NVIDIA CUDA toolkit
+    CUDA,
+    /// NVIDIA cuDNN
+    CuDNN,
+    /// NVIDIA TensorRT
+    TensorRT,
+    /// AMD ROCm
+    ROCm,
+    /// Intel OpenVINO
+    OpenVINO,
+    /// Microsoft DirectML (Windows)
+    DirectML,
+    /// Apple Metal Performance Shaders (macOS)
+    MetalPerformanceShaders,
+}
+
+impl std::fmt::Display for AIRuntime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AIRuntime::CUDA => write!(f, "CUDA"),
+            AIRuntime::CuDNN => write!(f, "cuDNN"),
+            AIRuntime::TensorRT => write!(f, "TensorRT"),
+            AIRuntime::ROCm => write!(f, "ROCm"),
+            AIRuntime::OpenVINO => write!(f, "OpenVINO"),
+            AIRuntime::DirectML => write!(f, "DirectML"),
+            AIRuntime::MetalPerformanceShaders => write!(f, "Metal Performance Shaders"),
+        }
+    }
+}
+
+/// An installed runtime
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeVersion {
+    /// Which runtime
+    pub runtime: AIRuntime,
+    /// Version string (if it could be determined)
+    pub version: Option<String>,
+    /// Install location
+    pub path: Option<PathBuf>,
+}
+
+/// Installed GPU compute and AI runtimes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SoftwareStack {
+    /// Every runtime that was found
+    pub runtimes: Vec<RuntimeVersion>,
+}
+
+impl SoftwareStack {
+    /// Look for installed runtimes
+    pub fn detect() -> Self {
+        let cuda = detect_cuda();
+        let cudnn = detect_cudnn(cuda.as_ref().and_then(|c| c.path.as_deref()));
+        let tensorrt = detect_tensorrt(cuda.as_ref().and_then(|c| c.path.as_deref()));
+
+        let runtimes = [
+            cuda,
+            cudnn,
+            tensorrt,
+            detect_rocm(),
+            detect_openvino(),
+            detect_directml(),
+            detect_mps(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        Self { runtimes }
+    }
+
+    /// Get every detected runtime
+    pub fn runtimes(&self) -> &[RuntimeVersion] {
+        &self.runtimes
+    }
+
+    /// Check whether a runtime is installed
+    pub fn has(&self, runtime: AIRuntime) -> bool {
+        self.get(runtime).is_some()
+    }
+
+    /// Get an installed runtime's details
+    pub fn get(&self, runtime: AIRuntime) -> Option<&RuntimeVersion> {
+        self.runtimes.iter().find(|r| r.runtime == runtime)
+    }
+
+    /// Get an installed runtime's version
+    pub fn version(&self, runtime: AIRuntime) -> Option<&str> {
+        self.get(runtime)?.version.as_deref()
+    }
+}
+
+fn found(runtime: AIRuntime, version: Option<String>, path: Option<PathBuf>) -> Option<RuntimeVersion> {
+    Some(RuntimeVersion { runtime, version, path })
+}
+
+/// First existing directory among environment variables and default locations
+fn install_dir(env_vars: &[&str], defaults: &[&str]) -> Option<PathBuf> {
+    env_vars
+        .iter()
+        .filter_map(|var| std::env::var_os(var).map(PathBuf::from))
+        .chain(defaults.iter().map(PathBuf::from))
+        .find(|path| path.is_dir())
+}
+
+fn detect_cuda() -> Option<RuntimeVersion> {
+    let root = install_dir(
+        &["CUDA_PATH", "CUDA_HOME"],
+        &["/usr/local/cuda", "/opt/cuda"],
+    );
+
+    if let Some(root) = root {
+        // version.json since CUDA 11.1, version.txt before that
+        let version = fs::read_to_string(root.join("version.json"))
+            .ok()
+            .and_then(|json| {
+                let value: serde_json::Value = serde_json::from_str(&json).ok()?;
+                value["cuda"]["version"].as_str().map(str::to_string)
+            })
+            .or_else(|| {
+                fs::read_to_string(root.join("version.txt"))
+                    .ok()
+                    .and_then(|text| text.split_whitespace().last().map(str::to_string))
+            })
+            .or_else(|| nvcc_version(&root.join("bin").join("nvcc")));
+        return found(AIRuntime::CUDA, version, Some(root));
+    }
+
+    // Toolkit installed somewhere on PATH
+    let version = nvcc_version(Path::new("nvcc"))?;
+    found(AIRuntime::CUDA, Some(version), None)
+}
+
+/// Parse "Cuda compilation tools, release 12.4, V12.4.131"
+fn nvcc_version(nvcc: &Path) -> Option<String> {
+    let output = Command::new(nvcc).arg("--version").output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let release = text.lines().find_map(|line| line.split("release ").nth(1))?;
+    Some(release.split(',').next()?.trim().to_string())
+}
+
+fn detect_cudnn(cuda_root: Option<&Path>) -> Option<RuntimeVersion> {
+    let mut include_dirs: Vec<PathBuf> = cuda_root.map(|root| root.join("include")).into_iter().collect();
+    include_dirs.extend(
+        ["/usr/include", "/usr/include/x86_64-linux-gnu", "/usr/include/aarch64-linux-gnu", "/usr/local/include"]
+            .iter()
+            .map(PathBuf::from),
+    );
+
+    for dir in include_dirs {
+        // cuDNN 8+ moved the version macros to cudnn_version.h
+        for header in ["cudnn_version.h", "cudnn.h"] {
+            let path = dir.join(header);
+            let Ok(text) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let define = |name: &str| {
+                text.lines().find_map(|line| {
+                    let rest = line.trim().strip_prefix("#define ")?.strip_prefix(name)?;
+                    rest.trim().parse::<u32>().ok()
+                })
+            };
+            if let (Some(major), Some(minor)) = (define("CUDNN_MAJOR"), define("CUDNN_MINOR")) {
+                let patch = define("CUDNN_PATCHLEVEL").unwrap_or(0);
+                return found(AIRuntime::CuDNN, Some(format!("{major}.{minor}.{patch}")), Some(dir));
+            }
+        }
+    }
+
+    None
+}
+
+fn detect_tensorrt(cuda_root: Option<&Path>) -> Option<RuntimeVersion> {
+    let mut include_dirs: Vec<PathBuf> = install_dir(&["TENSORRT_ROOT", "TRT_ROOT"], &["/usr/local/tensorrt", "/opt/tensorrt"])
+        .map(|root| root.join("include"))
+        .into_iter()
+        .chain(cuda_root.map(|root| root.join("include")))
+        .collect();
+    include_dirs.extend(
+        ["/usr/include", "/usr/include/x86_64-linux-gnu", "/usr/include/aarch64-linux-gnu", "/usr/local/include"]
+            .iter()
+            .map(PathBuf::from),
+    );
+
+    for dir in include_dirs {
+        let Ok(text) = fs::read_to_string(dir.join("NvInferVersion.h")) else {
+            continue;
+        };
+        // "#define NV_TENSORRT_MAJOR 10 //!< TensorRT major version."
+        let define = |name: &str| {
+            text.lines().find_map(|line| {
+                let rest = line.trim().strip_prefix("#define ")?.strip_prefix(name)?;
+                rest.split_whitespace().next()?.parse::<u32>().ok()
+            })
+        };
+        if let (Some(major), Some(minor)) = (define("NV_TENSORRT_MAJOR"), define("NV_TENSORRT_MINOR")) {
+            let patch = define("NV_TENSORRT_PATCH").unwrap_or(0);
+            return found(AIRuntime::TensorRT, Some(format!("{major}.{minor}.{patch}")), Some(dir));
+        }
+    }
+
+    None
+}
+
+fn detect_rocm() -> Option<RuntimeVersion> {
+    let root = install_dir(&["ROCM_PATH", "HIP_PATH"], &["/opt/rocm"])?;
+    // ".info/version" holds e.g. "6.0.2-115"
+    let version = fs::read_to_string(root.join(".info").join("version"))
+        .ok()
+        .map(|text| text.trim().split('-').next().unwrap_or_default().to_string())
+        .filter(|version| !version.is_empty());
+    found(AIRuntime::ROCm, version, Some(root))
+}
+
+fn detect_openvino() -> Option<RuntimeVersion> {
+    let root = install_dir(&["INTEL_OPENVINO_DIR", "OpenVINO_DIR"], &["/opt/intel/openvino"])
+        .or_else(|| {
+            // Versioned installs: /opt/intel/openvino_2024.1.0
+            let mut dirs: Vec<PathBuf> = fs::read_dir("/opt/intel")
+                .ok()?
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with("openvino_"))
+                })
+                .collect();
+            dirs.sort();
+            dirs.pop()
+        })?;
+
+    // Resolve the "openvino" symlink to its versioned directory name
+    let resolved = fs::canonicalize(&root).unwrap_or_else(|_| root.clone());
+    let version = resolved
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_prefix("openvino_"))
+        .map(str::to_string);
+    found(AIRuntime::OpenVINO, version, Some(root))
+}
+
+fn detect_directml() -> Option<RuntimeVersion> {
+    #[cfg(target_os = "windows")]
+    {
+        let system_root = std::env::var_os("SystemRoot").map(PathBuf::from)?;
+        let dll = system_root.join("System32").join("DirectML.dll");
+        if !dll.is_file() {
+            return None;
+        }
+        found(AIRuntime::DirectML, windows_file_version(&dll), Some(dll))
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        None
+    }
+}
+
+/// File version of a DLL via WMI
+#[cfg(target_os = "windows")]
+fn windows_file_version(path: &Path) -> Option<String> {
+    use std::collections::HashMap;
+    use wmi::{Variant, WMIConnection};
+
+    let com = crate::probe::com_library().ok()?;
+    let wmi = WMIConnection::new(com).ok()?;
+    let escaped = path.to_str()?.replace('\\', "\\\\");
+    let rows: Vec<HashMap<String, Variant>> = wmi
+        .raw_query(format!("SELECT Version FROM CIM_DataFile WHERE Name = '{escaped}'"))
+        .ok()?;
+    match rows.first()?.get("Version")? {
+        Variant::String(version) => Some(version.clone()),
+        _ => None,
+    }
+}
+
+fn detect_mps() -> Option<RuntimeVersion> {
+    #[cfg(target_os = "macos")]
+    {
+        let framework = PathBuf::from("/System/Library/Frameworks/MetalPerformanceShaders.framework");
+        if !framework.is_dir() {
+            return None;
+        }
+        // MPS ships with the OS, so report the macOS version it belongs to
+        let version = Command::new("sw_vers")
+            .arg("-productVersion")
+            .output()
+            .ok()
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|version| !version.is_empty());
+        found(AIRuntime::MetalPerformanceShaders, version, Some(framework))
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        None
+    }
+}
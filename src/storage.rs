@@ -1,5 +1,15 @@
+use crate::units::GIB;
 use crate::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use sysinfo::{DiskKind, Disks};
+
+mod ioctl;
+mod nvme;
+mod sd;
+
+pub use nvme::{NVMeInfo, NVMeNamespace};
+pub use sd::{SdCard, SdCardReader, SdCardType, SdSpeedClass};
 
 /// Storage device type
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -27,7 +37,992 @@ impl std::fmt::Display for StorageType {
     }
 }
 
+/// Interface a physical disk is attached through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BusType {
+    NVMe,
+    SATA,
+    SAS,
+    SCSI,
+    USB,
+    /// SD card or eMMC
+    MMC,
+    /// Paravirtualized disk (virtio, Hyper-V, file-backed)
+    Virtual,
+    /// Apple silicon internal storage
+    AppleFabric,
+    Unknown,
+}
+
+impl std::fmt::Display for BusType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BusType::NVMe => write!(f, "NVMe"),
+            BusType::SATA => write!(f, "SATA"),
+            BusType::SAS => write!(f, "SAS"),
+            BusType::SCSI => write!(f, "SCSI"),
+            BusType::USB => write!(f, "USB"),
+            BusType::MMC => write!(f, "MMC"),
+            BusType::Virtual => write!(f, "Virtual"),
+            BusType::AppleFabric => write!(f, "Apple Fabric"),
+            BusType::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// Expected performance of a disk, derived from its bus, PCIe link and rotation rate
+///
+/// Ordered from fastest to slowest; classes with very different
+/// characteristics (network, virtual, unknown) come last.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StoragePerformanceClass {
+    /// NVMe on a PCIe 5.0 link
+    NVMeGen5,
+    /// NVMe on a PCIe 4.0 link; also Apple silicon internal storage
+    NVMeGen4,
+    /// NVMe on a PCIe 3.0 link
+    NVMeGen3,
+    /// NVMe with an older or unknown link
+    NVMe,
+    /// SATA or SAS solid-state drive
+    SataSSD,
+    /// Enterprise hard drive at 10,000 RPM or more
+    HDD10K,
+    /// 7200 RPM hard drive
+    HDD7200,
+    /// 5400-5900 RPM hard drive
+    HDD5400,
+    /// Hard drive with an unknown rotation rate
+    HDD,
+    /// USB-attached disk or flash drive
+    USB,
+    /// Soldered eMMC storage
+    EMmc,
+    /// SD or microSD card
+    SDCard,
+    /// Network file system (NFS, SMB, ...)
+    Network,
+    /// Paravirtualized disk of a virtual machine
+    Virtual,
+    /// Not enough information
+    #[default]
+    Unknown,
+}
+
+impl StoragePerformanceClass {
+    /// Class from the storage type alone, when no physical disk details are available
+    pub fn from_storage_type(storage_type: &StorageType) -> Self {
+        match storage_type {
+            StorageType::NVMe => StoragePerformanceClass::NVMe,
+            StorageType::SSD => StoragePerformanceClass::SataSSD,
+            StorageType::HDD => StoragePerformanceClass::HDD,
+            StorageType::EMmc => StoragePerformanceClass::EMmc,
+            StorageType::SD => StoragePerformanceClass::SDCard,
+            StorageType::USB => StoragePerformanceClass::USB,
+            StorageType::Unknown => StoragePerformanceClass::Unknown,
+        }
+    }
+
+    /// Check if the disk is NVMe of any generation
+    pub fn is_nvme(&self) -> bool {
+        matches!(
+            self,
+            StoragePerformanceClass::NVMeGen5
+                | StoragePerformanceClass::NVMeGen4
+                | StoragePerformanceClass::NVMeGen3
+                | StoragePerformanceClass::NVMe
+        )
+    }
+
+    /// Check if the disk is an NVMe or SATA/SAS SSD
+    pub fn is_ssd(&self) -> bool {
+        self.is_nvme() || *self == StoragePerformanceClass::SataSSD
+    }
+
+    /// Check if the disk is a spinning hard drive
+    pub fn is_rotational(&self) -> bool {
+        matches!(
+            self,
+            StoragePerformanceClass::HDD10K
+                | StoragePerformanceClass::HDD7200
+                | StoragePerformanceClass::HDD5400
+                | StoragePerformanceClass::HDD
+        )
+    }
+
+    /// Typical sequential read speed of the class in MB/s, if it has one
+    pub fn typical_read_mb_s(&self) -> Option<u32> {
+        match self {
+            StoragePerformanceClass::NVMeGen5 => Some(12_000),
+            StoragePerformanceClass::NVMeGen4 => Some(7_000),
+            StoragePerformanceClass::NVMeGen3 => Some(3_500),
+            StoragePerformanceClass::NVMe => Some(2_000),
+            StoragePerformanceClass::SataSSD => Some(550),
+            StoragePerformanceClass::HDD10K => Some(250),
+            StoragePerformanceClass::HDD7200 => Some(200),
+            StoragePerformanceClass::HDD5400 => Some(140),
+            StoragePerformanceClass::HDD => Some(160),
+            StoragePerformanceClass::EMmc => Some(300),
+            StoragePerformanceClass::SDCard => Some(90),
+            StoragePerformanceClass::USB
+            | StoragePerformanceClass::Network
+            | StoragePerformanceClass::Virtual
+            | StoragePerformanceClass::Unknown => None,
+        }
+    }
+}
+
+impl std::fmt::Display for StoragePerformanceClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StoragePerformanceClass::NVMeGen5 => write!(f, "NVMe PCIe 5.0"),
+            StoragePerformanceClass::NVMeGen4 => write!(f, "NVMe PCIe 4.0"),
+            StoragePerformanceClass::NVMeGen3 => write!(f, "NVMe PCIe 3.0"),
+            StoragePerformanceClass::NVMe => write!(f, "NVMe"),
+            StoragePerformanceClass::SataSSD => write!(f, "SATA SSD"),
+            StoragePerformanceClass::HDD10K => write!(f, "HDD (10,000+ RPM)"),
+            StoragePerformanceClass::HDD7200 => write!(f, "HDD (7200 RPM)"),
+            StoragePerformanceClass::HDD5400 => write!(f, "HDD (5400 RPM)"),
+            StoragePerformanceClass::HDD => write!(f, "HDD"),
+            StoragePerformanceClass::USB => write!(f, "USB"),
+            StoragePerformanceClass::EMmc => write!(f, "eMMC"),
+            StoragePerformanceClass::SDCard => write!(f, "SD Card"),
+            StoragePerformanceClass::Network => write!(f, "Network"),
+            StoragePerformanceClass::Virtual => write!(f, "Virtual"),
+            StoragePerformanceClass::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// SMART health and wear data for a physical disk
+///
+/// Fields are `None` when the drive or platform does not report them. Reading
+/// SMART data usually requires elevated privileges; when it cannot be read the
+/// owning [`StorageInfo::smart`] is `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SmartHealth {
+    /// Overall SMART self-assessment result (true = passed)
+    pub passed: Option<bool>,
+    /// The drive predicts an imminent failure
+    pub predicted_failure: bool,
+    /// The drive is outside its temperature thresholds (NVMe critical warning
+    /// bit 1); transient, so not a predicted failure
+    #[serde(default)]
+    pub temperature_warning: bool,
+    /// Reallocated sector count (ATA attribute 5)
+    pub reallocated_sectors: Option<u64>,
+    /// NVMe "percentage used" wear estimate (can exceed 100)
+    pub percentage_used: Option<u8>,
+    /// Power-on hours
+    pub power_on_hours: Option<u64>,
+    /// Unrecovered media and data integrity errors
+    pub media_errors: Option<u64>,
+    /// Drive temperature in Celsius
+    pub temperature_celsius: Option<f32>,
+}
+
+impl SmartHealth {
+    /// Query SMART data for a physical disk
+    ///
+    /// `device` is `/dev/sda` or `/dev/nvme0n1` on Linux, `PhysicalDrive0` (or
+    /// the disk number) on Windows, and a disk identifier or mount point on macOS.
+    pub fn query(device: &str) -> Result<Self> {
+        #[cfg(target_os = "linux")]
+        {
+            Self::query_linux(device)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Self::query_windows(device)
+        }
+        #[cfg(target_os = "macos")]
+        {
+            Self::query_macos(device)
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            Err(crate::HardwareQueryError::platform_not_supported(format!(
+                "SMART queries are not supported for {device} on this platform"
+            )))
+        }
+    }
+
+    /// Decode an NVMe SMART / Health Information log page (Get Log Page 02h)
+    ///
+    /// Returns `None` if `log` is shorter than the 512-byte page.
+    pub fn from_nvme_smart_log(log: &[u8]) -> Option<Self> {
+        let log = log.get(..nvme::SMART_LOG_LEN)?.try_into().ok()?;
+        Some(nvme::parse_smart_log(log))
+    }
+
+    /// Remaining rated endurance in percent, if the drive reports wear
+    pub fn wear_remaining_percent(&self) -> Option<u8> {
+        self.percentage_used.map(|used| 100u8.saturating_sub(used))
+    }
+
+    /// Whether the drive shows signs of degradation worth replacing it for
+    pub fn is_degraded(&self) -> bool {
+        self.predicted_failure
+            || self.passed == Some(false)
+            || self.percentage_used.is_some_and(|used| used >= 90)
+            || self.reallocated_sectors.is_some_and(|count| count > 0)
+            || self.media_errors.is_some_and(|count| count > 0)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn query_linux(device: &str) -> Result<Self> {
+        use crate::HardwareQueryError;
+        use crate::probe::Command;
+
+        // smartctl exits non-zero to flag problems, so parse its JSON regardless
+        if let Ok(output) = Command::new("smartctl")
+            .args(["--json", "-H", "-A", device])
+            .output()
+        {
+            if let Ok(json) = serde_json::from_slice::<serde_json::Value>(&output.stdout) {
+                if let Some(health) = Self::from_smartctl_json(&json) {
+                    return Ok(health);
+                }
+            }
+        }
+
+        if device.contains("nvme") {
+            return linux::read_nvme_smart_log(device);
+        }
+
+        Err(HardwareQueryError::device_not_found(format!(
+            "SMART data unavailable for {device} (smartctl not installed or insufficient permissions)"
+        )))
+    }
+
+    /// Parse `smartctl --json` output
+    pub(crate) fn from_smartctl_json(json: &serde_json::Value) -> Option<Self> {
+        let passed = json["smart_status"]["passed"].as_bool();
+        let mut health = Self {
+            passed,
+            predicted_failure: passed == Some(false),
+            temperature_celsius: json["temperature"]["current"].as_f64().map(|t| t as f32),
+            power_on_hours: json["power_on_time"]["hours"].as_u64(),
+            ..Default::default()
+        };
+
+        if let Some(attributes) = json["ata_smart_attributes"]["table"].as_array() {
+            for attribute in attributes {
+                let raw = attribute["raw"]["value"].as_u64();
+                match attribute["id"].as_u64() {
+                    Some(5) => health.reallocated_sectors = raw,
+                    Some(9) if health.power_on_hours.is_none() => health.power_on_hours = raw,
+                    Some(187) => health.media_errors = raw,
+                    _ => {}
+                }
+            }
+        }
+
+        let nvme = &json["nvme_smart_health_information_log"];
+        if nvme.is_object() {
+            health.percentage_used = nvme["percentage_used"].as_u64().map(|v| v.min(255) as u8);
+            health.media_errors = nvme["media_errors"].as_u64();
+            health.power_on_hours = health.power_on_hours.or(nvme["power_on_hours"].as_u64());
+            // smartctl fails NVMe drives on any critical warning, including
+            // the transient temperature bit
+            if let Some(warning) = nvme["critical_warning"].as_u64() {
+                let warning = warning as u8;
+                let failure = warning & nvme::CRITICAL_WARNING_FAILURE_MASK != 0;
+                health.passed = Some(!failure);
+                health.predicted_failure = failure;
+                health.temperature_warning = warning & nvme::CRITICAL_WARNING_TEMPERATURE != 0;
+            }
+        }
+
+        if health == Self::default() {
+            None
+        } else {
+            Some(health)
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn query_windows(device: &str) -> Result<Self> {
+        use crate::HardwareQueryError;
+        use wmi::{Variant, WMIConnection};
+
+        let disk_number = device
+            .trim_start_matches(r"\\.\")
+            .trim_start_matches("PhysicalDrive")
+            .to_string();
+
+        let wmi_con = WMIConnection::with_namespace_path(
+            r"root\Microsoft\Windows\Storage",
+            crate::probe::com_library()?,
+        )?;
+
+        let as_u64 = |value: Option<&Variant>| match value {
+            Some(Variant::UI1(v)) => Some(*v as u64),
+            Some(Variant::UI2(v)) => Some(*v as u64),
+            Some(Variant::UI4(v)) => Some(*v as u64),
+            Some(Variant::UI8(v)) => Some(*v),
+            Some(Variant::String(v)) => v.parse().ok(),
+            _ => None,
+        };
+
+        let mut health = Self::default();
+
+        let disks: Vec<HashMap<String, Variant>> = wmi_con.raw_query(format!(
+            "SELECT HealthStatus FROM MSFT_PhysicalDisk WHERE DeviceId = '{disk_number}'"
+        ))?;
+        let Some(disk) = disks.first() else {
+            return Err(HardwareQueryError::device_not_found(format!(
+                "Physical disk {device} not found"
+            )));
+        };
+        // HealthStatus: 0 = Healthy, 1 = Warning, 2 = Unhealthy
+        if let Some(status) = as_u64(disk.get("HealthStatus")) {
+            health.passed = Some(status == 0);
+            health.predicted_failure = status == 2;
+        }
+
+        let counters: Vec<HashMap<String, Variant>> = wmi_con
+            .raw_query(format!(
+                "SELECT * FROM MSFT_StorageReliabilityCounter WHERE DeviceId = '{disk_number}'"
+            ))
+            .unwrap_or_default();
+        if let Some(counter) = counters.first() {
+            health.percentage_used = as_u64(counter.get("Wear")).map(|v| v.min(255) as u8);
+            health.power_on_hours = as_u64(counter.get("PowerOnHours"));
+            health.media_errors = as_u64(counter.get("ReadErrorsUncorrected"));
+            health.temperature_celsius = as_u64(counter.get("Temperature"))
+                .filter(|t| *t > 0)
+                .map(|t| t as f32);
+        }
+
+        Ok(health)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn query_macos(device: &str) -> Result<Self> {
+        use crate::HardwareQueryError;
+        use crate::probe::Command;
+
+        // diskutil reports the SMART status IOKit keeps for the whole disk
+        let output = Command::new("diskutil").args(["info", device]).output()?;
+        let info = String::from_utf8_lossy(&output.stdout);
+
+        let status = info
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("SMART Status:"))
+            .map(str::trim)
+            .ok_or_else(|| {
+                HardwareQueryError::device_not_found(format!(
+                    "SMART status unavailable for {device}"
+                ))
+            })?;
+
+        match status {
+            "Verified" => Ok(Self {
+                passed: Some(true),
+                ..Default::default()
+            }),
+            "Failing" => Ok(Self {
+                passed: Some(false),
+                predicted_failure: true,
+                ..Default::default()
+            }),
+            _ => Err(HardwareQueryError::device_not_found(format!(
+                "SMART not supported by {device}"
+            ))),
+        }
+    }
+}
+
+/// A physical disk, independent of how it is partitioned or mounted
+///
+/// Answers "which disk is failing"; see [`Volume`] for "which mount is full".
+/// [`volumes`](Self::volumes) lists the mount points on this disk and each
+/// volume's [`disk`](Volume::disk) names its disk's [`device`](Self::device).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhysicalDisk {
+    /// Device identifier (`/dev/nvme0n1`, `PhysicalDrive0`, `disk0`)
+    pub device: String,
+    /// Drive model
+    pub model: String,
+    /// Serial number
+    pub serial_number: Option<String>,
+    /// Firmware revision
+    pub firmware_version: Option<String>,
+    /// Interface the disk is attached through
+    pub bus: BusType,
+    /// Storage type
+    pub storage_type: StorageType,
+    /// Raw capacity in bytes
+    pub capacity_bytes: u64,
+    /// Has removable media (card, disc or cartridge)
+    pub removable: bool,
+    /// Can be ejected or unplugged while the system runs (USB, SD card, hot-plug bay)
+    #[serde(default)]
+    pub ejectable: bool,
+    /// SMART health, when it could be read
+    pub smart: Option<SmartHealth>,
+    /// Mount points of the volumes on this disk
+    pub volumes: Vec<String>,
+    /// Controller and namespace details for NVMe disks
+    #[serde(default)]
+    pub nvme: Option<NVMeInfo>,
+    /// Spindle speed of a hard drive in RPM, when the drive reports it
+    #[serde(default)]
+    pub rotation_rate_rpm: Option<u32>,
+    /// Whether the disk accepts TRIM / UNMAP, when known
+    #[serde(default)]
+    pub trim_supported: Option<bool>,
+}
+
+impl PhysicalDisk {
+    /// Query all physical disks
+    pub fn query_all() -> Result<Vec<Self>> {
+        Ok(StorageLayout::query()?.disks)
+    }
+
+    /// Get device identifier
+    pub fn device(&self) -> &str {
+        &self.device
+    }
+
+    /// Get drive model
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Get serial number
+    pub fn serial_number(&self) -> Option<&str> {
+        self.serial_number.as_deref()
+    }
+
+    /// Get firmware revision
+    pub fn firmware_version(&self) -> Option<&str> {
+        self.firmware_version.as_deref()
+    }
+
+    /// Get bus type
+    pub fn bus(&self) -> BusType {
+        self.bus
+    }
+
+    /// Get storage type
+    pub fn drive_type(&self) -> &StorageType {
+        &self.storage_type
+    }
+
+    /// Get raw capacity in GB
+    pub fn capacity_gb(&self) -> f64 {
+        self.capacity_bytes as f64 / (1024.0 * 1024.0 * 1024.0)
+    }
+
+    /// Get raw capacity in bytes
+    pub fn capacity_bytes(&self) -> u64 {
+        self.capacity_bytes
+    }
+
+    /// Get SMART health data
+    pub fn smart_health(&self) -> Option<&SmartHealth> {
+        self.smart.as_ref()
+    }
+
+    /// Whether SMART data shows the disk degrading
+    pub fn is_failing(&self) -> bool {
+        self.smart.as_ref().is_some_and(SmartHealth::is_degraded)
+    }
+
+    /// Get mount points of the volumes on this disk
+    pub fn volumes(&self) -> &[String] {
+        &self.volumes
+    }
+
+    /// Get NVMe controller details
+    pub fn nvme(&self) -> Option<&NVMeInfo> {
+        self.nvme.as_ref()
+    }
+
+    /// Whether the medium can be removed from the drive
+    pub fn is_removable(&self) -> bool {
+        self.removable
+    }
+
+    /// Whether the disk can be ejected or unplugged, as opposed to a fixed disk
+    pub fn is_ejectable(&self) -> bool {
+        self.ejectable
+    }
+
+    /// Get the spindle speed in RPM
+    pub fn rotation_rate_rpm(&self) -> Option<u32> {
+        self.rotation_rate_rpm
+    }
+
+    /// Whether the disk accepts TRIM / UNMAP
+    pub fn trim_supported(&self) -> Option<bool> {
+        self.trim_supported
+    }
+
+    /// Disk described by a Windows `STORAGE_DEVICE_DESCRIPTOR`
+    ///
+    /// Bus, model, serial number, firmware revision and removability come from
+    /// the descriptor; capacity is left at zero.
+    pub fn from_device_descriptor(device: &str, descriptor: &[u8]) -> Option<Self> {
+        ioctl::parse_device_descriptor(device, descriptor)
+    }
+
+    /// Expected performance from the bus, negotiated PCIe link and rotation rate
+    pub fn performance_class(&self) -> StoragePerformanceClass {
+        match self.bus {
+            BusType::NVMe => match self.nvme.as_ref().and_then(|nvme| nvme.pcie_link.as_ref()) {
+                Some(link) if link.generation >= 5 => StoragePerformanceClass::NVMeGen5,
+                Some(link) if link.generation == 4 => StoragePerformanceClass::NVMeGen4,
+                Some(link) if link.generation == 3 => StoragePerformanceClass::NVMeGen3,
+                _ => StoragePerformanceClass::NVMe,
+            },
+            BusType::AppleFabric => StoragePerformanceClass::NVMeGen4,
+            BusType::MMC if self.storage_type == StorageType::EMmc => StoragePerformanceClass::EMmc,
+            BusType::MMC => StoragePerformanceClass::SDCard,
+            BusType::USB => StoragePerformanceClass::USB,
+            BusType::Virtual => StoragePerformanceClass::Virtual,
+            _ => match (&self.storage_type, self.rotation_rate_rpm) {
+                (StorageType::HDD | StorageType::Unknown, Some(rpm)) if rpm >= 10_000 => StoragePerformanceClass::HDD10K,
+                (StorageType::HDD | StorageType::Unknown, Some(rpm)) if rpm >= 7_200 => StoragePerformanceClass::HDD7200,
+                (StorageType::HDD | StorageType::Unknown, Some(_)) => StoragePerformanceClass::HDD5400,
+                (storage_type, _) => StoragePerformanceClass::from_storage_type(storage_type),
+            },
+        }
+    }
+
+    /// Enumerate physical disks without SMART data or volume links
+    fn enumerate() -> Vec<Self> {
+        #[cfg(target_os = "linux")]
+        {
+            linux::physical_disks()
+        }
+        #[cfg(target_os = "windows")]
+        {
+            windows::physical_disks()
+        }
+        #[cfg(target_os = "macos")]
+        {
+            macos::physical_disks()
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            Vec::new()
+        }
+    }
+}
+
+/// Exact bytes when recorded, otherwise converted from the GB field of older snapshots
+fn bytes_or_gb(bytes: u64, gb: f64) -> u64 {
+    if bytes > 0 {
+        bytes
+    } else {
+        (gb * GIB as f64).round() as u64
+    }
+}
+
+/// A mounted filesystem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Volume {
+    /// Volume or partition name (`/dev/nvme0n1p2`, volume label)
+    pub name: String,
+    /// Mount point or drive letter
+    pub mount_point: String,
+    /// File system type
+    pub file_system: Option<String>,
+    /// Total size in GB
+    pub total_gb: f64,
+    /// Available space in GB
+    pub available_gb: f64,
+    /// Used space in GB
+    pub used_gb: f64,
+    /// Is removable
+    pub removable: bool,
+    /// Device identifier of the [`PhysicalDisk`] holding this volume
+    pub disk: Option<String>,
+    /// Total size in bytes
+    #[serde(default)]
+    pub total_bytes: u64,
+    /// Available space in bytes
+    #[serde(default)]
+    pub available_bytes: u64,
+    /// Mount options (`noatime`, `compress=zstd:3`, `ro`, ...)
+    #[serde(default)]
+    pub mount_options: Vec<String>,
+    /// Total inodes (file slots), on file systems that have a fixed count
+    #[serde(default)]
+    pub inodes_total: Option<u64>,
+    /// Free inodes
+    #[serde(default)]
+    pub inodes_free: Option<u64>,
+}
+
+/// File system types served over the network
+const NETWORK_FILE_SYSTEMS: &[&str] = &[
+    "nfs", "nfs4", "cifs", "smbfs", "smb3", "afpfs", "webdav", "davfs", "9p", "ceph",
+    "glusterfs", "lustre", "fuse.sshfs", "sshfs", "fuse.rclone",
+];
+
+/// Check if a file system type is served over the network
+pub(crate) fn is_network_file_system(file_system: &str) -> bool {
+    NETWORK_FILE_SYSTEMS.contains(&file_system.to_lowercase().as_str())
+}
+
+impl Volume {
+    /// Query all mounted volumes
+    pub fn query_all() -> Result<Vec<Self>> {
+        Ok(StorageLayout::query()?.volumes)
+    }
+
+    /// Get volume name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get mount point
+    pub fn mount_point(&self) -> &str {
+        &self.mount_point
+    }
+
+    /// Get file system type
+    pub fn file_system(&self) -> Option<&str> {
+        self.file_system.as_deref()
+    }
+
+    /// Get total size in GB
+    pub fn total_gb(&self) -> f64 {
+        self.total_gb
+    }
+
+    /// Get available space in GB
+    pub fn available_gb(&self) -> f64 {
+        self.available_gb
+    }
+
+    /// Get used space in GB
+    pub fn used_gb(&self) -> f64 {
+        self.used_gb
+    }
+
+    /// Get total size in bytes
+    pub fn total_bytes(&self) -> u64 {
+        bytes_or_gb(self.total_bytes, self.total_gb)
+    }
+
+    /// Get available space in bytes
+    pub fn available_bytes(&self) -> u64 {
+        bytes_or_gb(self.available_bytes, self.available_gb)
+    }
+
+    /// Get used space in bytes
+    pub fn used_bytes(&self) -> u64 {
+        self.total_bytes().saturating_sub(self.available_bytes())
+    }
+
+    /// Get usage percentage
+    pub fn usage_percent(&self) -> f64 {
+        if self.total_gb > 0.0 {
+            (self.used_gb / self.total_gb) * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Get the device identifier of the backing physical disk
+    pub fn disk(&self) -> Option<&str> {
+        self.disk.as_deref()
+    }
+
+    /// Check if the volume has sufficient free space
+    pub fn has_free_space(&self, required_gb: f64) -> bool {
+        self.available_gb >= required_gb
+    }
+
+    /// Get mount options
+    pub fn mount_options(&self) -> &[String] {
+        &self.mount_options
+    }
+
+    /// Check if a mount option is set (`noatime`, or the key of `compress=zstd`)
+    pub fn has_mount_option(&self, option: &str) -> bool {
+        self.mount_options
+            .iter()
+            .any(|o| o == option || o.split_once('=').is_some_and(|(key, _)| key == option))
+    }
+
+    /// Check if the volume is mounted read-only
+    pub fn is_read_only(&self) -> bool {
+        self.has_mount_option("ro")
+    }
+
+    /// Check if the file system is served over the network (NFS, SMB, sshfs, ...)
+    pub fn is_network(&self) -> bool {
+        self.file_system.as_deref().is_some_and(is_network_file_system)
+    }
+
+    /// Get the transparent compression algorithm (`zstd`, `lzo`, ...), if enabled
+    pub fn compression(&self) -> Option<&str> {
+        self.mount_options.iter().find_map(|o| {
+            let (key, value) = o.split_once('=')?;
+            matches!(key, "compress" | "compress-force" | "compression")
+                .then(|| value.split(':').next().unwrap_or(value))
+                .filter(|algo| *algo != "no" && *algo != "none")
+        })
+    }
+
+    /// Check if access-time updates are suppressed (`noatime` or `relatime`)
+    pub fn atime_optimized(&self) -> bool {
+        self.has_mount_option("noatime") || self.has_mount_option("relatime")
+    }
+
+    /// Get total inode count
+    pub fn inodes_total(&self) -> Option<u64> {
+        self.inodes_total
+    }
+
+    /// Get free inode count
+    pub fn inodes_free(&self) -> Option<u64> {
+        self.inodes_free
+    }
+
+    /// Get inode usage percentage
+    pub fn inode_usage_percent(&self) -> Option<f64> {
+        let total = self.inodes_total.filter(|total| *total > 0)?;
+        let free = self.inodes_free?;
+        Some(total.saturating_sub(free) as f64 / total as f64 * 100.0)
+    }
+
+    /// Describe setups that slow down builds and other file-heavy work
+    pub fn performance_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let fs = self.file_system.as_deref().unwrap_or("unknown");
+
+        if self.is_network() {
+            warnings.push(format!(
+                "{} is a network file system ({fs}); keep build directories on local storage",
+                self.mount_point
+            ));
+        } else if self.has_mount_option("rw") && !self.atime_optimized() {
+            warnings.push(format!(
+                "{} updates access times on every read; mount with noatime or relatime",
+                self.mount_point
+            ));
+        }
+
+        if let Some(usage) = self.inode_usage_percent() {
+            if usage >= 90.0 {
+                warnings.push(format!(
+                    "{} has used {usage:.0}% of its inodes; small-file workloads may fail before space runs out",
+                    self.mount_point
+                ));
+            }
+        }
+
+        warnings
+    }
+
+    /// Read mount options and inode counts for a mount point
+    #[allow(unused_variables)]
+    fn mount_details(
+        mount_point: &str,
+        mount_table: &HashMap<String, Vec<String>>,
+    ) -> (Vec<String>, Option<u64>, Option<u64>) {
+        #[cfg(target_os = "linux")]
+        {
+            let options = mount_table.get(mount_point).cloned().unwrap_or_default();
+            // fsfilcnt_t is narrower than u64 on some 32-bit targets
+            #[allow(clippy::unnecessary_cast)]
+            let (total, free) = std::ffi::CString::new(mount_point)
+                .ok()
+                .and_then(|path| {
+                    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+                    (unsafe { libc::statvfs(path.as_ptr(), &mut stat) } == 0).then_some(stat)
+                })
+                .map(|stat| (stat.f_files as u64, stat.f_ffree as u64))
+                .filter(|(total, _)| *total > 0)
+                .map_or((None, None), |(total, free)| (Some(total), Some(free)));
+            return (options, total, free);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let Some(stat) = std::ffi::CString::new(mount_point).ok().and_then(|path| {
+                let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+                (unsafe { libc::statfs(path.as_ptr(), &mut stat) } == 0).then_some(stat)
+            }) else {
+                return (Vec::new(), None, None);
+            };
+            let flags = stat.f_flags as libc::c_int;
+            let options = [
+                (libc::MNT_RDONLY, "ro"),
+                (libc::MNT_NOATIME, "noatime"),
+                (libc::MNT_NOSUID, "nosuid"),
+                (libc::MNT_NODEV, "nodev"),
+                (libc::MNT_NOEXEC, "noexec"),
+                (libc::MNT_JOURNALED, "journaled"),
+            ]
+            .iter()
+            .filter(|(flag, _)| flags & flag != 0)
+            .map(|(_, name)| name.to_string())
+            .collect();
+            let total = (stat.f_files > 0).then_some(stat.f_files);
+            return (options, total, total.map(|_| stat.f_ffree));
+        }
+
+        #[allow(unreachable_code)]
+        (Vec::new(), None, None)
+    }
+
+    /// Mount point to option list, from `/proc/self/mounts`
+    fn mount_table() -> HashMap<String, Vec<String>> {
+        #[cfg(target_os = "linux")]
+        {
+            if let Ok(mounts) = std::fs::read_to_string("/proc/self/mounts") {
+                return Self::parse_mount_table(&mounts);
+            }
+        }
+        HashMap::new()
+    }
+
+    /// Parse `fstab`-formatted mount lines; later mounts over the same point win
+    fn parse_mount_table(mounts: &str) -> HashMap<String, Vec<String>> {
+        let unescape = |field: &str| {
+            field
+                .replace("\\040", " ")
+                .replace("\\011", "\t")
+                .replace("\\012", "\n")
+                .replace("\\134", "\\")
+        };
+        mounts
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let mount_point = unescape(fields.nth(1)?);
+                let options = fields.nth(1)?.split(',').map(str::to_string).collect();
+                Some((mount_point, options))
+            })
+            .collect()
+    }
+}
+
+/// Physical disks, volumes and the combined per-volume view, from one scan
+#[derive(Debug, Clone, Default)]
+pub struct StorageLayout {
+    /// Per-volume view ([`HardwareInfo::storage_devices`](crate::HardwareInfo::storage_devices))
+    pub devices: Vec<StorageInfo>,
+    /// Physical disks ([`HardwareInfo::physical_disks`](crate::HardwareInfo::physical_disks))
+    pub disks: Vec<PhysicalDisk>,
+    /// Mounted volumes ([`HardwareInfo::volumes`](crate::HardwareInfo::volumes))
+    pub volumes: Vec<Volume>,
+}
+
+impl StorageLayout {
+    /// Scan disks and mounts, reading SMART data once per physical disk
+    pub(crate) fn query() -> Result<Self> {
+        let mut disks = PhysicalDisk::enumerate();
+        let mut smart_cache: HashMap<String, Option<SmartHealth>> = HashMap::new();
+        for disk in &mut disks {
+            if disk.bus == BusType::NVMe {
+                disk.nvme = NVMeInfo::for_disk(&disk.device);
+            }
+            disk.smart = SmartHealth::query(&disk.device).ok();
+            smart_cache.insert(disk.device.clone(), disk.smart.clone());
+        }
+
+        let bytes_to_gb = |bytes: u64| bytes as f64 / (1024.0 * 1024.0 * 1024.0);
+        let mut devices = Vec::new();
+        let mut volumes = Vec::new();
+        let mount_table = Volume::mount_table();
+
+        for disk in Disks::new_with_refreshed_list().list() {
+            let name = disk.name().to_string_lossy().to_string();
+            let mount_point = disk.mount_point().to_string_lossy().to_string();
+            let file_system = disk.file_system().to_string_lossy().to_string();
+            let file_system = (!file_system.is_empty()).then_some(file_system);
+
+            let device = StorageInfo::physical_device(&name, &mount_point);
+            let smart = device.as_ref().and_then(|device| {
+                smart_cache
+                    .entry(device.clone())
+                    .or_insert_with(|| SmartHealth::query(device).ok())
+                    .clone()
+            });
+            let physical = device
+                .as_ref()
+                .and_then(|device| disks.iter().find(|d| &d.device == device));
+
+            let capacity_gb = bytes_to_gb(disk.total_space());
+            let available_gb = bytes_to_gb(disk.available_space());
+            let used_gb = (capacity_gb - available_gb).max(0.0);
+
+            let (mount_options, inodes_total, inodes_free) =
+                Volume::mount_details(&mount_point, &mount_table);
+
+            volumes.push(Volume {
+                name: name.clone(),
+                mount_point: mount_point.clone(),
+                file_system: file_system.clone(),
+                total_gb: capacity_gb,
+                available_gb,
+                used_gb,
+                removable: disk.is_removable(),
+                disk: device.clone(),
+                total_bytes: disk.total_space(),
+                available_bytes: disk.available_space(),
+                mount_options,
+                inodes_total,
+                inodes_free,
+            });
+
+            devices.push(StorageInfo {
+                model: physical
+                    .map(|d| d.model.clone())
+                    .filter(|model| !model.is_empty())
+                    .or_else(|| StorageInfo::model_name(device.as_deref()))
+                    .unwrap_or_else(|| {
+                        if name.is_empty() {
+                            mount_point.clone()
+                        } else {
+                            name.clone()
+                        }
+                    }),
+                storage_type: StorageInfo::classify(&name, disk.kind(), disk.is_removable()),
+                capacity_gb,
+                available_gb,
+                used_gb,
+                mount_point,
+                file_system,
+                removable: disk.is_removable(),
+                read_speed_mb_s: None,
+                write_speed_mb_s: None,
+                device,
+                smart,
+                capacity_bytes: disk.total_space(),
+                available_bytes: disk.available_space(),
+            });
+        }
+
+        for disk in &mut disks {
+            disk.volumes = volumes
+                .iter()
+                .filter(|volume| volume.disk.as_deref() == Some(disk.device.as_str()))
+                .map(|volume| volume.mount_point.clone())
+                .collect();
+        }
+
+        Ok(Self {
+            devices,
+            disks,
+            volumes,
+        })
+    }
+}
+
 /// Storage device information
+///
+/// One entry per mounted volume, annotated with its backing disk. Use
+/// [`PhysicalDisk`] and [`Volume`] to look at disks and mounts separately.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageInfo {
     /// Device name/model
@@ -50,27 +1045,87 @@ pub struct StorageInfo {
     pub read_speed_mb_s: Option<f32>,
     /// Write speed in MB/s (if available)
     pub write_speed_mb_s: Option<f32>,
+    /// Physical disk backing this volume (e.g. `/dev/nvme0n1`, `PhysicalDrive0`, `disk0`)
+    #[serde(default)]
+    pub device: Option<String>,
+    /// SMART health of the backing disk, when it could be read
+    #[serde(default)]
+    pub smart: Option<SmartHealth>,
+    /// Total capacity in bytes
+    #[serde(default)]
+    pub capacity_bytes: u64,
+    /// Available space in bytes
+    #[serde(default)]
+    pub available_bytes: u64,
 }
 
 impl StorageInfo {
     /// Query all storage devices
     pub fn query_all() -> Result<Vec<Self>> {
-        // Note: The current version of sysinfo doesn't expose disk APIs
-        // This would be implemented using platform-specific APIs
-        let storage_devices = vec![Self {
-            model: "System Disk".to_string(),
-            storage_type: StorageType::SSD,
-            capacity_gb: 256.0,
-            available_gb: 128.0,
-            used_gb: 128.0,
-            mount_point: "C:".to_string(),
-            file_system: Some("NTFS".to_string()),
-            removable: false,
-            read_speed_mb_s: None,
-            write_speed_mb_s: None,
-        }];
-
-        Ok(storage_devices)
+        Ok(StorageLayout::query()?.devices)
+    }
+
+    fn classify(name: &str, kind: DiskKind, removable: bool) -> StorageType {
+        if name.contains("nvme") {
+            StorageType::NVMe
+        } else if name.contains("mmcblk") {
+            if removable { StorageType::SD } else { StorageType::EMmc }
+        } else if removable {
+            StorageType::USB
+        } else {
+            match kind {
+                DiskKind::SSD => StorageType::SSD,
+                DiskKind::HDD => StorageType::HDD,
+                DiskKind::Unknown(_) => StorageType::Unknown,
+            }
+        }
+    }
+
+    /// Resolve the physical disk behind a volume
+    #[allow(unused_variables)]
+    fn physical_device(name: &str, mount_point: &str) -> Option<String> {
+        #[cfg(target_os = "linux")]
+        {
+            let block = name.strip_prefix("/dev/")?;
+            let sys_path = std::path::Path::new("/sys/class/block").join(block);
+            if sys_path.join("partition").exists() {
+                let parent = std::fs::canonicalize(&sys_path).ok()?;
+                let parent = parent.parent()?.file_name()?.to_string_lossy().to_string();
+                Some(format!("/dev/{parent}"))
+            } else if sys_path.exists() {
+                Some(name.to_string())
+            } else {
+                None
+            }
+        }
+        #[cfg(target_os = "windows")]
+        {
+            windows::disk_number_for_mount(mount_point).map(|n| format!("PhysicalDrive{n}"))
+        }
+        #[cfg(target_os = "macos")]
+        {
+            macos::disk_for_mount(mount_point).or_else(|| Some(mount_point.to_string()))
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            None
+        }
+    }
+
+    #[allow(unused_variables)]
+    fn model_name(device: Option<&str>) -> Option<String> {
+        #[cfg(target_os = "linux")]
+        {
+            let block = device?.strip_prefix("/dev/")?;
+            let model = std::fs::read_to_string(format!("/sys/class/block/{block}/device/model"))
+                .ok()?;
+            let model = model.trim();
+            (!model.is_empty()).then(|| model.to_string())
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
     }
 
     /// Get device model/name
@@ -98,6 +1153,21 @@ impl StorageInfo {
         self.used_gb
     }
 
+    /// Get total capacity in bytes
+    pub fn capacity_bytes(&self) -> u64 {
+        bytes_or_gb(self.capacity_bytes, self.capacity_gb)
+    }
+
+    /// Get available space in bytes
+    pub fn available_bytes(&self) -> u64 {
+        bytes_or_gb(self.available_bytes, self.available_gb)
+    }
+
+    /// Get used space in bytes
+    pub fn used_bytes(&self) -> u64 {
+        self.capacity_bytes().saturating_sub(self.available_bytes())
+    }
+
     /// Get usage percentage
     pub fn usage_percent(&self) -> f64 {
         if self.capacity_gb > 0.0 {
@@ -107,8 +1177,445 @@ impl StorageInfo {
         }
     }
 
+    /// Get the physical disk backing this volume
+    pub fn device(&self) -> Option<&str> {
+        self.device.as_deref()
+    }
+
+    /// Get SMART health data for the backing disk
+    pub fn smart_health(&self) -> Option<&SmartHealth> {
+        self.smart.as_ref()
+    }
+
     /// Check if device has sufficient free space
     pub fn has_free_space(&self, required_gb: f64) -> bool {
         self.available_gb >= required_gb
     }
 }
+
+#[cfg(target_os = "linux")]
+pub(crate) use linux::is_physical_block_device;
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{nvme, BusType, PhysicalDisk, SmartHealth, StorageType};
+    use crate::Result;
+    use std::fs::File;
+    use std::os::fd::AsRawFd;
+    use std::path::Path;
+
+    /// Block devices that are not physical disks
+    const VIRTUAL_PREFIXES: &[&str] = &["loop", "ram", "zram", "dm-", "md", "sr", "fd", "nbd"];
+
+    /// Whether a `/sys/block` entry is a whole physical disk
+    pub(crate) fn is_physical_block_device(name: &str) -> bool {
+        !VIRTUAL_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+            && Path::new("/sys/block").join(name).exists()
+    }
+
+    fn read_trimmed(path: &Path) -> Option<String> {
+        let value = std::fs::read_to_string(path).ok()?;
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    /// Whole disks from `/sys/block`
+    pub(super) fn physical_disks() -> Vec<PhysicalDisk> {
+        let Ok(entries) = std::fs::read_dir("/sys/block") else {
+            return Vec::new();
+        };
+
+        let mut disks: Vec<PhysicalDisk> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if !is_physical_block_device(&name) {
+                    return None;
+                }
+                let sys_path = entry.path();
+                // size is always in 512-byte sectors
+                let sectors: u64 = read_trimmed(&sys_path.join("size"))?.parse().ok()?;
+                if sectors == 0 {
+                    return None;
+                }
+
+                let device_path = std::fs::canonicalize(&sys_path)
+                    .map(|path| path.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let bus = bus_type(&name, &device_path);
+                let removable = read_trimmed(&sys_path.join("removable")).as_deref() == Some("1");
+                let rotational =
+                    read_trimmed(&sys_path.join("queue/rotational")).as_deref() == Some("1");
+                let rotation_rate_rpm = rotational
+                    .then(|| std::fs::read(sys_path.join("device/vpd_pgb1")).ok())
+                    .flatten()
+                    .and_then(|page| rotation_rate(&page));
+                let trim_supported = read_trimmed(&sys_path.join("queue/discard_max_bytes"))
+                    .and_then(|bytes| bytes.parse::<u64>().ok())
+                    .map(|bytes| bytes > 0);
+
+                let storage_type = match bus {
+                    BusType::NVMe => StorageType::NVMe,
+                    BusType::MMC if removable => StorageType::SD,
+                    BusType::MMC => StorageType::EMmc,
+                    BusType::USB => StorageType::USB,
+                    _ if rotational => StorageType::HDD,
+                    _ => StorageType::SSD,
+                };
+                let ejectable =
+                    removable || matches!(storage_type, StorageType::USB | StorageType::SD);
+
+                let device = sys_path.join("device");
+                let model = read_trimmed(&device.join("model"))
+                    .or_else(|| read_trimmed(&device.join("name")))
+                    .unwrap_or_else(|| name.clone());
+                let serial_number = read_trimmed(&device.join("serial"))
+                    .or_else(|| read_trimmed(&device.join("device/serial")));
+                let firmware_version = read_trimmed(&device.join("firmware_rev"))
+                    .or_else(|| read_trimmed(&device.join("rev")))
+                    .or_else(|| read_trimmed(&device.join("device/firmware_rev")));
+
+                Some(PhysicalDisk {
+                    device: format!("/dev/{name}"),
+                    model,
+                    serial_number,
+                    firmware_version,
+                    bus,
+                    storage_type,
+                    capacity_bytes: sectors * 512,
+                    removable,
+                    ejectable,
+                    smart: None,
+                    volumes: Vec::new(),
+                    nvme: None,
+                    rotation_rate_rpm,
+                    trim_supported,
+                })
+            })
+            .collect();
+
+        disks.sort_by(|a, b| a.device.cmp(&b.device));
+        disks
+    }
+
+    /// Medium rotation rate from the Block Device Characteristics VPD page (0xB1)
+    ///
+    /// 0 means not reported and 1 a non-rotating medium; anything else is the RPM.
+    fn rotation_rate(page: &[u8]) -> Option<u32> {
+        let rate = u16::from_be_bytes([*page.get(4)?, *page.get(5)?]);
+        (rate > 1 && rate != 0xFFFF).then_some(rate as u32)
+    }
+
+    /// Bus type from the block device name and its resolved sysfs path
+    fn bus_type(name: &str, device_path: &str) -> BusType {
+        if name.starts_with("nvme") {
+            BusType::NVMe
+        } else if name.starts_with("mmcblk") {
+            BusType::MMC
+        } else if device_path.contains("/usb") {
+            BusType::USB
+        } else if name.starts_with("vd") || name.starts_with("xvd") || device_path.contains("/virtio") {
+            BusType::Virtual
+        } else if device_path.contains("/ata") {
+            BusType::SATA
+        } else if device_path.contains("/end_device-") {
+            BusType::SAS
+        } else if name.starts_with("sd") {
+            BusType::SCSI
+        } else {
+            BusType::Unknown
+        }
+    }
+
+    /// `struct nvme_admin_cmd` from `<linux/nvme_ioctl.h>`
+    #[repr(C)]
+    #[derive(Default)]
+    struct NvmeAdminCmd {
+        opcode: u8,
+        flags: u8,
+        rsvd1: u16,
+        nsid: u32,
+        cdw2: u32,
+        cdw3: u32,
+        metadata: u64,
+        addr: u64,
+        metadata_len: u32,
+        data_len: u32,
+        cdw10: u32,
+        cdw11: u32,
+        cdw12: u32,
+        cdw13: u32,
+        cdw14: u32,
+        cdw15: u32,
+        timeout_ms: u32,
+        result: u32,
+    }
+
+    /// `_IOWR('N', 0x41, struct nvme_admin_cmd)`
+    const NVME_IOCTL_ADMIN_CMD: libc::c_ulong = 0xC048_4E41;
+    const NVME_ADMIN_GET_LOG_PAGE: u8 = 0x02;
+    pub(super) const NVME_ADMIN_IDENTIFY: u8 = 0x06;
+    const NVME_LOG_SMART: u32 = 0x02;
+
+    /// Issue an NVMe admin command that reads into `data`
+    pub(super) fn nvme_admin(
+        device: &str,
+        opcode: u8,
+        nsid: u32,
+        cdw10: u32,
+        data: &mut [u8],
+    ) -> Result<()> {
+        let file = File::open(device)?;
+        let mut cmd = NvmeAdminCmd {
+            opcode,
+            nsid,
+            addr: data.as_mut_ptr() as u64,
+            data_len: data.len() as u32,
+            cdw10,
+            ..Default::default()
+        };
+
+        // SAFETY: cmd and data outlive the call and data_len matches the buffer size
+        let ret = unsafe { libc::ioctl(file.as_raw_fd(), NVME_IOCTL_ADMIN_CMD as _, &mut cmd) };
+        if ret != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        Ok(())
+    }
+
+    /// Read the NVMe SMART / Health Information log page directly from the controller
+    pub(super) fn read_nvme_smart_log(device: &str) -> Result<SmartHealth> {
+        let mut log = [0u8; nvme::SMART_LOG_LEN];
+        let numd = (nvme::SMART_LOG_LEN / 4 - 1) as u32;
+        nvme_admin(
+            device,
+            NVME_ADMIN_GET_LOG_PAGE,
+            0xFFFF_FFFF,
+            (numd << 16) | NVME_LOG_SMART,
+            &mut log,
+        )?;
+        Ok(nvme::parse_smart_log(&log))
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use super::{BusType, PhysicalDisk, StorageType};
+    use std::collections::HashMap;
+    use wmi::{Variant, WMIConnection};
+
+    /// Physical disks from the storage stack, or `MSFT_PhysicalDisk` if no disk answers
+    pub(super) fn physical_disks() -> Vec<PhysicalDisk> {
+        let disks = super::ioctl::windows::physical_disks();
+        if disks.is_empty() {
+            wmi_physical_disks()
+        } else {
+            disks
+        }
+    }
+
+    /// Physical disks from `MSFT_PhysicalDisk`
+    fn wmi_physical_disks() -> Vec<PhysicalDisk> {
+        let Some(wmi_con) = crate::probe::com_library()
+            .ok()
+            .and_then(|com| {
+                WMIConnection::with_namespace_path(r"root\Microsoft\Windows\Storage", com).ok()
+            })
+        else {
+            return Vec::new();
+        };
+
+        let disks: Vec<HashMap<String, Variant>> = wmi_con
+            .raw_query(
+                "SELECT DeviceId, FriendlyName, SerialNumber, FirmwareVersion, Size, BusType, MediaType, SpindleSpeed FROM MSFT_PhysicalDisk",
+            )
+            .unwrap_or_default();
+
+        let text = |disk: &HashMap<String, Variant>, key: &str| match disk.get(key) {
+            Some(Variant::String(s)) if !s.trim().is_empty() => Some(s.trim().to_string()),
+            _ => None,
+        };
+        let number = |disk: &HashMap<String, Variant>, key: &str| match disk.get(key) {
+            Some(Variant::UI2(v)) => Some(*v as u64),
+            Some(Variant::UI4(v)) => Some(*v as u64),
+            Some(Variant::UI8(v)) => Some(*v),
+            Some(Variant::String(v)) => v.parse().ok(),
+            _ => None,
+        };
+
+        disks
+            .iter()
+            .filter_map(|disk| {
+                let id = text(disk, "DeviceId")?;
+                let bus = number(disk, "BusType").map_or(BusType::Unknown, super::ioctl::bus_type);
+                // MediaType: 3 = HDD, 4 = SSD, 5 = SCM
+                let storage_type = match (bus, number(disk, "MediaType")) {
+                    (BusType::NVMe, _) => StorageType::NVMe,
+                    (BusType::USB, _) => StorageType::USB,
+                    (BusType::MMC, _) => StorageType::SD,
+                    (_, Some(3)) => StorageType::HDD,
+                    (_, Some(4)) | (_, Some(5)) => StorageType::SSD,
+                    _ => StorageType::Unknown,
+                };
+                // 0 for solid-state media, u32::MAX when unknown
+                let rotation_rate_rpm = number(disk, "SpindleSpeed")
+                    .filter(|&rpm| rpm > 0 && rpm < u32::MAX as u64)
+                    .map(|rpm| rpm as u32);
+
+                Some(PhysicalDisk {
+                    device: format!("PhysicalDrive{id}"),
+                    model: text(disk, "FriendlyName").unwrap_or_else(|| format!("Disk {id}")),
+                    serial_number: text(disk, "SerialNumber"),
+                    firmware_version: text(disk, "FirmwareVersion"),
+                    bus,
+                    storage_type,
+                    capacity_bytes: number(disk, "Size").unwrap_or(0),
+                    removable: matches!(bus, BusType::USB | BusType::MMC),
+                    ejectable: matches!(bus, BusType::USB | BusType::MMC),
+                    smart: None,
+                    volumes: Vec::new(),
+                    nvme: None,
+                    rotation_rate_rpm,
+                    trim_supported: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Disk number of the partition mounted at a drive letter (e.g. `C:\`)
+    pub(super) fn disk_number_for_mount(mount_point: &str) -> Option<u32> {
+        let letter = mount_point.chars().next()?.to_ascii_uppercase();
+        let wmi_con = WMIConnection::with_namespace_path(
+            r"root\Microsoft\Windows\Storage",
+            crate::probe::com_library().ok()?,
+        )
+        .ok()?;
+
+        let partitions: Vec<HashMap<String, Variant>> = wmi_con
+            .raw_query("SELECT DriveLetter, DiskNumber FROM MSFT_Partition")
+            .ok()?;
+
+        partitions.iter().find_map(|partition| {
+            let drive_letter = match partition.get("DriveLetter") {
+                Some(Variant::UI2(c)) => char::from_u32(*c as u32),
+                Some(Variant::String(s)) => s.chars().next(),
+                _ => None,
+            }?;
+            if drive_letter.to_ascii_uppercase() != letter {
+                return None;
+            }
+            match partition.get("DiskNumber") {
+                Some(Variant::UI4(n)) => Some(*n),
+                _ => None,
+            }
+        })
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::{BusType, PhysicalDisk, StorageType};
+    use crate::probe::Command;
+    use std::collections::HashMap;
+
+    /// `diskutil info` output as key/value pairs
+    fn diskutil_info(target: &str) -> Option<HashMap<String, String>> {
+        let output = Command::new("diskutil").args(["info", target]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| {
+                    let (key, value) = line.split_once(':')?;
+                    Some((key.trim().to_string(), value.trim().to_string()))
+                })
+                .collect(),
+        )
+    }
+
+    /// Whole disk identifier without the partition suffix (`disk0s2` -> `disk0`)
+    fn whole_disk(identifier: &str) -> String {
+        let identifier = identifier.trim_start_matches("/dev/");
+        match identifier[4.min(identifier.len())..].find('s') {
+            Some(index) => identifier[..4 + index].to_string(),
+            None => identifier.to_string(),
+        }
+    }
+
+    /// Physical disk holding the volume mounted at `mount_point`
+    ///
+    /// APFS volumes live on a synthesized container disk, so follow its
+    /// physical store back to the real device.
+    pub(super) fn disk_for_mount(mount_point: &str) -> Option<String> {
+        let info = diskutil_info(mount_point)?;
+        let store = info
+            .get("APFS Physical Store")
+            .or_else(|| info.get("Part of Whole"))?;
+        Some(whole_disk(store))
+    }
+
+    /// Physical disks listed by `diskutil list physical`
+    pub(super) fn physical_disks() -> Vec<PhysicalDisk> {
+        let Ok(output) = Command::new("diskutil").args(["list", "physical"]).output() else {
+            return Vec::new();
+        };
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.strip_prefix("/dev/"))
+            .filter_map(|line| line.split_whitespace().next())
+            .filter_map(|identifier| {
+                let info = diskutil_info(identifier)?;
+                let protocol = info.get("Protocol").map(String::as_str).unwrap_or_default();
+                let bus = match protocol {
+                    "PCI-Express" | "NVMe" => BusType::NVMe,
+                    "Apple Fabric" => BusType::AppleFabric,
+                    "SATA" => BusType::SATA,
+                    "USB" => BusType::USB,
+                    "Secure Digital" => BusType::MMC,
+                    "Disk Image" | "Virtual Interface" => BusType::Virtual,
+                    _ => BusType::Unknown,
+                };
+                let removable = info
+                    .get("Removable Media")
+                    .is_some_and(|value| value != "Fixed");
+                let solid_state = info.get("Solid State").map(String::as_str);
+                let storage_type = match (bus, solid_state) {
+                    (BusType::NVMe | BusType::AppleFabric, _) => StorageType::NVMe,
+                    (BusType::USB, _) => StorageType::USB,
+                    (BusType::MMC, _) => StorageType::SD,
+                    (_, Some("Yes")) => StorageType::SSD,
+                    (_, Some("No")) => StorageType::HDD,
+                    _ => StorageType::Unknown,
+                };
+                // "Disk Size: 500.3 GB (500277792768 Bytes) (exactly ...)"
+                let capacity_bytes = info
+                    .get("Disk Size")
+                    .and_then(|size| size.split_once('(')?.1.split_whitespace().next()?.parse().ok())
+                    .unwrap_or(0);
+
+                Some(PhysicalDisk {
+                    device: identifier.to_string(),
+                    model: info
+                        .get("Device / Media Name")
+                        .cloned()
+                        .unwrap_or_else(|| identifier.to_string()),
+                    serial_number: None,
+                    firmware_version: None,
+                    bus,
+                    storage_type,
+                    capacity_bytes,
+                    removable,
+                    ejectable: removable || info.get("Ejectable").is_some_and(|value| value == "Yes"),
+                    smart: None,
+                    volumes: Vec::new(),
+                    nvme: None,
+                    rotation_rate_rpm: None,
+                    trim_supported: info.get("TRIM Support").map(|value| value == "Yes"),
+                })
+            })
+            .collect()
+    }
+}
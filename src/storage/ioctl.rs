@@ -0,0 +1,248 @@
+//! Physical disk properties from the Windows storage stack
+//!
+//! `IOCTL_STORAGE_QUERY_PROPERTY` answers straight from the port driver
+//! instead of the strings WMI passes along: the device descriptor (bus type,
+//! vendor and product, revision, serial number), whether the medium incurs a
+//! seek penalty, and whether TRIM is enabled. A handle opened without any
+//! access rights is enough for these, so no elevation is needed. The
+//! rotation rate of ATA drives comes from their IDENTIFY DEVICE data, and
+//! `IOCTL_STORAGE_GET_HOTPLUG_INFO` tells hot-pluggable disks from fixed ones.
+
+use super::{BusType, PhysicalDisk, StorageType};
+
+/// `STORAGE_DEVICE_DESCRIPTOR` up to and including `BusType`
+const DEVICE_DESCRIPTOR_MIN_LEN: usize = 32;
+
+/// Map a `STORAGE_BUS_TYPE` value
+pub(super) fn bus_type(value: u64) -> BusType {
+    match value {
+        1 => BusType::SCSI,
+        3 | 11 => BusType::SATA,
+        7 => BusType::USB,
+        10 => BusType::SAS,
+        12 | 13 => BusType::MMC,
+        14 | 15 => BusType::Virtual,
+        17 => BusType::NVMe,
+        _ => BusType::Unknown,
+    }
+}
+
+/// Build a disk from a `STORAGE_DEVICE_DESCRIPTOR`
+///
+/// Capacity, TRIM and rotation rate are left for the caller; the storage
+/// type is only known here for buses that imply it.
+pub(super) fn parse_device_descriptor(device: &str, descriptor: &[u8]) -> Option<PhysicalDisk> {
+    if descriptor.len() < DEVICE_DESCRIPTOR_MIN_LEN {
+        return None;
+    }
+    let dword = |offset: usize| {
+        u32::from_le_bytes(descriptor[offset..offset + 4].try_into().expect("4-byte slice"))
+    };
+    // Offsets point at NUL-terminated ASCII; 0 means absent
+    let string = |field: usize| -> Option<String> {
+        let offset = dword(field) as usize;
+        let bytes = descriptor.get(offset..).filter(|_| offset != 0)?;
+        let end = bytes.iter().position(|&byte| byte == 0).unwrap_or(bytes.len());
+        let text = String::from_utf8_lossy(&bytes[..end]).trim().to_string();
+        (!text.is_empty()).then_some(text)
+    };
+
+    let removable = descriptor[10] != 0;
+    let bus = bus_type(dword(28) as u64);
+    // SATA drives report the SAT translation layer's "ATA" as their vendor
+    let vendor = string(12).filter(|vendor| !matches!(vendor.as_str(), "ATA" | "NVMe"));
+    let model = match (vendor, string(16)) {
+        (Some(vendor), Some(product)) if !product.starts_with(&vendor) => format!("{vendor} {product}"),
+        (_, Some(product)) => product,
+        (Some(vendor), None) => vendor,
+        (None, None) => device.to_string(),
+    };
+    let storage_type = match bus {
+        BusType::NVMe => StorageType::NVMe,
+        BusType::USB => StorageType::USB,
+        BusType::MMC if removable => StorageType::SD,
+        BusType::MMC => StorageType::EMmc,
+        _ => StorageType::Unknown,
+    };
+
+    let ejectable = removable || matches!(storage_type, StorageType::USB | StorageType::SD);
+
+    Some(PhysicalDisk {
+        device: device.to_string(),
+        model,
+        serial_number: string(24),
+        firmware_version: string(20),
+        bus,
+        storage_type,
+        capacity_bytes: 0,
+        removable,
+        ejectable,
+        smart: None,
+        volumes: Vec::new(),
+        nvme: None,
+        rotation_rate_rpm: None,
+        trim_supported: None,
+    })
+}
+
+#[cfg(target_os = "windows")]
+pub(super) mod windows {
+    use super::{BusType, PhysicalDisk, StorageType};
+    use ::windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use ::windows::Win32::Storage::FileSystem::{
+        CreateFileW, FILE_FLAGS_AND_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+    };
+    use ::windows::Win32::System::IO::DeviceIoControl;
+    use ::windows::core::HSTRING;
+
+    const IOCTL_STORAGE_QUERY_PROPERTY: u32 = 0x002D_1400;
+    const IOCTL_DISK_GET_DRIVE_GEOMETRY_EX: u32 = 0x0007_00A0;
+    const IOCTL_STORAGE_GET_HOTPLUG_INFO: u32 = 0x002D_0C14;
+    /// STORAGE_PROPERTY_ID values
+    const STORAGE_DEVICE_PROPERTY: u32 = 0;
+    const STORAGE_DEVICE_SEEK_PENALTY_PROPERTY: u32 = 7;
+    const STORAGE_DEVICE_TRIM_PROPERTY: u32 = 8;
+    const STORAGE_DEVICE_PROTOCOL_SPECIFIC_PROPERTY: u32 = 50;
+    const PROPERTY_STANDARD_QUERY: u32 = 0;
+    const PROTOCOL_TYPE_ATA: u32 = 2;
+    const ATA_DATA_TYPE_IDENTIFY: u32 = 1;
+    const ATA_IDENTIFY_LEN: usize = 512;
+    /// `STORAGE_PROPERTY_QUERY` header before `STORAGE_PROTOCOL_SPECIFIC_DATA`
+    const QUERY_HEADER_LEN: usize = 8;
+    /// `STORAGE_PROTOCOL_SPECIFIC_DATA`
+    const PROTOCOL_DATA_LEN: usize = 40;
+    /// Disk numbers probed; numbering has gaps after disks are removed
+    const MAX_PHYSICAL_DRIVES: u32 = 64;
+
+    struct Device(HANDLE);
+
+    impl Drop for Device {
+        fn drop(&mut self) {
+            // SAFETY: the handle was opened by CreateFileW and is closed once
+            let _ = unsafe { CloseHandle(self.0) };
+        }
+    }
+
+    /// Open a disk for queries only; no access rights means no elevation
+    fn open(device: &str) -> Option<Device> {
+        let path = HSTRING::from(format!(r"\\.\{device}"));
+        // SAFETY: path is a valid null-terminated wide string
+        let handle = unsafe {
+            CreateFileW(
+                &path,
+                0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                None,
+                OPEN_EXISTING,
+                FILE_FLAGS_AND_ATTRIBUTES(0),
+                None,
+            )
+        }
+        .ok()?;
+        Some(Device(handle))
+    }
+
+    /// Run `code` and return the bytes the driver wrote
+    fn ioctl(device: &Device, code: u32, input: &[u8], output_len: usize) -> Option<Vec<u8>> {
+        let mut output = vec![0u8; output_len];
+        let mut returned = 0u32;
+        // SAFETY: both buffers outlive the call and their lengths are passed
+        unsafe {
+            DeviceIoControl(
+                device.0,
+                code,
+                (!input.is_empty()).then_some(input.as_ptr().cast()),
+                input.len() as u32,
+                Some(output.as_mut_ptr().cast()),
+                output.len() as u32,
+                Some(&mut returned),
+                None,
+            )
+        }
+        .ok()?;
+        output.truncate(returned as usize);
+        Some(output)
+    }
+
+    fn query_property(device: &Device, property: u32) -> Option<Vec<u8>> {
+        // STORAGE_PROPERTY_QUERY { PropertyId, QueryType, AdditionalParameters[1] }, padded
+        let mut query = [0u8; 12];
+        query[0..4].copy_from_slice(&property.to_le_bytes());
+        query[4..8].copy_from_slice(&PROPERTY_STANDARD_QUERY.to_le_bytes());
+        ioctl(device, IOCTL_STORAGE_QUERY_PROPERTY, &query, 1024)
+    }
+
+    /// `DEVICE_SEEK_PENALTY_DESCRIPTOR` / `DEVICE_TRIM_DESCRIPTOR`: Version, Size, then the flag
+    fn flag_property(device: &Device, property: u32) -> Option<bool> {
+        query_property(device, property)?.get(8).map(|&flag| flag != 0)
+    }
+
+    /// Disk size from `DISK_GEOMETRY_EX`, whose `DiskSize` follows the 24-byte `DISK_GEOMETRY`
+    fn capacity(device: &Device) -> Option<u64> {
+        let geometry = ioctl(device, IOCTL_DISK_GET_DRIVE_GEOMETRY_EX, &[], 256)?;
+        Some(u64::from_le_bytes(geometry.get(24..32)?.try_into().ok()?))
+    }
+
+    /// Whether `STORAGE_HOTPLUG_INFO` marks the media or the whole device as hot-pluggable
+    fn hotplug(device: &Device) -> Option<bool> {
+        let info = ioctl(device, IOCTL_STORAGE_GET_HOTPLUG_INFO, &[], 8)?;
+        // Size, then MediaRemovable, MediaHotplug, DeviceHotplug
+        Some(info.get(4..7)?.iter().any(|&flag| flag != 0))
+    }
+
+    /// Nominal media rotation rate, IDENTIFY DEVICE word 217
+    fn ata_rotation_rate(device: &Device) -> Option<u32> {
+        let header = QUERY_HEADER_LEN + PROTOCOL_DATA_LEN;
+        let mut buffer = vec![0u8; header + ATA_IDENTIFY_LEN];
+        let fields = [
+            STORAGE_DEVICE_PROTOCOL_SPECIFIC_PROPERTY,
+            PROPERTY_STANDARD_QUERY,
+            PROTOCOL_TYPE_ATA,
+            ATA_DATA_TYPE_IDENTIFY,
+            0,
+            0,
+            PROTOCOL_DATA_LEN as u32, // ProtocolDataOffset, relative to the protocol data
+            ATA_IDENTIFY_LEN as u32,
+        ];
+        for (index, value) in fields.iter().enumerate() {
+            buffer[index * 4..index * 4 + 4].copy_from_slice(&value.to_le_bytes());
+        }
+        let response = ioctl(device, IOCTL_STORAGE_QUERY_PROPERTY, &buffer, buffer.len())?;
+
+        // STORAGE_PROTOCOL_DATA_DESCRIPTOR: Version, Size, then the protocol data
+        let word = |index: usize| -> Option<usize> {
+            Some(u32::from_le_bytes(response.get(8 + index * 4..12 + index * 4)?.try_into().ok()?) as usize)
+        };
+        let identify = response.get(8 + word(4)?..)?;
+        // 0 = not reported, 1 = non-rotating medium
+        let rate = u16::from_le_bytes([*identify.get(434)?, *identify.get(435)?]);
+        (rate > 1 && rate != 0xFFFF).then_some(rate as u32)
+    }
+
+    /// Every `PhysicalDriveN` that answers a device descriptor query
+    pub(in crate::storage) fn physical_disks() -> Vec<PhysicalDisk> {
+        (0..MAX_PHYSICAL_DRIVES)
+            .filter_map(|number| {
+                let name = format!("PhysicalDrive{number}");
+                let device = open(&name)?;
+                let descriptor = query_property(&device, STORAGE_DEVICE_PROPERTY)?;
+                let mut disk = super::parse_device_descriptor(&name, &descriptor)?;
+
+                disk.capacity_bytes = capacity(&device).unwrap_or(0);
+                disk.trim_supported = flag_property(&device, STORAGE_DEVICE_TRIM_PROPERTY);
+                disk.ejectable |= hotplug(&device).unwrap_or(false);
+                if disk.storage_type == StorageType::Unknown && disk.bus != BusType::Virtual {
+                    disk.storage_type = match flag_property(&device, STORAGE_DEVICE_SEEK_PENALTY_PROPERTY) {
+                        Some(true) => StorageType::HDD,
+                        Some(false) => StorageType::SSD,
+                        None => StorageType::Unknown,
+                    };
+                }
+                if disk.storage_type == StorageType::HDD {
+                    disk.rotation_rate_rpm = ata_rotation_rate(&device);
+                }
+                Some(disk)
+            })
+            .collect()
+    }
+}
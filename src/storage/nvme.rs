@@ -0,0 +1,556 @@
+//! NVMe controller and namespace details
+//!
+//! Linux reads `/sys/class/nvme` (and the controller's hwmon sensor) and uses
+//! admin commands for what sysfs does not expose. Windows sends Identify and
+//! Get Log Page requests through `IOCTL_STORAGE_QUERY_PROPERTY`, which needs an
+//! elevated process. Other platforms report no NVMe details.
+
+use super::SmartHealth;
+use crate::{PCIeLink, Result};
+use serde::{Deserialize, Serialize};
+
+/// Size of the SMART / Health Information log page
+pub(crate) const SMART_LOG_LEN: usize = 512;
+
+/// Critical warning bits that predict failure: available spare below its
+/// threshold (0), reliability degraded (2), media read-only (3) and volatile
+/// memory backup failed (4)
+pub(crate) const CRITICAL_WARNING_FAILURE_MASK: u8 = 0b1_1101;
+
+/// Critical warning bit for a temperature outside the thresholds, which
+/// clears again once the drive is back in range
+pub(crate) const CRITICAL_WARNING_TEMPERATURE: u8 = 0b10;
+
+/// Size of an Identify data structure
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+const IDENTIFY_LEN: usize = 4096;
+
+/// An NVMe namespace (the unit the OS sees as a block device)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NVMeNamespace {
+    /// Namespace ID
+    pub id: u32,
+    /// Block device for the namespace (`/dev/nvme0n1`, `PhysicalDrive1`), if any
+    pub device: Option<String>,
+    /// Capacity in bytes
+    pub capacity_bytes: u64,
+    /// Logical block size of the active LBA format in bytes
+    pub lba_size_bytes: u32,
+    /// Metadata bytes per logical block of the active LBA format
+    pub metadata_bytes: u16,
+}
+
+impl NVMeNamespace {
+    /// Get namespace ID
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Get block device
+    pub fn device(&self) -> Option<&str> {
+        self.device.as_deref()
+    }
+
+    /// Get capacity in GB
+    pub fn capacity_gb(&self) -> f64 {
+        self.capacity_bytes as f64 / (1024.0 * 1024.0 * 1024.0)
+    }
+
+    /// Get capacity in bytes
+    pub fn capacity_bytes(&self) -> u64 {
+        self.capacity_bytes
+    }
+
+    /// Get logical block size in bytes
+    pub fn lba_size_bytes(&self) -> u32 {
+        self.lba_size_bytes
+    }
+
+    /// Get metadata bytes per logical block
+    pub fn metadata_bytes(&self) -> u16 {
+        self.metadata_bytes
+    }
+}
+
+/// NVMe controller information
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NVMeInfo {
+    /// Controller name (`nvme0`, `PhysicalDrive0`)
+    pub controller: String,
+    /// Controller model
+    pub model: String,
+    /// Serial number
+    pub serial_number: Option<String>,
+    /// Firmware revision
+    pub firmware_version: Option<String>,
+    /// PCIe link the controller trained at (Linux)
+    pub pcie_link: Option<PCIeLink>,
+    /// Number of namespaces the controller supports (Identify Controller NN)
+    pub max_namespaces: Option<u32>,
+    /// Namespaces visible to the OS
+    pub namespaces: Vec<NVMeNamespace>,
+    /// Composite temperature in Celsius
+    pub composite_temperature_celsius: Option<f32>,
+}
+
+impl NVMeInfo {
+    /// Query all NVMe controllers
+    pub fn query_all() -> Result<Vec<Self>> {
+        #[cfg(target_os = "linux")]
+        {
+            linux::controllers()
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Ok(super::PhysicalDisk::enumerate()
+                .iter()
+                .filter(|disk| disk.bus == super::BusType::NVMe)
+                .filter_map(|disk| windows::query(&disk.device))
+                .collect())
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        {
+            Ok(Vec::new())
+        }
+    }
+
+    /// NVMe details for the controller behind a physical disk
+    #[allow(unused_variables)]
+    pub(crate) fn for_disk(device: &str) -> Option<Self> {
+        #[cfg(target_os = "linux")]
+        {
+            linux::query(&linux::controller_of(device)?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            windows::query(device)
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        {
+            None
+        }
+    }
+
+    /// Get controller name
+    pub fn controller(&self) -> &str {
+        &self.controller
+    }
+
+    /// Get controller model
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Get serial number
+    pub fn serial_number(&self) -> Option<&str> {
+        self.serial_number.as_deref()
+    }
+
+    /// Get firmware revision
+    pub fn firmware_version(&self) -> Option<&str> {
+        self.firmware_version.as_deref()
+    }
+
+    /// Get PCIe link
+    pub fn pcie_link(&self) -> Option<&PCIeLink> {
+        self.pcie_link.as_ref()
+    }
+
+    /// Get number of namespaces visible to the OS
+    pub fn namespace_count(&self) -> usize {
+        self.namespaces.len()
+    }
+
+    /// Get namespaces
+    pub fn namespaces(&self) -> &[NVMeNamespace] {
+        &self.namespaces
+    }
+
+    /// Get composite temperature in Celsius
+    pub fn composite_temperature_celsius(&self) -> Option<f32> {
+        self.composite_temperature_celsius
+    }
+}
+
+/// Decode the fields of the 512-byte NVMe SMART log this crate reports
+pub(crate) fn parse_smart_log(log: &[u8; SMART_LOG_LEN]) -> SmartHealth {
+    // 128-bit little-endian counters; the low 64 bits are plenty in practice
+    let counter = |offset: usize| {
+        u64::from_le_bytes(log[offset..offset + 8].try_into().expect("8-byte slice"))
+    };
+    let critical_warning = log[0];
+    let failure = critical_warning & CRITICAL_WARNING_FAILURE_MASK != 0;
+
+    SmartHealth {
+        passed: Some(!failure),
+        predicted_failure: failure,
+        temperature_warning: critical_warning & CRITICAL_WARNING_TEMPERATURE != 0,
+        reallocated_sectors: None,
+        percentage_used: Some(log[5]),
+        power_on_hours: Some(counter(128)),
+        media_errors: Some(counter(160)),
+        temperature_celsius: composite_temperature(log),
+    }
+}
+
+/// Composite temperature (bytes 1-2, Kelvin) of a SMART log page
+pub(crate) fn composite_temperature(log: &[u8; SMART_LOG_LEN]) -> Option<f32> {
+    let kelvin = u16::from_le_bytes([log[1], log[2]]);
+    (kelvin > 0).then_some(kelvin as f32 - 273.15)
+}
+
+/// Fields of an Identify Controller data structure
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+struct IdentifyController {
+    serial_number: String,
+    model: String,
+    firmware_version: String,
+    max_namespaces: u32,
+}
+
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn parse_identify_controller(data: &[u8; IDENTIFY_LEN]) -> IdentifyController {
+    let text = |range: std::ops::Range<usize>| {
+        String::from_utf8_lossy(&data[range]).trim().to_string()
+    };
+    IdentifyController {
+        serial_number: text(4..24),
+        model: text(24..64),
+        firmware_version: text(64..72),
+        max_namespaces: u32::from_le_bytes(data[516..520].try_into().expect("4-byte slice")),
+    }
+}
+
+/// Capacity and active LBA format from an Identify Namespace data structure
+#[cfg(target_os = "windows")]
+fn parse_identify_namespace(id: u32, data: &[u8; IDENTIFY_LEN]) -> Option<NVMeNamespace> {
+    let blocks = u64::from_le_bytes(data[0..8].try_into().expect("8-byte slice"));
+    // FLBAS bits 3:0 plus bits 6:5 as the upper index bits
+    let flbas = data[26];
+    let index = ((flbas & 0x0F) | ((flbas & 0x60) >> 1)) as usize;
+    let format = data.get(128 + index * 4..132 + index * 4)?;
+    let metadata_bytes = u16::from_le_bytes([format[0], format[1]]);
+    let lba_shift = format[2];
+    if blocks == 0 || !(9..32).contains(&lba_shift) {
+        return None;
+    }
+    let lba_size_bytes = 1u32 << lba_shift;
+
+    Some(NVMeNamespace {
+        id,
+        device: None,
+        capacity_bytes: blocks * lba_size_bytes as u64,
+        lba_size_bytes,
+        metadata_bytes,
+    })
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{IDENTIFY_LEN, NVMeInfo, NVMeNamespace, SMART_LOG_LEN};
+    use crate::{PCIeLink, Result};
+    use std::fs;
+    use std::path::Path;
+
+    const NVME_CLASS: &str = "/sys/class/nvme";
+
+    fn read_trimmed(path: &Path) -> Option<String> {
+        let value = fs::read_to_string(path).ok()?;
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    pub(super) fn controllers() -> Result<Vec<NVMeInfo>> {
+        let Ok(entries) = fs::read_dir(NVME_CLASS) else {
+            return Ok(Vec::new());
+        };
+        let mut controllers: Vec<NVMeInfo> = entries
+            .flatten()
+            .filter_map(|entry| query(&entry.file_name().to_string_lossy()))
+            .collect();
+        controllers.sort_by(|a, b| a.controller.cmp(&b.controller));
+        Ok(controllers)
+    }
+
+    /// Controller (`nvme0`) behind a namespace block device (`/dev/nvme0n1`)
+    pub(super) fn controller_of(device: &str) -> Option<String> {
+        let block = device.strip_prefix("/dev/")?;
+        let sys_device = fs::canonicalize(Path::new("/sys/block").join(block).join("device")).ok();
+        if let Some(name) = sys_device
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .filter(|name| Path::new(NVME_CLASS).join(name).exists())
+        {
+            return Some(name);
+        }
+        // nvme0n1 -> nvme0
+        let rest = block.strip_prefix("nvme")?;
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        (digits > 0).then(|| format!("nvme{}", &rest[..digits]))
+    }
+
+    pub(super) fn query(controller: &str) -> Option<NVMeInfo> {
+        let base = Path::new(NVME_CLASS).join(controller);
+        if !base.exists() {
+            return None;
+        }
+
+        let identify = {
+            let mut data = [0u8; IDENTIFY_LEN];
+            // CNS 1 = Identify Controller
+            crate::storage::linux::nvme_admin(
+                &format!("/dev/{controller}"),
+                crate::storage::linux::NVME_ADMIN_IDENTIFY,
+                0,
+                1,
+                &mut data,
+            )
+            .ok()
+            .map(|()| super::parse_identify_controller(&data))
+        };
+
+        let pcie_link = fs::canonicalize(base.join("device"))
+            .ok()
+            .and_then(|device| PCIeLink::from_sysfs(&device));
+
+        Some(NVMeInfo {
+            controller: controller.to_string(),
+            model: read_trimmed(&base.join("model"))
+                .or_else(|| identify.as_ref().map(|id| id.model.clone()))
+                .unwrap_or_else(|| controller.to_string()),
+            serial_number: read_trimmed(&base.join("serial"))
+                .or_else(|| identify.as_ref().map(|id| id.serial_number.clone())),
+            firmware_version: read_trimmed(&base.join("firmware_rev"))
+                .or_else(|| identify.as_ref().map(|id| id.firmware_version.clone())),
+            pcie_link,
+            max_namespaces: identify.map(|id| id.max_namespaces),
+            namespaces: namespaces(controller, &base),
+            composite_temperature_celsius: hwmon_temperature(&base)
+                .or_else(|| smart_temperature(controller)),
+        })
+    }
+
+    /// Namespaces listed under the controller (`nvme0n1`, or `nvme0c0n1` with multipath)
+    fn namespaces(controller: &str, base: &Path) -> Vec<NVMeNamespace> {
+        let Ok(entries) = fs::read_dir(base) else {
+            return Vec::new();
+        };
+
+        let mut namespaces: Vec<NVMeNamespace> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let suffix = name.strip_prefix(controller)?;
+                let suffix = match suffix.strip_prefix('c') {
+                    Some(rest) => &rest[rest.find('n')?..],
+                    None => suffix,
+                };
+                let index: u32 = suffix.strip_prefix('n')?.parse().ok()?;
+                let path = entry.path();
+
+                let sectors: u64 = read_trimmed(&path.join("size"))?.parse().ok()?;
+                let device = format!("/dev/{name}");
+                Some(NVMeNamespace {
+                    id: read_trimmed(&path.join("nsid"))
+                        .and_then(|id| id.parse().ok())
+                        .unwrap_or(index),
+                    device: Path::new(&device).exists().then_some(device),
+                    capacity_bytes: sectors * 512,
+                    lba_size_bytes: read_trimmed(&path.join("queue/logical_block_size"))
+                        .and_then(|size| size.parse().ok())
+                        .unwrap_or(512),
+                    metadata_bytes: read_trimmed(&path.join("metadata_bytes"))
+                        .and_then(|size| size.parse().ok())
+                        .unwrap_or(0),
+                })
+            })
+            .collect();
+
+        namespaces.sort_by_key(|namespace| namespace.id);
+        namespaces
+    }
+
+    /// Composite temperature from the controller's hwmon sensor (`temp1_input`)
+    fn hwmon_temperature(base: &Path) -> Option<f32> {
+        [base.to_path_buf(), base.join("device/hwmon")]
+            .iter()
+            .filter_map(|dir| fs::read_dir(dir).ok())
+            .flat_map(|entries| entries.flatten())
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("hwmon"))
+            .find_map(|entry| read_trimmed(&entry.path().join("temp1_input")))
+            .and_then(|millidegrees| millidegrees.parse::<f32>().ok())
+            .map(|millidegrees| millidegrees / 1000.0)
+    }
+
+    /// Composite temperature from the SMART log page (needs root)
+    fn smart_temperature(controller: &str) -> Option<f32> {
+        let mut log = [0u8; SMART_LOG_LEN];
+        let numd = (SMART_LOG_LEN / 4 - 1) as u32;
+        // Get Log Page, log identifier 2 (SMART / Health Information)
+        crate::storage::linux::nvme_admin(
+            &format!("/dev/{controller}"),
+            0x02,
+            0xFFFF_FFFF,
+            (numd << 16) | 0x02,
+            &mut log,
+        )
+        .ok()?;
+        super::composite_temperature(&log)
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use super::{IDENTIFY_LEN, NVMeInfo, SMART_LOG_LEN};
+    use ::windows::Win32::Foundation::{CloseHandle, GENERIC_READ, GENERIC_WRITE, HANDLE};
+    use ::windows::Win32::Storage::FileSystem::{
+        CreateFileW, FILE_FLAGS_AND_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+    };
+    use ::windows::Win32::System::IO::DeviceIoControl;
+    use ::windows::core::HSTRING;
+
+    const IOCTL_STORAGE_QUERY_PROPERTY: u32 = 0x002D_1400;
+    /// STORAGE_PROPERTY_ID values
+    const STORAGE_ADAPTER_PROTOCOL_SPECIFIC_PROPERTY: u32 = 49;
+    const STORAGE_DEVICE_PROTOCOL_SPECIFIC_PROPERTY: u32 = 50;
+    const PROPERTY_STANDARD_QUERY: u32 = 0;
+    const PROTOCOL_TYPE_NVME: u32 = 3;
+    const NVME_DATA_TYPE_IDENTIFY: u32 = 1;
+    const NVME_DATA_TYPE_LOG_PAGE: u32 = 2;
+
+    /// `STORAGE_PROPERTY_QUERY` header before `STORAGE_PROTOCOL_SPECIFIC_DATA`
+    const QUERY_HEADER_LEN: usize = 8;
+    /// `STORAGE_PROTOCOL_SPECIFIC_DATA`
+    const PROTOCOL_DATA_LEN: usize = 40;
+
+    struct Device(HANDLE);
+
+    impl Drop for Device {
+        fn drop(&mut self) {
+            // SAFETY: the handle was opened by CreateFileW and is closed once
+            let _ = unsafe { CloseHandle(self.0) };
+        }
+    }
+
+    fn open(device: &str) -> Option<Device> {
+        let path = HSTRING::from(format!(r"\\.\{device}"));
+        // SAFETY: path is a valid null-terminated wide string
+        let handle = unsafe {
+            CreateFileW(
+                &path,
+                (GENERIC_READ | GENERIC_WRITE).0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                None,
+                OPEN_EXISTING,
+                FILE_FLAGS_AND_ATTRIBUTES(0),
+                None,
+            )
+        }
+        .ok()?;
+        Some(Device(handle))
+    }
+
+    /// Run a protocol-specific NVMe query and return its data payload
+    fn query_protocol(
+        device: &Device,
+        property: u32,
+        data_type: u32,
+        request: u32,
+        sub_request: u32,
+        len: usize,
+    ) -> Option<Vec<u8>> {
+        let header = QUERY_HEADER_LEN + PROTOCOL_DATA_LEN;
+        let mut buffer = vec![0u8; header + len];
+        let fields = [
+            property,
+            PROPERTY_STANDARD_QUERY,
+            PROTOCOL_TYPE_NVME,
+            data_type,
+            request,
+            sub_request,
+            PROTOCOL_DATA_LEN as u32, // ProtocolDataOffset, relative to the protocol data
+            len as u32,
+        ];
+        for (index, value) in fields.iter().enumerate() {
+            buffer[index * 4..index * 4 + 4].copy_from_slice(&value.to_le_bytes());
+        }
+
+        let mut returned = 0u32;
+        // SAFETY: buffer is used for both input and output and its length is passed
+        unsafe {
+            DeviceIoControl(
+                device.0,
+                IOCTL_STORAGE_QUERY_PROPERTY,
+                Some(buffer.as_ptr().cast()),
+                buffer.len() as u32,
+                Some(buffer.as_mut_ptr().cast()),
+                buffer.len() as u32,
+                Some(&mut returned),
+                None,
+            )
+        }
+        .ok()?;
+
+        // STORAGE_PROTOCOL_DATA_DESCRIPTOR: Version, Size, then the protocol data
+        let protocol = &buffer[8..8 + PROTOCOL_DATA_LEN];
+        let word = |index: usize| {
+            u32::from_le_bytes(protocol[index * 4..index * 4 + 4].try_into().expect("4-byte slice"))
+        };
+        let (offset, length) = (word(4) as usize, word(5) as usize);
+        buffer.get(8 + offset..8 + offset + length.min(len)).map(<[u8]>::to_vec)
+    }
+
+    pub(super) fn query(device: &str) -> Option<NVMeInfo> {
+        let handle = open(device)?;
+
+        let controller = query_protocol(
+            &handle,
+            STORAGE_ADAPTER_PROTOCOL_SPECIFIC_PROPERTY,
+            NVME_DATA_TYPE_IDENTIFY,
+            1, // CNS 1 = Identify Controller
+            0,
+            IDENTIFY_LEN,
+        )
+        .and_then(|data| <[u8; IDENTIFY_LEN]>::try_from(data.as_slice()).ok())
+        .map(|data| super::parse_identify_controller(&data))?;
+
+        // Each namespace is its own PhysicalDrive; the driver answers for that one
+        let namespace = query_protocol(
+            &handle,
+            STORAGE_DEVICE_PROTOCOL_SPECIFIC_PROPERTY,
+            NVME_DATA_TYPE_IDENTIFY,
+            0, // CNS 0 = Identify Namespace
+            1,
+            IDENTIFY_LEN,
+        )
+        .and_then(|data| <[u8; IDENTIFY_LEN]>::try_from(data.as_slice()).ok())
+        .and_then(|data| super::parse_identify_namespace(1, &data))
+        .map(|mut namespace| {
+            namespace.device = Some(device.to_string());
+            namespace
+        });
+
+        let temperature = query_protocol(
+            &handle,
+            STORAGE_DEVICE_PROTOCOL_SPECIFIC_PROPERTY,
+            NVME_DATA_TYPE_LOG_PAGE,
+            2, // SMART / Health Information
+            0,
+            SMART_LOG_LEN,
+        )
+        .and_then(|data| <[u8; SMART_LOG_LEN]>::try_from(data.as_slice()).ok())
+        .and_then(|log| super::composite_temperature(&log));
+
+        Some(NVMeInfo {
+            controller: device.to_string(),
+            model: controller.model,
+            serial_number: Some(controller.serial_number).filter(|s| !s.is_empty()),
+            firmware_version: Some(controller.firmware_version).filter(|s| !s.is_empty()),
+            pcie_link: None,
+            max_namespaces: Some(controller.max_namespaces),
+            namespaces: namespace.into_iter().collect(),
+            composite_temperature_celsius: temperature,
+        })
+    }
+}
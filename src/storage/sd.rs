@@ -0,0 +1,331 @@
+//! SD card readers and the cards in them
+//!
+//! Linux lists built-in readers from `/sys/class/mmc_host`, where the card's
+//! CID fields and SD Status register (speed class, UHS and video speed
+//! grades) are exported, and USB readers from their block devices. Other
+//! platforms recognise readers among the physical disks; cards in USB
+//! readers only report their capacity.
+
+use super::{BusType, PhysicalDisk, StorageType};
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+/// Models under which USB card readers present themselves
+const READER_MODEL_HINTS: &[&str] = &[
+    "card reader", "cardreader", "card-reader", "sd/mmc", "sd card", "sdxc", "sdhc", "microsd",
+    "crw", "multi-card", "multicard", "mmc",
+];
+
+/// Check if a disk model names a card reader rather than a flash drive
+pub(crate) fn is_card_reader_model(model: &str) -> bool {
+    let model = model.to_lowercase();
+    READER_MODEL_HINTS.iter().any(|hint| model.contains(hint))
+}
+
+/// SD card capacity standard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SdCardType {
+    /// Standard Capacity, up to 2 GB
+    SDSC,
+    /// High Capacity, up to 32 GB
+    SDHC,
+    /// eXtended Capacity, up to 2 TB
+    SDXC,
+    /// Ultra Capacity, up to 128 TB
+    SDUC,
+}
+
+impl SdCardType {
+    /// Capacity standard a card of `capacity_bytes` belongs to
+    pub fn from_capacity(capacity_bytes: u64) -> Self {
+        const GB: u64 = 1_000_000_000;
+        match capacity_bytes {
+            bytes if bytes <= 2 * GB + GB / 10 => SdCardType::SDSC,
+            bytes if bytes <= 32 * GB + GB => SdCardType::SDHC,
+            bytes if bytes <= 2_000 * GB + 100 * GB => SdCardType::SDXC,
+            _ => SdCardType::SDUC,
+        }
+    }
+}
+
+impl std::fmt::Display for SdCardType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SdCardType::SDSC => write!(f, "SDSC"),
+            SdCardType::SDHC => write!(f, "SDHC"),
+            SdCardType::SDXC => write!(f, "SDXC"),
+            SdCardType::SDUC => write!(f, "SDUC"),
+        }
+    }
+}
+
+/// Speed ratings of an SD card, each a guaranteed minimum sequential write speed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SdSpeedClass {
+    /// Speed class (2, 4, 6 or 10 MB/s)
+    pub class: Option<u8>,
+    /// UHS speed grade (U1 = 10 MB/s, U3 = 30 MB/s)
+    pub uhs_grade: Option<u8>,
+    /// Video speed class (V6 to V90, in MB/s)
+    pub video_class: Option<u8>,
+}
+
+impl SdSpeedClass {
+    /// Ratings from the 512-bit SD Status register, most significant byte first
+    ///
+    /// Returns `None` when the register is too short or the card claims no rating.
+    pub fn from_sd_status(status: &[u8]) -> Option<Self> {
+        if status.len() < 16 {
+            return None;
+        }
+        // SPEED_CLASS [447:440], UHS_SPEED_GRADE [399:396], VIDEO_SPEED_CLASS [391:384]
+        let class = match status[8] {
+            1 => Some(2),
+            2 => Some(4),
+            3 => Some(6),
+            4 => Some(10),
+            _ => None,
+        };
+        let uhs_grade = Some(status[14] >> 4).filter(|grade| matches!(grade, 1 | 3));
+        let video_class = Some(status[15]).filter(|class| matches!(class, 6 | 10 | 30 | 60 | 90));
+
+        let speed = Self { class, uhs_grade, video_class };
+        (speed.min_write_mb_s() > 0).then_some(speed)
+    }
+
+    /// Highest guaranteed minimum write speed in MB/s across the ratings
+    pub fn min_write_mb_s(&self) -> u32 {
+        let class = self.class.unwrap_or(0) as u32;
+        let uhs = self.uhs_grade.unwrap_or(0) as u32 * 10;
+        let video = self.video_class.unwrap_or(0) as u32;
+        class.max(uhs).max(video)
+    }
+}
+
+impl std::fmt::Display for SdSpeedClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ratings: Vec<String> = [
+            self.class.map(|class| format!("C{class}")),
+            self.uhs_grade.map(|grade| format!("U{grade}")),
+            self.video_class.map(|class| format!("V{class}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        write!(f, "{}", ratings.join(" "))
+    }
+}
+
+/// A card inserted in an SD card reader
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SdCard {
+    /// Block device for the card (`/dev/mmcblk0`, `/dev/sdb`, `PhysicalDrive2`)
+    pub device: Option<String>,
+    /// Product name from the card's CID register
+    pub name: Option<String>,
+    /// Manufacturer ID from the CID register
+    pub manufacturer_id: Option<u8>,
+    /// OEM / application ID from the CID register (`SD`, `SM`)
+    pub oem_id: Option<String>,
+    /// Product serial number
+    pub serial_number: Option<String>,
+    /// Manufacturing date as `MM/YYYY`
+    pub manufacture_date: Option<String>,
+    /// Capacity in bytes
+    pub capacity_bytes: u64,
+    /// Capacity standard
+    pub card_type: SdCardType,
+    /// Speed ratings, when the reader exposes the SD Status register
+    pub speed: Option<SdSpeedClass>,
+}
+
+impl SdCard {
+    /// Get capacity in GB
+    pub fn capacity_gb(&self) -> f64 {
+        self.capacity_bytes as f64 / (1024.0 * 1024.0 * 1024.0)
+    }
+
+    /// Card with only a capacity known, as seen through a USB reader
+    fn from_capacity(device: &str, capacity_bytes: u64) -> Self {
+        Self {
+            device: Some(device.to_string()),
+            name: None,
+            manufacturer_id: None,
+            oem_id: None,
+            serial_number: None,
+            manufacture_date: None,
+            capacity_bytes,
+            card_type: SdCardType::from_capacity(capacity_bytes),
+            speed: None,
+        }
+    }
+}
+
+/// An SD card reader, built in or attached over USB
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SdCardReader {
+    /// Reader identifier (`mmc0`, `/dev/sdb`, `PhysicalDrive2`, `disk4`)
+    pub id: String,
+    /// Reader model or host controller driver
+    pub model: String,
+    /// Bus the reader is attached through (MMC for built-in readers)
+    pub bus: BusType,
+    /// Inserted card, if any
+    pub card: Option<SdCard>,
+}
+
+impl SdCardReader {
+    /// Query all SD card readers
+    ///
+    /// Empty readers are listed where the platform exposes them (Linux, and
+    /// Windows readers that keep their disk object without media).
+    pub fn query_all() -> Result<Vec<Self>> {
+        #[cfg(target_os = "linux")]
+        {
+            Ok(linux::card_readers())
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Ok(PhysicalDisk::enumerate().iter().filter_map(Self::from_disk).collect())
+        }
+    }
+
+    /// Check if a card is inserted
+    pub fn has_card(&self) -> bool {
+        self.card.is_some()
+    }
+
+    /// Reader for a physical disk that is an SD card or a card reader slot
+    #[cfg_attr(target_os = "linux", allow(dead_code))]
+    fn from_disk(disk: &PhysicalDisk) -> Option<Self> {
+        let is_reader = match disk.bus {
+            BusType::MMC => disk.storage_type == StorageType::SD,
+            BusType::USB => is_card_reader_model(&disk.model),
+            _ => false,
+        };
+        if !is_reader {
+            return None;
+        }
+        Some(Self {
+            id: disk.device.clone(),
+            model: disk.model.clone(),
+            bus: disk.bus,
+            card: (disk.capacity_bytes > 0).then(|| SdCard::from_capacity(&disk.device, disk.capacity_bytes)),
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{is_card_reader_model, BusType, SdCard, SdCardReader, SdCardType, SdSpeedClass};
+    use std::path::Path;
+
+    fn read_trimmed(path: &Path) -> Option<String> {
+        let value = std::fs::read_to_string(path).ok()?;
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    fn read_hex(path: &Path) -> Option<u64> {
+        u64::from_str_radix(read_trimmed(path)?.trim_start_matches("0x"), 16).ok()
+    }
+
+    /// Block device under `parent/block` and its size in bytes
+    fn block_device(parent: &Path) -> Option<(String, u64)> {
+        let entry = std::fs::read_dir(parent.join("block")).ok()?.flatten().next()?;
+        let sectors: u64 = read_trimmed(&entry.path().join("size"))?.parse().ok()?;
+        Some((format!("/dev/{}", entry.file_name().to_string_lossy()), sectors * 512))
+    }
+
+    /// Built-in readers from `/sys/class/mmc_host`, then USB readers from `/sys/block`
+    pub(super) fn card_readers() -> Vec<SdCardReader> {
+        let mut readers = Vec::new();
+
+        for host in std::fs::read_dir("/sys/class/mmc_host").into_iter().flatten().flatten() {
+            let host_name = host.file_name().to_string_lossy().to_string();
+            let host_path = host.path();
+            let card_path = std::fs::read_dir(&host_path)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .find(|entry| entry.file_name().to_string_lossy().starts_with(&format!("{host_name}:")))
+                .map(|entry| entry.path());
+
+            // eMMC and SDIO (Wi-Fi) devices share the host interface
+            let card = match card_path {
+                Some(path) if read_trimmed(&path.join("type")).as_deref() == Some("SD") => Some(sd_card(&path)),
+                Some(_) => continue,
+                None => None,
+            };
+            let model = std::fs::read_link(host_path.join("device/driver"))
+                .ok()
+                .and_then(|driver| Some(driver.file_name()?.to_string_lossy().to_string()))
+                .unwrap_or_else(|| host_name.clone());
+
+            readers.push(SdCardReader {
+                id: host_name,
+                model,
+                bus: BusType::MMC,
+                card,
+            });
+        }
+
+        for entry in std::fs::read_dir("/sys/block").into_iter().flatten().flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let sys_path = entry.path();
+            let on_usb = std::fs::canonicalize(&sys_path)
+                .is_ok_and(|path| path.to_string_lossy().contains("/usb"));
+            let removable = read_trimmed(&sys_path.join("removable")).as_deref() == Some("1");
+            let Some(model) = read_trimmed(&sys_path.join("device/model")) else {
+                continue;
+            };
+            if !name.starts_with("sd") || !on_usb || !removable || !is_card_reader_model(&model) {
+                continue;
+            }
+            let device = format!("/dev/{name}");
+            let capacity_bytes = read_trimmed(&sys_path.join("size"))
+                .and_then(|sectors| sectors.parse::<u64>().ok())
+                .map_or(0, |sectors| sectors * 512);
+
+            readers.push(SdCardReader {
+                card: (capacity_bytes > 0).then(|| SdCard::from_capacity(&device, capacity_bytes)),
+                id: device,
+                model,
+                bus: BusType::USB,
+            });
+        }
+
+        readers
+    }
+
+    /// Card details from its `/sys/bus/mmc/devices` entry
+    fn sd_card(path: &Path) -> SdCard {
+        let (device, capacity_bytes) = block_device(path).unzip();
+        let capacity_bytes = capacity_bytes.unwrap_or(0);
+        // OEMID is two ASCII characters packed into 16 bits
+        let oem_id = read_hex(&path.join("oemid"))
+            .map(|id| String::from_utf8_lossy(&(id as u16).to_be_bytes()).to_string())
+            .filter(|id| id.chars().all(|c| c.is_ascii_alphanumeric()));
+        // The SD Status register, as 16 big-endian words in hex
+        let speed = read_trimmed(&path.join("ssr"))
+            .filter(|hex| hex.is_ascii())
+            .map(|hex| {
+                (0..hex.len() / 2)
+                    .filter_map(|index| u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).ok())
+                    .collect::<Vec<u8>>()
+            })
+            .and_then(|status| SdSpeedClass::from_sd_status(&status));
+
+        SdCard {
+            device,
+            name: read_trimmed(&path.join("name")),
+            manufacturer_id: read_hex(&path.join("manfid")).map(|id| id as u8),
+            oem_id,
+            serial_number: read_trimmed(&path.join("serial")),
+            manufacture_date: read_trimmed(&path.join("date")),
+            capacity_bytes,
+            card_type: SdCardType::from_capacity(capacity_bytes),
+            speed,
+        }
+    }
+}
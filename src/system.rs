@@ -0,0 +1,522 @@
+//! System, baseboard and firmware identification
+//!
+//! Reads the SMBIOS/DMI tables the firmware publishes: system vendor and
+//! model, motherboard, BIOS/UEFI version and release date, chassis type, and
+//! whether Secure Boot is enforced.
+
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+/// Physical form factor of the system (SMBIOS chassis type)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ChassisType {
+    Desktop,
+    Tower,
+    MiniPC,
+    AllInOne,
+    Laptop,
+    Tablet,
+    Convertible,
+    Server,
+    RackMount,
+    Blade,
+    Embedded,
+    Other,
+    #[default]
+    Unknown,
+}
+
+impl ChassisType {
+    /// Classify an SMBIOS (type 3) chassis type code
+    pub fn from_smbios(code: u8) -> Self {
+        match code {
+            3 | 4 | 5 | 15 | 16 | 24 => ChassisType::Desktop,
+            6 | 7 => ChassisType::Tower,
+            35 | 36 => ChassisType::MiniPC,
+            13 => ChassisType::AllInOne,
+            8..=10 | 14 => ChassisType::Laptop,
+            11 | 30 => ChassisType::Tablet,
+            31 | 32 => ChassisType::Convertible,
+            17 | 25 => ChassisType::Server,
+            23 => ChassisType::RackMount,
+            28 | 29 => ChassisType::Blade,
+            33 | 34 => ChassisType::Embedded,
+            2 => ChassisType::Unknown,
+            _ => ChassisType::Other,
+        }
+    }
+
+    /// Check if this is a battery-powered, portable form factor
+    pub fn is_portable(&self) -> bool {
+        matches!(
+            self,
+            ChassisType::Laptop | ChassisType::Tablet | ChassisType::Convertible
+        )
+    }
+}
+
+impl std::fmt::Display for ChassisType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            ChassisType::Desktop => "Desktop",
+            ChassisType::Tower => "Tower",
+            ChassisType::MiniPC => "Mini PC",
+            ChassisType::AllInOne => "All-in-One",
+            ChassisType::Laptop => "Laptop",
+            ChassisType::Tablet => "Tablet",
+            ChassisType::Convertible => "Convertible",
+            ChassisType::Server => "Server",
+            ChassisType::RackMount => "Rack Mount",
+            ChassisType::Blade => "Blade",
+            ChassisType::Embedded => "Embedded",
+            ChassisType::Other => "Other",
+            ChassisType::Unknown => "Unknown",
+        };
+        f.write_str(&crate::l10n::localize("ChassisType", text))
+    }
+}
+
+/// Motherboard information
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BaseboardInfo {
+    /// Board manufacturer
+    pub manufacturer: Option<String>,
+    /// Board model
+    pub product: Option<String>,
+    /// Board revision
+    pub version: Option<String>,
+    /// Board serial number (usually requires elevated privileges)
+    pub serial_number: Option<String>,
+    /// Asset tag
+    pub asset_tag: Option<String>,
+}
+
+impl BaseboardInfo {
+    /// Get board manufacturer
+    pub fn manufacturer(&self) -> Option<&str> {
+        self.manufacturer.as_deref()
+    }
+
+    /// Get board model
+    pub fn product(&self) -> Option<&str> {
+        self.product.as_deref()
+    }
+
+    /// Get board revision
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Get board serial number
+    pub fn serial_number(&self) -> Option<&str> {
+        self.serial_number.as_deref()
+    }
+}
+
+/// BIOS / UEFI firmware information
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FirmwareInfo {
+    /// Firmware vendor
+    pub vendor: Option<String>,
+    /// Firmware version string
+    pub version: Option<String>,
+    /// Release date as `YYYY-MM-DD`
+    pub release_date: Option<String>,
+    /// Booted through UEFI rather than legacy BIOS
+    pub uefi: Option<bool>,
+    /// Secure Boot is enforcing (None if it could not be determined)
+    pub secure_boot: Option<bool>,
+}
+
+impl FirmwareInfo {
+    /// Get firmware vendor
+    pub fn vendor(&self) -> Option<&str> {
+        self.vendor.as_deref()
+    }
+
+    /// Get firmware version
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Get release date (`YYYY-MM-DD`)
+    pub fn release_date(&self) -> Option<&str> {
+        self.release_date.as_deref()
+    }
+
+    /// Check if the system booted via UEFI
+    pub fn is_uefi(&self) -> Option<bool> {
+        self.uefi
+    }
+
+    /// Check if Secure Boot is enabled
+    pub fn secure_boot(&self) -> Option<bool> {
+        self.secure_boot
+    }
+}
+
+/// System identification: vendor, model, board, firmware and chassis
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SystemInfo {
+    /// System manufacturer
+    pub manufacturer: Option<String>,
+    /// System model
+    pub product_name: Option<String>,
+    /// Product version
+    pub version: Option<String>,
+    /// Product family
+    pub family: Option<String>,
+    /// SKU number
+    pub sku: Option<String>,
+    /// System serial number (usually requires elevated privileges)
+    pub serial_number: Option<String>,
+    /// System UUID
+    pub uuid: Option<String>,
+    /// Chassis form factor
+    pub chassis_type: ChassisType,
+    /// Motherboard
+    pub baseboard: BaseboardInfo,
+    /// BIOS / UEFI firmware
+    pub firmware: FirmwareInfo,
+}
+
+impl SystemInfo {
+    /// Query system identification from the firmware tables
+    pub fn query() -> Result<Self> {
+        #[cfg(target_os = "linux")]
+        {
+            Ok(Self::query_linux())
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Self::query_windows()
+        }
+        #[cfg(target_os = "macos")]
+        {
+            Ok(Self::query_macos())
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            Ok(Self::default())
+        }
+    }
+
+    /// Get system manufacturer
+    pub fn manufacturer(&self) -> Option<&str> {
+        self.manufacturer.as_deref()
+    }
+
+    /// Get system model
+    pub fn product_name(&self) -> Option<&str> {
+        self.product_name.as_deref()
+    }
+
+    /// Get product version
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Get product family
+    pub fn family(&self) -> Option<&str> {
+        self.family.as_deref()
+    }
+
+    /// Get SKU number
+    pub fn sku(&self) -> Option<&str> {
+        self.sku.as_deref()
+    }
+
+    /// Get system serial number
+    pub fn serial_number(&self) -> Option<&str> {
+        self.serial_number.as_deref()
+    }
+
+    /// Get system UUID
+    pub fn uuid(&self) -> Option<&str> {
+        self.uuid.as_deref()
+    }
+
+    /// Get chassis form factor
+    pub fn chassis_type(&self) -> ChassisType {
+        self.chassis_type
+    }
+
+    /// Get motherboard information
+    pub fn baseboard(&self) -> &BaseboardInfo {
+        &self.baseboard
+    }
+
+    /// Get BIOS / UEFI firmware information
+    pub fn firmware(&self) -> &FirmwareInfo {
+        &self.firmware
+    }
+
+    /// Check if Secure Boot is enabled
+    pub fn secure_boot(&self) -> Option<bool> {
+        self.firmware.secure_boot
+    }
+
+    #[cfg(target_os = "linux")]
+    fn query_linux() -> Self {
+        use std::path::Path;
+
+        let dmi = |name: &str| {
+            std::fs::read_to_string(Path::new("/sys/class/dmi/id").join(name))
+                .ok()
+                .and_then(|value| meaningful(&value))
+        };
+
+        let uefi = Path::new("/sys/firmware/efi").exists();
+        let mut system = Self {
+            manufacturer: dmi("sys_vendor"),
+            product_name: dmi("product_name"),
+            version: dmi("product_version"),
+            family: dmi("product_family"),
+            sku: dmi("product_sku"),
+            serial_number: dmi("product_serial"),
+            uuid: dmi("product_uuid"),
+            chassis_type: dmi("chassis_type")
+                .and_then(|code| code.parse().ok())
+                .map(ChassisType::from_smbios)
+                .unwrap_or_default(),
+            baseboard: BaseboardInfo {
+                manufacturer: dmi("board_vendor"),
+                product: dmi("board_name"),
+                version: dmi("board_version"),
+                serial_number: dmi("board_serial"),
+                asset_tag: dmi("board_asset_tag"),
+            },
+            firmware: FirmwareInfo {
+                vendor: dmi("bios_vendor"),
+                version: dmi("bios_version"),
+                release_date: dmi("bios_date").and_then(|date| normalize_date(&date)),
+                uefi: Some(uefi),
+                // Legacy BIOS boots cannot verify signatures
+                secure_boot: if uefi { linux_secure_boot() } else { Some(false) },
+            },
+        };
+
+        // ARM boards without SMBIOS describe themselves in the device tree
+        if system.product_name.is_none() {
+            system.product_name = std::fs::read_to_string("/proc/device-tree/model")
+                .ok()
+                .and_then(|model| meaningful(model.trim_end_matches('\0')));
+        }
+
+        system
+    }
+
+    #[cfg(target_os = "windows")]
+    fn query_windows() -> Result<Self> {
+        use std::collections::HashMap;
+        use wmi::{Variant, WMIConnection};
+
+        let wmi_con = WMIConnection::new(crate::probe::com_library()?)?;
+        let first = |query: &str| {
+            wmi_con
+                .raw_query::<HashMap<String, Variant>>(query)
+                .ok()
+                .and_then(|rows| rows.into_iter().next())
+                .unwrap_or_default()
+        };
+        let text = |row: &HashMap<String, Variant>, key: &str| match row.get(key) {
+            Some(Variant::String(value)) => meaningful(value),
+            _ => None,
+        };
+
+        let computer = first("SELECT Manufacturer, Model, SystemFamily, SystemSKUNumber FROM Win32_ComputerSystem");
+        let product = first("SELECT IdentifyingNumber, UUID, Version FROM Win32_ComputerSystemProduct");
+        let board = first("SELECT Manufacturer, Product, Version, SerialNumber, Tag FROM Win32_BaseBoard");
+        let bios = first("SELECT Manufacturer, SMBIOSBIOSVersion, ReleaseDate FROM Win32_BIOS");
+        let enclosure = first("SELECT ChassisTypes FROM Win32_SystemEnclosure");
+
+        let chassis_type = match enclosure.get("ChassisTypes") {
+            Some(Variant::Array(codes)) => codes.iter().find_map(|code| match code {
+                Variant::UI2(code) => Some(ChassisType::from_smbios(*code as u8)),
+                Variant::I4(code) => Some(ChassisType::from_smbios(*code as u8)),
+                _ => None,
+            }),
+            _ => None,
+        };
+
+        // PEFirmwareType: 1 = BIOS, 2 = UEFI
+        let uefi = windows_registry_dword(r"HKLM\SYSTEM\CurrentControlSet\Control", "PEFirmwareType")
+            .map(|firmware_type| firmware_type == 2);
+        let secure_boot = match uefi {
+            Some(false) => Some(false),
+            _ => windows_registry_dword(
+                r"HKLM\SYSTEM\CurrentControlSet\Control\SecureBoot\State",
+                "UEFISecureBootEnabled",
+            )
+            .map(|enabled| enabled == 1),
+        };
+
+        Ok(Self {
+            manufacturer: text(&computer, "Manufacturer"),
+            product_name: text(&computer, "Model"),
+            version: text(&product, "Version"),
+            family: text(&computer, "SystemFamily"),
+            sku: text(&computer, "SystemSKUNumber"),
+            serial_number: text(&product, "IdentifyingNumber"),
+            uuid: text(&product, "UUID"),
+            chassis_type: chassis_type.unwrap_or_default(),
+            baseboard: BaseboardInfo {
+                manufacturer: text(&board, "Manufacturer"),
+                product: text(&board, "Product"),
+                version: text(&board, "Version"),
+                serial_number: text(&board, "SerialNumber"),
+                asset_tag: text(&board, "Tag"),
+            },
+            firmware: FirmwareInfo {
+                vendor: text(&bios, "Manufacturer"),
+                version: text(&bios, "SMBIOSBIOSVersion"),
+                release_date: text(&bios, "ReleaseDate").and_then(|date| normalize_date(&date)),
+                uefi,
+                secure_boot,
+            },
+        })
+    }
+
+    #[cfg(target_os = "macos")]
+    fn query_macos() -> Self {
+        use crate::probe::Command;
+
+        // IOPlatformExpertDevice carries the identity IOKit reads from the firmware
+        let platform = Command::new("ioreg")
+            .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+            .unwrap_or_default();
+        let property = |key: &str| {
+            let pattern = format!("\"{key}\" = ");
+            platform.lines().find_map(|line| {
+                let value = line.trim().strip_prefix(&pattern)?;
+                // Data properties print as <"text">, strings as "text"
+                let value = value.trim_start_matches('<').trim_end_matches('>');
+                meaningful(value.trim_matches('"').trim_end_matches('\0'))
+            })
+        };
+
+        let hardware = Command::new("system_profiler")
+            .args(["SPHardwareDataType", "-json"])
+            .output()
+            .ok()
+            .and_then(|output| serde_json::from_slice::<serde_json::Value>(&output.stdout).ok());
+        let hardware_field = |key: &str| {
+            hardware
+                .as_ref()?
+                .get("SPHardwareDataType")?
+                .get(0)?
+                .get(key)?
+                .as_str()
+                .and_then(meaningful)
+        };
+
+        let machine_name = hardware_field("machine_name");
+        let chassis_type = match machine_name.as_deref() {
+            Some(name) if name.starts_with("MacBook") => ChassisType::Laptop,
+            Some("iMac") | Some("iMac Pro") => ChassisType::AllInOne,
+            Some("Mac mini") => ChassisType::MiniPC,
+            Some("Mac Pro") => ChassisType::Tower,
+            Some("Mac Studio") => ChassisType::Desktop,
+            Some(_) => ChassisType::Other,
+            None => ChassisType::Unknown,
+        };
+
+        Self {
+            manufacturer: property("manufacturer"),
+            product_name: machine_name,
+            version: property("model"),
+            family: None,
+            sku: hardware_field("model_number"),
+            serial_number: property("IOPlatformSerialNumber"),
+            uuid: property("IOPlatformUUID"),
+            chassis_type,
+            // Macs report the logic board only by its board-id
+            baseboard: BaseboardInfo {
+                manufacturer: property("manufacturer"),
+                product: property("board-id"),
+                ..Default::default()
+            },
+            // Apple firmware has no SMBIOS release date and always boots via EFI/iBoot
+            firmware: FirmwareInfo {
+                vendor: Some("Apple Inc.".to_string()),
+                version: hardware_field("boot_rom_version"),
+                release_date: None,
+                uefi: Some(true),
+                secure_boot: None,
+            },
+        }
+    }
+}
+
+/// Drop the placeholder strings OEMs leave in unused SMBIOS fields
+fn meaningful(value: &str) -> Option<String> {
+    let value = value.trim();
+    let placeholder = matches!(
+        value.to_ascii_lowercase().as_str(),
+        "" | "none"
+            | "n/a"
+            | "unknown"
+            | "not specified"
+            | "not applicable"
+            | "not available"
+            | "to be filled by o.e.m."
+            | "default string"
+            | "system manufacturer"
+            | "system product name"
+            | "system version"
+            | "system serial number"
+            | "base board serial number"
+            | "0123456789"
+            | "123456789"
+    ) || value.chars().all(|c| c == '0' || c == ' ' || c == '-');
+    (!placeholder).then(|| value.to_string())
+}
+
+/// Convert SMBIOS (`MM/DD/YYYY`) or CIM (`YYYYMMDDhhmmss...`) dates to `YYYY-MM-DD`
+fn normalize_date(date: &str) -> Option<String> {
+    let date = date.trim();
+    let parts: Vec<&str> = date.split('/').collect();
+    if let [month, day, year] = parts.as_slice() {
+        let year: u32 = year.parse().ok()?;
+        // SMBIOS 2.3+ requires four-digit years; older firmware used two
+        let year = match year {
+            0..=79 => 2000 + year,
+            80..=99 => 1900 + year,
+            _ => year,
+        };
+        return Some(format!("{year:04}-{:02}-{:02}", month.parse::<u32>().ok()?, day.parse::<u32>().ok()?));
+    }
+
+    let digits = date.get(..8)?;
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("{}-{}-{}", &digits[..4], &digits[4..6], &digits[6..8]))
+}
+
+/// Read the UEFI `SecureBoot` variable (4 attribute bytes, then the value)
+#[cfg(target_os = "linux")]
+fn linux_secure_boot() -> Option<bool> {
+    let variable = std::fs::read(
+        "/sys/firmware/efi/efivars/SecureBoot-8be4df61-93ca-11d2-aa0d-00e098032b8c",
+    )
+    .ok()?;
+    variable.get(4).map(|value| *value == 1)
+}
+
+/// Read a REG_DWORD value with `reg query`
+#[cfg(target_os = "windows")]
+fn windows_registry_dword(key: &str, value: &str) -> Option<u32> {
+    let output = crate::probe::Command::new("reg")
+        .args(["query", key, "/v", value])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    // "    UEFISecureBootEnabled    REG_DWORD    0x1"
+    text.lines()
+        .filter(|line| line.trim_start().starts_with(value))
+        .find_map(|line| line.split_whitespace().last())
+        .and_then(|hex| u32::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+}
@@ -1,4 +1,4 @@
-use crate::Result;
+use crate::{HistoryBuffer, Result, Sample, SensorComponent, TimeSeries};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -58,6 +58,34 @@ pub struct FanCurve {
     pub max_speed_percent: f32,
 }
 
+impl FanCurve {
+    /// Fan speed percentage for `temperature`
+    ///
+    /// Interpolates linearly between curve points and clamps the result to
+    /// the curve's speed range. Hysteresis needs the previous reading, so it
+    /// is applied by `FanController::apply_curve` (`fan-control` feature).
+    pub fn speed_at(&self, temperature: f32) -> f32 {
+        let mut points: Vec<&CurvePoint> = self.curve_points.iter().collect();
+        points.sort_by(|a, b| a.temperature.total_cmp(&b.temperature));
+
+        let speed = match (points.first(), points.last()) {
+            (Some(first), _) if temperature <= first.temperature => first.speed_percent,
+            (_, Some(last)) if temperature >= last.temperature => last.speed_percent,
+            (None, _) | (_, None) => self.max_speed_percent,
+            _ => points
+                .windows(2)
+                .find(|pair| temperature <= pair[1].temperature)
+                .map(|pair| {
+                    let span = pair[1].temperature - pair[0].temperature;
+                    let t = if span > 0.0 { (temperature - pair[0].temperature) / span } else { 1.0 };
+                    pair[0].speed_percent + t * (pair[1].speed_percent - pair[0].speed_percent)
+                })
+                .unwrap_or(self.max_speed_percent),
+        };
+        speed.clamp(self.min_speed_percent, self.max_speed_percent.max(self.min_speed_percent))
+    }
+}
+
 /// Single point on a fan curve
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CurvePoint {
@@ -80,6 +108,94 @@ pub struct ThrottlingPrediction {
     pub recommendations: Vec<String>,
     /// Confidence level of prediction (0.0 to 1.0)
     pub confidence: f64,
+    /// Sensor expected to reach its limit first
+    #[serde(default)]
+    pub limiting_sensor: Option<String>,
+    /// Temperature at which the limiting sensor throttles
+    #[serde(default)]
+    pub throttle_temperature: Option<f32>,
+    /// Measured heating rate of the limiting sensor in °C per minute
+    #[serde(default)]
+    pub trend_per_minute: f32,
+    /// Unused fan capacity (0.0 to 1.0), if fan speeds are known
+    #[serde(default)]
+    pub fan_headroom: Option<f32>,
+    /// Predicted temperature of the limiting sensor over the prediction horizon
+    #[serde(default)]
+    pub predicted_curve: Vec<PredictedTemperature>,
+}
+
+/// One point of a predicted temperature curve
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PredictedTemperature {
+    /// Time from now
+    pub after: Duration,
+    /// Predicted temperature in Celsius
+    pub temperature: f32,
+}
+
+/// Limit assumed for sensors that report no critical or maximum temperature
+const DEFAULT_CRITICAL_TEMPERATURE: f32 = 90.0;
+
+/// How far ahead throttling is predicted
+const PREDICTION_HORIZON: Duration = Duration::from_secs(600);
+
+/// Spacing of the points on a predicted curve
+const PREDICTION_STEP: Duration = Duration::from_secs(30);
+
+/// Time constant of a typical heatsink: a measured heating trend flattens
+/// out over roughly this many seconds as the cooler catches up
+const THERMAL_TIME_CONSTANT_SECS: f64 = 300.0;
+
+/// How much sooner the trend flattens when fans have all their capacity left
+const FAN_RESPONSE: f64 = 0.5;
+
+/// Slope of a sensor's own temperature history in °C/s, with its sample count
+fn sensor_trend(sensor: &ThermalSensor) -> Option<(f64, usize)> {
+    let series = TimeSeries {
+        samples: sensor
+            .temperature_history
+            .iter()
+            .map(|reading| Sample {
+                timestamp: reading.timestamp,
+                value: reading.temperature as f64,
+            })
+            .collect(),
+    };
+    series.slope().map(|slope| (slope, series.len()))
+}
+
+/// Heating model of one sensor: exponential approach from the current
+/// temperature, starting at the measured slope
+struct SensorModel<'a> {
+    sensor: &'a ThermalSensor,
+    component: SensorComponent,
+    limit: f32,
+    slope: f64,
+    samples: usize,
+    time_constant: f64,
+}
+
+impl SensorModel<'_> {
+    fn temperature_after(&self, seconds: f64) -> f32 {
+        let rise = self.slope * self.time_constant * (1.0 - (-seconds / self.time_constant).exp());
+        self.sensor.temperature + rise as f32
+    }
+
+    fn time_to_limit(&self) -> Option<Duration> {
+        let remaining = (self.limit - self.sensor.temperature) as f64;
+        if remaining <= 0.0 {
+            return Some(Duration::ZERO);
+        }
+        let fraction = remaining / (self.slope * self.time_constant);
+        (self.slope > 0.0 && fraction < 1.0)
+            .then(|| Duration::from_secs_f64(-self.time_constant * (1.0 - fraction).ln()))
+    }
+
+    fn peak(&self) -> f32 {
+        self.temperature_after(PREDICTION_HORIZON.as_secs_f64())
+            .max(self.sensor.temperature)
+    }
 }
 
 /// Severity of thermal throttling
@@ -99,13 +215,14 @@ pub enum ThrottlingSeverity {
 
 impl std::fmt::Display for ThrottlingSeverity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ThrottlingSeverity::None => write!(f, "None"),
-            ThrottlingSeverity::Light => write!(f, "Light"),
-            ThrottlingSeverity::Moderate => write!(f, "Moderate"),
-            ThrottlingSeverity::Heavy => write!(f, "Heavy"),
-            ThrottlingSeverity::Severe => write!(f, "Severe"),
-        }
+        let text = match self {
+            ThrottlingSeverity::None => "None",
+            ThrottlingSeverity::Light => "Light",
+            ThrottlingSeverity::Moderate => "Moderate",
+            ThrottlingSeverity::Heavy => "Heavy",
+            ThrottlingSeverity::Severe => "Severe",
+        };
+        f.write_str(&crate::l10n::localize("ThrottlingSeverity", text))
     }
 }
 
@@ -162,12 +279,13 @@ pub enum ImplementationDifficulty {
 
 impl std::fmt::Display for ImplementationDifficulty {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ImplementationDifficulty::Easy => write!(f, "Easy"),
-            ImplementationDifficulty::Moderate => write!(f, "Moderate"),
-            ImplementationDifficulty::Difficult => write!(f, "Difficult"),
-            ImplementationDifficulty::Expert => write!(f, "Expert"),
-        }
+        let text = match self {
+            ImplementationDifficulty::Easy => "Easy",
+            ImplementationDifficulty::Moderate => "Moderate",
+            ImplementationDifficulty::Difficult => "Difficult",
+            ImplementationDifficulty::Expert => "Expert",
+        };
+        f.write_str(&crate::l10n::localize("ImplementationDifficulty", text))
     }
 }
 
@@ -186,12 +304,13 @@ pub enum CostCategory {
 
 impl std::fmt::Display for CostCategory {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            CostCategory::Free => write!(f, "Free"),
-            CostCategory::Low => write!(f, "Low cost"),
-            CostCategory::Medium => write!(f, "Medium cost"),
-            CostCategory::High => write!(f, "High cost"),
-        }
+        let text = match self {
+            CostCategory::Free => "Free",
+            CostCategory::Low => "Low cost",
+            CostCategory::Medium => "Medium cost",
+            CostCategory::High => "High cost",
+        };
+        f.write_str(&crate::l10n::localize("CostCategory", text))
     }
 }
 
@@ -235,13 +354,14 @@ pub enum ThermalStatus {
 
 impl std::fmt::Display for ThermalStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ThermalStatus::Normal => write!(f, "Normal"),
-            ThermalStatus::Warm => write!(f, "Warm"),
-            ThermalStatus::Hot => write!(f, "Hot"),
-            ThermalStatus::Critical => write!(f, "Critical"),
-            ThermalStatus::Unknown => write!(f, "Unknown"),
-        }
+        let text = match self {
+            ThermalStatus::Normal => "Normal",
+            ThermalStatus::Warm => "Warm",
+            ThermalStatus::Hot => "Hot",
+            ThermalStatus::Critical => "Critical",
+            ThermalStatus::Unknown => "Unknown",
+        };
+        f.write_str(&crate::l10n::localize("ThermalStatus", text))
     }
 }
 
@@ -263,6 +383,17 @@ impl ThermalInfo {
         })
     }
 
+    /// Placeholder used when thermal detection did not complete
+    pub(crate) fn unknown() -> Self {
+        Self {
+            sensors: Vec::new(),
+            fans: Vec::new(),
+            thermal_status: ThermalStatus::Unknown,
+            ambient_temperature: None,
+            tdp_info: None,
+        }
+    }
+
     /// Get temperature sensors
     pub fn sensors(&self) -> &[ThermalSensor] {
         &self.sensors
@@ -310,69 +441,178 @@ impl ThermalInfo {
         })
     }
 
-    /// Get CPU temperature (if available)
+    /// Get CPU package temperature, or the hottest core if no package sensor exists
     pub fn cpu_temperature(&self) -> Option<f32> {
-        self.sensors
-            .iter()
-            .find(|sensor| sensor.sensor_type.to_lowercase().contains("cpu"))
-            .map(|sensor| sensor.temperature)
+        self.component_temperature(SensorComponent::CpuPackage).or_else(|| {
+            self.sensor_mappings()
+                .into_iter()
+                .filter(|(_, mapping)| matches!(mapping.component, SensorComponent::CpuCore(_)))
+                .map(|(sensor, _)| sensor.temperature)
+                .reduce(f32::max)
+        })
     }
 
-    /// Get GPU temperature (if available)
+    /// Get temperature of the first GPU (if available)
     pub fn gpu_temperature(&self) -> Option<f32> {
-        self.sensors
-            .iter()
-            .find(|sensor| sensor.sensor_type.to_lowercase().contains("gpu"))
-            .map(|sensor| sensor.temperature)
+        self.component_temperature(SensorComponent::Gpu(0))
     }
 
-    /// Predict thermal throttling based on current conditions
+    /// Predict thermal throttling from each sensor's recorded temperature history
+    ///
+    /// `workload_intensity` scales the measured heating trend: 1.0 keeps the
+    /// current workload, 2.0 assumes it heats up twice as fast. Sensors
+    /// without at least two readings (see
+    /// [`update_sensor_history`](Self::update_sensor_history)) are assumed
+    /// to hold their temperature.
     pub fn predict_thermal_throttling(&self, workload_intensity: f32) -> ThrottlingPrediction {
-        let max_temp = self.max_temperature().unwrap_or(0.0);
-        let cpu_temp = self.cpu_temperature().unwrap_or(0.0);
-        let gpu_temp = self.gpu_temperature().unwrap_or(0.0);
+        self.predict_with_trends(workload_intensity, |sensor, _| sensor_trend(sensor))
+    }
 
-        // Simple prediction algorithm - would be enhanced with ML models
-        let critical_threshold = 90.0;
-        let temp_trend = self.calculate_temperature_trend();
-        
-        // Factor in workload intensity
-        let projected_temp_increase = workload_intensity * 10.0; // Simplified calculation
-        let projected_max_temp = max_temp + projected_temp_increase + temp_trend;
-
-        let will_throttle = projected_max_temp >= critical_threshold;
-        let time_to_throttle = if will_throttle && temp_trend > 0.0 {
-            let temp_diff = critical_threshold - max_temp;
-            let time_seconds = (temp_diff / temp_trend) * 60.0; // Convert to seconds
-            Some(Duration::from_secs(time_seconds.max(0.0) as u64))
-        } else {
-            None
+    /// Predict thermal throttling using CPU and GPU temperature trends from a
+    /// monitor's [`HistoryBuffer`] over `window`
+    ///
+    /// Sensors other than the CPU and GPU fall back to their own history.
+    pub fn predict_thermal_throttling_from_history(
+        &self,
+        history: &HistoryBuffer,
+        window: Duration,
+        workload_intensity: f32,
+    ) -> ThrottlingPrediction {
+        let cpu = history.cpu_temperature(window);
+        let gpu = history.gpu_temperature(window);
+        self.predict_with_trends(workload_intensity, |sensor, component| {
+            let series = match component {
+                SensorComponent::CpuPackage | SensorComponent::CpuCore(_) => &cpu,
+                SensorComponent::Gpu(_) => &gpu,
+                _ => return sensor_trend(sensor),
+            };
+            series.slope().map(|slope| (slope, series.len())).or_else(|| sensor_trend(sensor))
+        })
+    }
+
+    /// Shared prediction model; `trend` gives a sensor's slope in °C/s and the
+    /// number of samples it was fitted from
+    fn predict_with_trends(
+        &self,
+        workload_intensity: f32,
+        trend: impl Fn(&ThermalSensor, SensorComponent) -> Option<(f64, usize)>,
+    ) -> ThrottlingPrediction {
+        let fan_headroom = |component: SensorComponent| {
+            let mut fans = self.fans_for(component);
+            if fans.is_empty() {
+                fans = self.fans.iter().collect();
+            }
+            let unused: Vec<f32> = fans
+                .iter()
+                .filter_map(|fan| fan.speed_percent)
+                .map(|percent| (1.0 - percent / 100.0).clamp(0.0, 1.0))
+                .collect();
+            (!unused.is_empty()).then(|| unused.iter().sum::<f32>() / unused.len() as f32)
+        };
+
+        let models: Vec<SensorModel> = self
+            .sensor_mappings()
+            .into_iter()
+            .map(|(sensor, mapping)| {
+                let (slope, samples) = trend(sensor, mapping.component).unwrap_or((0.0, 0));
+                let headroom = fan_headroom(mapping.component).unwrap_or(0.0) as f64;
+                SensorModel {
+                    sensor,
+                    component: mapping.component,
+                    limit: sensor
+                        .critical_temperature
+                        .or(sensor.max_temperature)
+                        .unwrap_or(DEFAULT_CRITICAL_TEMPERATURE),
+                    slope: slope * workload_intensity.max(0.0) as f64,
+                    samples,
+                    time_constant: THERMAL_TIME_CONSTANT_SECS * (1.0 - FAN_RESPONSE * headroom),
+                }
+            })
+            .collect();
+
+        // The sensor that reaches its limit first, or else the one that gets closest
+        let limiting = models.iter().min_by(|a, b| {
+            match (a.time_to_limit(), b.time_to_limit()) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => (a.limit - a.peak()).total_cmp(&(b.limit - b.peak())),
+            }
+        });
+
+        let Some(limiting) = limiting else {
+            return ThrottlingPrediction {
+                will_throttle: false,
+                time_to_throttle: None,
+                severity: ThrottlingSeverity::None,
+                recommendations: Vec::new(),
+                confidence: 0.0,
+                limiting_sensor: None,
+                throttle_temperature: None,
+                trend_per_minute: 0.0,
+                fan_headroom: fan_headroom(SensorComponent::Unknown),
+                predicted_curve: Vec::new(),
+            };
         };
 
-        let severity = if projected_max_temp >= 95.0 {
+        let time_to_throttle = limiting.time_to_limit();
+        let will_throttle = time_to_throttle.is_some_and(|time| time <= PREDICTION_HORIZON);
+        let overshoot = limiting.peak() - limiting.limit;
+        let severity = if overshoot >= 5.0 {
             ThrottlingSeverity::Severe
-        } else if projected_max_temp >= 90.0 {
+        } else if overshoot >= 0.0 {
             ThrottlingSeverity::Heavy
-        } else if projected_max_temp >= 85.0 {
+        } else if overshoot >= -5.0 {
             ThrottlingSeverity::Moderate
-        } else if projected_max_temp >= 80.0 {
+        } else if overshoot >= -10.0 {
             ThrottlingSeverity::Light
         } else {
             ThrottlingSeverity::None
         };
 
+        let steps = (PREDICTION_HORIZON.as_secs() / PREDICTION_STEP.as_secs()) as u32;
+        let predicted_curve = (0..=steps)
+            .map(|step| {
+                let after = PREDICTION_STEP * step;
+                PredictedTemperature {
+                    after,
+                    temperature: limiting.temperature_after(after.as_secs_f64()),
+                }
+            })
+            .collect();
+
+        let headroom = fan_headroom(limiting.component);
         let mut recommendations = Vec::new();
         if will_throttle {
             recommendations.push("Reduce workload intensity".to_string());
-            recommendations.push("Increase fan speeds if possible".to_string());
-            if cpu_temp > gpu_temp {
-                recommendations.push("Focus on CPU cooling optimization".to_string());
-            } else if gpu_temp > cpu_temp {
-                recommendations.push("Focus on GPU cooling optimization".to_string());
+            match headroom {
+                Some(headroom) if headroom >= 0.2 => recommendations.push(format!(
+                    "Increase fan speeds ({:.0}% of fan capacity unused)",
+                    headroom * 100.0
+                )),
+                Some(_) => recommendations
+                    .push("Fans are near full speed; improve airflow or the cooler itself".to_string()),
+                None => recommendations.push("Increase fan speeds if possible".to_string()),
+            }
+            match limiting.component {
+                SensorComponent::CpuPackage | SensorComponent::CpuCore(_) => {
+                    recommendations.push("Focus on CPU cooling optimization".to_string())
+                }
+                SensorComponent::Gpu(_) => recommendations.push("Focus on GPU cooling optimization".to_string()),
+                SensorComponent::Unknown => {}
+                component => recommendations.push(format!("Focus on {component} cooling")),
             }
         }
 
-        let confidence = if temp_trend.abs() > 2.0 { 0.8 } else { 0.6 };
+        // More samples give a better fit; a reported limit beats the default
+        let mut confidence: f64 = match limiting.samples {
+            0 | 1 => 0.3,
+            2..=5 => 0.6,
+            _ => 0.8,
+        };
+        if limiting.sensor.critical_temperature.is_some() || limiting.sensor.max_temperature.is_some() {
+            confidence += 0.1;
+        }
 
         ThrottlingPrediction {
             will_throttle,
@@ -380,6 +620,11 @@ impl ThermalInfo {
             severity,
             recommendations,
             confidence,
+            limiting_sensor: Some(limiting.sensor.name.clone()),
+            throttle_temperature: Some(limiting.limit),
+            trend_per_minute: (limiting.slope * 60.0) as f32,
+            fan_headroom: headroom,
+            predicted_curve,
         }
     }
 
@@ -488,44 +733,445 @@ impl ThermalInfo {
         }
     }
 
-    fn calculate_temperature_trend(&self) -> f32 {
-        // Calculate average temperature trend across all sensors
-        let mut total_trend = 0.0;
-        let mut sensor_count = 0;
-
-        for sensor in &self.sensors {
-            if sensor.temperature_history.len() >= 3 {
-                let recent_temps: Vec<f32> = sensor.temperature_history
-                    .iter()
-                    .rev()
-                    .take(3)
-                    .map(|reading| reading.temperature)
-                    .collect();
-
-                // Simple linear trend calculation
-                let trend = (recent_temps[0] - recent_temps[2]) / 2.0;
-                total_trend += trend;
-                sensor_count += 1;
+    fn query_sensors() -> Result<Vec<ThermalSensor>> {
+        #[cfg(target_os = "linux")]
+        {
+            let sensors = Self::query_hwmon_sensors();
+            if sensors.is_empty() {
+                // Some ARM boards only expose ACPI/SoC thermal zones
+                return Ok(Self::query_thermal_zones());
+            }
+            Ok(sensors)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            #[cfg(feature = "librehardwaremonitor")]
+            {
+                let (sensors, _) = Self::query_lhm_sensors();
+                if !sensors.is_empty() {
+                    return Ok(sensors);
+                }
             }
+            Ok(Self::query_acpi_thermal_zones())
         }
+        #[cfg(all(target_os = "macos", feature = "smc"))]
+        {
+            Ok(crate::smc::smc_sensors())
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", all(target_os = "macos", feature = "smc"))))]
+        {
+            // Platform-specific implementation would go here
+            Ok(vec![])
+        }
+    }
 
-        if sensor_count > 0 {
-            total_trend / sensor_count as f32
-        } else {
-            0.0
+    fn query_fans() -> Result<Vec<FanInfo>> {
+        #[cfg(target_os = "linux")]
+        {
+            Ok(Self::query_hwmon_fans())
+        }
+        #[cfg(target_os = "windows")]
+        {
+            #[cfg(feature = "librehardwaremonitor")]
+            {
+                let (_, fans) = Self::query_lhm_sensors();
+                if !fans.is_empty() {
+                    return Ok(fans);
+                }
+            }
+            Ok(Self::query_win32_fans())
+        }
+        #[cfg(all(target_os = "macos", feature = "smc"))]
+        {
+            Ok(crate::smc::smc_fans())
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", all(target_os = "macos", feature = "smc"))))]
+        {
+            // Platform-specific implementation would go here
+            Ok(vec![])
         }
     }
 
-    fn query_sensors() -> Result<Vec<ThermalSensor>> {
-        // Platform-specific implementation would go here
-        // For now, return empty vector
-        Ok(vec![])
+    /// Read ACPI thermal zones from `MSAcpi_ThermalZoneTemperature`
+    ///
+    /// Values are in tenths of a Kelvin. The class lives in `root\WMI` and usually
+    /// needs administrator rights; without them no sensors are returned.
+    #[cfg(target_os = "windows")]
+    fn query_acpi_thermal_zones() -> Vec<ThermalSensor> {
+        use std::collections::HashMap;
+        use wmi::{Variant, WMIConnection};
+
+        let Ok(com) = crate::probe::com_library() else {
+            return Vec::new();
+        };
+        let Ok(wmi_con) = WMIConnection::with_namespace_path(r"root\WMI", com) else {
+            return Vec::new();
+        };
+        let Ok(zones) = wmi_con.raw_query::<HashMap<String, Variant>>(
+            "SELECT InstanceName, CurrentTemperature, CriticalTripPoint FROM MSAcpi_ThermalZoneTemperature",
+        ) else {
+            return Vec::new();
+        };
+
+        let celsius = |value: Option<&Variant>| match value {
+            Some(Variant::UI4(deci_kelvin)) if *deci_kelvin > 0 => {
+                Some(*deci_kelvin as f32 / 10.0 - 273.15)
+            }
+            _ => None,
+        };
+
+        zones
+            .iter()
+            .filter_map(|zone| {
+                let temperature = celsius(zone.get("CurrentTemperature"))?;
+                let name = match zone.get("InstanceName") {
+                    Some(Variant::String(name)) => name
+                        .rsplit('\\')
+                        .next()
+                        .unwrap_or(name)
+                        .trim_end_matches("_0")
+                        .to_string(),
+                    _ => "ACPI Thermal Zone".to_string(),
+                };
+                let sensor_type = if name.to_uppercase().contains("CPU") { "CPU" } else { "System" };
+                Some(ThermalSensor {
+                    name,
+                    temperature,
+                    critical_temperature: celsius(zone.get("CriticalTripPoint")),
+                    max_temperature: None,
+                    sensor_type: sensor_type.to_string(),
+                    temperature_history: Vec::new(),
+                })
+            })
+            .collect()
     }
 
-    fn query_fans() -> Result<Vec<FanInfo>> {
-        // Platform-specific implementation would go here
-        // For now, return empty vector
-        Ok(vec![])
+    /// Read fans from `Win32_Fan`
+    ///
+    /// Few firmwares populate `DesiredSpeed`; fans without a reading are skipped.
+    #[cfg(target_os = "windows")]
+    fn query_win32_fans() -> Vec<FanInfo> {
+        use std::collections::HashMap;
+        use wmi::{Variant, WMIConnection};
+
+        let Ok(wmi_con) = crate::probe::com_library().and_then(|com| Ok(WMIConnection::new(com)?)) else {
+            return Vec::new();
+        };
+        let Ok(fans) = wmi_con.raw_query::<HashMap<String, Variant>>(
+            "SELECT Name, DesiredSpeed, VariableSpeed FROM Win32_Fan",
+        ) else {
+            return Vec::new();
+        };
+
+        fans.iter()
+            .filter_map(|fan| {
+                let speed_rpm = match fan.get("DesiredSpeed") {
+                    Some(Variant::UI8(rpm)) if *rpm > 0 => *rpm as u32,
+                    Some(Variant::UI4(rpm)) if *rpm > 0 => *rpm,
+                    _ => return None,
+                };
+                Some(FanInfo {
+                    name: match fan.get("Name") {
+                        Some(Variant::String(name)) => name.clone(),
+                        _ => "System Fan".to_string(),
+                    },
+                    speed_rpm,
+                    max_speed_rpm: None,
+                    speed_percent: None,
+                    controllable: matches!(fan.get("VariableSpeed"), Some(Variant::Bool(true))),
+                    fan_curve: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Read temperatures and fans published by a running LibreHardwareMonitor
+    /// (or OpenHardwareMonitor) instance through its WMI provider
+    #[cfg(all(target_os = "windows", feature = "librehardwaremonitor"))]
+    fn query_lhm_sensors() -> (Vec<ThermalSensor>, Vec<FanInfo>) {
+        use std::collections::HashMap;
+        use wmi::{Variant, WMIConnection};
+
+        let Ok(com) = crate::probe::com_library() else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let rows = [r"root\LibreHardwareMonitor", r"root\OpenHardwareMonitor"]
+            .iter()
+            .find_map(|namespace| {
+                let wmi_con = WMIConnection::with_namespace_path(namespace, com).ok()?;
+                wmi_con
+                    .raw_query::<HashMap<String, Variant>>(
+                        "SELECT Name, Identifier, SensorType, Value, Max FROM Sensor \
+                         WHERE SensorType = 'Temperature' OR SensorType = 'Fan' OR SensorType = 'Control'",
+                    )
+                    .ok()
+                    .filter(|rows| !rows.is_empty())
+            })
+            .unwrap_or_default();
+
+        let text = |row: &HashMap<String, Variant>, key: &str| match row.get(key) {
+            Some(Variant::String(value)) => value.clone(),
+            _ => String::new(),
+        };
+        let number = |row: &HashMap<String, Variant>, key: &str| match row.get(key) {
+            Some(Variant::R4(value)) => Some(*value),
+            Some(Variant::R8(value)) => Some(*value as f32),
+            _ => None,
+        };
+        // Identifiers look like /amdcpu/0/temperature/2 or /gpu-nvidia/0/fan/1
+        let hardware = |identifier: &str| {
+            identifier.trim_start_matches('/').split('/').next().unwrap_or("").to_string()
+        };
+
+        let mut sensors = Vec::new();
+        let mut fans = Vec::new();
+        // Control sensors report fan duty cycle in percent, keyed by hardware and index
+        let controls: HashMap<String, f32> = rows
+            .iter()
+            .filter(|row| text(row, "SensorType") == "Control")
+            .filter_map(|row| {
+                let identifier = text(row, "Identifier").replace("/control/", "/fan/");
+                Some((identifier, number(row, "Value")?))
+            })
+            .collect();
+
+        for row in &rows {
+            let identifier = text(row, "Identifier");
+            let Some(value) = number(row, "Value") else {
+                continue;
+            };
+            let device = hardware(&identifier);
+
+            match text(row, "SensorType").as_str() {
+                "Temperature" => {
+                    let sensor_type = if device.ends_with("cpu") {
+                        "CPU"
+                    } else if device.starts_with("gpu") {
+                        "GPU"
+                    } else if device == "nvme" || device == "hdd" || device == "ssd" {
+                        "Storage"
+                    } else {
+                        "System"
+                    };
+                    sensors.push(ThermalSensor {
+                        name: text(row, "Name"),
+                        temperature: value,
+                        critical_temperature: None,
+                        max_temperature: number(row, "Max"),
+                        sensor_type: sensor_type.to_string(),
+                        temperature_history: Vec::new(),
+                    });
+                }
+                "Fan" if value > 0.0 => {
+                    let speed_percent = controls.get(&identifier).copied();
+                    fans.push(FanInfo {
+                        name: text(row, "Name"),
+                        speed_rpm: value as u32,
+                        max_speed_rpm: None,
+                        speed_percent,
+                        controllable: speed_percent.is_some(),
+                        fan_curve: None,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        (sensors, fans)
+    }
+
+    /// Classify an hwmon chip driver name into a sensor type
+    fn hwmon_sensor_type(chip: &str) -> &'static str {
+        match chip {
+            "coretemp" | "k10temp" | "k8temp" | "zenpower" | "cpu_thermal" | "via_cputemp" => "CPU",
+            "amdgpu" | "radeon" | "nouveau" | "i915" | "xe" => "GPU",
+            "nvme" | "drivetemp" => "Storage",
+            _ if chip.starts_with("iwlwifi") => "Network",
+            _ if chip.starts_with("BAT") => "Battery",
+            _ => "System",
+        }
+    }
+
+    /// hwmon device directories with their chip names
+    #[cfg(target_os = "linux")]
+    pub(crate) fn hwmon_chips() -> Vec<(String, std::path::PathBuf)> {
+        let Ok(entries) = std::fs::read_dir("/sys/class/hwmon") else {
+            return Vec::new();
+        };
+
+        let mut chips: Vec<(String, std::path::PathBuf)> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter_map(|path| {
+                let name = std::fs::read_to_string(path.join("name")).ok()?;
+                Some((name.trim().to_string(), path))
+            })
+            .collect();
+        chips.sort_by(|a, b| a.1.cmp(&b.1));
+        chips
+    }
+
+    /// Indices N of the `{prefix}N_input` files in an hwmon directory, sorted
+    fn hwmon_channels(dir: &std::path::Path, prefix: &str) -> Vec<u32> {
+        let mut channels: Vec<u32> = std::fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.strip_prefix(prefix)?.strip_suffix("_input")?.parse().ok()
+            })
+            .collect();
+        channels.sort_unstable();
+        channels
+    }
+
+    pub(crate) fn read_sysfs(path: &std::path::Path) -> Option<String> {
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Read temperature sensors from `/sys/class/hwmon`
+    #[cfg(target_os = "linux")]
+    fn query_hwmon_sensors() -> Vec<ThermalSensor> {
+        Self::hwmon_chips()
+            .iter()
+            .flat_map(|(_, dir)| Self::sensors_from_hwmon(dir))
+            .collect()
+    }
+
+    /// Parse the temperature channels of an hwmon device directory
+    ///
+    /// `dir` is `/sys/class/hwmon/hwmonN` (or a copy of it). Sensors are named
+    /// after the chip's `name` and the `tempN_label` files; `tempN_input`,
+    /// `tempN_crit` and `tempN_max` are millidegrees.
+    pub fn sensors_from_hwmon(dir: &std::path::Path) -> Vec<ThermalSensor> {
+        let Some(chip) = Self::read_sysfs(&dir.join("name")) else {
+            return Vec::new();
+        };
+        let millidegrees = |path: std::path::PathBuf| {
+            Self::read_sysfs(&path)
+                .and_then(|value| value.parse::<i64>().ok())
+                .map(|value| value as f32 / 1000.0)
+        };
+
+        let mut sensors = Vec::new();
+        for channel in Self::hwmon_channels(dir, "temp") {
+            let Some(temperature) = millidegrees(dir.join(format!("temp{channel}_input"))) else {
+                continue;
+            };
+            let label = Self::read_sysfs(&dir.join(format!("temp{channel}_label")))
+                .unwrap_or_else(|| format!("temp{channel}"));
+
+            sensors.push(ThermalSensor {
+                name: format!("{chip} {label}"),
+                temperature,
+                critical_temperature: millidegrees(dir.join(format!("temp{channel}_crit")))
+                    .filter(|t| *t > 0.0),
+                max_temperature: millidegrees(dir.join(format!("temp{channel}_max")))
+                    .filter(|t| *t > 0.0),
+                sensor_type: Self::hwmon_sensor_type(&chip).to_string(),
+                temperature_history: Vec::new(),
+            });
+        }
+        sensors
+    }
+
+    /// Read ACPI / SoC thermal zones from `/sys/class/thermal`
+    #[cfg(target_os = "linux")]
+    fn query_thermal_zones() -> Vec<ThermalSensor> {
+        let Ok(entries) = std::fs::read_dir("/sys/class/thermal") else {
+            return Vec::new();
+        };
+
+        let mut sensors: Vec<ThermalSensor> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with("thermal_zone"))
+            })
+            .filter_map(|path| Self::sensor_from_thermal_zone(&path))
+            .collect();
+        sensors.sort_by(|a, b| a.name.cmp(&b.name));
+        sensors
+    }
+
+    /// Parse a `/sys/class/thermal/thermal_zoneN` directory
+    ///
+    /// The sensor is named after the zone `type`; `temp` is millidegrees.
+    /// Returns `None` if the zone has no readable temperature.
+    pub fn sensor_from_thermal_zone(dir: &std::path::Path) -> Option<ThermalSensor> {
+        let temperature = Self::read_sysfs(&dir.join("temp"))?.parse::<i64>().ok()?;
+        let zone = Self::read_sysfs(&dir.join("type")).unwrap_or_else(|| "zone".to_string());
+        let sensor_type = if zone.contains("cpu") || zone.contains("x86_pkg") {
+            "CPU"
+        } else if zone.contains("gpu") {
+            "GPU"
+        } else {
+            "System"
+        };
+        Some(ThermalSensor {
+            name: zone,
+            temperature: temperature as f32 / 1000.0,
+            critical_temperature: None,
+            max_temperature: None,
+            sensor_type: sensor_type.to_string(),
+            temperature_history: Vec::new(),
+        })
+    }
+
+    /// Read fan tachometers from `/sys/class/hwmon`
+    #[cfg(target_os = "linux")]
+    fn query_hwmon_fans() -> Vec<FanInfo> {
+        Self::hwmon_chips()
+            .iter()
+            .flat_map(|(_, dir)| Self::fans_from_hwmon(dir))
+            .collect()
+    }
+
+    /// Parse the fan tachometers of an hwmon device directory
+    ///
+    /// `fanN_input` is the speed in RPM. The percentage comes from `fanN_max`
+    /// or, failing that, the `pwmN` duty cycle (0-255).
+    pub fn fans_from_hwmon(dir: &std::path::Path) -> Vec<FanInfo> {
+        let Some(chip) = Self::read_sysfs(&dir.join("name")) else {
+            return Vec::new();
+        };
+        let number = |path: std::path::PathBuf| {
+            Self::read_sysfs(&path).and_then(|value| value.parse::<u32>().ok())
+        };
+
+        let mut fans = Vec::new();
+        for channel in Self::hwmon_channels(dir, "fan") {
+            let Some(speed_rpm) = number(dir.join(format!("fan{channel}_input"))) else {
+                continue;
+            };
+            let label = Self::read_sysfs(&dir.join(format!("fan{channel}_label")))
+                .unwrap_or_else(|| format!("fan{channel}"));
+            let max_speed_rpm = number(dir.join(format!("fan{channel}_max"))).filter(|rpm| *rpm > 0);
+
+            // pwmN (0-255) drives fanN on most Super I/O chips
+            let pwm = number(dir.join(format!("pwm{channel}")));
+            let speed_percent = match (max_speed_rpm, pwm) {
+                (Some(max), _) => Some((speed_rpm as f32 / max as f32 * 100.0).min(100.0)),
+                (None, Some(pwm)) => Some(pwm as f32 / 255.0 * 100.0),
+                _ => None,
+            };
+
+            fans.push(FanInfo {
+                name: format!("{chip} {label}"),
+                speed_rpm,
+                max_speed_rpm,
+                speed_percent,
+                controllable: dir.join(format!("pwm{channel}_enable")).exists(),
+                fan_curve: None,
+            });
+        }
+        fans
     }
 
     fn query_ambient_temperature() -> Result<Option<f32>> {
@@ -534,8 +1180,16 @@ impl ThermalInfo {
     }
 
     fn query_tdp_info() -> Result<Option<TDPInfo>> {
-        // Platform-specific implementation would go here
-        Ok(None)
+        let limits = crate::CPUPowerLimits::query();
+        if limits.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(TDPInfo {
+            cpu_tdp: limits.sustained_watts,
+            gpu_tdp: None,
+            system_tdp: None,
+            power_ratio: None,
+        }))
     }
 
     fn calculate_thermal_status(sensors: &[ThermalSensor]) -> ThermalStatus {
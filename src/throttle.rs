@@ -0,0 +1,425 @@
+//! Observed clock throttling
+//!
+//! [`ThermalInfo::predict_thermal_throttling`](crate::ThermalInfo::predict_thermal_throttling)
+//! estimates whether throttling is likely; this module reports throttling the
+//! hardware actually did. On Linux the kernel counts PROCHOT and thermal
+//! events per CPU core and package (`thermal_throttle` in sysfs), and with the
+//! `msr` module loaded and root access `MSR_CORE_PERF_LIMIT_REASONS` names the
+//! limits currently holding the clocks down. Intel GPUs expose the same
+//! reasons in sysfs; NVIDIA GPUs report them through NVML (`nvidia` feature).
+//!
+//! [`ThrottleStatus`] is one reading; [`ThrottleEvent::between`] compares two.
+
+use crate::HardwareComponent;
+use serde::{Deserialize, Serialize};
+
+/// Why a device lowered its clocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ThrottleCause {
+    /// Temperature limit, PROCHOT assertion or voltage regulator over-temperature
+    Thermal,
+    /// Sustained or short-term power limit (PL1/PL2, GPU power cap, external power brake)
+    PowerLimit,
+    /// Voltage regulator current limit (EDP/TDC, PL4)
+    CurrentLimit,
+}
+
+impl std::fmt::Display for ThrottleCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            ThrottleCause::Thermal => "thermal",
+            ThrottleCause::PowerLimit => "power limit",
+            ThrottleCause::CurrentLimit => "current limit",
+        };
+        f.write_str(&crate::l10n::localize("ThrottleCause", text))
+    }
+}
+
+/// `MSR_CORE_PERF_LIMIT_REASONS` status bits (Intel SDM vol. 4)
+const PERF_LIMIT_REASONS: &[(u64, ThrottleCause)] = &[
+    (1 << 0, ThrottleCause::Thermal),       // PROCHOT
+    (1 << 1, ThrottleCause::Thermal),       // Thermal
+    (1 << 5, ThrottleCause::Thermal),       // Running average thermal limit
+    (1 << 6, ThrottleCause::Thermal),       // VR thermal alert
+    (1 << 7, ThrottleCause::CurrentLimit),  // VR thermal design current
+    (1 << 10, ThrottleCause::PowerLimit),   // Package PL1
+    (1 << 11, ThrottleCause::PowerLimit),   // Package PL2
+];
+
+/// i915 `throttle_reason_<name>` and xe `reason_<name>` attributes
+const GPU_REASONS: &[(&str, ThrottleCause)] = &[
+    ("prochot", ThrottleCause::Thermal),
+    ("thermal", ThrottleCause::Thermal),
+    ("ratl", ThrottleCause::Thermal),
+    ("vr_thermalert", ThrottleCause::Thermal),
+    ("pl1", ThrottleCause::PowerLimit),
+    ("pl2", ThrottleCause::PowerLimit),
+    ("pl4", ThrottleCause::CurrentLimit),
+    ("vr_tdc", ThrottleCause::CurrentLimit),
+];
+
+impl ThrottleCause {
+    /// Decode an Intel `MSR_CORE_PERF_LIMIT_REASONS` value
+    ///
+    /// Only the status bits (0-15) are looked at; the log bits above them
+    /// record past throttling. The causes are sorted and deduplicated.
+    pub fn from_perf_limit_reasons(value: u64) -> Vec<Self> {
+        let mut causes: Vec<Self> = PERF_LIMIT_REASONS
+            .iter()
+            .filter(|(bit, _)| value & bit != 0)
+            .map(|(_, cause)| *cause)
+            .collect();
+        causes.sort();
+        causes.dedup();
+        causes
+    }
+
+    /// Decode an NVML `nvmlClocksThrottleReasons` bitmask
+    ///
+    /// Idle, application clock and sync boost reasons are not throttling and
+    /// are ignored.
+    #[cfg(feature = "nvidia")]
+    pub fn from_nvml_throttle_reasons(bits: u64) -> Vec<Self> {
+        use nvml_wrapper::bitmasks::device::ThrottleReasons;
+
+        let reasons = ThrottleReasons::from_bits_truncate(bits);
+        let mut causes = Vec::new();
+        let thermal = reasons
+            .intersects(ThrottleReasons::SW_THERMAL_SLOWDOWN | ThrottleReasons::HW_THERMAL_SLOWDOWN);
+        let power = reasons
+            .intersects(ThrottleReasons::SW_POWER_CAP | ThrottleReasons::HW_POWER_BRAKE_SLOWDOWN);
+        if thermal {
+            causes.push(ThrottleCause::Thermal);
+        }
+        if power {
+            causes.push(ThrottleCause::PowerLimit);
+        }
+        // HW slowdown without a thermal or power brake reason is the board's
+        // fast-trigger over-current protection
+        if reasons.contains(ThrottleReasons::HW_SLOWDOWN) && !thermal && !power {
+            causes.push(ThrottleCause::CurrentLimit);
+        }
+        causes
+    }
+}
+
+/// Throttling state of one CPU package or GPU
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThrottleStatus {
+    /// [`HardwareComponent::CPU`] or [`HardwareComponent::GPU`]
+    pub component: HardwareComponent,
+    /// CPU package (`package0`) or GPU PCI address
+    pub device: String,
+    /// Limits holding the clocks down when the status was read
+    pub active_causes: Vec<ThrottleCause>,
+    /// Thermal throttle events counted since boot, over the package and its cores
+    pub thermal_events: Option<u64>,
+    /// Time the package spent thermally throttled since boot, in milliseconds
+    pub throttled_time_ms: Option<u64>,
+}
+
+impl ThrottleStatus {
+    /// Read the throttling state of every CPU package and GPU that reports one
+    ///
+    /// Devices without throttle counters or reasons are left out, so the list
+    /// is empty on platforms without a backend.
+    pub fn query_all() -> Vec<Self> {
+        #[allow(unused_mut)]
+        let mut statuses = Vec::new();
+
+        #[cfg(target_os = "linux")]
+        {
+            statuses.extend(linux::cpu_packages());
+            statuses.extend(linux::intel_gpus());
+        }
+
+        #[cfg(feature = "nvidia")]
+        statuses.extend(nvidia_gpus());
+
+        statuses
+    }
+
+    /// Whether any limit was holding the clocks down when the status was read
+    pub fn is_throttling(&self) -> bool {
+        !self.active_causes.is_empty()
+    }
+
+    /// Get device identifier
+    pub fn device(&self) -> &str {
+        &self.device
+    }
+
+    /// Read the throttle reasons of an Intel GPU from its DRM card directory
+    ///
+    /// `card_dir` is `/sys/class/drm/cardN` (or a copy of it). i915 publishes
+    /// `gt/gt<n>/throttle_reason_<name>`, xe
+    /// `device/tile<n>/gt<n>/freq0/throttle/reason_<name>`; each holds 0 or 1.
+    /// The device is named after the PCI address `device` links to. Returns
+    /// `None` if the card has no throttle reason attributes.
+    pub fn from_drm_card(card_dir: &std::path::Path) -> Option<Self> {
+        let device = card_dir.join("device");
+        let mut dirs: Vec<(std::path::PathBuf, &str)> = subdirs(&card_dir.join("gt"), "gt")
+            .into_iter()
+            .map(|gt| (gt, "throttle_reason_"))
+            .collect();
+        for tile in subdirs(&device, "tile") {
+            for gt in subdirs(&tile, "gt") {
+                dirs.push((gt.join("freq0/throttle"), "reason_"));
+            }
+        }
+
+        let mut found = false;
+        let mut causes = Vec::new();
+        for (dir, prefix) in &dirs {
+            for (name, cause) in GPU_REASONS {
+                let Some(active) = read_u64(&dir.join(format!("{prefix}{name}"))) else {
+                    continue;
+                };
+                found = true;
+                if active != 0 && !causes.contains(cause) {
+                    causes.push(*cause);
+                }
+            }
+        }
+        if !found {
+            return None;
+        }
+        causes.sort();
+
+        let bus_id = std::fs::canonicalize(&device)
+            .ok()
+            .and_then(|path| path.file_name().map(|name| name.to_string_lossy().into_owned()))
+            .or_else(|| card_dir.file_name().map(|name| name.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+        Some(Self {
+            component: HardwareComponent::GPU,
+            device: bus_id,
+            active_causes: causes,
+            thermal_events: None,
+            throttled_time_ms: None,
+        })
+    }
+}
+
+/// Throttling that started or recurred between two readings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThrottleEvent {
+    /// [`HardwareComponent::CPU`] or [`HardwareComponent::GPU`]
+    pub component: HardwareComponent,
+    /// CPU package (`package0`) or GPU PCI address
+    pub device: String,
+    /// Causes that became active or were counted in the interval
+    pub causes: Vec<ThrottleCause>,
+    /// Thermal throttle events counted in the interval
+    pub new_events: u64,
+}
+
+impl ThrottleEvent {
+    /// Throttling between two readings, for devices present in both
+    ///
+    /// A limit that stays active across readings is reported once, when it
+    /// first appears; counted thermal events are reported every interval they
+    /// increase.
+    pub fn between(before: &[ThrottleStatus], after: &[ThrottleStatus]) -> Vec<Self> {
+        after
+            .iter()
+            .filter_map(|current| {
+                let previous = before
+                    .iter()
+                    .find(|s| s.component == current.component && s.device == current.device)?;
+                Self::from_statuses(previous, current)
+            })
+            .collect()
+    }
+
+    fn from_statuses(previous: &ThrottleStatus, current: &ThrottleStatus) -> Option<Self> {
+        let mut causes: Vec<ThrottleCause> = current
+            .active_causes
+            .iter()
+            .filter(|cause| !previous.active_causes.contains(cause))
+            .copied()
+            .collect();
+
+        // Counters reset when the driver reloads; treat that as no new events
+        let new_events = match (previous.thermal_events, current.thermal_events) {
+            (Some(before), Some(after)) => after.saturating_sub(before),
+            _ => 0,
+        };
+        let throttled_longer = matches!(
+            (previous.throttled_time_ms, current.throttled_time_ms),
+            (Some(before), Some(after)) if after > before
+        );
+        if (new_events > 0 || throttled_longer) && !causes.contains(&ThrottleCause::Thermal) {
+            causes.push(ThrottleCause::Thermal);
+        }
+
+        if causes.is_empty() {
+            return None;
+        }
+        causes.sort();
+        Some(Self {
+            component: current.component,
+            device: current.device.clone(),
+            causes,
+            new_events,
+        })
+    }
+}
+
+/// Subdirectories named `<prefix><n>`, in order
+fn subdirs(dir: &std::path::Path, prefix: &str) -> Vec<std::path::PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<std::path::PathBuf> = entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix(prefix))
+                .is_some_and(|n| n.parse::<u32>().is_ok())
+        })
+        .map(|entry| entry.path())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn read_u64(path: &std::path::Path) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(feature = "nvidia")]
+fn nvidia_gpus() -> Vec<ThrottleStatus> {
+    let Ok(nvml) = nvml_wrapper::Nvml::init() else {
+        return Vec::new();
+    };
+    let mut statuses = Vec::new();
+    for index in 0..nvml.device_count().unwrap_or(0) {
+        let Ok(device) = nvml.device_by_index(index) else {
+            continue;
+        };
+        let (Ok(reasons), Ok(pci)) = (device.current_throttle_reasons(), device.pci_info()) else {
+            continue;
+        };
+
+        statuses.push(ThrottleStatus {
+            component: HardwareComponent::GPU,
+            device: pci.bus_id,
+            active_causes: ThrottleCause::from_nvml_throttle_reasons(reasons.bits()),
+            thermal_events: None,
+            throttled_time_ms: None,
+        });
+    }
+    statuses
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{read_u64, ThrottleCause, ThrottleStatus};
+    use crate::HardwareComponent;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::path::Path;
+
+    /// Address of `MSR_CORE_PERF_LIMIT_REASONS`
+    const PERF_LIMIT_REASONS_MSR: u64 = 0x64f;
+
+    #[derive(Default)]
+    struct Package {
+        first_cpu: Option<u32>,
+        package_events: Option<u64>,
+        package_time_ms: Option<u64>,
+        /// Core throttle count per core ID; hyperthread siblings share one
+        core_events: BTreeMap<u32, u64>,
+    }
+
+    pub(super) fn cpu_packages() -> Vec<ThrottleStatus> {
+        let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") else {
+            return Vec::new();
+        };
+        let mut cpus: Vec<u32> = entries
+            .flatten()
+            .filter_map(|entry| entry.file_name().to_str()?.strip_prefix("cpu")?.parse().ok())
+            .collect();
+        cpus.sort_unstable();
+
+        let mut packages: BTreeMap<u32, Package> = BTreeMap::new();
+        for cpu in cpus {
+            let dir = Path::new("/sys/devices/system/cpu").join(format!("cpu{cpu}"));
+            let package_id = read_u64(&dir.join("topology/physical_package_id")).unwrap_or(0) as u32;
+            let package = packages.entry(package_id).or_default();
+            package.first_cpu.get_or_insert(cpu);
+
+            let throttle = dir.join("thermal_throttle");
+            if let Some(count) = read_u64(&throttle.join("package_throttle_count")) {
+                package.package_events = Some(count);
+            }
+            if let Some(ms) = read_u64(&throttle.join("package_throttle_total_time_ms")) {
+                package.package_time_ms = Some(ms);
+            }
+            if let (Some(core_id), Some(count)) = (
+                read_u64(&dir.join("topology/core_id")),
+                read_u64(&throttle.join("core_throttle_count")),
+            ) {
+                package.core_events.insert(core_id as u32, count);
+            }
+        }
+
+        packages
+            .into_iter()
+            .filter_map(|(id, package)| {
+                let active_causes = package.first_cpu.map(perf_limit_reasons).unwrap_or_default();
+                let thermal_events = match (package.package_events, package.core_events.is_empty()) {
+                    (None, true) => None,
+                    (events, _) => Some(events.unwrap_or(0) + package.core_events.values().sum::<u64>()),
+                };
+                if thermal_events.is_none() && active_causes.is_empty() {
+                    return None;
+                }
+                Some(ThrottleStatus {
+                    component: HardwareComponent::CPU,
+                    device: format!("package{id}"),
+                    active_causes,
+                    thermal_events,
+                    throttled_time_ms: package.package_time_ms,
+                })
+            })
+            .collect()
+    }
+
+    /// Active causes from the package's limit reasons register
+    ///
+    /// Needs root and the `msr` module; reads fail on CPUs without the
+    /// register, which leaves the causes empty.
+    fn perf_limit_reasons(cpu: u32) -> Vec<ThrottleCause> {
+        use std::os::unix::fs::FileExt;
+
+        let Ok(file) = fs::File::open(format!("/dev/cpu/{cpu}/msr")) else {
+            return Vec::new();
+        };
+        let mut value = [0u8; 8];
+        if file.read_exact_at(&mut value, PERF_LIMIT_REASONS_MSR).is_err() {
+            return Vec::new();
+        }
+        ThrottleCause::from_perf_limit_reasons(u64::from_le_bytes(value))
+    }
+
+    pub(super) fn intel_gpus() -> Vec<ThrottleStatus> {
+        let Ok(entries) = fs::read_dir("/sys/class/drm") else {
+            return Vec::new();
+        };
+        let mut cards: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| name.starts_with("card") && !name.contains('-'))
+            .collect();
+        cards.sort();
+
+        cards
+            .iter()
+            .filter_map(|card| ThrottleStatus::from_drm_card(&Path::new("/sys/class/drm").join(card)))
+            .collect()
+    }
+}
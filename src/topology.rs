@@ -0,0 +1,902 @@
+//! CPU topology: sockets, NUMA nodes, SMT siblings and cache sharing domains
+//!
+//! Core counts alone are not enough for placing work on large machines. This
+//! module describes which logical CPUs belong to which package and NUMA node,
+//! which ones are hyperthread siblings, and which ones share each cache level.
+//! Logical CPU ids match the operating system's numbering (and
+//! [`CPUInfo::core_usage`](crate::CPUInfo::core_usage) indices).
+//!
+//! On heterogeneous CPUs (Apple Silicon, Intel hybrid, ARM big.LITTLE /
+//! DynamIQ) the cores are also grouped into performance and efficiency
+//! clusters.
+
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+/// Kind of CPU cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CacheType {
+    Data,
+    Instruction,
+    Unified,
+}
+
+impl std::fmt::Display for CacheType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CacheType::Data => write!(f, "Data"),
+            CacheType::Instruction => write!(f, "Instruction"),
+            CacheType::Unified => write!(f, "Unified"),
+        }
+    }
+}
+
+/// A single cache instance and the logical CPUs that share it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheDomain {
+    /// Cache level (1, 2, 3, ...)
+    pub level: u8,
+    /// Cache type
+    pub cache_type: CacheType,
+    /// Size in KB
+    pub size_kb: u32,
+    /// Logical CPUs sharing this cache instance
+    pub shared_cpus: Vec<u32>,
+}
+
+/// A NUMA node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NumaNode {
+    /// Node id
+    pub id: u32,
+    /// Logical CPUs local to this node
+    pub cpus: Vec<u32>,
+    /// Memory attached to this node in MB (if known)
+    pub memory_mb: Option<u64>,
+    /// Relative access distance to every node, indexed by node position (if known)
+    pub distances: Vec<u32>,
+}
+
+/// Placement of a single logical CPU
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogicalProcessor {
+    /// Logical CPU id
+    pub id: u32,
+    /// Physical core id (unique within the socket)
+    pub core_id: u32,
+    /// Socket / package id
+    pub socket_id: u32,
+    /// NUMA node id (if known)
+    pub numa_node: Option<u32>,
+    /// Logical CPUs on the same physical core, including this one
+    pub smt_siblings: Vec<u32>,
+}
+
+/// Kind of core in a heterogeneous CPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CoreType {
+    /// Performance cores (Apple P-cores, Intel P-cores, ARM big / prime cores);
+    /// also used for every core of a homogeneous CPU
+    Performance,
+    /// Efficiency cores (Apple E-cores, Intel E-cores, ARM LITTLE cores)
+    Efficiency,
+}
+
+impl std::fmt::Display for CoreType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CoreType::Performance => write!(f, "Performance"),
+            CoreType::Efficiency => write!(f, "Efficiency"),
+        }
+    }
+}
+
+/// A group of identical cores
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoreCluster {
+    /// Performance or efficiency cores
+    pub core_type: CoreType,
+    /// Platform name of the cluster (macOS perflevel name, Linux PMU name)
+    pub name: Option<String>,
+    /// Number of physical cores
+    pub physical_cores: u32,
+    /// Logical CPUs belonging to the cluster
+    pub logical_cpus: Vec<u32>,
+    /// Highest frequency the cluster's cores reach in MHz (if known)
+    pub max_frequency_mhz: Option<u32>,
+    /// L2 cache per shared instance in KB (if known)
+    pub l2_cache_kb: Option<u32>,
+}
+
+impl CoreCluster {
+    /// Get core type
+    pub fn core_type(&self) -> CoreType {
+        self.core_type
+    }
+
+    /// Get cluster name
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Get number of physical cores
+    pub fn physical_cores(&self) -> u32 {
+        self.physical_cores
+    }
+
+    /// Get logical CPUs in the cluster
+    pub fn logical_cpus(&self) -> &[u32] {
+        &self.logical_cpus
+    }
+
+    /// Get maximum frequency in MHz
+    pub fn max_frequency_mhz(&self) -> Option<u32> {
+        self.max_frequency_mhz
+    }
+
+    /// Get L2 cache size in KB
+    pub fn l2_cache_kb(&self) -> Option<u32> {
+        self.l2_cache_kb
+    }
+}
+
+/// CPU topology of the system
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CPUTopology {
+    /// Number of populated sockets / packages
+    pub sockets: u32,
+    /// NUMA nodes (a single node on UMA systems)
+    pub numa_nodes: Vec<NumaNode>,
+    /// Every logical CPU, ordered by id
+    pub processors: Vec<LogicalProcessor>,
+    /// Every cache instance
+    pub caches: Vec<CacheDomain>,
+    /// Core clusters, fastest first (a single cluster on homogeneous CPUs)
+    #[serde(default)]
+    pub clusters: Vec<CoreCluster>,
+}
+
+impl CPUTopology {
+    /// Query the CPU topology from the system
+    pub fn query() -> Result<Self> {
+        #[cfg(target_os = "linux")]
+        {
+            Self::query_linux()
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Self::query_windows()
+        }
+        #[cfg(target_os = "macos")]
+        {
+            Self::query_macos()
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            Err(crate::HardwareQueryError::platform_not_supported(
+                "CPU topology detection is not supported on this platform",
+            ))
+        }
+    }
+
+    /// Flat topology used when the platform cannot report one: one socket, one
+    /// node, and no SMT
+    pub fn flat(logical_cpus: u32) -> Self {
+        let cpus: Vec<u32> = (0..logical_cpus).collect();
+        Self {
+            sockets: 1,
+            numa_nodes: vec![NumaNode {
+                id: 0,
+                cpus: cpus.clone(),
+                memory_mb: None,
+                distances: vec![10],
+            }],
+            processors: cpus
+                .iter()
+                .map(|&id| LogicalProcessor {
+                    id,
+                    core_id: id,
+                    socket_id: 0,
+                    numa_node: Some(0),
+                    smt_siblings: vec![id],
+                })
+                .collect(),
+            caches: Vec::new(),
+            clusters: vec![CoreCluster {
+                core_type: CoreType::Performance,
+                name: None,
+                physical_cores: logical_cpus,
+                logical_cpus: cpus,
+                max_frequency_mhz: None,
+                l2_cache_kb: None,
+            }],
+        }
+    }
+
+    /// Get number of sockets
+    pub fn sockets(&self) -> u32 {
+        self.sockets
+    }
+
+    /// Get NUMA nodes
+    pub fn numa_nodes(&self) -> &[NumaNode] {
+        &self.numa_nodes
+    }
+
+    /// Get logical processors
+    pub fn processors(&self) -> &[LogicalProcessor] {
+        &self.processors
+    }
+
+    /// Get cache instances
+    pub fn caches(&self) -> &[CacheDomain] {
+        &self.caches
+    }
+
+    /// Whether the system has more than one NUMA node
+    pub fn is_numa(&self) -> bool {
+        self.numa_nodes.len() > 1
+    }
+
+    /// NUMA node of a logical CPU
+    pub fn node_of(&self, cpu: u32) -> Option<u32> {
+        self.processors
+            .iter()
+            .find(|processor| processor.id == cpu)
+            .and_then(|processor| processor.numa_node)
+    }
+
+    /// Groups of logical CPUs that share a physical core (only cores with SMT)
+    pub fn smt_sibling_groups(&self) -> Vec<Vec<u32>> {
+        let mut groups: Vec<Vec<u32>> = Vec::new();
+        for processor in &self.processors {
+            if processor.smt_siblings.len() > 1 && !groups.contains(&processor.smt_siblings) {
+                groups.push(processor.smt_siblings.clone());
+            }
+        }
+        groups
+    }
+
+    /// Logical CPUs sharing the given cache level with `cpu` (including `cpu`)
+    pub fn cpus_sharing_cache(&self, cpu: u32, level: u8) -> Vec<u32> {
+        self.caches
+            .iter()
+            .find(|cache| {
+                cache.level == level
+                    && cache.cache_type != CacheType::Instruction
+                    && cache.shared_cpus.contains(&cpu)
+            })
+            .map(|cache| cache.shared_cpus.clone())
+            .unwrap_or_else(|| vec![cpu])
+    }
+
+    /// Get core clusters, fastest first
+    pub fn clusters(&self) -> &[CoreCluster] {
+        &self.clusters
+    }
+
+    /// Check if the CPU mixes performance and efficiency cores
+    pub fn is_heterogeneous(&self) -> bool {
+        self.clusters
+            .iter()
+            .any(|cluster| cluster.core_type == CoreType::Efficiency)
+    }
+
+    /// Number of physical cores of `core_type`
+    pub fn cores_of_type(&self, core_type: CoreType) -> u32 {
+        self.clusters
+            .iter()
+            .filter(|cluster| cluster.core_type == core_type)
+            .map(|cluster| cluster.physical_cores)
+            .sum()
+    }
+
+    /// One logical CPU per physical core, useful for pinning compute-bound threads
+    pub fn one_cpu_per_core(&self) -> Vec<u32> {
+        let mut cpus: Vec<u32> = self
+            .processors
+            .iter()
+            .filter_map(|processor| processor.smt_siblings.iter().min().copied())
+            .collect();
+        cpus.sort_unstable();
+        cpus.dedup();
+        cpus
+    }
+
+    /// Logical CPUs matching `criteria`, for thread pinning
+    pub fn cpu_set(&self, criteria: &crate::AffinityCriteria) -> crate::CpuSet {
+        criteria.select(self)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn query_linux() -> Result<Self> {
+        use std::fs;
+        use std::path::Path;
+
+        let read = |path: &Path| fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+        let cpu_root = Path::new("/sys/devices/system/cpu");
+
+        let online = read(&cpu_root.join("online")).ok_or_else(|| {
+            crate::HardwareQueryError::system_info_unavailable("Cannot read online CPU list")
+        })?;
+
+        let mut numa_nodes = Vec::new();
+        if let Ok(entries) = fs::read_dir("/sys/devices/system/node") {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let Some(id) = name.strip_prefix("node").and_then(|id| id.parse::<u32>().ok())
+                else {
+                    continue;
+                };
+                let path = entry.path();
+                // meminfo line: "Node 0 MemTotal:       16303716 kB"
+                let memory_mb = read(&path.join("meminfo")).and_then(|meminfo| {
+                    meminfo
+                        .lines()
+                        .find(|line| line.contains("MemTotal:"))
+                        .and_then(|line| line.split_whitespace().rev().nth(1))
+                        .and_then(|kb| kb.parse::<u64>().ok())
+                        .map(|kb| kb / 1024)
+                });
+                numa_nodes.push(NumaNode {
+                    id,
+                    cpus: read(&path.join("cpulist"))
+                        .map(|list| parse_cpu_list(&list))
+                        .unwrap_or_default(),
+                    memory_mb,
+                    distances: read(&path.join("distance"))
+                        .map(|d| d.split_whitespace().filter_map(|v| v.parse().ok()).collect())
+                        .unwrap_or_default(),
+                });
+            }
+        }
+        numa_nodes.sort_by_key(|node| node.id);
+
+        let mut processors = Vec::new();
+        let mut caches: Vec<CacheDomain> = Vec::new();
+        let mut sockets: Vec<u32> = Vec::new();
+
+        for id in parse_cpu_list(&online) {
+            let cpu_dir = cpu_root.join(format!("cpu{id}"));
+            let topology = cpu_dir.join("topology");
+            let socket_id = read(&topology.join("physical_package_id"))
+                .and_then(|v| v.parse::<i64>().ok())
+                .map(|v| v.max(0) as u32)
+                .unwrap_or(0);
+            if !sockets.contains(&socket_id) {
+                sockets.push(socket_id);
+            }
+
+            processors.push(LogicalProcessor {
+                id,
+                core_id: read(&topology.join("core_id"))
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(id),
+                socket_id,
+                numa_node: numa_nodes
+                    .iter()
+                    .find(|node| node.cpus.contains(&id))
+                    .map(|node| node.id),
+                smt_siblings: read(&topology.join("thread_siblings_list"))
+                    .map(|list| parse_cpu_list(&list))
+                    .unwrap_or_else(|| vec![id]),
+            });
+
+            let Ok(indices) = fs::read_dir(cpu_dir.join("cache")) else {
+                continue;
+            };
+            for index in indices.flatten() {
+                let path = index.path();
+                if !index.file_name().to_string_lossy().starts_with("index") {
+                    continue;
+                }
+                let Some(level) = read(&path.join("level")).and_then(|v| v.parse().ok()) else {
+                    continue;
+                };
+                let cache_type = match read(&path.join("type")).as_deref() {
+                    Some("Data") => CacheType::Data,
+                    Some("Instruction") => CacheType::Instruction,
+                    _ => CacheType::Unified,
+                };
+                let domain = CacheDomain {
+                    level,
+                    cache_type,
+                    size_kb: read(&path.join("size")).map(|s| parse_size_kb(&s)).unwrap_or(0),
+                    shared_cpus: read(&path.join("shared_cpu_list"))
+                        .map(|list| parse_cpu_list(&list))
+                        .unwrap_or_else(|| vec![id]),
+                };
+                if !caches.contains(&domain) {
+                    caches.push(domain);
+                }
+            }
+        }
+
+        if numa_nodes.is_empty() {
+            numa_nodes.push(NumaNode {
+                id: 0,
+                cpus: processors.iter().map(|processor| processor.id).collect(),
+                memory_mb: None,
+                distances: vec![10],
+            });
+            for processor in &mut processors {
+                processor.numa_node = Some(0);
+            }
+        }
+
+        let clusters = Self::clusters_linux(cpu_root, &processors, &caches);
+        Ok(Self {
+            sockets: sockets.len().max(1) as u32,
+            numa_nodes,
+            processors,
+            caches,
+            clusters,
+        })
+    }
+
+    /// Intel hybrid parts register separate `cpu_core` / `cpu_atom` PMUs; ARM
+    /// DT and ACPI systems publish a relative `cpu_capacity` per CPU
+    #[cfg(target_os = "linux")]
+    fn clusters_linux(
+        cpu_root: &std::path::Path,
+        processors: &[LogicalProcessor],
+        caches: &[CacheDomain],
+    ) -> Vec<CoreCluster> {
+        use std::fs;
+
+        let read = |path: std::path::PathBuf| fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+        let max_frequency_mhz = |cpus: &[u32]| {
+            cpus.iter()
+                .filter_map(|cpu| {
+                    read(cpu_root.join(format!("cpu{cpu}/cpufreq/cpuinfo_max_freq")))
+                        .and_then(|khz| khz.parse::<u32>().ok())
+                })
+                .max()
+                .map(|khz| khz / 1000)
+        };
+
+        let mut groups: Vec<(CoreType, Option<String>, Vec<u32>)> = Vec::new();
+        let pmus = ["cpu_core", "cpu_atom"].map(|pmu| {
+            read(std::path::Path::new("/sys/devices").join(pmu).join("cpus")).map(|list| parse_cpu_list(&list))
+        });
+        if let [Some(performance), Some(efficiency)] = pmus {
+            groups.push((CoreType::Performance, Some("cpu_core".to_string()), performance));
+            groups.push((CoreType::Efficiency, Some("cpu_atom".to_string()), efficiency));
+        } else {
+            let capacities: Vec<(u32, u64)> = processors
+                .iter()
+                .filter_map(|processor| {
+                    read(cpu_root.join(format!("cpu{}/cpu_capacity", processor.id)))
+                        .and_then(|capacity| capacity.parse().ok())
+                        .map(|capacity| (processor.id, capacity))
+                })
+                .collect();
+            if capacities.len() == processors.len() {
+                groups = rank_clusters(&capacities)
+                    .into_iter()
+                    .map(|(core_type, cpus)| (core_type, None, cpus))
+                    .collect();
+            }
+        }
+        if groups.is_empty() {
+            groups.push((
+                CoreType::Performance,
+                None,
+                processors.iter().map(|processor| processor.id).collect(),
+            ));
+        }
+
+        groups
+            .into_iter()
+            .filter(|(_, _, cpus)| !cpus.is_empty())
+            .map(|(core_type, name, logical_cpus)| CoreCluster {
+                core_type,
+                name,
+                physical_cores: count_physical_cores(processors, &logical_cpus),
+                max_frequency_mhz: max_frequency_mhz(&logical_cpus),
+                l2_cache_kb: caches
+                    .iter()
+                    .find(|cache| cache.level == 2 && cache.shared_cpus.contains(&logical_cpus[0]))
+                    .map(|cache| cache.size_kb),
+                logical_cpus,
+            })
+            .collect()
+    }
+
+    #[cfg(target_os = "windows")]
+    fn query_windows() -> Result<Self> {
+        use crate::HardwareQueryError;
+        use std::collections::BTreeMap;
+        use winapi::um::sysinfoapi::GetLogicalProcessorInformationEx;
+
+        const RELATION_PROCESSOR_CORE: u32 = 0;
+        const RELATION_NUMA_NODE: u32 = 1;
+        const RELATION_CACHE: u32 = 2;
+        const RELATION_PROCESSOR_PACKAGE: u32 = 3;
+        const RELATION_ALL: u32 = 0xFFFF;
+
+        let mut length: u32 = 0;
+        // SAFETY: a null buffer with zero length only queries the required size
+        unsafe {
+            GetLogicalProcessorInformationEx(RELATION_ALL, std::ptr::null_mut(), &mut length);
+        }
+        if length == 0 {
+            return Err(HardwareQueryError::system_info_unavailable(
+                "GetLogicalProcessorInformationEx returned no data",
+            ));
+        }
+
+        let mut buffer = vec![0u8; length as usize];
+        // SAFETY: buffer is `length` bytes long, as requested by the previous call
+        let ok = unsafe {
+            GetLogicalProcessorInformationEx(
+                RELATION_ALL,
+                buffer.as_mut_ptr() as *mut _,
+                &mut length,
+            )
+        };
+        if ok == 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+
+        // Records are variable-sized SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX
+        // structs; fields are read at their documented offsets.
+        let u8_at = |offset: usize| buffer[offset];
+        let u16_at = |offset: usize| u16::from_le_bytes([buffer[offset], buffer[offset + 1]]);
+        let u32_at = |offset: usize| {
+            u32::from_le_bytes(buffer[offset..offset + 4].try_into().expect("4-byte slice"))
+        };
+        let group_cpus = |offset: usize| -> Vec<u32> {
+            // GROUP_AFFINITY { KAFFINITY Mask; WORD Group; WORD Reserved[3]; }
+            let mask = u64::from_le_bytes(
+                buffer[offset..offset + 8].try_into().expect("8-byte slice"),
+            );
+            let group = u16::from_le_bytes([buffer[offset + 8], buffer[offset + 9]]) as u32;
+            (0..64)
+                .filter(|bit| mask & (1u64 << bit) != 0)
+                .map(|bit| group * 64 + bit)
+                .collect()
+        };
+
+        let mut cores: Vec<Vec<u32>> = Vec::new();
+        let mut packages: Vec<Vec<u32>> = Vec::new();
+        let mut nodes: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        let mut caches = Vec::new();
+        let mut efficiency_classes: Vec<(u32, u64)> = Vec::new();
+
+        let mut offset = 0usize;
+        while offset + 8 <= buffer.len() {
+            let relationship = u32_at(offset);
+            let size = u32_at(offset + 4) as usize;
+            if size == 0 || offset + size > buffer.len() {
+                break;
+            }
+            let body = offset + 8;
+
+            match relationship {
+                RELATION_PROCESSOR_CORE | RELATION_PROCESSOR_PACKAGE => {
+                    // PROCESSOR_RELATIONSHIP { Flags, EfficiencyClass, Reserved[20], GroupCount, GroupMask[] }
+                    let group_count = u16_at(body + 22) as usize;
+                    let cpus: Vec<u32> = (0..group_count)
+                        .flat_map(|i| group_cpus(body + 24 + i * 16))
+                        .collect();
+                    if relationship == RELATION_PROCESSOR_CORE {
+                        // Higher efficiency classes are faster; all zero on homogeneous CPUs
+                        let efficiency_class = u8_at(body + 1) as u64;
+                        efficiency_classes.extend(cpus.iter().map(|&cpu| (cpu, efficiency_class)));
+                        cores.push(cpus);
+                    } else {
+                        packages.push(cpus);
+                    }
+                }
+                RELATION_NUMA_NODE => {
+                    // NUMA_NODE_RELATIONSHIP { NodeNumber, Reserved[18], GroupCount, GroupMask }
+                    let node = u32_at(body);
+                    nodes.entry(node).or_default().extend(group_cpus(body + 24));
+                }
+                RELATION_CACHE => {
+                    // CACHE_RELATIONSHIP { Level, Associativity, LineSize, CacheSize, Type, Reserved[18], GroupCount, GroupMask }
+                    let cache_type = match u32_at(body + 8) {
+                        1 => CacheType::Instruction,
+                        2 => CacheType::Data,
+                        _ => CacheType::Unified,
+                    };
+                    caches.push(CacheDomain {
+                        level: u8_at(body),
+                        cache_type,
+                        size_kb: u32_at(body + 4) / 1024,
+                        shared_cpus: group_cpus(body + 32),
+                    });
+                }
+                _ => {}
+            }
+
+            offset += size;
+        }
+
+        let mut processors: Vec<LogicalProcessor> = Vec::new();
+        for (socket_id, package) in packages.iter().enumerate() {
+            for (core_id, core) in cores
+                .iter()
+                .filter(|core| core.iter().all(|cpu| package.contains(cpu)))
+                .enumerate()
+            {
+                for &id in core {
+                    processors.push(LogicalProcessor {
+                        id,
+                        core_id: core_id as u32,
+                        socket_id: socket_id as u32,
+                        numa_node: nodes
+                            .iter()
+                            .find(|(_, cpus)| cpus.contains(&id))
+                            .map(|(node, _)| *node),
+                        smt_siblings: core.clone(),
+                    });
+                }
+            }
+        }
+        processors.sort_by_key(|processor| processor.id);
+
+        let clusters = rank_clusters(&efficiency_classes)
+            .into_iter()
+            .map(|(core_type, logical_cpus)| CoreCluster {
+                core_type,
+                name: None,
+                physical_cores: count_physical_cores(&processors, &logical_cpus),
+                max_frequency_mhz: None,
+                l2_cache_kb: caches
+                    .iter()
+                    .find(|cache| cache.level == 2 && cache.shared_cpus.contains(&logical_cpus[0]))
+                    .map(|cache| cache.size_kb),
+                logical_cpus,
+            })
+            .collect();
+
+        Ok(Self {
+            sockets: packages.len().max(1) as u32,
+            numa_nodes: nodes
+                .into_iter()
+                .map(|(id, cpus)| NumaNode {
+                    id,
+                    cpus,
+                    memory_mb: None,
+                    distances: Vec::new(),
+                })
+                .collect(),
+            processors,
+            caches,
+            clusters,
+        })
+    }
+
+    #[cfg(target_os = "macos")]
+    fn query_macos() -> Result<Self> {
+        use crate::probe::Command;
+
+        let sysctl = |name: &str| -> Option<String> {
+            let output = Command::new("sysctl").arg("-n").arg(name).output().ok()?;
+            Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+        let numbers = |name: &str| -> Vec<u64> {
+            sysctl(name)
+                .map(|s| s.split_whitespace().filter_map(|v| v.parse().ok()).collect())
+                .unwrap_or_default()
+        };
+
+        let logical = sysctl("hw.logicalcpu")
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(1);
+        let physical = sysctl("hw.physicalcpu")
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(logical)
+            .max(1);
+        let mut topology = Self::flat(logical);
+        topology.sockets = sysctl("hw.packages")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1);
+
+        // Logical CPUs are numbered core by core on Intel Macs with SMT
+        let threads_per_core = (logical / physical).max(1);
+        for processor in &mut topology.processors {
+            let core_id = processor.id / threads_per_core;
+            processor.core_id = core_id;
+            processor.smt_siblings = (core_id * threads_per_core..(core_id + 1) * threads_per_core)
+                .filter(|cpu| *cpu < logical)
+                .collect();
+        }
+
+        // hw.cacheconfig[i] = number of logical CPUs sharing a level-i cache,
+        // hw.cachesize[i] = size of that cache in bytes (index 0 is memory)
+        let cache_config = numbers("hw.cacheconfig");
+        let cache_sizes = numbers("hw.cachesize");
+        for (level, (&sharing, &size)) in cache_config
+            .iter()
+            .zip(cache_sizes.iter())
+            .enumerate()
+            .skip(1)
+        {
+            if sharing == 0 || size == 0 {
+                continue;
+            }
+            let cache_type = if level == 1 { CacheType::Data } else { CacheType::Unified };
+            for start in (0..logical).step_by(sharing as usize) {
+                topology.caches.push(CacheDomain {
+                    level: level as u8,
+                    cache_type,
+                    size_kb: (size / 1024) as u32,
+                    shared_cpus: (start..(start + sharing as u32).min(logical)).collect(),
+                });
+            }
+        }
+
+        topology.clusters = Self::clusters_macos(sysctl, logical, physical);
+        Ok(topology)
+    }
+
+    /// Apple Silicon describes each core type as a `hw.perflevelN` (0 is the
+    /// fastest). The kernel numbers the efficiency cores first, so logical
+    /// CPUs are assigned from the slowest level up. Peak clocks come from the
+    /// power manager's DVFS tables.
+    #[cfg(target_os = "macos")]
+    fn clusters_macos(sysctl: impl Fn(&str) -> Option<String>, logical: u32, physical: u32) -> Vec<CoreCluster> {
+        let number = |name: &str| sysctl(name).and_then(|v| v.parse::<u32>().ok());
+        let levels = number("hw.nperflevels").unwrap_or(0);
+
+        if levels < 2 {
+            return vec![CoreCluster {
+                core_type: CoreType::Performance,
+                name: None,
+                physical_cores: physical,
+                logical_cpus: (0..logical).collect(),
+                max_frequency_mhz: sysctl("hw.cpufrequency_max")
+                    .and_then(|hz| hz.parse::<u64>().ok())
+                    .map(|hz| (hz / 1_000_000) as u32),
+                l2_cache_kb: None,
+            }];
+        }
+
+        let dvfs = apple_dvfs_max_frequencies();
+        let mut clusters = Vec::new();
+        let mut next_cpu = 0;
+        for level in (0..levels).rev() {
+            let key = |field: &str| format!("hw.perflevel{level}.{field}");
+            let level_logical = number(&key("logicalcpu")).unwrap_or(0);
+            let core_type = if level == levels - 1 {
+                CoreType::Efficiency
+            } else {
+                CoreType::Performance
+            };
+            clusters.push(CoreCluster {
+                core_type,
+                name: sysctl(&key("name")).filter(|name| !name.is_empty()),
+                physical_cores: number(&key("physicalcpu")).unwrap_or(level_logical),
+                logical_cpus: (next_cpu..(next_cpu + level_logical).min(logical)).collect(),
+                max_frequency_mhz: match core_type {
+                    CoreType::Efficiency => dvfs.efficiency,
+                    CoreType::Performance if level == 0 => dvfs.performance,
+                    CoreType::Performance => None,
+                },
+                l2_cache_kb: number(&key("l2cachesize")).map(|bytes| bytes / 1024),
+            });
+            next_cpu += level_logical;
+        }
+        clusters.reverse();
+        clusters
+    }
+}
+
+/// Peak E- and P-cluster clocks in MHz from Apple Silicon DVFS tables
+#[cfg(target_os = "macos")]
+#[derive(Debug, Default)]
+struct AppleDvfs {
+    efficiency: Option<u32>,
+    performance: Option<u32>,
+}
+
+/// Read the `voltage-states1-sram` (E-cluster) and `voltage-states5-sram`
+/// (P-cluster) tables of the `pmgr` node: pairs of little-endian u32
+/// (frequency, voltage), with frequencies in Hz up to M3 and kHz on M4
+#[cfg(target_os = "macos")]
+fn apple_dvfs_max_frequencies() -> AppleDvfs {
+    use crate::probe::Command;
+
+    let Ok(output) = Command::new("ioreg").args(["-r", "-d", "1", "-w", "0", "-n", "pmgr"]).output() else {
+        return AppleDvfs::default();
+    };
+    let text = String::from_utf8_lossy(&output.stdout);
+    let table_max = |property: &str| -> Option<u32> {
+        let line = text.lines().find(|line| line.contains(&format!("\"{property}\"")))?;
+        let hex = line.split_once('<')?.1.split_once('>')?.0;
+        let bytes: Vec<u8> = (0..hex.len() / 2)
+            .filter_map(|i| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok())
+            .collect();
+        let max = bytes
+            .chunks_exact(8)
+            .map(|pair| u32::from_le_bytes([pair[0], pair[1], pair[2], pair[3]]))
+            .max()?;
+        Some(if max > 100_000_000 { max / 1_000_000 } else { max / 1000 })
+    };
+
+    AppleDvfs {
+        efficiency: table_max("voltage-states1-sram"),
+        performance: table_max("voltage-states5-sram"),
+    }
+}
+
+/// Parse a Linux CPU list such as `0-3,8,10-11`
+pub(crate) fn parse_cpu_list(list: &str) -> Vec<u32> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',').filter(|part| !part.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.trim().parse::<u32>(), end.trim().parse::<u32>()) {
+                    cpus.extend(start..=end);
+                }
+            }
+            None => {
+                if let Ok(cpu) = part.trim().parse() {
+                    cpus.push(cpu);
+                }
+            }
+        }
+    }
+    cpus
+}
+
+/// Group logical CPUs by a relative performance rank, fastest first
+///
+/// The slowest group holds the efficiency cores; every faster group (ARM
+/// prime and big cores alike) is a performance cluster. A single rank means
+/// a homogeneous CPU.
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn rank_clusters(ranks: &[(u32, u64)]) -> Vec<(CoreType, Vec<u32>)> {
+    let mut levels: Vec<u64> = ranks.iter().map(|(_, rank)| *rank).collect();
+    levels.sort_unstable_by(|a, b| b.cmp(a));
+    levels.dedup();
+
+    let slowest = levels.len().saturating_sub(1);
+    levels
+        .iter()
+        .enumerate()
+        .map(|(index, level)| {
+            let core_type = if index == slowest && index > 0 {
+                CoreType::Efficiency
+            } else {
+                CoreType::Performance
+            };
+            let mut cpus: Vec<u32> = ranks
+                .iter()
+                .filter(|(_, rank)| rank == level)
+                .map(|(cpu, _)| *cpu)
+                .collect();
+            cpus.sort_unstable();
+            (core_type, cpus)
+        })
+        .collect()
+}
+
+/// Number of distinct physical cores among `cpus`
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn count_physical_cores(processors: &[LogicalProcessor], cpus: &[u32]) -> u32 {
+    let mut cores: Vec<(u32, u32)> = processors
+        .iter()
+        .filter(|processor| cpus.contains(&processor.id))
+        .map(|processor| (processor.socket_id, processor.core_id))
+        .collect();
+    cores.sort_unstable();
+    cores.dedup();
+    cores.len() as u32
+}
+
+/// Parse a sysfs cache size such as `32K` or `1M` into KB
+#[cfg(target_os = "linux")]
+fn parse_size_kb(size: &str) -> u32 {
+    let size = size.trim();
+    if let Some(kb) = size.strip_suffix('K') {
+        kb.parse().unwrap_or(0)
+    } else if let Some(mb) = size.strip_suffix('M') {
+        mb.parse::<u32>().map(|mb| mb * 1024).unwrap_or(0)
+    } else {
+        size.parse::<u32>().map(|bytes| bytes / 1024).unwrap_or(0)
+    }
+}
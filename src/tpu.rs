@@ -1,9 +1,10 @@
-use crate::Result;
+use crate::{PCIDevice, Result, USBDevice};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 #[cfg(target_os = "linux")]
-use std::process::Command;
+use crate::probe::Command;
 
 /// TPU vendor information
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -186,85 +187,88 @@ impl TPUInfo {
     
     /// Detect Google Coral Edge TPUs
     fn detect_edge_tpus() -> Result<Vec<TPUInfo>> {
-        let tpus = Vec::new();
+        let mut tpus = Vec::new();
         
-        #[cfg(target_os = "linux")]
-        {
-            // Check for Edge TPU via USB
-            if let Ok(output) = Command::new("lsusb").output() {
-                let output_str = String::from_utf8_lossy(&output.stdout);
-                for line in output_str.lines() {
-                    if line.contains("18d1") && line.contains("9302") { // Google Edge TPU USB
-                        tpus.push(TPUInfo {
-                            vendor: TPUVendor::Google,
-                            model_name: "Google Coral Edge TPU".to_string(),
-                            architecture: TPUArchitecture::GoogleCoralEdge,
-                            connection_type: TPUConnectionType::USB,
-                            tops_performance: Some(4.0), // 4 TOPS at INT8
-                            memory_gb: None, // Uses host memory
-                            memory_bandwidth_gbps: Some(2.0), // USB 3.0 bandwidth
-                            core_count: Some(1),
-                            driver_version: Self::get_edge_tpu_driver_version(),
-                            firmware_version: None,
-                            device_id: Some("18d1:9302".to_string()),
-                            supported_frameworks: vec![
-                                "TensorFlow Lite".to_string(),
-                                "PyCoral".to_string(),
-                                "OpenVINO".to_string(),
-                            ],
-                            power_consumption: Some(2.0), // ~2W
-                            temperature: None,
-                            clock_frequency: Some(500), // ~500MHz
-                            supported_dtypes: vec![
-                                "int8".to_string(),
-                                "uint8".to_string(),
-                            ],
-                            capabilities: HashMap::from([
-                                ("quantized_only".to_string(), "true".to_string()),
-                                ("edge_optimized".to_string(), "true".to_string()),
-                            ]),
-                        });
-                    }
-                }
-            }
-            
-            // Check for Edge TPU via PCIe (M.2 or Mini PCIe)
-            if let Ok(output) = Command::new("lspci").output() {
-                let output_str = String::from_utf8_lossy(&output.stdout);
-                for line in output_str.lines() {
-                    if line.contains("Coral") || (line.contains("Google") && line.contains("Edge")) {
-                        tpus.push(TPUInfo {
-                            vendor: TPUVendor::Google,
-                            model_name: "Google Coral Edge TPU (PCIe)".to_string(),
-                            architecture: TPUArchitecture::GoogleCoralEdge,
-                            connection_type: TPUConnectionType::M2,
-                            tops_performance: Some(4.0),
-                            memory_gb: None,
-                            memory_bandwidth_gbps: Some(8.0), // PCIe bandwidth
-                            core_count: Some(1),
-                            driver_version: Self::get_edge_tpu_driver_version(),
-                            firmware_version: None,
-                            device_id: None,
-                            supported_frameworks: vec![
-                                "TensorFlow Lite".to_string(),
-                                "PyCoral".to_string(),
-                            ],
-                            power_consumption: Some(2.5), // Slightly higher for PCIe
-                            temperature: None,
-                            clock_frequency: Some(500),
-                            supported_dtypes: vec![
-                                "int8".to_string(),
-                                "uint8".to_string(),
-                            ],
-                            capabilities: HashMap::from([
-                                ("quantized_only".to_string(), "true".to_string()),
-                                ("edge_optimized".to_string(), "true".to_string()),
-                                ("pcie_interface".to_string(), "true".to_string()),
-                            ]),
-                        });
-                    }
-                }
+        // USB accelerators enumerate as 1a6e:089a until libedgetpu loads their
+        // firmware, then as 18d1:9302
+        for device in USBDevice::query_all().unwrap_or_default() {
+            let id = format!("{}:{}", device.vendor_id, device.product_id).to_lowercase();
+            let firmware_loaded = match id.as_str() {
+                EDGE_TPU_USB_ID => true,
+                EDGE_TPU_USB_BOOT_ID => false,
+                _ => continue,
+            };
+            let validation = EdgeTpuValidation::check_usb(&device, firmware_loaded);
+            let mut capabilities = HashMap::from([
+                ("quantized_only".to_string(), "true".to_string()),
+                ("edge_optimized".to_string(), "true".to_string()),
+            ]);
+            validation.record(&mut capabilities);
+            tpus.push(TPUInfo {
+                vendor: TPUVendor::Google,
+                model_name: "Google Coral Edge TPU".to_string(),
+                architecture: TPUArchitecture::GoogleCoralEdge,
+                connection_type: TPUConnectionType::USB,
+                tops_performance: Some(4.0), // 4 TOPS at INT8
+                memory_gb: None, // Uses host memory
+                memory_bandwidth_gbps: Some(2.0), // USB 3.0 bandwidth
+                core_count: Some(1),
+                driver_version: None, // No kernel driver; libusb talks to the device
+                firmware_version: None,
+                device_id: Some(id),
+                supported_frameworks: vec![
+                    "TensorFlow Lite".to_string(),
+                    "PyCoral".to_string(),
+                    "OpenVINO".to_string(),
+                ],
+                power_consumption: Some(2.0), // ~2W
+                temperature: None,
+                clock_frequency: Some(500), // ~500MHz
+                supported_dtypes: vec![
+                    "int8".to_string(),
+                    "uint8".to_string(),
+                ],
+                capabilities,
+            });
+        }
+        
+        // Check for Edge TPU via PCIe (M.2 or Mini PCIe): Global Unichip 1ac1:089a
+        for device in PCIDevice::query_all().unwrap_or_default() {
+            if device.vendor_id() != Some(0x1AC1) || device.product_id() != Some(0x089A) {
+                continue;
             }
+            let validation = EdgeTpuValidation::check_pcie();
+            let mut capabilities = HashMap::from([
+                ("quantized_only".to_string(), "true".to_string()),
+                ("edge_optimized".to_string(), "true".to_string()),
+                ("pcie_interface".to_string(), "true".to_string()),
+            ]);
+            validation.record(&mut capabilities);
+            tpus.push(TPUInfo {
+                vendor: TPUVendor::Google,
+                model_name: "Google Coral Edge TPU (PCIe)".to_string(),
+                architecture: TPUArchitecture::GoogleCoralEdge,
+                connection_type: TPUConnectionType::M2,
+                tops_performance: Some(4.0),
+                memory_gb: None,
+                memory_bandwidth_gbps: Some(8.0), // PCIe bandwidth
+                core_count: Some(1),
+                driver_version: validation.driver_version.clone(),
+                firmware_version: None,
+                device_id: Some(device.device_id().to_string()),
+                supported_frameworks: vec![
+                    "TensorFlow Lite".to_string(),
+                    "PyCoral".to_string(),
+                ],
+                power_consumption: Some(2.5), // Slightly higher for PCIe
+                temperature: None,
+                clock_frequency: Some(500),
+                supported_dtypes: vec![
+                    "int8".to_string(),
+                    "uint8".to_string(),
+                ],
+                capabilities,
+            });
         }
         
         Ok(tpus)
@@ -272,69 +276,62 @@ impl TPUInfo {
     
     /// Detect Intel Habana accelerators
     fn detect_intel_habana() -> Result<Vec<TPUInfo>> {
-        let tpus = Vec::new();
+        let mut tpus = Vec::new();
         
-        #[cfg(target_os = "linux")]
-        {
-            // Check for Habana devices via sysfs
-            if std::path::Path::new("/sys/class/accel").exists() {
-                if let Ok(entries) = std::fs::read_dir("/sys/class/accel") {
-                    for entry in entries.flatten() {
-                        if let Some(name) = entry.file_name().to_str() {
-                            if name.starts_with("accel") {
-                                // Try to determine if it's Habana
-                                let device_path = format!("/sys/class/accel/{}/device", name);
-                                if let Ok(vendor) = std::fs::read_to_string(format!("{}/vendor", device_path)) {
-                                    if vendor.trim() == "0x1da3" { // Intel vendor ID for Habana
-                                        if let Ok(device) = std::fs::read_to_string(format!("{}/device", device_path)) {
-                                            let (model_name, architecture, tops) = match device.trim() {
-                                                "0x1000" => ("Intel Habana Gaudi", TPUArchitecture::IntelHabanaGaudi, 400.0),
-                                                "0x1020" => ("Intel Habana Gaudi2", TPUArchitecture::IntelHabanaGaudi2, 800.0),
-                                                "0x1050" => ("Intel Habana Goya", TPUArchitecture::IntelHabanaGoya, 100.0),
-                                                _ => ("Intel Habana Device", TPUArchitecture::IntelHabanaGaudi, 400.0),
-                                            };
-                                            
-                                            tpus.push(TPUInfo {
-                                                vendor: TPUVendor::Intel,
-                                                model_name: model_name.to_string(),
-                                                architecture,
-                                                connection_type: TPUConnectionType::PCIe,
-                                                tops_performance: Some(tops),
-                                                memory_gb: Some(32.0), // Typical for Gaudi
-                                                memory_bandwidth_gbps: Some(2400.0), // HBM2E bandwidth
-                                                core_count: Some(8), // Typical core count
-                                                driver_version: Self::get_habana_driver_version(),
-                                                firmware_version: None,
-                                                device_id: Some(device.trim().to_string()),
-                                                supported_frameworks: vec![
-                                                    "PyTorch".to_string(),
-                                                    "TensorFlow".to_string(),
-                                                    "ONNX Runtime".to_string(),
-                                                    "Habana SynapseAI".to_string(),
-                                                ],
-                                                power_consumption: Some(350.0), // High performance = high power
-                                                temperature: None,
-                                                clock_frequency: Some(1300), // ~1.3GHz
-                                                supported_dtypes: vec![
-                                                    "float32".to_string(),
-                                                    "bfloat16".to_string(),
-                                                    "float16".to_string(),
-                                                    "int8".to_string(),
-                                                ],
-                                                capabilities: HashMap::from([
-                                                    ("matrix_multiply_engine".to_string(), "true".to_string()),
-                                                    ("tensor_processor_core".to_string(), "true".to_string()),
-                                                    ("high_bandwidth_memory".to_string(), "true".to_string()),
-                                                ]),
-                                            });
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+        // Habana Labs PCI vendor ID
+        for device in PCIDevice::query_all().unwrap_or_default() {
+            if device.vendor_id() != Some(0x1DA3) {
+                continue;
             }
+            let Some(device_id) = device.product_id() else {
+                continue;
+            };
+            let (model_name, architecture, tops) = match device_id {
+                0x1000 => ("Intel Habana Gaudi".to_string(), TPUArchitecture::IntelHabanaGaudi, 400.0),
+                0x1020 => ("Intel Habana Gaudi2".to_string(), TPUArchitecture::IntelHabanaGaudi2, 800.0),
+                0x0001 => ("Intel Habana Goya".to_string(), TPUArchitecture::IntelHabanaGoya, 100.0),
+                _ => {
+                    let name = Some(device.device_name())
+                        .filter(|name| !name.is_empty())
+                        .map(|name| format!("Intel Habana {name}"))
+                        .unwrap_or_else(|| "Intel Habana Device".to_string());
+                    (name, TPUArchitecture::IntelHabanaGaudi, 400.0)
+                }
+            };
+            
+            tpus.push(TPUInfo {
+                vendor: TPUVendor::Intel,
+                model_name,
+                architecture,
+                connection_type: TPUConnectionType::PCIe,
+                tops_performance: Some(tops),
+                memory_gb: Some(32.0), // Typical for Gaudi
+                memory_bandwidth_gbps: Some(2400.0), // HBM2E bandwidth
+                core_count: Some(8), // Typical core count
+                driver_version: Self::get_habana_driver_version(),
+                firmware_version: None,
+                device_id: Some(device.device_id().to_string()),
+                supported_frameworks: vec![
+                    "PyTorch".to_string(),
+                    "TensorFlow".to_string(),
+                    "ONNX Runtime".to_string(),
+                    "Habana SynapseAI".to_string(),
+                ],
+                power_consumption: Some(350.0), // High performance = high power
+                temperature: None,
+                clock_frequency: Some(1300), // ~1.3GHz
+                supported_dtypes: vec![
+                    "float32".to_string(),
+                    "bfloat16".to_string(),
+                    "float16".to_string(),
+                    "int8".to_string(),
+                ],
+                capabilities: HashMap::from([
+                    ("matrix_multiply_engine".to_string(), "true".to_string()),
+                    ("tensor_processor_core".to_string(), "true".to_string()),
+                    ("high_bandwidth_memory".to_string(), "true".to_string()),
+                ]),
+            });
         }
         
         Ok(tpus)
@@ -352,6 +349,24 @@ impl TPUInfo {
     }
     
     // Helper functions for driver version detection
+    /// Version of the "habanalabs" kernel driver
+    fn get_habana_driver_version() -> Option<String> {
+        Self::kernel_module_version("habanalabs")
+    }
+    
+    fn kernel_module_version(_module: &str) -> Option<String> {
+        #[cfg(target_os = "linux")]
+        {
+            std::fs::read_to_string(format!("/sys/module/{_module}/version"))
+                .ok()
+                .map(|version| version.trim().to_string())
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
+    }
+    
     fn get_tpu_driver_version() -> Option<String> {
         // Try to get TensorFlow version with TPU support
         #[cfg(target_os = "linux")]
@@ -400,3 +415,205 @@ impl TPUInfo {
             .any(|d| d.to_lowercase() == dtype.to_lowercase())
     }
 }
+
+/// Coral USB Accelerator once libedgetpu has loaded its firmware
+const EDGE_TPU_USB_ID: &str = "18d1:9302";
+/// Coral USB Accelerator before its firmware is loaded
+const EDGE_TPU_USB_BOOT_ID: &str = "1a6e:089a";
+
+/// Where the Coral packages install libedgetpu
+const EDGE_TPU_LIBRARY_DIRS: &[&str] = &[
+    "/usr/lib/x86_64-linux-gnu",
+    "/usr/lib/aarch64-linux-gnu",
+    "/usr/lib/arm-linux-gnueabihf",
+    "/usr/lib64",
+    "/usr/lib",
+    "/usr/local/lib",
+    "/opt/homebrew/lib",
+    r"C:\Windows\System32",
+];
+
+/// Whether a detected Edge TPU can actually be used
+///
+/// Detection only proves the device is on the bus. Inference also needs the
+/// libedgetpu runtime, and on PCIe/M.2 modules the gasket and apex kernel
+/// modules and an accessible `/dev/apex_N` node. [`remediation`](Self::remediation)
+/// lists what to fix; the results are also recorded in [`TPUInfo::capabilities`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeTpuValidation {
+    /// How the Edge TPU is attached
+    pub connection_type: TPUConnectionType,
+    /// Device nodes the runtime opens (`/dev/apex_0`, `/dev/bus/usb/002/003`)
+    pub device_nodes: Vec<String>,
+    /// Whether the current user can read and write every device node, when known
+    pub device_accessible: Option<bool>,
+    /// libedgetpu runtime library, if found
+    pub library_path: Option<PathBuf>,
+    /// Whether the gasket framework module is loaded (PCIe, Linux)
+    pub gasket_loaded: bool,
+    /// Whether the apex driver module is loaded (PCIe, Linux)
+    pub apex_loaded: bool,
+    /// apex driver version
+    pub driver_version: Option<String>,
+    /// Whether a USB accelerator runs the runtime's firmware (it is loaded on first use)
+    pub firmware_loaded: Option<bool>,
+}
+
+impl EdgeTpuValidation {
+    /// Check the runtime for a USB accelerator
+    fn check_usb(device: &USBDevice, firmware_loaded: bool) -> Self {
+        let node = format!("/dev/bus/usb/{:03}/{:03}", device.bus_number, device.device_address);
+        let device_nodes: Vec<String> = cfg!(target_os = "linux")
+            .then_some(node)
+            .filter(|node| std::path::Path::new(node).exists())
+            .into_iter()
+            .collect();
+        Self {
+            connection_type: TPUConnectionType::USB,
+            device_accessible: Self::accessible(&device_nodes),
+            device_nodes,
+            library_path: Self::find_library(),
+            gasket_loaded: false,
+            apex_loaded: false,
+            driver_version: None,
+            firmware_loaded: Some(firmware_loaded),
+        }
+    }
+
+    /// Check the runtime and driver for a PCIe or M.2 module
+    fn check_pcie() -> Self {
+        let mut device_nodes: Vec<String> = std::fs::read_dir("/dev")
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| name.starts_with("apex_"))
+            .map(|name| format!("/dev/{name}"))
+            .collect();
+        device_nodes.sort();
+        Self {
+            connection_type: TPUConnectionType::M2,
+            device_accessible: Self::accessible(&device_nodes),
+            device_nodes,
+            library_path: Self::find_library(),
+            gasket_loaded: std::path::Path::new("/sys/module/gasket").exists(),
+            apex_loaded: std::path::Path::new("/sys/module/apex").exists(),
+            driver_version: TPUInfo::kernel_module_version("apex"),
+            firmware_loaded: None,
+        }
+    }
+
+    /// libedgetpu in the loader search path or a standard install location
+    fn find_library() -> Option<PathBuf> {
+        let (file_name, search_path) = if cfg!(target_os = "windows") {
+            ("edgetpu.dll", "PATH")
+        } else if cfg!(target_os = "macos") {
+            ("libedgetpu.1.dylib", "DYLD_LIBRARY_PATH")
+        } else {
+            ("libedgetpu.so.1", "LD_LIBRARY_PATH")
+        };
+        std::env::var_os(search_path)
+            .map(|paths| std::env::split_paths(&paths).collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter()
+            .chain(EDGE_TPU_LIBRARY_DIRS.iter().map(PathBuf::from))
+            .map(|dir| dir.join(file_name))
+            .find(|path| path.exists())
+    }
+
+    /// Whether the current user may open every node for reading and writing
+    fn accessible(device_nodes: &[String]) -> Option<bool> {
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            (!device_nodes.is_empty()).then(|| {
+                device_nodes.iter().all(|node| {
+                    let Ok(path) = std::ffi::CString::new(std::ffi::OsStr::new(node).as_bytes()) else {
+                        return false;
+                    };
+                    // SAFETY: path is a valid NUL-terminated string
+                    unsafe { libc::access(path.as_ptr(), libc::R_OK | libc::W_OK) == 0 }
+                })
+            })
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = device_nodes;
+            None
+        }
+    }
+
+    /// Steps that stand between the detected device and a working runtime
+    pub fn remediation(&self) -> Vec<String> {
+        let mut steps = Vec::new();
+        let linux = cfg!(target_os = "linux");
+        let pcie = self.connection_type != TPUConnectionType::USB;
+
+        if self.library_path.is_none() {
+            steps.push(if cfg!(target_os = "windows") {
+                "Install the Edge TPU runtime (edgetpu.dll) from https://coral.ai/software and run its install.bat".to_string()
+            } else if cfg!(target_os = "macos") {
+                "Install the Edge TPU runtime (libedgetpu.1.dylib) from https://coral.ai/software and run its install.sh".to_string()
+            } else {
+                "Install the Edge TPU runtime: add the coral-edgetpu-stable apt repository and run \
+                 `sudo apt-get install libedgetpu1-std`"
+                    .to_string()
+            });
+        }
+
+        if pcie && linux {
+            if !self.gasket_loaded || !self.apex_loaded {
+                steps.push(
+                    "Install the PCIe driver with `sudo apt-get install gasket-dkms`, then reboot or run `sudo modprobe apex`"
+                        .to_string(),
+                );
+            } else if self.device_nodes.is_empty() {
+                steps.push("The apex driver is loaded but no /dev/apex_N node exists; check `dmesg | grep apex` for errors".to_string());
+            }
+        }
+
+        if self.device_accessible == Some(false) {
+            steps.push(if pcie {
+                format!(
+                    "Grant access to {}: add the udev rule `SUBSYSTEM==\"apex\", MODE=\"0660\", GROUP=\"apex\"`, \
+                     then `sudo groupadd apex; sudo usermod -aG apex $USER` and log in again",
+                    self.device_nodes.join(", ")
+                )
+            } else {
+                "Grant access to the USB accelerator: install the udev rules shipped with libedgetpu \
+                 (99-edgetpu-accelerator.rules), add the user to the plugdev group and replug the device"
+                    .to_string()
+            });
+        }
+
+        steps
+    }
+
+    /// Check if nothing is missing
+    pub fn is_ready(&self) -> bool {
+        self.remediation().is_empty()
+    }
+
+    /// Record the results and remediation steps as TPU capabilities
+    fn record(&self, capabilities: &mut HashMap<String, String>) {
+        let remediation = self.remediation();
+        capabilities.insert("runtime_ready".to_string(), remediation.is_empty().to_string());
+        capabilities.insert(
+            "libedgetpu".to_string(),
+            self.library_path.as_ref().map_or("not found".to_string(), |path| path.display().to_string()),
+        );
+        if !self.device_nodes.is_empty() {
+            capabilities.insert("device_node".to_string(), self.device_nodes.join(", "));
+        }
+        if let Some(version) = &self.driver_version {
+            capabilities.insert("apex_driver_version".to_string(), version.clone());
+        }
+        if let Some(loaded) = self.firmware_loaded {
+            let state = if loaded { "loaded" } else { "not loaded (libedgetpu loads it on first use)" };
+            capabilities.insert("usb_firmware".to_string(), state.to_string());
+        }
+        if !remediation.is_empty() {
+            capabilities.insert("remediation".to_string(), remediation.join("; "));
+        }
+    }
+}
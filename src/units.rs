@@ -0,0 +1,83 @@
+//! Byte-size formatting
+//!
+//! The `*_gb()` accessors return binary gigabytes (GiB) as floats for quick
+//! comparisons. For exact arithmetic use the `*_bytes()` accessors, and for
+//! display pick a [`UnitSystem`] so every size in a UI uses the same units.
+
+use serde::{Deserialize, Serialize};
+
+const BINARY_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const DECIMAL_UNITS: [&str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+
+/// Units used to present byte sizes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UnitSystem {
+    /// Powers of 1024 (KiB, MiB, GiB), as memory and most operating systems report
+    #[default]
+    Binary,
+    /// Powers of 1000 (kB, MB, GB), as drive vendors and macOS Finder report
+    Decimal,
+}
+
+impl UnitSystem {
+    /// Size of one "kilo" step: 1024 or 1000
+    pub fn base(self) -> u64 {
+        match self {
+            UnitSystem::Binary => 1024,
+            UnitSystem::Decimal => 1000,
+        }
+    }
+
+    /// Convert bytes to gigabytes (GiB or GB)
+    pub fn to_gigabytes(self, bytes: u64) -> f64 {
+        bytes as f64 / (self.base() as f64).powi(3)
+    }
+
+    /// Format bytes with the largest unit below the value and one decimal place
+    ///
+    /// ```rust
+    /// use hardware_query::UnitSystem;
+    ///
+    /// assert_eq!(UnitSystem::Binary.format(16 * 1024 * 1024 * 1024), "16.0 GiB");
+    /// assert_eq!(UnitSystem::Decimal.format(16 * 1024 * 1024 * 1024), "17.2 GB");
+    /// assert_eq!(UnitSystem::Decimal.format(512), "512 B");
+    /// ```
+    pub fn format(self, bytes: u64) -> String {
+        self.format_with_precision(bytes, 1)
+    }
+
+    /// Format bytes with the largest unit below the value and `precision` decimal places
+    pub fn format_with_precision(self, bytes: u64, precision: usize) -> String {
+        let units = match self {
+            UnitSystem::Binary => &BINARY_UNITS,
+            UnitSystem::Decimal => &DECIMAL_UNITS,
+        };
+        let base = self.base() as f64;
+        let mut value = bytes as f64;
+        let mut unit = 0;
+        while value >= base && unit < units.len() - 1 {
+            value /= base;
+            unit += 1;
+        }
+        if unit == 0 {
+            format!("{bytes} B")
+        } else {
+            format!("{value:.precision$} {}", units[unit])
+        }
+    }
+}
+
+impl std::fmt::Display for UnitSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnitSystem::Binary => write!(f, "Binary"),
+            UnitSystem::Decimal => write!(f, "Decimal"),
+        }
+    }
+}
+
+/// Bytes in one mebibyte, the unit most `*_mb` fields are stored in
+pub(crate) const MIB: u64 = 1024 * 1024;
+
+/// Bytes in one gibibyte, the unit most `*_gb` fields are stored in
+pub(crate) const GIB: u64 = 1024 * 1024 * 1024;
@@ -1,6 +1,70 @@
+//! USB devices and their hub topology
+//!
+//! Every device records the hub it hangs off and the port it uses, so
+//! [`USBTopology`] can rebuild the tree `lsusb -t` shows. Class codes are
+//! decoded to names, and vendor/product names missing from the device's own
+//! string descriptors are resolved through the system `usb.ids` database.
+
 use crate::Result;
 use serde::{Deserialize, Serialize};
 
+/// Negotiated USB signalling rate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum USBSpeed {
+    /// 1.5 Mbit/s (USB 1.0)
+    Low,
+    /// 12 Mbit/s (USB 1.1)
+    Full,
+    /// 480 Mbit/s (USB 2.0)
+    High,
+    /// 5 Gbit/s (USB 3.2 Gen 1)
+    Super,
+    /// 10 Gbit/s (USB 3.2 Gen 2)
+    SuperPlus,
+    /// 20 Gbit/s (USB 3.2 Gen 2x2)
+    SuperPlusGen2x2,
+}
+
+impl USBSpeed {
+    /// Signalling rate in Mbit/s
+    pub fn mbps(&self) -> f64 {
+        match self {
+            USBSpeed::Low => 1.5,
+            USBSpeed::Full => 12.0,
+            USBSpeed::High => 480.0,
+            USBSpeed::Super => 5_000.0,
+            USBSpeed::SuperPlus => 10_000.0,
+            USBSpeed::SuperPlusGen2x2 => 20_000.0,
+        }
+    }
+
+    /// Map a rate in Mbit/s (as reported by sysfs) to a speed
+    pub fn from_mbps(mbps: f64) -> Option<Self> {
+        match mbps {
+            m if m <= 0.0 => None,
+            m if m < 12.0 => Some(USBSpeed::Low),
+            m if m < 480.0 => Some(USBSpeed::Full),
+            m if m < 5_000.0 => Some(USBSpeed::High),
+            m if m < 10_000.0 => Some(USBSpeed::Super),
+            m if m < 20_000.0 => Some(USBSpeed::SuperPlus),
+            _ => Some(USBSpeed::SuperPlusGen2x2),
+        }
+    }
+}
+
+impl std::fmt::Display for USBSpeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            USBSpeed::Low => write!(f, "Low Speed (1.5 Mbps)"),
+            USBSpeed::Full => write!(f, "Full Speed (12 Mbps)"),
+            USBSpeed::High => write!(f, "High Speed (480 Mbps)"),
+            USBSpeed::Super => write!(f, "SuperSpeed (5 Gbps)"),
+            USBSpeed::SuperPlus => write!(f, "SuperSpeed+ (10 Gbps)"),
+            USBSpeed::SuperPlusGen2x2 => write!(f, "SuperSpeed+ Gen 2x2 (20 Gbps)"),
+        }
+    }
+}
+
 /// USB device information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct USBDevice {
@@ -28,14 +92,54 @@ pub struct USBDevice {
     pub driver: Option<String>,
     /// Is device currently connected
     pub connected: bool,
+    /// Stable identifier within the topology (e.g. `1-2.3`, `usb1` for root hubs)
+    #[serde(default)]
+    pub id: String,
+    /// Identifier of the hub this device is plugged into (`None` for root hubs)
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    /// Port number on the parent hub
+    #[serde(default)]
+    pub port: Option<u8>,
+    /// Negotiated speed
+    #[serde(default)]
+    pub speed: Option<USBSpeed>,
+    /// Raw bDeviceClass code
+    #[serde(default)]
+    pub class_code: u8,
+    /// Raw bDeviceSubClass code
+    #[serde(default)]
+    pub subclass_code: u8,
+    /// Raw bDeviceProtocol code
+    #[serde(default)]
+    pub protocol_code: u8,
+    /// Decoded subclass / protocol name
+    #[serde(default)]
+    pub subclass_name: Option<String>,
+    /// Decoded classes of the device's interfaces (composite devices)
+    #[serde(default)]
+    pub interface_classes: Vec<String>,
 }
 
 impl USBDevice {
     /// Query all USB devices
     pub fn query_all() -> Result<Vec<Self>> {
-        // Platform-specific implementation would go here
-        // For now, return empty vector
-        Ok(vec![])
+        #[cfg(target_os = "linux")]
+        {
+            Self::query_linux()
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Self::query_windows()
+        }
+        #[cfg(target_os = "macos")]
+        {
+            Self::query_macos()
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        {
+            Ok(Vec::new())
+        }
     }
 
     /// Get vendor ID
@@ -73,35 +177,554 @@ impl USBDevice {
         self.connected
     }
 
+    /// Get topology identifier
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get parent hub identifier
+    pub fn parent_id(&self) -> Option<&str> {
+        self.parent_id.as_deref()
+    }
+
+    /// Get port number on the parent hub
+    pub fn port(&self) -> Option<u8> {
+        self.port
+    }
+
+    /// Get negotiated speed
+    pub fn speed(&self) -> Option<USBSpeed> {
+        self.speed
+    }
+
+    /// Get decoded subclass name
+    pub fn subclass_name(&self) -> Option<&str> {
+        self.subclass_name.as_deref()
+    }
+
+    /// Get decoded interface classes
+    pub fn interface_classes(&self) -> &[String] {
+        &self.interface_classes
+    }
+
+    /// Check if device is a hub
+    pub fn is_hub(&self) -> bool {
+        self.class_code == 0x09
+    }
+
+    /// Check if device is a root hub of a host controller
+    pub fn is_root_hub(&self) -> bool {
+        self.is_hub() && self.parent_id.is_none()
+    }
+
     /// Check if device is a storage device
     pub fn is_storage_device(&self) -> bool {
-        self.device_class.to_lowercase().contains("mass storage")
-            || self.device_class.to_lowercase().contains("storage")
+        self.has_class(&["mass storage", "storage"])
     }
 
     /// Check if device is an input device
     pub fn is_input_device(&self) -> bool {
-        self.device_class.to_lowercase().contains("hid")
-            || self.device_class.to_lowercase().contains("human interface")
-            || self.device_class.to_lowercase().contains("input")
+        self.has_class(&["hid", "human interface", "input"])
     }
 
     /// Check if device is an audio device
     pub fn is_audio_device(&self) -> bool {
-        self.device_class.to_lowercase().contains("audio")
+        self.has_class(&["audio"])
     }
 
     /// Check if device is a video device
     pub fn is_video_device(&self) -> bool {
-        self.device_class.to_lowercase().contains("video")
-            || self.device_class.to_lowercase().contains("camera")
+        self.has_class(&["video", "camera"])
+    }
+
+    /// Device class or any interface class contains one of `needles`
+    fn has_class(&self, needles: &[&str]) -> bool {
+        std::iter::once(&self.device_class)
+            .chain(&self.interface_classes)
+            .map(|class| class.to_lowercase())
+            .any(|class| needles.iter().any(|needle| class.contains(needle)))
     }
 
     /// Check if device supports USB 3.0 or higher
     pub fn is_high_speed(&self) -> bool {
+        if let Some(speed) = self.speed {
+            return speed >= USBSpeed::Super;
+        }
         self.usb_version.contains("3.")
             || self.usb_version.contains("3")
                 && !self.usb_version.contains("2.")
                 && !self.usb_version.contains("1.")
     }
+
+    /// Fill vendor/product names from the ID database where the device gave none
+    fn resolve_names(&mut self) {
+        let ids = crate::ids::usb_ids();
+        let (Some(vendor), Some(product)) = (
+            crate::ids::hex(&self.vendor_id),
+            crate::ids::hex(&self.product_id),
+        ) else {
+            return;
+        };
+        if self.vendor_name.is_empty() {
+            self.vendor_name = ids.vendor(vendor).unwrap_or_default().to_string();
+        }
+        if self.product_name.is_empty() {
+            self.product_name = ids.device(vendor, product).unwrap_or_default().to_string();
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn query_linux() -> Result<Vec<Self>> {
+        use std::fs;
+        use std::path::Path;
+
+        let root = Path::new("/sys/bus/usb/devices");
+        let Ok(entries) = fs::read_dir(root) else {
+            return Ok(Vec::new());
+        };
+
+        let mut devices = Vec::new();
+        for entry in entries.flatten() {
+            let id = entry.file_name().to_string_lossy().into_owned();
+            // "1-2:1.0" entries are interfaces, not devices
+            if id.contains(':') {
+                continue;
+            }
+            let path = entry.path();
+            let read = |attr: &str| {
+                fs::read_to_string(path.join(attr))
+                    .ok()
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+            let code = |attr: &str| read(attr).and_then(|v| u8::from_str_radix(&v, 16).ok()).unwrap_or(0);
+
+            let (class_code, subclass_code, protocol_code) =
+                (code("bDeviceClass"), code("bDeviceSubClass"), code("bDeviceProtocol"));
+
+            // Interface classes of a composite device live in "<id>:<config>.<n>"
+            let mut interfaces: Vec<(u8, u8, u8)> = fs::read_dir(&path)
+                .map(|dir| {
+                    dir.flatten()
+                        .filter(|iface| iface.file_name().to_string_lossy().starts_with(&format!("{id}:")))
+                        .map(|iface| {
+                            let code = |attr: &str| {
+                                fs::read_to_string(iface.path().join(attr))
+                                    .ok()
+                                    .and_then(|v| u8::from_str_radix(v.trim(), 16).ok())
+                                    .unwrap_or(0)
+                            };
+                            (code("bInterfaceClass"), code("bInterfaceSubClass"), code("bInterfaceProtocol"))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            interfaces.sort_unstable();
+            interfaces.dedup();
+
+            let (parent_id, port) = parent_and_port(&id);
+
+            let mut device = Self {
+                vendor_id: read("idVendor").unwrap_or_default(),
+                product_id: read("idProduct").unwrap_or_default(),
+                vendor_name: read("manufacturer").unwrap_or_default(),
+                product_name: read("product").unwrap_or_default(),
+                device_class: String::new(),
+                usb_version: read("version").unwrap_or_default(),
+                serial_number: read("serial"),
+                bus_number: read("busnum").and_then(|v| v.parse().ok()).unwrap_or(0),
+                device_address: read("devnum").and_then(|v| v.parse().ok()).unwrap_or(0),
+                port_path: read("devpath"),
+                driver: fs::read_link(path.join("driver"))
+                    .ok()
+                    .and_then(|link| link.file_name().map(|n| n.to_string_lossy().into_owned())),
+                connected: true,
+                id,
+                parent_id,
+                port,
+                speed: read("speed").and_then(|v| v.parse().ok()).and_then(USBSpeed::from_mbps),
+                class_code,
+                subclass_code,
+                protocol_code,
+                subclass_name: None,
+                interface_classes: Vec::new(),
+            };
+            device.apply_class_codes(&interfaces);
+            device.resolve_names();
+            devices.push(device);
+        }
+
+        devices.sort_by(|a, b| (a.bus_number, &a.id).cmp(&(b.bus_number, &b.id)));
+        Ok(devices)
+    }
+
+    /// Decode the device class, falling back to the interfaces for class 0 devices
+    fn apply_class_codes(&mut self, interfaces: &[(u8, u8, u8)]) {
+        self.interface_classes = interfaces
+            .iter()
+            .map(|&(class, _, _)| class_name(class).to_string())
+            .collect();
+        self.interface_classes.dedup();
+
+        let (class, subclass, protocol) = match (self.class_code, interfaces.first()) {
+            // Class defined per interface: describe the device by its first interface
+            (0x00, Some(&first)) => first,
+            _ => (self.class_code, self.subclass_code, self.protocol_code),
+        };
+        self.device_class = class_name(class).to_string();
+        self.subclass_name = subclass_name(class, subclass, protocol).map(str::to_string);
+    }
+
+    #[cfg(target_os = "windows")]
+    fn query_windows() -> Result<Vec<Self>> {
+        use std::collections::HashMap;
+        use wmi::{Variant, WMIConnection};
+
+        let wmi_con = WMIConnection::new(crate::probe::com_library()?)?;
+        let entities: Vec<HashMap<String, Variant>> = wmi_con.raw_query(
+            "SELECT DeviceID, Name, Manufacturer, Service, CompatibleID FROM Win32_PnPEntity \
+             WHERE DeviceID LIKE 'USB\\\\%'",
+        )?;
+
+        let string = |entity: &HashMap<String, Variant>, key: &str| match entity.get(key) {
+            Some(Variant::String(value)) if !value.is_empty() => Some(value.clone()),
+            _ => None,
+        };
+
+        let mut devices = Vec::new();
+        for entity in &entities {
+            let Some(device_id) = string(entity, "DeviceID") else {
+                continue;
+            };
+            // "USB\VID_046D&PID_C52B\5&2A3B..."; interface nodes carry "&MI_xx"
+            let mut parts = device_id.split('\\');
+            let (Some(_), Some(hardware), instance) = (parts.next(), parts.next(), parts.next()) else {
+                continue;
+            };
+            if hardware.contains("&MI_") {
+                continue;
+            }
+            let field = |prefix: &str| {
+                hardware
+                    .split('&')
+                    .find_map(|part| part.strip_prefix(prefix))
+                    .map(|id| id.to_lowercase())
+            };
+            let (Some(vendor_id), Some(product_id)) = (field("VID_"), field("PID_")) else {
+                continue;
+            };
+
+            // CompatibleID holds e.g. "USB\Class_03&SubClass_01&Prot_02"
+            let compatible = match entity.get("CompatibleID") {
+                Some(Variant::Array(ids)) => ids
+                    .iter()
+                    .filter_map(|id| match id {
+                        Variant::String(id) => Some(id.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+                _ => Vec::new(),
+            };
+            let code = |prefix: &str| {
+                compatible.iter().find_map(|id| {
+                    id.split(['\\', '&'])
+                        .find_map(|part| part.strip_prefix(prefix))
+                        .and_then(|v| u8::from_str_radix(v, 16).ok())
+                })
+            };
+
+            let mut device = Self {
+                vendor_id,
+                product_id,
+                vendor_name: string(entity, "Manufacturer")
+                    .filter(|m| !m.starts_with('('))
+                    .unwrap_or_default(),
+                product_name: string(entity, "Name").unwrap_or_default(),
+                device_class: String::new(),
+                usb_version: String::new(),
+                // Instance IDs containing '&' are generated by Windows, not device serials
+                serial_number: instance.filter(|i| !i.contains('&')).map(str::to_string),
+                bus_number: 0,
+                device_address: 0,
+                port_path: None,
+                driver: string(entity, "Service"),
+                connected: true,
+                id: device_id.clone(),
+                // Win32_PnPEntity has no parent link; the hub tree needs SetupAPI
+                parent_id: None,
+                port: None,
+                speed: None,
+                class_code: code("Class_").unwrap_or(0),
+                subclass_code: code("SubClass_").unwrap_or(0),
+                protocol_code: code("Prot_").unwrap_or(0),
+                subclass_name: None,
+                interface_classes: Vec::new(),
+            };
+            device.apply_class_codes(&[]);
+            device.resolve_names();
+            devices.push(device);
+        }
+
+        Ok(devices)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn query_macos() -> Result<Vec<Self>> {
+        use crate::probe::Command;
+
+        let output = Command::new("system_profiler")
+            .args(["SPUSBDataType", "-json"])
+            .output()?;
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+
+        let mut devices = Vec::new();
+        if let Some(buses) = json["SPUSBDataType"].as_array() {
+            for (bus_index, bus) in buses.iter().enumerate() {
+                let bus_id = format!("usb{}", bus_index + 1);
+                let mut root = Self::from_profiler(bus, bus_id.clone(), None, bus_index as u8 + 1);
+                root.class_code = 0x09;
+                root.apply_class_codes(&[]);
+                devices.push(root);
+                Self::walk_profiler(bus, &bus_id, bus_index as u8 + 1, &mut devices);
+            }
+        }
+        Ok(devices)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn walk_profiler(node: &serde_json::Value, parent_id: &str, bus: u8, devices: &mut Vec<Self>) {
+        let Some(items) = node["_items"].as_array() else {
+            return;
+        };
+        for (index, item) in items.iter().enumerate() {
+            // location_id "0x14120000 / 5": one nibble per tier holds the port
+            let port = item["location_id"]
+                .as_str()
+                .and_then(|location| location.split_whitespace().next())
+                .and_then(|hex| u32::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+                .and_then(|location| {
+                    let depth = parent_id.matches('.').count() + parent_id.matches('-').count();
+                    let nibble = (location >> (20 - 4 * depth.min(5) as u32)) & 0xf;
+                    (nibble != 0).then_some(nibble as u8)
+                })
+                .unwrap_or(index as u8 + 1);
+            let id = if parent_id.starts_with("usb") {
+                format!("{bus}-{port}")
+            } else {
+                format!("{parent_id}.{port}")
+            };
+            let mut device = Self::from_profiler(item, id.clone(), Some(parent_id.to_string()), bus);
+            device.port = Some(port);
+            devices.push(device);
+            Self::walk_profiler(item, &id, bus, devices);
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn from_profiler(item: &serde_json::Value, id: String, parent_id: Option<String>, bus: u8) -> Self {
+        let text = |key: &str| item[key].as_str().map(str::to_string);
+        // "0x05ac  (Apple Inc.)"
+        let split_id = |key: &str| {
+            let value = text(key).unwrap_or_default();
+            let (id, name) = value.split_once(' ').unwrap_or((&value, ""));
+            let name = name.trim().trim_start_matches('(').trim_end_matches(')').to_string();
+            (id.trim_start_matches("0x").to_lowercase(), name)
+        };
+        let (vendor_id, vendor_name) = split_id("vendor_id");
+        let (product_id, _) = split_id("product_id");
+        let speed = text("device_speed").or_else(|| text("host_controller_speed")).and_then(|speed| {
+            match speed.as_str() {
+                "low_speed" => Some(USBSpeed::Low),
+                "full_speed" => Some(USBSpeed::Full),
+                "high_speed" => Some(USBSpeed::High),
+                "super_speed" => Some(USBSpeed::Super),
+                "super_speed_plus" => Some(USBSpeed::SuperPlus),
+                _ => None,
+            }
+        });
+        let is_hub = text("_name").is_some_and(|name| name.to_lowercase().contains("hub"));
+
+        let mut device = Self {
+            vendor_id,
+            product_id,
+            vendor_name: text("manufacturer").unwrap_or(vendor_name),
+            product_name: text("_name").unwrap_or_default(),
+            device_class: String::new(),
+            usb_version: text("bcd_device").unwrap_or_default(),
+            serial_number: text("serial_num"),
+            bus_number: bus,
+            device_address: 0,
+            port_path: text("location_id"),
+            driver: None,
+            connected: true,
+            id,
+            parent_id,
+            port: None,
+            speed,
+            // system_profiler does not report class codes; hubs are recognisable by name
+            class_code: if is_hub { 0x09 } else { 0x00 },
+            subclass_code: 0,
+            protocol_code: 0,
+            subclass_name: None,
+            interface_classes: Vec::new(),
+        };
+        device.apply_class_codes(&[]);
+        device.resolve_names();
+        device
+    }
+}
+
+/// Hub tree of all USB devices
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct USBTopology {
+    /// Every device, root hubs included
+    pub devices: Vec<USBDevice>,
+}
+
+impl USBTopology {
+    /// Query the USB tree
+    pub fn query() -> Result<Self> {
+        Ok(Self::from_devices(USBDevice::query_all()?))
+    }
+
+    /// Build the tree from already queried devices
+    pub fn from_devices(devices: Vec<USBDevice>) -> Self {
+        Self { devices }
+    }
+
+    /// Get every device
+    pub fn devices(&self) -> &[USBDevice] {
+        &self.devices
+    }
+
+    /// Find a device by its topology identifier
+    pub fn get(&self, id: &str) -> Option<&USBDevice> {
+        self.devices.iter().find(|device| device.id == id)
+    }
+
+    /// Devices without a parent (root hubs, or every device where the platform has no hierarchy)
+    pub fn roots(&self) -> Vec<&USBDevice> {
+        self.devices
+            .iter()
+            .filter(|device| device.parent_id.as_ref().is_none_or(|parent| self.get(parent).is_none()))
+            .collect()
+    }
+
+    /// Devices plugged directly into `device`, ordered by port
+    pub fn children(&self, device: &USBDevice) -> Vec<&USBDevice> {
+        let mut children: Vec<&USBDevice> = self
+            .devices
+            .iter()
+            .filter(|child| child.parent_id.as_deref() == Some(device.id.as_str()))
+            .collect();
+        children.sort_by_key(|child| child.port);
+        children
+    }
+
+    /// Hub `device` is plugged into
+    pub fn parent(&self, device: &USBDevice) -> Option<&USBDevice> {
+        self.get(device.parent_id.as_deref()?)
+    }
+
+    /// Hubs between `device` and its root hub, nearest first
+    pub fn ancestors(&self, device: &USBDevice) -> Vec<&USBDevice> {
+        let mut ancestors = Vec::new();
+        let mut current = self.parent(device);
+        while let Some(hub) = current {
+            // Guard against malformed parent links
+            if ancestors.len() > self.devices.len() {
+                break;
+            }
+            ancestors.push(hub);
+            current = self.parent(hub);
+        }
+        ancestors
+    }
+
+    /// Number of hubs between `device` and its root hub
+    pub fn depth(&self, device: &USBDevice) -> usize {
+        self.ancestors(device).len()
+    }
+}
+
+/// Parent identifier and port from a sysfs device name
+///
+/// `usb1` is a root hub, `1-2` sits on port 2 of `usb1`, `1-2.4` on port 4 of `1-2`.
+#[cfg(target_os = "linux")]
+fn parent_and_port(id: &str) -> (Option<String>, Option<u8>) {
+    if id.starts_with("usb") {
+        return (None, None);
+    }
+    if let Some((parent, port)) = id.rsplit_once('.') {
+        return (Some(parent.to_string()), port.parse().ok());
+    }
+    match id.split_once('-') {
+        Some((bus, port)) => (Some(format!("usb{bus}")), port.parse().ok()),
+        None => (None, None),
+    }
+}
+
+/// Name of a USB base class code
+pub(crate) fn class_name(class: u8) -> &'static str {
+    match class {
+        0x00 => "Defined at Interface level",
+        0x01 => "Audio",
+        0x02 => "Communications",
+        0x03 => "Human Interface Device",
+        0x05 => "Physical",
+        0x06 => "Still Imaging",
+        0x07 => "Printer",
+        0x08 => "Mass Storage",
+        0x09 => "Hub",
+        0x0a => "CDC Data",
+        0x0b => "Smart Card",
+        0x0d => "Content Security",
+        0x0e => "Video",
+        0x0f => "Personal Healthcare",
+        0x10 => "Audio/Video",
+        0x11 => "Billboard",
+        0x12 => "USB Type-C Bridge",
+        0x3c => "I3C",
+        0xdc => "Diagnostic",
+        0xe0 => "Wireless Controller",
+        0xef => "Miscellaneous",
+        0xfe => "Application Specific",
+        0xff => "Vendor Specific",
+        _ => "Unknown",
+    }
+}
+
+/// Name of a USB subclass / protocol combination within `class`
+pub(crate) fn subclass_name(class: u8, subclass: u8, protocol: u8) -> Option<&'static str> {
+    let name = match (class, subclass, protocol) {
+        (0x01, 0x01, _) => "Audio Control",
+        (0x01, 0x02, _) => "Audio Streaming",
+        (0x01, 0x03, _) => "MIDI Streaming",
+        (0x02, 0x02, _) => "Abstract Control Model (Serial)",
+        (0x02, 0x06, _) => "Ethernet Networking",
+        (0x02, 0x0d, _) => "Network Control Model",
+        (0x03, 0x01, 0x01) => "Boot Keyboard",
+        (0x03, 0x01, 0x02) => "Boot Mouse",
+        (0x03, 0x01, _) => "Boot Interface",
+        (0x06, 0x01, 0x01) => "Picture Transfer Protocol",
+        (0x08, 0x01, _) => "RBC",
+        (0x08, 0x02, _) => "MMC-5 (ATAPI)",
+        (0x08, 0x04, _) => "Floppy (UFI)",
+        (0x08, 0x06, 0x62) => "SCSI (USB Attached SCSI)",
+        (0x08, 0x06, _) => "SCSI (Bulk-Only)",
+        (0x09, 0x00, 0x00) => "Full Speed Hub",
+        (0x09, 0x00, 0x01) => "Single TT",
+        (0x09, 0x00, 0x02) => "Multiple TT",
+        (0x09, 0x00, 0x03) => "SuperSpeed Hub",
+        (0x0e, 0x01, _) => "Video Control",
+        (0x0e, 0x02, _) => "Video Streaming",
+        (0x0e, 0x03, _) => "Video Interface Collection",
+        (0xe0, 0x01, 0x01) => "Bluetooth",
+        (0xef, 0x02, 0x01) => "Interface Association",
+        (0xfe, 0x01, _) => "Device Firmware Upgrade",
+        (0xfe, 0x02, _) => "IrDA Bridge",
+        (0xfe, 0x03, _) => "Test and Measurement",
+        _ => return None,
+    };
+    Some(name)
 }
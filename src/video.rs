@@ -0,0 +1,626 @@
+//! Hardware video encode/decode engines
+//!
+//! GPUs carry fixed-function video engines (NVIDIA NVENC/NVDEC, Intel Quick
+//! Sync, AMD VCN, Apple VideoToolbox) whose codec support depends on the
+//! engine generation rather than on drivers. The generation is derived from
+//! the CUDA compute capability where available and from the model name
+//! otherwise.
+//!
+//! Where the platform exposes it, the codec matrix is read from the driver
+//! instead: NVML on NVIDIA, VA-API (`vainfo`) on Linux, Media Foundation on
+//! Windows and VideoToolbox on macOS.
+
+use crate::{GPUInfo, GPUType, GPUVendor};
+use serde::{Deserialize, Serialize};
+
+/// Video codec handled by a hardware engine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VideoCodec {
+    H264,
+    HEVC,
+    VP9,
+    AV1,
+    ProRes,
+}
+
+impl std::fmt::Display for VideoCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VideoCodec::H264 => write!(f, "H.264"),
+            VideoCodec::HEVC => write!(f, "HEVC"),
+            VideoCodec::VP9 => write!(f, "VP9"),
+            VideoCodec::AV1 => write!(f, "AV1"),
+            VideoCodec::ProRes => write!(f, "ProRes"),
+        }
+    }
+}
+
+/// Fixed-function video engine of a GPU
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VideoEngine {
+    /// Engine family (NVENC, Quick Sync, VCN, VideoToolbox)
+    pub name: String,
+    /// GPU the engine belongs to
+    pub gpu: String,
+    /// Engine generation, if known (`8th gen (Ada)`, `VCN 4.0`)
+    pub generation: Option<String>,
+    /// Codecs it can encode
+    pub encode: Vec<VideoCodec>,
+    /// Codecs it can decode
+    pub decode: Vec<VideoCodec>,
+}
+
+/// Engine family, generation, encode and decode codecs
+type EngineTable = (&'static str, Option<&'static str>, &'static [VideoCodec], &'static [VideoCodec]);
+
+impl VideoEngine {
+    /// Video engine of a GPU, or `None` for GPUs without one (A100, H100, virtual GPUs)
+    ///
+    /// Codec lists come from the GPU's detected [`VideoCapabilities`] when
+    /// present and from the generation tables otherwise.
+    pub fn for_gpu(gpu: &GPUInfo) -> Option<Self> {
+        if gpu.gpu_type == GPUType::Virtual {
+            return None;
+        }
+        let (name, generation, mut encode, mut decode) = match Self::engine_table(gpu) {
+            Some((family, generation, encode, decode)) => (
+                family.to_string(),
+                generation.map(str::to_string),
+                encode.to_vec(),
+                decode.to_vec(),
+            ),
+            None => (gpu.video.as_ref()?.source.clone(), None, Vec::new(), Vec::new()),
+        };
+        if let Some(video) = &gpu.video {
+            encode = video.encode.iter().map(|support| support.codec).collect();
+            decode = video.decode.iter().map(|support| support.codec).collect();
+        }
+        if encode.is_empty() && decode.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            name,
+            gpu: gpu.model_name().to_string(),
+            generation,
+            encode,
+            decode,
+        })
+    }
+
+    fn engine_table(gpu: &GPUInfo) -> Option<EngineTable> {
+        use VideoCodec::*;
+
+        let name = gpu.model_name().to_lowercase();
+        let table: EngineTable = match gpu.vendor {
+            GPUVendor::NVIDIA => {
+                let capability = gpu.cuda_capability().and_then(|c| c.parse::<f64>().ok());
+                // Compute-only datacenter parts have NVDEC but no NVENC
+                if matches!(capability, Some(c) if c == 8.0 || c == 9.0)
+                    || ["a100", "h100", "h200", "a30"].iter().any(|part| name.contains(part))
+                {
+                    ("NVDEC", None, &[], &[H264, HEVC, VP9, AV1])
+                } else if capability.is_some_and(|c| c >= 10.0) || name.contains("rtx 50") {
+                    ("NVENC", Some("9th gen (Blackwell)"), &[H264, HEVC, AV1], &[H264, HEVC, VP9, AV1])
+                } else if capability.is_some_and(|c| c >= 8.9)
+                    || name.contains("rtx 40")
+                    || name.contains(" ada")
+                    || name.contains("l40")
+                    || name.contains(" l4")
+                {
+                    ("NVENC", Some("8th gen (Ada)"), &[H264, HEVC, AV1], &[H264, HEVC, VP9, AV1])
+                } else if capability.is_some_and(|c| c >= 8.6) || name.contains("rtx 30") || name.contains("rtx a") {
+                    ("NVENC", Some("7th gen (Ampere)"), &[H264, HEVC], &[H264, HEVC, VP9, AV1])
+                } else if capability.is_some_and(|c| c >= 7.5)
+                    || name.contains("rtx 20")
+                    || name.contains("gtx 16")
+                    || name.contains("quadro rtx")
+                {
+                    ("NVENC", Some("7th gen (Turing)"), &[H264, HEVC], &[H264, HEVC, VP9])
+                } else if capability.is_some_and(|c| c >= 6.0) || name.contains("gtx 10") {
+                    ("NVENC", Some("6th gen (Pascal)"), &[H264, HEVC], &[H264, HEVC, VP9])
+                } else {
+                    ("NVENC", None, &[H264], &[H264])
+                }
+            }
+            GPUVendor::AMD => {
+                if name.contains("rx 9") {
+                    ("VCN", Some("VCN 5.0"), &[H264, HEVC, AV1], &[H264, HEVC, VP9, AV1])
+                } else if name.contains("rx 7") || name.contains("radeon 7") || name.contains("radeon 8") {
+                    ("VCN", Some("VCN 4.0"), &[H264, HEVC, AV1], &[H264, HEVC, VP9, AV1])
+                } else if name.contains("rx 6") || name.contains("radeon 6") {
+                    ("VCN", Some("VCN 3.0"), &[H264, HEVC], &[H264, HEVC, VP9, AV1])
+                } else if name.contains("rx 5") {
+                    ("VCN", Some("VCN 2.0"), &[H264, HEVC], &[H264, HEVC, VP9])
+                } else if name.contains("instinct") {
+                    ("VCN", None, &[], &[H264, HEVC, VP9, AV1])
+                } else {
+                    ("VCN", None, &[H264, HEVC], &[H264, HEVC])
+                }
+            }
+            GPUVendor::Intel => {
+                if name.contains("arc") {
+                    ("Quick Sync", Some("Xe"), &[H264, HEVC, AV1], &[H264, HEVC, VP9, AV1])
+                } else if name.contains("iris xe") || name.contains("uhd graphics 7") {
+                    ("Quick Sync", Some("Gen12"), &[H264, HEVC], &[H264, HEVC, VP9, AV1])
+                } else {
+                    ("Quick Sync", None, &[H264, HEVC], &[H264, HEVC, VP9])
+                }
+            }
+            GPUVendor::Apple => {
+                // Every Apple silicon chip except the base M1 has a ProRes engine; M3 and newer decode AV1
+                let base_m1 = name.contains("m1")
+                    && !["pro", "max", "ultra"].iter().any(|tier| name.contains(tier));
+                let av1 = ["m3", "m4", "m5"].iter().any(|chip| name.contains(chip));
+                let (encode, decode): (&[VideoCodec], &[VideoCodec]) = match (base_m1, av1) {
+                    (true, _) => (&[H264, HEVC], &[H264, HEVC, VP9]),
+                    (false, false) => (&[H264, HEVC, ProRes], &[H264, HEVC, VP9, ProRes]),
+                    (false, true) => (&[H264, HEVC, ProRes], &[H264, HEVC, VP9, AV1, ProRes]),
+                };
+                ("VideoToolbox", None, encode, decode)
+            }
+            _ => return None,
+        };
+        Some(table)
+    }
+
+    /// Check if the engine encodes a codec
+    pub fn can_encode(&self, codec: VideoCodec) -> bool {
+        self.encode.contains(&codec)
+    }
+
+    /// Check if the engine decodes a codec
+    pub fn can_decode(&self, codec: VideoCodec) -> bool {
+        self.decode.contains(&codec)
+    }
+}
+
+/// Hardware support for one codec in one direction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodecSupport {
+    /// Codec
+    pub codec: VideoCodec,
+    /// Profiles reported by the driver (`Main`, `Main10`, `High`, `Profile0`); empty if not reported
+    #[serde(default)]
+    pub profiles: Vec<String>,
+    /// Largest frame width in pixels, if known
+    pub max_width: Option<u32>,
+    /// Largest frame height in pixels, if known
+    pub max_height: Option<u32>,
+}
+
+impl CodecSupport {
+    fn new(codec: VideoCodec) -> Self {
+        Self {
+            codec,
+            profiles: Vec::new(),
+            max_width: None,
+            max_height: None,
+        }
+    }
+
+    /// Get the largest frame size as (width, height), if known
+    pub fn max_resolution(&self) -> Option<(u32, u32)> {
+        Some((self.max_width?, self.max_height?))
+    }
+}
+
+/// Encode/decode codec matrix reported by the platform video API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VideoCapabilities {
+    /// API the matrix was read from (NVML, VA-API, Media Foundation, VideoToolbox)
+    pub source: String,
+    /// Codecs the hardware encodes
+    pub encode: Vec<CodecSupport>,
+    /// Codecs the hardware decodes
+    pub decode: Vec<CodecSupport>,
+    /// Concurrent encode sessions allowed by the driver; `None` if uncapped or unknown
+    #[serde(default)]
+    pub max_encode_sessions: Option<u32>,
+}
+
+impl VideoCapabilities {
+    /// Check if the hardware encodes a codec
+    pub fn can_encode(&self, codec: VideoCodec) -> bool {
+        self.encode_support(codec).is_some()
+    }
+
+    /// Check if the hardware decodes a codec
+    pub fn can_decode(&self, codec: VideoCodec) -> bool {
+        self.decode_support(codec).is_some()
+    }
+
+    /// Get encode details for a codec
+    pub fn encode_support(&self, codec: VideoCodec) -> Option<&CodecSupport> {
+        self.encode.iter().find(|support| support.codec == codec)
+    }
+
+    /// Get decode details for a codec
+    pub fn decode_support(&self, codec: VideoCodec) -> Option<&CodecSupport> {
+        self.decode.iter().find(|support| support.codec == codec)
+    }
+
+    fn support_mut(list: &mut Vec<CodecSupport>, codec: VideoCodec) -> &mut CodecSupport {
+        match list.iter().position(|support| support.codec == codec) {
+            Some(index) => &mut list[index],
+            None => {
+                list.push(CodecSupport::new(codec));
+                list.last_mut().expect("just pushed")
+            }
+        }
+    }
+}
+
+/// Codec matrix of an NVIDIA GPU from its NVENC/NVDEC generation, confirmed through NVML
+#[cfg(feature = "nvidia")]
+pub(crate) fn nvml_capabilities(gpu: &GPUInfo, device: &nvml_wrapper::Device) -> Option<VideoCapabilities> {
+    use nvml_wrapper::enum_wrappers::device::EncoderType;
+    use nvml_wrapper::error::NvmlError;
+
+    let (_, _, encode, decode) = VideoEngine::engine_table(gpu)?;
+    // NVENC/NVDEC handle 4096x4096 H.264 and 8192x8192 for the newer codecs
+    let sized = |codec: VideoCodec| {
+        let edge = if codec == VideoCodec::H264 { 4096 } else { 8192 };
+        CodecSupport {
+            max_width: Some(edge),
+            max_height: Some(edge),
+            ..CodecSupport::new(codec)
+        }
+    };
+    let encode = encode
+        .iter()
+        .copied()
+        .filter(|codec| {
+            let query = match codec {
+                VideoCodec::H264 => EncoderType::H264,
+                VideoCodec::HEVC => EncoderType::HEVC,
+                _ => return true,
+            };
+            !matches!(device.encoder_capacity(query), Err(NvmlError::NotSupported))
+        })
+        .map(sized)
+        .collect();
+    // GeForce drivers cap concurrent NVENC sessions; professional cards are uncapped
+    let name = gpu.model_name().to_lowercase();
+    let max_encode_sessions = (name.contains("geforce") || name.contains("titan")).then_some(8);
+
+    Some(VideoCapabilities {
+        source: "NVML".to_string(),
+        encode,
+        decode: decode.iter().copied().map(sized).collect(),
+        max_encode_sessions,
+    })
+}
+
+/// Fill in video capabilities from the platform video API for GPUs without them
+pub(crate) fn apply_probes(gpus: &mut [GPUInfo]) {
+    #[cfg(target_os = "linux")]
+    for gpu in gpus.iter_mut().filter(|gpu| gpu.video.is_none()) {
+        gpu.video = vaapi::query(gpu);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let by_vendor = media_foundation::query();
+        for gpu in gpus.iter_mut().filter(|gpu| gpu.video.is_none()) {
+            let vendor_id = match gpu.vendor {
+                GPUVendor::NVIDIA => 0x10DE,
+                GPUVendor::AMD => 0x1002,
+                GPUVendor::Intel => 0x8086,
+                GPUVendor::Qualcomm => 0x5143,
+                _ => continue,
+            };
+            gpu.video = by_vendor
+                .iter()
+                .find(|(id, _)| *id == vendor_id)
+                .map(|(_, caps)| caps.clone());
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    if let Some(caps) = videotoolbox::query() {
+        // VideoToolbox does not say which GPU it runs on; it is the Apple or integrated one
+        let target = gpus
+            .iter()
+            .position(|gpu| gpu.vendor == GPUVendor::Apple)
+            .or_else(|| gpus.iter().position(|gpu| gpu.gpu_type == GPUType::Integrated))
+            .or_else(|| (!gpus.is_empty()).then_some(0));
+        if let Some(gpu) = target.map(|index| &mut gpus[index]) {
+            if gpu.video.is_none() {
+                gpu.video = Some(caps);
+            }
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    let _ = gpus;
+}
+
+#[cfg(target_os = "linux")]
+mod vaapi {
+    use super::{VideoCapabilities, VideoCodec};
+    use crate::GPUInfo;
+
+    /// Query VA-API through `vainfo` on the GPU's DRM render node
+    pub(super) fn query(gpu: &GPUInfo) -> Option<VideoCapabilities> {
+        let bus_id = gpu.pci_bus_id.as_deref()?.to_lowercase();
+        // NVML reports an 8-digit PCI domain, sysfs uses 4
+        let bus_id = match bus_id.split_once(':') {
+            Some((domain, rest)) if domain.len() > 4 => format!("{}:{rest}", &domain[domain.len() - 4..]),
+            _ => bus_id,
+        };
+        let render_node = std::fs::read_dir(format!("/sys/bus/pci/devices/{bus_id}/drm"))
+            .ok()?
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .find(|name| name.starts_with("renderD"))?;
+        let device = format!("/dev/dri/{render_node}");
+
+        // `-a` adds per-profile attributes (max picture size) on libva-utils 2.18+
+        let run = |extra: &[&str]| {
+            crate::probe::Command::new("vainfo")
+                .args(["--display", "drm", "--device", device.as_str()])
+                .args(extra)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        };
+        let output = run(&["-a"]).or_else(|| run(&[]))?;
+        let caps = parse_vainfo(&output);
+        (!caps.encode.is_empty() || !caps.decode.is_empty()).then_some(caps)
+    }
+
+    /// Parse `vainfo` output, either the `Profile : Entrypoint` list or the `-a` attribute dump
+    fn parse_vainfo(output: &str) -> VideoCapabilities {
+        let mut caps = VideoCapabilities {
+            source: "VA-API".to_string(),
+            encode: Vec::new(),
+            decode: Vec::new(),
+            max_encode_sessions: None,
+        };
+        // (codec, encode) of the `-a` section being read
+        let mut current: Option<(VideoCodec, bool)> = None;
+
+        for line in output.lines().map(str::trim) {
+            let pair = line
+                .split_once('/')
+                .filter(|(profile, _)| profile.starts_with("VAProfile"))
+                .or_else(|| line.split_once(':').filter(|(profile, _)| profile.starts_with("VAProfile")));
+            if let Some((profile, entrypoint)) = pair {
+                current = None;
+                let Some((codec, profile)) = codec_profile(profile.trim()) else {
+                    continue;
+                };
+                let entrypoint = entrypoint.trim();
+                let encode = if entrypoint == "VAEntrypointVLD" {
+                    false
+                } else if entrypoint.starts_with("VAEntrypointEncSlice") {
+                    true
+                } else {
+                    continue;
+                };
+                let list = if encode { &mut caps.encode } else { &mut caps.decode };
+                let support = VideoCapabilities::support_mut(list, codec);
+                if !support.profiles.iter().any(|p| p == profile) {
+                    support.profiles.push(profile.to_string());
+                }
+                current = Some((codec, encode));
+            } else if let (Some((codec, encode)), Some((attribute, value))) = (current, line.split_once(':')) {
+                let Ok(value) = value.trim().parse::<u32>() else {
+                    continue;
+                };
+                let list = if encode { &mut caps.encode } else { &mut caps.decode };
+                let support = VideoCapabilities::support_mut(list, codec);
+                match attribute.trim() {
+                    "VAConfigAttribMaxPictureWidth" => support.max_width = support.max_width.max(Some(value)),
+                    "VAConfigAttribMaxPictureHeight" => support.max_height = support.max_height.max(Some(value)),
+                    _ => {}
+                }
+            }
+        }
+        caps
+    }
+
+    /// Split `VAProfileHEVCMain10` into (HEVC, "Main10")
+    fn codec_profile(profile: &str) -> Option<(VideoCodec, &str)> {
+        let name = profile.strip_prefix("VAProfile")?;
+        [
+            ("H264", VideoCodec::H264),
+            ("HEVC", VideoCodec::HEVC),
+            ("VP9", VideoCodec::VP9),
+            ("AV1", VideoCodec::AV1),
+        ]
+        .into_iter()
+        .find_map(|(prefix, codec)| name.strip_prefix(prefix).map(|rest| (codec, rest)))
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod media_foundation {
+    use super::{CodecSupport, VideoCapabilities, VideoCodec};
+    use ::windows::Win32::Media::MediaFoundation::{
+        IMFActivate, MFMediaType_Video, MFShutdown, MFStartup, MFTEnumEx, MFVideoFormat_AV1, MFVideoFormat_H264,
+        MFVideoFormat_HEVC, MFVideoFormat_VP90, MF_VERSION, MFSTARTUP_LITE, MFT_CATEGORY_VIDEO_DECODER,
+        MFT_CATEGORY_VIDEO_ENCODER, MFT_ENUM_FLAG, MFT_ENUM_FLAG_HARDWARE, MFT_ENUM_FLAG_SORTANDFILTER,
+        MFT_ENUM_HARDWARE_VENDOR_ID_Attribute, MFT_REGISTER_TYPE_INFO,
+    };
+    use ::windows::Win32::System::Com::CoTaskMemFree;
+    use ::windows::core::{GUID, PWSTR};
+
+    /// Hardware encoder and decoder MFTs, grouped by PCI vendor ID
+    pub(super) fn query() -> Vec<(u32, VideoCapabilities)> {
+        let Ok(_com) = crate::probe::com_library() else {
+            return Vec::new();
+        };
+        if unsafe { MFStartup(MF_VERSION, MFSTARTUP_LITE) }.is_err() {
+            return Vec::new();
+        }
+
+        let mut by_vendor: Vec<(u32, VideoCapabilities)> = Vec::new();
+        let codecs = [
+            (VideoCodec::H264, MFVideoFormat_H264),
+            (VideoCodec::HEVC, MFVideoFormat_HEVC),
+            (VideoCodec::VP9, MFVideoFormat_VP90),
+            (VideoCodec::AV1, MFVideoFormat_AV1),
+        ];
+        for (codec, subtype) in codecs {
+            let info = MFT_REGISTER_TYPE_INFO {
+                guidMajorType: MFMediaType_Video,
+                guidSubtype: subtype,
+            };
+            for (encode, category) in [(true, MFT_CATEGORY_VIDEO_ENCODER), (false, MFT_CATEGORY_VIDEO_DECODER)] {
+                // Encoders produce the codec, decoders consume it
+                let (input, output) = if encode { (None, Some(&info)) } else { (Some(&info), None) };
+                for vendor_id in hardware_vendors(category, input, output) {
+                    let index = match by_vendor.iter().position(|(id, _)| *id == vendor_id) {
+                        Some(index) => index,
+                        None => {
+                            by_vendor.push((
+                                vendor_id,
+                                VideoCapabilities {
+                                    source: "Media Foundation".to_string(),
+                                    encode: Vec::new(),
+                                    decode: Vec::new(),
+                                    max_encode_sessions: None,
+                                },
+                            ));
+                            by_vendor.len() - 1
+                        }
+                    };
+                    let caps = &mut by_vendor[index].1;
+                    let list = if encode { &mut caps.encode } else { &mut caps.decode };
+                    if !list.iter().any(|support| support.codec == codec) {
+                        list.push(CodecSupport::new(codec));
+                    }
+                }
+            }
+        }
+
+        let _ = unsafe { MFShutdown() };
+        by_vendor
+    }
+
+    /// PCI vendor IDs of the hardware MFTs matching a category and media type
+    fn hardware_vendors(
+        category: GUID,
+        input: Option<&MFT_REGISTER_TYPE_INFO>,
+        output: Option<&MFT_REGISTER_TYPE_INFO>,
+    ) -> Vec<u32> {
+        let mut activates: *mut Option<IMFActivate> = std::ptr::null_mut();
+        let mut count = 0u32;
+        let flags = MFT_ENUM_FLAG(MFT_ENUM_FLAG_HARDWARE.0 | MFT_ENUM_FLAG_SORTANDFILTER.0);
+        let result = unsafe {
+            MFTEnumEx(
+                category,
+                flags,
+                input.map(|info| info as *const _),
+                output.map(|info| info as *const _),
+                &mut activates,
+                &mut count,
+            )
+        };
+        if result.is_err() || activates.is_null() {
+            return Vec::new();
+        }
+
+        // SAFETY: MFTEnumEx returned `count` activation objects in a CoTaskMemAlloc'd array
+        let list = unsafe { std::slice::from_raw_parts_mut(activates, count as usize) };
+        let vendors = list
+            .iter_mut()
+            .filter_map(|activate| {
+                // Taking the object releases it when dropped
+                let activate = activate.take()?;
+                let mut value = PWSTR::null();
+                let mut length = 0;
+                unsafe { activate.GetAllocatedString(&MFT_ENUM_HARDWARE_VENDOR_ID_Attribute, &mut value, &mut length) }
+                    .ok()?;
+                let text = unsafe { value.to_string() }.ok();
+                unsafe { CoTaskMemFree(Some(value.0 as *const _)) };
+                // "VEN_10DE"
+                u32::from_str_radix(text?.trim().trim_start_matches("VEN_"), 16).ok()
+            })
+            .collect();
+        unsafe { CoTaskMemFree(Some(activates as *const _)) };
+        vendors
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod videotoolbox {
+    use super::{CodecSupport, VideoCapabilities, VideoCodec};
+    use core_foundation::array::{CFArray, CFArrayRef};
+    use core_foundation::base::{CFType, TCFType};
+    use core_foundation::boolean::CFBoolean;
+    use core_foundation::dictionary::CFDictionary;
+    use core_foundation::number::CFNumber;
+    use core_foundation::string::{CFString, CFStringRef};
+    use std::ffi::c_void;
+
+    #[link(name = "VideoToolbox", kind = "framework")]
+    unsafe extern "C" {
+        fn VTIsHardwareDecodeSupported(codec_type: u32) -> u8;
+        fn VTCopyVideoEncoderList(options: *const c_void, list_of_encoders_out: *mut CFArrayRef) -> i32;
+        static kVTVideoEncoderList_CodecType: CFStringRef;
+        static kVTVideoEncoderList_IsHardwareAccelerated: CFStringRef;
+    }
+
+    const fn fourcc(code: &[u8; 4]) -> u32 {
+        u32::from_be_bytes(*code)
+    }
+
+    const CODECS: [(VideoCodec, u32); 5] = [
+        (VideoCodec::H264, fourcc(b"avc1")),
+        (VideoCodec::HEVC, fourcc(b"hvc1")),
+        (VideoCodec::VP9, fourcc(b"vp09")),
+        (VideoCodec::AV1, fourcc(b"av01")),
+        (VideoCodec::ProRes, fourcc(b"apcn")),
+    ];
+
+    /// Hardware decoders and encoders registered with VideoToolbox
+    pub(super) fn query() -> Option<VideoCapabilities> {
+        let decode: Vec<CodecSupport> = CODECS
+            .iter()
+            .filter(|(_, code)| unsafe { VTIsHardwareDecodeSupported(*code) } != 0)
+            .map(|(codec, _)| CodecSupport::new(*codec))
+            .collect();
+
+        let mut encode: Vec<CodecSupport> = Vec::new();
+        let mut list: CFArrayRef = std::ptr::null();
+        if unsafe { VTCopyVideoEncoderList(std::ptr::null(), &mut list) } == 0 && !list.is_null() {
+            let encoders = unsafe { CFArray::<CFDictionary<CFString, CFType>>::wrap_under_create_rule(list) };
+            let (codec_key, hardware_key) = unsafe {
+                (
+                    CFString::wrap_under_get_rule(kVTVideoEncoderList_CodecType),
+                    CFString::wrap_under_get_rule(kVTVideoEncoderList_IsHardwareAccelerated),
+                )
+            };
+            for encoder in encoders.iter() {
+                let hardware = encoder
+                    .find(&hardware_key)
+                    .and_then(|value| value.downcast::<CFBoolean>())
+                    .is_some_and(bool::from);
+                let code = encoder
+                    .find(&codec_key)
+                    .and_then(|value| value.downcast::<CFNumber>())
+                    .and_then(|number| number.to_i64());
+                let codec = CODECS
+                    .iter()
+                    .find(|(_, fourcc)| code == Some(*fourcc as i64))
+                    .map(|(codec, _)| *codec);
+                if let (true, Some(codec)) = (hardware, codec) {
+                    if !encode.iter().any(|support| support.codec == codec) {
+                        encode.push(CodecSupport::new(codec));
+                    }
+                }
+            }
+        }
+
+        (!encode.is_empty() || !decode.is_empty()).then(|| VideoCapabilities {
+            source: "VideoToolbox".to_string(),
+            encode,
+            decode,
+            max_encode_sessions: None,
+        })
+    }
+}
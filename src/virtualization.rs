@@ -6,6 +6,7 @@
 use crate::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 
 /// Virtualization environment information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +29,197 @@ pub struct VirtualizationInfo {
     pub security_features: Vec<SecurityFeature>,
     /// Platform-specific virtualization details
     pub platform_specific: HashMap<String, String>,
+    /// Pod resources and allocated devices (when running in Kubernetes)
+    #[serde(default)]
+    pub kubernetes: Option<KubernetesInfo>,
+}
+
+/// Resources of the Kubernetes pod container this process runs in
+///
+/// Requests and limits come from Downward API environment variables
+/// (`CPU_REQUEST`, `CPU_LIMIT`, `MEMORY_REQUEST`, `MEMORY_LIMIT`) or the
+/// same names in lowercase as files of a Downward API volume mounted at
+/// `/etc/podinfo`. Without them, limits are taken from the cgroup and the CPU
+/// request from the cgroup CPU weight. Allocated devices are read from the
+/// variables device plugins set (`NVIDIA_VISIBLE_DEVICES`, ...).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KubernetesInfo {
+    /// Pod name (`POD_NAME`, falling back to the hostname)
+    pub pod_name: Option<String>,
+    /// Pod namespace (`POD_NAMESPACE` or the service account namespace)
+    pub namespace: Option<String>,
+    /// Node the pod is scheduled on (`NODE_NAME`)
+    pub node_name: Option<String>,
+    /// CPU request in millicores
+    pub cpu_request_millicores: Option<u64>,
+    /// CPU limit in millicores
+    pub cpu_limit_millicores: Option<u64>,
+    /// Memory request in bytes
+    pub memory_request_bytes: Option<u64>,
+    /// Memory limit in bytes
+    pub memory_limit_bytes: Option<u64>,
+    /// Device IDs allocated by device plugins, keyed by extended resource name (`nvidia.com/gpu`)
+    pub allocated_devices: HashMap<String, Vec<String>>,
+}
+
+/// Extended resources and the variable their device plugin sets to the allocated device IDs
+const DEVICE_PLUGIN_VARS: &[(&str, &str)] = &[
+    ("nvidia.com/gpu", "NVIDIA_VISIBLE_DEVICES"),
+    ("habana.ai/gaudi", "HABANA_VISIBLE_DEVICES"),
+    ("aws.amazon.com/neuron", "NEURON_RT_VISIBLE_CORES"),
+];
+
+/// Directory of a Downward API volume with resource files
+const PODINFO_DIR: &str = "/etc/podinfo";
+
+/// NVIDIA device plugin `volume-mounts` strategy: one empty file per allocated GPU
+const NVIDIA_DEVICE_LIST_DIR: &str = "/var/run/nvidia-container-devices";
+
+const SERVICE_ACCOUNT_NAMESPACE: &str = "/var/run/secrets/kubernetes.io/serviceaccount/namespace";
+
+impl KubernetesInfo {
+    /// Detect the pod's resources from the Downward API, cgroup and device plugins
+    pub fn detect() -> Self {
+        let limits = VirtualizationInfo::detect_resource_limits().unwrap_or_else(|_| ResourceLimits::unrestricted());
+        Self::detect_with_limits(&limits)
+    }
+
+    fn detect_with_limits(limits: &ResourceLimits) -> Self {
+        let mut info = Self::from_downward_api(|name| {
+            std::env::var(name)
+                .ok()
+                .or_else(|| std::fs::read_to_string(Path::new(PODINFO_DIR).join(name.to_lowercase())).ok())
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        });
+
+        if info.pod_name.is_none() {
+            info.pod_name = std::env::var("HOSTNAME").ok().filter(|name| !name.is_empty());
+        }
+        if info.namespace.is_none() {
+            info.namespace = std::fs::read_to_string(SERVICE_ACCOUNT_NAMESPACE)
+                .ok()
+                .map(|namespace| namespace.trim().to_string())
+                .filter(|namespace| !namespace.is_empty());
+        }
+        if !info.allocated_devices.contains_key("nvidia.com/gpu") {
+            if let Ok(entries) = std::fs::read_dir(NVIDIA_DEVICE_LIST_DIR) {
+                let mut ids: Vec<String> = entries
+                    .flatten()
+                    .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                    .collect();
+                ids.sort();
+                if !ids.is_empty() {
+                    info.allocated_devices.insert("nvidia.com/gpu".to_string(), ids);
+                }
+            }
+        }
+
+        if info.cpu_limit_millicores.is_none() {
+            info.cpu_limit_millicores = limits.cpu_limits.quota_percent.map(|percent| (percent as f64 * 10.0).round() as u64);
+        }
+        if info.memory_limit_bytes.is_none() {
+            info.memory_limit_bytes = limits.memory_limits.max_memory_bytes;
+        }
+        if info.memory_request_bytes.is_none() {
+            // Only set when the kubelet's MemoryQoS feature is enabled
+            info.memory_request_bytes = limits.memory_limits.reservation_bytes;
+        }
+        #[cfg(target_os = "linux")]
+        if info.cpu_request_millicores.is_none() {
+            info.cpu_request_millicores = cgroup::cpu_request_millicores();
+        }
+
+        info
+    }
+
+    /// Build from Downward API values, looked up by environment variable name
+    ///
+    /// `lookup` is called with `POD_NAME`, `POD_NAMESPACE`, `NODE_NAME`,
+    /// `CPU_REQUEST`, `CPU_LIMIT`, `MEMORY_REQUEST`, `MEMORY_LIMIT` and the
+    /// device plugin variables. Values are Kubernetes quantities (`500m`, `2`,
+    /// `512Mi`, `1G`).
+    pub fn from_downward_api(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let allocated_devices = DEVICE_PLUGIN_VARS
+            .iter()
+            .filter_map(|(resource, var)| {
+                let ids = parse_device_list(&lookup(var)?);
+                (!ids.is_empty()).then(|| (resource.to_string(), ids))
+            })
+            .collect();
+
+        Self {
+            pod_name: lookup("POD_NAME"),
+            namespace: lookup("POD_NAMESPACE"),
+            node_name: lookup("NODE_NAME"),
+            cpu_request_millicores: lookup("CPU_REQUEST").and_then(|v| parse_cpu_quantity(&v)),
+            cpu_limit_millicores: lookup("CPU_LIMIT").and_then(|v| parse_cpu_quantity(&v)),
+            memory_request_bytes: lookup("MEMORY_REQUEST").and_then(|v| parse_memory_quantity(&v)),
+            memory_limit_bytes: lookup("MEMORY_LIMIT").and_then(|v| parse_memory_quantity(&v)),
+            allocated_devices,
+        }
+    }
+
+    /// Get the IDs of the GPUs allocated to this container (`nvidia.com/gpu`)
+    pub fn allocated_gpu_ids(&self) -> &[String] {
+        self.allocated_devices
+            .get("nvidia.com/gpu")
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Get the CPU request in cores
+    pub fn cpu_request_cores(&self) -> Option<f64> {
+        self.cpu_request_millicores.map(|m| m as f64 / 1000.0)
+    }
+
+    /// Get the CPU limit in cores
+    pub fn cpu_limit_cores(&self) -> Option<f64> {
+        self.cpu_limit_millicores.map(|m| m as f64 / 1000.0)
+    }
+}
+
+/// Parse a CPU quantity (`250m`, `2`, `0.5`) into millicores
+fn parse_cpu_quantity(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let millicores = match value.strip_suffix('m') {
+        Some(millis) => millis.parse::<f64>().ok()?,
+        None => value.parse::<f64>().ok()? * 1000.0,
+    };
+    (millicores > 0.0).then(|| millicores.ceil() as u64)
+}
+
+/// Parse a memory quantity (`512Mi`, `1G`, `1073741824`) into bytes
+fn parse_memory_quantity(value: &str) -> Option<u64> {
+    const SUFFIXES: &[(&str, f64)] = &[
+        ("Ki", 1024.0),
+        ("Mi", 1024.0 * 1024.0),
+        ("Gi", 1024.0 * 1024.0 * 1024.0),
+        ("Ti", 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        ("Pi", 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        ("k", 1e3),
+        ("M", 1e6),
+        ("G", 1e9),
+        ("T", 1e12),
+        ("P", 1e15),
+    ];
+    let value = value.trim();
+    let (number, multiplier) = SUFFIXES
+        .iter()
+        .find_map(|(suffix, multiplier)| value.strip_suffix(suffix).map(|number| (number, *multiplier)))
+        .unwrap_or((value, 1.0));
+    let bytes = number.parse::<f64>().ok()? * multiplier;
+    (bytes > 0.0).then_some(bytes as u64)
+}
+
+/// Split a device plugin list (`GPU-1a2b,GPU-3c4d`), ignoring the `all`/`none`/`void` keywords
+fn parse_device_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty() && !matches!(*id, "all" | "none" | "void"))
+        .map(str::to_string)
+        .collect()
 }
 
 /// Type of virtualization environment
@@ -117,6 +309,75 @@ pub struct ResourceLimits {
     pub gpu_limits: GPULimits,
 }
 
+impl ResourceLimits {
+    /// Limits describing an environment with no restrictions applied
+    pub(crate) fn unrestricted() -> Self {
+        Self {
+            cpu_limits: CPULimits {
+                max_cores: None,
+                quota_percent: None,
+                shares: None,
+                affinity_mask: None,
+                disabled_features: Vec::new(),
+            },
+            memory_limits: MemoryLimits {
+                max_memory_bytes: None,
+                max_swap_bytes: None,
+                reservation_bytes: None,
+                oom_kill_disabled: false,
+                numa_policy: None,
+            },
+            io_limits: IOLimits {
+                max_read_iops: None,
+                max_write_iops: None,
+                max_read_bps: None,
+                max_write_bps: None,
+                device_weights: HashMap::new(),
+            },
+            network_limits: NetworkLimits {
+                max_bandwidth_bps: None,
+                network_namespace: false,
+                port_restrictions: Vec::new(),
+                network_policies: Vec::new(),
+            },
+            gpu_limits: GPULimits {
+                gpu_access: true,
+                accessible_devices: Vec::new(),
+                memory_limits: HashMap::new(),
+                capability_restrictions: Vec::new(),
+            },
+        }
+    }
+
+    /// Number of CPUs' worth of time the environment may use (e.g. 1.5 for a
+    /// `--cpus=1.5` container), combining the CPU quota and the cpuset
+    pub fn cpu_limit_cores(&self) -> Option<f64> {
+        let quota = self.cpu_limits.quota_percent.map(|percent| percent as f64 / 100.0);
+        let cores = self.cpu_limits.max_cores.map(f64::from);
+        match (quota, cores) {
+            (Some(quota), Some(cores)) => Some(quota.min(cores)),
+            (quota, cores) => quota.or(cores),
+        }
+    }
+
+    /// Memory limit in bytes (if one is imposed)
+    pub fn memory_limit_bytes(&self) -> Option<u64> {
+        self.memory_limits.max_memory_bytes
+    }
+
+    /// Limits a cgroup hierarchy imposes on one of its members
+    ///
+    /// `root` is the cgroup mount (`/sys/fs/cgroup`, or a copy of it) and
+    /// `membership` the contents of `/proc/<pid>/cgroup`. A root with a
+    /// `cgroup.controllers` file is read as cgroup v2, anything else as v1.
+    #[cfg(target_os = "linux")]
+    pub fn from_cgroup(root: &Path, membership: &str) -> Self {
+        let mut limits = Self::unrestricted();
+        cgroup::apply_from(&mut limits, root, membership);
+        limits
+    }
+}
+
 /// CPU resource limits
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CPULimits {
@@ -275,8 +536,8 @@ pub enum SecurityFeature {
 impl VirtualizationInfo {
     /// Detect current virtualization environment
     pub fn detect() -> Result<Self> {
-        let environment_type = Self::detect_environment_type()?;
         let hypervisor = Self::detect_hypervisor()?;
+        let environment_type = Self::detect_environment_type(hypervisor.is_some())?;
         let container_runtime = Self::detect_container_runtime()?;
         let resource_limits = Self::detect_resource_limits()?;
         let gpu_passthrough = Self::detect_gpu_passthrough()?;
@@ -284,6 +545,15 @@ impl VirtualizationInfo {
         let nested_virtualization = Self::detect_nested_virtualization()?;
         let security_features = Self::detect_security_features()?;
         let platform_specific = Self::gather_platform_specific_info()?;
+        let kubernetes = (environment_type == VirtualizationType::Kubernetes)
+            .then(|| KubernetesInfo::detect_with_limits(&resource_limits));
+
+        let mut resource_limits = resource_limits;
+        if let Some(kubernetes) = &kubernetes {
+            if !kubernetes.allocated_gpu_ids().is_empty() {
+                resource_limits.gpu_limits.accessible_devices = kubernetes.allocated_gpu_ids().to_vec();
+            }
+        }
 
         Ok(Self {
             environment_type,
@@ -295,9 +565,31 @@ impl VirtualizationInfo {
             nested_virtualization,
             security_features,
             platform_specific,
+            kubernetes,
         })
     }
 
+    /// Placeholder used when virtualization detection did not complete
+    pub(crate) fn unknown() -> Self {
+        Self {
+            environment_type: VirtualizationType::Unknown,
+            hypervisor: None,
+            container_runtime: None,
+            resource_limits: ResourceLimits::unrestricted(),
+            gpu_passthrough: GPUPassthroughInfo {
+                available: false,
+                passthrough_type: GPUPassthroughType::None,
+                devices: Vec::new(),
+                performance_overhead: 0.0,
+            },
+            performance_impact: Self::calculate_performance_impact(&VirtualizationType::Unknown),
+            nested_virtualization: false,
+            security_features: Vec::new(),
+            platform_specific: HashMap::new(),
+            kubernetes: None,
+        }
+    }
+
     /// Check if running in any virtualized environment
     pub fn is_virtualized(&self) -> bool {
         self.environment_type != VirtualizationType::Native
@@ -378,25 +670,25 @@ impl VirtualizationInfo {
         recommendations
     }
 
-    fn detect_environment_type() -> Result<VirtualizationType> {
+    fn detect_environment_type(under_hypervisor: bool) -> Result<VirtualizationType> {
         // Platform-specific detection logic would go here
         // For now, implement basic detection
 
-        // Check for common container indicators
-        if Self::check_docker_container()? {
-            return Ok(VirtualizationType::Docker);
-        }
-
+        // Check for common container indicators. Pods on Docker nodes also
+        // have /.dockerenv, so Kubernetes is checked first.
         if Self::check_kubernetes_pod()? {
             return Ok(VirtualizationType::Kubernetes);
         }
 
+        if Self::check_docker_container()? {
+            return Ok(VirtualizationType::Docker);
+        }
+
         if Self::check_wsl()? {
             return Ok(VirtualizationType::WSL);
         }
 
-        // Check for VM indicators
-        if Self::check_virtual_machine()? {
+        if under_hypervisor {
             return Ok(VirtualizationType::VirtualMachine);
         }
 
@@ -409,8 +701,8 @@ impl VirtualizationInfo {
     }
 
     fn check_kubernetes_pod() -> Result<bool> {
-        // Check for Kubernetes environment variables
-        Ok(std::env::var("KUBERNETES_SERVICE_HOST").is_ok())
+        // Check for Kubernetes environment variables and the service account mount
+        Ok(std::env::var("KUBERNETES_SERVICE_HOST").is_ok() || Path::new(SERVICE_ACCOUNT_NAMESPACE).exists())
     }
 
     fn check_wsl() -> Result<bool> {
@@ -424,14 +716,8 @@ impl VirtualizationInfo {
         Ok(false)
     }
 
-    fn check_virtual_machine() -> Result<bool> {
-        // Basic VM detection - would be enhanced with platform-specific checks
-        Ok(false)
-    }
-
     fn detect_hypervisor() -> Result<Option<String>> {
-        // Platform-specific hypervisor detection
-        Ok(None)
+        Ok(hypervisor::detect())
     }
 
     fn detect_container_runtime() -> Result<Option<ContainerRuntime>> {
@@ -440,42 +726,15 @@ impl VirtualizationInfo {
     }
 
     fn detect_resource_limits() -> Result<ResourceLimits> {
-        // Resource limits detection
-        Ok(ResourceLimits {
-            cpu_limits: CPULimits {
-                max_cores: None,
-                quota_percent: None,
-                shares: None,
-                affinity_mask: None,
-                disabled_features: Vec::new(),
-            },
-            memory_limits: MemoryLimits {
-                max_memory_bytes: None,
-                max_swap_bytes: None,
-                reservation_bytes: None,
-                oom_kill_disabled: false,
-                numa_policy: None,
-            },
-            io_limits: IOLimits {
-                max_read_iops: None,
-                max_write_iops: None,
-                max_read_bps: None,
-                max_write_bps: None,
-                device_weights: HashMap::new(),
-            },
-            network_limits: NetworkLimits {
-                max_bandwidth_bps: None,
-                network_namespace: false,
-                port_restrictions: Vec::new(),
-                network_policies: Vec::new(),
-            },
-            gpu_limits: GPULimits {
-                gpu_access: true,
-                accessible_devices: Vec::new(),
-                memory_limits: HashMap::new(),
-                capability_restrictions: Vec::new(),
-            },
-        })
+        #[allow(unused_mut)]
+        let mut limits = ResourceLimits::unrestricted();
+
+        #[cfg(target_os = "linux")]
+        {
+            cgroup::apply(&mut limits);
+        }
+
+        Ok(limits)
     }
 
     fn detect_gpu_passthrough() -> Result<GPUPassthroughInfo> {
@@ -534,3 +793,425 @@ impl std::fmt::Display for VirtualizationType {
         }
     }
 }
+
+/// Hypervisor identification from CPUID, firmware (DMI) strings and OS flags
+///
+/// CPUID names the hypervisor itself and, for Hyper-V and Xen, its version.
+/// DMI strings name the machine model the hypervisor presents, which gives the
+/// QEMU machine version and the cloud behind KVM (`KVM (Amazon EC2)`).
+mod hypervisor {
+    /// A hypervisor name and its version, if known
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub(super) struct Hypervisor {
+        pub name: String,
+        pub version: Option<String>,
+    }
+
+    impl Hypervisor {
+        fn new(name: &str, version: Option<String>) -> Self {
+            Self {
+                name: name.to_string(),
+                version,
+            }
+        }
+
+        fn describe(&self) -> String {
+            match &self.version {
+                Some(version) => format!("{} {version}", self.name),
+                None => self.name.clone(),
+            }
+        }
+    }
+
+    /// Hypervisor description, or `None` on bare metal
+    pub(super) fn detect() -> Option<String> {
+        combine(cpuid(), platform())
+    }
+
+    fn combine(cpuid: Option<Hypervisor>, platform: Option<Hypervisor>) -> Option<String> {
+        match (cpuid, platform) {
+            (Some(cpuid), Some(platform)) if cpuid.name != platform.name => {
+                Some(format!("{} ({})", cpuid.describe(), platform.describe()))
+            }
+            (Some(cpuid), _) => Some(cpuid.describe()),
+            (None, platform) => platform.map(|platform| platform.describe()),
+        }
+    }
+
+    /// Map a CPUID leaf 0x40000000 vendor signature to a hypervisor name
+    pub(super) fn cpuid_vendor_name(signature: &str) -> Option<&'static str> {
+        Some(match signature.trim_end_matches('\0') {
+            "KVMKVMKVM" => "KVM",
+            "Microsoft Hv" => "Microsoft Hyper-V",
+            "VMwareVMware" => "VMware",
+            "XenVMMXenVMM" => "Xen",
+            "TCGTCGTCGTCG" => "QEMU",
+            "VBoxVBoxVBox" => "VirtualBox",
+            " lrpepyh  vr" | "prl hyperv  " => "Parallels",
+            "bhyve bhyve " => "bhyve",
+            "ACRNACRNACRN" => "ACRN",
+            "QNXQVMBSQG" => "QNX Hypervisor",
+            "Linux KVM Hv" => "KVM",
+            "HAXMHAXMHAXM" => "Intel HAXM",
+            "UnisysSpar64" => "Unisys s-Par",
+            "SRESRESRESRE" => "Lockheed Martin LMHS",
+            "EVMMEVMMEVMM" => "Intel KGT",
+            _ => return None,
+        })
+    }
+
+    /// Read the hypervisor leaves when CPUID reports a hypervisor
+    #[cfg(target_arch = "x86_64")]
+    #[allow(unused_unsafe)] // CPUID intrinsics are safe on newer toolchains
+    fn cpuid() -> Option<Hypervisor> {
+        use std::arch::x86_64::__cpuid;
+
+        // Leaf 1 ECX bit 31 is reserved for hypervisors to set
+        if unsafe { __cpuid(1) }.ecx & (1 << 31) == 0 {
+            return None;
+        }
+
+        let signature = |base: u32| {
+            let leaf = unsafe { __cpuid(base) };
+            let bytes: Vec<u8> = [leaf.ebx, leaf.ecx, leaf.edx]
+                .iter()
+                .flat_map(|reg| reg.to_le_bytes())
+                .collect();
+            (leaf.eax, String::from_utf8_lossy(&bytes).into_owned())
+        };
+
+        let (max_leaf, vendor) = signature(0x4000_0000);
+        let name = cpuid_vendor_name(&vendor)?;
+        let version = match name {
+            "Microsoft Hyper-V" => {
+                // KVM and Xen can expose Hyper-V enlightenments first and their own leaves at +0x100
+                let (_, shifted) = signature(0x4000_0100);
+                if let Some(real) = cpuid_vendor_name(&shifted) {
+                    if real != name {
+                        return Some(Hypervisor::new(real, None));
+                    }
+                }
+                if max_leaf < 0x4000_0003 {
+                    None
+                } else {
+                    // The root partition of a Hyper-V host (and VBS-enabled Windows) also sees
+                    // this signature; only it may create partitions
+                    if unsafe { __cpuid(0x4000_0003) }.ebx & 1 != 0 {
+                        return None;
+                    }
+                    let version = unsafe { __cpuid(0x4000_0002) };
+                    Some(format!("{}.{} (build {})", version.ebx >> 16, version.ebx & 0xFFFF, version.eax))
+                }
+            }
+            "Xen" if max_leaf >= 0x4000_0001 => {
+                let version = unsafe { __cpuid(0x4000_0001) }.eax;
+                Some(format!("{}.{}", version >> 16, version & 0xFFFF))
+            }
+            _ => None,
+        };
+        Some(Hypervisor::new(name, version))
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    fn cpuid() -> Option<Hypervisor> {
+        None
+    }
+
+    /// Identify a hypervisor from the firmware's system vendor, product and version strings
+    pub(super) fn from_dmi(
+        vendor: Option<&str>,
+        product: Option<&str>,
+        version: Option<&str>,
+        bios_version: Option<&str>,
+    ) -> Option<Hypervisor> {
+        let vendor = vendor.unwrap_or_default();
+        let product = product.unwrap_or_default();
+        let lower = |value: &str| value.to_ascii_lowercase();
+
+        if vendor == "QEMU" || product.starts_with("Standard PC") {
+            // Machine types look like "pc-q35-8.2" or "pc-i440fx-7.1"
+            let version = version
+                .and_then(|version| version.rsplit('-').next())
+                .filter(|version| version.chars().next().is_some_and(|c| c.is_ascii_digit()))
+                .map(str::to_string);
+            return Some(Hypervisor::new("QEMU", version));
+        }
+        if vendor == "innotek GmbH" || product == "VirtualBox" {
+            return Some(Hypervisor::new("VirtualBox", None));
+        }
+        if vendor.starts_with("VMware") || product.starts_with("VMware") {
+            return Some(Hypervisor::new("VMware", None));
+        }
+        if vendor == "Microsoft Corporation" && product == "Virtual Machine" {
+            // "Hyper-V UEFI Release v4.1"
+            let version = bios_version
+                .and_then(|bios| bios.rsplit(" v").next().filter(|v| *v != bios))
+                .map(|v| format!("(generation {})", v.trim()));
+            return Some(Hypervisor::new("Microsoft Hyper-V", version));
+        }
+        if vendor == "Xen" || lower(product).contains("hvm domu") {
+            return Some(Hypervisor::new("Xen", version.map(str::to_string)));
+        }
+        if lower(vendor).starts_with("parallels") || product.starts_with("Parallels") {
+            return Some(Hypervisor::new("Parallels", None));
+        }
+        if vendor == "BHYVE" || product == "BHYVE" {
+            return Some(Hypervisor::new("bhyve", None));
+        }
+        // Bare-metal instances keep the cloud's vendor string
+        if vendor == "Amazon EC2" && !product.ends_with(".metal") {
+            return Some(Hypervisor::new("Amazon EC2", None));
+        }
+        if product == "Google Compute Engine" {
+            return Some(Hypervisor::new("Google Compute Engine", None));
+        }
+        if vendor == "OpenStack Foundation" || product == "OpenStack Nova" {
+            return Some(Hypervisor::new("OpenStack", None));
+        }
+        if product.starts_with("VirtualMac") || product.starts_with("Apple Virtualization") {
+            return Some(Hypervisor::new("Apple Virtualization", None));
+        }
+        None
+    }
+
+    #[cfg(target_os = "linux")]
+    fn platform() -> Option<Hypervisor> {
+        use std::fs;
+
+        let system = crate::SystemInfo::query().ok()?;
+        let dmi = from_dmi(
+            system.manufacturer.as_deref(),
+            system.product_name.as_deref(),
+            system.version.as_deref(),
+            system.firmware.version.as_deref(),
+        );
+        if dmi.is_some() {
+            return dmi;
+        }
+
+        // Xen PV guests have no DMI but the kernel exposes the hypervisor
+        let read = |name: &str| fs::read_to_string(format!("/sys/hypervisor/{name}")).ok();
+        if read("type")?.trim() != "xen" {
+            return None;
+        }
+        let version = read("version/major").zip(read("version/minor")).map(|(major, minor)| {
+            let extra = read("version/extra").unwrap_or_default();
+            format!("{}.{}{}", major.trim(), minor.trim(), extra.trim())
+        });
+        Some(Hypervisor::new("Xen", version))
+    }
+
+    #[cfg(target_os = "windows")]
+    fn platform() -> Option<Hypervisor> {
+        let system = crate::SystemInfo::query().ok()?;
+        from_dmi(
+            system.manufacturer.as_deref(),
+            system.product_name.as_deref(),
+            system.version.as_deref(),
+            system.firmware.version.as_deref(),
+        )
+    }
+
+    #[cfg(target_os = "macos")]
+    fn platform() -> Option<Hypervisor> {
+        use crate::probe::Command;
+
+        let output = Command::new("sysctl")
+            .args(["-n", "kern.hv_vmm_present", "hw.model"])
+            .output()
+            .ok()?;
+        let output = String::from_utf8_lossy(&output.stdout);
+        let mut lines = output.lines().map(str::trim);
+        let vmm_present = lines.next() == Some("1");
+        let model = lines.next().unwrap_or_default();
+
+        let dmi = from_dmi(None, Some(model), None, None);
+        if dmi.is_some() || !vmm_present {
+            return dmi;
+        }
+        // Guests of Virtualization.framework-based hypervisors report Apple's model names
+        Some(Hypervisor::new("Apple Virtualization", None))
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    fn platform() -> Option<Hypervisor> {
+        None
+    }
+}
+
+/// cgroup v1/v2 CPU and memory limits of the current process
+#[cfg(target_os = "linux")]
+mod cgroup {
+    use super::ResourceLimits;
+    use crate::topology::parse_cpu_list;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+    /// cgroup v1 reports "no limit" as i64::MAX rounded down to a page
+    const V1_UNLIMITED: u64 = 0x7FFF_FFFF_FFFF_F000;
+
+    /// CPU request of a Kubernetes container, recovered from its CPU weight
+    ///
+    /// The kubelet sets `cpu.shares = millicores * 1024 / 1000` (minimum 2) and
+    /// converts shares to a cgroup v2 weight as `1 + (shares - 2) * 9999 / 262142`.
+    pub(super) fn cpu_request_millicores() -> Option<u64> {
+        let membership = fs::read_to_string("/proc/self/cgroup").ok()?;
+        let root = Path::new(CGROUP_ROOT);
+        let shares = if root.join("cgroup.controllers").exists() {
+            let path = membership.lines().find_map(|line| line.strip_prefix("0::"))?;
+            let weight: u64 = read(&v2_dir(root, path), "cpu.weight")?.parse().ok()?;
+            2 + weight.checked_sub(1)? * 262_142 / 9_999
+        } else {
+            read(&v1_controller_dir(root, &membership, "cpu")?, "cpu.shares")?.parse().ok()?
+        };
+        // Two shares is the floor for containers without a request
+        (shares > 2).then(|| (shares * 1000).div_ceil(1024))
+    }
+
+    pub(super) fn apply(limits: &mut ResourceLimits) {
+        let Ok(membership) = fs::read_to_string("/proc/self/cgroup") else {
+            return;
+        };
+        apply_from(limits, Path::new(CGROUP_ROOT), &membership);
+    }
+
+    pub(super) fn apply_from(limits: &mut ResourceLimits, root: &Path, membership: &str) {
+        if root.join("cgroup.controllers").exists() {
+            if let Some(path) = membership.lines().find_map(|line| line.strip_prefix("0::")) {
+                apply_v2(limits, root, path);
+            }
+        } else {
+            apply_v1(limits, root, membership);
+        }
+    }
+
+    fn read(dir: &Path, name: &str) -> Option<String> {
+        fs::read_to_string(dir.join(name))
+            .ok()
+            .map(|value| value.trim().to_string())
+    }
+
+    fn min_option<T: PartialOrd>(current: Option<T>, new: Option<T>) -> Option<T> {
+        match (current, new) {
+            (Some(a), Some(b)) => Some(if b < a { b } else { a }),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Record a CPU quota as a percentage of one CPU and the cores it rounds up to
+    fn apply_quota(limits: &mut ResourceLimits, quota: f64, period: f64) {
+        if quota <= 0.0 || period <= 0.0 {
+            return;
+        }
+        let percent = (quota / period * 100.0) as f32;
+        let cpu = &mut limits.cpu_limits;
+        cpu.quota_percent = min_option(cpu.quota_percent, Some(percent));
+        cpu.max_cores = min_option(cpu.max_cores, Some((quota / period).ceil() as u32));
+    }
+
+    fn apply_cpuset(limits: &mut ResourceLimits, cpus: &str) {
+        let count = parse_cpu_list(cpus).len() as u32;
+        if count > 0 {
+            limits.cpu_limits.affinity_mask = Some(cpus.to_string());
+            limits.cpu_limits.max_cores = min_option(limits.cpu_limits.max_cores, Some(count));
+        }
+    }
+
+    /// Limits in cgroup v2 are inherited, so every ancestor up to the root is checked
+    fn apply_v2(limits: &mut ResourceLimits, root: &Path, path: &str) {
+        let dir = v2_dir(root, path);
+
+        if let Some(cpus) = read(&dir, "cpuset.cpus.effective") {
+            apply_cpuset(limits, &cpus);
+        }
+        if let Some(weight) = read(&dir, "cpu.weight").and_then(|w| w.parse().ok()) {
+            limits.cpu_limits.shares = Some(weight);
+        }
+        if let Some(low) = read(&dir, "memory.low").and_then(|v| v.parse::<u64>().ok()) {
+            limits.memory_limits.reservation_bytes = Some(low).filter(|&low| low > 0);
+        }
+
+        for dir in dir.ancestors().take_while(|d| d.starts_with(root)) {
+            if let Some(cpu_max) = read(dir, "cpu.max") {
+                let mut parts = cpu_max.split_whitespace();
+                if let (Some(Ok(quota)), Some(Ok(period))) = (
+                    parts.next().map(str::parse::<f64>),
+                    parts.next().map(str::parse::<f64>),
+                ) {
+                    apply_quota(limits, quota, period);
+                }
+            }
+
+            let memory = &mut limits.memory_limits;
+            memory.max_memory_bytes = min_option(
+                memory.max_memory_bytes,
+                read(dir, "memory.max").and_then(|v| v.parse().ok()),
+            );
+            memory.max_swap_bytes = min_option(
+                memory.max_swap_bytes,
+                read(dir, "memory.swap.max").and_then(|v| v.parse().ok()),
+            );
+        }
+    }
+
+    fn v2_dir(root: &Path, path: &str) -> PathBuf {
+        let dir = root.join(path.trim_start_matches('/'));
+        // Inside a cgroup namespace the container's own cgroup is mounted at the root
+        if dir.is_dir() { dir } else { root.to_path_buf() }
+    }
+
+    fn v1_controller_dir(root: &Path, membership: &str, controller: &str) -> Option<PathBuf> {
+        // Lines look like "4:cpu,cpuacct:/docker/<id>"
+        membership.lines().find_map(|line| {
+            let mut fields = line.splitn(3, ':');
+            let (_, controllers, path) = (fields.next()?, fields.next()?, fields.next()?);
+            if !controllers.split(',').any(|c| c == controller) {
+                return None;
+            }
+            let mount = root.join(controllers);
+            let mount = if mount.is_dir() { mount } else { root.join(controller) };
+            let nested = mount.join(path.trim_start_matches('/'));
+            Some(if nested.is_dir() { nested } else { mount })
+        })
+    }
+
+    fn apply_v1(limits: &mut ResourceLimits, root: &Path, membership: &str) {
+        let controller_dir = |controller: &str| v1_controller_dir(root, membership, controller);
+        let parse = |dir: &Path, name: &str| read(dir, name).and_then(|v| v.parse::<i64>().ok());
+        let limit = |value: Option<i64>| {
+            value
+                .and_then(|v| u64::try_from(v).ok())
+                .filter(|&v| v < V1_UNLIMITED)
+        };
+
+        if let Some(dir) = controller_dir("cpu") {
+            if let (Some(quota), Some(period)) =
+                (parse(&dir, "cpu.cfs_quota_us"), parse(&dir, "cpu.cfs_period_us"))
+            {
+                // A quota of -1 means unlimited
+                apply_quota(limits, quota as f64, period as f64);
+            }
+            limits.cpu_limits.shares = parse(&dir, "cpu.shares").and_then(|v| u32::try_from(v).ok());
+        }
+
+        if let Some(dir) = controller_dir("cpuset") {
+            if let Some(cpus) = read(&dir, "cpuset.cpus") {
+                apply_cpuset(limits, &cpus);
+            }
+        }
+
+        if let Some(dir) = controller_dir("memory") {
+            let memory = &mut limits.memory_limits;
+            memory.max_memory_bytes = limit(parse(&dir, "memory.limit_in_bytes"));
+            memory.reservation_bytes = limit(parse(&dir, "memory.soft_limit_in_bytes"));
+            // memsw is memory plus swap
+            memory.max_swap_bytes = limit(parse(&dir, "memory.memsw.limit_in_bytes"))
+                .zip(memory.max_memory_bytes)
+                .map(|(total, memory)| total.saturating_sub(memory));
+            memory.oom_kill_disabled = read(&dir, "memory.oom_control")
+                .is_some_and(|control| control.lines().any(|line| line == "oom_kill_disable 1"));
+        }
+    }
+}
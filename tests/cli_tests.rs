@@ -0,0 +1,64 @@
+//! Tests for the `hardware-query` binary (needs the `cli` feature)
+
+use hardware_query::{GPUInfo, SystemOverview};
+use std::process::{Command, Output};
+
+fn hardware_query(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_hardware-query"))
+        .args(args)
+        .output()
+        .expect("Failed to run hardware-query")
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "hardware-query failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn test_cli_gpu_json() {
+    let json = stdout(&hardware_query(&["gpu", "--json"]));
+    let gpus: Vec<GPUInfo> = serde_json::from_str(&json).expect("`gpu --json` should print a GPU list");
+    assert_eq!(gpus.len(), GPUInfo::query_all().unwrap().len());
+}
+
+#[test]
+fn test_cli_overview() {
+    let json = stdout(&hardware_query(&["overview", "--json"]));
+    let overview: SystemOverview = serde_json::from_str(&json).expect("`overview --json` should print an overview");
+    assert!(overview.cpu.cores > 0);
+    assert!(overview.effective_cores() <= overview.cpu.threads);
+
+    let text = stdout(&hardware_query(&["overview"]));
+    assert!(text.starts_with("CPU: "));
+    assert!(text.contains(&overview.cpu.name));
+}
+
+#[test]
+fn test_cli_report_formats() {
+    let markdown = stdout(&hardware_query(&["report", "--detail", "summary"]));
+    assert!(markdown.starts_with("# "));
+
+    let html = stdout(&hardware_query(&["report", "--html", "--redact"]));
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.trim_end().ends_with("</html>"));
+}
+
+#[test]
+fn test_cli_rejects_bad_arguments() {
+    let output = hardware_query(&["monitor", "--interval", "0s"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("interval must be positive"));
+
+    let output = hardware_query(&["monitor", "--interval", "2h"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown interval unit `h`"));
+
+    let output = hardware_query(&["nonexistent"]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}
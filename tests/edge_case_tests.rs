@@ -2,7 +2,7 @@
 
 use hardware_query::{
     HardwareQueryError,
-    CPUInfo, GPUInfo, HardwareInfo, MemoryInfo, StorageInfo,
+    BatteryInfo, CPUInfo, GPUInfo, HardwareInfo, MemoryInfo, StorageInfo,
 };
 
 #[test]
@@ -120,3 +120,109 @@ fn test_hardware_info_timestamp() {
     assert!(hw_info.timestamp <= now);
     assert!(hw_info.timestamp > now - 3600); // Within the last hour
 }
+
+#[test]
+fn test_has_feature_ignores_separators() {
+    let cpu_info = CPUInfo::query().expect("Failed to query CPU info");
+
+    for feature in cpu_info.features() {
+        let name = feature.to_string();
+        assert!(cpu_info.has_feature(&name));
+        assert!(cpu_info.has_feature(&name.to_lowercase().replace('.', "")));
+    }
+}
+
+#[test]
+fn test_storage_smart_health_consistency() {
+    let storage_devices = StorageInfo::query_all().expect("Failed to query storage info");
+
+    for storage in storage_devices {
+        if let Some(smart) = storage.smart_health() {
+            // SMART data is only attached when the backing disk was resolved
+            assert!(storage.device().is_some());
+
+            if smart.predicted_failure {
+                assert!(smart.is_degraded());
+            }
+            if let Some(remaining) = smart.wear_remaining_percent() {
+                assert!(remaining <= 100);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_cpu_topology_consistency() {
+    let cpu_info = CPUInfo::query().expect("Failed to query CPU info");
+    let topology = cpu_info.topology();
+
+    assert!(topology.sockets() >= 1);
+    assert!(!topology.numa_nodes().is_empty());
+
+    for processor in topology.processors() {
+        // Every logical CPU is its own SMT sibling
+        assert!(processor.smt_siblings.contains(&processor.id));
+        assert!(topology.cpus_sharing_cache(processor.id, 2).contains(&processor.id));
+    }
+
+    assert!(topology.one_cpu_per_core().len() <= topology.processors().len());
+}
+
+#[test]
+fn test_core_clusters_consistency() {
+    let cpu_info = CPUInfo::query().expect("Failed to query CPU info");
+    let clusters = cpu_info.core_clusters();
+    assert!(!clusters.is_empty());
+
+    // Clusters partition the logical CPUs
+    let mut cpus: Vec<u32> = clusters.iter().flat_map(|c| c.logical_cpus().to_vec()).collect();
+    let total = cpus.len();
+    cpus.sort_unstable();
+    cpus.dedup();
+    assert_eq!(cpus.len(), total);
+
+    for cluster in clusters {
+        assert!(cluster.physical_cores() as usize <= cluster.logical_cpus().len());
+    }
+    assert_eq!(
+        cpu_info.performance_cores() + cpu_info.efficiency_cores(),
+        clusters.iter().map(|c| c.physical_cores()).sum::<u32>()
+    );
+    if cpu_info.topology().is_heterogeneous() {
+        assert!(cpu_info.efficiency_cores() > 0);
+    }
+}
+
+#[test]
+fn test_battery_health_consistency() {
+    // Desktops and CI machines have no battery; only validate what is reported
+    if let Ok(battery) = BatteryInfo::query() {
+        assert!((0.0..=100.0).contains(&battery.percentage()));
+
+        if let Some(health) = battery.health_percent() {
+            assert!((0.0..=100.0).contains(&health));
+        }
+        if let (Some(design), Some(full)) =
+            (battery.design_capacity_wh(), battery.full_charge_capacity_wh())
+        {
+            assert!(design > 0.0 && full > 0.0);
+        }
+    }
+}
+
+#[test]
+fn test_query_degrades_instead_of_failing() {
+    let hw_info = HardwareInfo::query().expect("Query should not fail on component errors");
+
+    assert_eq!(hw_info.is_complete(), hw_info.errors().is_empty());
+    for error in hw_info.errors().iter().chain(hw_info.warnings()) {
+        assert!(!error.message.is_empty());
+    }
+
+    // Snapshots written before errors were recorded still deserialize
+    let mut value: serde_json::Value = serde_json::from_str(&hw_info.to_json().unwrap()).unwrap();
+    value.as_object_mut().unwrap().remove("errors");
+    value.as_object_mut().unwrap().remove("warnings");
+    let restored = HardwareInfo::from_json(&value.to_string()).unwrap();
+    assert!(restored.errors().is_empty());
+}
@@ -68,6 +68,18 @@ fn test_gpu_detection() {
         );
         assert!(gpu.memory_mb > 0, "GPU memory should be > 0");
     }
+
+    // The NVIDIA kernel driver is enough to identify its GPUs, with or without NVML
+    if std::fs::read_dir("/proc/driver/nvidia/gpus").is_ok_and(|mut entries| entries.next().is_some()) {
+        use hardware_query::GPUVendor;
+
+        let nvidia: Vec<_> = gpus.iter().filter(|gpu| gpu.vendor() == &GPUVendor::NVIDIA).collect();
+        assert!(!nvidia.is_empty());
+        for gpu in nvidia {
+            assert!(gpu.driver_version.is_some());
+            assert!(gpu.supports_cuda());
+        }
+    }
 }
 
 #[test]
@@ -119,3 +131,3777 @@ fn test_serialization() {
         "Deserialized object should match original"
     );
 }
+
+#[test]
+fn test_schema_version_round_trip() {
+    let hw_info = HardwareInfo::query().expect("Failed to query hardware info");
+    assert_eq!(hw_info.schema_version, hardware_query::SCHEMA_VERSION);
+
+    let json = hw_info.to_json().expect("Failed to serialize to JSON");
+    let deserialized = HardwareInfo::from_json(&json).expect("Failed to deserialize from JSON");
+    assert_eq!(deserialized.schema_version, hardware_query::SCHEMA_VERSION);
+
+    let pretty = hw_info.to_json_pretty().expect("Failed to serialize to pretty JSON");
+    assert_eq!(pretty, json);
+    let from_pretty = HardwareInfo::from_json(&pretty).expect("Failed to deserialize pretty JSON");
+    assert_eq!(from_pretty.timestamp, hw_info.timestamp);
+    assert_eq!(from_pretty.cpu.logical_cores(), hw_info.cpu.logical_cores());
+
+    let compact = hw_info.to_json_compact().expect("Failed to serialize to compact JSON");
+    assert!(!compact.contains('\n') && compact.len() < json.len());
+    assert_eq!(HardwareInfo::from_json(&compact).unwrap().schema_version, hardware_query::SCHEMA_VERSION);
+
+    // Snapshots from a newer, unknown schema must be rejected
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value["schema_version"] = serde_json::json!(hardware_query::SCHEMA_VERSION + 1);
+    assert!(HardwareInfo::from_json(&value.to_string()).is_err());
+}
+
+#[test]
+fn test_gpu_from_drm_device() {
+    use hardware_query::{GPUType, GPUVendor};
+
+    let dir = tempfile::tempdir().unwrap();
+    let write = |device: &std::path::Path, files: &[(&str, &str)]| {
+        std::fs::create_dir_all(device).unwrap();
+        for (name, contents) in files {
+            std::fs::write(device.join(name), format!("{contents}\n")).unwrap();
+        }
+    };
+
+    // amdgpu reports its VRAM size
+    let radeon = dir.path().join("card1/device");
+    write(&radeon, &[
+        ("vendor", "0x1002"),
+        ("device", "0x744C"),
+        ("subsystem_vendor", "0x1da2"),
+        ("subsystem_device", "0x471d"),
+        ("mem_info_vram_total", "25753026560"),
+        ("uevent", "DRIVER=amdgpu\nPCI_CLASS=30000\nPCI_SLOT_NAME=0000:03:00.0"),
+    ]);
+    let gpu = GPUInfo::from_drm_device(&radeon).unwrap();
+    assert_eq!(gpu.vendor(), &GPUVendor::AMD);
+    assert_eq!(gpu.memory_mb(), 24560);
+    assert!(gpu.model_name().contains("[1002:744c]"));
+    assert_eq!(gpu.pci_device_id.as_deref(), Some("1002:744c"));
+    assert_eq!(gpu.pci_subsystem_id.as_deref(), Some("1da2:471d"));
+    assert_eq!(gpu.pci_bus_id.as_deref(), Some("0000:03:00.0"));
+    assert!(gpu.supports_rocm());
+
+    // Intel without local memory is integrated
+    let iris = dir.path().join("card0/device");
+    write(&iris, &[
+        ("vendor", "0x8086"),
+        ("device", "0xa7a0"),
+        ("product_name", "Intel Iris Xe Graphics"),
+        ("uevent", "DRIVER=i915\nPCI_SLOT_NAME=0000:00:02.0"),
+    ]);
+    let gpu = GPUInfo::from_drm_device(&iris).unwrap();
+    assert_eq!(gpu.model_name(), "Intel Iris Xe Graphics");
+    assert_eq!(gpu.gpu_type(), &GPUType::Integrated);
+    assert!(gpu.has_unified_memory());
+
+    // Platform devices without PCI IDs are skipped
+    let simple = dir.path().join("card2/device");
+    write(&simple, &[("uevent", "DRIVER=simple-framebuffer")]);
+    assert!(GPUInfo::from_drm_device(&simple).is_none());
+}
+
+#[test]
+fn test_nvidia_procfs_parsing() {
+    use hardware_query::GPUVendor;
+
+    let information = "Model: \t\t NVIDIA GeForce RTX 4090
+IRQ:   \t\t 187
+GPU UUID: \t GPU-5f3b5c5e-9c1a-4d1e-8f6d-2b8c0e6f1a2b
+Video BIOS: \t 95.02.18.80.87
+Bus Type: \t PCIe
+DMA Size: \t 47 bits
+DMA Mask: \t 0x7fffffffffff
+Bus Location: \t 0000:01:00.0
+Device Minor: \t 0
+GPU Excluded:\t No
+";
+    let gpu = GPUInfo::from_nvidia_procfs(information).unwrap();
+    assert_eq!(gpu.vendor(), &GPUVendor::NVIDIA);
+    assert_eq!(gpu.model_name(), "NVIDIA GeForce RTX 4090");
+    assert_eq!(gpu.pci_bus_id.as_deref(), Some("0000:01:00.0"));
+    assert_eq!(gpu.vbios_version.as_deref(), Some("95.02.18.80.87"));
+    assert!(gpu.supports_cuda());
+    assert!(gpu.memory_mb() > 0, "VRAM is estimated when the driver doesn't report it");
+
+    // Older drivers print question marks for fields they can't read
+    let gpu = GPUInfo::from_nvidia_procfs("Model: \t\t Tesla T4\nVideo BIOS: \t ??.??.??.??.??\n").unwrap();
+    assert_eq!(gpu.vbios_version, None);
+    assert_eq!(gpu.pci_bus_id, None);
+    assert!(GPUInfo::from_nvidia_procfs("Model: \nIRQ: 187\n").is_none());
+    assert!(GPUInfo::from_nvidia_procfs("").is_none());
+
+    let version = "NVRM version: NVIDIA UNIX x86_64 Kernel Module  550.54.14  Thu Feb 22 01:44:30 UTC 2024
+GCC version:  gcc version 12.2.0 (Debian 12.2.0-14)
+";
+    assert_eq!(GPUInfo::nvidia_driver_version_from_procfs(version).as_deref(), Some("550.54.14"));
+    assert_eq!(
+        GPUInfo::nvidia_driver_version_from_procfs(
+            "NVRM version: NVIDIA UNIX Open Kernel Module for x86_64  560.35.03  Release Build  (dvs-builder@U16-I3-B03-4-3)"
+        )
+        .as_deref(),
+        Some("560.35.03")
+    );
+    assert_eq!(GPUInfo::nvidia_driver_version_from_procfs("GCC version: 12.2.0"), None);
+}
+
+#[test]
+fn test_amdgpu_sysfs_metrics() {
+    let dir = tempfile::tempdir().unwrap();
+    let device = dir.path().join("card1/device");
+    let hwmon = device.join("hwmon/hwmon3");
+    std::fs::create_dir_all(&hwmon).unwrap();
+    for (path, contents) in [
+        (device.join("vendor"), "0x1002"),
+        (device.join("device"), "0x744c"),
+        (device.join("uevent"), "DRIVER=amdgpu\nPCI_SLOT_NAME=0000:03:00.0"),
+        (device.join("gpu_busy_percent"), "37"),
+        (device.join("mem_info_vram_total"), "25753026560"),
+        (device.join("mem_info_vram_used"), "4294967296"),
+        (device.join("pp_dpm_sclk"), "0: 500Mhz\n1: 1800Mhz *\n2: 2500Mhz"),
+        (device.join("pp_dpm_mclk"), "0: 96Mhz\n1: 1250Mhz *"),
+        (hwmon.join("temp1_input"), "54000"),
+        (hwmon.join("power1_average"), "123000000"),
+        (hwmon.join("power1_cap"), "303000000"),
+    ] {
+        std::fs::write(path, format!("{contents}\n")).unwrap();
+    }
+
+    let mut gpu = GPUInfo::from_drm_device(&device).unwrap();
+    gpu.apply_amdgpu_sysfs_metrics(&device);
+    assert_eq!(gpu.usage_percent, Some(37.0));
+    assert_eq!(gpu.memory_mb(), 24560);
+    // The inventory reports the highest DPM state, not the active one
+    assert_eq!(gpu.boost_clock, Some(2500));
+    assert_eq!(gpu.memory_clock, Some(1250));
+    assert_eq!(gpu.temperature, Some(54.0));
+    assert_eq!(gpu.power_consumption, Some(123.0));
+    assert_eq!(gpu.power_limit, Some(303.0));
+
+    #[cfg(target_os = "linux")]
+    {
+        use hardware_query::{GPUUsage, GPUVendor};
+
+        let usage = GPUUsage::from_amdgpu_sysfs(&device, "0000:03:00.0");
+        assert_eq!(usage.vendor, GPUVendor::AMD);
+        assert_eq!(usage.utilization_percent, Some(37.0));
+        assert_eq!(usage.memory_used_mb, Some(4096));
+        assert_eq!(usage.memory_total_mb, Some(24560));
+        assert_eq!(usage.core_clock_mhz, Some(1800));
+        assert_eq!(usage.memory_clock_mhz, Some(1250));
+        assert_eq!(usage.power_draw_watts, Some(123.0));
+        assert_eq!(usage.power_limit_watts, Some(303.0));
+    }
+}
+
+#[test]
+fn test_metal_gpu_parsing() {
+    use hardware_query::GPUVendor;
+
+    assert_eq!(GPUVendor::from_device_name("Apple M3 Max"), GPUVendor::Apple);
+    assert_eq!(GPUVendor::from_device_name("AMD Radeon Pro 5500M"), GPUVendor::AMD);
+    assert_eq!(GPUVendor::from_device_name("Intel(R) UHD Graphics 630"), GPUVendor::Intel);
+    assert_eq!(
+        GPUVendor::from_device_name("Matrox G200"),
+        GPUVendor::Unknown("Matrox G200".to_string())
+    );
+
+    let ioreg = r#"+-o AGXAcceleratorG15X  <class AGXAcceleratorG15X, id 0x1000003f1, registered, matched, active, busy 0 (0 ms), retain 50>
+    {
+      "IOClass" = "AGXAcceleratorG15X"
+      "model" = "Apple M3 Max"
+      "gpu-core-count" = 40
+      "AGXParameterBufferMaxSize" = 1207959552
+    }
++-o AMDRadeonX6000_AMDNavi14GraphicsAccelerator  <class AMDRadeonX6000_AMDNavi14GraphicsAccelerator>
+    {
+      "IOClass" = "AMDRadeonX6000_AMDNavi14GraphicsAccelerator"
+    }
+"#;
+    assert_eq!(GPUInfo::core_counts_from_ioreg(ioreg), vec![40]);
+    assert!(GPUInfo::core_counts_from_ioreg("").is_empty());
+}
+
+#[test]
+fn test_gpu_topology_matrix() {
+    let hw_info = HardwareInfo::query().expect("Failed to query hardware info");
+    let topology = hw_info.gpu_topology();
+    assert_eq!(topology.gpus().len(), hw_info.gpus().len());
+
+    let matrix = topology.peer_bandwidth_matrix();
+    for (a, row) in matrix.iter().enumerate() {
+        assert_eq!(row.len(), matrix.len());
+        assert_eq!(row[a], 0.0, "A GPU has no peer bandwidth to itself");
+        assert!(row.iter().all(|bandwidth| *bandwidth >= 0.0));
+    }
+}
+
+#[test]
+fn test_query_with_options() {
+    use hardware_query::{HardwareComponent, QueryOptions};
+    use std::time::Duration;
+
+    let options = QueryOptions::new()
+        .with_timeout(Duration::from_secs(60))
+        .with_component_timeout(HardwareComponent::Thermal, Duration::from_secs(5));
+    assert_eq!(
+        options.timeout_for(HardwareComponent::Thermal),
+        Some(Duration::from_secs(5))
+    );
+    assert_eq!(
+        options.timeout_for(HardwareComponent::CPU),
+        Some(Duration::from_secs(60))
+    );
+
+    let hw_info = HardwareInfo::query_with_options(&options).expect("Failed to query hardware info");
+    // Timed-out components are listed rather than failing the query
+    for component in hw_info.timed_out_components() {
+        assert!(HardwareComponent::ALL.contains(&component));
+    }
+    if !hw_info.timed_out_components().contains(&HardwareComponent::CPU) {
+        assert!(hw_info.cpu().logical_cores() > 0);
+    }
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_hardware_info_query_async() {
+    let hw_info = HardwareInfo::query_async()
+        .await
+        .expect("Failed to query hardware info asynchronously");
+
+    assert!(hw_info.timestamp > 0, "Timestamp should be > 0");
+    assert!(hw_info.cpu.physical_cores() > 0, "Should have CPU info");
+}
+
+#[test]
+fn test_cache_config_ttls() {
+    use hardware_query::{CacheConfig, HardwareComponent};
+    use std::time::Duration;
+
+    let config = CacheConfig::new()
+        .with_static_ttl(Duration::from_secs(60))
+        .with_dynamic_ttl(Duration::from_secs(2))
+        .with_component_ttl(HardwareComponent::GPU, Duration::from_secs(5));
+
+    assert_eq!(config.ttl_for(HardwareComponent::CPU), Duration::from_secs(60));
+    assert_eq!(config.ttl_for(HardwareComponent::Thermal), Duration::from_secs(2));
+    assert_eq!(config.ttl_for(HardwareComponent::GPU), Duration::from_secs(5));
+}
+
+#[test]
+fn test_query_cache_reuses_static_data() {
+    use hardware_query::{CacheConfig, QueryCache};
+
+    let cache = QueryCache::new(CacheConfig::new());
+    let first = cache.query().expect("Failed to query hardware info through cache");
+    let second = cache.query().expect("Failed to query hardware info through cache");
+
+    assert_eq!(first.cpu.model_name(), second.cpu.model_name());
+    assert_eq!(first.gpus.len(), second.gpus.len());
+}
+
+#[test]
+fn test_benchmark_assessment_short_run() {
+    use hardware_query::HardwarePresets;
+    use std::time::Duration;
+
+    let assessment = HardwarePresets::benchmark_assessment(Duration::from_millis(300))
+        .expect("Benchmark assessment should succeed");
+
+    assert!(assessment.results.cpu_gflops_single > 0.0);
+    assert!(assessment.results.cpu_gflops_multi >= assessment.results.cpu_gflops_single * 0.5);
+    assert!(assessment.results.memory_bandwidth_gbps > 0.0);
+    assert!(assessment.performance_score <= 100);
+}
+
+#[cfg(feature = "monitoring")]
+#[test]
+fn test_history_buffer_trends() {
+    use hardware_query::{HistoryBuffer, Metric};
+    use std::time::{Duration, SystemTime};
+
+    let mut history = HistoryBuffer::new(3);
+    let start = SystemTime::now() - Duration::from_secs(40);
+    for (i, temperature) in [50.0, 60.0, 70.0, 80.0].into_iter().enumerate() {
+        history.record(Metric::CpuTemperature, temperature, start + Duration::from_secs(10 * i as u64));
+    }
+
+    // Oldest sample was evicted
+    let series = history.cpu_temperature(Duration::from_secs(3600));
+    assert_eq!(series.len(), 3);
+    assert_eq!(series.min(), Some(60.0));
+    assert_eq!(series.max(), Some(80.0));
+    assert_eq!(series.avg(), Some(70.0));
+    assert!((series.slope().unwrap() - 1.0).abs() < 1e-9);
+
+    assert!(history.gpu_temperature(Duration::from_secs(3600)).is_empty());
+}
+
+#[cfg(feature = "monitoring")]
+#[test]
+fn test_alert_rules_config() {
+    use hardware_query::{AlertRule, Comparison, MonitoringConfig};
+    use std::time::Duration;
+
+    let mut config = MonitoringConfig::default();
+    config.add_rule(AlertRule::gpu_temp().above(83.0).for_duration(Duration::from_secs(30)));
+    config.add_rule(AlertRule::memory_usage().above(90.0).with_hysteresis(-5.0).with_id("memory-pressure"));
+
+    assert_eq!(config.rules.len(), 2);
+    assert_eq!(config.rules[0].id(), "GPU temperature > 83");
+    assert_eq!(config.rules[0].sustained, Duration::from_secs(30));
+    assert_eq!(config.rules[1].id(), "memory-pressure");
+    assert_eq!(config.rules[1].hysteresis, 5.0);
+
+    let rule = AlertRule::cpu_usage().below(10.0);
+    assert_eq!(rule.comparison, Comparison::Below);
+}
+
+#[cfg(feature = "monitoring")]
+#[test]
+fn test_monitoring_config_defaults_missing_fields() {
+    use hardware_query::MonitoringConfig;
+
+    // Configs saved before hotplug, history, rules and the other options existed
+    let old = r#"{
+        "update_interval": { "secs": 10, "nanos": 0 },
+        "enable_thermal": true,
+        "enable_power": false,
+        "enable_hardware": true,
+        "thermal_threshold": 85.0,
+        "power_threshold": null,
+        "background_monitoring": true
+    }"#;
+    let config: MonitoringConfig = serde_json::from_str(old).expect("Failed to read an older config");
+    let defaults = MonitoringConfig::default();
+    assert!(!config.enable_power);
+    assert_eq!(config.enable_hotplug, defaults.enable_hotplug);
+    assert_eq!(config.history_length, defaults.history_length);
+    assert_eq!(config.enable_ecc, defaults.enable_ecc);
+}
+
+#[test]
+fn test_network_interface_details() {
+    use hardware_query::NetworkType;
+
+    let hw_info = HardwareInfo::query().unwrap();
+    for interface in hw_info.network_interfaces() {
+        if interface.wireless().is_some() {
+            assert_eq!(interface.network_type(), &NetworkType::WiFi);
+        }
+        if let Some(mtu) = interface.mtu() {
+            assert!(mtu > 0);
+        }
+        if let Some(speed) = interface.speed_mbps() {
+            assert!(speed > 0);
+        }
+    }
+}
+
+#[test]
+fn test_usb_topology() {
+    let hw_info = HardwareInfo::query().unwrap();
+    let topology = hw_info.usb_topology();
+
+    for device in topology.devices() {
+        // Every parent link points at a hub in the tree
+        if let Some(parent) = topology.parent(device) {
+            assert!(parent.is_hub(), "{} is attached to non-hub {}", device.id(), parent.id());
+            assert!(topology.children(parent).iter().any(|child| child.id() == device.id()));
+        }
+        assert!(topology.depth(device) <= topology.devices().len());
+    }
+    assert!(topology.roots().len() <= topology.devices().len());
+}
+
+#[test]
+fn test_pci_device_ids() {
+    use hardware_query::PCIDevice;
+
+    for device in PCIDevice::query_all().unwrap() {
+        let (Some(vendor), Some(product)) = (device.vendor_id(), device.product_id()) else {
+            panic!("unparseable PCI ID {}", device.device_id());
+        };
+        // Names come from the same database the lookup API uses
+        if let Some(name) = PCIDevice::lookup_device_name(vendor, product) {
+            assert!(!name.is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_memory_modules() {
+    use hardware_query::{MemoryInfo, MemoryType};
+
+    assert_eq!(MemoryType::from_name("DDR5"), MemoryType::DDR5);
+    assert_eq!(MemoryType::from_name("LPDDR4X"), MemoryType::LPDDR4);
+
+    let memory = MemoryInfo::query().unwrap();
+    for module in memory.modules() {
+        assert!(module.size_mb() > 0);
+    }
+    // Module speeds drive the reported memory speed; no modules means unknown
+    if memory.modules().is_empty() {
+        assert_eq!(memory.speed_mhz(), 0);
+        assert!(memory.bandwidth_gb_s().is_none());
+    }
+}
+
+#[test]
+fn test_ecc_status() {
+    let memory = hardware_query::MemoryInfo::query().unwrap();
+    let ecc = memory.ecc_status();
+
+    // Per-DIMM counters never exceed the controller totals
+    if let Some(total) = ecc.correctable_errors() {
+        let per_dimm: u64 = ecc.dimms().iter().map(|d| d.correctable_errors).sum();
+        assert!(per_dimm <= total || ecc.dimms().is_empty());
+    }
+    if ecc.has_uncorrectable_errors() {
+        assert!(ecc.enabled());
+    }
+}
+
+#[test]
+fn test_system_info() {
+    use hardware_query::ChassisType;
+
+    assert_eq!(ChassisType::from_smbios(10), ChassisType::Laptop);
+    assert_eq!(ChassisType::from_smbios(23), ChassisType::RackMount);
+    assert!(ChassisType::from_smbios(31).is_portable());
+
+    let hw_info = HardwareInfo::query().unwrap();
+    let firmware = hw_info.system().firmware();
+    if let Some(date) = firmware.release_date() {
+        // Normalized to YYYY-MM-DD
+        assert_eq!(date.len(), 10);
+        assert_eq!(&date[4..5], "-");
+    }
+    // Legacy BIOS cannot enforce Secure Boot
+    if firmware.is_uefi() == Some(false) {
+        assert_eq!(firmware.secure_boot(), Some(false));
+    }
+}
+
+#[test]
+fn test_power_state() {
+    use hardware_query::{PowerProfile, PowerSource, PowerState};
+
+    assert_eq!(PowerState::from_plan_name("Balanced"), PowerState::Balanced);
+    assert_eq!(PowerState::from_plan_name("balance_power"), PowerState::Balanced);
+    assert_eq!(PowerState::from_plan_name("Ultimate Performance"), PowerState::HighPerformance);
+    assert_eq!(PowerState::from_plan_name("low-power"), PowerState::PowerSaver);
+    assert_eq!(PowerState::from_plan_name("Power saver"), PowerState::PowerSaver);
+    assert_eq!(
+        PowerState::from_plan_name("Contoso Quiet Office"),
+        PowerState::Custom("Contoso Quiet Office".to_string())
+    );
+
+    let profile = PowerProfile::query().unwrap();
+    assert_eq!(profile.is_on_battery(), profile.power_source == PowerSource::Battery);
+    if cfg!(target_os = "linux") {
+        assert_ne!(profile.power_source, PowerSource::Unknown);
+    }
+    match &profile.power_plan {
+        Some(_) => assert_ne!(profile.power_state, PowerState::Unknown),
+        None => assert!(profile.available_power_modes.is_empty()),
+    }
+    assert!(profile.available_power_modes.iter().filter(|mode| mode.is_active).count() <= 1);
+}
+
+#[cfg(feature = "monitoring")]
+#[tokio::test]
+async fn test_monitoring_event_stream() {
+    use futures::StreamExt;
+    use hardware_query::{HardwareMonitor, MonitoringConfig, MonitoringEvent};
+    use std::time::Duration;
+
+    let monitor = HardwareMonitor::with_config(MonitoringConfig {
+        update_interval: Duration::from_millis(50),
+        enable_hardware: false,
+        enable_thermal: false,
+        enable_power: false,
+        enable_hotplug: false,
+        ..MonitoringConfig::default()
+    });
+    let mut events = monitor.events();
+    monitor.start_monitoring().await.unwrap();
+
+    let event = tokio::time::timeout(Duration::from_secs(10), events.next())
+        .await
+        .expect("no event within timeout");
+    assert!(matches!(event, Some(MonitoringEvent::MetricsUpdate { .. })));
+
+    monitor.stop_monitoring().await;
+}
+
+#[cfg(feature = "monitoring")]
+#[tokio::test]
+async fn test_monitor_lifecycle() {
+    use hardware_query::{HardwareMonitor, MonitorState, MonitoringConfig};
+    use std::time::Duration;
+
+    let monitor = HardwareMonitor::with_config(MonitoringConfig {
+        update_interval: Duration::from_millis(50),
+        enable_hardware: false,
+        enable_thermal: false,
+        enable_power: false,
+        enable_hotplug: false,
+        ..MonitoringConfig::default()
+    });
+    assert_eq!(monitor.state().await, MonitorState::Stopped);
+    assert!(monitor.pause().await.is_err());
+
+    monitor.start_monitoring().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let health = monitor.health().await;
+    assert_eq!(health.state, MonitorState::Running);
+    assert!(health.samples > 0);
+    assert!(health.late_samples <= health.samples);
+
+    monitor.pause().await.unwrap();
+    assert_eq!(monitor.state().await, MonitorState::Paused);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let paused_samples = monitor.health().await.samples;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(monitor.health().await.samples, paused_samples);
+
+    monitor.update_config(MonitoringConfig {
+        update_interval: Duration::from_millis(20),
+        thermal_threshold: 70.0,
+        ..monitor.config()
+    }).await;
+    assert_eq!(monitor.health().await.configured_interval, Duration::from_millis(20));
+
+    monitor.resume().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(monitor.health().await.samples > paused_samples);
+
+    monitor.stop().await.unwrap();
+    assert_eq!(monitor.state().await, MonitorState::Stopped);
+    assert!(monitor.health().await.keeping_up);
+    monitor.stop().await.unwrap();
+}
+
+#[test]
+fn test_gpu_usage() {
+    use hardware_query::{GPUUsage, GPUVendor};
+
+    for gpu in GPUUsage::query_all() {
+        assert!(!gpu.device().is_empty());
+        if let Some(percent) = gpu.memory_used_percent() {
+            assert!((0.0..=100.0).contains(&percent), "{}: {percent}% VRAM used", gpu.device);
+        }
+    }
+
+    let gpu = GPUUsage {
+        device: "0000:03:00.0".to_string(),
+        name: Some("Radeon RX 7900 XTX".to_string()),
+        vendor: GPUVendor::AMD,
+        utilization_percent: Some(97.0),
+        memory_used_mb: Some(23_552),
+        memory_total_mb: Some(24_576),
+        core_clock_mhz: Some(2_482),
+        memory_clock_mhz: Some(1_250),
+        power_draw_watts: Some(341.0),
+        power_limit_watts: Some(355.0),
+        temperature: Some(71.0),
+    };
+    assert_eq!(gpu.memory_free_mb(), Some(1_024));
+    assert!((gpu.memory_used_percent().unwrap() - 95.83).abs() < 0.01);
+}
+
+#[cfg(feature = "monitoring")]
+#[test]
+fn test_vram_exhausted_event() {
+    use hardware_query::{EventSeverity, MonitoringEvent};
+
+    let event = MonitoringEvent::VramExhausted {
+        device: "0000:01:00.0".to_string(),
+        name: Some("NVIDIA GeForce RTX 4090".to_string()),
+        memory_used_mb: 23_900,
+        memory_total_mb: 24_564,
+        used_percent: 97.3,
+        timestamp: std::time::SystemTime::now(),
+    };
+    assert_eq!(event.kind(), "VramExhausted");
+    assert_eq!(event.severity(), EventSeverity::Warning);
+    assert_eq!(event.summary(), "NVIDIA GeForce RTX 4090 VRAM at 97% (23900 of 24564 MB)");
+}
+
+#[test]
+fn test_performance_counters() {
+    use hardware_query::{GPUEngineUsage, HardwareQueryError, PerformanceCounterSampler, PerformanceCounters};
+    use std::time::Duration;
+
+    match PerformanceCounterSampler::new() {
+        Ok(mut sampler) => {
+            std::thread::sleep(Duration::from_millis(250));
+            let counters = sampler.collect().unwrap();
+            assert!(counters.per_core_usage.iter().all(|usage| (0.0..=100.0).contains(usage)));
+        }
+        #[cfg(not(target_os = "windows"))]
+        Err(e) => assert!(matches!(e, HardwareQueryError::PlatformNotSupported(_)), "unexpected error: {e}"),
+        // Counter sets can be disabled (lodctr /d) or the service unavailable in containers
+        #[cfg(target_os = "windows")]
+        Err(e) => assert!(!matches!(e, HardwareQueryError::PlatformNotSupported(_)), "unexpected error: {e}"),
+    }
+
+    let engine = |luid: &str, engine_type: &str, utilization_percent| GPUEngineUsage {
+        adapter_luid: luid.to_string(),
+        engine_type: engine_type.to_string(),
+        utilization_percent,
+    };
+    let counters = PerformanceCounters {
+        per_core_usage: vec![10.0, 30.0, 50.0, 70.0],
+        per_core_frequency_mhz: vec![4_200.0, 4_400.0],
+        dpc_time_percent: Some(0.4),
+        interrupt_time_percent: Some(0.2),
+        interrupts_per_sec: Some(12_000.0),
+        disk_queue_lengths: Vec::new(),
+        gpu_engines: vec![
+            engine("0x00000000_0x0000D1B5", "3D", 62.0),
+            engine("0x00000000_0x0000D1B5", "VideoDecode", 15.0),
+            engine("0x00000000_0x0000E2C1", "Copy", 4.0),
+        ],
+        interval: Duration::from_secs(1),
+    };
+    assert_eq!(counters.average_cpu_usage(), Some(40.0));
+    assert_eq!(counters.average_frequency_mhz(), Some(4_300.0));
+    assert_eq!(
+        counters.gpu_utilization(),
+        vec![
+            ("0x00000000_0x0000D1B5".to_string(), 62.0),
+            ("0x00000000_0x0000E2C1".to_string(), 4.0),
+        ]
+    );
+}
+
+#[test]
+fn test_power_rails() {
+    use hardware_query::PowerRails;
+
+    #[cfg(not(all(target_os = "macos", feature = "smc")))]
+    assert_eq!(PowerRails::query(), None);
+
+    let apple_silicon = "\
+**** Processor usage ****
+
+E-Cluster HW active frequency: 1020 MHz
+CPU Power: 1834 mW
+GPU Power: 112 mW
+ANE Power: 0 mW
+Combined Power (CPU + GPU + ANE): 1946 mW
+";
+    let rails = PowerRails::from_powermetrics(apple_silicon);
+    assert_eq!(rails.cpu_watts, Some(1.834));
+    assert_eq!(rails.gpu_watts, Some(0.112));
+    assert_eq!(rails.ane_watts, Some(0.0));
+    assert_eq!(rails.total(), Some(1.946));
+
+    let intel = PowerRails::from_powermetrics("Intel energy model derived package power (CPUs+GT+SA): 3.45W\n");
+    assert_eq!(intel.package_watts, Some(3.45));
+    assert_eq!(intel.cpu_watts, None);
+    assert!(!intel.is_empty());
+
+    assert!(PowerRails::from_powermetrics("powermetrics must be invoked as the superuser").is_empty());
+}
+
+#[test]
+fn test_thermal_headroom() {
+    use hardware_query::{CPUPowerLimits, ComponentHeadroom, ComponentLoad, HeadroomLimit, ThermalHeadroom, ThermalInfo};
+
+    let limits = CPUPowerLimits::query();
+    if let (Some(sustained), Some(burst)) = (limits.sustained_watts, limits.burst_watts) {
+        assert!(sustained > 0.0 && burst > 0.0);
+    }
+
+    let thermal = ThermalInfo::query().unwrap();
+    let headroom = ThermalHeadroom::assess(&thermal, &limits, None, None, &[]);
+    assert!((0.0..=100.0).contains(&headroom.cpu.sustainable_load_percent));
+    assert_eq!(headroom.bottleneck().device, "CPU");
+
+    // A laptop GPU whose cooler, not its 80 W limit, sets the ceiling
+    let load = ComponentLoad {
+        temperature: Some(80.0),
+        throttle_temperature: 87.0,
+        ambient_temperature: 30.0,
+        power_limit_watts: Some(80.0),
+        full_load_watts: Some(80.0),
+        power_draw_watts: Some(50.0),
+        load_percent: Some(100.0),
+    };
+    let gpu = ComponentHeadroom::assess("0000:01:00.0", None, &load, 1.0);
+    assert_eq!(gpu.limited_by, HeadroomLimit::Thermal);
+    assert!((gpu.thermal_limit_watts.unwrap() - 57.0).abs() < 0.01);
+    assert!((gpu.headroom_watts.unwrap() - 7.0).abs() < 0.01);
+    assert_eq!(gpu.sustainable_load_percent, 100.0);
+
+    // Without power readings the temperature bands decide
+    let idle = ComponentLoad {
+        temperature: Some(45.0),
+        throttle_temperature: 95.0,
+        ambient_temperature: 25.0,
+        ..ComponentLoad::default()
+    };
+    let cpu = ComponentHeadroom::assess("CPU", None, &idle, 0.95);
+    assert_eq!(cpu.limited_by, HeadroomLimit::Unknown);
+    assert_eq!(cpu.thermal_margin, Some(50.0));
+    assert_eq!(cpu.headroom_watts, None);
+    assert!((cpu.sustainable_load_percent - 95.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_resizable_bar() {
+    use hardware_query::{GPUInfo, GPUType, ResizableBar};
+
+    for gpu in GPUInfo::query_all().unwrap() {
+        if let Some(rebar) = gpu.resizable_bar() {
+            assert_eq!(gpu.gpu_type, GPUType::Discrete);
+            assert_eq!(rebar.enabled, rebar.bar_size_mb > 256);
+        }
+    }
+
+    // AMD layout: 256 MB VRAM window below 4 GiB, doorbells in BAR2, registers in BAR5
+    let resource = "\
+0x00000000d0000000 0x00000000dfffffff 0x000000000014220c
+0x0000000000000000 0x0000000000000000 0x0000000000000000
+0x00000000e0000000 0x00000000e01fffff 0x000000000014220c
+0x0000000000000000 0x0000000000000000 0x0000000000000000
+0x000000000000e000 0x000000000000e0ff 0x0000000000040101
+0x00000000fcb00000 0x00000000fcb7ffff 0x0000000000040200
+";
+    let rebar = ResizableBar::from_pci_resources(resource, &[]).unwrap();
+    assert_eq!(rebar.bar_index, Some(0));
+    assert_eq!(rebar.bar_size_mb, 256);
+    assert!(!rebar.enabled);
+    assert_eq!(rebar.above_4g_decoding, Some(false));
+    assert_eq!(rebar.supported, None);
+    assert!(!rebar.is_limited());
+
+    // Full config space with a Resizable BAR capability for BAR0 supporting up to 32 GB
+    let mut config = vec![0u8; 4096];
+    config[0x100..0x104].copy_from_slice(&0x0001_0015u32.to_le_bytes());
+    config[0x104..0x108].copy_from_slice(&(0x0000_ffffu32 << 4).to_le_bytes());
+    // One resizable BAR, index 0, current size 2^8 MB
+    config[0x108..0x10c].copy_from_slice(&((1u32 << 5) | (8 << 8)).to_le_bytes());
+    let rebar = ResizableBar::from_pci_resources(resource, &config).unwrap();
+    assert_eq!(rebar.supported, Some(true));
+    assert_eq!(rebar.max_bar_size_mb, Some(32_768));
+    assert!(rebar.is_limited());
+
+    // Capability list without Resizable BAR (AER only)
+    let mut config = vec![0u8; 4096];
+    config[0x100..0x104].copy_from_slice(&0x0001_0001u32.to_le_bytes());
+    let rebar = ResizableBar::from_pci_resources(resource, &config).unwrap();
+    assert_eq!(rebar.supported, Some(false));
+    assert!(!rebar.is_limited());
+
+    assert_eq!(ResizableBar::from_pci_resources("", &[]), None);
+}
+
+#[test]
+fn test_battery_drain() {
+    use hardware_query::{BatteryDrain, BatteryInfo};
+    use std::time::Duration;
+
+    let reading = |percentage: f32, status: &str, remaining_wh: f32| -> BatteryInfo {
+        serde_json::from_value(serde_json::json!({
+            "percentage": percentage,
+            "status": status,
+            "remaining_capacity_wh": remaining_wh,
+        }))
+        .unwrap()
+    };
+
+    let drain = BatteryDrain::between(
+        &reading(80.0, "Discharging", 40.0),
+        &reading(75.0, "Discharging", 37.5),
+        Duration::from_secs(900),
+    )
+    .unwrap();
+    assert!((drain.percent_per_hour - 20.0).abs() < 1e-6);
+    assert!((drain.watts.unwrap() - 10.0).abs() < 1e-6);
+    assert!((drain.hours_remaining(75.0).unwrap() - 3.75).abs() < 1e-6);
+
+    // Charging or plugged in at either end is not a drain
+    assert!(BatteryDrain::between(
+        &reading(80.0, "Charging", 40.0),
+        &reading(75.0, "Discharging", 37.5),
+        Duration::from_secs(900),
+    )
+    .is_none());
+}
+
+#[cfg(feature = "monitoring")]
+#[test]
+fn test_battery_events() {
+    use hardware_query::{EventSeverity, MonitoringEvent};
+    use std::time::SystemTime;
+
+    let low = MonitoringEvent::LowBattery {
+        percentage: 18.0,
+        critical: false,
+        time_remaining_minutes: Some(42),
+        timestamp: SystemTime::now(),
+    };
+    assert_eq!(low.severity(), EventSeverity::Warning);
+    assert_eq!(low.summary(), "Low battery: 18% (42 min left)");
+
+    let critical = MonitoringEvent::LowBattery {
+        percentage: 4.0,
+        critical: true,
+        time_remaining_minutes: None,
+        timestamp: SystemTime::now(),
+    };
+    assert_eq!(critical.severity(), EventSeverity::Critical);
+
+    let unplugged = MonitoringEvent::ChargerStateChanged {
+        plugged_in: false,
+        percentage: 96.0,
+        timestamp: SystemTime::now(),
+    };
+    assert_eq!(unplugged.kind(), "ChargerStateChanged");
+    assert_eq!(unplugged.summary(), "Charger unplugged at 96%");
+
+    let drain = MonitoringEvent::AbnormalBatteryDrain {
+        percent_per_hour: 55.0,
+        watts: Some(31.5),
+        percentage: 70.0,
+        timestamp: SystemTime::now(),
+    };
+    assert_eq!(drain.summary(), "Battery draining at 55%/h (31.5 W)");
+}
+
+#[cfg(feature = "monitoring")]
+#[test]
+fn test_adaptive_sampling_intervals() {
+    use hardware_query::{AdaptiveSampling, SamplingReading};
+    use std::time::Duration;
+
+    let sampling = AdaptiveSampling {
+        min_interval: Duration::from_secs(1),
+        max_interval: Duration::from_secs(20),
+        ..AdaptiveSampling::default()
+    };
+    let limits = SamplingReading { temperature: Some(90.0), power: Some(100.0) };
+    let reading = |temperature, power| SamplingReading { temperature: Some(temperature), power: Some(power) };
+    let elapsed = Duration::from_secs(6);
+
+    // Stable readings back off until max_interval
+    let mut current = Duration::from_secs(2);
+    for _ in 0..20 {
+        current = sampling.next_interval(current, elapsed, &reading(50.0, 20.0), &reading(50.1, 20.0), &limits);
+    }
+    assert_eq!(current, Duration::from_secs(20));
+
+    // +2°C in 6 s is 20°C/min: rapid
+    let next = sampling.next_interval(current, elapsed, &reading(50.0, 20.0), &reading(52.0, 20.0), &limits);
+    assert_eq!(next, Duration::from_secs(1));
+
+    // +0.5°C in 6 s is 5°C/min: changing, so the interval halves
+    let next = sampling.next_interval(current, elapsed, &reading(50.0, 20.0), &reading(50.5, 20.0), &limits);
+    assert_eq!(next, Duration::from_secs(10));
+
+    // Within 10% of the power threshold
+    let next = sampling.next_interval(current, elapsed, &reading(50.0, 92.0), &reading(50.0, 92.0), &limits);
+    assert_eq!(next, Duration::from_secs(1));
+
+    // No readings at all count as stable
+    let empty = SamplingReading::default();
+    let next = sampling.next_interval(Duration::from_secs(4), elapsed, &empty, &empty, &limits);
+    assert_eq!(next, Duration::from_secs(6));
+}
+
+#[cfg(feature = "monitoring")]
+#[tokio::test]
+async fn test_event_sinks() {
+    use hardware_query::{EventSeverity, JsonlFileSink, MonitoringCallback, MonitoringEvent};
+    use std::time::SystemTime;
+
+    let alert = MonitoringEvent::ThermalAlert {
+        sensor_name: "k10temp Tctl".to_string(),
+        temperature: 92.0,
+        threshold: 85.0,
+        timestamp: SystemTime::now(),
+    };
+    let ecc = MonitoringEvent::MemoryErrors {
+        new_uncorrectable: 1,
+        uncorrectable_errors: 3,
+        correctable_errors: None,
+        timestamp: SystemTime::now(),
+    };
+    let error = MonitoringEvent::MonitoringError { error: "probe failed".to_string(), timestamp: SystemTime::now() };
+    assert_eq!(alert.kind(), "ThermalAlert");
+    assert_eq!(alert.severity(), EventSeverity::Warning);
+    assert_eq!(ecc.severity(), EventSeverity::Critical);
+    assert!(alert.summary().contains("k10temp Tctl"));
+    assert!(EventSeverity::Critical > EventSeverity::Info);
+
+    // Every line is a JSON record; the file rotates once it passes max_bytes
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("events.jsonl");
+    let sink = JsonlFileSink::new(&path).unwrap().with_max_bytes(512).with_max_files(2);
+    for _ in 0..20 {
+        sink.on_event(&alert).await;
+    }
+    let current = std::fs::read_to_string(&path).unwrap();
+    let record: serde_json::Value = serde_json::from_str(current.lines().next().unwrap()).unwrap();
+    assert_eq!(record["kind"], "ThermalAlert");
+    assert_eq!(record["severity"], "warning");
+    assert!(record["timestamp_ms"].as_u64().unwrap() > 0);
+    assert!(current.len() <= 512);
+    assert!(dir.path().join("events.jsonl.1").exists());
+    assert!(dir.path().join("events.jsonl.2").exists());
+    assert!(!dir.path().join("events.jsonl.3").exists());
+
+    // Events below the minimum severity are dropped
+    let critical_path = dir.path().join("critical.jsonl");
+    let critical = JsonlFileSink::new(&critical_path).unwrap().with_min_severity(EventSeverity::Critical);
+    critical.on_event(&alert).await;
+    critical.on_event(&error).await;
+    critical.on_event(&ecc).await;
+    let lines = std::fs::read_to_string(&critical_path).unwrap();
+    assert_eq!(lines.lines().count(), 1);
+    assert!(lines.contains("MemoryErrors"));
+
+    #[cfg(target_os = "linux")]
+    {
+        let syslog = hardware_query::SystemLogSink::new("hardware-query-test").unwrap();
+        syslog.on_event(&ecc).await;
+    }
+}
+
+#[test]
+fn test_gpu_processes() {
+    let gpus = GPUInfo::query_all().expect("Failed to query GPU info");
+    for gpu in &gpus {
+        let processes = gpu.processes();
+
+        // Sorted by memory use, largest first, and each pid listed once
+        for pair in processes.windows(2) {
+            assert!(pair[0].used_memory_mb() >= pair[1].used_memory_mb());
+        }
+        let mut pids: Vec<u32> = processes.iter().map(|p| p.pid()).collect();
+        pids.sort_unstable();
+        pids.dedup();
+        assert_eq!(pids.len(), processes.len());
+    }
+}
+
+#[test]
+fn test_cpu_frequency_scaling() {
+    let cpu = CPUInfo::query().expect("Failed to query CPU info");
+    let scaling = cpu.frequency_scaling();
+
+    if let Some(average) = scaling.average_frequency_mhz() {
+        assert!(average > 0.0);
+    }
+    if let (Some(min), Some(max)) = (scaling.scaling_min_mhz(), scaling.scaling_max_mhz()) {
+        assert!(min <= max);
+    }
+    if let (Some(min), Some(max)) = (scaling.hardware_min_mhz(), scaling.hardware_max_mhz()) {
+        assert!(min <= max);
+    }
+    if let Some(governor) = scaling.governor() {
+        if !scaling.available_governors().is_empty() {
+            assert!(scaling.available_governors().iter().any(|g| g == governor));
+        }
+    }
+}
+
+#[test]
+fn test_cpu_throughput_estimates() {
+    use hardware_query::{CPUFeature, CPUVendor};
+    use std::time::Duration;
+
+    let hw_info = HardwareInfo::query().unwrap();
+    let mut cpu = hw_info.cpu().clone();
+
+    // 8 cores at 4 GHz with AVX2 + FMA: 8 lanes x 2 x 2 pipes = 32 FLOPs/cycle
+    cpu.vendor = CPUVendor::Intel;
+    cpu.architecture = "x86_64".to_string();
+    cpu.physical_cores = 8;
+    cpu.base_frequency = 3000;
+    cpu.max_frequency = 4000;
+    cpu.features = vec![CPUFeature::SSE2, CPUFeature::AVX, CPUFeature::AVX2, CPUFeature::FMA];
+    assert_eq!(cpu.flops_per_cycle(), 32.0);
+    assert!((cpu.estimated_gflops().unwrap() - 1024.0).abs() < 1e-9);
+    cpu.features.push(CPUFeature::AVX512);
+    assert_eq!(cpu.flops_per_cycle(), 64.0);
+    // AMD executes AVX-512 on 256-bit units
+    cpu.vendor = CPUVendor::AMD;
+    assert_eq!(cpu.flops_per_cycle(), 32.0);
+    cpu.features = vec![CPUFeature::SSE2];
+    assert_eq!(cpu.flops_per_cycle(), 8.0);
+    cpu.vendor = CPUVendor::Apple;
+    cpu.architecture = "aarch64".to_string();
+    assert_eq!(cpu.flops_per_cycle(), 32.0);
+    cpu.max_frequency = 0;
+    cpu.base_frequency = 0;
+    assert_eq!(cpu.estimated_gflops(), None);
+
+    let mut memory = hw_info.memory().clone();
+    memory.bandwidth_gb_s = Some(51.2);
+    assert_eq!(hw_info.cpu().estimated_memory_bandwidth(&memory), Some(51.2f32 as f64));
+    memory.bandwidth_gb_s = None;
+    cpu.brand = "Apple M2 Max".to_string();
+    assert!((cpu.estimated_memory_bandwidth(&memory).unwrap() - 409.6).abs() < 0.1);
+
+    let validation = hw_info.cpu().validate_estimates(hw_info.memory(), Duration::from_millis(200));
+    assert!(validation.measured_gflops > 0.0);
+    assert!(validation.measured_memory_bandwidth_gbps > 0.0);
+    assert_eq!(validation.gflops_efficiency().is_some(), hw_info.cpu().estimated_gflops().is_some());
+}
+
+#[test]
+fn test_cpu_affinity_sets() {
+    use hardware_query::{AffinityCriteria, CPUTopology, CoreCluster, CoreType, CpuSet, GroupAffinity};
+
+    // Node 0: four P-cores with SMT (0-7) and two E-cores (8, 9); node 1: two P-cores with SMT (10-13)
+    let mut topology = CPUTopology::flat(14);
+    for processor in &mut topology.processors {
+        let id = processor.id;
+        let (core, node) = match id {
+            0..=7 => (id / 2, 0),
+            8 | 9 => (id - 4, 0),
+            _ => (id / 2, 1),
+        };
+        processor.core_id = core;
+        processor.socket_id = node;
+        processor.numa_node = Some(node);
+        processor.smt_siblings = match id {
+            8 | 9 => vec![id],
+            _ => vec![id & !1, id | 1],
+        };
+    }
+    let cluster = |core_type, logical_cpus: Vec<u32>| CoreCluster {
+        core_type,
+        name: None,
+        physical_cores: logical_cpus.len() as u32,
+        logical_cpus,
+        max_frequency_mhz: None,
+        l2_cache_kb: None,
+    };
+    topology.clusters = vec![
+        cluster(CoreType::Performance, (0..8).chain(10..14).collect()),
+        cluster(CoreType::Efficiency, vec![8, 9]),
+    ];
+
+    assert_eq!(topology.cpu_set(&AffinityCriteria::new()).len(), 14);
+    let criteria = AffinityCriteria::new().numa_node(0).core_type(CoreType::Performance).one_per_core();
+    let set = topology.cpu_set(&criteria);
+    assert_eq!(set.cpus(), &[0, 2, 4, 6]);
+    assert_eq!(set.to_cpu_list(), "0,2,4,6");
+    assert_eq!(set.to_hex_mask(), "0x55");
+
+    let set = topology.cpu_set(&AffinityCriteria::new().numa_node(0));
+    assert_eq!(set.to_cpu_list(), "0-9");
+    assert_eq!(topology.cpu_set(&AffinityCriteria::new().core_type(CoreType::Efficiency)).to_cpu_list(), "8-9");
+    assert_eq!(topology.cpu_set(&AffinityCriteria::new().socket(1).one_per_core()).cpus(), &[10, 12]);
+    assert!(topology.cpu_set(&AffinityCriteria::new().numa_node(3)).is_empty());
+
+    // Round trip through the Linux list format, and across processor groups
+    let set = CpuSet::from_cpu_list("0-2,5,64-65,130");
+    assert_eq!(set.len(), 7);
+    assert!(set.contains(64) && !set.contains(3));
+    assert_eq!(set.to_string(), "0-2,5,64-65,130");
+    assert_eq!(set.mask_words(), vec![0b100111, 0b11, 0b100]);
+    assert_eq!(set.to_hex_mask(), "0x400000000000000030000000000000027");
+    assert_eq!(
+        set.group_affinities(),
+        vec![
+            GroupAffinity { group: 0, mask: 0b100111 },
+            GroupAffinity { group: 1, mask: 0b11 },
+            GroupAffinity { group: 2, mask: 0b100 },
+        ]
+    );
+    assert_eq!(CpuSet::default().to_hex_mask(), "0x0");
+
+    #[cfg(target_os = "linux")]
+    {
+        let cpu_set = set.to_cpu_set_t();
+        assert!(unsafe { libc::CPU_ISSET(130, &cpu_set) });
+        assert!(!unsafe { libc::CPU_ISSET(3, &cpu_set) });
+        assert_eq!(unsafe { libc::CPU_COUNT(&cpu_set) }, 7);
+    }
+}
+
+#[test]
+fn test_model_fit_planner() {
+    use hardware_query::{ModelFitPlanner, ModelPlacement, Quantization};
+
+    assert_eq!("q4_k_m".parse::<Quantization>().unwrap(), Quantization::Q4);
+    assert_eq!("FP16".parse::<Quantization>().unwrap(), Quantization::FP16);
+    assert!("q9".parse::<Quantization>().is_err());
+
+    // 8B parameters at 16 bits per weight
+    let fp16 = ModelFitPlanner::from_billions(8.0);
+    assert!((fp16.weights_gb() - 16.0).abs() < 0.01);
+    let q4 = ModelFitPlanner::from_billions(8.0).quantization(Quantization::Q4);
+    assert!(q4.memory_required_gb() < fp16.memory_required_gb());
+    let long_context = ModelFitPlanner::from_billions(8.0).context_length(32768);
+    assert!(long_context.kv_cache_gb() > fp16.kv_cache_gb());
+
+    let hw_info = HardwareInfo::query().unwrap();
+    let fit = q4.plan_for(&hw_info);
+    assert_eq!(fit.quantization(), Quantization::Q4);
+    assert_eq!(fit.is_runnable(), fit.estimated_tokens_per_second().is_some());
+    if fit.fits_in_vram() {
+        assert!(matches!(
+            fit.placement(),
+            ModelPlacement::Gpu | ModelPlacement::MultiGpu | ModelPlacement::UnifiedMemory
+        ));
+    }
+    assert!(!fit.suggested_quantizations().contains(&Quantization::FP32));
+
+    // Far larger than any machine
+    let huge = ModelFitPlanner::from_billions(100_000.0).plan_for(&hw_info);
+    assert_eq!(huge.placement(), ModelPlacement::DoesNotFit);
+    assert!(huge.suggested_quantizations().is_empty());
+}
+
+#[test]
+fn test_gpu_performance_database() {
+    use hardware_query::{GPUPerformanceDatabase, GPUPerformanceTier};
+
+    let db = GPUPerformanceDatabase::embedded();
+    assert!(!db.is_empty());
+
+    // Most specific match wins and trademark markers are ignored
+    let ti = db.lookup("NVIDIA GeForce RTX 4070 Ti").unwrap();
+    assert_eq!(ti.name(), "RTX 4070 Ti");
+    let base = db.lookup("NVIDIA GeForce RTX 4070").unwrap();
+    assert!(ti.score() > base.score());
+    assert_eq!(db.lookup("AMD Radeon RX 7900 XTX").unwrap().name(), "RX 7900 XTX");
+    assert_eq!(db.lookup("Intel(R) Iris(R) Xe Graphics").unwrap().name(), "Iris Xe");
+    assert!(db.lookup("NVIDIA GeForce RTX 40700").is_none());
+
+    // Same VRAM, very different performance
+    let flagship = db.lookup("NVIDIA GeForce RTX 4090").unwrap();
+    let old = db.lookup("NVIDIA GeForce GTX 1070").unwrap();
+    assert_eq!(flagship.tier(), GPUPerformanceTier::Flagship);
+    assert!(old.tier() < flagship.tier());
+    assert!(flagship.raytracing() && !old.raytracing());
+
+    // Updated entries override embedded ones
+    let mut updated = GPUPerformanceDatabase::embedded();
+    let extra = GPUPerformanceDatabase::parse("# newer cards\nRTX 4090 | 101 | rt\nRTX 9090 | 300 | rt\n").unwrap();
+    updated.extend(extra);
+    assert_eq!(updated.len(), db.len() + 1);
+    assert_eq!(updated.lookup("GeForce RTX 4090").unwrap().score(), 101);
+    assert!(GPUPerformanceDatabase::parse("RTX 4090 | fast").is_err());
+
+    let assessment = hardware_query::HardwarePresets::gaming_assessment().unwrap();
+    assert_eq!(
+        assessment.gpu_tier,
+        assessment.gpu_performance_score.map(GPUPerformanceTier::from_score)
+    );
+    assert!(assessment.gaming_score <= 100);
+}
+
+#[test]
+fn test_gpu_specs_database() {
+    use hardware_query::{GPUInfo, GPUSpecsDatabase};
+
+    let db = GPUSpecsDatabase::embedded();
+    assert!(!db.is_empty());
+
+    // RTX 4090: 384-bit GDDR6X at 21 Gbps, 82.6 FP32 / 330 FP16 tensor TFLOPS
+    let rtx4090 = db.lookup("NVIDIA GeForce RTX 4090").unwrap();
+    assert!((rtx4090.memory_bandwidth_gbps() - 1008.0).abs() < 0.1);
+    assert!((rtx4090.fp32_tflops() - 82.6).abs() < 0.1);
+    assert!((rtx4090.fp16_tflops().unwrap() - 330.3).abs() < 0.5);
+    assert!((rtx4090.int8_tops().unwrap() - 660.6).abs() < 1.0);
+    assert_eq!(rtx4090.tensor_core_generation(), Some("4th gen Tensor Cores"));
+    assert_eq!(db.lookup("NVIDIA GeForce RTX 4090 Laptop GPU").unwrap().name, "RTX 4090 Laptop");
+    assert_eq!(db.lookup("NVIDIA A100-SXM4-80GB").unwrap().name, "A100 SXM4 80GB");
+
+    // RDNA 3 dual issue doubles FP32; GTX 16 has no tensor cores
+    assert!((db.lookup("AMD Radeon RX 7900 XTX").unwrap().fp32_tflops() - 61.4).abs() < 0.1);
+    let gtx = db.lookup("NVIDIA GeForce GTX 1660 Ti").unwrap();
+    assert_eq!(gtx.tensor_cores, None);
+    assert_eq!(gtx.tensor_core_generation(), None);
+    assert!((db.lookup("Apple M4 Max").unwrap().memory_bandwidth_gbps() - 546.1).abs() < 0.1);
+
+    // Unknown architectures still get bandwidth and FP32
+    let custom = GPUSpecsDatabase::parse("Future GPU 9000 | nextgen | 1000 | 2000 | 256 | 32 | GDDR8 | 0
+").unwrap();
+    let future = custom.lookup("Future GPU 9000").unwrap();
+    assert!((future.fp32_tflops() - 4.0).abs() < 1e-9);
+    assert_eq!(future.fp16_tflops(), None);
+    assert!(GPUSpecsDatabase::parse("RTX 4090 | ada | many").is_err());
+
+    let gpu: GPUInfo = serde_json::from_value(serde_json::json!({
+        "vendor": "NVIDIA",
+        "model_name": "NVIDIA GeForce RTX 3080",
+        "gpu_type": "Discrete",
+        "memory_mb": 10240,
+        "compute_capabilities": {
+            "cuda": "8.6",
+            "rocm": false,
+            "directml": false,
+            "opencl": false,
+            "vulkan": false,
+            "metal": false,
+        },
+    }))
+    .unwrap();
+    assert_eq!(gpu.specs().unwrap().memory_type, "GDDR6X");
+    assert_eq!(gpu.tensor_core_generation(), Some("3rd gen Tensor Cores"));
+    assert!(gpu.fp16_tflops().unwrap() > gpu.fp32_tflops().unwrap());
+
+    // Detection fills in what the drivers left out
+    for gpu in HardwareInfo::query().unwrap().gpus() {
+        if gpu.specs().is_some() {
+            assert!(gpu.memory_bandwidth.is_some());
+            assert!(gpu.shader_units.is_some());
+        }
+    }
+}
+
+#[test]
+fn test_physical_disks_and_volumes() {
+    let hw_info = HardwareInfo::query().unwrap();
+
+    for disk in hw_info.physical_disks() {
+        assert!(!disk.device().is_empty());
+        assert!(disk.capacity_gb() >= 0.0);
+        assert_eq!(disk.is_failing(), disk.smart_health().is_some_and(|s| s.is_degraded()));
+        for volume in hw_info.volumes_on(disk) {
+            assert!(disk.volumes().iter().any(|mount| mount == volume.mount_point()));
+        }
+    }
+
+    for volume in hw_info.volumes() {
+        assert!(volume.available_gb() <= volume.total_gb() + 0.01);
+        assert!((0.0..=100.0).contains(&volume.usage_percent()));
+        if let Some(disk) = hw_info.disk_for(volume) {
+            assert_eq!(Some(disk.device()), volume.disk());
+            assert!(disk.volumes().iter().any(|mount| mount == volume.mount_point()));
+        }
+    }
+
+    // The per-volume StorageInfo view stays in step with the volumes
+    assert_eq!(hw_info.volumes().len(), hw_info.storage_devices().len());
+}
+
+#[test]
+fn test_storage_performance_class() {
+    use hardware_query::{PhysicalDisk, StoragePerformanceClass, SystemOverview};
+
+    let disk = |bus: &str, storage_type: &str, extra: serde_json::Value| -> PhysicalDisk {
+        let mut value = serde_json::json!({
+            "device": "/dev/test",
+            "model": "Test Disk",
+            "serial_number": null,
+            "firmware_version": null,
+            "bus": bus,
+            "storage_type": storage_type,
+            "capacity_bytes": 1_000_000_000_000u64,
+            "removable": false,
+            "smart": null,
+            "volumes": [],
+        });
+        value.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(value).unwrap()
+    };
+    let nvme_link = |generation: u8| {
+        serde_json::json!({
+            "nvme": {
+                "controller": "nvme0",
+                "model": "Test NVMe",
+                "serial_number": null,
+                "firmware_version": null,
+                "pcie_link": { "generation": generation, "width": 4, "max_generation": null, "max_width": null },
+                "max_namespaces": null,
+                "namespaces": [],
+                "composite_temperature_celsius": null,
+            }
+        })
+    };
+
+    let class = |disk: PhysicalDisk| disk.performance_class();
+    assert_eq!(class(disk("NVMe", "NVMe", nvme_link(4))), StoragePerformanceClass::NVMeGen4);
+    assert_eq!(class(disk("NVMe", "NVMe", nvme_link(3))), StoragePerformanceClass::NVMeGen3);
+    assert_eq!(class(disk("NVMe", "NVMe", nvme_link(5))), StoragePerformanceClass::NVMeGen5);
+    assert_eq!(class(disk("NVMe", "NVMe", serde_json::json!({}))), StoragePerformanceClass::NVMe);
+    assert_eq!(class(disk("SATA", "SSD", serde_json::json!({}))), StoragePerformanceClass::SataSSD);
+    assert_eq!(
+        class(disk("SATA", "HDD", serde_json::json!({ "rotation_rate_rpm": 7200 }))),
+        StoragePerformanceClass::HDD7200
+    );
+    assert_eq!(
+        class(disk("SATA", "HDD", serde_json::json!({ "rotation_rate_rpm": 5400 }))),
+        StoragePerformanceClass::HDD5400
+    );
+    assert_eq!(
+        class(disk("SAS", "HDD", serde_json::json!({ "rotation_rate_rpm": 15000 }))),
+        StoragePerformanceClass::HDD10K
+    );
+    assert_eq!(class(disk("SATA", "HDD", serde_json::json!({}))), StoragePerformanceClass::HDD);
+    assert_eq!(class(disk("MMC", "SD", serde_json::json!({}))), StoragePerformanceClass::SDCard);
+    assert_eq!(class(disk("MMC", "EMmc", serde_json::json!({}))), StoragePerformanceClass::EMmc);
+    assert_eq!(class(disk("Virtual", "SSD", serde_json::json!({}))), StoragePerformanceClass::Virtual);
+
+    assert!(StoragePerformanceClass::NVMeGen4.is_nvme() && StoragePerformanceClass::NVMeGen4.is_ssd());
+    assert!(StoragePerformanceClass::SataSSD.is_ssd() && !StoragePerformanceClass::SataSSD.is_nvme());
+    assert!(StoragePerformanceClass::HDD7200.is_rotational());
+    assert!(!StoragePerformanceClass::SDCard.is_ssd());
+    assert!(
+        StoragePerformanceClass::NVMeGen4.typical_read_mb_s() > StoragePerformanceClass::SataSSD.typical_read_mb_s()
+    );
+    assert_eq!(StoragePerformanceClass::HDD7200.to_string(), "HDD (7200 RPM)");
+
+    // The overview carries the primary drive's class
+    let overview = SystemOverview::quick().unwrap();
+    println!("Primary drive: {} ({})", overview.storage.drive_type, overview.storage.performance_class);
+    let json = serde_json::to_value(&overview.storage).unwrap();
+    assert!(json.get("performance_class").is_some());
+}
+
+#[test]
+fn test_storage_device_descriptor() {
+    use hardware_query::{BusType, PhysicalDisk, StorageType};
+
+    // STORAGE_DEVICE_DESCRIPTOR header followed by its NUL-terminated strings
+    let descriptor = |removable: bool, bus_type: u32| {
+        let mut bytes = vec![0u8; 40];
+        bytes[10] = removable as u8;
+        let mut put_string = |field: usize, text: &str| {
+            let offset = bytes.len() as u32;
+            bytes[field..field + 4].copy_from_slice(&offset.to_le_bytes());
+            bytes.extend_from_slice(text.as_bytes());
+            bytes.push(0);
+        };
+        put_string(12, "ATA     ");
+        put_string(16, "Samsung SSD 870 EVO 1TB");
+        put_string(20, "SVT02B6Q");
+        put_string(24, "  S6PUNX0T123456  ");
+        bytes[28..32].copy_from_slice(&bus_type.to_le_bytes());
+        bytes
+    };
+
+    let disk = PhysicalDisk::from_device_descriptor("PhysicalDrive0", &descriptor(false, 11)).unwrap();
+    assert_eq!(disk.device(), "PhysicalDrive0");
+    assert_eq!(disk.bus(), BusType::SATA);
+    assert_eq!(disk.model(), "Samsung SSD 870 EVO 1TB");
+    assert_eq!(disk.serial_number(), Some("S6PUNX0T123456"));
+    assert_eq!(disk.firmware_version(), Some("SVT02B6Q"));
+    assert!(!disk.removable);
+    assert!(!disk.is_ejectable());
+    // SATA leaves SSD vs HDD to the seek penalty query
+    assert_eq!(*disk.drive_type(), StorageType::Unknown);
+    assert_eq!(disk.trim_supported(), None);
+
+    let nvme = PhysicalDisk::from_device_descriptor("PhysicalDrive1", &descriptor(false, 17)).unwrap();
+    assert_eq!(nvme.bus(), BusType::NVMe);
+    assert_eq!(*nvme.drive_type(), StorageType::NVMe);
+
+    let card = PhysicalDisk::from_device_descriptor("PhysicalDrive2", &descriptor(true, 12)).unwrap();
+    assert_eq!(card.bus(), BusType::MMC);
+    assert_eq!(*card.drive_type(), StorageType::SD);
+    assert!(card.removable);
+    assert!(card.is_ejectable());
+
+    assert!(PhysicalDisk::from_device_descriptor("PhysicalDrive3", &[0u8; 16]).is_none());
+}
+
+#[test]
+fn test_sd_card_readers() {
+    use hardware_query::{SdCardReader, SdCardType, SdSpeedClass};
+
+    for reader in SdCardReader::query_all().unwrap() {
+        assert!(!reader.id.is_empty());
+        if let Some(card) = &reader.card {
+            assert_eq!(card.card_type, SdCardType::from_capacity(card.capacity_bytes));
+        }
+    }
+
+    assert_eq!(SdCardType::from_capacity(2_000_000_000), SdCardType::SDSC);
+    assert_eq!(SdCardType::from_capacity(31_914_983_424), SdCardType::SDHC);
+    assert_eq!(SdCardType::from_capacity(127_865_454_592), SdCardType::SDXC);
+    assert_eq!(SdCardType::from_capacity(4_000_000_000_000), SdCardType::SDUC);
+
+    // SD Status register: Class 10 (byte 8), U3 (upper nibble of byte 14), V30 (byte 15)
+    let mut status = [0u8; 64];
+    status[8] = 4;
+    status[14] = 0x30;
+    status[15] = 30;
+    let speed = SdSpeedClass::from_sd_status(&status).unwrap();
+    assert_eq!((speed.class, speed.uhs_grade, speed.video_class), (Some(10), Some(3), Some(30)));
+    assert_eq!(speed.min_write_mb_s(), 30);
+    assert_eq!(speed.to_string(), "C10 U3 V30");
+
+    assert!(SdSpeedClass::from_sd_status(&[0u8; 64]).is_none());
+    assert!(SdSpeedClass::from_sd_status(&status[..8]).is_none());
+}
+
+#[test]
+#[cfg(feature = "monitoring")]
+fn test_removable_media_device_class() {
+    use hardware_query::DeviceClass;
+
+    assert_eq!(DeviceClass::RemovableMedia.to_string(), "Removable media");
+}
+
+#[test]
+#[cfg(feature = "monitoring")]
+fn test_hotplug_uevent_parsing() {
+    use hardware_query::{DeviceChange, DeviceClass, HardwareChangeType};
+
+    let uevent = |fields: &[&str]| fields.join("\0").into_bytes();
+    let no_media = |_: &str| false;
+
+    let usb = uevent(&[
+        "add@/devices/pci0000:00/0000:00:14.0/usb1/1-2",
+        "ACTION=add",
+        "DEVPATH=/devices/pci0000:00/0000:00:14.0/usb1/1-2",
+        "SUBSYSTEM=usb",
+        "DEVNAME=bus/usb/001/005",
+        "DEVTYPE=usb_device",
+        "PRODUCT=18d1/9302/100",
+    ]);
+    let change = DeviceChange::from_uevent(&usb, no_media).unwrap();
+    assert_eq!(change.change_type, HardwareChangeType::DeviceConnected);
+    assert_eq!(change.device_class, DeviceClass::USB);
+    assert_eq!(change.description, "bus/usb/001/005");
+    assert_eq!(change.summary(), "USB device connected: bus/usb/001/005");
+
+    // Interfaces of a USB device are not devices of their own
+    let interface = uevent(&["ACTION=add", "SUBSYSTEM=usb", "DEVTYPE=usb_interface"]);
+    assert!(DeviceChange::from_uevent(&interface, no_media).is_none());
+
+    let nvme = uevent(&[
+        "remove@/devices/pci0000:00/0000:00:1d.0/0000:3d:00.0/nvme/nvme1/nvme1n1",
+        "ACTION=remove",
+        "DEVPATH=/devices/pci0000:00/0000:00:1d.0/0000:3d:00.0/nvme/nvme1/nvme1n1",
+        "SUBSYSTEM=block",
+        "DEVNAME=nvme1n1",
+        "DEVTYPE=disk",
+    ]);
+    let change = DeviceChange::from_uevent(&nvme, no_media).unwrap();
+    assert_eq!(change.change_type, HardwareChangeType::DeviceDisconnected);
+    assert_eq!(change.device_class, DeviceClass::Storage);
+
+    let gpu = uevent(&["ACTION=add", "SUBSYSTEM=drm", "DEVNAME=dri/card1", "DEVTYPE=drm_minor"]);
+    assert_eq!(DeviceChange::from_uevent(&gpu, no_media).unwrap().device_class, DeviceClass::GPU);
+    let render_node = uevent(&["ACTION=add", "SUBSYSTEM=drm", "DEVNAME=dri/renderD128"]);
+    assert!(DeviceChange::from_uevent(&render_node, no_media).is_none());
+
+    // Card inserted into and ejected from a built-in reader
+    let media_change = uevent(&[
+        "change@/devices/platform/mmc0/mmc0:0001/block/mmcblk0",
+        "ACTION=change",
+        "SUBSYSTEM=block",
+        "DEVNAME=mmcblk0",
+        "DEVTYPE=disk",
+        "DISK_MEDIA_CHANGE=1",
+    ]);
+    let inserted = DeviceChange::from_uevent(&media_change, |name| name == "mmcblk0").unwrap();
+    assert_eq!(inserted.change_type, HardwareChangeType::DeviceConnected);
+    assert_eq!(inserted.device_class, DeviceClass::RemovableMedia);
+    let ejected = DeviceChange::from_uevent(&media_change, no_media).unwrap();
+    assert_eq!(ejected.change_type, HardwareChangeType::DeviceDisconnected);
+
+    // Other change events carry no hotplug information
+    let resize = uevent(&["ACTION=change", "SUBSYSTEM=block", "DEVNAME=sda", "DEVTYPE=disk"]);
+    assert!(DeviceChange::from_uevent(&resize, no_media).is_none());
+    assert!(DeviceChange::from_uevent(b"garbage", no_media).is_none());
+}
+
+#[test]
+#[cfg(feature = "monitoring")]
+fn test_device_snapshot_diff() {
+    use hardware_query::{DeviceClass, DeviceSnapshot, HardwareChangeType};
+
+    let mut before = DeviceSnapshot::default();
+    before.insert("usb:046d:c52b@1-3", DeviceClass::USB, "Unifying Receiver");
+    before.insert("gpu:0000:01:00.0", DeviceClass::GPU, "GeForce RTX 4070");
+    before.insert("storage:Samsung SSD 990 PRO:/", DeviceClass::Storage, "Samsung SSD 990 PRO (/)");
+    before.insert("storage:SD Card:/media/card", DeviceClass::RemovableMedia, "SD Card (/media/card)");
+
+    let mut after = DeviceSnapshot::default();
+    after.insert("usb:18d1:9302@2-1", DeviceClass::USB, "Coral USB Accelerator");
+    after.insert("gpu:0000:01:00.0", DeviceClass::GPU, "GeForce RTX 4070");
+    after.insert("gpu:0000:3d:00.0", DeviceClass::GPU, "Radeon RX 7900 XTX");
+    after.insert("storage:Samsung SSD 990 PRO:/", DeviceClass::Storage, "Samsung SSD 990 PRO (/)");
+    after.insert("storage:USB DISK:/media/usb", DeviceClass::RemovableMedia, "USB DISK (/media/usb)");
+    assert_eq!(after.len(), 5);
+
+    let mut changes: Vec<_> = before
+        .diff(&after)
+        .into_iter()
+        .map(|change| (change.change_type, change.device_class, change.description))
+        .collect();
+    changes.sort_by(|a, b| a.2.cmp(&b.2));
+    assert_eq!(
+        changes,
+        [
+            (HardwareChangeType::DeviceConnected, DeviceClass::USB, "Coral USB Accelerator".to_string()),
+            (HardwareChangeType::DeviceConnected, DeviceClass::GPU, "Radeon RX 7900 XTX".to_string()),
+            (HardwareChangeType::DeviceDisconnected, DeviceClass::RemovableMedia, "SD Card (/media/card)".to_string()),
+            (HardwareChangeType::DeviceConnected, DeviceClass::RemovableMedia, "USB DISK (/media/usb)".to_string()),
+            (HardwareChangeType::DeviceDisconnected, DeviceClass::USB, "Unifying Receiver".to_string()),
+        ]
+    );
+
+    assert!(after.diff(&after).is_empty());
+    assert!(DeviceSnapshot::default().is_empty());
+}
+
+#[test]
+fn test_nvme_smart_critical_warning() {
+    use hardware_query::SmartHealth;
+
+    let mut log = [0u8; 512];
+    log[1..3].copy_from_slice(&350u16.to_le_bytes()); // 76.85 °C
+    log[5] = 12;
+
+    // Temperature over threshold (bit 1) is a warning, not a predicted failure
+    log[0] = 0b10;
+    let hot = SmartHealth::from_nvme_smart_log(&log).unwrap();
+    assert!(hot.temperature_warning);
+    assert!(!hot.predicted_failure);
+    assert_eq!(hot.passed, Some(true));
+    assert!(!hot.is_degraded());
+    assert_eq!(hot.percentage_used, Some(12));
+
+    // Spare (0), reliability (2), read-only (3) and volatile backup (4) predict failure
+    for bit in [0, 2, 3, 4] {
+        log[0] = 1 << bit;
+        let failing = SmartHealth::from_nvme_smart_log(&log).unwrap();
+        assert!(failing.predicted_failure, "bit {bit}");
+        assert!(!failing.temperature_warning);
+        assert_eq!(failing.passed, Some(false));
+    }
+
+    assert!(SmartHealth::from_nvme_smart_log(&log[..256]).is_none());
+}
+
+#[test]
+fn test_nvme_details() {
+    use hardware_query::NVMeInfo;
+
+    for controller in NVMeInfo::query_all().unwrap() {
+        assert!(!controller.controller().is_empty());
+        assert_eq!(controller.namespace_count(), controller.namespaces().len());
+        if let Some(max) = controller.max_namespaces {
+            assert!(controller.namespace_count() <= max as usize || max == 0);
+        }
+        if let Some(link) = controller.pcie_link() {
+            assert!((1..=6).contains(&link.generation));
+            assert!(link.width > 0);
+        }
+        if let Some(temp) = controller.composite_temperature_celsius() {
+            assert!((-40.0..150.0).contains(&temp));
+        }
+        for namespace in controller.namespaces() {
+            assert!(namespace.lba_size_bytes().is_power_of_two());
+        }
+    }
+
+    let hw_info = HardwareInfo::query().unwrap();
+    for disk in hw_info.physical_disks() {
+        if disk.nvme().is_some() {
+            assert_eq!(disk.bus(), hardware_query::BusType::NVMe);
+        }
+    }
+}
+
+#[test]
+fn test_disk_io_rates() {
+    use hardware_query::{DiskIoCounters, DiskIoStats};
+    use std::time::{Duration, Instant};
+
+    let read_at = Instant::now();
+    let before = DiskIoCounters {
+        device: "disk-a".to_string(),
+        read_bytes: 0,
+        write_bytes: 0,
+        reads: 0,
+        writes: 0,
+        in_flight: Some(0),
+        busy_time_ms: Some(0),
+        weighted_time_ms: Some(0),
+        read_at,
+    };
+    let after = DiskIoCounters {
+        read_bytes: 200 * 1024 * 1024,
+        write_bytes: 100 * 1024 * 1024,
+        reads: 400,
+        writes: 200,
+        busy_time_ms: Some(1900),
+        weighted_time_ms: Some(8000),
+        read_at: read_at + Duration::from_secs(2),
+        ..before.clone()
+    };
+
+    let stats = DiskIoStats::between(&[before], &[after]);
+    assert_eq!(stats.len(), 1);
+    let disk = &stats[0];
+    assert!((disk.throughput_mb_s() - 150.0).abs() < 1e-6);
+    assert!((disk.total_iops() - 300.0).abs() < 1e-6);
+    assert!((disk.queue_depth - 4.0).abs() < 1e-6);
+    assert_eq!(disk.utilization_percent, Some(95.0));
+    assert!(disk.is_saturated(90.0));
+
+    // Live counters are cumulative, so a later reading never yields negative rates
+    if let Ok(live) = DiskIoStats::sample(Duration::from_millis(100)) {
+        for disk in live {
+            assert!(disk.read_bytes_per_sec >= 0.0 && disk.write_iops >= 0.0);
+            assert!(disk.utilization_percent.is_none_or(|u| (0.0..=100.0).contains(&u)));
+        }
+    }
+}
+
+#[test]
+fn test_energy_meter() {
+    use hardware_query::{EnergyCounter, EnergyDomain, EnergyMeter, EnergyReading, EnergySample};
+    use std::time::{Duration, Instant};
+
+    let counter = |domain, name: &str, joules| EnergyCounter {
+        domain,
+        name: name.to_string(),
+        joules,
+        wrap_joules: Some(262_143.0),
+    };
+    let read_at = Instant::now();
+    let before = EnergySample {
+        counters: vec![
+            counter(EnergyDomain::Package, "package-0", 1000.0),
+            counter(EnergyDomain::Cores, "core-0", 500.0),
+            counter(EnergyDomain::Dram, "dram-0", 262_100.0),
+        ],
+        read_at,
+    };
+    let after = EnergySample {
+        counters: vec![
+            counter(EnergyDomain::Package, "package-0", 1100.0),
+            counter(EnergyDomain::Cores, "core-0", 580.0),
+            // Wrapped around since the first sample
+            counter(EnergyDomain::Dram, "dram-0", 7.0),
+            counter(EnergyDomain::Gpu, "0000:03:00.0", 50.0),
+        ],
+        read_at: read_at + Duration::from_secs(10),
+    };
+
+    let reading = EnergyReading::between(&before, &after);
+    assert_eq!(reading.domains.len(), 3);
+    assert!((reading.joules(EnergyDomain::Package) - 100.0).abs() < 1e-9);
+    assert!((reading.joules(EnergyDomain::Dram) - 50.0).abs() < 1e-9);
+    assert!((reading.watts(EnergyDomain::Package) - 10.0).abs() < 1e-9);
+    // Cores are part of the package
+    assert!((reading.total_joules() - 150.0).abs() < 1e-9);
+    assert!((reading.average_watts() - 15.0).abs() < 1e-9);
+    assert_eq!(after.energy_since(&before), reading);
+
+    // Counters need root on most systems
+    if let Ok(meter) = EnergyMeter::new() {
+        let reading = meter.measure(Duration::from_millis(100)).unwrap();
+        assert!(reading.domains.iter().all(|energy| energy.joules >= 0.0));
+    }
+}
+
+#[test]
+fn test_throttle_events() {
+    use hardware_query::{HardwareComponent, ThrottleCause, ThrottleEvent, ThrottleStatus};
+
+    let status = |device: &str, causes: Vec<ThrottleCause>, events: Option<u64>| ThrottleStatus {
+        component: HardwareComponent::CPU,
+        device: device.to_string(),
+        active_causes: causes,
+        thermal_events: events,
+        throttled_time_ms: None,
+    };
+    let before = vec![
+        status("package0", vec![], Some(10)),
+        status("package1", vec![ThrottleCause::PowerLimit], Some(4)),
+    ];
+    let after = vec![
+        status("package0", vec![], Some(13)),
+        status("package1", vec![ThrottleCause::PowerLimit, ThrottleCause::CurrentLimit], Some(4)),
+        status("package2", vec![ThrottleCause::Thermal], Some(1)),
+    ];
+
+    let events = ThrottleEvent::between(&before, &after);
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].device, "package0");
+    assert_eq!(events[0].causes, vec![ThrottleCause::Thermal]);
+    assert_eq!(events[0].new_events, 3);
+    // The power limit was already active; only the new cause is reported
+    assert_eq!(events[1].causes, vec![ThrottleCause::CurrentLimit]);
+    assert!(ThrottleEvent::between(&after, &after).is_empty());
+
+    for status in ThrottleStatus::query_all() {
+        assert!(matches!(status.component, HardwareComponent::CPU | HardwareComponent::GPU));
+        assert!(!status.device.is_empty());
+    }
+}
+
+#[test]
+fn test_throttle_reason_decoding() {
+    use hardware_query::{HardwareComponent, ThrottleCause, ThrottleStatus};
+
+    // PROCHOT + PL1, with the matching log bits (16+) set
+    assert_eq!(
+        ThrottleCause::from_perf_limit_reasons(0x0401_0401),
+        vec![ThrottleCause::Thermal, ThrottleCause::PowerLimit]
+    );
+    // Thermal + running average thermal + VR TDC, deduplicated and sorted
+    assert_eq!(
+        ThrottleCause::from_perf_limit_reasons(0xA2),
+        vec![ThrottleCause::Thermal, ThrottleCause::CurrentLimit]
+    );
+    assert!(ThrottleCause::from_perf_limit_reasons(0).is_empty());
+    assert!(ThrottleCause::from_perf_limit_reasons(0xFFFF_0000).is_empty());
+
+    #[cfg(feature = "nvidia")]
+    {
+        // SW power cap + HW thermal slowdown
+        assert_eq!(
+            ThrottleCause::from_nvml_throttle_reasons(0x44),
+            vec![ThrottleCause::Thermal, ThrottleCause::PowerLimit]
+        );
+        // HW slowdown alone is over-current protection
+        assert_eq!(ThrottleCause::from_nvml_throttle_reasons(0x08), vec![ThrottleCause::CurrentLimit]);
+        assert_eq!(ThrottleCause::from_nvml_throttle_reasons(0x48), vec![ThrottleCause::Thermal]);
+        // GPU idle and application clocks are not throttling
+        assert!(ThrottleCause::from_nvml_throttle_reasons(0x03).is_empty());
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let write = |path: std::path::PathBuf, contents: &str| {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, format!("{contents}\n")).unwrap();
+    };
+
+    // i915: card/gt/gt<n>/throttle_reason_<name>
+    let i915 = dir.path().join("card0");
+    write(i915.join("gt/gt0/throttle_reason_pl1"), "1");
+    write(i915.join("gt/gt0/throttle_reason_thermal"), "0");
+    write(i915.join("gt/gt1/throttle_reason_prochot"), "1");
+    write(i915.join("gt/gt1/throttle_reason_pl4"), "0");
+    let status = ThrottleStatus::from_drm_card(&i915).unwrap();
+    assert_eq!(status.component, HardwareComponent::GPU);
+    assert_eq!(status.active_causes, vec![ThrottleCause::Thermal, ThrottleCause::PowerLimit]);
+
+    // xe: device/tile<n>/gt<n>/freq0/throttle/reason_<name>, named by PCI address
+    let pci = dir.path().join("0000:03:00.0");
+    write(pci.join("tile0/gt0/freq0/throttle/reason_vr_tdc"), "1");
+    write(pci.join("tile0/gt0/freq0/throttle/reason_pl2"), "0");
+    let xe = dir.path().join("card1");
+    std::fs::create_dir_all(&xe).unwrap();
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&pci, xe.join("device")).unwrap();
+        let status = ThrottleStatus::from_drm_card(&xe).unwrap();
+        assert_eq!(status.device, "0000:03:00.0");
+        assert_eq!(status.active_causes, vec![ThrottleCause::CurrentLimit]);
+        assert!(status.is_throttling());
+    }
+
+    // A card without throttle attributes reports nothing
+    let simple = dir.path().join("card2");
+    std::fs::create_dir_all(&simple).unwrap();
+    assert!(ThrottleStatus::from_drm_card(&simple).is_none());
+}
+
+#[test]
+fn test_network_io_rates() {
+    use hardware_query::{NetworkInfo, NetworkIoStats};
+    use std::time::Duration;
+
+    let reading = |rx: u64, tx: u64, errors: u64, is_up: bool| -> NetworkInfo {
+        serde_json::from_value(serde_json::json!({
+            "name": "eth0",
+            "network_type": "Ethernet",
+            "mac_address": "00:11:22:33:44:55",
+            "ip_addresses": [],
+            "speed_mbps": 1000,
+            "is_up": is_up,
+            "bytes_received": rx,
+            "bytes_transmitted": tx,
+            "packets_received": rx / 1000,
+            "packets_transmitted": tx / 1000,
+            "receive_errors": errors,
+            "transmit_errors": 0,
+        }))
+        .unwrap()
+    };
+
+    let before = [reading(0, 0, 1, true)];
+    let after = [reading(25_000_000, 5_000_000, 4, false)];
+    let stats = NetworkIoStats::between(&before, &after, Duration::from_secs(2));
+    assert_eq!(stats.len(), 1);
+    let eth0 = &stats[0];
+    assert_eq!(eth0.interface(), "eth0");
+    assert!(!eth0.is_up);
+    assert!((eth0.rx_mbps() - 100.0).abs() < 1e-6);
+    assert!((eth0.tx_mbps() - 20.0).abs() < 1e-6);
+    assert!((eth0.rx_packets_per_sec - 12_500.0).abs() < 1e-6);
+    assert_eq!(eth0.rx_errors, 3);
+    assert_eq!(eth0.link_utilization_percent(1000), Some(10.0));
+    assert_eq!(eth0.link_utilization_percent(0), None);
+
+    // A zero-length interval yields no rates
+    assert!(NetworkIoStats::between(&before, &after, Duration::ZERO).is_empty());
+}
+
+#[test]
+fn test_gpu_api_probing() {
+    use hardware_query::{GPUInfo, VulkanDeviceInfo};
+
+    let device: VulkanDeviceInfo = serde_json::from_value(serde_json::json!({
+        "name": "NVIDIA GeForce RTX 4070",
+        "api_version": "1.3.277",
+        "vendor_id": 0x10de,
+        "device_id": 0x2786,
+        "device_type": "Discrete",
+        "max_compute_workgroup_invocations": 1024,
+        "max_compute_workgroup_size": [1024, 1024, 64],
+        "extensions": ["VK_KHR_ray_query", "VK_KHR_swapchain"],
+    }))
+    .unwrap();
+    assert_eq!(device.api_version(), "1.3.277");
+    assert!(device.supports_extension("VK_KHR_ray_query"));
+    assert!(!device.supports_extension("VK_NV_ray_tracing"));
+
+    // Probed details, when present, agree with the summary flags
+    for gpu in GPUInfo::query_all().unwrap() {
+        if gpu.vulkan_device().is_some() {
+            assert!(gpu.supports_vulkan());
+        }
+        if let Some(opencl) = gpu.opencl_device() {
+            assert!(gpu.supports_opencl());
+            assert!(opencl.version().starts_with("OpenCL"));
+        }
+        if gpu.metal_device().is_some() {
+            assert!(gpu.supports_metal());
+        }
+    }
+
+    #[cfg(feature = "vulkan")]
+    for device in VulkanDeviceInfo::query_all().unwrap_or_default() {
+        assert!(device.max_compute_workgroup_invocations > 0);
+    }
+    #[cfg(feature = "opencl")]
+    for device in hardware_query::OpenCLDeviceInfo::query_all().unwrap_or_default() {
+        assert!(device.max_workgroup_size > 0);
+    }
+}
+
+#[test]
+fn test_directx_capabilities() {
+    use hardware_query::{DirectXCapabilities, GPUInfo};
+
+    let ultimate = DirectXCapabilities {
+        feature_level: "12_2".to_string(),
+        shader_model: Some("6.7".to_string()),
+        raytracing_tier: Some("1.1".to_string()),
+        mesh_shader_tier: 1,
+        variable_rate_shading_tier: 2,
+        sampler_feedback_tier: Some("1.0".to_string()),
+        resource_binding_tier: 3,
+    };
+    assert!(ultimate.is_dx12_ultimate());
+    assert!(ultimate.supports_raytracing() && ultimate.supports_mesh_shaders());
+
+    // Older drivers report 12_1 but every Ultimate tier
+    let older_driver = DirectXCapabilities {
+        feature_level: "12_1".to_string(),
+        ..ultimate.clone()
+    };
+    assert!(older_driver.is_dx12_ultimate());
+
+    let pascal = DirectXCapabilities {
+        feature_level: "12_1".to_string(),
+        shader_model: Some("6.6".to_string()),
+        raytracing_tier: None,
+        mesh_shader_tier: 0,
+        variable_rate_shading_tier: 0,
+        sampler_feedback_tier: None,
+        resource_binding_tier: 3,
+    };
+    assert!(!pascal.is_dx12_ultimate());
+    assert!(!pascal.supports_variable_rate_shading());
+
+    for gpu in GPUInfo::query_all().unwrap() {
+        if let Some(directx) = gpu.directx() {
+            assert!(directx.feature_level() >= "11_0");
+        }
+        if !cfg!(all(feature = "directx", target_os = "windows")) {
+            assert!(gpu.directx().is_none());
+        }
+    }
+}
+
+#[test]
+fn test_intel_gpu_capabilities() {
+    use hardware_query::{ComputeCapabilities, GPUInfo, GPUType, GPUVendor};
+
+    // Snapshots from before Level Zero detection still load
+    let capabilities: ComputeCapabilities = serde_json::from_value(serde_json::json!({
+        "cuda": null,
+        "rocm": false,
+        "directml": false,
+        "opencl": true,
+        "vulkan": true,
+        "metal": false,
+        "compute_units": null,
+        "max_workgroup_size": null,
+    }))
+    .unwrap();
+    assert!(!capabilities.level_zero && !capabilities.oneapi);
+
+    for gpu in GPUInfo::query_all().unwrap() {
+        if gpu.supports_level_zero() || gpu.supports_oneapi() {
+            assert_eq!(gpu.vendor(), &GPUVendor::Intel);
+        }
+        if gpu.vendor() == &GPUVendor::Intel && gpu.gpu_type() != &GPUType::Integrated {
+            assert!(!gpu.has_unified_memory());
+            assert!(gpu.memory_mb() > 0);
+        }
+    }
+}
+
+#[cfg(feature = "bluetooth")]
+#[test]
+fn test_bluetooth_info() {
+    use hardware_query::{BluetoothAdapter, BluetoothDevice, BluetoothInfo};
+
+    let info = BluetoothInfo {
+        adapters: vec![BluetoothAdapter {
+            id: "hci0".to_string(),
+            name: "laptop".to_string(),
+            address: Some("5C:F3:70:8B:3B:AB".to_string()),
+            version: Some("5.3".to_string()),
+            manufacturer: Some("Intel".to_string()),
+            powered: Some(true),
+            low_energy: Some(true),
+        }],
+        devices: vec![
+            BluetoothDevice {
+                name: "Headphones".to_string(),
+                address: Some("00:1A:7D:DA:71:13".to_string()),
+                device_type: Some("audio-headset".to_string()),
+                paired: true,
+                connected: Some(true),
+                low_energy: Some(false),
+                battery_percent: Some(80),
+                adapter: Some("hci0".to_string()),
+            },
+            BluetoothDevice {
+                name: "Keyboard".to_string(),
+                address: None,
+                device_type: None,
+                paired: true,
+                connected: None,
+                low_energy: None,
+                battery_percent: None,
+                adapter: None,
+            },
+        ],
+    };
+    assert!(info.is_available());
+    assert!(info.supports_low_energy());
+    assert_eq!(info.connected_devices().map(|d| d.name()).collect::<Vec<_>>(), ["Headphones"]);
+
+    let json = serde_json::to_string(&info).unwrap();
+    assert_eq!(serde_json::from_str::<BluetoothInfo>(&json).unwrap(), info);
+
+    // Adapter-only queries never list devices
+    if let Ok(live) = BluetoothInfo::query() {
+        assert!(live.devices().is_empty());
+    }
+    if let Ok(live) = BluetoothInfo::query_with_devices() {
+        for device in live.devices() {
+            assert!(device.paired || device.is_connected());
+        }
+    }
+}
+
+#[test]
+fn test_hardware_diff() {
+    use hardware_query::{ChangeKind, HardwareComponent, HardwareDiff, USBDevice};
+
+    let before = HardwareInfo::query().unwrap();
+    assert!(before.diff(&before.clone()).is_empty());
+
+    let mut after = before.clone();
+    after.memory.total_mb += 16 * 1024;
+    after.system.firmware.version = Some("2.0.1".to_string());
+    let stick: USBDevice = serde_json::from_value(serde_json::json!({
+        "vendor_id": "0781",
+        "product_id": "5581",
+        "vendor_name": "SanDisk",
+        "product_name": "Ultra",
+        "device_class": "Mass Storage",
+        "usb_version": "3.0",
+        "serial_number": "4C530001",
+        "bus_number": 2,
+        "device_address": 3,
+        "port_path": "2-1",
+        "driver": "usb-storage",
+        "connected": true,
+        "id": "2-1",
+        "parent_id": null,
+    }))
+    .unwrap();
+    after.usb_devices.push(stick);
+
+    let diff = before.diff(&after);
+    assert!(diff.of_kind(ChangeKind::Removed).next().is_none());
+
+    let memory: Vec<_> = diff.for_component(HardwareComponent::Memory).collect();
+    assert_eq!(memory.len(), 1);
+    assert_eq!(memory[0].field.as_deref(), Some("total"));
+    assert_eq!(memory[0].kind, ChangeKind::Modified);
+
+    let bios = diff
+        .for_component(HardwareComponent::System)
+        .find(|change| change.item == "BIOS")
+        .unwrap();
+    assert_eq!(bios.after.as_deref(), Some("2.0.1"));
+
+    let added: Vec<_> = diff.of_kind(ChangeKind::Added).collect();
+    assert_eq!(added.len(), 1);
+    assert_eq!(added[0].item, "SanDisk Ultra");
+
+    // Swapping the snapshots reverses the report
+    let reverse = after.diff(&before);
+    assert_eq!(reverse.of_kind(ChangeKind::Removed).count(), 1);
+
+    let json = serde_json::to_string(&diff).unwrap();
+    assert_eq!(serde_json::from_str::<HardwareDiff>(&json).unwrap(), diff);
+
+    let report = diff.to_string();
+    assert!(report.starts_with("3 hardware change(s):"));
+    assert!(report.contains("+ Devices: SanDisk Ultra"));
+}
+
+#[test]
+fn test_snapshot_store() {
+    use hardware_query::SnapshotStore;
+
+    fn exercise(store: &SnapshotStore) {
+        assert!(store.is_empty().unwrap());
+        assert!(store.latest().unwrap().is_none());
+
+        let first = HardwareInfo::query().unwrap();
+        let mut second = first.clone();
+        second.timestamp += 3600;
+        second.system.firmware.version = Some("9.9.9".to_string());
+        let mut third = second.clone();
+        third.timestamp += 3600;
+
+        // Saved out of order, listed oldest first
+        for info in [&third, &first, &second] {
+            store.save(info).unwrap();
+        }
+        assert_eq!(store.list().unwrap(), [first.timestamp, second.timestamp, third.timestamp]);
+        assert_eq!(store.latest().unwrap().unwrap().timestamp, third.timestamp);
+        assert_eq!(store.load(first.timestamp).unwrap().memory.total_mb, first.memory.total_mb);
+        assert!(store.load(first.timestamp - 1).is_err());
+
+        let diff = store.diff(first.timestamp, second.timestamp).unwrap();
+        assert!(diff.changes().iter().any(|change| change.item == "BIOS"));
+        // The unchanged third snapshot is left out of the history
+        let history = store.history().unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].after_timestamp, second.timestamp);
+
+        assert_eq!(store.prune_before(second.timestamp).unwrap(), 1);
+        assert_eq!(store.prune(1).unwrap(), 1);
+        assert_eq!(store.list().unwrap(), [third.timestamp]);
+        assert!(store.remove(third.timestamp).unwrap());
+        assert!(!store.remove(third.timestamp).unwrap());
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    exercise(&SnapshotStore::open_dir(dir.path().join("snapshots")).unwrap());
+    #[cfg(feature = "sqlite")]
+    exercise(&SnapshotStore::open_sqlite(dir.path().join("snapshots.db")).unwrap());
+}
+
+#[test]
+fn test_hardware_fingerprint() {
+    use hardware_query::{Fingerprint, FingerprintOptions, FingerprintSource};
+
+    let mut info = HardwareInfo::query().unwrap();
+    info.cpu.brand = "AMD Ryzen 9 7950X 16-Core Processor".to_string();
+    info.system.baseboard.serial_number = Some("BSN12345".to_string());
+    info.system.uuid = Some("4c4c4544-0042-3510-8052-b4c04f4d3233".to_string());
+
+    let options = FingerprintOptions::new();
+    let fingerprint = info.fingerprint(&options).unwrap();
+    assert_eq!(fingerprint.id().len(), 64);
+    assert!(fingerprint.sources().any(|source| source == FingerprintSource::BoardSerial));
+
+    // Live readings do not affect the ID
+    let mut later = info.clone();
+    later.timestamp += 86_400;
+    later.memory.available_mb /= 2;
+    assert_eq!(later.fingerprint(&options).unwrap(), fingerprint);
+
+    // Salting and source selection give unrelated IDs
+    let salted = info.fingerprint(&options.clone().with_salt("my-app")).unwrap();
+    assert_ne!(salted.id(), fingerprint.id());
+    assert_eq!(salted.matching_components(&fingerprint), 0);
+    let without_board = options.clone().without_source(FingerprintSource::BoardSerial);
+    assert_ne!(info.fingerprint(&without_board).unwrap().id(), fingerprint.id());
+
+    // A new motherboard changes the ID but leaves the other components intact
+    let mut new_board = info.clone();
+    new_board.system.baseboard.serial_number = Some("BSN99999".to_string());
+    let replaced = new_board.fingerprint(&options).unwrap();
+    assert_ne!(replaced.id(), fingerprint.id());
+    assert_eq!(replaced.matching_components(&fingerprint), fingerprint.components.len() - 1);
+
+    // Serial formatting differences are normalized away
+    let mut reformatted = info.clone();
+    reformatted.system.baseboard.serial_number = Some(" bsn12345 ".to_string());
+    assert_eq!(reformatted.fingerprint(&options).unwrap().id(), fingerprint.id());
+
+    let json = serde_json::to_string(&fingerprint).unwrap();
+    assert_eq!(serde_json::from_str::<Fingerprint>(&json).unwrap(), fingerprint);
+
+    let mut anonymous = info.clone();
+    anonymous.system.baseboard.serial_number = None;
+    assert!(anonymous.fingerprint(&FingerprintOptions::only([FingerprintSource::BoardSerial])).is_err());
+}
+
+#[test]
+fn test_redacted_output() {
+    use hardware_query::{RedactionMode, RedactionOptions, USBDevice};
+
+    let mut info = HardwareInfo::query().unwrap();
+    info.system.serial_number = Some("5CG1234XYZ".to_string());
+    info.system.uuid = Some("4c4c4544-0042-3510-8052-b4c04f4d3233".to_string());
+    info.system.baseboard.serial_number = Some("BSN12345".to_string());
+    let usb: USBDevice = serde_json::from_value(serde_json::json!({
+        "vendor_id": "0781",
+        "product_id": "5581",
+        "vendor_name": "SanDisk",
+        "product_name": "Ultra",
+        "device_class": "Mass Storage",
+        "usb_version": "3.0",
+        "serial_number": "4C530001",
+        "bus_number": 2,
+        "device_address": 3,
+        "port_path": null,
+        "driver": null,
+        "connected": true,
+        "id": "USB\\VID_0781&PID_5581\\4C530001",
+        "parent_id": null,
+    }))
+    .unwrap();
+    info.usb_devices.push(usb);
+    for volume in &mut info.volumes {
+        volume.mount_point = "/run/media/alice/BACKUP".to_string();
+    }
+
+    let redacted = info.redacted();
+    let json = redacted.to_json().unwrap();
+    for secret in ["5CG1234XYZ", "4c4c4544", "BSN12345", "4C530001", "alice"] {
+        assert!(!json.contains(secret), "{secret} leaked");
+    }
+    for interface in redacted.network_interfaces() {
+        assert!(interface.ip_addresses.iter().all(|ip| ip == "[redacted]"));
+    }
+    for volume in &redacted.volumes {
+        assert_eq!(volume.mount_point, "/run/media/[redacted]/BACKUP");
+    }
+
+    // Technical data is untouched
+    assert_eq!(redacted.cpu.brand, info.cpu.brand);
+    assert_eq!(redacted.memory.total_mb, info.memory.total_mb);
+    assert_eq!(redacted.gpus.len(), info.gpus.len());
+    let usb = redacted.usb_devices.last().unwrap();
+    assert_eq!(usb.product_id, "5581");
+    assert_eq!(usb.id, "USB\\VID_0781&PID_5581\\[redacted]");
+
+    // Hashing keeps equal values equal without revealing them
+    let options = RedactionOptions::hashed("report-salt");
+    assert_eq!(options.mode, RedactionMode::Hash);
+    let hashed = info.redacted_with(&options);
+    let serial = hashed.usb_devices.last().unwrap().serial_number.clone().unwrap();
+    assert!(serial.starts_with("[redacted:"));
+    assert!(hashed.usb_devices.last().unwrap().id.ends_with(&serial));
+    assert_eq!(info.redacted_with(&options).system.uuid, hashed.system.uuid);
+    assert_ne!(info.redacted_with(&RedactionOptions::hashed("other")).system.uuid, hashed.system.uuid);
+}
+
+#[test]
+fn test_monitor_edid() {
+    use hardware_query::MonitorInfo;
+
+    let mut edid = vec![0u8; 128];
+    edid[..8].copy_from_slice(&[0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00]);
+    // "DEL": D=4, E=5, L=12 packed as 5-bit letters
+    edid[8..10].copy_from_slice(&((4u16 << 10) | (5 << 5) | 12).to_be_bytes());
+    edid[10..12].copy_from_slice(&0xA0C4u16.to_le_bytes());
+    edid[12..16].copy_from_slice(&0x3033_4C42u32.to_le_bytes());
+    edid[17] = 32; // 2022
+    edid[21] = 60;
+    edid[22] = 34;
+    // Preferred timing: 2560x1440
+    edid[54..56].copy_from_slice(&24_150u16.to_le_bytes());
+    edid[56] = 0x00;
+    edid[58] = 0xA0;
+    edid[59] = 0xA0;
+    edid[61] = 0x50;
+    // Monitor name and serial string descriptors
+    let descriptor = |tag: u8, text: &str| {
+        let mut bytes = vec![0u8, 0, 0, tag, 0];
+        bytes.extend_from_slice(text.as_bytes());
+        bytes.push(0x0A);
+        bytes.resize(18, b' ');
+        bytes
+    };
+    edid[72..90].copy_from_slice(&descriptor(0xFC, "DELL U2722D"));
+    edid[90..108].copy_from_slice(&descriptor(0xFF, "7HYC3H3"));
+
+    let monitor = MonitorInfo::from_edid(Some("card0-DP-1"), &edid).unwrap();
+    assert_eq!(monitor.connector.as_deref(), Some("card0-DP-1"));
+    assert_eq!(monitor.manufacturer_id.as_deref(), Some("DEL"));
+    assert_eq!(monitor.manufacturer(), Some("Dell"));
+    assert_eq!(monitor.model.as_deref(), Some("DELL U2722D"));
+    assert_eq!(monitor.product_code, Some(0xA0C4));
+    // The serial string descriptor wins over the numeric serial
+    assert_eq!(monitor.serial_number.as_deref(), Some("7HYC3H3"));
+    assert_eq!(monitor.manufacture_year, Some(2022));
+    assert_eq!((monitor.native_width, monitor.native_height), (Some(2560), Some(1440)));
+    assert!((monitor.diagonal_inches().unwrap() - 27.2).abs() < 0.1);
+
+    edid[0] = 0xFF;
+    assert!(MonitorInfo::from_edid(None, &edid).is_none());
+    assert!(MonitorInfo::from_edid(None, &edid[..64]).is_none());
+
+    for monitor in MonitorInfo::query_all().unwrap() {
+        assert!(monitor.manufacturer_id.as_deref().is_none_or(|id| id.len() == 3));
+    }
+}
+
+#[test]
+fn test_asset_inventory() {
+    use hardware_query::{AssetCategory, HardwarePresets, MonitorInfo, RedactionOptions};
+
+    let mut info = HardwareInfo::query().unwrap();
+    info.system.serial_number = Some("5CG1234XYZ".to_string());
+    info.system.uuid = Some("4c4c4544-0042-3510-8052-b4c04f4d3233".to_string());
+    let monitor = MonitorInfo {
+        connector: Some("card0-HDMI-A-1".to_string()),
+        manufacturer_id: Some("SAM".to_string()),
+        model: Some("LS27A600U".to_string()),
+        serial_number: Some("HNAR401234".to_string()),
+        width_cm: Some(60),
+        height_cm: Some(34),
+        native_width: Some(2560),
+        native_height: Some(1440),
+        ..Default::default()
+    };
+
+    let inventory = HardwarePresets::asset_inventory_for(&info, std::slice::from_ref(&monitor), None);
+    assert_eq!(inventory.serial_number.as_deref(), Some("5CG1234XYZ"));
+    assert_eq!(inventory.memory_total_bytes, info.memory.total_mb * 1024 * 1024);
+    assert_eq!(inventory.components_of(AssetCategory::Processor).count(), 1);
+    assert_eq!(inventory.components_of(AssetCategory::Disk).count(), info.physical_disks().len());
+    assert_eq!(inventory.components_of(AssetCategory::GPU).count(), info.gpus().len());
+    let listed_monitor = inventory.components_of(AssetCategory::Monitor).next().unwrap();
+    assert_eq!(listed_monitor.manufacturer.as_deref(), Some("Samsung"));
+    assert_eq!(listed_monitor.details.as_deref(), Some("2560x1440, 27.2\""));
+    for adapter in inventory.components_of(AssetCategory::NetworkAdapter) {
+        assert!(adapter.identifier.as_deref().is_some_and(|mac| !mac.is_empty()));
+    }
+
+    // The inventory is flat enough to serialize as-is
+    let json = serde_json::to_string(&inventory).unwrap();
+    assert!(json.contains("HNAR401234"));
+
+    let redacted = HardwarePresets::asset_inventory_for(&info, &[monitor], Some(&RedactionOptions::new()));
+    let json = serde_json::to_string(&redacted).unwrap();
+    for secret in ["5CG1234XYZ", "4c4c4544", "HNAR401234"] {
+        assert!(!json.contains(secret), "{secret} leaked");
+    }
+    assert_eq!(redacted.components.len(), inventory.components.len());
+    assert_eq!(redacted.model, inventory.model);
+
+    // Hashes line up with a redacted HardwareInfo using the same salt
+    let options = RedactionOptions::hashed("cmdb");
+    assert_eq!(inventory.redacted_with(&options).uuid, info.redacted_with(&options).system.uuid);
+}
+
+#[test]
+fn test_fixture_provider() {
+    use hardware_query::{ComponentErrorKind, FixtureProvider, HardwareComponent};
+
+    let mut recorded = HardwareInfo::query().unwrap();
+    recorded.timestamp = 1_700_000_000;
+    recorded.memory.total_mb = 262_144;
+    recorded.cpu.brand = "Fixture CPU".to_string();
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("workstation.json");
+    std::fs::write(&path, recorded.to_json().unwrap()).unwrap();
+
+    let provider = FixtureProvider::from_file(&path).unwrap();
+    let replayed = HardwareInfo::query_with_provider(provider.clone()).unwrap();
+    assert_eq!(replayed.timestamp, 1_700_000_000);
+    assert_eq!(replayed.cpu().brand, "Fixture CPU");
+    assert_eq!(replayed.memory().total_mb, 262_144);
+    assert_eq!(replayed.physical_disks.len(), recorded.physical_disks.len());
+    assert!(replayed.errors().is_empty());
+    assert!(replayed.diff(&recorded).is_empty());
+
+    // A failing component is reported and replaced with a placeholder
+    let failing = provider.with_failure(HardwareComponent::Memory, "EDAC driver not loaded");
+    let degraded = HardwareInfo::query_with_provider(failing).unwrap();
+    assert_eq!(degraded.errors().len(), 1);
+    assert_eq!(degraded.errors()[0].component, HardwareComponent::Memory);
+    assert_eq!(degraded.errors()[0].kind, ComponentErrorKind::Failed);
+    assert!(degraded.errors()[0].message.contains("EDAC driver not loaded"));
+    assert_ne!(degraded.memory().total_mb, 262_144);
+    assert_eq!(degraded.cpu().brand, "Fixture CPU");
+}
+
+#[test]
+fn test_unsupported_components() {
+    use hardware_query::{ComponentErrorKind, FixtureProvider, HardwareComponent};
+
+    let snapshot = HardwareInfo::query().unwrap();
+    let provider = FixtureProvider::new(snapshot)
+        .with_unsupported(HardwareComponent::Storage, "Storage information is not exposed to web pages")
+        .with_failure(HardwareComponent::Memory, "EDAC driver not loaded");
+    let info = HardwareInfo::query_with_provider(provider).unwrap();
+
+    assert_eq!(info.errors().len(), 2);
+    let storage = info
+        .errors()
+        .iter()
+        .find(|error| error.component == HardwareComponent::Storage)
+        .unwrap();
+    assert_eq!(storage.kind, ComponentErrorKind::Unsupported);
+    assert_eq!(info.unsupported_components(), vec![HardwareComponent::Storage]);
+    assert!(info.storage_devices().is_empty());
+}
+
+#[cfg(feature = "sim")]
+#[test]
+fn test_simulated_machine_profiles() {
+    use hardware_query::{GPUType, GPUVendor, MachineProfile};
+
+    for profile in MachineProfile::ALL {
+        let info = HardwareInfo::query_with_provider(profile.provider().unwrap()).unwrap();
+        assert!(info.errors().is_empty(), "{profile}: {:?}", info.errors());
+        assert!(info.diff(&profile.hardware_info().unwrap()).is_empty());
+        assert_eq!(profile.to_string().parse::<MachineProfile>().unwrap(), profile);
+        assert_eq!(info.cpu().logical_cores() as usize, info.cpu().core_usage().len());
+        info.fingerprint(&Default::default()).unwrap();
+    }
+
+    let laptop = MachineProfile::LaptopIntegratedGpu.hardware_info().unwrap();
+    assert_eq!(laptop.gpus()[0].gpu_type(), &GPUType::Integrated);
+    assert!(laptop.battery().is_some());
+    assert_eq!(laptop.npus().len(), 1);
+
+    let dgx = MachineProfile::DgxH100.hardware_info().unwrap();
+    assert_eq!(dgx.gpus().len(), 8);
+    assert!(dgx.gpus().iter().all(|gpu| gpu.vendor() == &GPUVendor::NVIDIA && gpu.memory_gb() > 79.0));
+    assert_eq!(dgx.cpu().topology.numa_nodes.len(), 2);
+    assert!(dgx.memory().ecc_support);
+
+    let pi = MachineProfile::RaspberryPi5.hardware_info().unwrap();
+    assert_eq!(pi.cpu().architecture, "aarch64");
+    assert!(pi.arm_hardware().is_some());
+
+    let desktop = MachineProfile::GamingDesktopWindows.hardware_info().unwrap();
+    assert!(desktop.gpus()[0].directx().unwrap().is_dx12_ultimate());
+    assert_eq!(desktop.volumes()[0].mount_point, "C:\\");
+
+    assert!("toaster".parse::<MachineProfile>().is_err());
+}
+
+#[test]
+fn test_fan_curve_speed() {
+    use hardware_query::{CurvePoint, FanCurve};
+
+    let curve = FanCurve {
+        curve_points: vec![
+            CurvePoint { temperature: 80.0, speed_percent: 100.0 },
+            CurvePoint { temperature: 40.0, speed_percent: 30.0 },
+            CurvePoint { temperature: 60.0, speed_percent: 50.0 },
+        ],
+        hysteresis: 3.0,
+        min_speed_percent: 35.0,
+        max_speed_percent: 90.0,
+    };
+    // Clamped to the curve's speed range
+    assert_eq!(curve.speed_at(20.0), 35.0);
+    assert_eq!(curve.speed_at(95.0), 90.0);
+    assert!((curve.speed_at(50.0) - 40.0).abs() < 1e-4);
+    assert!((curve.speed_at(70.0) - 75.0).abs() < 1e-4);
+}
+
+#[cfg(feature = "fan-control")]
+#[test]
+fn test_fan_controller() {
+    use hardware_query::{FanController, HardwareQueryError};
+
+    // Needs root and a controllable fan; never change real fan speeds here
+    match FanController::open() {
+        Ok(controller) => {
+            let controller = controller.with_minimum_duty(150.0);
+            assert_eq!(controller.minimum_duty(), 100.0);
+            assert!(controller.fans().iter().all(|fan| !fan.name.is_empty()));
+        }
+        Err(e) => assert!(matches!(
+            e,
+            HardwareQueryError::DeviceNotFound(_)
+                | HardwareQueryError::PermissionDenied(_)
+                | HardwareQueryError::PlatformNotSupported(_)
+                | HardwareQueryError::SystemInfoUnavailable(_)
+        )),
+    }
+}
+
+#[test]
+fn test_kubernetes_downward_api() {
+    use hardware_query::KubernetesInfo;
+    use std::collections::HashMap;
+
+    let values: HashMap<&str, &str> = HashMap::from([
+        ("POD_NAME", "trainer-0"),
+        ("POD_NAMESPACE", "ml"),
+        ("CPU_REQUEST", "500m"),
+        ("CPU_LIMIT", "2"),
+        ("MEMORY_REQUEST", "512Mi"),
+        ("MEMORY_LIMIT", "4G"),
+        ("NVIDIA_VISIBLE_DEVICES", "GPU-1a2b, GPU-3c4d"),
+        ("HABANA_VISIBLE_DEVICES", "all"),
+    ]);
+    let info = KubernetesInfo::from_downward_api(|name| values.get(name).map(|v| v.to_string()));
+
+    assert_eq!(info.pod_name.as_deref(), Some("trainer-0"));
+    assert_eq!(info.namespace.as_deref(), Some("ml"));
+    assert_eq!(info.node_name, None);
+    assert_eq!(info.cpu_request_millicores, Some(500));
+    assert_eq!(info.cpu_limit_cores(), Some(2.0));
+    assert_eq!(info.memory_request_bytes, Some(512 * 1024 * 1024));
+    assert_eq!(info.memory_limit_bytes, Some(4_000_000_000));
+    assert_eq!(info.allocated_gpu_ids(), ["GPU-1a2b", "GPU-3c4d"]);
+    assert!(!info.allocated_devices.contains_key("habana.ai/gaudi"));
+
+    let virtualization = HardwareInfo::query().unwrap().virtualization().clone();
+    if virtualization.environment_type != hardware_query::VirtualizationType::Kubernetes {
+        assert!(virtualization.kubernetes.is_none());
+    }
+}
+
+#[test]
+fn test_hypervisor_detection() {
+    use hardware_query::{VirtualizationInfo, VirtualizationType};
+
+    let info = VirtualizationInfo::detect().unwrap();
+    if let Some(hypervisor) = &info.hypervisor {
+        assert!(!hypervisor.is_empty());
+        assert!(info.is_virtualized());
+        if !info.is_containerized() && !matches!(info.environment_type, VirtualizationType::WSL | VirtualizationType::WSL2) {
+            assert_eq!(info.environment_type, VirtualizationType::VirtualMachine);
+        }
+    } else if info.environment_type == VirtualizationType::VirtualMachine {
+        panic!("virtual machine detected without a hypervisor");
+    }
+}
+
+#[test]
+fn test_shared_hardware_info() {
+    use hardware_query::SharedHardwareInfo;
+
+    let shared = SharedHardwareInfo::query().unwrap();
+    let model = shared.read().cpu().model_name().to_string();
+    let cores = shared.read().cpu().logical_cores();
+
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = shared.clone();
+            std::thread::spawn(move || {
+                for _ in 0..10 {
+                    let info = shared.read();
+                    assert_eq!(info.cpu().logical_cores(), cores);
+                }
+            })
+        })
+        .collect();
+    let refresher = {
+        let shared = shared.clone();
+        std::thread::spawn(move || {
+            shared.refresh_cpu_usage().unwrap();
+            // Sensors may be unavailable in CI; a failed refresh keeps the old readings
+            let _ = shared.refresh_thermal();
+        })
+    };
+    for handle in readers {
+        handle.join().unwrap();
+    }
+    refresher.join().unwrap();
+
+    assert_eq!(shared.handle_count(), 1);
+    let snapshot = shared.snapshot();
+    assert_eq!(snapshot.cpu().model_name(), model);
+    assert!(snapshot.cpu().core_usage().iter().all(|usage| (0.0..=100.0).contains(usage)));
+}
+
+#[test]
+fn test_selective_refresh() {
+    use hardware_query::DynamicComponent;
+
+    let mut info = HardwareInfo::query().unwrap();
+    let model = info.cpu().model_name().to_string();
+    let gpus = info.gpus().len();
+    let modules = info.memory().modules.len();
+
+    let started = std::time::Instant::now();
+    // Thermal and power readings may be unavailable in CI; the rest must refresh
+    let _ = info.refresh(&DynamicComponent::ALL);
+    info.refresh(&[DynamicComponent::CpuUsage, DynamicComponent::Memory]).unwrap();
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+
+    assert_eq!(info.cpu().model_name(), model);
+    assert_eq!(info.gpus().len(), gpus);
+    assert_eq!(info.memory().modules.len(), modules);
+    assert_eq!(info.cpu().core_usage().len(), info.cpu().logical_cores() as usize);
+    assert!(info.cpu().core_usage().iter().all(|usage| (0.0..=100.0).contains(usage)));
+    assert!(info.memory().total_mb > 0);
+    assert!(info.memory().used_mb <= info.memory().total_mb);
+}
+
+#[test]
+fn test_byte_accessors_and_units() {
+    use hardware_query::UnitSystem;
+
+    let info = HardwareInfo::query().unwrap();
+    let memory = info.memory();
+    assert_eq!(memory.total_bytes(), memory.total_mb() * 1024 * 1024);
+    assert!(memory.used_bytes() <= memory.total_bytes());
+    for gpu in info.gpus() {
+        assert_eq!(gpu.memory_bytes(), gpu.memory_mb() * 1024 * 1024);
+    }
+    for volume in &info.volumes {
+        assert_eq!(volume.used_bytes(), volume.total_bytes() - volume.available_bytes());
+        let gb = UnitSystem::Binary.to_gigabytes(volume.total_bytes());
+        assert!((gb - volume.total_gb()).abs() < 0.01);
+    }
+    for device in info.storage_devices() {
+        assert!(device.available_bytes() <= device.capacity_bytes());
+    }
+
+    assert_eq!(UnitSystem::default(), UnitSystem::Binary);
+    assert_eq!(UnitSystem::Binary.format(0), "0 B");
+    assert_eq!(UnitSystem::Binary.format(1536), "1.5 KiB");
+    assert_eq!(UnitSystem::Decimal.format(1536), "1.5 kB");
+    assert_eq!(UnitSystem::Decimal.format_with_precision(2_000_398_934_016, 2), "2.00 TB");
+    assert_eq!(UnitSystem::Binary.format_with_precision(2_000_398_934_016, 2), "1.82 TiB");
+    assert_eq!(UnitSystem::Decimal.to_gigabytes(16_000_000_000), 16.0);
+}
+
+#[test]
+fn test_structured_errors() {
+    use hardware_query::{
+        ComponentErrorKind, ErrorCode, ErrorComponent, FixtureProvider, HardwareComponent, HardwareQueryError,
+    };
+    use std::error::Error;
+
+    let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "open /dev/cpu/0/msr");
+    let error = HardwareQueryError::from_io(ErrorComponent::Cpu, "Reading MSR 0x64F requires root", io);
+    assert_eq!(error.code(), ErrorCode::PermissionDenied);
+    assert_eq!(error.component(), Some(ErrorComponent::Cpu));
+    assert!(error.is_permission_denied());
+    assert!(!error.is_transient());
+    assert_eq!(error.source().unwrap().to_string(), "open /dev/cpu/0/msr");
+    assert_eq!(error.to_string(), "CPU error (permission denied): Reading MSR 0x64F requires root");
+
+    let busy = HardwareQueryError::new(ErrorComponent::Nvml, ErrorCode::Transient, "GPU is lost");
+    assert!(busy.is_transient());
+    assert!(busy.source().is_none());
+
+    // Legacy variants map onto codes too
+    assert!(HardwareQueryError::device_not_found("no battery").is_not_present());
+    assert_eq!(HardwareQueryError::gpu_driver_error("NVML mismatch").component(), Some(ErrorComponent::Gpu));
+    assert_eq!(HardwareQueryError::from(std::io::Error::from(std::io::ErrorKind::TimedOut)).code(), ErrorCode::Timeout);
+    assert_eq!(ErrorComponent::from(HardwareComponent::Storage), ErrorComponent::Storage);
+
+    // Per-component failures carry the code
+    let provider = FixtureProvider::new(HardwareInfo::query().unwrap())
+        .with_unsupported(HardwareComponent::Thermal, "No sensors in a browser")
+        .with_failure(HardwareComponent::Memory, "EDAC driver not loaded");
+    let info = HardwareInfo::query_with_provider(provider).unwrap();
+    for error in info.errors() {
+        match error.component {
+            HardwareComponent::Thermal => {
+                assert_eq!(error.kind, ComponentErrorKind::Unsupported);
+                assert_eq!(error.code, ErrorCode::Unsupported);
+            }
+            HardwareComponent::Memory => assert_eq!(error.code, ErrorCode::Unavailable),
+            other => panic!("unexpected error for {other}"),
+        }
+    }
+}
+
+#[test]
+fn test_capabilities_check() {
+    use hardware_query::{AccessLevel, Capabilities, Subsystem};
+
+    let capabilities = Capabilities::check();
+    assert_eq!(capabilities.subsystems().len(), Subsystem::ALL.len());
+    for subsystem in Subsystem::ALL {
+        let access = capabilities.get(subsystem).unwrap();
+        match access.level {
+            AccessLevel::Full => {
+                assert!(access.missing.is_empty());
+                assert!(access.degraded_data.is_none());
+            }
+            AccessLevel::Degraded => {
+                assert!(!access.missing.is_empty(), "{subsystem} is degraded without a reason");
+                assert!(access.degraded_data.is_some());
+            }
+            AccessLevel::Unavailable => assert!(access.degraded_data.is_some()),
+        }
+    }
+
+    let missing = capabilities.missing_requirements();
+    for (index, requirement) in missing.iter().enumerate() {
+        assert!(!missing[index + 1..].contains(requirement));
+    }
+    assert!(capabilities.degraded().iter().all(|access| !access.is_full()));
+}
+
+#[test]
+fn test_filesystem_details() {
+    use hardware_query::Volume;
+
+    let volume = |fs: &str, options: &[&str], inodes: Option<(u64, u64)>| Volume {
+        name: "/dev/test".to_string(),
+        mount_point: "/work".to_string(),
+        file_system: Some(fs.to_string()),
+        total_gb: 100.0,
+        available_gb: 50.0,
+        used_gb: 50.0,
+        removable: false,
+        disk: None,
+        total_bytes: 0,
+        available_bytes: 0,
+        mount_options: options.iter().map(|o| o.to_string()).collect(),
+        inodes_total: inodes.map(|(total, _)| total),
+        inodes_free: inodes.map(|(_, free)| free),
+    };
+
+    let btrfs = volume("btrfs", &["rw", "noatime", "compress=zstd:3", "ssd"], Some((0, 0)));
+    assert_eq!(btrfs.compression(), Some("zstd"));
+    assert!(btrfs.has_mount_option("compress"));
+    assert!(btrfs.atime_optimized());
+    assert!(!btrfs.is_network());
+    assert_eq!(btrfs.inode_usage_percent(), None);
+    assert!(btrfs.performance_warnings().is_empty());
+
+    let nfs = volume("nfs4", &["rw", "relatime", "vers=4.2"], None);
+    assert!(nfs.is_network());
+    assert_eq!(nfs.compression(), None);
+    assert_eq!(nfs.performance_warnings().len(), 1);
+
+    let ext4 = volume("ext4", &["rw"], Some((1000, 50)));
+    assert!(!ext4.atime_optimized());
+    assert_eq!(ext4.inode_usage_percent(), Some(95.0));
+    assert_eq!(ext4.performance_warnings().len(), 2);
+
+    let read_only = volume("ext4", &["ro"], Some((1000, 900)));
+    assert!(read_only.is_read_only());
+    assert!(read_only.performance_warnings().is_empty());
+
+    for volume in HardwareInfo::query().unwrap().volumes() {
+        if let Some(usage) = volume.inode_usage_percent() {
+            assert!((0.0..=100.0).contains(&usage));
+        }
+        if let (Some(total), Some(free)) = (volume.inodes_total(), volume.inodes_free()) {
+            assert!(free <= total);
+        }
+    }
+}
+
+#[test]
+fn test_external_commands_disabled() {
+    use hardware_query::{QueryOptions, NO_EXTERNAL_COMMANDS_ENV_VAR};
+
+    assert!(QueryOptions::new().external_commands);
+    let options = QueryOptions::new().allow_external_commands(false);
+    assert!(!options.external_commands);
+    assert_eq!(NO_EXTERNAL_COMMANDS_ENV_VAR, "HARDWARE_QUERY_NO_EXTERNAL_COMMANDS");
+
+    // Native and file-based probing still produce a complete snapshot
+    let hw_info = HardwareInfo::query_with_options(&options).unwrap();
+    assert!(!hw_info.cpu().model_name().is_empty());
+    assert!(hw_info.memory().total_gb() > 0.0);
+}
+
+#[test]
+fn test_localizer_hook() {
+    use hardware_query::{clear_localizer, localize, set_localizer, ChassisType, PowerSource, PowerState};
+
+    assert_eq!(ChassisType::RackMount.to_string(), "Rack Mount");
+
+    set_localizer(|context: &str, text: &str| match (context, text) {
+        ("ChassisType", "Rack Mount") => Some("Rack-Einbau".to_string()),
+        ("PowerState", "Custom") => Some("Benutzerdefiniert".to_string()),
+        ("PowerSource", "Battery") => Some("Akku".to_string()),
+        _ => None,
+    });
+    assert_eq!(ChassisType::RackMount.to_string(), "Rack-Einbau");
+    assert_eq!(
+        PowerState::Custom("Studio".to_string()).to_string(),
+        "Benutzerdefiniert: Studio"
+    );
+    assert_eq!(PowerSource::Battery.to_string(), "Akku");
+    // Unknown strings fall back to English
+    assert_eq!(localize("ChassisType", "Blade"), "Blade");
+
+    clear_localizer();
+    assert_eq!(ChassisType::RackMount.to_string(), "Rack Mount");
+}
+
+#[test]
+fn test_markdown_and_html_report() {
+    use hardware_query::{HardwareReport, ReportDetail, ReportSection};
+
+    let hw_info = HardwareInfo::query().unwrap();
+    let summary = hw_info.report(ReportDetail::Summary);
+    let full = hw_info.report(ReportDetail::Full);
+    let titles = |report: &HardwareReport| {
+        report.sections().iter().map(|section| section.title.clone()).collect::<Vec<_>>()
+    };
+    assert_eq!(&titles(&summary)[..2], ["Summary", "Health"]);
+    assert!(titles(&summary).iter().all(|title| titles(&full).contains(title)));
+    assert!(titles(&full).contains(&"CPU".to_string()));
+    assert!(!titles(&summary).contains(&"CPU".to_string()));
+
+    let markdown = hw_info.to_markdown(ReportDetail::Standard);
+    assert!(markdown.starts_with("# Hardware Report\n"));
+    assert!(markdown.contains("## Summary\n\n| Property | Value |\n| --- | --- |\n"));
+
+    let html = hw_info.to_html(ReportDetail::Standard);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.trim_end().ends_with("</html>"));
+    assert_eq!(html.matches("<section>").count(), html.matches("</section>").count());
+
+    // Host-supplied text is escaped in both formats
+    let report = summary.with_title("Ticket #42").with_section(
+        ReportSection::new("Notes")
+            .with_properties(vec![("Reporter", "a|b <script>".to_string())])
+            .with_list(vec!["line one\nline two".to_string()]),
+    );
+    let markdown = report.to_markdown();
+    assert!(markdown.contains("| Reporter | a\\|b \\<script\\> |"));
+    let html = report.to_html();
+    assert!(html.contains("<td>a|b &lt;script&gt;</td>"));
+    assert!(!html.contains("<script>"));
+    assert!(html.contains("<title>Ticket #42</title>"));
+}
+
+#[test]
+fn test_ai_assessment_counts_npus() {
+    use hardware_query::{
+        HardwarePresets, NPUArchitecture, NPUInfo, NPUType, NPUVendor, SimpleAccelerator,
+        SoftwareStack, SystemOverview,
+    };
+    use std::collections::HashMap;
+
+    let npu = NPUInfo {
+        vendor: NPUVendor::Intel,
+        model_name: "Intel AI Boost".to_string(),
+        npu_type: NPUType::Integrated,
+        architecture: NPUArchitecture::IntelXDNA,
+        tops_performance: Some(48.0),
+        memory_mb: None,
+        driver_version: None,
+        firmware_version: None,
+        pci_device_id: None,
+        usb_device_id: None,
+        supported_frameworks: vec!["OpenVINO".to_string(), "ONNX Runtime".to_string()],
+        power_consumption: None,
+        temperature: None,
+        clock_frequency: None,
+        capabilities: HashMap::new(),
+    };
+    assert!(npu.supports_dtype("INT8"));
+    assert!(!npu.supports_dtype("float64"));
+
+    let mut overview = SystemOverview::from_hardware_info(HardwareInfo::query().unwrap()).unwrap();
+    overview.accelerators.clear();
+    let baseline = HardwarePresets::ai_assessment_from(overview.clone(), SoftwareStack::default());
+
+    overview.accelerators.push(SimpleAccelerator {
+        kind: "NPU".to_string(),
+        name: npu.model_name().to_string(),
+        vendor: npu.vendor().to_string(),
+        tops: npu.tops_performance(),
+        frameworks: npu.supported_frameworks.clone(),
+        dtypes: npu.supported_dtypes(),
+    });
+    assert_eq!(overview.accelerator_tops(), 48.0);
+    let assessment = HardwarePresets::ai_assessment_from(overview, SoftwareStack::default());
+
+    assert!(assessment.ai_score > baseline.ai_score || baseline.ai_score == 100);
+    let openvino = assessment.frameworks.iter().find(|f| f.name == "OpenVINO").unwrap();
+    assert!(!openvino.requirements_met, "OpenVINO is not installed");
+    let onnx = assessment.frameworks.iter().find(|f| f.name == "ONNX Runtime").unwrap();
+    assert!(onnx.notes.contains("Intel AI Boost (48 TOPS)"));
+    assert!(assessment
+        .model_recommendations
+        .small_models
+        .iter()
+        .any(|model| model.name.contains("INT8, NPU")));
+    assert!(assessment.optimizations.iter().any(|o| o.starts_with("Use OpenVINO")));
+    assert_eq!(
+        format!("{:?}", assessment.performance.inference_capability),
+        "Excellent"
+    );
+}
+
+#[test]
+fn test_framework_requirement_matrix() {
+    use hardware_query::{
+        FrameworkRequirement, HardwarePresets, SimpleGPU, SoftwareStack, SystemOverview,
+    };
+
+    let tensorflow = HardwarePresets::framework_requirements("TensorFlow");
+    assert!(tensorflow.contains(&FrameworkRequirement::CpuFeature("avx".to_string())));
+    assert!(HardwarePresets::framework_requirements("unknown").is_empty());
+
+    let mut overview = SystemOverview::from_hardware_info(HardwareInfo::query().unwrap()).unwrap();
+    overview.memory_gb = 16.0;
+    overview.cpu.vendor = "Intel".to_string();
+    overview.cpu.features = vec!["sse4.2".to_string()];
+    overview.gpu = Some(SimpleGPU {
+        name: "GeForce GTX 780".to_string(),
+        vram_gb: 1.5,
+        vendor: "NVIDIA".to_string(),
+        ai_capable: true,
+        compute_capability: Some("3.5".to_string()),
+        // Windows form of driver 536.40
+        driver_version: Some("31.0.15.3640".to_string()),
+    });
+    let assessment = HardwarePresets::ai_assessment_from(overview, SoftwareStack::default());
+
+    let pytorch = assessment.frameworks.iter().find(|f| f.name == "PyTorch").unwrap();
+    assert!(!pytorch.requirements_met);
+    let unmet: Vec<String> = pytorch
+        .unmet_requirements
+        .iter()
+        .map(|check| check.requirement.to_string())
+        .collect();
+    assert!(unmet.contains(&"CUDA compute capability 5.0".to_string()));
+    assert!(unmet.contains(&"2 GB VRAM".to_string()));
+    assert!(unmet.contains(&"CUDA installed".to_string()));
+    assert!(!unmet.iter().any(|u| u.contains("cuDNN")), "PyTorch bundles cuDNN");
+    let driver = pytorch
+        .requirements
+        .iter()
+        .find(|check| matches!(check.requirement, FrameworkRequirement::DriverVersion { .. }))
+        .unwrap();
+    assert!(driver.met);
+    assert!(driver.detail.contains("536.40"));
+
+    let tensorflow = assessment.frameworks.iter().find(|f| f.name == "TensorFlow").unwrap();
+    assert!(tensorflow
+        .unmet_requirements
+        .iter()
+        .any(|check| check.requirement == FrameworkRequirement::CpuFeature("avx".to_string())));
+    assert!(tensorflow
+        .requirements
+        .iter()
+        .any(|check| check.met && check.requirement == FrameworkRequirement::ComputeCapability("3.5".to_string())));
+
+    let onnx = assessment.frameworks.iter().find(|f| f.name == "ONNX Runtime").unwrap();
+    assert!(onnx.requirements_met && onnx.unmet_requirements.is_empty());
+}
+
+#[test]
+fn test_llm_assessment() {
+    use hardware_query::{HardwarePresets, Quantization, ThroughputClass};
+
+    let hardware = HardwareInfo::query().unwrap();
+    let assessment = HardwarePresets::llm_assessment_for(&hardware).unwrap();
+
+    assert_eq!(assessment.context_length, 4096);
+    assert!(!assessment.max_model_sizes.iter().any(|s| s.quantization == Quantization::FP32));
+    // Narrower formats never fit a smaller model
+    for wide in &assessment.max_model_sizes {
+        assert!(wide.max_parameters_in_gpu_billions <= wide.max_parameters_billions + 0.1);
+        for narrow in &assessment.max_model_sizes {
+            if narrow.quantization.bits_per_weight() < wide.quantization.bits_per_weight() {
+                assert!(narrow.max_parameters_billions + 0.1 >= wide.max_parameters_billions);
+            }
+        }
+    }
+
+    let contexts: Vec<u32> = assessment.context_memory.iter().map(|c| c.context_length).collect();
+    assert_eq!(contexts, vec![4096, 8192, 32768, 131072]);
+    for pair in assessment.context_memory.windows(2) {
+        assert!(pair[1].kv_cache_gb > pair[0].kv_cache_gb);
+    }
+
+    // Prefill is compute bound and never slower than decode
+    if let (Some(prefill), Some(decode)) = (assessment.prefill.tokens_per_second, assessment.decode.tokens_per_second) {
+        assert!(prefill >= decode);
+    }
+    assert_eq!(ThroughputClass::from_prefill_tokens_per_second(1500.0), ThroughputClass::Fast);
+    assert_eq!(ThroughputClass::from_prefill_tokens_per_second(20.0), ThroughputClass::Impractical);
+
+    let names: Vec<&str> = assessment.runtimes.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["llama.cpp", "vLLM", "TensorRT-LLM"]);
+    let llama_cpp = &assessment.runtimes[0];
+    assert_eq!(llama_cpp.viable, hardware.memory().total_gb() >= 4.0);
+    if hardware.gpus().iter().all(|gpu| gpu.cuda_capability().is_none()) {
+        assert!(!assessment.runtimes[2].viable, "TensorRT-LLM needs an NVIDIA GPU");
+    }
+}
+
+#[test]
+fn test_image_generation_assessment() {
+    use hardware_query::HardwarePresets;
+
+    let hardware = HardwareInfo::query().unwrap();
+    let assessment = HardwarePresets::image_generation_assessment_for(&hardware).unwrap();
+
+    let models: Vec<(&str, u32)> = assessment
+        .models
+        .iter()
+        .map(|fit| (fit.model.as_str(), fit.resolution))
+        .collect();
+    assert_eq!(
+        models,
+        vec![("SD 1.5", 512), ("SD 1.5", 768), ("SDXL", 1024), ("SDXL", 1536), ("Flux.1", 1024), ("Flux.1", 1536)]
+    );
+    for pair in assessment.models.windows(2).filter(|pair| pair[0].model == pair[1].model) {
+        assert!(pair[1].vram_required_gb > pair[0].vram_required_gb);
+        assert!(pair[1].recommended_batch_size <= pair[0].recommended_batch_size);
+    }
+    for fit in &assessment.models {
+        assert_eq!(fit.fits, fit.recommended_batch_size > 0);
+        assert_eq!(fit.fits, fit.vram_required_gb <= assessment.usable_vram_gb);
+        assert!(fit.recommended_batch_size <= 8);
+        assert_eq!(fit.notes.is_none(), fit.fits);
+    }
+    if assessment.bf16_supported {
+        assert!(assessment.fp16_supported);
+    }
+
+    let names: Vec<&str> = assessment.accelerations.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, vec!["xformers", "TensorRT"]);
+    if hardware.gpus().iter().all(|gpu| gpu.cuda_capability().is_none()) {
+        assert!(assessment.accelerations.iter().all(|a| !a.supported));
+    }
+    if assessment.gpu.is_none() {
+        assert_eq!(assessment.usable_vram_gb, 0.0);
+        assert!(assessment.models.iter().all(|fit| !fit.fits));
+    }
+}
+
+#[test]
+fn test_content_creation_assessment() {
+    use hardware_query::{GPUInfo, HardwarePresets, VideoCodec};
+
+    let gpu = |vendor: &str, name: &str, cuda: Option<&str>| -> GPUInfo {
+        serde_json::from_value(serde_json::json!({
+            "vendor": vendor,
+            "model_name": name,
+            "gpu_type": "Discrete",
+            "memory_mb": 12288,
+            "compute_capabilities": {
+                "cuda": cuda,
+                "rocm": false,
+                "directml": false,
+                "opencl": false,
+                "vulkan": false,
+                "metal": vendor == "Apple",
+            },
+        }))
+        .unwrap()
+    };
+
+    let ada = gpu("NVIDIA", "GeForce RTX 4070", Some("8.9")).video_engine().unwrap();
+    assert_eq!(ada.name, "NVENC");
+    assert_eq!(ada.generation.as_deref(), Some("8th gen (Ada)"));
+    assert!(ada.can_encode(VideoCodec::AV1));
+    let turing = gpu("NVIDIA", "GeForce GTX 1660", None).video_engine().unwrap();
+    assert!(turing.can_encode(VideoCodec::HEVC) && !turing.can_encode(VideoCodec::AV1));
+    let a100 = gpu("NVIDIA", "A100-SXM4-40GB", Some("8.0")).video_engine().unwrap();
+    assert!(a100.encode.is_empty() && a100.can_decode(VideoCodec::HEVC));
+    assert_eq!(gpu("AMD", "Radeon RX 7900 XTX", None).video_engine().unwrap().generation.as_deref(), Some("VCN 4.0"));
+    let m1 = gpu("Apple", "Apple M1", None).video_engine().unwrap();
+    assert!(!m1.can_encode(VideoCodec::ProRes));
+    let m3_max = gpu("Apple", "Apple M3 Max", None).video_engine().unwrap();
+    assert!(m3_max.can_encode(VideoCodec::ProRes) && m3_max.can_decode(VideoCodec::AV1));
+
+    let mut hardware = HardwareInfo::query().unwrap();
+    let assessment = HardwarePresets::content_creation_assessment_for(&hardware).unwrap();
+    let resolutions: Vec<&str> = assessment.timelines.iter().map(|t| t.resolution.as_str()).collect();
+    assert_eq!(resolutions, vec!["1080p", "4K", "6K", "8K"]);
+    for timeline in &assessment.timelines {
+        assert_eq!(timeline.native_editing, timeline.limitations.is_empty());
+        assert_eq!(timeline.proxy_recommended, !timeline.native_editing);
+    }
+    // A timeline that needs proxies stays proxied at higher resolutions
+    for pair in assessment.timelines.windows(2) {
+        assert!(pair[1].proxy_recommended || !pair[0].proxy_recommended);
+    }
+
+    hardware.gpus = vec![gpu("NVIDIA", "GeForce RTX 4070", Some("8.9"))];
+    let assessment = HardwarePresets::content_creation_assessment_for(&hardware).unwrap();
+    assert_eq!(assessment.video_engines.len(), 1);
+    assert!(assessment.gpu_memory_gb >= 12.0);
+    assert!(assessment.recommendations.iter().any(|r| r.starts_with("Export AV1 with NVENC")));
+    assert!(!assessment.timelines[1].limitations.iter().any(|l| l.contains("HEVC")));
+}
+
+#[test]
+fn test_video_capabilities() {
+    use hardware_query::{GPUInfo, VideoCapabilities, VideoCodec};
+
+    let base = serde_json::json!({
+        "vendor": "Intel",
+        "model_name": "Intel Arc A770",
+        "gpu_type": "Discrete",
+        "memory_mb": 16384,
+        "compute_capabilities": {
+            "cuda": null,
+            "rocm": false,
+            "directml": false,
+            "opencl": false,
+            "vulkan": false,
+            "metal": false,
+        },
+    });
+    // Older serialized GPUs have no codec matrix
+    let gpu: GPUInfo = serde_json::from_value(base.clone()).unwrap();
+    assert!(gpu.video_capabilities().is_none());
+    assert!(gpu.video_engine().unwrap().can_encode(VideoCodec::AV1));
+
+    let caps: VideoCapabilities = serde_json::from_value(serde_json::json!({
+        "source": "VA-API",
+        "encode": [{ "codec": "H264", "profiles": ["High"], "max_width": 4096, "max_height": 2304 }],
+        "decode": [
+            { "codec": "H264", "max_width": 4096, "max_height": 2304 },
+            { "codec": "HEVC", "profiles": ["Main", "Main10"], "max_width": 8192, "max_height": null },
+        ],
+    }))
+    .unwrap();
+    assert_eq!(caps.max_encode_sessions, None);
+    assert!(caps.can_encode(VideoCodec::H264) && !caps.can_encode(VideoCodec::HEVC));
+    assert!(caps.can_decode(VideoCodec::HEVC) && !caps.can_decode(VideoCodec::AV1));
+    assert_eq!(caps.encode_support(VideoCodec::H264).unwrap().max_resolution(), Some((4096, 2304)));
+    assert_eq!(caps.decode_support(VideoCodec::HEVC).unwrap().max_resolution(), None);
+    assert!(caps.decode_support(VideoCodec::H264).unwrap().profiles.is_empty());
+
+    // Detected codecs take precedence over the generation tables
+    let mut with_caps = base;
+    with_caps["video"] = serde_json::to_value(&caps).unwrap();
+    let gpu: GPUInfo = serde_json::from_value(with_caps).unwrap();
+    let engine = gpu.video_engine().unwrap();
+    assert_eq!(engine.name, "Quick Sync");
+    assert_eq!(engine.encode, vec![VideoCodec::H264]);
+    assert_eq!(engine.decode, vec![VideoCodec::H264, VideoCodec::HEVC]);
+
+    for gpu in HardwareInfo::query().unwrap().gpus() {
+        if let Some(caps) = gpu.video_capabilities() {
+            assert!(!caps.source.is_empty());
+            assert!(!caps.encode.is_empty() || !caps.decode.is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_gpu_selection() {
+    use hardware_query::{GPUInfo, GPUVendor, GpuCriteria};
+
+    let gpu = |vendor: &str, name: &str, gpu_type: &str, memory_mb: u64, cuda: Option<&str>, usage: Option<f32>| -> GPUInfo {
+        serde_json::from_value(serde_json::json!({
+            "vendor": vendor,
+            "model_name": name,
+            "gpu_type": gpu_type,
+            "memory_mb": memory_mb,
+            "usage_percent": usage,
+            "compute_capabilities": {
+                "cuda": cuda,
+                "rocm": vendor == "AMD",
+                "directml": false,
+                "opencl": false,
+                "vulkan": true,
+                "metal": false,
+            },
+        }))
+        .unwrap()
+    };
+
+    let mut hardware = HardwareInfo::query().unwrap();
+    hardware.gpus = vec![
+        gpu("Intel", "Intel UHD Graphics 770", "Integrated", 32_768, None, Some(5.0)),
+        gpu("NVIDIA", "GeForce GTX 1080 Ti", "Discrete", 11_264, Some("6.1"), Some(0.0)),
+        gpu("AMD", "Radeon RX 7900 XTX", "Discrete", 24_576, None, Some(40.0)),
+        gpu("NVIDIA", "GeForce RTX 3060", "Discrete", 12_288, Some("8.6"), Some(90.0)),
+    ];
+
+    // Dedicated GPUs rank ahead of an integrated GPU with more shared memory
+    let selection = hardware.select_gpu(&GpuCriteria::most_vram()).unwrap();
+    assert_eq!(selection.index, 2);
+    assert_eq!(selection.gpu.model_name(), "Radeon RX 7900 XTX");
+
+    let criteria = GpuCriteria::most_vram().prefer_vendor(GPUVendor::NVIDIA);
+    let order: Vec<usize> = hardware.rank_gpus(&criteria).iter().map(|s| s.index).collect();
+    assert_eq!(order, vec![3, 1, 2, 0]);
+
+    let criteria = GpuCriteria::most_vram().prefer_vendor(GPUVendor::NVIDIA).require_cuda("7.0");
+    assert_eq!(hardware.select_gpu(&criteria).unwrap().index, 3);
+    assert_eq!(hardware.rank_gpus(&criteria).len(), 1);
+
+    assert_eq!(hardware.select_gpu(&GpuCriteria::least_loaded()).unwrap().index, 1);
+    assert_eq!(hardware.select_gpu(&GpuCriteria::least_loaded().require_memory_gb(16.0)).unwrap().index, 2);
+    assert!(hardware.select_gpu(&GpuCriteria::most_vram().require_cuda("9.0")).is_none());
+
+    let criteria = GpuCriteria::most_vram().require_vendor(GPUVendor::Intel).dedicated_only();
+    assert!(hardware.select_gpu(&criteria).is_none());
+}
+
+#[test]
+fn test_llama_cpp_report() {
+    use hardware_query::{GPUInfo, GgmlBackend, HardwarePresets, ModelFitPlanner, Quantization};
+
+    let gpu: GPUInfo = serde_json::from_value(serde_json::json!({
+        "vendor": "NVIDIA",
+        "model_name": "GeForce RTX 3060",
+        "gpu_type": "Discrete",
+        "memory_mb": 12_288,
+        "compute_capabilities": {
+            "cuda": "8.6",
+            "rocm": false,
+            "directml": true,
+            "opencl": true,
+            "vulkan": true,
+            "metal": false,
+        },
+    }))
+    .unwrap();
+
+    let mut hardware = HardwareInfo::query().unwrap();
+    hardware.gpus.clear();
+    let report = HardwarePresets::llama_cpp_report_for(&hardware);
+    println!("llama.cpp report (CPU only): {report:#?}");
+    assert_eq!(report.best_backend().backend, GgmlBackend::CPU);
+    assert!(report.threads >= 1);
+    assert!(report.reference_offload.iter().all(|offload| offload.gpu_layers == 0));
+
+    hardware.gpus = vec![gpu];
+    let report = HardwarePresets::llama_cpp_report_for(&hardware);
+    assert!(report.supports(GgmlBackend::Vulkan));
+    assert!(report.supports(GgmlBackend::CPU));
+    assert!(!report.supports(GgmlBackend::HIP));
+    if cfg!(target_os = "macos") {
+        return;
+    }
+    assert_eq!(report.best_backend().backend, GgmlBackend::CUDA);
+    assert_eq!(report.best_backend().gpu_indices, vec![0]);
+
+    // An 8B Q4 model fits in 12 GB, output layer included
+    let offload = report.gpu_layers(&ModelFitPlanner::from_billions(8.0).quantization(Quantization::Q4).layers(32));
+    assert_eq!(offload.model, "8B Q4");
+    assert!(offload.full_offload);
+    assert_eq!(offload.gpu_layers, 33);
+    assert_eq!(offload.ngl_arg(), "-ngl 33");
+
+    // A 70B Q4 model only partially fits
+    let offload = &report.reference_offload[3];
+    assert_eq!(offload.model, "70B Q4");
+    assert!(!offload.full_offload);
+    assert!(offload.gpu_layers > 0 && offload.gpu_layers < offload.total_layers);
+    assert_eq!(offload.backend, GgmlBackend::CUDA);
+}
+
+#[test]
+fn test_onnx_execution_providers() {
+    use hardware_query::{AIRuntime, GPUInfo, HardwarePresets, OnnxExecutionProvider, RuntimeVersion, SoftwareStack};
+
+    let gpu = |name: &str, memory_mb: u64, cuda: &str| -> GPUInfo {
+        serde_json::from_value(serde_json::json!({
+            "vendor": "NVIDIA",
+            "model_name": name,
+            "gpu_type": "Discrete",
+            "memory_mb": memory_mb,
+            "compute_capabilities": {
+                "cuda": cuda,
+                "rocm": false,
+                "directml": false,
+                "opencl": false,
+                "vulkan": true,
+                "metal": false,
+            },
+        }))
+        .unwrap()
+    };
+    let runtime = |runtime: AIRuntime, version: &str| RuntimeVersion {
+        runtime,
+        version: Some(version.to_string()),
+        path: None,
+    };
+
+    let mut hardware = HardwareInfo::query().unwrap();
+    hardware.gpus = vec![gpu("GeForce GTX 1080 Ti", 11_264, "6.1"), gpu("GeForce RTX 3060", 12_288, "8.6")];
+    hardware.npus.clear();
+    let software = SoftwareStack {
+        runtimes: vec![runtime(AIRuntime::CUDA, "12.4"), runtime(AIRuntime::CuDNN, "9.1.0")],
+    };
+
+    let providers = HardwarePresets::onnx_execution_providers_for(&hardware, &software);
+    println!("ONNX execution providers: {providers:#?}");
+
+    // CUDA is usable and leads; TensorRT is possible but missing its runtime
+    assert_eq!(providers[0].provider, OnnxExecutionProvider::CUDA);
+    assert_eq!(providers[0].gpu_index, Some(1));
+    assert!(providers[0].runtime_ready);
+    assert!(providers[0].reason.contains("12.4"));
+
+    let tensorrt = providers.iter().find(|p| p.provider == OnnxExecutionProvider::TensorRT).unwrap();
+    assert_eq!(tensorrt.gpu_index, Some(1));
+    assert!(!tensorrt.runtime_ready);
+    assert!(tensorrt.reason.contains("install TensorRT"));
+
+    let cpu = providers.iter().find(|p| p.provider == OnnxExecutionProvider::CPU).unwrap();
+    assert!(cpu.runtime_ready);
+    assert_eq!(cpu.provider.ort_name(), "CPUExecutionProvider");
+    assert!(providers.windows(2).all(|pair| pair[0].runtime_ready || !pair[1].runtime_ready));
+
+    // With TensorRT installed it moves ahead of CUDA
+    let mut software = software;
+    software.runtimes.push(runtime(AIRuntime::TensorRT, "10.0.1"));
+    let providers = HardwarePresets::onnx_execution_providers_for(&hardware, &software);
+    assert_eq!(providers[0].provider, OnnxExecutionProvider::TensorRT);
+    assert_eq!(providers[1].provider, OnnxExecutionProvider::CUDA);
+
+    // Pascal is too old for TensorRT but still runs CUDA
+    hardware.gpus.truncate(1);
+    let providers = HardwarePresets::onnx_execution_providers_for(&hardware, &software);
+    assert!(providers.iter().all(|p| p.provider != OnnxExecutionProvider::TensorRT));
+    assert_eq!(providers[0].gpu_index, Some(0));
+}
+
+#[test]
+fn test_requirements_compliance() {
+    use hardware_query::{GPUInfo, GpuRequirement, HardwareRequirement, Requirements};
+
+    let requirements = Requirements::new()
+        .with_physical_cores(1)
+        .with_memory_gb(0.5)
+        .with_gpu(GpuRequirement::new().with_memory_gb(8.0).with_cuda_capability("7.5"))
+        .with_cpu_feature("definitely-not-a-feature")
+        .with_free_disk_gb(1_000_000.0);
+
+    // The spec round-trips through JSON
+    let json = serde_json::to_string(&requirements).unwrap();
+    assert_eq!(serde_json::from_str::<Requirements>(&json).unwrap(), requirements);
+    let parsed: Requirements = serde_json::from_value(serde_json::json!({
+        "requirements": [{ "MemoryGb": 16.0 }, { "Gpu": { "min_memory_gb": 8.0 } }],
+    }))
+    .unwrap();
+    assert_eq!(parsed.requirements[0], HardwareRequirement::MemoryGb(16.0));
+    assert_eq!(parsed.requirements[1], HardwareRequirement::Gpu(GpuRequirement::new().with_memory_gb(8.0)));
+
+    let gpu = |name: &str, memory_mb: u64, cuda: Option<&str>| -> GPUInfo {
+        serde_json::from_value(serde_json::json!({
+            "vendor": "NVIDIA",
+            "model_name": name,
+            "gpu_type": "Discrete",
+            "memory_mb": memory_mb,
+            "compute_capabilities": {
+                "cuda": cuda,
+                "rocm": false,
+                "directml": false,
+                "opencl": false,
+                "vulkan": false,
+                "metal": false,
+            },
+        }))
+        .unwrap()
+    };
+
+    let mut hardware = HardwareInfo::query().unwrap();
+    hardware.gpus = vec![gpu("GeForce GTX 1080", 8192, Some("6.1"))];
+    let report = requirements.check(&hardware);
+    assert_eq!(report.checks.len(), requirements.requirements.len());
+    assert!(report.checks[0].passed && report.checks[1].passed);
+    assert!(!report.checks[2].passed && report.checks[2].detail.contains("CUDA 6.1"));
+    assert!(!report.checks[3].passed && !report.checks[4].passed);
+    assert!(!report.is_compliant());
+    assert_eq!(report.failures().len(), 3);
+    assert!(report.to_string().contains("[FAIL] GPU (8 GB+, CUDA 7.5+)"));
+
+    hardware.gpus.push(gpu("GeForce RTX 3080", 10240, Some("8.6")));
+    assert!(requirements.check(&hardware).checks[2].passed);
+    hardware.gpus.clear();
+    assert_eq!(requirements.check(&hardware).checks[2].detail, "no GPU detected");
+
+    assert!(Requirements::new().check(&hardware).is_compliant());
+}
+
+#[test]
+fn test_custom_scoring_plugin() {
+    use hardware_query::{HardwarePresets, NamedScore, ScoringPlugin};
+
+    struct AudioDsp;
+
+    impl ScoringPlugin for AudioDsp {
+        fn name(&self) -> &str {
+            "Audio DSP"
+        }
+
+        fn scores(&self, hardware: &HardwareInfo) -> Vec<NamedScore> {
+            vec![
+                NamedScore::new("Cores", hardware.cpu().physical_cores() as f64 * 1000.0),
+                NamedScore::new("Latency", -5.0).with_detail("no RT kernel"),
+            ]
+        }
+
+        fn recommendations(&self, _hardware: &HardwareInfo, scores: &[NamedScore]) -> Vec<String> {
+            scores
+                .iter()
+                .filter(|score| score.score < 50)
+                .map(|score| format!("Improve {}", score.name))
+                .collect()
+        }
+    }
+
+    struct Empty;
+
+    impl ScoringPlugin for Empty {
+        fn name(&self) -> &str {
+            "Empty"
+        }
+
+        fn scores(&self, _hardware: &HardwareInfo) -> Vec<NamedScore> {
+            Vec::new()
+        }
+    }
+
+    let hardware = HardwareInfo::query().unwrap();
+    let assessment = HardwarePresets::custom_assessment_for(&AudioDsp, &hardware).unwrap();
+    assert_eq!(assessment.name, "Audio DSP");
+    // Scores are clamped to 0-100 and averaged by default
+    assert_eq!(assessment.score("Cores"), Some(100));
+    assert_eq!(assessment.score("Latency"), Some(0));
+    assert_eq!(assessment.scores[1].detail.as_deref(), Some("no RT kernel"));
+    assert_eq!(assessment.overall_score, 50);
+    assert_eq!(assessment.recommendations, vec!["Improve Latency".to_string()]);
+    assert_eq!(assessment.score("Missing"), None);
+
+    let empty = HardwarePresets::custom_assessment_for(&Empty, &hardware).unwrap();
+    assert_eq!(empty.overall_score, 0);
+    assert!(empty.recommendations.is_empty());
+}
+
+#[test]
+fn test_recommended_parallelism() {
+    use hardware_query::{ResourceLimits, WorkloadKind};
+
+    let hardware = HardwareInfo::query().unwrap();
+    let cpu = hardware.cpu();
+    let budget = std::thread::available_parallelism().unwrap().get() as u32;
+
+    let compute = cpu.recommended_parallelism(WorkloadKind::Compute);
+    assert_eq!(compute.kind, WorkloadKind::Compute);
+    assert!(compute.threads >= 1);
+    assert!(compute.threads <= budget.min(cpu.logical_cores().max(1)));
+    assert!(compute.performance_threads <= compute.threads);
+    assert!(compute.cache_domains >= 1);
+
+    let mixed = cpu.recommended_parallelism(WorkloadKind::Mixed);
+    assert!(mixed.threads >= compute.threads);
+    let io = cpu.recommended_parallelism(WorkloadKind::Io);
+    assert!(io.threads >= mixed.threads);
+    assert!(io.threads >= 4);
+
+    // A 1.5 CPU container quota caps CPU-bound pools at one thread
+    let mut limits: ResourceLimits = hardware.virtualization().resource_limits.clone();
+    limits.cpu_limits.quota_percent = Some(150.0);
+    let quota = cpu.recommended_parallelism_within(WorkloadKind::Compute, &limits);
+    assert_eq!(quota.threads, 1);
+    assert!(quota.cpu_budget <= 1.5);
+    let quota_io = cpu.recommended_parallelism_within(WorkloadKind::Io, &limits);
+    assert_eq!(quota_io.threads, 4);
+
+    // Per-node splits only appear on multi-node machines
+    assert!(compute.per_numa_node.len() != 1);
+}
+
+#[test]
+fn test_device_topology() {
+    let hardware = HardwareInfo::query().unwrap();
+    let topology = hardware.device_topology();
+    assert_eq!(topology.devices().len(), hardware.pci_devices().len());
+
+    for device in topology.devices() {
+        assert_eq!(topology.device(&device.bus_location).map(|found| &found.bus_location), Some(&device.bus_location));
+        if let Some(root_port) = &device.root_port {
+            assert_eq!(device.upstream_bridges.first(), Some(root_port));
+            assert!(topology.devices_below(root_port).iter().any(|below| below.bus_location == device.bus_location));
+        }
+        if let Some(node) = device.numa_node {
+            assert!(topology.devices_on_node(node).iter().any(|on| on.bus_location == device.bus_location));
+        }
+        for interface in &device.interfaces {
+            assert!(topology.device_for_interface(interface).is_some());
+        }
+        assert!(device.remote_interrupts().len() <= device.interrupts.len());
+    }
+
+    let grouped: usize = topology.iommu_groups().iter().map(|(_, members)| members.len()).sum();
+    assert_eq!(grouped, topology.devices().iter().filter(|device| device.iommu_group.is_some()).count());
+    assert!(topology.network_devices().iter().all(|device| device.is_network));
+}
+
+#[test]
+fn test_iommu_groups() {
+    use hardware_query::{IOMMUGroup, PCIDevice};
+
+    fn device(bus_location: &str, class_code: u32, driver: Option<&str>, group: u32) -> PCIDevice {
+        let mut device: PCIDevice = serde_json::from_value(serde_json::json!({
+            "device_id": "10de:2684",
+            "vendor_name": "NVIDIA Corporation",
+            "device_name": "",
+            "bus_location": bus_location,
+            "device_class": "",
+            "subsystem_id": null,
+            "driver": driver,
+            "revision": null,
+            "irq": null,
+            "memory_regions": [],
+            "class_code": class_code,
+        }))
+        .unwrap();
+        device.iommu_group = Some(group);
+        device
+    }
+
+    let devices = vec![
+        device("0000:01:00.0", 0x030000, Some("vfio-pci"), 14),
+        device("0000:01:00.1", 0x040300, None, 14),
+        device("0000:00:01.0", 0x060400, Some("pcieport"), 14),
+        device("0000:02:00.0", 0x030000, Some("nvidia"), 15),
+        device("0000:03:00.0", 0x020000, Some("igb"), 15),
+    ];
+    let groups = IOMMUGroup::from_devices(&devices);
+    assert_eq!(groups.iter().map(IOMMUGroup::id).collect::<Vec<_>>(), vec![14, 15]);
+
+    // GPU + HDMI audio behind a root port, bound to vfio-pci
+    let isolated = &groups[0];
+    assert_eq!(isolated.devices()[0].bus_location, "0000:00:01.0");
+    assert_eq!(isolated.endpoints().len(), 2);
+    assert!(isolated.is_viable());
+    assert!(isolated.is_bound_to_vfio());
+    assert!(isolated.has_gpu());
+    assert!(isolated.contains("0000:01:00.1"));
+
+    // GPU sharing a group with a NIC
+    let shared = &groups[1];
+    assert!(!shared.is_viable());
+    assert_eq!(shared.blocking_devices()[0].bus_location, "0000:03:00.0");
+    assert!(!shared.is_bound_to_vfio());
+
+    let hardware = HardwareInfo::query().unwrap();
+    for group in hardware.iommu_groups() {
+        assert!(group.devices().iter().all(|device| device.iommu_group() == Some(group.id())));
+    }
+}
+
+#[test]
+fn test_hwmon_sensor_parsing() {
+    use hardware_query::ThermalInfo;
+
+    let dir = tempfile::tempdir().unwrap();
+    let write = |path: std::path::PathBuf, contents: &str| {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, format!("{contents}\n")).unwrap();
+    };
+
+    let coretemp = dir.path().join("hwmon2");
+    write(coretemp.join("name"), "coretemp");
+    write(coretemp.join("temp1_input"), "47000");
+    write(coretemp.join("temp1_label"), "Package id 0");
+    write(coretemp.join("temp1_crit"), "100000");
+    write(coretemp.join("temp1_max"), "80000");
+    write(coretemp.join("temp2_input"), "45500");
+    // A channel without an input reading is skipped
+    write(coretemp.join("temp3_label"), "Core 4");
+
+    let sensors = ThermalInfo::sensors_from_hwmon(&coretemp);
+    assert_eq!(sensors.len(), 2);
+    assert_eq!(sensors[0].name, "coretemp Package id 0");
+    assert_eq!(sensors[0].temperature, 47.0);
+    assert_eq!(sensors[0].critical_temperature, Some(100.0));
+    assert_eq!(sensors[0].max_temperature, Some(80.0));
+    assert_eq!(sensors[0].sensor_type, "CPU");
+    assert_eq!(sensors[1].name, "coretemp temp2");
+    assert_eq!(sensors[1].temperature, 45.5);
+    assert_eq!(sensors[1].critical_temperature, None);
+
+    let nvme = dir.path().join("hwmon1");
+    write(nvme.join("name"), "nvme");
+    write(nvme.join("temp1_input"), "38850");
+    write(nvme.join("temp1_label"), "Composite");
+    assert_eq!(ThermalInfo::sensors_from_hwmon(&nvme)[0].sensor_type, "Storage");
+
+    // Super I/O fans: one with a maximum, one with only a PWM duty cycle
+    let nct = dir.path().join("hwmon3");
+    write(nct.join("name"), "nct6798");
+    write(nct.join("fan1_input"), "600");
+    write(nct.join("fan1_max"), "2400");
+    write(nct.join("fan2_input"), "1200");
+    write(nct.join("fan2_label"), "CPU_FAN");
+    write(nct.join("pwm2"), "51");
+    write(nct.join("pwm2_enable"), "2");
+
+    let fans = ThermalInfo::fans_from_hwmon(&nct);
+    assert_eq!(fans.len(), 2);
+    assert_eq!(fans[0].name, "nct6798 fan1");
+    assert_eq!(fans[0].speed_rpm, 600);
+    assert_eq!(fans[0].max_speed_rpm, Some(2400));
+    assert_eq!(fans[0].speed_percent, Some(25.0));
+    assert!(!fans[0].controllable);
+    assert_eq!(fans[1].name, "nct6798 CPU_FAN");
+    assert_eq!(fans[1].speed_percent, Some(20.0));
+    assert!(fans[1].controllable);
+    assert!(ThermalInfo::sensors_from_hwmon(&nct).is_empty());
+
+    // Directories without a chip name are not hwmon devices
+    assert!(ThermalInfo::sensors_from_hwmon(dir.path()).is_empty());
+
+    let zone = dir.path().join("thermal_zone0");
+    write(zone.join("type"), "x86_pkg_temp");
+    write(zone.join("temp"), "52000");
+    let sensor = ThermalInfo::sensor_from_thermal_zone(&zone).unwrap();
+    assert_eq!(sensor.name, "x86_pkg_temp");
+    assert_eq!(sensor.temperature, 52.0);
+    assert_eq!(sensor.sensor_type, "CPU");
+
+    let soc = dir.path().join("thermal_zone1");
+    write(soc.join("type"), "gpu-thermal");
+    write(soc.join("temp"), "-5000");
+    let sensor = ThermalInfo::sensor_from_thermal_zone(&soc).unwrap();
+    assert_eq!(sensor.temperature, -5.0);
+    assert_eq!(sensor.sensor_type, "GPU");
+
+    assert!(ThermalInfo::sensor_from_thermal_zone(&dir.path().join("thermal_zone9")).is_none());
+}
+
+#[test]
+fn test_sensor_component_mapping() {
+    use hardware_query::{
+        FanInfo, MappingConfidence, SensorComponent, ThermalInfo, ThermalSensor,
+    };
+
+    fn sensor(name: &str, sensor_type: &str, temperature: f32) -> ThermalSensor {
+        ThermalSensor {
+            name: name.to_string(),
+            temperature,
+            critical_temperature: None,
+            max_temperature: None,
+            sensor_type: sensor_type.to_string(),
+            temperature_history: Vec::new(),
+        }
+    }
+    fn fan(name: &str) -> FanInfo {
+        FanInfo {
+            name: name.to_string(),
+            speed_rpm: 1200,
+            max_speed_rpm: None,
+            speed_percent: None,
+            controllable: false,
+            fan_curve: None,
+        }
+    }
+
+    let thermal = ThermalInfo {
+        sensors: vec![
+            sensor("acpitz temp1", "System", 30.0),
+            sensor("nct6798 SYSTIN", "System", 35.0),
+            sensor("nct6798 CPUTIN", "System", 48.0),
+            sensor("coretemp Core 3", "CPU", 61.0),
+            sensor("coretemp Package id 0", "CPU", 64.0),
+            sensor("amdgpu edge", "GPU", 55.0),
+            sensor("amdgpu edge", "GPU", 71.0),
+            sensor("nvme Composite", "Storage", 40.0),
+            sensor("nvme Composite", "Storage", 44.0),
+        ],
+        fans: vec![fan("nct6798 CPU_FAN"), fan("nct6798 SYS_FAN1"), fan("amdgpu fan1"), fan("amdgpu fan1")],
+        ..HardwareInfo::query().unwrap().thermal().clone()
+    };
+
+    let mappings = thermal.sensor_mappings();
+    assert_eq!(mappings[0].1.component, SensorComponent::Chassis);
+    assert_eq!(mappings[0].1.confidence, MappingConfidence::Low);
+    assert_eq!(mappings[1].1.component, SensorComponent::Chassis);
+    assert_eq!(mappings[3].1.component, SensorComponent::CpuCore(3));
+    assert_eq!(mappings[6].1.component, SensorComponent::Gpu(1));
+    assert_eq!(mappings[8].1.component, SensorComponent::Storage(1));
+
+    // The driver's package sensor beats the Super I/O CPUTIN reading
+    assert_eq!(thermal.cpu_temperature(), Some(64.0));
+    assert_eq!(thermal.sensors_for(SensorComponent::CpuPackage).len(), 2);
+    assert_eq!(thermal.gpu_temperature(), Some(55.0));
+    assert_eq!(thermal.component_temperature(SensorComponent::Gpu(1)), Some(71.0));
+    assert_eq!(thermal.component_temperature(SensorComponent::Vrm), None);
+    assert_eq!(SensorComponent::Gpu(1).to_string(), "GPU 1");
+
+    assert_eq!(thermal.fans_for(SensorComponent::CpuPackage)[0].name, "nct6798 CPU_FAN");
+    assert_eq!(thermal.fans_for(SensorComponent::Chassis)[0].name, "nct6798 SYS_FAN1");
+    assert_eq!(thermal.fan_mappings()[3].1.component, SensorComponent::Gpu(1));
+
+    // Without a package sensor the hottest core stands in
+    let cores_only = ThermalInfo {
+        sensors: vec![sensor("coretemp Core 0", "CPU", 50.0), sensor("coretemp Core 1", "CPU", 58.0)],
+        fans: Vec::new(),
+        ..thermal
+    };
+    assert_eq!(cores_only.cpu_temperature(), Some(58.0));
+}
+
+#[test]
+fn test_throttling_prediction_model() {
+    use hardware_query::{FanInfo, HistoryBuffer, ThermalInfo, ThermalSensor, TemperatureReading, ThrottlingSeverity};
+    use std::time::{Duration, SystemTime};
+
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    // CPU package heating 1 °C every 10 s towards its 95 °C limit
+    let history: Vec<TemperatureReading> = (0..6)
+        .map(|step| TemperatureReading {
+            temperature: 70.0 + step as f32,
+            timestamp: start + Duration::from_secs(10 * step),
+        })
+        .collect();
+    let sensor = |name: &str, sensor_type: &str, temperature: f32, history: Vec<TemperatureReading>| ThermalSensor {
+        name: name.to_string(),
+        temperature,
+        critical_temperature: Some(95.0),
+        max_temperature: None,
+        sensor_type: sensor_type.to_string(),
+        temperature_history: history,
+    };
+    let fan = |name: &str, percent: f32| FanInfo {
+        name: name.to_string(),
+        speed_rpm: 1500,
+        max_speed_rpm: None,
+        speed_percent: Some(percent),
+        controllable: true,
+        fan_curve: None,
+    };
+
+    let thermal = ThermalInfo {
+        sensors: vec![
+            sensor("coretemp Package id 0", "CPU", 75.0, history),
+            sensor("nvme Composite", "Storage", 40.0, Vec::new()),
+        ],
+        fans: vec![fan("nct6798 CPU_FAN", 100.0)],
+        ..HardwareInfo::query().unwrap().thermal().clone()
+    };
+
+    let prediction = thermal.predict_thermal_throttling(1.0);
+    assert_eq!(prediction.limiting_sensor.as_deref(), Some("coretemp Package id 0"));
+    assert_eq!(prediction.throttle_temperature, Some(95.0));
+    assert!((prediction.trend_per_minute - 6.0).abs() < 0.01);
+    assert_eq!(prediction.fan_headroom, Some(0.0));
+    assert!(prediction.will_throttle);
+    assert!(prediction.severity == ThrottlingSeverity::Severe);
+    let time = prediction.time_to_throttle.unwrap();
+    // 20 °C at 6 °C/min takes at least 200 s; flattening makes it slower
+    assert!(time > Duration::from_secs(200) && time < Duration::from_secs(600));
+    let curve = &prediction.predicted_curve;
+    assert_eq!(curve.first().unwrap().temperature, 75.0);
+    assert!(curve.windows(2).all(|pair| pair[1].temperature >= pair[0].temperature));
+    assert!(prediction.confidence >= 0.8);
+
+    // Fans with capacity left flatten the curve; a lighter workload avoids throttling
+    let cooled = ThermalInfo { fans: vec![fan("nct6798 CPU_FAN", 30.0)], ..thermal.clone() };
+    let cooled_prediction = cooled.predict_thermal_throttling(1.0);
+    assert!(cooled_prediction.predicted_curve.last().unwrap().temperature < curve.last().unwrap().temperature);
+    assert!(!thermal.predict_thermal_throttling(0.2).will_throttle);
+
+    // An empty monitor history falls back to each sensor's own readings
+    let empty_history = HistoryBuffer::new(10);
+    let fallback = thermal.predict_thermal_throttling_from_history(&empty_history, Duration::from_secs(600), 1.0);
+    assert_eq!(fallback.time_to_throttle, prediction.time_to_throttle);
+
+    // Without a trend the temperature is assumed to hold
+    let mut cold = thermal.clone();
+    cold.sensors[0].temperature_history.clear();
+    let steady = cold.predict_thermal_throttling(1.0);
+    assert!(!steady.will_throttle);
+    assert_eq!(steady.trend_per_minute, 0.0);
+}
+
+#[test]
+fn test_edge_tpu_validation() {
+    use hardware_query::{EdgeTpuValidation, TPUConnectionType, TPUInfo};
+    use std::path::PathBuf;
+
+    // M.2 module with nothing installed
+    let missing = EdgeTpuValidation {
+        connection_type: TPUConnectionType::M2,
+        device_nodes: Vec::new(),
+        device_accessible: None,
+        library_path: None,
+        gasket_loaded: false,
+        apex_loaded: false,
+        driver_version: None,
+        firmware_loaded: None,
+    };
+    assert!(!missing.is_ready());
+    let steps = missing.remediation();
+    assert!(steps[0].contains("runtime"));
+    if cfg!(target_os = "linux") {
+        assert!(steps.iter().any(|step| step.contains("gasket-dkms")));
+    }
+
+    // Driver loaded but the device node belongs to root
+    let locked = EdgeTpuValidation {
+        device_nodes: vec!["/dev/apex_0".to_string()],
+        device_accessible: Some(false),
+        library_path: Some(PathBuf::from("/usr/lib/x86_64-linux-gnu/libedgetpu.so.1")),
+        gasket_loaded: true,
+        apex_loaded: true,
+        driver_version: Some("1.2".to_string()),
+        ..missing.clone()
+    };
+    let steps = locked.remediation();
+    assert_eq!(steps.len(), 1);
+    assert!(steps[0].contains("/dev/apex_0") && steps[0].contains("usermod"));
+
+    let ready = EdgeTpuValidation { device_accessible: Some(true), ..locked };
+    assert!(ready.is_ready());
+
+    // USB accelerators need no kernel driver
+    let usb = EdgeTpuValidation {
+        connection_type: TPUConnectionType::USB,
+        firmware_loaded: Some(false),
+        ..ready
+    };
+    assert!(usb.is_ready());
+
+    for tpu in TPUInfo::query_all().unwrap() {
+        if let Some(ready) = tpu.capabilities.get("runtime_ready") {
+            assert_eq!(ready == "false", tpu.capabilities.contains_key("remediation"));
+        }
+    }
+}
@@ -41,6 +41,11 @@ fn test_system_overview_quick() {
 
     // Validate performance score
     assert!(overview.performance_score <= 100, "Performance score should be <= 100");
+
+    // Container limits can only shrink what the host reports
+    assert!(overview.effective_cores() >= 1);
+    assert!(overview.effective_cores() <= overview.cpu.threads);
+    assert!(overview.effective_memory_gb() <= overview.memory_gb);
 }
 
 #[test]
@@ -180,6 +185,51 @@ fn test_hardware_query_builder_presets() {
     assert!(perf_info.memory.is_some());
 }
 
+#[test]
+fn test_hardware_query_builder_selection() {
+    // Selected paths pull in their components without with_cpu()/with_storage()
+    let custom_info = HardwareQueryBuilder::new()
+        .select(&["cpu.model_name", "cpu.logical_cores", "storage_devices.*.capacity_gb"])
+        .filter_storage(|device| device.capacity_gb > 0.0)
+        .query()
+        .expect("Failed to build selective hardware query");
+    assert!(custom_info.cpu.is_some());
+    assert!(custom_info.storage_devices.iter().all(|device| device.capacity_gb > 0.0));
+
+    let value = custom_info.to_value().expect("Failed to project selected fields");
+    let object = value.as_object().unwrap();
+    assert_eq!(object.len(), 2);
+    let cpu = object["cpu"].as_object().unwrap();
+    assert_eq!(cpu.len(), 2);
+    assert!(cpu["model_name"].is_string());
+    let devices = object["storage_devices"].as_array().unwrap();
+    assert_eq!(devices.len(), custom_info.storage_devices.len());
+    for device in devices {
+        assert_eq!(device.as_object().unwrap().keys().collect::<Vec<_>>(), ["capacity_gb"]);
+    }
+    assert!(custom_info.to_json().unwrap().len() < serde_json::to_string(&custom_info).unwrap().len());
+
+    assert!(HardwareQueryBuilder::new().select(&["gpu_count"]).query().is_err());
+    // The power profile only exists with the monitoring feature
+    let power = HardwareQueryBuilder::new().select(&["power.total_power_draw"]).query();
+    assert_eq!(power.is_ok(), cfg!(feature = "monitoring"));
+    let gpus = HardwareQueryBuilder::new()
+        .select(&["gpu.*.memory_mb"])
+        .filter_gpus(|_| false)
+        .query()
+        .expect("Failed to build filtered GPU query");
+    assert!(gpus.has_component("GPU"));
+    assert!(gpus.gpus.is_empty());
+    assert_eq!(gpus.to_value().unwrap(), serde_json::json!({ "gpus": [] }));
+
+    // Out-of-range indices select nothing instead of padding the array
+    let out_of_range = HardwareQueryBuilder::new()
+        .select(&["storage_devices.18446744073709551615", "storage_devices.100000000.capacity_gb"])
+        .query()
+        .expect("Failed to build out-of-range query");
+    assert_eq!(out_of_range.to_value().unwrap(), serde_json::json!({}));
+}
+
 #[test]
 fn test_system_overview_serialization() {
     let overview = SystemOverview::quick().expect("Failed to get system overview");
@@ -246,6 +296,142 @@ fn test_ai_assessment_comprehensive() {
     
     // Model recommendations should be reasonable
     assert!(!ai_assessment.model_recommendations.small_models.is_empty());
+
+    // Every detected runtime is reported once
+    let runtimes = ai_assessment.runtime_versions.runtimes();
+    for runtime in runtimes {
+        assert_eq!(runtimes.iter().filter(|r| r.runtime == runtime.runtime).count(), 1);
+    }
+}
+
+#[test]
+fn test_container_limits() {
+    use hardware_query::HardwareInfo;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+    let mut hardware = HardwareInfo::from_json(include_str!("../src/sim/dgx_h100.json")).unwrap();
+    let host = SystemOverview::from_hardware_info(hardware.clone()).unwrap();
+    assert_eq!(host.effective_cores(), host.cpu.threads);
+    assert_eq!(host.effective_memory_gb(), host.memory_gb);
+
+    // `docker run --cpus=1.5 --memory=2g`
+    let limits = &mut hardware.virtualization.resource_limits;
+    limits.cpu_limits.quota_percent = Some(150.0);
+    limits.memory_limits.max_memory_bytes = Some(2 * GIB);
+    let container = SystemOverview::from_hardware_info(hardware.clone()).unwrap();
+    assert_eq!(container.cpu_limit, Some(1.5));
+    assert_eq!(container.memory_limit_gb, Some(2.0));
+    assert_eq!(container.effective_cores(), 2);
+    assert_eq!(container.effective_memory_gb(), 2.0);
+
+    // A memory limit above physical memory doesn't raise it
+    hardware.virtualization.resource_limits.memory_limits.max_memory_bytes = Some(u64::MAX / 2);
+    let loose = SystemOverview::from_hardware_info(hardware).unwrap();
+    assert_eq!(loose.effective_memory_gb(), loose.memory_gb);
+
+    #[cfg(target_os = "linux")]
+    {
+        use hardware_query::ResourceLimits;
+
+        let dir = tempfile::tempdir().unwrap();
+        let write = |path: std::path::PathBuf, contents: &str| {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, format!("{contents}\n")).unwrap();
+        };
+
+        // cgroup v2: limits are inherited, the tightest one wins
+        let v2 = dir.path().join("v2");
+        let pod = v2.join("kubepods/pod1");
+        write(v2.join("cgroup.controllers"), "cpuset cpu io memory pids");
+        write(pod.join("memory.max"), &GIB.to_string());
+        write(pod.join("cpu.max"), "max 100000");
+        write(pod.join("ctr/cpu.max"), "150000 100000");
+        write(pod.join("ctr/memory.max"), &(2 * GIB).to_string());
+        write(pod.join("ctr/memory.swap.max"), "0");
+        write(pod.join("ctr/cpuset.cpus.effective"), "0-3");
+        write(pod.join("ctr/cpu.weight"), "79");
+        let limits = ResourceLimits::from_cgroup(&v2, "0::/kubepods/pod1/ctr\n");
+        assert_eq!(limits.cpu_limits.quota_percent, Some(150.0));
+        assert_eq!(limits.cpu_limits.max_cores, Some(2));
+        assert_eq!(limits.cpu_limits.affinity_mask.as_deref(), Some("0-3"));
+        assert_eq!(limits.cpu_limits.shares, Some(79));
+        assert_eq!(limits.cpu_limit_cores(), Some(1.5));
+        assert_eq!(limits.memory_limit_bytes(), Some(GIB));
+        assert_eq!(limits.memory_limits.max_swap_bytes, Some(0));
+
+        // cgroup v1: one hierarchy per controller, "unlimited" is a huge value
+        let v1 = dir.path().join("v1");
+        write(v1.join("cpu,cpuacct/docker/abc/cpu.cfs_quota_us"), "50000");
+        write(v1.join("cpu,cpuacct/docker/abc/cpu.cfs_period_us"), "100000");
+        write(v1.join("cpu,cpuacct/docker/abc/cpu.shares"), "512");
+        write(v1.join("cpuset/docker/abc/cpuset.cpus"), "2,4-5");
+        write(v1.join("memory/docker/abc/memory.limit_in_bytes"), "536870912");
+        write(v1.join("memory/docker/abc/memory.memsw.limit_in_bytes"), "1073741824");
+        write(v1.join("memory/docker/abc/memory.soft_limit_in_bytes"), "9223372036854771712");
+        write(v1.join("memory/docker/abc/memory.oom_control"), "oom_kill_disable 1\nunder_oom 0");
+        let membership = "12:memory:/docker/abc\n4:cpu,cpuacct:/docker/abc\n3:cpuset:/docker/abc\n";
+        let limits = ResourceLimits::from_cgroup(&v1, membership);
+        assert_eq!(limits.cpu_limits.quota_percent, Some(50.0));
+        assert_eq!(limits.cpu_limits.max_cores, Some(1));
+        assert_eq!(limits.cpu_limits.shares, Some(512));
+        assert_eq!(limits.cpu_limit_cores(), Some(0.5));
+        assert_eq!(limits.memory_limit_bytes(), Some(512 * 1024 * 1024));
+        assert_eq!(limits.memory_limits.max_swap_bytes, Some(512 * 1024 * 1024));
+        assert_eq!(limits.memory_limits.reservation_bytes, None);
+        assert!(limits.memory_limits.oom_kill_disabled);
+
+        // No cgroup files at all: unrestricted
+        let limits = ResourceLimits::from_cgroup(&dir.path().join("none"), "0::/\n");
+        assert_eq!(limits.cpu_limit_cores(), None);
+        assert_eq!(limits.memory_limit_bytes(), None);
+    }
+}
+
+#[test]
+fn test_ai_assessment_runtime_versions() {
+    use hardware_query::{AIRuntime, HardwareInfo, RuntimeVersion, SoftwareStack};
+
+    // 8x H100 snapshot, so the assessment doesn't depend on the test machine
+    let hardware = HardwareInfo::from_json(include_str!("../src/sim/dgx_h100.json")).unwrap();
+    let overview = SystemOverview::from_hardware_info(hardware).unwrap();
+    assert_eq!(overview.gpu.as_ref().unwrap().vendor, "NVIDIA");
+
+    let bare = HardwarePresets::ai_assessment_from(overview.clone(), SoftwareStack::default());
+    assert!(bare.runtime_versions.runtimes().is_empty());
+    let pytorch = bare.frameworks.iter().find(|f| f.name == "PyTorch").unwrap();
+    assert!(!pytorch.requirements_met, "CUDA is not installed");
+    assert!(bare.optimizations.contains(&"Install CUDA to enable GPU acceleration".to_string()));
+    assert!(bare.optimizations.contains(&"Install cuDNN to enable GPU acceleration".to_string()));
+
+    let runtime = |runtime: AIRuntime, version: &str, path: &str| RuntimeVersion {
+        runtime,
+        version: Some(version.to_string()),
+        path: Some(path.into()),
+    };
+    let installed = SoftwareStack {
+        runtimes: vec![
+            runtime(AIRuntime::CUDA, "12.4", "/usr/local/cuda-12.4"),
+            runtime(AIRuntime::CuDNN, "9.1.0", "/usr/lib/x86_64-linux-gnu"),
+            runtime(AIRuntime::OpenVINO, "2024.3.0", "/opt/intel/openvino_2024"),
+        ],
+    };
+    let assessment = HardwarePresets::ai_assessment_from(overview, installed.clone());
+    assert_eq!(assessment.runtime_versions, installed);
+    assert_eq!(assessment.runtime_versions.version(AIRuntime::CUDA), Some("12.4"));
+    assert!(!assessment.runtime_versions.has(AIRuntime::ROCm));
+
+    let pytorch = assessment.frameworks.iter().find(|f| f.name == "PyTorch").unwrap();
+    assert!(pytorch.requirements_met, "{:?}", pytorch.unmet_requirements);
+    assert!(!assessment.optimizations.iter().any(|o| o.starts_with("Install ")));
+    let openvino = assessment.frameworks.iter().find(|f| f.name == "OpenVINO").unwrap();
+    assert!(openvino.requirements_met);
+    assert!(openvino.notes.contains("2024.3.0"));
+
+    // Assessments saved before runtime detection still load
+    let mut json = serde_json::to_value(&assessment).unwrap();
+    json.as_object_mut().unwrap().remove("runtime_versions");
+    let loaded: hardware_query::AIHardwareAssessment = serde_json::from_value(json).unwrap();
+    assert!(loaded.runtime_versions.runtimes().is_empty());
 }
 
 #[test]
@@ -280,3 +466,35 @@ fn test_server_assessment_comprehensive() {
     assert!(server_assessment.server_score <= 100, "Server score should be <= 100");
     assert!(!server_assessment.workload_suitability.is_empty(), "Should list suitable workloads");
 }
+
+#[test]
+fn test_hardware_query_builder_component_selection() {
+    use hardware_query::{Component, HardwareComponent};
+
+    let custom_info = HardwareQueryBuilder::new()
+        .with_server_focused()
+        .skip(Component::Storage)
+        .skip(Component::Pci)
+        .with(Component::Npu)
+        .without_external_commands()
+        .without_wmi()
+        .query()
+        .expect("Failed to run restricted query");
+
+    assert!(custom_info.has_component("CPU"));
+    assert!(custom_info.has_component("NPU"));
+    assert!(!custom_info.has_component("Storage"));
+    assert!(!custom_info.has_component("PCI"));
+    assert!(custom_info.storage_devices.is_empty());
+    assert!(custom_info.pci_devices.is_empty());
+    // Skipped components are never probed, so they cannot fail
+    assert!(custom_info
+        .errors
+        .iter()
+        .all(|e| e.component != HardwareComponent::Storage && e.component != HardwareComponent::Devices));
+
+    // Builder subsystems report failures under the same groups as HardwareInfo
+    assert_eq!(HardwareComponent::from(Component::Tpu), HardwareComponent::Accelerators);
+    assert_eq!(HardwareComponent::from(Component::Usb), HardwareComponent::Devices);
+    assert_eq!(HardwareComponent::from(Component::Cpu), HardwareComponent::CPU);
+}